@@ -0,0 +1,174 @@
+//! Dataset annotations: free-text notes about the dataset and a description of each
+//! column, a lightweight data dictionary.
+//!
+//! Edited in the "Columns" side panel ("Notes & Descriptions"), shown as header tooltips,
+//! and included in the export manifests (see `ExportManifest`). They are kept in a
+//! sidecar file next to the data (`<file>.notes.json`), so they travel with it.
+
+use crate::PolarsViewResult;
+
+use egui::{ScrollArea, TextEdit, Ui};
+use polars::prelude::Schema;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+/// Suffix appended to the data file name to build the sidecar path
+/// (e.g., `sales.csv` -> `sales.csv.notes.json`).
+pub const ANNOTATIONS_SUFFIX: &str = ".notes.json";
+
+/// Notes about a dataset and descriptions of its columns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Annotations {
+    /// Free-text notes about the dataset (source, caveats, ...).
+    pub notes: String,
+    /// Description of each column, keyed by column name.
+    pub columns: BTreeMap<String, String>,
+}
+
+impl Annotations {
+    /// Returns the sidecar path of a data file: `<data_path>.notes.json`.
+    pub fn sidecar_path(data_path: impl AsRef<Path>) -> PathBuf {
+        let mut path = data_path.as_ref().as_os_str().to_owned();
+        path.push(ANNOTATIONS_SUFFIX);
+        PathBuf::from(path)
+    }
+
+    /// Reads the annotations of the data file `data_path` (none if it has no sidecar).
+    pub fn load(data_path: impl AsRef<Path>) -> PolarsViewResult<Self> {
+        let sidecar = Self::sidecar_path(data_path);
+        if !sidecar.is_file() {
+            return Ok(Annotations::default());
+        }
+        Ok(serde_json::from_str(&std::fs::read_to_string(sidecar)?)?)
+    }
+
+    /// Writes the annotations of the data file `data_path` to its sidecar, as
+    /// pretty-printed JSON. Empty annotations remove the sidecar instead.
+    pub fn save(&self, data_path: impl AsRef<Path>) -> PolarsViewResult<()> {
+        let sidecar = Self::sidecar_path(data_path);
+        if self.is_empty() {
+            if sidecar.is_file() {
+                std::fs::remove_file(&sidecar)?;
+            }
+            return Ok(());
+        }
+        std::fs::write(&sidecar, serde_json::to_string_pretty(self)?)?;
+        tracing::debug!("Annotations written to {}", sidecar.display());
+        Ok(())
+    }
+
+    /// Returns `true` if there are no notes and no column descriptions.
+    pub fn is_empty(&self) -> bool {
+        self.notes.trim().is_empty() && self.columns.values().all(|text| text.trim().is_empty())
+    }
+
+    /// The description of `column`, if it has one.
+    pub fn description(&self, column: &str) -> Option<&str> {
+        self.columns
+            .get(column)
+            .map(|text| text.trim())
+            .filter(|text| !text.is_empty())
+    }
+
+    /// The non-empty descriptions of the columns of `schema`, keyed by column name.
+    pub fn descriptions_of(&self, schema: &Schema) -> BTreeMap<String, String> {
+        schema
+            .iter_names()
+            .filter_map(|name| {
+                self.description(name)
+                    .map(|text| (name.to_string(), text.to_string()))
+            })
+            .collect()
+    }
+
+    /// Renders the editor: the notes and one description input per column of `schema`.
+    ///
+    /// Returns `true` if something was edited.
+    pub fn render(&mut self, ui: &mut Ui, schema: &Schema) -> bool {
+        let mut changed = false;
+
+        ui.label("Dataset notes:");
+        changed |= ui
+            .add(
+                TextEdit::multiline(&mut self.notes)
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY)
+                    .hint_text("Source, caveats, contacts..."),
+            )
+            .changed();
+
+        ui.label("Column descriptions (shown as header tooltips):");
+        ScrollArea::vertical()
+            .id_salt("column_descriptions")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (name, dtype) in schema.iter() {
+                    ui.label(name.as_str()).on_hover_text(dtype.to_string());
+                    let text = self.columns.entry(name.to_string()).or_default();
+                    changed |= ui
+                        .add(TextEdit::singleline(text).desired_width(f32::INFINITY))
+                        .changed();
+                }
+            });
+
+        // Columns without a description are not kept.
+        self.columns.retain(|_name, text| !text.is_empty());
+        changed
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_annotations`
+#[cfg(test)]
+mod tests_annotations {
+    use super::*;
+    use polars::prelude::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn sidecar_round_trip() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let data_path = dir.path().join("sales.csv");
+        assert_eq!(
+            Annotations::sidecar_path(&data_path),
+            dir.path().join("sales.csv.notes.json")
+        );
+        assert_eq!(Annotations::load(&data_path)?, Annotations::default());
+
+        let mut annotations = Annotations {
+            notes: "Monthly ERP extract.".to_string(),
+            ..Default::default()
+        };
+        annotations
+            .columns
+            .insert("price".to_string(), "Unit price, with taxes".to_string());
+        annotations.save(&data_path)?;
+        assert_eq!(Annotations::load(&data_path)?, annotations);
+
+        let schema = Schema::from_iter([
+            Field::new("id".into(), DataType::Int64),
+            Field::new("price".into(), DataType::Float64),
+        ]);
+        assert_eq!(annotations.description("id"), None);
+        assert_eq!(
+            annotations
+                .descriptions_of(&schema)
+                .keys()
+                .collect::<Vec<_>>(),
+            ["price"]
+        );
+
+        // Empty annotations remove the sidecar.
+        Annotations::default().save(&data_path)?;
+        assert!(!Annotations::sidecar_path(&data_path).exists());
+        Ok(())
+    }
+}
@@ -1,7 +1,10 @@
-use crate::{DEFAULT_CSV_DELIMITER, NULL_VALUES, PolarsViewError, PolarsViewResult};
+use crate::{
+    DEFAULT_CSV_DELIMITER, NULL_VALUES, PolarsViewError, PolarsViewResult, StdinFormat,
+    SyntheticSpec,
+};
 
 use clap::{
-    Parser,
+    ArgGroup, Parser,
     builder::{
         Styles,
         styling::{AnsiColor, Effects},
@@ -37,6 +40,7 @@ const EX2: &str = r#" polars-view data.csv -f "^(Chave|Key).*$""#;
 const EX3: &str =
     r#" polars-view data.csv -q "SELECT * FROM AllData WHERE \"Col Name\" Like '%ABC%'""#;
 const EX4: &str = r#" polars-view -q "SELECT * FROM AllData WHERE \"Valor Total\" > 5000" -r "^Val.*$" data.parquet"#;
+const EX5: &str = r#" cat data.csv | polars-view - -d ",""#;
 
 /// Command-line arguments for the PolarsView application.
 #[derive(Parser, Debug, Clone)]
@@ -47,7 +51,9 @@ const EX4: &str = r#" polars-view -q "SELECT * FROM AllData WHERE \"Valor Total\
     next_line_help = true,
     help_template = APPLET_TEMPLATE,
     styles=get_styles(),
-    after_help = format!("EXAMPLES:\n{EX1}\n{EX2}\n{EX3}\n{EX4}")
+    after_help = format!("EXAMPLES:\n{EX1}\n{EX2}\n{EX3}\n{EX4}\n{EX5}"),
+    // The data comes from FILE_PATH or from standard input.
+    group(ArgGroup::new("input").args(["path", "stdin"])),
 )]
 pub struct Arguments {
     /// CSV delimiter character. [Default: ';']
@@ -58,7 +64,7 @@ pub struct Arguments {
         help = "CSV delimiter character",
         long_help = "Sets the CSV delimiter.\n\
         Auto-detect tries common separators (, ; | \\t) if initial parse fails.",
-        requires = "path"
+        requires = "input"
     )]
     pub delimiter: String,
 
@@ -90,7 +96,7 @@ REGEX_PATTERN Requirements:
 
 [NOTE] Primarily affects CSV/JSON reading where type inference occurs.
 ",
-        requires = "path",
+        requires = "input",
         value_parser = validate_force_string_argument_regex
     )]
     pub force_string_patterns: Option<String>,
@@ -104,7 +110,7 @@ REGEX_PATTERN Requirements:
         help = "Comma-separated values interpreted as NULL",
         long_help = "Specify custom null strings. Whitespace trimmed.\n\
         Use quotes for values with commas/spaces (e.g., \"NA\",\"-\").",
-        requires = "path"
+        requires = "input"
     )]
     pub null_values: String,
 
@@ -114,10 +120,86 @@ REGEX_PATTERN Requirements:
         required = false,
         help = "Path to data file (CSV/JSON/NDJSON/Parquet) [Optional]",
         long_help = "Path to the input data file.\n\
-        If omitted, opens the UI to load a file manually (menu or drag-drop)."
+        If omitted, opens the UI to load a file manually (menu or drag-drop).\n\
+        `-` reads the data from standard input (see --stdin).\n\
+        An http://, https:// or s3:// URL is downloaded, then loaded."
     )]
     pub path: Option<PathBuf>,
 
+    /// Read the data from standard input (the same as FILE_PATH `-`).
+    #[arg(
+        long,
+        help = "Read the data from standard input (the same as FILE_PATH `-`)",
+        long_help = "Reads CSV, JSON, NDJSON or Parquet data piped from another tool.\n\
+        The format is guessed from the data unless --format is given.\n\
+        Example: cat data.csv | polars-view --stdin --format csv",
+        action = clap::ArgAction::SetTrue
+    )]
+    pub stdin: bool,
+
+    /// Format of the data read from standard input [Default: guessed].
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        help = "Format of the data read from standard input [Default: guessed]",
+        long_help = "Format of the piped data: csv, json, ndjson or parquet.\n\
+        If omitted: Parquet (by its magic bytes), JSON (`[`), NDJSON (`{`), otherwise CSV."
+    )]
+    pub format: Option<StdinFormat>,
+
+    /// Read only the first N rows of the file (preview mode) [requires data file].
+    #[arg(
+        long,
+        value_name = "N",
+        help = "Read only the first N rows of the file (preview) [requires FILE_PATH]",
+        long_help = "Preview mode: reads only the first N rows, so massive files open at once.\n\
+        A banner above the table offers to load the full file.\n\
+        Example: polars-view --head 1000 huge.csv",
+        requires = "input",
+        value_parser = clap::value_parser!(usize)
+    )]
+    pub head: Option<usize>,
+
+    /// CSV quote character [Default: sniffed].
+    #[arg(
+        long,
+        value_name = "CHAR",
+        help = "CSV quote character [Default: sniffed, \" or ']",
+        requires = "input"
+    )]
+    pub quote: Option<char>,
+
+    /// CSV escape character of quotes inside fields [Default: sniffed].
+    #[arg(
+        long,
+        value_name = "CHAR",
+        help = "CSV escape character of quotes inside fields [Default: sniffed]",
+        long_help = "Character written before a quote inside a quoted field, e.g., \\ for \"a \\\"b\\\" c\".\n\
+        The quote character itself means doubled quotes (\"a \"\"b\"\" c\").",
+        requires = "input"
+    )]
+    pub escape: Option<char>,
+
+    /// CSV comment prefix: lines starting with it are skipped.
+    #[arg(
+        long,
+        value_name = "PREFIX",
+        help = "Skip CSV lines starting with PREFIX (e.g., #)",
+        requires = "input"
+    )]
+    pub comment: Option<String>,
+
+    /// Rows skipped at the start of a CSV file, before the header.
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 0,
+        help = "Skip the first N rows of a CSV file, before the header (e.g., report titles)",
+        requires = "input"
+    )]
+    pub skip_rows: usize,
+
     /// SQL query to apply after loading data [requires data file].
     #[arg(
         short = 'q',
@@ -126,7 +208,7 @@ REGEX_PATTERN Requirements:
         help = "SQL query to apply to loaded data (use quotes) [requires FILE_PATH]",
         long_help = "Optional Polars SQL query to execute after loading.\n\
         Example: -q \"SELECT * FROM AllData WHERE count > 10\"",
-        requires = "path"
+        requires = "input"
     )]
     pub query: Option<String>,
 
@@ -156,11 +238,62 @@ REGEX_PATTERN Requirements:
     Application example:
         polars-view data.csv -a \"^Val.*$\"
     ",
-        requires = "path",
+        requires = "input",
         value_parser = validate_normalize_argument_regex
     )]
     pub regex: Option<String>,
 
+    /// Reopen the last session (file, load options, query, sorting, format) when no file is given.
+    #[arg(
+        long,
+        help = "Reopen the last opened file with its view [ignored with FILE_PATH]",
+        long_help = "Reopens the last opened file with its load options, SQL query,\n\
+        sorting and display format. The same as enabling \"Settings > Restore last\n\
+        session on startup\". Ignored when FILE_PATH is given.",
+        action = clap::ArgAction::SetTrue,
+        conflicts_with = "path"
+    )]
+    pub restore_session: bool,
+
+    /// Start with built-in defaults, ignoring all persisted state.
+    #[arg(
+        long,
+        help = "Ignore saved sessions, settings, presets and directory configs",
+        long_help = "Starts with the built-in defaults: the last session, settings, presets,\n\
+        recent files, window position and `.polars-view.toml` directory configs are\n\
+        neither read nor written. Use it to recover when corrupted saved state\n\
+        breaks startup.",
+        action = clap::ArgAction::SetTrue,
+        conflicts_with = "restore_session"
+    )]
+    pub safe_mode: bool,
+
+    /// Generate a synthetic dataset and open it (instead of FILE_PATH).
+    #[arg(
+        long,
+        value_name = "SPEC",
+        help = "Open a generated dataset, e.g. \"rows=1000 schema=id:id,price:normal(100,15)\"",
+        long_help = "\
+    Generates a synthetic dataset and opens it, to demo features or test exports
+    without real data. SPEC is a list of settings separated by spaces:
+
+    rows=N        number of rows [Default: 1000]
+    seed=N        random seed: the same SPEC always gives the same data [Default: 42]
+    nulls=F       fraction (0 to 1) of null values in every column but `id` [Default: 0]
+    schema=COLS   comma-separated `name:kind` columns (required)
+
+    Kinds (parameters are optional):
+    id, int(min,max), uniform(min,max), normal(mean,std), str(a|b|c), bool(p),
+    date(YYYY-MM-DD,days)
+
+    Application example:
+        polars-view --generate \"rows=5000 nulls=0.1 schema=id:id,day:date,city:str(Lisbon|Porto),total:uniform(0,900)\"
+    ",
+        conflicts_with_all = ["path", "restore_session"],
+        value_parser = validate_generate_argument
+    )]
+    pub generate: Option<SyntheticSpec>,
+
     /// Table name for SQL queries [requires -q/--query]. [Default: AllData]
     #[arg(
         short = 't',
@@ -172,6 +305,31 @@ REGEX_PATTERN Requirements:
         requires = "query"
     )]
     pub table_name: String,
+
+    /// View state (JSON) to apply after loading [requires data file].
+    #[arg(
+        long,
+        value_name = "VIEW_FILE",
+        help = "View state JSON to apply after loading [requires FILE_PATH]",
+        long_help = "Applies a view exported via \"File > Export View...\":\n\
+        load options, SQL query, sorting and display format.\n\
+        Example: --view sales.view.json",
+        requires = "input"
+    )]
+    pub view: Option<PathBuf>,
+
+    /// SPED/EFD layout presets (JSON) to use in addition to the built-in ones.
+    #[cfg(feature = "format-special")]
+    #[arg(
+        long,
+        value_name = "PRESETS_FILE",
+        help = "Layout presets JSON (SPED/EFD column types, names, decimals, alignments)",
+        long_help = "Loads layout presets from a JSON array of objects:\n\
+        { \"name\": ..., \"detect\": [columns], \"columns\": [{ \"column\", \"rename\", \"dtype\", \"decimal\", \"align\" }] }\n\
+        A preset is applied automatically when a file has all its `detect` columns.\n\
+        User presets take precedence over the built-in ones."
+    )]
+    pub presets: Option<PathBuf>,
 }
 
 impl Arguments {
@@ -220,6 +378,15 @@ fn validate_normalize_argument_regex(s: &str) -> PolarsViewResult<String> {
     validate_cli_regex(s, "--regex")
 }
 
+/// clap validator specifically for the '--generate' argument.
+fn validate_generate_argument(s: &str) -> PolarsViewResult<SyntheticSpec> {
+    s.parse()
+        .map_err(|reason| PolarsViewError::InvalidArgument {
+            arg_name: "--generate".to_string(),
+            reason,
+        })
+}
+
 /// clap validator specifically for the '--force-string-cols' argument.
 fn validate_force_string_argument_regex(s: &str) -> PolarsViewResult<String> {
     validate_cli_regex(s, "--force-string-cols")
@@ -256,6 +423,21 @@ mod tests_args {
         assert_eq!(args.table_name, "AllData"); // Table name defaults even without query
         assert!(!args.exclude_null_cols); // Flag defaults to false
         assert_eq!(args.regex, None); // Optional, defaults to None
+        assert_eq!(args.view, None); // Optional, defaults to None
+    }
+
+    #[test]
+    fn test_args_view_state() {
+        let args = Arguments::parse_from(["polars-view", "--view", "sales.view.json", "data.csv"]);
+
+        assert_eq!(args.path, Some(test_path("data.csv")));
+        assert_eq!(args.view, Some(PathBuf::from("sales.view.json")));
+    }
+
+    #[test]
+    fn test_args_view_state_requires_path() {
+        let result = Arguments::try_parse_from(["polars-view", "--view", "sales.view.json"]);
+        assert!(result.is_err());
     }
 
     #[test]
@@ -339,6 +521,33 @@ mod tests_args {
         assert_eq!(args.regex, Some(regex_str.to_string()));
     }
 
+    #[test]
+    fn test_args_stdin() {
+        let args = Arguments::parse_from(["polars-view", "-", "-q", "SELECT 1"]);
+        assert_eq!(args.path, Some(test_path("-")));
+        assert!(!args.stdin);
+
+        let args =
+            Arguments::parse_from(["polars-view", "--stdin", "--format", "ndjson", "-d", ","]);
+        assert!(args.stdin);
+        assert_eq!(args.path, None);
+        assert_eq!(args.format, Some(StdinFormat::Ndjson));
+
+        // FILE_PATH and --stdin conflict.
+        assert!(Arguments::try_parse_from(["polars-view", "--stdin", "data.csv"]).is_err());
+    }
+
+    #[test]
+    fn test_args_safe_mode() {
+        let args = Arguments::parse_from(["polars-view", "--safe-mode", "data.csv"]);
+        assert!(args.safe_mode);
+        assert!(!Arguments::parse_from(["polars-view"]).safe_mode);
+
+        // Restoring the last session contradicts safe mode.
+        let result = Arguments::try_parse_from(["polars-view", "--safe-mode", "--restore-session"]);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_args_no_path_provided_uses_default() {
         // No path provided, clap should use the default_value "."
@@ -0,0 +1,329 @@
+//! Cell editing ("File" > "Edit Cells"): the table cells become text fields, and the typed
+//! values are kept as a patch (`CellEdits`), keyed by the row of the displayed data and the
+//! column name, until they are applied to it (see `DataContainer::with_cell_edits`).
+//!
+//! The patch is applied by "Apply", "Done", "Save" and "Save As...", which then write the
+//! edited data to the original file or a new one. While it is pending, the data operations
+//! that would replace the displayed rows (sort, filters, query, load options) wait for it to
+//! be applied or discarded.
+//!
+//! Applied edits also go to the rows they came from in the unfiltered data, so they survive
+//! later sorts and column filters. A new SQL query, a change of the load options or a reload
+//! starts over from the file, without the edits, as does a change of the column filters run
+//! on the scan of a Parquet file (see `ParquetPushdown`).
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use egui::{Color32, Response, TextEdit, Ui};
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+/// Whether the cells of a `dtype` column can be edited as text: numbers, text, booleans
+/// and dates (not nested types such as List or Struct).
+pub fn is_editable(dtype: &DataType) -> bool {
+    dtype.is_primitive_numeric()
+        || dtype.is_decimal()
+        || dtype.is_bool()
+        || dtype.is_string()
+        || dtype.is_categorical()
+        || matches!(dtype, DataType::Date | DataType::Datetime(..))
+}
+
+/// The value of `column` at `row` as edited: empty for null, strings without quotes.
+fn cell_text(column: &Column, row: usize) -> String {
+    match column.get(row) {
+        Ok(value) if !value.is_null() => value.str_value().into_owned(),
+        _ => String::new(),
+    }
+}
+
+/// Converts the typed `texts` (`None`: not edited or empty, i.e. null) to a column of `dtype`.
+/// Booleans accept `true`/`false` (any case); the other types are cast from the text.
+fn parse_texts(name: &str, texts: &[Option<&str>], dtype: &DataType) -> PolarsResult<Series> {
+    if dtype.is_bool() {
+        let values = texts
+            .iter()
+            .map(|text| match text.map(|text| text.to_lowercase()) {
+                None => Ok(None),
+                Some(text) if text == "true" => Ok(Some(true)),
+                Some(text) if text == "false" => Ok(Some(false)),
+                Some(text) => polars_bail!(ComputeError: "`{text}` is not true or false"),
+            })
+            .collect::<PolarsResult<Vec<Option<bool>>>>()?;
+        return Ok(Series::new(name.into(), values));
+    }
+    Series::new(name.into(), texts).strict_cast(dtype)
+}
+
+/// Pending cell edits: the typed text of each edited cell, keyed by `(row, column name)`.
+/// An empty text sets the cell to null.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellEdits {
+    cells: BTreeMap<(usize, String), String>,
+}
+
+/// Actions of the edit toolbar above the table (see `CellEdits::render_toolbar`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellEditAction {
+    /// Write the pending edits into the displayed data.
+    Apply,
+    /// Forget the pending edits.
+    Discard,
+    /// Apply the edits and save to the original file.
+    Save,
+    /// Apply the edits and save to a new file.
+    SaveAs,
+    /// Apply the edits and leave edit mode.
+    Close,
+}
+
+impl CellEdits {
+    /// Number of edited cells.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether no cell was edited.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Sets the text of the cell `(row, column)`.
+    pub fn insert(&mut self, row: usize, column: &str, text: impl Into<String>) {
+        self.cells.insert((row, column.to_string()), text.into());
+    }
+
+    /// Forgets the pending edits.
+    pub fn clear(&mut self) {
+        self.cells.clear();
+    }
+
+    /// The names of the edited columns (with repetitions).
+    pub fn columns(&self) -> impl Iterator<Item = &str> {
+        self.cells.keys().map(|(_, name)| name.as_str())
+    }
+
+    /// The same edits at other rows: the edit of row `r` moves to row `rows[r]`.
+    ///
+    /// ### Errors
+    /// An edited row out of `rows`.
+    pub fn moved_to(&self, rows: &[usize]) -> PolarsViewResult<CellEdits> {
+        let cells = self
+            .cells
+            .iter()
+            .map(|((row, name), text)| match rows.get(*row) {
+                Some(moved) => Ok(((*moved, name.clone()), text.clone())),
+                None => Err(PolarsViewError::CellEdit(format!(
+                    "row {} of column `{name}` is out of range ({} rows)",
+                    row + 1,
+                    rows.len()
+                ))),
+            })
+            .collect::<PolarsViewResult<_>>()?;
+        Ok(CellEdits { cells })
+    }
+
+    /// Returns `df` with the edited cells replaced by their typed values, converted to the
+    /// column types.
+    ///
+    /// ### Errors
+    /// A value that doesn't convert (e.g., `abc` in an Int64 column), a missing column or a
+    /// row out of range: nothing is changed, and the error names the cell.
+    pub fn apply(&self, df: &DataFrame) -> PolarsViewResult<DataFrame> {
+        let mut by_column: BTreeMap<&str, Vec<(usize, &str)>> = BTreeMap::new();
+        for ((row, name), text) in &self.cells {
+            by_column.entry(name).or_default().push((*row, text));
+        }
+
+        let mut df = df.clone();
+        let height = df.height();
+        for (name, cells) in by_column {
+            let column = df.column(name)?;
+            let dtype = column.dtype().clone();
+
+            let mut mask = vec![false; height];
+            let mut texts: Vec<Option<&str>> = vec![None; height];
+            for (row, text) in cells {
+                if row >= height {
+                    return Err(PolarsViewError::CellEdit(format!(
+                        "row {} of column `{name}` is out of range ({height} rows)",
+                        row + 1
+                    )));
+                }
+                // One value at a time, so the error names the cell.
+                let text = (!text.is_empty()).then_some(text);
+                parse_texts(name, &[text], &dtype).map_err(|error| {
+                    PolarsViewError::CellEdit(format!(
+                        "row {}, column `{name}`: `{}` is not a valid {dtype} ({error})",
+                        row + 1,
+                        text.unwrap_or_default()
+                    ))
+                })?;
+                mask[row] = true;
+                texts[row] = text;
+            }
+
+            let mask = BooleanChunked::from_slice("mask".into(), &mask);
+            let patch = parse_texts(name, &texts, &dtype)?;
+            let edited = patch.zip_with(&mask, column.as_materialized_series())?;
+            df.with_column(edited)?;
+        }
+
+        tracing::debug!("{} cell edits applied", self.cells.len());
+        Ok(df)
+    }
+
+    /// Renders the cell `(row, column)` as a text field showing its edited text (or its
+    /// value), tinted once edited. Typing the value back removes the edit.
+    pub fn render_cell(&mut self, ui: &mut Ui, row: usize, column: &Column) -> Response {
+        let key = (row, column.name().to_string());
+        let value = cell_text(column, row);
+        let mut text = self
+            .cells
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| value.clone());
+
+        if self.cells.contains_key(&key) {
+            let color = ui.visuals().warn_fg_color.gamma_multiply(0.25);
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+
+        let response = ui.add(
+            TextEdit::singleline(&mut text)
+                .frame(false)
+                .hint_text("null")
+                .desired_width(f32::INFINITY),
+        );
+        if response.changed() {
+            if text == value {
+                self.cells.remove(&key);
+            } else {
+                self.cells.insert(key, text);
+            }
+        }
+        response
+    }
+
+    /// Renders the edit toolbar above the table: the number of pending edits and the
+    /// Apply / Discard / Save / Save As... / Done buttons.
+    pub fn render_toolbar(&self, ui: &mut Ui) -> Option<CellEditAction> {
+        let mut action = None;
+        ui.horizontal(|ui| {
+            ui.colored_label(Color32::from_rgb(230, 150, 0), "✏ Edit mode");
+            ui.label(match self.len() {
+                0 => "no pending edits".to_string(),
+                1 => "1 pending edit".to_string(),
+                n => format!("{n} pending edits"),
+            })
+            .on_hover_text(
+                "Empty cells are null. Apply or discard the edits before sorting or filtering;\n\
+                applied edits are kept, but a new SQL query, a change of the load options\n\
+                or a reload starts over from the file, without the edits.",
+            );
+            ui.separator();
+
+            let pending = !self.is_empty();
+            if ui
+                .add_enabled(pending, egui::Button::new("Apply"))
+                .on_hover_text("Write the edits into the displayed data.")
+                .clicked()
+            {
+                action = Some(CellEditAction::Apply);
+            }
+            if ui
+                .add_enabled(pending, egui::Button::new("Discard"))
+                .clicked()
+            {
+                action = Some(CellEditAction::Discard);
+            }
+            if ui
+                .button("Save")
+                .on_hover_text("Apply the edits and write the data back to the original file.")
+                .clicked()
+            {
+                action = Some(CellEditAction::Save);
+            }
+            if ui
+                .button("Save As...")
+                .on_hover_text("Apply the edits and write the data to a new file.")
+                .clicked()
+            {
+                action = Some(CellEditAction::SaveAs);
+            }
+            if ui
+                .button("Done")
+                .on_hover_text("Apply the edits and leave edit mode.")
+                .clicked()
+            {
+                action = Some(CellEditAction::Close);
+            }
+        });
+        action
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_cell_edit`
+#[cfg(test)]
+mod tests_cell_edit {
+    use super::*;
+
+    #[test]
+    fn edits_are_applied_with_the_column_types() -> PolarsViewResult<()> {
+        let mut df = df!(
+            "id" => &[1i64, 2, 3],
+            "name" => &["Ana", "Bia", "Caio"],
+            "active" => &[true, false, true]
+        )?;
+        let tags: Vec<Series> = (1..=3).map(|n| Series::new("".into(), [n])).collect();
+        df.with_column(Series::new("tags".into(), tags))?;
+        assert!(is_editable(df.column("id")?.dtype()));
+        assert!(!is_editable(df.column("tags")?.dtype()));
+
+        let mut edits = CellEdits::default();
+        edits.insert(0, "id", "10");
+        edits.insert(2, "id", "");
+        edits.insert(1, "name", "Beatriz");
+        edits.insert(1, "active", "TRUE");
+
+        let edited = edits.apply(&df)?;
+        assert_eq!(edited.column("id")?.dtype(), &DataType::Int64);
+        let ids: Vec<Option<i64>> = edited.column("id")?.i64()?.into_iter().collect();
+        assert_eq!(ids, [Some(10), Some(2), None]);
+        assert_eq!(edited.column("name")?.get(1)?.str_value(), "Beatriz");
+        assert_eq!(edited.column("active")?.get(1)?, AnyValue::Boolean(true));
+
+        // An invalid value names the cell; nothing is applied.
+        edits.insert(0, "id", "abc");
+        let error = edits.apply(&df).unwrap_err().to_string();
+        assert!(error.contains("row 1, column `id`"), "{error}");
+
+        let mut edits = CellEdits::default();
+        edits.insert(5, "name", "Out");
+        assert!(edits.apply(&df).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn edits_move_to_other_rows() -> PolarsViewResult<()> {
+        let df = df!("name" => &["Ana", "Bia", "Caio"])?;
+
+        // Displayed rows 0 and 1 show the rows 2 and 0 of `df` (e.g., sorted, then filtered).
+        let mut edits = CellEdits::default();
+        edits.insert(0, "name", "Carlos");
+        edits.insert(1, "name", "Ana Maria");
+        assert_eq!(edits.columns().collect::<Vec<_>>(), ["name", "name"]);
+
+        let moved = edits.moved_to(&[2, 0])?.apply(&df)?;
+        let names: Vec<Option<&str>> = moved.column("name")?.str()?.into_iter().collect();
+        assert_eq!(names, [Some("Ana Maria"), Some("Bia"), Some("Carlos")]);
+
+        assert!(edits.moved_to(&[2]).is_err());
+        Ok(())
+    }
+}
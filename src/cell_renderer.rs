@@ -0,0 +1,375 @@
+//! Cell renderers of the table: how the value of a data cell is drawn (text, boolean
+//! icon, link, image thumbnail, sparkline).
+//!
+//! A renderer implements `CellRenderer` and is registered in `CELL_RENDERERS`; the
+//! renderer of each column is chosen in the "Format" panel and stored by id in
+//! `DataFormat::cell_renderers`, falling back to `TextRenderer` (see `resolve_renderer`).
+//! `render_table_row` handles the selection, search highlight and context menu of the
+//! cell around the `Response` returned by the renderer.
+
+use crate::DataFormat;
+
+use egui::{
+    Color32, Image, Label, OpenUrl, Rect, Response, RichText, Sense, Shape, Stroke, Ui, Vec2,
+    WidgetText, pos2,
+};
+use polars::prelude::*;
+
+/// A data cell handed to a `CellRenderer`.
+pub struct CellContent<'a> {
+    /// The column of the cell.
+    pub column: &'a Column,
+    /// Data row index of the cell.
+    pub row: usize,
+    /// The formatted value (see `format_cell_value`), with the search highlights, if any.
+    pub text: WidgetText,
+    /// Whether the text wraps (the expanded row, see `DataFormat::expand_hovered_row`).
+    pub wrap: bool,
+}
+
+impl CellContent<'_> {
+    /// The raw value of the cell (`Null` if it cannot be read).
+    pub fn value(&self) -> AnyValue<'_> {
+        self.column.get(self.row).unwrap_or(AnyValue::Null)
+    }
+
+    /// The text label of the cell, sensing clicks (the default rendering).
+    fn label(&self) -> Label {
+        let label = Label::new(self.text.clone()).sense(Sense::click());
+        if self.wrap { label.wrap() } else { label }
+    }
+}
+
+/// Draws the value of a data cell.
+///
+/// The returned `Response` must sense clicks: it drives the cell selection, the row
+/// inspector (double-click) and the cell context menu.
+pub trait CellRenderer: Sync {
+    /// Identifier stored in `DataFormat::cell_renderers` (and in saved views).
+    fn id(&self) -> &'static str;
+
+    /// Name shown in the "Format" panel.
+    fn label(&self) -> &'static str;
+
+    /// Whether the renderer can draw the cells of a column of type `dtype`.
+    fn applies_to(&self, dtype: &DataType) -> bool;
+
+    /// Draws the cell in `ui` (the cell area, with its alignment layout).
+    fn render(&self, ui: &mut Ui, cell: &CellContent) -> Response;
+}
+
+/// Default renderer: the formatted value as text.
+pub struct TextRenderer;
+
+impl CellRenderer for TextRenderer {
+    fn id(&self) -> &'static str {
+        "text"
+    }
+
+    fn label(&self) -> &'static str {
+        "Text"
+    }
+
+    fn applies_to(&self, _dtype: &DataType) -> bool {
+        true
+    }
+
+    fn render(&self, ui: &mut Ui, cell: &CellContent) -> Response {
+        ui.add(cell.label())
+    }
+}
+
+/// Boolean columns as icons: ✔ (true) and ✖ (false); nulls stay empty.
+pub struct BooleanIconRenderer;
+
+impl CellRenderer for BooleanIconRenderer {
+    fn id(&self) -> &'static str {
+        "boolean_icon"
+    }
+
+    fn label(&self) -> &'static str {
+        "Icon"
+    }
+
+    fn applies_to(&self, dtype: &DataType) -> bool {
+        dtype.is_bool()
+    }
+
+    fn render(&self, ui: &mut Ui, cell: &CellContent) -> Response {
+        let icon = match cell.value() {
+            AnyValue::Boolean(true) => RichText::new("✔").color(Color32::from_rgb(90, 170, 90)),
+            AnyValue::Boolean(false) => RichText::new("✖").color(Color32::from_rgb(210, 80, 80)),
+            _ => RichText::new(""),
+        };
+        ui.add(Label::new(icon).sense(Sense::click()))
+            .on_hover_text(cell.text.text())
+    }
+}
+
+/// URLs (`http://`, `https://`, `mailto:`) as links: Ctrl + click opens them in the browser
+/// (a plain click selects the cell, as for the other renderers).
+pub struct LinkRenderer;
+
+/// Whether `text` is a URL opened by `LinkRenderer`.
+pub fn is_link(text: &str) -> bool {
+    ["http://", "https://", "mailto:"]
+        .iter()
+        .any(|scheme| text.starts_with(scheme))
+}
+
+impl CellRenderer for LinkRenderer {
+    fn id(&self) -> &'static str {
+        "link"
+    }
+
+    fn label(&self) -> &'static str {
+        "Link"
+    }
+
+    fn applies_to(&self, dtype: &DataType) -> bool {
+        dtype.is_string()
+    }
+
+    fn render(&self, ui: &mut Ui, cell: &CellContent) -> Response {
+        let url = match cell.value() {
+            AnyValue::String(text) => text.trim().to_string(),
+            _ => String::new(),
+        };
+        if !is_link(&url) {
+            return ui.add(cell.label());
+        }
+
+        let text = RichText::new(cell.text.text())
+            .color(ui.visuals().hyperlink_color)
+            .underline();
+        let response = ui
+            .add(Label::new(text).sense(Sense::click()))
+            .on_hover_cursor(egui::CursorIcon::PointingHand)
+            .on_hover_text(format!("{url}\nCtrl + click: open"));
+        if response.clicked() && ui.input(|i| i.modifiers.command) {
+            ui.ctx().open_url(OpenUrl::new_tab(url));
+        }
+        response
+    }
+}
+
+/// Images as thumbnails (the height of the row): Binary cells with the image bytes,
+/// or String cells with an image URL or file path.
+pub struct ThumbnailRenderer;
+
+impl CellRenderer for ThumbnailRenderer {
+    fn id(&self) -> &'static str {
+        "thumbnail"
+    }
+
+    fn label(&self) -> &'static str {
+        "Thumbnail"
+    }
+
+    fn applies_to(&self, dtype: &DataType) -> bool {
+        matches!(dtype, DataType::Binary | DataType::String)
+    }
+
+    fn render(&self, ui: &mut Ui, cell: &CellContent) -> Response {
+        let Some(image) = cell_image(cell) else {
+            return ui.add(cell.label());
+        };
+
+        let height = ui.available_height();
+        ui.add(
+            image
+                .clone()
+                .max_height(height)
+                .maintain_aspect_ratio(true)
+                .sense(Sense::click()),
+        )
+        .on_hover_ui(|ui| {
+            // A larger preview.
+            ui.add(
+                image
+                    .max_size(Vec2::splat(256.0))
+                    .maintain_aspect_ratio(true),
+            );
+        })
+    }
+}
+
+/// The image of a cell drawn by `ThumbnailRenderer`, if its value is one: the bytes of a
+/// Binary cell (cached by column and row), or the URL or file path of a String cell.
+fn cell_image(cell: &CellContent) -> Option<Image<'static>> {
+    match cell.value() {
+        AnyValue::Binary(bytes) if !bytes.is_empty() => Some(Image::from_bytes(
+            format!("bytes://{}/{}", cell.column.name(), cell.row),
+            bytes.to_vec(),
+        )),
+        AnyValue::String(text) if is_link(text) => Some(Image::from_uri(text.to_string())),
+        AnyValue::String(text) if !text.trim().is_empty() => {
+            Some(Image::from_uri(format!("file://{}", text.trim())))
+        }
+        _ => None,
+    }
+}
+
+/// Numeric List/Array cells as a sparkline (a line through the items, scaled to the cell).
+pub struct SparklineRenderer;
+
+/// The items of a numeric List/Array `value` as `f64` (nulls skipped).
+pub fn sparkline_values(value: &AnyValue) -> Vec<f64> {
+    let items = match value {
+        AnyValue::List(items) => items.clone(),
+        AnyValue::Array(items, _) => items.clone(),
+        _ => return Vec::new(),
+    };
+    items
+        .cast(&DataType::Float64)
+        .ok()
+        .and_then(|items| {
+            items
+                .f64()
+                .ok()
+                .map(|values| values.into_iter().flatten().collect())
+        })
+        .unwrap_or_default()
+}
+
+impl CellRenderer for SparklineRenderer {
+    fn id(&self) -> &'static str {
+        "sparkline"
+    }
+
+    fn label(&self) -> &'static str {
+        "Sparkline"
+    }
+
+    fn applies_to(&self, dtype: &DataType) -> bool {
+        matches!(dtype, DataType::List(_) | DataType::Array(..))
+            && dtype
+                .inner_dtype()
+                .is_some_and(|inner| inner.is_primitive_numeric())
+    }
+
+    fn render(&self, ui: &mut Ui, cell: &CellContent) -> Response {
+        let values = sparkline_values(&cell.value());
+        let size = Vec2::new(ui.available_width(), ui.available_height());
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+
+        if values.len() >= 2 && ui.is_rect_visible(rect) {
+            let rect = rect.shrink2(Vec2::new(2.0, 3.0));
+            let (min, max) = values
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+                    (min.min(v), max.max(v))
+                });
+            let points = sparkline_points(&values, min, max, rect);
+            let stroke = Stroke::new(1.5, ui.visuals().hyperlink_color);
+            ui.painter().add(Shape::line(points, stroke));
+        }
+
+        response.on_hover_text(cell.text.text())
+    }
+}
+
+/// Points of the sparkline of `values` (within `min..=max`) in `rect`.
+fn sparkline_points(values: &[f64], min: f64, max: f64, rect: Rect) -> Vec<egui::Pos2> {
+    let step = rect.width() / (values.len() - 1) as f32;
+    let range = max - min;
+    values
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let fraction = if range > 0.0 {
+                ((value - min) / range) as f32
+            } else {
+                0.5
+            };
+            pos2(
+                rect.left() + index as f32 * step,
+                rect.bottom() - fraction * rect.height(),
+            )
+        })
+        .collect()
+}
+
+/// The available renderers, in the order offered by the "Format" panel.
+/// New render modes are added here.
+pub static CELL_RENDERERS: [&dyn CellRenderer; 5] = [
+    &TextRenderer,
+    &BooleanIconRenderer,
+    &LinkRenderer,
+    &ThumbnailRenderer,
+    &SparklineRenderer,
+];
+
+/// The renderer of the column `column` (see `DataFormat::cell_renderers`): the chosen
+/// one if it applies to the column type, else `TextRenderer`.
+pub fn resolve_renderer(format: &DataFormat, column: &Column) -> &'static dyn CellRenderer {
+    format
+        .cell_renderers
+        .get(column.name().as_str())
+        .and_then(|id| CELL_RENDERERS.iter().find(|renderer| renderer.id() == id))
+        .filter(|renderer| renderer.applies_to(column.dtype()))
+        .copied()
+        .unwrap_or(&TextRenderer)
+}
+
+/// The renderers offered for a column of type `dtype` (`TextRenderer` first).
+pub fn renderers_for(dtype: &DataType) -> Vec<&'static dyn CellRenderer> {
+    CELL_RENDERERS
+        .iter()
+        .filter(|renderer| renderer.applies_to(dtype))
+        .copied()
+        .collect()
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_cell_renderer`
+#[cfg(test)]
+mod tests_cell_renderer {
+    use super::*;
+
+    #[test]
+    fn renderers_resolved_by_column_and_dtype() -> PolarsResult<()> {
+        let df = df!(
+            "flag" => &[true, false],
+            "url" => &["https://example.com", "x"]
+        )?;
+        let mut format = DataFormat::default();
+        format
+            .cell_renderers
+            .insert("flag".to_string(), "boolean_icon".to_string());
+        // Does not apply to a String column: falls back to text.
+        format
+            .cell_renderers
+            .insert("url".to_string(), "sparkline".to_string());
+
+        assert_eq!(
+            resolve_renderer(&format, df.column("flag")?).id(),
+            "boolean_icon"
+        );
+        assert_eq!(resolve_renderer(&format, df.column("url")?).id(), "text");
+
+        let ids: Vec<&str> = renderers_for(&DataType::String)
+            .iter()
+            .map(|renderer| renderer.id())
+            .collect();
+        assert_eq!(ids, ["text", "link", "thumbnail"]);
+
+        assert!(is_link("https://example.com") && !is_link("example.com"));
+        Ok(())
+    }
+
+    #[test]
+    fn sparkline_of_numeric_lists() -> PolarsResult<()> {
+        let items = Series::new("items".into(), &[Some(1i32), None, Some(3)]);
+        assert_eq!(sparkline_values(&AnyValue::List(items)), [1.0, 3.0]);
+
+        let rect = Rect::from_min_max(pos2(0.0, 0.0), pos2(10.0, 4.0));
+        let points = sparkline_points(&[1.0, 3.0], 1.0, 3.0, rect);
+        assert_eq!(points, [pos2(0.0, 4.0), pos2(10.0, 0.0)]);
+        Ok(())
+    }
+}
@@ -0,0 +1,312 @@
+//! Table cell selection (single cell, rectangular range, whole rows or columns)
+//! and its conversion to delimited text (TSV/CSV) or JSON records for the clipboard, plus
+//! the values of a whole column (header context menu).
+
+use crate::{PolarsViewResult, write_json};
+
+use polars::prelude::*;
+use std::ops::Range;
+
+/// What a `CellSelection` spans.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SelectionKind {
+    /// The rectangle between the anchor and the cursor cells.
+    #[default]
+    Cells,
+    /// All columns of the rows between the anchor and the cursor.
+    Rows,
+    /// All rows of the columns between the anchor and the cursor.
+    Columns,
+}
+
+/// A selection in the table, in (row, column) indices of the displayed data.
+///
+/// Column indices refer to the *visible* columns (see `DataContainer::visible_columns`).
+/// Click selects a cell, Shift + click extends the selection from the anchor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellSelection {
+    /// Where the selection started (first click).
+    pub anchor: (usize, usize),
+    /// Where the selection ends (last Shift + click).
+    pub cursor: (usize, usize),
+    pub kind: SelectionKind,
+}
+
+impl CellSelection {
+    /// Selects the single cell at (`row`, `column`).
+    pub fn cell(row: usize, column: usize) -> Self {
+        CellSelection {
+            anchor: (row, column),
+            cursor: (row, column),
+            kind: SelectionKind::Cells,
+        }
+    }
+
+    /// Selects the whole row `row`.
+    pub fn row(row: usize) -> Self {
+        CellSelection {
+            kind: SelectionKind::Rows,
+            ..Self::cell(row, 0)
+        }
+    }
+
+    /// Selects the whole column `column`.
+    pub fn column(column: usize) -> Self {
+        CellSelection {
+            kind: SelectionKind::Columns,
+            ..Self::cell(0, column)
+        }
+    }
+
+    /// Moves the cursor to (`row`, `column`), keeping the anchor and the kind (Shift + click).
+    pub fn extend_to(&mut self, row: usize, column: usize) {
+        self.cursor = (row, column);
+    }
+
+    /// Row and column ranges of the selection, clamped to a table of `height` x `width`.
+    pub fn bounds(&self, height: usize, width: usize) -> (Range<usize>, Range<usize>) {
+        let span = |a: usize, b: usize, len: usize| a.min(b).min(len)..(a.max(b) + 1).min(len);
+
+        let rows = match self.kind {
+            SelectionKind::Columns => 0..height,
+            _ => span(self.anchor.0, self.cursor.0, height),
+        };
+        let columns = match self.kind {
+            SelectionKind::Rows => 0..width,
+            _ => span(self.anchor.1, self.cursor.1, width),
+        };
+
+        (rows, columns)
+    }
+
+    /// Returns `true` if the cell at (`row`, `column`) is selected.
+    pub fn contains(&self, row: usize, column: usize, height: usize, width: usize) -> bool {
+        let (rows, columns) = self.bounds(height, width);
+        rows.contains(&row) && columns.contains(&column)
+    }
+
+    /// Returns `true` if the selection is a single cell.
+    pub fn is_single_cell(&self) -> bool {
+        self.kind == SelectionKind::Cells && self.anchor == self.cursor
+    }
+
+    /// Writes the selected cells of `df` (restricted to `columns`, the visible column names)
+    /// as delimited text: TSV with `separator = '\t'`, CSV with `','`.
+    ///
+    /// A single cell is copied as its raw value; otherwise a header line with the column
+    /// names comes first. Fields containing the separator, quotes or line breaks are quoted.
+    pub fn to_delimited(
+        &self,
+        df: &DataFrame,
+        columns: &[&str],
+        separator: char,
+    ) -> PolarsResult<String> {
+        let (rows, range) = self.bounds(df.height(), columns.len());
+        let selected = df.select(columns[range].iter().copied())?;
+
+        if self.is_single_cell() {
+            return match selected.columns().first() {
+                Some(column) if !rows.is_empty() => Ok(cell_text(&column.get(rows.start)?)),
+                _ => Ok(String::new()),
+            };
+        }
+
+        let quote = |field: String| {
+            if field.contains([separator, '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field
+            }
+        };
+
+        let mut lines = vec![
+            selected
+                .get_column_names_str()
+                .into_iter()
+                .map(|name| quote(name.to_string()))
+                .collect::<Vec<_>>()
+                .join(&separator.to_string()),
+        ];
+
+        for row in rows {
+            let fields = selected
+                .columns()
+                .iter()
+                .map(|column| Ok(quote(cell_text(&column.get(row)?))))
+                .collect::<PolarsResult<Vec<String>>>()?;
+            lines.push(fields.join(&separator.to_string()));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    /// Writes the selected rows of `df` as a pretty JSON array of objects, one per row, with
+    /// the keys in column order (e.g., API test payloads, bug reports).
+    ///
+    /// Rows carry all the `columns` (the visible column names), except for a column
+    /// selection, which keeps its columns. Numbers, booleans, nulls and nested values keep
+    /// their JSON types; dates and other values are written as strings.
+    pub fn to_json(&self, df: &DataFrame, columns: &[&str]) -> PolarsViewResult<String> {
+        let (rows, range) = self.bounds(df.height(), columns.len());
+        let columns = match self.kind {
+            SelectionKind::Columns => &columns[range],
+            _ => columns,
+        };
+        let mut selected = df
+            .select(columns.iter().copied())?
+            .slice(rows.start as i64, rows.len());
+
+        let mut buffer = Vec::new();
+        write_json(&mut selected, &mut buffer, JsonFormat::Json, true)?;
+        Ok(String::from_utf8_lossy(&buffer).trim_end().to_string())
+    }
+}
+
+/// Screen reader (AccessKit) label of the data cell at (`row`, `column`) (0-based, visible
+/// columns), e.g., "Row 3, column 2, name: Alice". Coordinates are 1-based, as read aloud.
+pub fn cell_accessible_label(row: usize, column: usize, column_name: &str, value: &str) -> String {
+    let value = if value.is_empty() { "empty" } else { value };
+    format!(
+        "Row {}, column {}, {column_name}: {value}",
+        row + 1,
+        column + 1
+    )
+}
+
+/// The values of `column`, one per line (see `cell_text`), for the clipboard.
+///
+/// With `distinct`, each value is copied once, in order of first appearance.
+pub fn column_to_text(column: &Column, distinct: bool) -> PolarsResult<String> {
+    let column = if distinct {
+        column.unique_stable()?
+    } else {
+        column.clone()
+    };
+
+    let lines = (0..column.len())
+        .map(|row| Ok(cell_text(&column.get(row)?)))
+        .collect::<PolarsResult<Vec<String>>>()?;
+    Ok(lines.join("\n"))
+}
+
+/// Text of a copied cell: raw strings, empty nulls, and the Polars display of other values.
+fn cell_text(value: &AnyValue) -> String {
+    match value {
+        AnyValue::Null => String::new(),
+        AnyValue::String(text) => text.to_string(),
+        AnyValue::StringOwned(text) => text.to_string(),
+        other => other.to_string(),
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_cell_selection`
+#[cfg(test)]
+mod tests_cell_selection {
+    use super::*;
+
+    #[test]
+    fn bounds_by_kind() {
+        let mut selection = CellSelection::cell(4, 2);
+        selection.extend_to(1, 3);
+        assert_eq!(selection.bounds(10, 5), (1..5, 2..4));
+        assert!(selection.contains(3, 3, 10, 5));
+        assert!(!selection.contains(0, 3, 10, 5));
+
+        let mut rows = CellSelection::row(2);
+        rows.extend_to(3, 0);
+        assert_eq!(rows.bounds(10, 5), (2..4, 0..5));
+
+        assert_eq!(CellSelection::column(1).bounds(10, 5), (0..10, 1..2));
+
+        // Clamped to the table size (e.g., after a filter reduced the rows).
+        assert_eq!(CellSelection::cell(20, 1).bounds(10, 5), (10..10, 1..2));
+    }
+
+    #[test]
+    fn accessible_labels() {
+        assert_eq!(
+            cell_accessible_label(2, 1, "name", "Alice"),
+            "Row 3, column 2, name: Alice"
+        );
+        assert_eq!(
+            cell_accessible_label(0, 0, "id", ""),
+            "Row 1, column 1, id: empty"
+        );
+    }
+
+    #[test]
+    fn copies_as_tsv_and_csv() -> PolarsResult<()> {
+        let df = df!(
+            "id" => &[1, 2, 3],
+            "name" => &[Some("a,b"), None, Some("say \"hi\"")],
+            "hidden" => &[true, false, true],
+        )?;
+        let columns = ["id", "name"];
+
+        assert_eq!(
+            CellSelection::cell(0, 1).to_delimited(&df, &columns, ',')?,
+            "a,b"
+        );
+
+        let mut selection = CellSelection::cell(0, 0);
+        selection.extend_to(2, 1);
+        assert_eq!(
+            selection.to_delimited(&df, &columns, '\t')?,
+            "id\tname\n1\ta,b\n2\t\n3\t\"say \"\"hi\"\"\""
+        );
+        assert_eq!(
+            selection.to_delimited(&df, &columns, ',')?,
+            "id,name\n1,\"a,b\"\n2,\n3,\"say \"\"hi\"\"\""
+        );
+
+        assert_eq!(
+            CellSelection::row(1).to_delimited(&df, &columns, '\t')?,
+            "id\tname\n2\t"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn copies_rows_as_json() -> PolarsViewResult<()> {
+        let df = df!(
+            "id" => &[1, 2, 3],
+            "name" => &[Some("a"), None, Some("c")],
+            "active" => &[true, false, true],
+        )?;
+        let columns = ["id", "name", "active"];
+
+        // A cell range copies the whole rows, with typed values.
+        let mut selection = CellSelection::cell(0, 1);
+        selection.extend_to(1, 1);
+        let records: serde_json::Value = serde_json::from_str(&selection.to_json(&df, &columns)?)?;
+        assert_eq!(
+            records,
+            serde_json::json!([
+                {"id": 1, "name": "a", "active": true},
+                {"id": 2, "name": null, "active": false},
+            ])
+        );
+
+        // A column selection keeps its columns.
+        let json = CellSelection::column(0).to_json(&df, &columns)?;
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&json)?,
+            serde_json::json!([{"id": 1}, {"id": 2}, {"id": 3}])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn column_values_to_text() -> PolarsResult<()> {
+        let column = Column::new("city".into(), &[Some("b"), Some("a"), None, Some("b")]);
+
+        assert_eq!(column_to_text(&column, false)?, "b\na\n\nb");
+        assert_eq!(column_to_text(&column, true)?, "b\na\n");
+        Ok(())
+    }
+}
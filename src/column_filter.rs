@@ -0,0 +1,301 @@
+//! Per-column quick filters, typed in the filter row under the table header
+//! (see `DataFormat::show_filter_row`).
+//!
+//! Each non-empty filter becomes a Polars expression (see `column_filter_expr`); the
+//! expressions are combined with AND and applied lazily to the query result, without
+//! rewriting the SQL query.
+
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+/// Filter text per column name. Empty texts are ignored.
+pub type ColumnFilters = BTreeMap<String, String>;
+
+/// Filter text keeping the rows where the column is null (any column type).
+pub const NULL_FILTER: &str = "is null";
+
+/// Filter text keeping the rows where the column is not null (any column type).
+pub const NOT_NULL_FILTER: &str = "is not null";
+
+/// Key of `ColumnFilters` whose filter (`NULL_FILTER` or `NOT_NULL_FILTER`) applies to
+/// all columns: rows with a null in any column, or rows without nulls.
+pub const ANY_COLUMN: &str = "*";
+
+/// Hover text of the filter row inputs.
+pub const COLUMN_FILTER_HELP: &str = "Filter this column (Enter to apply, empty to clear).\n\n\
+    Text: case-insensitive \"contains\".\n\
+    Numbers: 10 (equal), !=10, >10, >=10, <10, <=10, 10..20 (inclusive range).\n\
+    Categorical/Enum: =value (exact category, also picked from the ⏷ list).\n\
+    Any type: is null, is not null.";
+
+/// Comparison parsed from a numeric filter text.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumericFilter {
+    Eq(f64),
+    NotEq(f64),
+    Gt(f64),
+    GtEq(f64),
+    Lt(f64),
+    LtEq(f64),
+    Between(f64, f64),
+}
+
+impl NumericFilter {
+    /// Parses `10`, `=10`, `!=10`, `>10`, `>=10`, `<10`, `<=10` or `10..20`.
+    fn parse(text: &str) -> Option<Self> {
+        let number = |s: &str| s.trim().parse::<f64>().ok();
+
+        if let Some((low, high)) = text.split_once("..") {
+            return Some(NumericFilter::Between(number(low)?, number(high)?));
+        }
+
+        // Two-character operators first.
+        let filter = if let Some(rest) = text.strip_prefix("!=") {
+            NumericFilter::NotEq(number(rest)?)
+        } else if let Some(rest) = text.strip_prefix(">=") {
+            NumericFilter::GtEq(number(rest)?)
+        } else if let Some(rest) = text.strip_prefix("<=") {
+            NumericFilter::LtEq(number(rest)?)
+        } else if let Some(rest) = text.strip_prefix('>') {
+            NumericFilter::Gt(number(rest)?)
+        } else if let Some(rest) = text.strip_prefix('<') {
+            NumericFilter::Lt(number(rest)?)
+        } else {
+            NumericFilter::Eq(number(text.strip_prefix('=').unwrap_or(text))?)
+        };
+
+        Some(filter)
+    }
+
+    /// The predicate on `column`.
+    fn to_expr(self, column: Expr) -> Expr {
+        match self {
+            NumericFilter::Eq(value) => column.eq(lit(value)),
+            NumericFilter::NotEq(value) => column.neq(lit(value)),
+            NumericFilter::Gt(value) => column.gt(lit(value)),
+            NumericFilter::GtEq(value) => column.gt_eq(lit(value)),
+            NumericFilter::Lt(value) => column.lt(lit(value)),
+            NumericFilter::LtEq(value) => column.lt_eq(lit(value)),
+            NumericFilter::Between(low, high) => {
+                column.clone().gt_eq(lit(low)).and(column.lt_eq(lit(high)))
+            }
+        }
+    }
+}
+
+/// Builds the predicate of the filter `text` on the column `name` of type `dtype`.
+///
+/// - `NULL_FILTER`/`NOT_NULL_FILTER` (any column): null presence.
+/// - Numeric columns: equality, comparison or range (see `COLUMN_FILTER_HELP`).
+/// - Categorical/Enum columns: `=value` keeps the rows of one category.
+/// - Other columns (or numeric filters that do not parse): case-insensitive "contains"
+///   on the text representation of the values.
+///
+/// Returns `None` for an empty (blank) filter.
+pub fn column_filter_expr(name: &str, dtype: &DataType, text: &str) -> Option<Expr> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    if text.eq_ignore_ascii_case(NULL_FILTER) {
+        return Some(col(name).is_null());
+    }
+    if text.eq_ignore_ascii_case(NOT_NULL_FILTER) {
+        return Some(col(name).is_not_null());
+    }
+
+    if dtype.is_primitive_numeric()
+        && let Some(filter) = NumericFilter::parse(text)
+    {
+        return Some(filter.to_expr(col(name)));
+    }
+
+    if (dtype.is_categorical() || dtype.is_enum())
+        && let Some(category) = text.strip_prefix('=')
+    {
+        return Some(col(name).cast(DataType::String).eq(lit(category.trim())));
+    }
+
+    let values = match dtype {
+        DataType::String => col(name),
+        _ => col(name).cast(DataType::String),
+    };
+
+    Some(
+        values
+            .str()
+            .to_lowercase()
+            .str()
+            .contains_literal(lit(text.to_lowercase())),
+    )
+}
+
+/// Whether the filter `text` on a column of type `dtype` is a simple predicate that a
+/// Parquet scan evaluates (see `ParquetPushdown`): a comparison or range on a numeric
+/// column, or "contains" on a String column. The other filters run in memory.
+pub fn is_pushable_filter(dtype: &DataType, text: &str) -> bool {
+    let text = text.trim();
+    if text.is_empty()
+        || text.eq_ignore_ascii_case(NULL_FILTER)
+        || text.eq_ignore_ascii_case(NOT_NULL_FILTER)
+    {
+        return false;
+    }
+
+    if dtype.is_primitive_numeric() {
+        NumericFilter::parse(text).is_some()
+    } else {
+        dtype.is_string()
+    }
+}
+
+/// Builds the predicate of the `ANY_COLUMN` filter `text` on the columns of `schema`:
+/// rows with a null in any column (`NULL_FILTER`) or without nulls (`NOT_NULL_FILTER`).
+///
+/// Returns `None` for other texts or an empty schema.
+pub fn any_column_null_expr(schema: &Schema, text: &str) -> Option<Expr> {
+    let text = text.trim();
+    let columns = schema.iter_names().map(|name| col(name.clone()));
+
+    if text.eq_ignore_ascii_case(NULL_FILTER) {
+        columns.map(Expr::is_null).reduce(|acc, expr| acc.or(expr))
+    } else if text.eq_ignore_ascii_case(NOT_NULL_FILTER) {
+        columns
+            .map(Expr::is_not_null)
+            .reduce(|acc, expr| acc.and(expr))
+    } else {
+        None
+    }
+}
+
+/// Combines (AND) the predicates of all `filters` whose column exists in `schema`
+/// (and of the `ANY_COLUMN` filter, see `any_column_null_expr`).
+///
+/// Returns `None` if no filter applies.
+pub fn column_filters_predicate(schema: &Schema, filters: &ColumnFilters) -> Option<Expr> {
+    filters
+        .iter()
+        .filter_map(|(name, text)| match name.as_str() {
+            ANY_COLUMN => any_column_null_expr(schema, text),
+            _ => column_filter_expr(name, schema.get(name)?, text),
+        })
+        .reduce(|acc, expr| acc.and(expr))
+}
+
+/// Applies `filters` to `df` (lazily). Returns a copy of `df` if no filter applies.
+pub fn apply_column_filters(df: &DataFrame, filters: &ColumnFilters) -> PolarsResult<DataFrame> {
+    match column_filters_predicate(df.schema(), filters) {
+        Some(predicate) => df.clone().lazy().filter(predicate).collect(),
+        None => Ok(df.clone()),
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_column_filter`
+#[cfg(test)]
+mod tests_column_filter {
+    use super::*;
+
+    fn filters(pairs: &[(&str, &str)]) -> ColumnFilters {
+        pairs
+            .iter()
+            .map(|(name, text)| (name.to_string(), text.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_numeric_filters() {
+        assert_eq!(NumericFilter::parse("10"), Some(NumericFilter::Eq(10.0)));
+        assert_eq!(NumericFilter::parse("= 10"), Some(NumericFilter::Eq(10.0)));
+        assert_eq!(
+            NumericFilter::parse("!=1.5"),
+            Some(NumericFilter::NotEq(1.5))
+        );
+        assert_eq!(
+            NumericFilter::parse(">= -2"),
+            Some(NumericFilter::GtEq(-2.0))
+        );
+        assert_eq!(NumericFilter::parse("<3"), Some(NumericFilter::Lt(3.0)));
+        assert_eq!(
+            NumericFilter::parse("1..5"),
+            Some(NumericFilter::Between(1.0, 5.0))
+        );
+        assert_eq!(NumericFilter::parse("abc"), None);
+        assert_eq!(NumericFilter::parse("1..x"), None);
+    }
+
+    #[test]
+    fn filters_strings_and_numbers() -> PolarsResult<()> {
+        let df = df!(
+            "name" => &["Apple", "banana", "Cherry", "pineapple"],
+            "price" => &[1.5, 0.5, 3.0, 2.0],
+            "qty" => &[10, 20, 30, 40],
+        )?;
+
+        let result = apply_column_filters(&df, &filters(&[("name", "APPLE")]))?;
+        let names: Vec<Option<&str>> = result.column("name")?.str()?.into_iter().collect();
+        assert_eq!(names, vec![Some("Apple"), Some("pineapple")]);
+
+        let result = apply_column_filters(&df, &filters(&[("price", "1..2"), ("qty", ">10")]))?;
+        let qty: Vec<Option<i32>> = result.column("qty")?.i32()?.into_iter().collect();
+        assert_eq!(qty, vec![Some(40)]);
+
+        // Unparsable numeric filter: "contains" on the text representation.
+        let result = apply_column_filters(&df, &filters(&[("qty", "3?")]))?;
+        assert_eq!(result.height(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn filters_one_category() -> PolarsResult<()> {
+        let categorical = DataType::from_categories(Categories::global());
+        let df = df!("city" => &["Porto", "Lisbon", "Porto Alegre", "Porto"])?
+            .lazy()
+            .with_column(col("city").cast(categorical))
+            .collect()?;
+
+        let result = apply_column_filters(&df, &filters(&[("city", "=Porto")]))?;
+        assert_eq!(result.height(), 2);
+
+        // Without "=": case-insensitive "contains".
+        let result = apply_column_filters(&df, &filters(&[("city", "porto")]))?;
+        assert_eq!(result.height(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn ignores_blank_filters_and_unknown_columns() -> PolarsResult<()> {
+        let df = df!("a" => &[1, 2, 3])?;
+
+        let result = apply_column_filters(&df, &filters(&[("a", "  "), ("missing", "x")]))?;
+        assert_eq!(result.height(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn filters_null_presence() -> PolarsResult<()> {
+        let df = df!(
+            "name" => &[Some("a"), None, Some("c"), Some("d")],
+            "qty" => &[Some(1), Some(2), None, Some(4)],
+        )?;
+
+        let result = apply_column_filters(&df, &filters(&[("name", "is null")]))?;
+        assert_eq!(result.height(), 1);
+        let result = apply_column_filters(&df, &filters(&[("qty", "IS NOT NULL")]))?;
+        assert_eq!(result.height(), 3);
+
+        let result = apply_column_filters(&df, &filters(&[(ANY_COLUMN, NULL_FILTER)]))?;
+        let qty: Vec<Option<i32>> = result.column("qty")?.i32()?.into_iter().collect();
+        assert_eq!(qty, vec![Some(2), None]);
+
+        let result = apply_column_filters(&df, &filters(&[(ANY_COLUMN, NOT_NULL_FILTER)]))?;
+        let names: Vec<Option<&str>> = result.column("name")?.str()?.into_iter().collect();
+        assert_eq!(names, vec![Some("a"), Some("d")]);
+        Ok(())
+    }
+}
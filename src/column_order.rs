@@ -0,0 +1,80 @@
+//! Display order of the table columns (`DataFormat::column_order`), changed by dragging
+//! a header cell onto another one. The `DataFrame` keeps its column order; the display
+//! order is applied when rendering and when exporting.
+
+/// Indices of `names` (the columns of a `DataFrame`) in display order: the names listed
+/// in `order` first, in that order, then the other names in their original order.
+///
+/// Names of `order` missing from `names` (e.g., removed by a SQL query) are ignored.
+pub fn ordered_indices(names: &[&str], order: &[String]) -> Vec<usize> {
+    let listed = order
+        .iter()
+        .filter_map(|name| names.iter().position(|n| n == name));
+
+    let mut seen = vec![false; names.len()];
+    listed
+        .chain(0..names.len())
+        .filter(|&index| !std::mem::replace(&mut seen[index], true))
+        .collect()
+}
+
+/// New display order of the columns `names` (in their current display order) after
+/// dropping the column `moved` on the column `target`: `moved` takes the position of
+/// `target`, which shifts towards the original position of `moved`.
+///
+/// Returns `None` if either column is missing or they are the same.
+pub fn move_column(names: &[&str], moved: &str, target: &str) -> Option<Vec<String>> {
+    let from = names.iter().position(|name| *name == moved)?;
+    let to = names.iter().position(|name| *name == target)?;
+    if from == to {
+        return None;
+    }
+
+    let mut order: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    let column = order.remove(from);
+    order.insert(to, column);
+    Some(order)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_column_order`
+#[cfg(test)]
+mod tests_column_order {
+    use super::*;
+
+    #[test]
+    fn order_and_move_columns() {
+        let names = ["a", "b", "c", "d"];
+
+        // No order: the original order. Unknown names are ignored, unlisted ones come last.
+        assert_eq!(ordered_indices(&names, &[]), [0, 1, 2, 3]);
+        let order = ["c".to_string(), "x".to_string(), "a".to_string()];
+        assert_eq!(ordered_indices(&names, &order), [2, 0, 1, 3]);
+
+        // Moving right puts the column after the target, moving left before it.
+        assert_eq!(
+            move_column(&names, "a", "c"),
+            Some(
+                vec!["b", "c", "a", "d"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            )
+        );
+        assert_eq!(
+            move_column(&names, "d", "b"),
+            Some(
+                vec!["a", "d", "b", "c"]
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            )
+        );
+        assert_eq!(move_column(&names, "a", "a"), None);
+        assert_eq!(move_column(&names, "a", "x"), None);
+    }
+}
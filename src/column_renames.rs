@@ -0,0 +1,88 @@
+//! Column renames ("Columns" > "Rename Columns"): a column name is double-clicked and
+//! edited in place. The new names are kept in `DataFilter::column_renames`, keyed by the
+//! column name as read, and applied to the data right after the type overrides (see
+//! `rename_columns`), so the SQL query, the filters and the exports all see the new names.
+
+use crate::PolarsViewResult;
+
+use polars::prelude::*;
+use std::collections::BTreeMap;
+
+/// Renames the columns of `df` named in `renames` (old name -> new name), lazily.
+/// Missing columns are ignored.
+pub fn rename_columns(
+    df: DataFrame,
+    renames: &BTreeMap<String, String>,
+) -> PolarsViewResult<DataFrame> {
+    let (old_names, new_names): (Vec<&str>, Vec<&str>) = renames
+        .iter()
+        .filter(|(old, new)| old != new)
+        .map(|(old, new)| (old.as_str(), new.as_str()))
+        .unzip();
+
+    if old_names.is_empty() {
+        return Ok(df);
+    }
+    Ok(df.lazy().rename(old_names, new_names, false).collect()?)
+}
+
+/// Checks `new_name` for the column `old_name` among the columns `names` (as read), given
+/// the other `renames`: it must not be empty nor the current name of another column.
+///
+/// Returns the trimmed name, or the reason it is rejected.
+pub fn validate_rename(
+    names: &[&str],
+    renames: &BTreeMap<String, String>,
+    old_name: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err("The name can't be empty.".to_string());
+    }
+
+    let taken = names
+        .iter()
+        .filter(|name| **name != old_name)
+        .map(|name| renames.get(*name).map_or(*name, String::as_str))
+        .any(|current| current == new_name);
+    if taken {
+        return Err(format!("There is already a column `{new_name}`."));
+    }
+    Ok(new_name.to_string())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_column_renames`
+#[cfg(test)]
+mod tests_column_renames {
+    use super::*;
+
+    #[test]
+    fn columns_are_renamed() -> PolarsViewResult<()> {
+        let df = df!("vl_total" => &[1.5, 2.0], "cod" => &[1, 2])?;
+        let renames = BTreeMap::from([
+            ("vl_total".to_string(), "Total".to_string()),
+            ("missing".to_string(), "Other".to_string()),
+        ]);
+
+        let df = rename_columns(df, &renames)?;
+        assert_eq!(df.get_column_names_str(), ["Total", "cod"]);
+
+        let names = ["vl_total", "cod"];
+        assert_eq!(
+            validate_rename(&names, &renames, "cod", " Code "),
+            Ok("Code".to_string())
+        );
+        assert!(validate_rename(&names, &renames, "cod", "Total").is_err());
+        assert!(validate_rename(&names, &renames, "cod", "  ").is_err());
+        // A column can take back its own name, and the old name of a renamed one.
+        assert!(validate_rename(&names, &renames, "vl_total", "vl_total").is_ok());
+        assert!(validate_rename(&names, &renames, "cod", "vl_total").is_ok());
+        Ok(())
+    }
+}
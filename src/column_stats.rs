@@ -0,0 +1,301 @@
+//! Summary statistics of a column (min, max, mean, median, std, null and distinct counts)
+//! for the "Statistics" side panel section. Computed with lazy aggregations on a
+//! background thread.
+
+use crate::PolarsViewResult;
+
+use egui::{ComboBox, Grid, Ui};
+use polars::prelude::*;
+use std::sync::Arc;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// Summary statistics of one column.
+///
+/// Statistics that do not apply to the column type are `None`: `min`/`max` for nested
+/// and categorical columns, `mean`/`median`/`std` for non-numeric columns.
+/// `Decimal` columns are numeric (`mean`/`median`/`std` are computed as `f64`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ColumnStats {
+    pub column: String,
+    /// Data type name (e.g., `f64`, `str`).
+    pub dtype: String,
+    /// Number of rows.
+    pub count: usize,
+    pub null_count: usize,
+    /// Number of distinct values (null counts as one value).
+    pub distinct_count: Option<usize>,
+    /// Smallest value, as text.
+    pub min: Option<String>,
+    /// Largest value, as text.
+    pub max: Option<String>,
+    pub mean: Option<f64>,
+    pub median: Option<f64>,
+    /// Sample standard deviation (`ddof = 1`).
+    pub std: Option<f64>,
+}
+
+/// Computes the `ColumnStats` of the column `name` of `df` in a single lazy query.
+pub fn compute_column_stats(df: &DataFrame, name: &str) -> PolarsViewResult<ColumnStats> {
+    let dtype = df.column(name)?.dtype().clone();
+    let values = col(name);
+
+    let is_numeric = dtype.is_primitive_numeric() || dtype.is_decimal();
+    let is_orderable = is_numeric || dtype.is_temporal() || dtype.is_string() || dtype.is_bool();
+
+    let mut exprs = vec![
+        len().cast(DataType::UInt64).alias("count"),
+        values
+            .clone()
+            .null_count()
+            .cast(DataType::UInt64)
+            .alias("null_count"),
+    ];
+    if !dtype.is_nested() {
+        exprs.push(
+            values
+                .clone()
+                .n_unique()
+                .cast(DataType::UInt64)
+                .alias("distinct_count"),
+        );
+    }
+    if is_orderable {
+        exprs.push(values.clone().min().cast(DataType::String).alias("min"));
+        exprs.push(values.clone().max().cast(DataType::String).alias("max"));
+    }
+    if is_numeric {
+        let float = values.cast(DataType::Float64);
+        exprs.push(float.clone().mean().alias("mean"));
+        exprs.push(float.clone().median().alias("median"));
+        exprs.push(float.std(1).alias("std"));
+    }
+
+    let stats = df.clone().lazy().select(exprs).collect()?;
+
+    let get_u64 = |field: &str| -> PolarsViewResult<Option<usize>> {
+        match stats.column(field) {
+            Ok(column) => Ok(column.u64()?.get(0).map(|value| value as usize)),
+            Err(_) => Ok(None),
+        }
+    };
+    let get_f64 = |field: &str| -> PolarsViewResult<Option<f64>> {
+        match stats.column(field) {
+            Ok(column) => Ok(column.f64()?.get(0)),
+            Err(_) => Ok(None),
+        }
+    };
+    let get_str = |field: &str| -> PolarsViewResult<Option<String>> {
+        match stats.column(field) {
+            Ok(column) => Ok(column.str()?.get(0).map(str::to_string)),
+            Err(_) => Ok(None),
+        }
+    };
+
+    Ok(ColumnStats {
+        column: name.to_string(),
+        dtype: dtype.to_string(),
+        count: get_u64("count")?.unwrap_or(0),
+        null_count: get_u64("null_count")?.unwrap_or(0),
+        distinct_count: get_u64("distinct_count")?,
+        min: get_str("min")?,
+        max: get_str("max")?,
+        mean: get_f64("mean")?,
+        median: get_f64("median")?,
+        std: get_f64("std")?,
+    })
+}
+
+/// State of the "Statistics" side panel section: the selected column and its
+/// statistics, recomputed in the background when the column or the data changes.
+#[derive(Debug, Default)]
+pub struct StatisticsPanel {
+    /// The column to summarize.
+    pub column_name: String,
+    stats: Option<ColumnStats>,
+    error: Option<String>,
+    /// Column and `DataFrame` (address) of the last computation.
+    computed: Option<(String, usize)>,
+    pending: Option<oneshot::Receiver<PolarsViewResult<ColumnStats>>>,
+}
+
+impl StatisticsPanel {
+    /// Whether a computation is still running (receives its result if it arrived).
+    ///
+    /// Used for the busy indicator of the tabs, including inactive ones.
+    pub fn is_pending(&mut self) -> bool {
+        self.poll();
+        self.pending.is_some()
+    }
+
+    /// Receives the result of a pending computation, if available.
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.pending = None;
+                    match result {
+                        Ok(stats) => self.stats = Some(stats),
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.pending = None;
+                    self.error = Some("statistics computation ended unexpectedly".to_string());
+                }
+            }
+        }
+    }
+
+    /// Starts `compute_column_stats` on a blocking thread of `runtime`.
+    /// A computation still pending is abandoned (its result is dropped).
+    fn spawn_compute(&mut self, df: Arc<DataFrame>, runtime: &Handle, ctx: &egui::Context) {
+        let (tx, rx) = oneshot::channel();
+        let column_name = self.column_name.clone();
+        let ctx = ctx.clone();
+
+        runtime.spawn_blocking(move || {
+            let result = compute_column_stats(&df, &column_name);
+            if tx.send(result).is_err() {
+                tracing::debug!("Statistics receiver dropped.");
+            }
+            ctx.request_repaint();
+        });
+
+        self.stats = None;
+        self.error = None;
+        self.pending = Some(rx);
+    }
+
+    /// Renders the column picker and the statistics of the selected column of `df`.
+    ///
+    /// Statistics are computed on `runtime` when the column or `df` changes; decimals
+    /// are shown with `decimal` places.
+    pub fn render(&mut self, ui: &mut Ui, df: &Arc<DataFrame>, runtime: &Handle, decimal: usize) {
+        self.poll();
+
+        let schema = df.schema();
+        if schema.is_empty() {
+            ui.label("No columns.");
+            return;
+        }
+        if !schema.contains(&self.column_name) {
+            self.column_name = schema
+                .iter_names()
+                .next()
+                .map(|n| n.to_string())
+                .unwrap_or_default();
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Column:");
+            ComboBox::from_id_salt("statistics_column")
+                .selected_text(&self.column_name)
+                .show_ui(ui, |ui| {
+                    for name in schema.iter_names() {
+                        ui.selectable_value(&mut self.column_name, name.to_string(), name.as_str());
+                    }
+                });
+        });
+
+        let key = (self.column_name.clone(), Arc::as_ptr(df) as usize);
+        if self.computed.as_ref() != Some(&key) {
+            self.spawn_compute(df.clone(), runtime, ui.ctx());
+            self.computed = Some(key);
+        }
+
+        if self.pending.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Computing statistics...");
+            });
+        } else if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        } else if let Some(stats) = &self.stats {
+            render_stats(ui, stats, decimal);
+        }
+    }
+}
+
+/// Shows `stats` as a two-column grid, skipping statistics that do not apply.
+fn render_stats(ui: &mut Ui, stats: &ColumnStats, decimal: usize) {
+    let float = |value: Option<f64>| value.map(|v| format!("{v:.decimal$}"));
+    let percent = |part: usize| match stats.count {
+        0 => String::new(),
+        total => format!(" ({:.1}%)", 100.0 * part as f64 / total as f64),
+    };
+
+    let rows: [(&str, Option<String>); 9] = [
+        ("Type:", Some(stats.dtype.clone())),
+        ("Rows:", Some(stats.count.to_string())),
+        (
+            "Nulls:",
+            Some(format!("{}{}", stats.null_count, percent(stats.null_count))),
+        ),
+        ("Distinct:", stats.distinct_count.map(|n| n.to_string())),
+        ("Min:", stats.min.clone()),
+        ("Max:", stats.max.clone()),
+        ("Mean:", float(stats.mean)),
+        ("Median:", float(stats.median)),
+        ("Std:", float(stats.std)),
+    ];
+
+    Grid::new("statistics_grid")
+        .num_columns(2)
+        .spacing([10.0, 6.0])
+        .striped(true)
+        .show(ui, |ui| {
+            for (label, value) in rows {
+                if let Some(value) = value {
+                    ui.label(label);
+                    ui.add(egui::Label::new(value).selectable(true));
+                    ui.end_row();
+                }
+            }
+        });
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_column_stats`
+#[cfg(test)]
+mod tests_column_stats {
+    use super::*;
+
+    #[test]
+    fn numeric_column_stats() -> PolarsViewResult<()> {
+        let df = df!("value" => &[Some(1.0), Some(2.0), None, Some(3.0), Some(3.0)])?;
+
+        let stats = compute_column_stats(&df, "value")?;
+        dbg!(&stats);
+
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.null_count, 1);
+        assert_eq!(stats.distinct_count, Some(4)); // 1, 2, 3 and null.
+        assert_eq!(stats.min.as_deref(), Some("1.0"));
+        assert_eq!(stats.max.as_deref(), Some("3.0"));
+        assert_eq!(stats.mean, Some(2.25));
+        assert_eq!(stats.median, Some(2.5));
+        assert!((stats.std.unwrap() - 0.957_427).abs() < 1e-6);
+        Ok(())
+    }
+
+    #[test]
+    fn string_column_stats() -> PolarsViewResult<()> {
+        let df = df!("name" => &["b", "a", "c", "a"])?;
+
+        let stats = compute_column_stats(&df, "name")?;
+        assert_eq!(stats.distinct_count, Some(3));
+        assert_eq!(stats.min.as_deref(), Some("a"));
+        assert_eq!(stats.max.as_deref(), Some("c"));
+        assert_eq!(stats.mean, None);
+        assert_eq!(stats.std, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Per-column data type overrides ("Columns" > "Data Types"): the user picks the type of
+//! a column when the inferred one is wrong (e.g., IDs read as numbers, dates as text).
+//!
+//! The overrides are kept in `DataFilter::column_types`. CSV files are read with them
+//! (see `DataFilter::attempt_read_csv`), and every file gets its columns cast after the read
+//! (see `cast_column_types`): values that don't convert become null.
+
+use crate::PolarsViewResult;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Data types offered by the type override editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ColumnType {
+    Int64,
+    Float64,
+    String,
+    Date,
+    Datetime,
+    Boolean,
+    Categorical,
+}
+
+impl ColumnType {
+    /// All the types, in the order of the editor's dropdown.
+    pub const ALL: [ColumnType; 7] = [
+        ColumnType::Int64,
+        ColumnType::Float64,
+        ColumnType::String,
+        ColumnType::Date,
+        ColumnType::Datetime,
+        ColumnType::Boolean,
+        ColumnType::Categorical,
+    ];
+
+    /// Name shown in the dropdown.
+    pub fn label(self) -> &'static str {
+        match self {
+            ColumnType::Int64 => "Int64",
+            ColumnType::Float64 => "Float64",
+            ColumnType::String => "String",
+            ColumnType::Date => "Date",
+            ColumnType::Datetime => "Datetime",
+            ColumnType::Boolean => "Boolean",
+            ColumnType::Categorical => "Categorical",
+        }
+    }
+
+    /// The Polars data type.
+    pub fn dtype(self) -> DataType {
+        match self {
+            ColumnType::Int64 => DataType::Int64,
+            ColumnType::Float64 => DataType::Float64,
+            ColumnType::String => DataType::String,
+            ColumnType::Date => DataType::Date,
+            ColumnType::Datetime => DataType::Datetime(TimeUnit::Milliseconds, None),
+            ColumnType::Boolean => DataType::Boolean,
+            ColumnType::Categorical => DataType::from_categories(Categories::global()),
+        }
+    }
+
+    /// Whether the CSV reader can parse the column directly as this type (the others are
+    /// read with the inferred type, then cast).
+    pub fn is_read_type(self) -> bool {
+        matches!(
+            self,
+            ColumnType::Int64 | ColumnType::Float64 | ColumnType::String | ColumnType::Boolean
+        )
+    }
+}
+
+/// Casts the columns of `df` named in `column_types` (missing columns are ignored).
+/// The cast is not strict: values that don't convert become null.
+pub fn cast_column_types(
+    mut df: DataFrame,
+    column_types: &BTreeMap<String, ColumnType>,
+) -> PolarsViewResult<DataFrame> {
+    for (name, column_type) in column_types {
+        let Ok(column) = df.column(name) else {
+            tracing::debug!("cast_column_types: no column '{name}'");
+            continue;
+        };
+        let dtype = column_type.dtype();
+        if column.dtype() == &dtype {
+            continue;
+        }
+        let cast = column.cast(&dtype)?;
+        df.with_column(cast)?;
+    }
+    Ok(df)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_column_types`
+#[cfg(test)]
+mod tests_column_types {
+    use super::*;
+
+    #[test]
+    fn columns_are_cast() -> PolarsViewResult<()> {
+        let df = df!(
+            "id" => &[1i64, 2, 3],
+            "amount" => &["1.5", "x", "3"],
+            "day" => &["2024-01-31", "2024-02-29", "bad"]
+        )?;
+        let column_types = BTreeMap::from([
+            ("id".to_string(), ColumnType::String),
+            ("amount".to_string(), ColumnType::Float64),
+            ("day".to_string(), ColumnType::Date),
+            ("missing".to_string(), ColumnType::Boolean),
+        ]);
+
+        let df = cast_column_types(df, &column_types)?;
+        println!("{df}");
+        assert_eq!(df.column("id")?.dtype(), &DataType::String);
+        let amounts: Vec<Option<f64>> = df.column("amount")?.f64()?.into_iter().collect();
+        assert_eq!(amounts, [Some(1.5), None, Some(3.0)]);
+        assert_eq!(df.column("day")?.dtype(), &DataType::Date);
+        assert_eq!(df.column("day")?.null_count(), 1);
+        Ok(())
+    }
+}
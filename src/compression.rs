@@ -0,0 +1,200 @@
+//! Compressed text files (`data.csv.gz`, `events.ndjson.zst`, `export.json.bz2`).
+//!
+//! `FileExtension::from_path` looks through the compression suffix, so the file opens with
+//! the reader of its inner format. The file is streamed through the decoder into memory
+//! (see `open_decompressed` and `DataSource`): no decompressed copy is written to disk, the
+//! readers share the decompressed buffer (see `SharedBytes`), and a preview (`--head N`)
+//! only decompresses the first lines.
+
+use crate::PolarsViewResult;
+
+use std::{
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Compression formats recognized by their extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// gzip (`.gz`).
+    Gzip,
+    /// Zstandard (`.zst`).
+    Zstd,
+    /// bzip2 (`.bz2`).
+    Bzip2,
+}
+
+impl Compression {
+    /// The compression of the file at `path`, from its extension (`None`: not compressed).
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "gz" | "gzip" => Some(Compression::Gzip),
+            "zst" | "zstd" => Some(Compression::Zstd),
+            "bz2" => Some(Compression::Bzip2),
+            _ => None,
+        }
+    }
+
+    /// A reader that decompresses `file` on the fly.
+    fn decoder(self, file: File) -> io::Result<Box<dyn Read + Send>> {
+        let file = BufReader::new(file);
+        Ok(match self {
+            // `MultiGzDecoder`: files written by `cat a.gz b.gz` have several members.
+            Compression::Gzip => Box::new(flate2::read::MultiGzDecoder::new(file)),
+            Compression::Zstd => Box::new(zstd::stream::read::Decoder::with_buffer(file)?),
+            Compression::Bzip2 => Box::new(bzip2::read::MultiBzDecoder::new(file)),
+        })
+    }
+}
+
+/// Returns the path of the file inside the compressed file `path`: `data.csv.gz` -> `data.csv`.
+pub fn inner_path(path: &Path) -> PathBuf {
+    path.with_extension("")
+}
+
+/// Opens the file at `path` for reading, decompressing it on the fly if it is compressed.
+pub fn open_decompressed(path: &Path) -> io::Result<Box<dyn Read + Send>> {
+    let file = File::open(path)?;
+    match Compression::from_path(path) {
+        Some(compression) => compression.decoder(file),
+        None => Ok(Box::new(BufReader::new(file))),
+    }
+}
+
+/// Bytes shared by the readers of `DataSource::Memory`: `Cursor::new(SharedBytes(bytes))`
+/// reads them without copying them.
+#[derive(Debug, Clone)]
+pub struct SharedBytes(pub Arc<Vec<u8>>);
+
+impl AsRef<[u8]> for SharedBytes {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// The data of a text file as the readers get it: the file itself, or the decompressed
+/// bytes of a compressed file.
+#[derive(Debug, Clone)]
+pub enum DataSource {
+    /// A plain file, read (or scanned) from its path.
+    File(PathBuf),
+    /// The decompressed bytes of a compressed file (or another copy of the data made in
+    /// memory), read through a `Cursor` over `SharedBytes`.
+    Memory(Arc<Vec<u8>>),
+}
+
+impl DataSource {
+    /// The data of the file at `path`: the file itself, or its bytes decompressed in
+    /// memory by streaming it through the decoder if it is compressed.
+    ///
+    /// With `max_lines`, the decompression stops after that many lines (the readers of a
+    /// preview need the first lines only).
+    pub fn open(path: &Path, max_lines: Option<usize>) -> PolarsViewResult<Self> {
+        let Some(compression) = Compression::from_path(path) else {
+            return Ok(DataSource::File(path.to_path_buf()));
+        };
+
+        let mut reader = BufReader::new(open_decompressed(path)?);
+        let mut bytes = Vec::new();
+        match max_lines {
+            Some(max_lines) => {
+                for _ in 0..max_lines {
+                    if reader.read_until(b'\n', &mut bytes)? == 0 {
+                        break;
+                    }
+                }
+            }
+            None => {
+                reader.read_to_end(&mut bytes)?;
+            }
+        }
+
+        tracing::info!(
+            "'{}' decompressed in memory ({compression:?}, {} bytes, lines: {max_lines:?})",
+            path.display(),
+            bytes.len()
+        );
+        Ok(DataSource::Memory(Arc::new(bytes)))
+    }
+
+    /// Up to `max_bytes` bytes from the start of the data.
+    pub fn sample(&self, max_bytes: u64) -> io::Result<Vec<u8>> {
+        let mut sample = Vec::new();
+        match self {
+            DataSource::File(path) => {
+                File::open(path)?.take(max_bytes).read_to_end(&mut sample)?;
+            }
+            DataSource::Memory(bytes) => {
+                let end = bytes.len().min(max_bytes as usize);
+                sample.extend_from_slice(&bytes[..end]);
+            }
+        }
+        Ok(sample)
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_compression`
+#[cfg(test)]
+mod tests_compression {
+    use super::*;
+    use crate::FileExtension;
+    use std::io::Write;
+
+    #[test]
+    fn compressed_files_are_decompressed() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let text = "id;name\n1;Ana\n2;João\n";
+
+        let gzip = dir.path().join("data.csv.gz");
+        let mut encoder =
+            flate2::write::GzEncoder::new(File::create(&gzip)?, flate2::Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        encoder.finish()?;
+
+        let zstd = dir.path().join("events.ndjson.zst");
+        std::fs::write(&zstd, zstd::encode_all(text.as_bytes(), 3)?)?;
+
+        let bzip2 = dir.path().join("export.JSON.bz2");
+        let mut encoder =
+            bzip2::write::BzEncoder::new(File::create(&bzip2)?, bzip2::Compression::default());
+        encoder.write_all(text.as_bytes())?;
+        encoder.finish()?;
+
+        assert_eq!(FileExtension::from_path(&gzip), FileExtension::Csv);
+        assert_eq!(FileExtension::from_path(&zstd), FileExtension::NDJson);
+        assert_eq!(FileExtension::from_path(&bzip2), FileExtension::Json);
+
+        for path in [&gzip, &zstd, &bzip2] {
+            let mut decompressed = String::new();
+            open_decompressed(path)?.read_to_string(&mut decompressed)?;
+            assert_eq!(decompressed, text);
+
+            let DataSource::Memory(bytes) = DataSource::open(path, None)? else {
+                panic!("{} is not decompressed in memory", path.display());
+            };
+            assert_eq!(&bytes[..], text.as_bytes());
+            assert_eq!(SharedBytes(bytes).as_ref(), text.as_bytes());
+
+            // A preview decompresses the first lines only.
+            let DataSource::Memory(head) = DataSource::open(path, Some(2))? else {
+                panic!("{} is not decompressed in memory", path.display());
+            };
+            assert_eq!(&head[..], b"id;name\n1;Ana\n");
+        }
+
+        let plain = dir.path().join("plain.csv");
+        std::fs::write(&plain, text)?;
+        let source = DataSource::open(&plain, None)?;
+        assert!(matches!(&source, DataSource::File(path) if *path == plain));
+        assert_eq!(source.sample(7)?, b"id;name");
+        Ok(())
+    }
+}
@@ -0,0 +1,429 @@
+//! CSV dialect sniffing: the quote character (`"` or `'`) and the escape style of quotes
+//! inside quoted fields (doubled `""` or backslash `\"`), detected from the start of the
+//! file for a given delimiter (see `DataFilter::read_csv_data`).
+//!
+//! Polars only understands doubled quotes: files with backslash escapes (exported by some
+//! ERPs) are rewritten, by chunks, to a temporary copy with doubled quotes before being
+//! read. The copy is kept in the private temporary folder and reused while the file is
+//! unchanged (see `temp_files.rs`).
+//!
+//! The start of the file is read once per load (see `CsvInput`), and sniffed once for each
+//! delimiter tried.
+//!
+//! The quote and escape characters set in the Query panel (`--quote`, `--escape`) replace
+//! the sniffed ones (see `DialectOverride`).
+
+use crate::{DataSource, PolarsViewResult, derived_file};
+
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    sync::Arc,
+};
+
+/// Number of bytes read from the start of the file to sniff its dialect.
+pub const SNIFF_SAMPLE_BYTES: u64 = 64 * 1024;
+
+/// Size of the chunks read by `unescape_stream`.
+const UNESCAPE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Quote characters recognized by `sniff_dialect`, in order of preference.
+const QUOTE_CHARS: [u8; 2] = [b'"', b'\''];
+
+/// How a quote character is written inside a quoted field.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum CsvEscape {
+    /// `"a ""quoted"" word"` (RFC 4180, the Polars convention).
+    #[default]
+    Doubled,
+    /// `"a \"quoted\" word"`.
+    Backslash,
+    /// Another escape character, set by the user (e.g., `"a ~"quoted~" word"`).
+    Custom(u8),
+}
+
+impl CsvEscape {
+    /// The escape character written before quotes (`None`: doubled quotes).
+    pub fn escape_char(self) -> Option<u8> {
+        match self {
+            CsvEscape::Doubled => None,
+            CsvEscape::Backslash => Some(b'\\'),
+            CsvEscape::Custom(escape) => Some(escape),
+        }
+    }
+}
+
+/// Quote character and escape style of a CSV file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvDialect {
+    pub quote_char: u8,
+    pub escape: CsvEscape,
+}
+
+/// Quote and escape characters set by the user, replacing the sniffed ones (`None`: sniffed).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DialectOverride {
+    pub quote_char: Option<u8>,
+    /// Escape character of quotes; the quote character itself means doubled quotes.
+    pub escape_char: Option<u8>,
+}
+
+impl DialectOverride {
+    /// Applies the override to the sniffed `dialect`.
+    pub fn apply(self, mut dialect: CsvDialect) -> CsvDialect {
+        if let Some(quote_char) = self.quote_char {
+            dialect.quote_char = quote_char;
+        }
+        dialect.escape = match self.escape_char {
+            None => dialect.escape,
+            Some(escape) if escape == dialect.quote_char => CsvEscape::Doubled,
+            Some(b'\\') => CsvEscape::Backslash,
+            Some(escape) => CsvEscape::Custom(escape),
+        };
+        dialect
+    }
+}
+
+impl Default for CsvDialect {
+    fn default() -> Self {
+        CsvDialect {
+            quote_char: b'"',
+            escape: CsvEscape::Doubled,
+        }
+    }
+}
+
+/// Detects the quote character and escape style of `sample` (the start of a CSV file),
+/// assuming fields are separated by `delimiter`.
+///
+/// A quote character opens a field after a delimiter or a line break, and closes it before
+/// one; the score of a quote character is its number of balanced open/close pairs, so a
+/// quote that only opens fields (e.g., Excel-style `'0123` text values) is not a quote
+/// character. The highest score wins (`"` on ties). Backslash escapes win if `\q` occurs
+/// more often than doubled quotes `qq` inside fields. Any input is accepted: unknown
+/// patterns give `CsvDialect::default()`.
+pub fn sniff_dialect(sample: &[u8], delimiter: u8) -> CsvDialect {
+    let is_boundary = |byte: Option<&u8>| match byte {
+        None => true,
+        Some(&byte) => byte == delimiter || byte == b'\n' || byte == b'\r',
+    };
+
+    let score = |quote: u8| {
+        let (mut opening, mut closing) = (0, 0);
+        for index in (0..sample.len()).filter(|&index| sample[index] == quote) {
+            let before = index.checked_sub(1).map(|before| &sample[before]);
+            match (is_boundary(before), is_boundary(sample.get(index + 1))) {
+                (true, false) => opening += 1,
+                (false, true) => closing += 1,
+                // A lone quote between delimiters, or a quote inside a field.
+                _ => {}
+            }
+        }
+        opening.min(closing)
+    };
+
+    let mut best = CsvDialect::default();
+    let mut best_score = 0;
+    for quote_char in QUOTE_CHARS {
+        let score = score(quote_char);
+        if score > best_score {
+            best = CsvDialect {
+                quote_char,
+                escape: CsvEscape::Doubled,
+            };
+            best_score = score;
+        }
+    }
+
+    let quote = best.quote_char;
+    let pairs = sample.windows(2);
+    let backslashes = pairs
+        .clone()
+        .enumerate()
+        .filter(|(index, pair)| {
+            // An escaped quote is not a closing quote (`"C:\"` ends with a backslash).
+            *pair == [b'\\', quote] && !is_boundary(sample.get(index + 2))
+        })
+        .count();
+    let doubled = pairs
+        .enumerate()
+        .filter(|(index, pair)| {
+            // `""` next to a delimiter is an empty field, not an escaped quote.
+            let before = index.checked_sub(1).map(|before| &sample[before]);
+            *pair == [quote, quote] && !is_boundary(before) && !is_boundary(sample.get(index + 2))
+        })
+        .count();
+
+    if backslashes > doubled {
+        best.escape = CsvEscape::Backslash;
+    }
+    best
+}
+
+/// Rewrites the backslash escapes of the quoted fields of `data` as Polars reads them:
+/// `\q` becomes `qq` and `\\` becomes `\`. Other bytes (and backslashes outside quoted
+/// fields) are kept.
+pub fn unescape_backslashes(data: &[u8], quote: u8) -> Vec<u8> {
+    unescape_quotes(data, b'\\', quote)
+}
+
+/// Rewrites the escapes of the quoted fields of `data` as Polars reads them, for any
+/// `escape` character: `eq` becomes `qq` and `ee` becomes `e` (see `unescape_backslashes`).
+pub fn unescape_quotes(data: &[u8], escape: u8, quote: u8) -> Vec<u8> {
+    let mut output = Vec::with_capacity(data.len());
+    let mut unescaper = Unescaper::new(escape, quote);
+    for &byte in data {
+        unescaper.push(byte, &mut output);
+    }
+    unescaper.finish(&mut output);
+    output
+}
+
+/// Rewrites the escapes of the CSV read from `reader` into `writer`, by chunks (see
+/// `unescape_quotes`).
+pub fn unescape_stream(
+    mut reader: impl Read,
+    writer: &mut dyn Write,
+    escape: u8,
+    quote: u8,
+) -> io::Result<()> {
+    let mut unescaper = Unescaper::new(escape, quote);
+    let mut input = vec![0; UNESCAPE_CHUNK_BYTES];
+    let mut output = Vec::with_capacity(UNESCAPE_CHUNK_BYTES * 2);
+
+    loop {
+        let read = match reader.read(&mut input) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        };
+        output.clear();
+        for &byte in &input[..read] {
+            unescaper.push(byte, &mut output);
+        }
+        writer.write_all(&output)?;
+    }
+
+    output.clear();
+    unescaper.finish(&mut output);
+    writer.write_all(&output)
+}
+
+/// State of the escape rewriting across chunks (see `unescape_quotes`).
+struct Unescaper {
+    escape: u8,
+    quote: u8,
+    /// Whether a quoted field is open.
+    in_quotes: bool,
+    /// Whether the last byte was an escape character inside a quoted field, kept until
+    /// the next byte tells what it escapes.
+    pending_escape: bool,
+}
+
+impl Unescaper {
+    fn new(escape: u8, quote: u8) -> Self {
+        Unescaper {
+            escape,
+            quote,
+            in_quotes: false,
+            pending_escape: false,
+        }
+    }
+
+    /// Rewrites the next `byte` into `output`.
+    fn push(&mut self, byte: u8, output: &mut Vec<u8>) {
+        if self.pending_escape {
+            self.pending_escape = false;
+            if byte == self.quote {
+                output.extend([self.quote, self.quote]);
+                return;
+            }
+            if byte == self.escape {
+                output.push(self.escape);
+                return;
+            }
+            // Not an escape: kept as is.
+            output.push(self.escape);
+        }
+
+        if byte == self.escape && self.in_quotes {
+            self.pending_escape = true;
+            return;
+        }
+        if byte == self.quote {
+            self.in_quotes = !self.in_quotes;
+        }
+        output.push(byte);
+    }
+
+    /// Writes the escape character held at the end of the data, if any.
+    fn finish(&mut self, output: &mut Vec<u8>) {
+        if std::mem::take(&mut self.pending_escape) {
+            output.push(self.escape);
+        }
+    }
+}
+
+/// A CSV file prepared for reading: its data (see `DataFilter::csv_input`) and the start of
+/// it, read once per load and sniffed for each delimiter tried (see `CsvInput::prepare`).
+#[derive(Debug, Clone)]
+pub struct CsvInput {
+    pub source: DataSource,
+    sample: Vec<u8>,
+}
+
+impl CsvInput {
+    /// Reads the start of `source` (`SNIFF_SAMPLE_BYTES`).
+    pub fn new(source: DataSource) -> io::Result<Self> {
+        let sample = source.sample(SNIFF_SAMPLE_BYTES)?;
+        Ok(CsvInput { source, sample })
+    }
+
+    /// Sniffs the dialect of the CSV for `delimiter` (see `sniff_dialect`), with the
+    /// characters set by the user (`dialect_override`) replacing the sniffed ones.
+    ///
+    /// Returns the data to read and the dialect. With escapes, the data is a copy without
+    /// them (see `unescape_quotes`): in memory for data in memory, otherwise a file in the
+    /// private temporary folder, reused while the file is unchanged. This is a blocking
+    /// operation; call it from `spawn_blocking`.
+    pub fn prepare(
+        &self,
+        delimiter: u8,
+        dialect_override: DialectOverride,
+    ) -> PolarsViewResult<(DataSource, CsvDialect)> {
+        let dialect = dialect_override.apply(sniff_dialect(&self.sample, delimiter));
+        let Some(escape) = dialect.escape.escape_char() else {
+            return Ok((self.source.clone(), dialect));
+        };
+        let quote = dialect.quote_char;
+
+        let path = match &self.source {
+            DataSource::File(path) => path,
+            DataSource::Memory(bytes) => {
+                let unescaped = unescape_quotes(bytes, escape, quote);
+                return Ok((DataSource::Memory(Arc::new(unescaped)), dialect));
+            }
+        };
+
+        let kind = format!("unescaped-{escape:02x}{quote:02x}");
+        let copy = derived_file(&kind, path, |writer| {
+            let reader = BufReader::new(File::open(path)?);
+            unescape_stream(reader, writer, escape, quote)?;
+            tracing::info!(
+                "CSV '{}' uses '{}' escapes: reading an unescaped copy",
+                path.display(),
+                escape as char
+            );
+            Ok(())
+        })?;
+        Ok((DataSource::File(copy), dialect))
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_csv_sniffer`
+#[cfg(test)]
+mod tests_csv_sniffer {
+    use super::*;
+
+    #[test]
+    fn quote_and_escape_detection() {
+        let rfc = b"id,text\n1,\"say \"\"hi\"\"\"\n2,\"a, b\"\n3,\"\"\n";
+        assert_eq!(sniff_dialect(rfc, b','), CsvDialect::default());
+
+        let erp = b"id;text\n1;'it\\'s';'x'\n2;'a; b';'C:\\\\'\n";
+        assert_eq!(
+            sniff_dialect(erp, b';'),
+            CsvDialect {
+                quote_char: b'\'',
+                escape: CsvEscape::Backslash,
+            }
+        );
+
+        // No quotes at all.
+        assert_eq!(sniff_dialect(b"a|b\n1|2\n", b'|'), CsvDialect::default());
+
+        // Excel-style text values (`'0123`) open fields but never close them.
+        let excel = b"id;code;name\n1;'0123;\"Ana\"\n2;'0456;Bia\n3;'0789;Caio\n";
+        assert_eq!(sniff_dialect(excel, b';'), CsvDialect::default());
+    }
+
+    #[test]
+    fn backslash_escapes_are_doubled() {
+        let data = b"id;text;path\n1;'it\\'s';C:\\dir\n2;'C:\\\\';'a\\nb'\n";
+        let expected = b"id;text;path\n1;'it''s';C:\\dir\n2;'C:\\';'a\\nb'\n";
+        assert_eq!(unescape_backslashes(data, b'\''), expected);
+
+        // By chunks: an escape at the end of a chunk waits for the next byte.
+        for split in 0..data.len() {
+            let reader = data[..split].chain(&data[split..]);
+            let mut output = Vec::new();
+            unescape_stream(reader, &mut output, b'\\', b'\'').unwrap();
+            assert_eq!(output, expected, "split at {split}");
+        }
+    }
+
+    #[test]
+    fn user_characters_replace_the_sniffed_ones() {
+        let sniffed = CsvDialect::default();
+        let dialect_override = DialectOverride {
+            quote_char: Some(b'\''),
+            escape_char: Some(b'~'),
+        };
+        assert_eq!(
+            dialect_override.apply(sniffed),
+            CsvDialect {
+                quote_char: b'\'',
+                escape: CsvEscape::Custom(b'~'),
+            }
+        );
+        // The quote character as escape: doubled quotes.
+        let doubled = DialectOverride {
+            escape_char: Some(b'"'),
+            ..Default::default()
+        };
+        assert_eq!(doubled.apply(sniffed).escape, CsvEscape::Doubled);
+        assert_eq!(DialectOverride::default().apply(sniffed), sniffed);
+
+        let data = b"id;text\n1;'it~'s ~~ok~~'\n";
+        assert_eq!(
+            unescape_quotes(data, b'~', b'\''),
+            b"id;text\n1;'it''s ~ok~'\n"
+        );
+    }
+
+    /// Arbitrary bytes (a deterministic pseudo-random sequence) never make the sniffer panic.
+    #[test]
+    fn arbitrary_input() {
+        let mut state: u32 = 0x2545_f491;
+        for length in 0..512 {
+            let sample: Vec<u8> = (0..length)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    [
+                        b'"',
+                        b'\'',
+                        b'\\',
+                        b',',
+                        b';',
+                        b'\n',
+                        b'a',
+                        (state >> 8) as u8,
+                    ][(state % 8) as usize]
+                })
+                .collect();
+
+            let dialect = sniff_dialect(&sample, b',');
+            assert!(QUOTE_CHARS.contains(&dialect.quote_char));
+            let unescaped = unescape_backslashes(&sample, dialect.quote_char);
+            assert!(unescaped.len() <= sample.len());
+        }
+    }
+}
@@ -0,0 +1,421 @@
+//! Currency conversion: loads a small rate table (`date`, `currency`, `rate`) and appends
+//! a converted-amount column by looking up each row's date/currency pair.
+
+use crate::{PolarsViewError, PolarsViewResult, read_csv_partial_from_path};
+
+use egui::{ComboBox, Context, Grid, TextEdit, Window};
+use polars::prelude::*;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+};
+use tokio::task::spawn_blocking;
+
+/// Required column names (case-insensitive) of a rate table.
+pub const RATE_COLUMNS: [&str; 3] = ["date", "currency", "rate"];
+
+/// Columns and output name of a currency conversion:
+/// `output = amount * rate(date, currency)`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurrencyConversion {
+    pub date_column: String,
+    pub currency_column: String,
+    pub amount_column: String,
+    pub output_column: String,
+}
+
+/// Normalizes a date key: `dd/mm/yyyy` becomes `yyyy-mm-dd`; other values are only trimmed
+/// (dates read as `Date` are formatted as `yyyy-mm-dd` when cast to String).
+fn normalize_date_key(value: &str) -> String {
+    let value = value.trim();
+    let parts: Vec<&str> = value.split('/').collect();
+
+    match parts.as_slice() {
+        [day, month, year] if year.len() == 4 && day.len() <= 2 && month.len() <= 2 => {
+            format!("{year}-{month:0>2}-{day:0>2}")
+        }
+        _ => value.to_string(),
+    }
+}
+
+/// Normalizes a currency key (e.g., `" usd "` -> `"USD"`).
+fn normalize_currency_key(value: &str) -> String {
+    value.trim().to_uppercase()
+}
+
+/// Parses a rate, accepting a decimal comma (e.g., `"5,1234"`) when there is no dot.
+fn parse_rate(value: &str) -> Option<f64> {
+    let value = value.trim();
+    if value.contains(',') && !value.contains('.') {
+        value.replace(',', ".").parse().ok()
+    } else {
+        value.parse().ok()
+    }
+}
+
+/// Exchange rates keyed by (date, currency).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RateTable {
+    rates: HashMap<(String, String), f64>,
+}
+
+impl RateTable {
+    /// Builds a rate table from a `DataFrame` with `date`, `currency` and `rate` columns
+    /// (names are case-insensitive; values may be of any type).
+    ///
+    /// Rows with nulls or unparsable rates are ignored; for repeated keys, the last rate wins.
+    pub fn from_dataframe(df: &DataFrame) -> PolarsViewResult<Self> {
+        let mut columns = Vec::with_capacity(RATE_COLUMNS.len());
+
+        for required in RATE_COLUMNS {
+            let column = df
+                .columns()
+                .iter()
+                .find(|column| column.name().eq_ignore_ascii_case(required))
+                .ok_or_else(|| {
+                    PolarsViewError::Other(format!(
+                        "Rate table must have the columns {RATE_COLUMNS:?}; `{required}` not found."
+                    ))
+                })?;
+            columns.push(column.cast(&DataType::String)?);
+        }
+
+        let dates = columns[0].as_materialized_series().str()?;
+        let currencies = columns[1].as_materialized_series().str()?;
+        let rates = columns[2].as_materialized_series().str()?;
+
+        let mut table = RateTable::default();
+        for ((date, currency), rate) in dates.into_iter().zip(currencies).zip(rates) {
+            if let (Some(date), Some(currency), Some(rate)) =
+                (date, currency, rate.and_then(parse_rate))
+            {
+                table.rates.insert(
+                    (normalize_date_key(date), normalize_currency_key(currency)),
+                    rate,
+                );
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Reads a rate table from a CSV file (delimiter `,`, `;` or tab, detected from the header).
+    /// Both the header and the table are read on blocking threads.
+    pub async fn read_csv(path: &Path) -> PolarsViewResult<Self> {
+        let header_path = path.to_path_buf();
+        let header = spawn_blocking(move || -> PolarsViewResult<String> {
+            let mut header = Vec::new();
+            BufReader::new(File::open(header_path)?).read_until(b'\n', &mut header)?;
+            Ok(String::from_utf8_lossy(&header).into_owned())
+        })
+        .await??;
+
+        let delimiter = [b';', b'\t', b',']
+            .into_iter()
+            .max_by_key(|delimiter| header.matches(*delimiter as char).count())
+            .unwrap_or(b',');
+
+        let df = read_csv_partial_from_path(delimiter, b'"', (None, 0), usize::MAX, path).await?;
+        RateTable::from_dataframe(&df)
+    }
+
+    /// Number of (date, currency) rates.
+    pub fn len(&self) -> usize {
+        self.rates.len()
+    }
+
+    /// Returns `true` if the table has no rates.
+    pub fn is_empty(&self) -> bool {
+        self.rates.is_empty()
+    }
+
+    /// Returns the rate for `date` and `currency`, if any.
+    pub fn get(&self, date: &str, currency: &str) -> Option<f64> {
+        self.rates
+            .get(&(normalize_date_key(date), normalize_currency_key(currency)))
+            .copied()
+    }
+}
+
+/// Appends (or replaces) `conversion.output_column` with `amount * rate(date, currency)`.
+///
+/// Rows without a matching rate (or with a null date, currency or amount) get null.
+/// A `Datetime` date column matches the rates of the day of each timestamp.
+///
+/// ### Returns
+/// The new `DataFrame` and the number of rows without a matching rate.
+pub fn append_converted_column(
+    mut df: DataFrame,
+    rates: &RateTable,
+    conversion: &CurrencyConversion,
+) -> PolarsViewResult<(DataFrame, usize)> {
+    // Rate keys are days: a timestamp is cast to `Date`, formatted as `yyyy-mm-dd`.
+    let dates = df.column(&conversion.date_column)?;
+    let dates = match dates.dtype() {
+        DataType::Datetime(..) => dates.cast(&DataType::Date)?,
+        _ => dates.clone(),
+    }
+    .cast(&DataType::String)?;
+    let currencies = df
+        .column(&conversion.currency_column)?
+        .cast(&DataType::String)?;
+    let amounts = df
+        .column(&conversion.amount_column)?
+        .cast(&DataType::Float64)?;
+
+    let converted: Float64Chunked = dates
+        .as_materialized_series()
+        .str()?
+        .into_iter()
+        .zip(currencies.as_materialized_series().str()?)
+        .zip(amounts.as_materialized_series().f64()?)
+        .map(|((date, currency), amount)| Some(amount? * rates.get(date?, currency?)?))
+        .collect();
+
+    let unmatched = converted.null_count();
+    df.with_column(
+        converted
+            .with_name(conversion.output_column.as_str().into())
+            .into_column(),
+    )?;
+
+    Ok((df, unmatched))
+}
+
+/// Action requested by the "Currency Conversion" dialog.
+pub enum CurrencyAction {
+    /// Choose the rate table file (handled by `layout.rs` with a file dialog).
+    PickRatesFile,
+    /// Apply the conversion with the rates of the given file.
+    Apply(PathBuf, CurrencyConversion),
+}
+
+/// State of the "Currency Conversion" dialog (opened from the "File" menu).
+#[derive(Default)]
+pub struct CurrencyDialog {
+    /// Whether the dialog is shown.
+    pub open: bool,
+    /// The rate table file (CSV with `date`, `currency`, `rate` columns).
+    pub rates_path: Option<PathBuf>,
+    pub conversion: CurrencyConversion,
+}
+
+impl CurrencyDialog {
+    /// Shows the dialog (if open) for a `DataFrame` with the given `schema`.
+    /// Returns the action requested by the user, if any.
+    pub fn show(&mut self, ctx: &Context, schema: &Schema) -> Option<CurrencyAction> {
+        if !self.open {
+            return None;
+        }
+
+        let columns: Vec<String> = schema.iter_names().map(|name| name.to_string()).collect();
+        let numeric: Vec<String> = schema
+            .iter()
+            .filter(|(_name, dtype)| dtype.is_primitive_numeric())
+            .map(|(name, _dtype)| name.to_string())
+            .collect();
+
+        let conversion = &mut self.conversion;
+        if conversion.output_column.is_empty() && !conversion.amount_column.is_empty() {
+            conversion.output_column = format!("{}_converted", conversion.amount_column);
+        }
+
+        let mut action = None;
+        let mut open = self.open;
+
+        Window::new("Currency Conversion")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                Grid::new("currency_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 10.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Rates file:");
+                        ui.horizontal(|ui| {
+                            let name = self
+                                .rates_path
+                                .as_ref()
+                                .and_then(|path| path.file_name())
+                                .map(|name| name.to_string_lossy().to_string())
+                                .unwrap_or_else(|| "(none)".to_string());
+                            ui.label(name);
+                            if ui
+                                .button("Choose...")
+                                .on_hover_text(
+                                    "CSV file with the columns `date`, `currency` and `rate`.",
+                                )
+                                .clicked()
+                            {
+                                action = Some(CurrencyAction::PickRatesFile);
+                            }
+                        });
+                        ui.end_row();
+
+                        for (label, value, options) in [
+                            ("Date column:", &mut conversion.date_column, &columns),
+                            (
+                                "Currency column:",
+                                &mut conversion.currency_column,
+                                &columns,
+                            ),
+                            ("Amount column:", &mut conversion.amount_column, &numeric),
+                        ] {
+                            ui.label(label);
+                            ComboBox::from_id_salt(label)
+                                .selected_text(value.as_str())
+                                .show_ui(ui, |ui| {
+                                    for name in options {
+                                        ui.selectable_value(value, name.clone(), name);
+                                    }
+                                });
+                            ui.end_row();
+                        }
+
+                        ui.label("Output column:");
+                        ui.add(TextEdit::singleline(&mut conversion.output_column));
+                        ui.end_row();
+                    });
+
+                ui.separator();
+
+                let ready = self.rates_path.is_some()
+                    && [
+                        &conversion.date_column,
+                        &conversion.currency_column,
+                        &conversion.amount_column,
+                        &conversion.output_column,
+                    ]
+                    .iter()
+                    .all(|name| !name.trim().is_empty());
+
+                if ui
+                    .add_enabled(ready, egui::Button::new("Apply"))
+                    .on_hover_text(
+                        "Append `output = amount × rate`, matching rates by date and currency.",
+                    )
+                    .clicked()
+                    && let Some(path) = &self.rates_path
+                {
+                    action = Some(CurrencyAction::Apply(path.clone(), conversion.clone()));
+                }
+            });
+
+        if matches!(action, Some(CurrencyAction::Apply(..))) {
+            open = false;
+        }
+        self.open = open;
+
+        action
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_currency`
+#[cfg(test)]
+mod tests_currency {
+    use super::*;
+
+    #[test]
+    fn rate_table_normalizes_keys() -> PolarsViewResult<()> {
+        let rates = df!(
+            "Date" => &["31/01/2024", "2024-02-01", "2024-02-01"],
+            "Currency" => &["usd", "USD", "EUR"],
+            "Rate" => &["4,9535", "4.97", "bad"],
+        )?;
+
+        let table = RateTable::from_dataframe(&rates)?;
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get("2024-01-31", " USD"), Some(4.9535));
+        assert_eq!(table.get("1/2/2024", "usd"), Some(4.97));
+        assert_eq!(table.get("2024-02-01", "EUR"), None);
+        Ok(())
+    }
+
+    #[test]
+    fn rate_table_requires_columns() -> PolarsViewResult<()> {
+        let rates = df!("date" => &["2024-01-31"], "rate" => &[1.0])?;
+        assert!(RateTable::from_dataframe(&rates).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn append_converted_amounts() -> PolarsViewResult<()> {
+        let rates = df!(
+            "date" => &["2024-01-31", "2024-01-31"],
+            "currency" => &["USD", "EUR"],
+            "rate" => &[5.0, 6.0],
+        )?;
+        let table = RateTable::from_dataframe(&rates)?;
+
+        let dates = ["2024-01-31", "2024-01-31", "2024-02-01", "2024-01-31"];
+        let df = df!(
+            "date" => &dates,
+            "currency" => &["USD", "EUR", "USD", "JPY"],
+            "amount" => &[Some(10.0), Some(2.0), Some(1.0), None],
+        )?
+        .lazy()
+        .with_column(col("date").str().to_date(StrptimeOptions::default()))
+        .collect()?;
+
+        let conversion = CurrencyConversion {
+            date_column: "date".to_string(),
+            currency_column: "currency".to_string(),
+            amount_column: "amount".to_string(),
+            output_column: "amount_brl".to_string(),
+        };
+
+        let (df, unmatched) = append_converted_column(df, &table, &conversion)?;
+        dbg!(&df);
+
+        let converted: Vec<Option<f64>> = df.column("amount_brl")?.f64()?.into_iter().collect();
+        assert_eq!(converted, vec![Some(50.0), Some(12.0), None, None]);
+        assert_eq!(unmatched, 2);
+        Ok(())
+    }
+
+    #[test]
+    fn append_converted_amounts_by_timestamp() -> PolarsViewResult<()> {
+        let rates = df!(
+            "date" => &["2024-01-31"],
+            "currency" => &["USD"],
+            "rate" => &[5.0],
+        )?;
+        let table = RateTable::from_dataframe(&rates)?;
+
+        // 2024-01-31 10:30:00 and 2024-02-01 23:59:00 (UTC).
+        let timestamps = Series::new("date".into(), &[1_706_697_000_000_i64, 1_706_831_940_000])
+            .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?;
+        let df = DataFrame::new(
+            2,
+            vec![
+                timestamps.into_column(),
+                Column::new("currency".into(), &["USD", "USD"]),
+                Column::new("amount".into(), &[10.0, 1.0]),
+            ],
+        )?;
+
+        let conversion = CurrencyConversion {
+            date_column: "date".to_string(),
+            currency_column: "currency".to_string(),
+            amount_column: "amount".to_string(),
+            output_column: "amount_brl".to_string(),
+        };
+
+        let (df, unmatched) = append_converted_column(df, &table, &conversion)?;
+
+        let converted: Vec<Option<f64>> = df.column("amount_brl")?.f64()?.into_iter().collect();
+        assert_eq!(converted, vec![Some(50.0), None]);
+        assert_eq!(unmatched, 1);
+        Ok(())
+    }
+}
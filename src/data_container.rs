@@ -1,18 +1,73 @@
-use egui::{Id, TextStyle, Ui};
+use egui::scroll_area::ScrollBarVisibility;
+use egui::{
+    Align, Color32, Id, Label, Layout, RichText, ScrollArea, Sense, TextEdit, TextStyle, Ui,
+    WidgetInfo, WidgetText, WidgetType,
+};
 use egui_extras::{Column, TableBuilder, TableRow};
 use polars::prelude::Column as PColumn;
 use polars::prelude::*;
-use std::sync::Arc;
+use std::{collections::HashMap, ops::Range, path::PathBuf, sync::Arc};
+use tempfile::TempPath;
 
 use crate::polars::transforms::{
-    AddRowIndexTransform, DataFrameTransform, DropColumnsTransform, NormalizeTransform,
-    RemoveNullColumnsTransform, ReplaceNullsTransform, SqlTransform,
+    CastColumnTypesTransform, DataFrameTransform, DropColumnsTransform, FlattenStructsTransform,
+    NormalizeTransform, ParseDatesTransform, RemoveNullColumnsTransform, RenameColumnsTransform,
+    ReplaceNullsTransform, SqlTransform,
 };
 use crate::{
-    DataFilter, DataFormat, FileExtension, HeaderSortState, PolarsViewError, PolarsViewResult,
-    SortBy, SortableHeaderRenderer, get_decimal_and_layout,
+    ANY_COLUMN, Annotations, COLUMN_FILTER_HELP, CategoricalSaving, CellContent, CellEdits,
+    CellSelection, ColumnFilters, CurrencyConversion, DEFAULT_QUERY, DataFilter, DataFormat,
+    FileExtension, HeaderClick, HeaderSortState, LazyTable, ListDisplay, LoadStage,
+    NOT_NULL_FILTER, NULL_FILTER, NULL_VALUES, ParquetPushdown, PolarsViewError, PolarsViewResult,
+    QueryHistory, QuickFilterOp, RateTable, SelectionKind, SortBy, SortExpression,
+    SortableHeaderRenderer, TableSearch, ViewState, add_row_index_column, add_virtual_columns,
+    append_converted_column, apply_column_filters, apply_header_click, cast_string_columns,
+    categories_by_column, cell_accessible_label, check_cancelled, column_filters_predicate,
+    column_to_text, compose_quick_filter, convert_units, format_cell, get_decimal_and_layout,
+    infer_string_dtypes, is_editable, is_single_struct, null_badge, ordered_indices,
+    quick_filter_condition, report_stage, resolve_renderer, sort_single_column,
+    strings_to_categorical, unit_conversions,
 };
 
+/// Maximum characters of a cell value shown in the cell context menu labels.
+const MAX_MENU_VALUE_CHARS: usize = 30;
+
+/// Temporary column holding the row of `df_unfiltered` shown at each row of `df`
+/// (see `DataContainer::unfiltered_rows`).
+const ROW_KEY_COLUMN: &str = "__polars_view_row__";
+
+/// Which columns a table drawn by `render_table` shows: with frozen columns
+/// (`DataFormat::frozen_columns`) or the row number gutter (`DataFormat::show_row_numbers`),
+/// they get their own table, fixed on the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TablePart {
+    /// The row number gutter and the frozen columns.
+    Frozen,
+    /// The other visible columns, scrolled horizontally.
+    Scrolling,
+}
+
+/// Drag and drop payload of a header cell: the name of the dragged column.
+struct DraggedColumn(String);
+
+/// Data rows drawn by `render_table` in one frame, and the expanded row
+/// (`DataFormat::expand_hovered_row`).
+#[derive(Debug, Default)]
+struct BodyRows {
+    /// Data row index of the first table row (the first row of the page in paged mode).
+    offset: usize,
+    /// Data row drawn with its cells wrapped (full content), if any.
+    expanded: Option<usize>,
+    /// Data row under the pointer in this frame.
+    hovered: Option<usize>,
+    /// Height of the wrapped cells of the expanded row in this frame.
+    expanded_height: f32,
+}
+
+/// Expanded row `(row, height)` of the last frame, kept in egui's temporary memory:
+/// a row is expanded one frame after it is hovered, once its wrapped height is known.
+const EXPANDED_ROW_ID: &str = "expanded_row";
+
 /// Internal struct holding calculated configuration for `TableBuilder`.
 /// Generated by `prepare_table_build_config`.
 struct TableBuildConfig {
@@ -20,15 +75,67 @@ struct TableBuildConfig {
     num_columns: usize,
     header_height: f32,
     column_sizing_strategy: Column, // Use 'static as Column doesn't take a lifetime here
+    /// Locked width of each column of the part, in display order (see `DataFormat::locked_widths`).
+    locked_widths: Vec<Option<f32>>,
+    /// Width of the row number gutter, if the table has one (see `has_row_gutter`).
+    gutter_width: Option<f32>,
     table_id: Id,
 }
 
+/// Interactions with the table during one frame (see `DataContainer::render_table`),
+/// handled by `layout.rs`.
+#[derive(Debug, Default)]
+pub struct TableEvents {
+    /// New sort criteria requested by a header click.
+    pub sort: Option<Vec<SortBy>>,
+    /// New column filters requested by the filter row (`format.show_filter_row`).
+    pub column_filters: Option<ColumnFilters>,
+    /// Double-clicked data cell `(row_index, column_name)`: opens the row inspector.
+    pub inspect_cell: Option<(usize, String)>,
+    /// New SQL query requested by a quick filter of the cell context menu.
+    pub query: Option<String>,
+    /// Column to hide, requested by the header context menu.
+    pub hide_column: Option<String>,
+    /// Column to freeze (`true`) or unfreeze, requested by the header context menu.
+    pub freeze_column: Option<(String, bool)>,
+    /// Alignment of a column (`None`: the alignment of its data type), requested by the
+    /// header context menu.
+    pub align_column: Option<(String, Option<Align>)>,
+    /// Width to lock a column at (`None`: unlock it), requested by the header context menu.
+    pub lock_width: Option<(String, Option<f32>)>,
+    /// Column dragged onto another header cell `(moved, target)`: `moved` takes the
+    /// display position of `target` (see `move_column`).
+    pub move_column: Option<(String, String)>,
+    /// Column to group by, requested by the header context menu: opens the value
+    /// counts window (see `ValueCountsView`).
+    pub group_by: Option<String>,
+}
+
+impl TableEvents {
+    /// Keeps the events of `self`, taking the missing ones from `other`.
+    fn or(self, other: TableEvents) -> TableEvents {
+        TableEvents {
+            sort: self.sort.or(other.sort),
+            column_filters: self.column_filters.or(other.column_filters),
+            inspect_cell: self.inspect_cell.or(other.inspect_cell),
+            query: self.query.or(other.query),
+            hide_column: self.hide_column.or(other.hide_column),
+            freeze_column: self.freeze_column.or(other.freeze_column),
+            align_column: self.align_column.or(other.align_column),
+            lock_width: self.lock_width.or(other.lock_width),
+            move_column: self.move_column.or(other.move_column),
+            group_by: self.group_by.or(other.group_by),
+        }
+    }
+}
+
 /// Container for the Polars DataFrame and its associated display and filter state.
 ///
 /// ## State Management:
 /// - Holds the core data (`df`, `df_original`) and related settings.
 /// - **`df`**: The currently displayed DataFrame, potentially sorted based on `self.sort`.
 /// - **`df_original`**: The DataFrame state immediately after loading/querying, before UI sorts.
+/// - **`df_unfiltered`**: The result of the load/query pipeline, before the column filters (`column_filters`).
 /// - **`filter`**: Configuration used for *loading* the data (path, delimiter, SQL query, etc.). **Does NOT contain sorting information.**
 /// - **`format`**: Configuration for *displaying* the data (alignment, decimals, etc.).
 /// - **`sort`**: `Vec<SortBy>` defining the active sort order applied to `df`. An empty Vec means `df` reflects `df_original`.
@@ -49,6 +156,10 @@ pub struct DataContainer {
     /// Allows resetting the view efficiently.
     pub df_original: Arc<DataFrame>,
 
+    /// The result of the load/transform pipeline *before* the column filters
+    /// (`column_filters`), so filters can be loosened without re-running the SQL query.
+    pub df_unfiltered: Arc<DataFrame>,
+
     /// Detected file extension of the originally loaded data.
     pub extension: Arc<FileExtension>,
 
@@ -63,6 +174,55 @@ pub struct DataContainer {
     /// An empty vector signifies that `df` should be the same as `df_original`.
     /// Order in the vector determines sort precedence.
     pub sort: Vec<SortBy>,
+
+    /// The sort expression (see `SortExpression`) last applied to `df`, if any.
+    /// Reset when the data is reloaded/re-queried.
+    pub sort_expression: Option<SortExpression>,
+
+    /// Memory report of the String columns converted to Categorical during the last
+    /// load (see `DataFilter::categorical`). Empty if the conversion is disabled.
+    pub categorical_savings: Vec<CategoricalSaving>,
+
+    /// Boolean column whose `false` values highlight the row in the table
+    /// (e.g., `CNPJ_valid` added by the fiscal validators). `None` disables highlighting.
+    pub highlight_column: Option<String>,
+
+    /// Name of the SPED/EFD layout preset applied when the file was read
+    /// (`format-special` feature, see `sped_presets.rs`).
+    pub layout_preset: Option<String>,
+
+    /// Per-column filters typed in the filter row (see `ColumnFilters`), applied to
+    /// `df_unfiltered` to produce `df`. Cleared when a file is read.
+    pub column_filters: ColumnFilters,
+
+    /// Categories of the Categorical/Enum columns of `df_unfiltered` (see `column_categories`),
+    /// listed by the filter row. Computed on load, so sorts and filters do not change them.
+    pub categories: Arc<HashMap<String, Vec<String>>>,
+
+    /// The lazily scanned file when `filter.lazy_scan` is set (see `LazyTable`): `df`,
+    /// `df_original` and `df_unfiltered` then hold only the rows of the current page.
+    /// `None` when the whole file is loaded.
+    pub lazy: Option<LazyTable>,
+
+    /// The SQL query run on the Parquet scan (see `ParquetPushdown`): `df_original` then
+    /// holds the query result, with the pushable column filters applied (see
+    /// `ParquetPushdown::filters`). A change of the query, of the pushable filters or of the
+    /// load options they depend on reads the file again. `None` for other files and load
+    /// options.
+    pub pushdown: Option<ParquetPushdown>,
+
+    /// Notes and column descriptions of the file (see `annotations.rs`), read from its
+    /// sidecar when the file is read. Column descriptions are shown as header tooltips.
+    pub annotations: Arc<Annotations>,
+
+    /// Name of the row index column added by the last load (`DataFilter::index_column_name`,
+    /// made unique among the columns), `None` without it. Read by `key_columns`.
+    pub row_index_column: Option<PlSmallStr>,
+
+    /// The temporary file holding the data, when it was written for the readers (e.g., a
+    /// database query result, see `load_temp_file`). Shared by the containers derived from
+    /// this one (sorts, filters, reloads); the file is removed with the last of them.
+    pub temp_file: Option<Arc<TempPath>>,
 }
 
 // Default implementation initializes with an empty sort vector.
@@ -72,28 +232,91 @@ impl Default for DataContainer {
         let default_df = Arc::new(DataFrame::default());
         DataContainer {
             df: default_df.clone(),
-            df_original: default_df,
+            df_original: default_df.clone(),
+            df_unfiltered: default_df,
             extension: Arc::new(FileExtension::Missing),
             filter: Arc::new(DataFilter::default()), // Filters has no sort field
             format: Arc::new(DataFormat::default()),
             sort: Vec::new(), // Initialize sort as empty Vec
+            sort_expression: None,
+            categorical_savings: Vec::new(),
+            highlight_column: None,
+            layout_preset: None,
+            column_filters: ColumnFilters::new(),
+            categories: Arc::new(HashMap::new()),
+            lazy: None,
+            pushdown: None,
+            annotations: Arc::new(Annotations::default()),
+            row_index_column: None,
+            temp_file: None,
         }
     }
 }
 
+/// The rows displayed for the pipeline result `df`: the virtual columns of `format` added
+/// (see `add_virtual_columns`), the columns converted to their display units (see
+/// `convert_units`), then `column_filters` applied, so the filters see the displayed values.
+fn view_rows(
+    df: &DataFrame,
+    format: &DataFormat,
+    column_filters: &ColumnFilters,
+) -> PolarsViewResult<DataFrame> {
+    let df = add_virtual_columns(df, &format.virtual_columns)?;
+    let df = convert_units(&df, &format.column_units)?;
+    Ok(apply_column_filters(&df, column_filters)?)
+}
+
+/// Sorts `df` by the header sort `criteria` (column names, directions and null placement).
+/// The sort is stable: rows with equal keys keep their order in `df`.
+fn sort_by_criteria(df: &DataFrame, criteria: &[SortBy]) -> PolarsResult<DataFrame> {
+    let column_names: Vec<PlSmallStr> = criteria
+        .iter()
+        .map(|sort| sort.column_name.clone().into()) // PlSmallStr is efficient here
+        .collect();
+
+    let descending_flags: Vec<bool> = criteria.iter().map(|sort| !sort.ascending).collect();
+    let nulls_last_flags: Vec<bool> = criteria.iter().map(|sort| sort.nulls_last).collect();
+
+    // Set descending flags and nulls_last flags for multi-column sort.
+    let sort_options = SortMultipleOptions::default()
+        .with_order_descending_multi(descending_flags)
+        .with_nulls_last_multi(nulls_last_flags)
+        .with_maintain_order(true) // Maintain relative order of equal elements
+        .with_multithreaded(true);
+
+    df.sort(column_names, sort_options)
+}
+
 impl DataContainer {
+    /// Whether `format` computes other rows than the current format in `view_rows`:
+    /// different virtual columns or unit conversions.
+    fn changes_view_rows(&self, format: &DataFormat) -> bool {
+        format.virtual_columns != self.format.virtual_columns
+            || unit_conversions(&format.column_units) != unit_conversions(&self.format.column_units)
+    }
+
+    /// Whether `df` has the rows and columns of `df_unfiltered` (up to sorting): no column
+    /// filters nor virtual columns. Operations adding a column then share the result.
+    fn df_matches_unfiltered(&self) -> bool {
+        self.column_filters.is_empty()
+            && self.format.virtual_columns.is_empty()
+            && unit_conversions(&self.format.column_units).is_empty()
+    }
+
     /// Asynchronously prepares the initial DataFrame for processing.
     /// Reads from file if `filter.read_data_from_file` is true (validating path and updating self.extension, self.df_original),
     /// or clones data from `self.df_original` if false.
     ///
     /// ### Arguments:
     /// * `filter`: The DataFilter, modified (e.g. `read_data_from_file` reset).
+    /// * `pushed_filters`: The column filters run on the Parquet scan (see `pushdown`).
     ///
     /// ### Returns:
     /// * A DataFrame value representing the initial data to begin the transformation pipeline.
     async fn prepare_initial_dataframe(
         &mut self,               // Mutate self for initial load (extension, df_original)
         filter: &mut DataFilter, // Mutate filter (read_data_from_file)
+        pushed_filters: ColumnFilters,
     ) -> PolarsViewResult<DataFrame> {
         if filter.read_data_from_file {
             // --- Path Validation ---
@@ -103,7 +326,14 @@ impl DataContainer {
             }
 
             // --- Data Reading ---
-            let (new_df, extension) = filter.get_df_and_extension().await?; // Reads, may update filter.csv_delimiter
+            let (new_df, extension) = match self.pushdown.as_mut() {
+                // The query (and the pushable column filters) run on the scan.
+                Some(pushdown) => (
+                    pushdown.collect(pushed_filters).await?,
+                    FileExtension::Parquet,
+                ),
+                None => filter.get_df_and_extension().await?, // Reads, may update filter.csv_delimiter
+            };
             tracing::debug!(
                 "prepare_initial_dataframe: read data from file. Dims: {}x{}, Ext: {:?}, Delimiter: '{}'",
                 new_df.height(),
@@ -112,6 +342,20 @@ impl DataContainer {
                 filter.csv_delimiter
             );
 
+            // Apply the matching SPED/EFD layout preset (dtypes and names), if any.
+            #[cfg(feature = "format-special")]
+            let new_df = {
+                let preset = crate::find_preset(new_df.schema());
+                self.layout_preset = preset.as_ref().map(|preset| preset.name.clone());
+                match preset {
+                    Some(preset) => {
+                        tracing::info!("Applying layout preset: {}", preset.name);
+                        preset.apply(new_df)?
+                    }
+                    None => new_df,
+                }
+            };
+
             // Update self with the new data's initial state and extension
             self.extension = Arc::new(extension);
             self.df_original = Arc::new(new_df.clone()); // Store original DataFrame (deep copy data here)
@@ -140,20 +384,90 @@ impl DataContainer {
     /// 3. Reset `filter.apply_sql` flag if SQL transformation was included in the pipeline.
     /// 4. Execute the pipeline: Iterate through the `transformations` vector, calling `apply` on each, chaining the output DataFrame.
     /// 5. Update the final `filter.schema` based on the DataFrame's state after all transformations.
-    /// 6. Update `self.df`, `self.filter`, `self.format`, and reset `self.sort` with the results of this operation.
+    /// 6. Update `self.df` (with the column filters applied), `self.df_unfiltered`, `self.filter`, `self.format`,
+    ///    and reset `self.sort` with the results of this operation.
     /// 7. Return the modified `self`.
     pub async fn load_data(
         mut self,
         mut filter: DataFilter,
-        format: DataFormat,
+        mut format: DataFormat,
     ) -> PolarsViewResult<Self> {
+        // Lazy scan: only the first page is collected (see `load_lazy`).
+        if filter.lazy_scan && LazyTable::supports(&filter.absolute_path) {
+            return self.load_lazy(filter, format).await;
+        }
+        self.lazy = None;
+
+        let read_from_file = filter.read_data_from_file;
+
+        // Column filters refer to the columns of the previous file: reset them on read
+        // (before the scan, which runs the pushable ones).
+        if read_from_file {
+            self.column_filters.clear();
+        }
+
+        // 1a. Parquet: the SQL query runs on the scan if no load step needs the whole file
+        //     first (see `ParquetPushdown`). A pushed-down `df_original` is a query result,
+        //     not the file: it is reused if only the load steps after the query changed,
+        //     otherwise (or when leaving pushdown) the file is read again.
+        let mut pushed_filters = ColumnFilters::new();
+        let previous = self.pushdown.take();
+        if ParquetPushdown::supports(&filter) {
+            let (pushdown, reused) = match previous {
+                Some(pushdown) if !filter.read_data_from_file && pushdown.scans(&filter) => {
+                    (pushdown, true)
+                }
+                _ => (ParquetPushdown::scan(&filter)?, false),
+            };
+            pushed_filters = pushdown.pushable(&filter, &format, &self.column_filters);
+            if !reused || pushed_filters != pushdown.filters {
+                filter.read_data_from_file = true;
+            }
+            self.pushdown = Some(pushdown);
+        } else if previous.is_some() {
+            filter.read_data_from_file = true;
+        }
+
         // 1. Get Initial DataFrame value & Update self (df_original, extension)
-        let mut data_frame = self.prepare_initial_dataframe(&mut filter).await?;
+        if read_from_file {
+            report_stage(LoadStage::Reading, None);
+        }
+        let mut data_frame = self
+            .prepare_initial_dataframe(&mut filter, pushed_filters)
+            .await?;
+        check_cancelled()?;
+        report_stage(LoadStage::Sql, Some(data_frame.height()));
+
+        // 1b. A newly read file gets the decimals/alignments of its layout preset (if any).
+        #[cfg(feature = "format-special")]
+        if read_from_file {
+            format.column_formats = self
+                .layout_preset
+                .as_deref()
+                .and_then(crate::preset_by_name)
+                .map(|preset| preset.column_formats())
+                .unwrap_or_default();
+        }
 
         // 2. Build the Transformation Pipeline based on the filter configuration.
         // Create a vector of trait objects representing the transformations to apply.
         let mut transformations: Vec<Box<dyn DataFrameTransform + Send + Sync>> = Vec::new();
 
+        // Flatten Struct columns first (if flag is set), so the other steps see the flat names.
+        if filter.flatten_structs {
+            transformations.push(Box::new(FlattenStructsTransform));
+        }
+
+        // Column types picked in the type editor, before the steps that depend on the types.
+        if !filter.column_types.is_empty() {
+            transformations.push(Box::new(CastColumnTypesTransform));
+        }
+
+        // Column names edited in the Columns panel, before the query and the exports.
+        if !filter.column_renames.is_empty() {
+            transformations.push(Box::new(RenameColumnsTransform));
+        }
+
         // 2a. Drop/Remove Columns by (Regex) if flag is set
         if filter.drop {
             transformations.push(Box::new(DropColumnsTransform));
@@ -164,12 +478,22 @@ impl DataContainer {
             transformations.push(Box::new(NormalizeTransform));
         }
 
-        // 2c. Replace specific Values with Null
-        transformations.push(Box::new(ReplaceNullsTransform));
+        // Parse String Columns (Regex) as dates with the given format if flag is set
+        if filter.parse_dates {
+            transformations.push(Box::new(ParseDatesTransform));
+        }
+
+        // 2c. Replace specific Values with Null (done by the scan when pushed down)
+        if self.pushdown.is_none() {
+            transformations.push(Box::new(ReplaceNullsTransform));
+        }
 
-        // 2d. SQL Execution if flag is set
+        // 2d. SQL Execution if flag is set (the pushed-down query already ran on the scan)
+        let sql_applied = filter.apply_sql;
         if filter.apply_sql {
-            transformations.push(Box::new(SqlTransform));
+            if self.pushdown.is_none() {
+                transformations.push(Box::new(SqlTransform));
+            }
             filter.apply_sql = false; // Reset flag
         }
 
@@ -178,44 +502,380 @@ impl DataContainer {
             transformations.push(Box::new(RemoveNullColumnsTransform));
         }
 
-        // 2f. Add Row Index Column (Conditional) if flag is set
-        // This must run relatively late as its name conflict check uses the *current* schema.
-        if filter.add_row_index {
-            transformations.push(Box::new(AddRowIndexTransform));
-        }
-
         // 3. Execute the Pipeline: Apply each selected transformation sequentially.
         for transform in transformations {
+            check_cancelled()?; // A superseded load stops between stages.
             data_frame = transform.apply(data_frame, &filter)?;
         }
 
+        // 3a. Add Row Index Column (Conditional) if flag is set, after the other steps: its
+        //     name conflict check uses the *current* schema. The resolved name is recorded.
+        let row_index = filter.get_row_index(data_frame.schema())?;
+        self.row_index_column = row_index.as_ref().map(|row_index| row_index.name.clone());
+        data_frame = add_row_index_column(data_frame, row_index)?;
+
+        // 3b. Convert high-duplication String columns to Categorical (if flag is set).
+        // Not a `DataFrameTransform`, since the per-column memory report is kept for the Info panel.
+        self.categorical_savings = Vec::new();
+        if filter.categorical {
+            check_cancelled()?;
+            let (df, savings) = strings_to_categorical(data_frame, filter.categorical_ratio)?;
+            data_frame = df;
+            self.categorical_savings = savings;
+        }
+
         // 4. Update filter's `schema` with the final schema after all transformations are applied.
         filter.schema = data_frame.schema().clone();
 
+        // Only successfully applied queries are recorded in the history, with the
+        // dimensions of the result. A newly read file first recalls its saved history.
+        if read_from_file {
+            filter.query_history = QueryHistory::for_filter(&filter);
+        }
+        if sql_applied {
+            filter.add_query_to_history(data_frame.height(), data_frame.width());
+        }
+
         tracing::debug!("Load/transform pipeline successfully applied!");
         tracing::debug!("Final filter state after load: {:#?}", filter);
 
+        // 4b. The notes of a newly read file are read from its sidecar.
+        if read_from_file {
+            let annotations = Annotations::load(&filter.absolute_path).unwrap_or_else(|error| {
+                tracing::warn!("Failed to read the notes of the file: {error}");
+                Annotations::default()
+            });
+            self.annotations = Arc::new(annotations);
+        }
+
+        // 4c. Decimal places inferred from the data (if `auto_decimal`).
+        format.infer_decimals_from(&data_frame);
+
+        // 4d. Categories of the Categorical/Enum columns, for the filter row.
+        self.categories = Arc::new(categories_by_column(&data_frame)?);
+
         // 5. Update self fields with the final results.
-        self.df = Arc::new(data_frame);
+        self.df = Arc::new(view_rows(&data_frame, &format, &self.column_filters)?);
+        self.df_unfiltered = Arc::new(data_frame);
         self.filter = Arc::new(filter);
         self.format = Arc::new(format);
         self.sort = Vec::new();
+        self.sort_expression = None;
 
         // 6. Return the modified container value.
         Ok(self)
     }
 
+    /// Loads the temporary file `temp_file` (see `temp_files.rs`) with `load_data`, keeping
+    /// it until the last container derived from the result is dropped.
+    pub async fn load_temp_file(
+        mut self,
+        temp_file: TempPath,
+        mut filter: DataFilter,
+        format: DataFormat,
+    ) -> PolarsViewResult<Self> {
+        filter.set_path(&temp_file)?;
+        filter.read_data_from_file = true;
+        self.temp_file = Some(Arc::new(temp_file));
+        self.load_data(filter, format).await
+    }
+
+    /// Reads the file again (e.g., after it changed on disk, see `file_watch.rs`, or on
+    /// "Reload"/F5) with the load options and SQL query of `filter` and the `format`, then
+    /// restores the column filters, the sort expression and the header sort criteria of
+    /// the columns still present.
+    pub async fn reload(
+        self,
+        mut filter: DataFilter,
+        format: DataFormat,
+    ) -> PolarsViewResult<Self> {
+        let column_filters = self.column_filters.clone();
+        let sort_expression = self.sort_expression.clone();
+        let sort = self.sort.clone();
+
+        filter.read_data_from_file = true;
+        filter.apply_sql = true;
+
+        let mut container = self.load_data(filter, format).await?;
+        if container.lazy.is_some() {
+            return Ok(container); // Lazy scans are not filtered or sorted in memory.
+        }
+
+        let columns = container.df.schema().clone();
+        let column_filters = column_filters
+            .into_iter()
+            .filter(|(name, _text)| name == ANY_COLUMN || columns.contains(name))
+            .collect();
+        container.sort_expression = sort_expression;
+        container.sort = sort
+            .into_iter()
+            .filter(|sort| columns.contains(&sort.column_name))
+            .collect();
+        container.apply_column_filters(column_filters).await
+    }
+
+    /// Lazy variant of `load_data` (`filter.lazy_scan`): scans the file, applies the SQL
+    /// query and collects only the first page into `df` (the table is always `paged`).
+    ///
+    /// The other load options (row number, normalization, null values, column removal,
+    /// categorical conversion) need the whole data and are not applied.
+    async fn load_lazy(
+        mut self,
+        mut filter: DataFilter,
+        mut format: DataFormat,
+    ) -> PolarsViewResult<Self> {
+        if !filter.absolute_path.exists() {
+            tracing::error!("load_lazy: File not found: {:?}", filter.absolute_path);
+            return Err(PolarsViewError::FileNotFound(filter.absolute_path.clone()));
+        }
+
+        let (mut table, extension) = LazyTable::scan(&filter).await?;
+        tracing::debug!("load_lazy: {} rows, {:?}", table.total_rows, extension);
+
+        format.paged = true;
+        let rows = format.page_rows(0, table.total_rows);
+        let data_frame = table.window(rows.start, rows.len()).await?;
+
+        if filter.read_data_from_file {
+            filter.query_history = QueryHistory::for_filter(&filter);
+        }
+        if filter.apply_sql {
+            filter.apply_sql = false;
+            filter.add_query_to_history(table.total_rows, data_frame.width());
+        }
+        filter.read_data_from_file = false;
+        filter.schema = data_frame.schema().clone();
+        format.infer_decimals_from(&data_frame);
+        self.categories = Arc::new(categories_by_column(&data_frame)?);
+
+        self.column_filters.clear();
+        self.df = Arc::new(view_rows(&data_frame, &format, &self.column_filters)?);
+        let data_frame = Arc::new(data_frame);
+        self.df_original = data_frame.clone();
+        self.df_unfiltered = data_frame;
+        self.extension = Arc::new(extension);
+        self.filter = Arc::new(filter);
+        self.format = Arc::new(format);
+        self.sort = Vec::new();
+        self.sort_expression = None;
+        self.categorical_savings = Vec::new();
+        self.lazy = Some(table);
+        self.pushdown = None;
+        self.row_index_column = None;
+
+        Ok(self)
+    }
+
+    /// Asynchronously creates a *new* `DataContainer` with page `page` (0-based, see
+    /// `DataFormat::page_rows`) of the lazily scanned file collected into `df`.
+    /// The column filters are re-applied to the new rows. Does nothing if not in lazy mode.
+    ///
+    /// Triggered by `layout.rs` when the page navigation of the status bar changes page.
+    pub async fn load_lazy_page(mut self, page: usize) -> PolarsViewResult<Self> {
+        let Some(mut table) = self.lazy.take() else {
+            return Ok(self);
+        };
+
+        let rows = self.format.page_rows(page, table.total_rows);
+        report_stage(LoadStage::Reading, Some(rows.len()));
+        let data_frame = table.window(rows.start, rows.len()).await?;
+        tracing::debug!("load_lazy_page: page {page}, rows {rows:?}");
+
+        self.df = Arc::new(view_rows(&data_frame, &self.format, &self.column_filters)?);
+        let data_frame = Arc::new(data_frame);
+        self.df_original = data_frame.clone();
+        self.df_unfiltered = data_frame;
+        self.sort_expression = None;
+        self.lazy = Some(table);
+
+        Ok(self)
+    }
+
+    /// Number of rows of the data: of the whole query result in lazy mode (`lazy`),
+    /// otherwise of `df`.
+    pub fn total_rows(&self) -> usize {
+        match &self.lazy {
+            Some(table) => table.total_rows,
+            None => self.df.height(),
+        }
+    }
+
+    /// Checks that `df` holds all the rows of the data, so it can be saved or exported: not
+    /// with a lazy scan (`lazy`), where it holds only the rows of the current page.
+    pub fn check_exportable(&self) -> PolarsViewResult<()> {
+        match &self.lazy {
+            Some(table) => Err(PolarsViewError::Other(format!(
+                "\"Lazy Scan\" reads one page at a time ({} of {} rows): turn it off to save or export the data.",
+                self.df.height(),
+                table.total_rows
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that the data can be written back to its file ("Save"): not in preview mode
+    /// (`DataFilter::head_rows`), where the rows after the limit were not read.
+    pub fn check_source_saveable(&self) -> PolarsViewResult<()> {
+        self.check_exportable()?;
+        match self.filter.head_rows {
+            Some(rows) => Err(PolarsViewError::Other(format!(
+                "Preview mode reads only the first {rows} rows: saving would truncate the file. Load the full file, or use \"Save As...\"."
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Offers to flatten the data if the file was read as a single Struct column (e.g., a
+    /// JSON array of nested objects, see `is_single_struct`) and is not flattened yet.
+    ///
+    /// Returns `true` if "Flatten" was clicked (see `DataFilter::flatten_structs`).
+    pub fn render_flatten_offer(&self, ui: &mut Ui) -> bool {
+        if self.filter.flatten_structs || !is_single_struct(&self.df_original) {
+            return false;
+        }
+
+        let mut flatten = false;
+        ui.horizontal(|ui| {
+            ui.label("This file holds a single nested (Struct) column.");
+            flatten = ui
+                .button("Flatten")
+                .on_hover_text(
+                    "Show one column per field, named with dots (e.g., `customer.address.city`).",
+                )
+                .clicked();
+        });
+        ui.separator();
+        flatten
+    }
+
+    /// The row limit of the preview mode (`DataFilter::head_rows`), if the file was read
+    /// with it and has at least that many rows (so rows may be missing).
+    pub fn preview_rows(&self) -> Option<usize> {
+        self.filter
+            .head_rows
+            .filter(|&rows| self.lazy.is_none() && self.df_original.height() >= rows)
+    }
+
+    /// Returns a copy of this container with the display settings `format`, if the change
+    /// is a pure view change (alignment, decimals, hidden, frozen or reordered columns,
+    /// row numbers, ...). The data is shared (`Arc`), so this is cheap enough for the UI
+    /// thread: `layout.rs` applies such changes at once, without the async pipeline.
+    ///
+    /// Returns `None` if the change needs `update_format`: `auto_decimal` was toggled
+    /// (decimal places are inferred from the data), the virtual columns or unit conversions
+    /// changed (see `changes_view_rows`), or the paging of a lazy scan changed.
+    ///
+    /// This is not a separate view state: the display settings stay in the container's
+    /// `format` (the table and the exports read it), so a view change still builds a new
+    /// `DataContainer`, only without the async pipeline. Moving them to a struct held by
+    /// `PolarsViewApp` is not done.
+    pub fn with_format(&self, format: DataFormat) -> Option<Self> {
+        if format.auto_decimal != self.format.auto_decimal || self.changes_view_rows(&format) {
+            return None;
+        }
+        if self.lazy.is_some()
+            && (!format.paged || format.rows_per_page != self.format.rows_per_page)
+        {
+            return None;
+        }
+
+        let mut container = self.clone();
+        container.format = Arc::new(format);
+        Some(container)
+    }
+
     /// Asynchronously creates a *new* `DataContainer` with updated format settings.
     /// Preserves the existing data (`df`, `df_original`) and sort criteria (`sort`).
     ///
-    /// Triggered by `layout.rs` when format UI elements change. This is a very fast operation.
+    /// Triggered by `layout.rs` when format UI elements change. This is a very fast operation,
+    /// unless the virtual columns or unit conversions changed: `df` is then derived again
+    /// from `df_unfiltered`
+    /// (see `apply_column_filters`), without the sort criteria and column filters of the
+    /// removed virtual columns.
     pub async fn update_format(
         mut self,
-        format: DataFormat, // NEW format settings
+        mut format: DataFormat, // NEW format settings
     ) -> PolarsViewResult<Self> {
+        // Toggling `auto_decimal` (re)computes or clears the inferred decimal places.
+        if format.auto_decimal != self.format.auto_decimal {
+            format.infer_decimals_from(&self.df_unfiltered);
+        }
+
+        // Lazy scan: the table stays paged; a new page size reloads the page of the first row.
+        let mut reload_page = None;
+        if let Some(table) = &self.lazy {
+            format.paged = true;
+            if format.rows_per_page != self.format.rows_per_page {
+                reload_page = Some(format.page_of_row(table.offset));
+            }
+        }
+
+        let view_changed = self.changes_view_rows(&format);
+        if view_changed {
+            let kept = |name: &str| {
+                self.df_unfiltered.schema().contains(name)
+                    || format
+                        .virtual_columns
+                        .iter()
+                        .any(|column| column.name == name)
+            };
+            self.sort.retain(|sort| kept(&sort.column_name));
+            self.column_filters
+                .retain(|name, _text| name == ANY_COLUMN || kept(name));
+        }
+
         tracing::debug!("update_format: Updating format to {:#?}", format);
         self.format = Arc::new(format); // update format
 
+        match reload_page {
+            Some(page) => self.load_lazy_page(page).await,
+            None if view_changed => {
+                let column_filters = self.column_filters.clone();
+                self.apply_column_filters(column_filters).await
+            }
+            None => Ok(self),
+        }
+    }
+
+    /// Asynchronously creates a *new* `DataContainer` with `df` filtered by `column_filters`
+    /// (see `column_filter.rs`), starting from `df_unfiltered` with the virtual columns of
+    /// `format` (see `view_rows`). The active sort expression and
+    /// header sort criteria are re-applied to the filtered rows.
+    ///
+    /// Triggered by `layout.rs` when a filter in the filter row under the header is changed.
+    pub async fn apply_column_filters(
+        mut self,
+        column_filters: ColumnFilters,
+    ) -> PolarsViewResult<Self> {
+        tracing::debug!("apply_column_filters: {:#?}", column_filters);
+        report_stage(LoadStage::Filtering, Some(self.df_unfiltered.height()));
+
+        // Parquet pushdown: other pushable filters run on a new scan (see `load_data`).
+        if let Some(pushdown) = &self.pushdown
+            && pushdown.pushable(&self.filter, &self.format, &column_filters) != pushdown.filters
+        {
+            let (filter, format) = (self.filter.as_ref().clone(), self.format.as_ref().clone());
+            let (sort, sort_expression) =
+                (std::mem::take(&mut self.sort), self.sort_expression.take());
+            self.column_filters = column_filters.clone();
+            self = self.load_data(filter, format).await?;
+            self.sort = sort;
+            self.sort_expression = sort_expression;
+        }
+
+        let mut df = view_rows(&self.df_unfiltered, &self.format, &column_filters)?;
+        if let Some(sort_expression) = &self.sort_expression {
+            df = sort_expression.sort(&df)?;
+        }
+
+        self.df = Arc::new(df);
+        self.column_filters = column_filters;
+
+        if !self.sort.is_empty() {
+            let sort = std::mem::take(&mut self.sort);
+            self = self.apply_sort(sort).await?;
+        }
+
         Ok(self)
     }
 
@@ -225,332 +885,1548 @@ impl DataContainer {
     /// Triggered by `layout.rs` after a user clicks a sortable header, resulting in new criteria.
     /// Handles multi-column sorting based on the order, direction, and nulls_last settings
     /// in `new_sort_criteria`.
-    /// If `new_sort_criteria` is empty, it resets the view by setting `df` to `df_original`.
+    /// If `new_sort_criteria` is empty, it resets the view to the row order of the data
+    /// (the sort expression is cleared too).
     ///
     /// ## Logic & State Update:
-    /// 1. Check if `new_sort_criteria` is empty.
-    /// 2. **Handle Empty (Reset):** If empty, create new container:
-    ///    *   `df`: Cloned `Arc` of the input `df_original`.
-    ///    *   `df_original`: Cloned `Arc` of the input `df_original`.
-    ///    *   `sort`: The empty `new_sort_criteria` vector.
-    ///    *   Other fields cloned from input container.
-    /// 3. **Handle Non-Empty (Apply Sort):** If not empty:
-    ///    a. Extract column names, descending flags, and **nulls_last flags** from `new_sort_criteria`.
-    ///    b. Configure Polars `SortMultipleOptions`.
-    ///    c. Call `data_container.df.sort()` using the *currently displayed* df as input.
-    ///    d. Create new container:
-    ///    *   `df`: *New* `Arc` wrapping the **sorted** DataFrame.
-    ///    *   `df_original`: *Cloned* `Arc` of the **input** `df_original`.
-    ///    *   `sort`: The `new_sort_criteria` that *caused* this sort.
-    ///    *   Other fields cloned from input container.
-    /// 4. Return `Ok(new_container)`.
+    /// 1. **Lazy scan:** the criteria sort the scan, and the current page is read again.
+    /// 2. Otherwise, `sort` is set to `new_sort_criteria`, and `df` is rebuilt from
+    ///    `df_unfiltered` (see `displayed`): the column filters, then the sort expression
+    ///    and the header criteria (see `sort_by_criteria`). Starting from `df_unfiltered`
+    ///    keeps the cell edits applied to it (see `with_cell_edits`).
+    /// 3. Return `Ok(new_container)`.
     pub async fn apply_sort(
         mut self,                       // Current container state
         new_sort_criteria: Vec<SortBy>, // The *desired* new sort state
     ) -> PolarsViewResult<Self> {
+        // Lazy scan: the whole query is sorted (lazily) and the current page is reloaded.
+        if let Some(table) = &self.lazy {
+            let page = self.format.page_of_row(table.offset);
+            self.lazy = Some(table.sorted(&new_sort_criteria));
+            self.sort = new_sort_criteria;
+            return self.load_lazy_page(page).await;
+        }
+        report_stage(LoadStage::Sorting, Some(self.df.height()));
+
+        // The rows are sorted from the filtered rows of `df_unfiltered` (see `displayed`),
+        // so the order depends on the criteria only, not on the previous sorts.
         if new_sort_criteria.is_empty() {
-            // --- 2. Handle Empty (Reset) ---
+            tracing::debug!("apply_sort: Sort criteria list is empty. Resetting the row order.");
+            self.sort_expression = None;
+        } else {
             tracing::debug!(
-                "apply_sort: Sort criteria list is empty. Resetting df to df_original."
+                "apply_sort: Applying cumulative sort. Criteria: {:#?}",
+                new_sort_criteria
             );
+        }
+        self.sort = new_sort_criteria; // Store the criteria that produced this state
+        self.df = Arc::new(self.displayed(&self.df_unfiltered, None)?);
 
-            // Get filter and format
-            let format = self.format.as_ref().clone();
-            let mut filter = self.filter.as_ref().clone();
-            filter.apply_sql = true;
-
-            // Apply transformations
-            // self.sort = Vec::new(); // Store the empty Vec as the current state
-            self = self.load_data(filter, format).await?;
+        Ok(self)
+    }
 
-            return Ok(self);
-        }
+    /// Asynchronously creates a *new* `DataContainer` with `df` sorted by an arbitrary
+    /// SQL expression (see `SortExpression`).
+    ///
+    /// Triggered by `layout.rs` from the "Sort" side panel section. Header sort criteria
+    /// are cleared, since the displayed order no longer corresponds to them.
+    pub async fn apply_sort_expression(
+        mut self,
+        sort_expression: SortExpression,
+    ) -> PolarsViewResult<Self> {
+        tracing::debug!("apply_sort_expression: {:#?}", sort_expression);
+        report_stage(LoadStage::Sorting, Some(self.df.height()));
 
-        // --- 3. Handle Non-Empty (Apply Sort) ---
-        tracing::debug!(
-            "apply_sort: Applying cumulative sort. Criteria: {:#?}",
-            new_sort_criteria
-        );
+        self.sort = Vec::new();
+        self.sort_expression = Some(sort_expression);
+        self.df = Arc::new(self.displayed(&self.df_unfiltered, None)?);
 
-        // 3a. Extract sort parameters
-        let column_names: Vec<PlSmallStr> = new_sort_criteria
-            .iter()
-            .map(|sort| sort.column_name.clone().into()) // PlSmallStr is efficient here
-            .collect();
+        Ok(self)
+    }
 
-        let descending_flags: Vec<bool> = new_sort_criteria
-            .iter()
-            .map(|sort| !sort.ascending)
-            .collect();
+    /// Asynchronously loads/transforms data with the settings of a `ViewState`:
+    /// `filter` is updated with the view's load/query settings, then the view's
+    /// column filters, sort expression and header sort criteria are applied (in this order).
+    ///
+    /// Used by "File > Import View..." (`layout.rs`) and the `--view` CLI argument (`main.rs`).
+    pub async fn load_with_view_state(
+        self,
+        mut filter: DataFilter,
+        view: ViewState,
+    ) -> PolarsViewResult<Self> {
+        view.apply_to_filter(&mut filter);
 
-        let nulls_last_flags: Vec<bool> = new_sort_criteria
-            .iter()
-            .map(|sort| sort.nulls_last)
-            .collect();
+        let mut container = self.load_data(filter, view.to_format()).await?;
 
-        // 3b. Configure Polars Sort Options
-        // Set descending flags and **nulls_last flags** for multi-column sort.
-        let sort_options = SortMultipleOptions::default()
-            .with_order_descending_multi(descending_flags)
-            .with_nulls_last_multi(nulls_last_flags) // Use the extracted flags
-            .with_maintain_order(true) // Maintain relative order of equal elements
-            .with_multithreaded(true);
+        if !view.column_filters.is_empty() {
+            container = container.apply_column_filters(view.column_filters).await?;
+        }
 
-        // 3c. Perform Sorting on the *current* df
-        // NOTE: Sorting based on the *new cumulative* criteria.
-        let df_sorted = self.df.sort(column_names, sort_options)?;
-        tracing::debug!("apply_sort: Polars multi-column sort successful.");
+        if let Some(sort_expression) = view.sort_expression
+            && !sort_expression.is_empty()
+        {
+            container = container.apply_sort_expression(sort_expression).await?;
+        }
 
-        self.df = Arc::new(df_sorted); // Use the newly sorted DataFrame
-        self.sort = new_sort_criteria; // Store the criteria that produced this state
+        if !view.sort.is_empty() {
+            container = container.apply_sort(view.sort).await?;
+        }
 
-        // 3d. Create New Container with sorted data and new criteria
-        Ok(self)
+        Ok(container)
     }
 
-    // --- UI Rendering Methods ---
+    /// Estimated memory of the data held by this container (`df`, `df_original` and
+    /// `df_unfiltered`, each distinct `DataFrame` counted once), in bytes.
+    ///
+    /// Buffers shared between these `DataFrame`s are counted once per `DataFrame`,
+    /// so this is an upper bound.
+    pub fn estimated_memory(&self) -> usize {
+        let mut frames = vec![&self.df];
+        for df in [&self.df_original, &self.df_unfiltered] {
+            if !frames.iter().any(|seen| Arc::ptr_eq(seen, df)) {
+                frames.push(df);
+            }
+        }
+        frames.iter().map(|df| df.estimated_size()).sum()
+    }
 
-    /// Renders the main data table using `egui_extras::TableBuilder`.
-    /// Handles sort interactions via `render_table_header`.
-    ///
-    /// Returns `Some(new_sort_criteria)` if a header click requires a sort state update.
-    pub fn render_table(&self, ui: &mut Ui) -> Option<Vec<SortBy>> {
-        // Variable to capture the new sort criteria if a header is clicked.
-        let mut updated_sort_criteria: Option<Vec<SortBy>> = None;
-
-        // Closure to render the header row. Captures `self` and the output Option.
-        let analyze_header = |mut table_row: TableRow<'_, '_>| {
-            self.render_table_header(
-                &mut table_row,
-                &mut updated_sort_criteria, // Pass mutable ref to capture signal
-            );
+    /// Asynchronously creates a *new* `DataContainer` with compacted memory:
+    /// `df` and `df_original` are rechunked (one chunk per column) and over-allocated
+    /// buffers are shrunk.
+    ///
+    /// Triggered by `layout.rs` from the "Compact memory" button in the "Info" panel.
+    /// Useful after heavy filtering, as contiguous columns speed up later sorts and scans.
+    pub async fn compact_memory(mut self) -> PolarsViewResult<Self> {
+        let size_before = self.df.estimated_size() + self.df_original.estimated_size();
+
+        let compact = |df: &DataFrame| {
+            let mut df = df.clone();
+            df.as_single_chunk_par();
+            df.shrink_to_fit();
+            Arc::new(df)
         };
 
-        // Closure to render data rows.
-        let analyze_rows = |mut table_row: TableRow<'_, '_>| {
-            self.render_table_row(&mut table_row);
+        self.df = compact(&self.df);
+        self.df_original = compact(&self.df_original);
+        self.df_unfiltered = if self.df_matches_unfiltered() {
+            self.df.clone()
+        } else {
+            compact(&self.df_unfiltered)
         };
 
-        // Configure and build the table.
-        self.build_configured_table(ui, analyze_header, analyze_rows);
+        tracing::debug!(
+            "compact_memory: estimated size {} -> {} bytes",
+            size_before,
+            self.df.estimated_size() + self.df_original.estimated_size()
+        );
 
-        // Return the signal from header interactions.
-        updated_sort_criteria
+        Ok(self)
     }
 
-    /// Renders the header row, creating clickable cells for sorting.
-    /// Reads the current sort state (`self.sort`), including nulls_last. On click,
-    /// calculates the *next* sort state (cycling through 4 sorted states + NotSorted),
-    /// modifies a *cloned* sort criteria `Vec`, and signals this *new `Vec`* back
-    /// via the `sort_signal` output parameter.
+    /// Asynchronously creates a *new* `DataContainer` with its String columns converted to
+    /// numbers, booleans, dates or datetimes when all their values parse as such
+    /// (see `infer_string_dtypes`), e.g. after a SQL query or text transforms.
     ///
-    /// ### Arguments
-    /// * `table_row`: Egui context for the header row.
-    /// * `sort_signal`: Output parameter (`&mut Option<Vec<SortBy>>`). Set to `Some(new_criteria)`
-    ///   if a click occurred that requires updating the sort state.
-    fn render_table_header(
-        &self,
-        table_row: &mut TableRow<'_, '_>,
-        sort_signal: &mut Option<Vec<SortBy>>,
-    ) {
-        for column_name in self.df.get_column_names() {
-            table_row.col(|ui| {
-                // 1. Determine current interaction state based on `ascending` and `nulls_last`.
-                let (current_interaction_state, sort_index) = self
-                    .sort
-                    .iter()
-                    .position(|criterion| criterion.column_name == *column_name)
-                    .map_or((HeaderSortState::NotSorted, None), |index| {
-                        let criterion = &self.sort[index];
-                        // ** Map to the correct 4-state enum based on both bools **
-                        let state = match (criterion.ascending, criterion.nulls_last) {
-                            (false, false) => HeaderSortState::DescendingNullsFirst,
-                            (true, false) => HeaderSortState::AscendingNullsFirst,
-                            (false, true) => HeaderSortState::DescendingNullsLast,
-                            (true, true) => HeaderSortState::AscendingNullsLast,
-                        };
-                        (state, Some(index))
-                    });
+    /// Types are inferred from `df_unfiltered` (all rows), then applied to `df` and
+    /// `df_original`. The schema and the inferred decimal places are updated.
+    ///
+    /// Triggered by `layout.rs` from the "Re-infer types" button in the "Info" panel.
+    pub async fn reinfer_types(mut self) -> PolarsViewResult<Self> {
+        let dtypes = infer_string_dtypes(&self.df_unfiltered);
+        tracing::info!("reinfer_types: {:?}", dtypes);
 
-                // 2. Render the sortable header widget (uses the new state and get_icon).
-                let response = ui.render_sortable_header(
-                    column_name,
-                    &current_interaction_state,
-                    sort_index, // Pass index for display (e.g., "1▼")
-                    self.format.use_enhanced_header,
-                );
+        if dtypes.is_empty() {
+            return Ok(self);
+        }
 
-                // 3. Handle Click Response.
-                if response.clicked() {
-                    tracing::debug!(
-                        "Header clicked: '{}'. Current state: {:?}, Index: {:?}",
-                        column_name,
-                        current_interaction_state,
-                        sort_index
-                    );
-                    // Calculate the next state in the 5-state cycle
-                    let next_interaction_state = current_interaction_state.cycle_next();
-                    tracing::debug!("Next interaction state: {:#?}", next_interaction_state);
-
-                    // 4. Prepare the *new* list of sort criteria based on the click outcome.
-                    let mut new_sort_criteria = self.sort.clone(); // Start with current criteria
-                    let column_name_string = column_name.to_string();
-                    let current_pos = new_sort_criteria
-                        .iter()
-                        .position(|c| c.column_name == *column_name);
-
-                    // 5. Modify the cloned vector based on the next interaction state.
-                    match next_interaction_state {
-                        HeaderSortState::NotSorted => {
-                            // Remove the sort criterion for this column if it exists.
-                            if let Some(pos) = current_pos {
-                                new_sort_criteria.remove(pos);
-                            }
-                        }
-                        // Handle the 4 sorted states: update existing or add new.
-                        _ => {
-                            // ** Determine new ascending and nulls_last from the next state **
-                            let (new_ascending, new_nulls_last) = match next_interaction_state {
-                                HeaderSortState::DescendingNullsFirst => (false, false),
-                                HeaderSortState::AscendingNullsFirst => (true, false),
-                                HeaderSortState::DescendingNullsLast => (false, true),
-                                HeaderSortState::AscendingNullsLast => (true, true),
-                                // NotSorted case is handled above, this is exhaustive for sorted states
-                                HeaderSortState::NotSorted => {
-                                    unreachable!("NotSorted case already handled")
-                                }
-                            };
+        let cast = |df: &DataFrame| -> PolarsViewResult<Arc<DataFrame>> {
+            Ok(Arc::new(cast_string_columns(df.clone(), &dtypes)?))
+        };
 
-                            if let Some(pos) = current_pos {
-                                // Update existing criterion in place.
-                                new_sort_criteria[pos].ascending = new_ascending;
-                                new_sort_criteria[pos].nulls_last = new_nulls_last;
-                            } else {
-                                // Add new criterion to the end of the vector.
-                                new_sort_criteria.push(SortBy {
-                                    column_name: column_name_string,
-                                    ascending: new_ascending,
-                                    nulls_last: new_nulls_last,
-                                });
-                            }
-                        }
-                    } // end match next_interaction_state
+        // Keep the `DataFrame`s shared when they are the same (e.g., no sort nor filters).
+        let df_unfiltered = cast(&self.df_unfiltered)?;
+        let share_or_cast = |df: &Arc<DataFrame>| -> PolarsViewResult<Arc<DataFrame>> {
+            if Arc::ptr_eq(df, &self.df_unfiltered) {
+                Ok(df_unfiltered.clone())
+            } else {
+                cast(df)
+            }
+        };
+        self.df = share_or_cast(&self.df)?;
+        self.df_original = share_or_cast(&self.df_original)?;
+        self.df_unfiltered = df_unfiltered;
 
-                    tracing::debug!(
-                        "Signaling new sort criteria for async update: {:#?}",
-                        new_sort_criteria
-                    );
+        let mut filter = self.filter.as_ref().clone();
+        filter.schema = self.df_unfiltered.schema().clone();
+        self.filter = Arc::new(filter);
 
-                    // 6. Set the output parameter to signal the required action and the new sort state.
-                    *sort_signal = Some(new_sort_criteria);
-                } // end if response.clicked()
-            }); // End cell definition
-        } // End loop over columns
+        let mut format = self.format.as_ref().clone();
+        format.infer_decimals_from(&self.df_unfiltered);
+        self.format = Arc::new(format);
+
+        Ok(self)
     }
 
-    /// Renders a single data row in the table body.
-    ///
-    /// Called by the `analyze_rows` closure (defined in `render_table`) for each row index
-    /// provided by the `egui_extras::TableBuilder`.
+    /// Asynchronously creates a *new* `DataContainer` with a converted-amount column
+    /// (`CurrencyConversion::output_column`) appended to `df`, using the rates read from
+    /// the CSV file at `rates_path` (see `RateTable`).
     ///
-    /// For each cell in the row:
-    /// 1. Calls `get_decimal_and_layout` (using `self.format`) to determine the `egui::Layout` (for alignment)
-    ///    and `Option<usize>` (for decimal places, if applicable) based on the column's `DataType`.
-    /// 2. Calls `Self::format_cell_value` to retrieve the `AnyValue` from the DataFrame and format it
-    ///    into a `String`, applying decimal rounding if needed.
-    /// 3. Adds a cell to the `egui` row (`table_row.col`) and renders the formatted string as a `Label`
-    ///    within the determined `Layout`.
+    /// The column is also appended to `df_original` (when it has the source columns), so it
+    /// survives header sorts and can be used in SQL queries until the file is re-read.
     ///
-    /// ### Arguments
-    /// * `table_row`: The `egui_extras::TableRow` context providing the `row_index` and cell adding methods.
-    fn render_table_row(&self, table_row: &mut TableRow<'_, '_>) {
-        let row_index = table_row.index(); // Get the 0-based data row index.
+    /// Triggered by `layout.rs` from the "Currency Conversion" dialog.
+    pub async fn convert_currency(
+        mut self,
+        rates_path: PathBuf,
+        conversion: CurrencyConversion,
+    ) -> PolarsViewResult<Self> {
+        let rates = RateTable::read_csv(&rates_path).await?;
 
-        // Iterate through each column (Polars Series) in the DataFrame.
-        for column_series in self.df.columns() {
-            // Determine alignment and decimal places using the feature-flagged helper.
-            // Passes the Series and the current format settings Arc.
-            let (opt_decimal, layout) = get_decimal_and_layout(column_series, &self.format);
+        if rates.is_empty() {
+            return Err(PolarsViewError::Other(format!(
+                "No valid rates found in {}",
+                rates_path.display()
+            )));
+        }
 
-            // Get the raw AnyValue and format it into a display String.
-            let value_str = self.format_cell_value(column_series, row_index, opt_decimal);
+        let (df, unmatched) =
+            append_converted_column(self.df.as_ref().clone(), &rates, &conversion)?;
+        tracing::info!(
+            "convert_currency: {} rates loaded; {} of {} rows without a matching rate",
+            rates.len(),
+            unmatched,
+            df.height()
+        );
+        self.df = Arc::new(df);
 
-            // Add a cell to the egui row.
-            table_row.col(|ui| {
-                // Apply the determined layout (alignment) to the cell content. Prevent wrapping.
-                ui.with_layout(layout.with_main_wrap(false), |ui| {
-                    ui.label(value_str); // Display the formatted value.
-                });
+        // With column filters, `df_unfiltered` also needs the column (to loosen the filters later).
+        self.df_unfiltered = if self.df_matches_unfiltered() {
+            self.df.clone()
+        } else {
+            let (df_unfiltered, _) =
+                append_converted_column(self.df_unfiltered.as_ref().clone(), &rates, &conversion)?;
+            Arc::new(df_unfiltered)
+        };
+
+        if let Ok((df_original, _)) =
+            append_converted_column(self.df_original.as_ref().clone(), &rates, &conversion)
+        {
+            self.df_original = Arc::new(df_original);
+        }
+
+        Ok(self)
+    }
+
+    /// Asynchronously creates a *new* `DataContainer` with a `<column_name>_valid` Boolean
+    /// column (check digit validation of CPF/CNPJ/NF-e keys, see `add_validity_column`)
+    /// appended to `df` and `df_original`. Rows with invalid values are highlighted.
+    ///
+    /// Triggered by `layout.rs` from the "Validators" side panel section.
+    #[cfg(feature = "format-special")]
+    pub async fn validate_fiscal_document(
+        mut self,
+        column_name: String,
+        document: crate::FiscalDocument,
+    ) -> PolarsViewResult<Self> {
+        let (df, invalid) =
+            crate::add_validity_column(self.df.as_ref().clone(), &column_name, document)?;
+        tracing::info!(
+            "validate_fiscal_document: {} invalid {} values in `{}`",
+            invalid,
+            document.label(),
+            column_name
+        );
+        self.df = Arc::new(df);
+
+        // With column filters, `df_unfiltered` also needs the column (to loosen the filters later).
+        self.df_unfiltered = if self.df_matches_unfiltered() {
+            self.df.clone()
+        } else {
+            let (df_unfiltered, _) = crate::add_validity_column(
+                self.df_unfiltered.as_ref().clone(),
+                &column_name,
+                document,
+            )?;
+            Arc::new(df_unfiltered)
+        };
+
+        if let Ok((df_original, _)) =
+            crate::add_validity_column(self.df_original.as_ref().clone(), &column_name, document)
+        {
+            self.df_original = Arc::new(df_original);
+        }
+
+        self.highlight_column = Some(format!("{column_name}{}", crate::VALIDITY_SUFFIX));
+
+        Ok(self)
+    }
+
+    /// Describes the operations baked into `df` relative to the source file, in pipeline order.
+    ///
+    /// Used to show what a "Save" to the original path would write.
+    /// Returns an empty vector if `df` holds the file contents as loaded.
+    pub fn applied_operations(&self) -> Vec<String> {
+        let filter = &self.filter;
+        let mut operations = Vec::new();
+
+        if let Some(table) = &self.lazy {
+            operations.push(format!(
+                "Lazy scan: only rows {} to {} of {} are loaded",
+                table.offset + 1,
+                table.offset + self.df_original.height(),
+                table.total_rows
+            ));
+        }
+        if let Some(rows) = filter.head_rows {
+            operations.push(format!("Preview: only the first {rows} rows are read"));
+        }
+        if let Some(preset) = &self.layout_preset {
+            operations.push(format!("Apply layout preset `{preset}` (types and names)"));
+        }
+
+        if filter.drop {
+            operations.push(format!("Drop columns matching `{}`", filter.drop_regex));
+        }
+        if filter.normalize {
+            operations.push(format!(
+                "Normalize string columns matching `{}`",
+                filter.normalize_regex
+            ));
+        }
+        if filter.null_values != NULL_VALUES {
+            operations.push(format!("Replace values with null: {}", filter.null_values));
+        }
+        for step in filter.query_steps.iter().filter(|step| !step.is_empty()) {
+            let query = step.query.split_whitespace().collect::<Vec<_>>().join(" ");
+            operations.push(format!("SQL step `{}`: {query}", step.name.trim()));
+        }
+        if filter.query.trim() != DEFAULT_QUERY.trim() {
+            let query = filter
+                .query
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+            operations.push(format!("SQL query: {query}"));
+        }
+        if filter.exclude_null_cols {
+            operations.push("Remove all-null columns".to_string());
+        }
+        if filter.add_row_index {
+            operations.push(format!(
+                "Add row index column `{}` (offset {})",
+                filter.index_column_name, filter.index_column_offset
+            ));
+        }
+        if !self.format.virtual_columns.is_empty() {
+            let columns: Vec<&str> = self
+                .format
+                .virtual_columns
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect();
+            operations.push(format!("Add virtual columns: {}", columns.join(", ")));
+        }
+        let conversions: Vec<String> = self
+            .format
+            .column_units
+            .iter()
+            .filter(|(_, unit)| unit.factor().is_some())
+            .map(|(name, unit)| format!("{name} ({} → {})", unit.unit, unit.shown()))
+            .collect();
+        if !conversions.is_empty() {
+            operations.push(format!("Convert units: {}", conversions.join(", ")));
+        }
+        let column_filters: Vec<String> = self
+            .column_filters
+            .iter()
+            .filter(|(_, text)| !text.trim().is_empty())
+            .map(|(name, text)| match name.as_str() {
+                ANY_COLUMN => format!("any column: {}", text.trim()),
+                _ => format!("{name}: {}", text.trim()),
+            })
+            .collect();
+        if !column_filters.is_empty() {
+            operations.push(format!("Column filters: {}", column_filters.join("; ")));
+        }
+        if !self.categorical_savings.is_empty() {
+            let columns: Vec<&str> = self
+                .categorical_savings
+                .iter()
+                .map(|saving| saving.column_name.as_str())
+                .collect();
+            operations.push(format!("Convert to Categorical: {}", columns.join(", ")));
+        }
+        if let Some(sort_expression) = &self.sort_expression {
+            operations.push(format!(
+                "Sort by expression: {}",
+                sort_expression.expression
+            ));
+        }
+        if !self.sort.is_empty() {
+            let columns: Vec<String> = self
+                .sort
+                .iter()
+                .map(|sort| {
+                    let direction = if sort.ascending { "asc" } else { "desc" };
+                    format!("{} {direction}", sort.column_name)
+                })
+                .collect();
+            operations.push(format!("Sort by: {}", columns.join(", ")));
+        }
+
+        let (rows, cols) = self.df.shape();
+        let (rows_original, cols_original) = self.df_original.shape();
+        if (rows, cols) != (rows_original, cols_original) {
+            operations.push(format!(
+                "Shape: {rows_original} rows x {cols_original} columns -> {rows} rows x {cols} columns"
+            ));
+        }
+
+        operations
+    }
+
+    // --- UI Rendering Methods ---
+
+    /// Renders the main data table using `egui_extras::TableBuilder`.
+    /// Handles sort and filter interactions via `render_table_header`, and cell
+    /// interactions via `render_table_row`.
+    ///
+    /// `selection` is the cell selection, updated by clicks (see `render_table_row`).
+    /// `search` highlights the matches of the global search (Ctrl+F); its pending
+    /// `scroll_to_row` is consumed here.
+    /// `page` is the current page in paged mode (`DataFormat::paged`): only its rows are
+    /// shown. It is clamped to the last page, and moved to the page of a search match.
+    /// `edits` is the patch of the edit mode (see `cell_edit.rs`): `Some`, the editable
+    /// cells are drawn as text fields.
+    ///
+    /// Returns the `TableEvents` of this frame (sort, column filters, row inspector, quick filter)
+    /// that require an update by `layout.rs`.
+    pub fn render_table(
+        &self,
+        ui: &mut Ui,
+        selection: &mut Option<CellSelection>,
+        search: &mut TableSearch,
+        page: &mut usize,
+        edits: &mut Option<CellEdits>,
+    ) -> TableEvents {
+        let scroll_to_row = search.scroll_to_row.take();
+        let search = &*search;
+
+        // Rows of the current page (all rows if not paged). In lazy mode (`lazy`), `df`
+        // already holds just the current page (see `load_lazy_page`).
+        let rows = if self.lazy.is_some() {
+            *page = (*page).min(self.format.page_count(self.total_rows()) - 1);
+            0..self.df.height()
+        } else {
+            let total_rows = self.df.height();
+            if let Some(row) = scroll_to_row {
+                *page = self.format.page_of_row(row);
+            }
+            *page = (*page).min(self.format.page_count(total_rows) - 1);
+            self.format.page_rows(*page, total_rows)
+        };
+        let scroll_to_row = scroll_to_row.map(|row| row - rows.start);
+
+        // Expanded row: the one hovered (or selected, as a single row) in the last frame.
+        let expanded_id = Id::new(EXPANDED_ROW_ID);
+        let expanded = if self.format.expand_hovered_row {
+            ui.data(|data| data.get_temp::<Option<(usize, f32)>>(expanded_id))
+                .flatten()
+                .filter(|(row, _)| rows.contains(row))
+        } else {
+            None
+        };
+        let mut body_rows = BodyRows {
+            offset: rows.start,
+            expanded: expanded.map(|(row, _)| row),
+            ..Default::default()
+        };
+        // Table row and height of the expanded row.
+        let expanded_row = expanded.map(|(row, height)| (row - rows.start, height));
+
+        // Events of the header and of the data rows (captured by separate closures).
+        let mut header_events = TableEvents::default();
+        let mut row_events = TableEvents::default();
+
+        // Draws the table `part` (header and rows), scrolled vertically to `offset`;
+        // returns its vertical scroll offset.
+        let mut draw = |ui: &mut Ui, part: TablePart, offset: Option<f32>| {
+            // Closure to render the header row. Captures `self` and the header events.
+            let analyze_header = |mut table_row: TableRow<'_, '_>| {
+                self.render_table_header(&mut table_row, part, &mut header_events);
+            };
+
+            // Closure to render data rows.
+            let analyze_rows = |mut table_row: TableRow<'_, '_>| {
+                self.render_table_row(
+                    &mut table_row,
+                    part,
+                    &mut body_rows,
+                    selection,
+                    search,
+                    edits,
+                    &mut row_events,
+                );
+            };
+
+            // Configure and build the table.
+            self.build_configured_table(
+                ui,
+                part,
+                rows.len(),
+                (scroll_to_row, offset, expanded_row),
+                analyze_header,
+                analyze_rows,
+            )
+        };
+
+        if !self.has_row_gutter(TablePart::Frozen)
+            && self.part_columns(TablePart::Frozen).next().is_none()
+        {
+            ScrollArea::horizontal()
+                .id_salt("central_scroll")
+                .auto_shrink([false, false])
+                .show(ui, |ui| draw(ui, TablePart::Scrolling, None));
+        } else {
+            // Two tables, scrolled vertically together: the one scrolled in the last frame
+            // moves the other one in this frame.
+            let offset_id = Id::new("frozen_columns_offset");
+            let offset = ui.data(|data| data.get_temp::<f32>(offset_id));
+
+            let (frozen, scrolling) = ui
+                .horizontal_top(|ui| {
+                    let frozen = draw(ui, TablePart::Frozen, offset);
+                    ui.separator();
+                    let scrolling = ScrollArea::horizontal()
+                        .id_salt("central_scroll")
+                        .auto_shrink([false, false])
+                        .show(ui, |ui| draw(ui, TablePart::Scrolling, offset))
+                        .inner;
+                    (frozen, scrolling)
+                })
+                .inner;
+
+            let previous = offset.unwrap_or_default();
+            let new_offset = if (frozen - previous).abs() > 0.5 {
+                frozen
+            } else {
+                scrolling
+            };
+            if (new_offset - previous).abs() > 0.5 || offset.is_none() {
+                ui.data_mut(|data| data.insert_temp(offset_id, new_offset));
+                ui.ctx().request_repaint();
+            }
+        }
+
+        if self.format.expand_hovered_row {
+            self.update_expanded_row(ui, &rows, &body_rows, selection);
+        }
+
+        // Return the signals from header and cell interactions.
+        header_events.or(row_events)
+    }
+
+    /// Visible columns drawn by the table `part` (see `DataFormat::frozen_columns`), with
+    /// their index among all visible columns (the column index of `CellSelection`).
+    fn part_columns(&self, part: TablePart) -> impl Iterator<Item = (usize, &PColumn)> {
+        self.visible_columns()
+            .enumerate()
+            .filter(move |(_, column)| {
+                let frozen = self
+                    .format
+                    .frozen_columns
+                    .iter()
+                    .any(|name| name == column.name().as_str());
+                frozen == (part == TablePart::Frozen)
+            })
+    }
+
+    /// Stores the row to expand in the next frame (see `EXPANDED_ROW_ID`): the hovered row,
+    /// else the selected row if the selection (of the shown `rows`) is within a single row.
+    /// Its height is the one measured this frame if it was already expanded.
+    fn update_expanded_row(
+        &self,
+        ui: &Ui,
+        rows: &Range<usize>,
+        body_rows: &BodyRows,
+        selection: &Option<CellSelection>,
+    ) {
+        let selected_row = selection.and_then(|selection| {
+            let width = self.visible_columns().count();
+            let (selected, _) = selection.bounds(self.df.height(), width);
+            (selected.len() == 1 && rows.contains(&selected.start)).then_some(selected.start)
+        });
+        let expanded = body_rows.hovered.or(selected_row).map(|row| {
+            let measured = body_rows.expanded == Some(row);
+            (
+                row,
+                if measured {
+                    body_rows.expanded_height
+                } else {
+                    0.0
+                },
+            )
+        });
+
+        let id = Id::new(EXPANDED_ROW_ID);
+        if ui.data(|data| data.get_temp::<Option<(usize, f32)>>(id)) != Some(expanded) {
+            ui.data_mut(|data| data.insert_temp(id, expanded));
+            ui.ctx().request_repaint();
+        }
+    }
+
+    /// Whether the table `part` starts with the row number gutter (`format.show_row_numbers`).
+    fn has_row_gutter(&self, part: TablePart) -> bool {
+        part == TablePart::Frozen && self.format.show_row_numbers
+    }
+
+    /// Columns shown in the table, in display order: all columns of `df` except
+    /// `format.hidden_columns` (see `ordered_columns`).
+    pub fn visible_columns(&self) -> impl Iterator<Item = &PColumn> {
+        self.ordered_columns()
+            .filter(|column| !self.format.hidden_columns.contains(column.name().as_str()))
+    }
+
+    /// All columns of `df`, in display order (`format.column_order`, see `ordered_indices`).
+    pub fn ordered_columns(&self) -> impl Iterator<Item = &PColumn> {
+        let columns = self.df.columns();
+        let names: Vec<&str> = columns
+            .iter()
+            .map(|column| column.name().as_str())
+            .collect();
+        ordered_indices(&names, &self.format.column_order)
+            .into_iter()
+            .map(move |index| &columns[index])
+    }
+
+    /// Returns a copy of this container whose `df` has its columns in display order
+    /// (used by the exports). The row order is kept.
+    pub fn with_column_order(&self) -> PolarsViewResult<Self> {
+        let mut container = self.clone();
+        if !self.format.column_order.is_empty() {
+            let names: Vec<PlSmallStr> = self
+                .ordered_columns()
+                .map(|column| column.name().clone())
+                .collect();
+            container.df = Arc::new(self.df.select(names)?);
+        }
+        Ok(container)
+    }
+
+    /// Returns a copy of this container with the cell `edits` applied to `df` (see
+    /// `CellEdits::apply`) and to the rows of `df_unfiltered` they show (see
+    /// `unfiltered_rows`), so the edits are kept when the sort or the column filters change.
+    ///
+    /// ### Errors
+    /// An edit of a computed column (see `is_computed_column`), or a value that doesn't
+    /// convert to its column type: nothing is changed.
+    pub fn with_cell_edits(&self, edits: &CellEdits) -> PolarsViewResult<Self> {
+        if let Some(name) = edits.columns().find(|name| self.is_computed_column(name)) {
+            return Err(PolarsViewError::CellEdit(format!(
+                "column `{name}` is computed (virtual column or unit conversion): it can't be edited"
+            )));
+        }
+
+        let df = edits.apply(&self.df)?;
+        let rows = self.unfiltered_rows()?;
+        let df_unfiltered = edits.moved_to(&rows)?.apply(&self.df_unfiltered)?;
+
+        let mut container = self.clone();
+        container.df = Arc::new(df);
+        container.df_unfiltered = Arc::new(df_unfiltered);
+        Ok(container)
+    }
+
+    /// Whether the column `name` of `df` is computed for the view (a virtual column, or a
+    /// column converted to its display unit) rather than read: its cells can't be edited.
+    pub fn is_computed_column(&self, name: &str) -> bool {
+        self.format
+            .virtual_columns
+            .iter()
+            .any(|column| column.name == name)
+            || unit_conversions(&self.format.column_units).contains_key(name)
+    }
+
+    /// `df` (the data of this container or a version of it, such as `df_unfiltered`) as
+    /// displayed with the view settings: `view_rows`, then sorted by `sort_expression` and
+    /// the header `sort` criteria. The sorts are stable, so the row order depends on these
+    /// settings only.
+    ///
+    /// With `row_key`, a column of that name holds the row of `df` each displayed row comes
+    /// from (the column filters ignore it).
+    fn displayed(&self, df: &DataFrame, row_key: Option<&str>) -> PolarsViewResult<DataFrame> {
+        let mut rows = match row_key {
+            None => view_rows(df, &self.format, &self.column_filters)?,
+            Some(row_key) => {
+                let df = add_virtual_columns(df, &self.format.virtual_columns)?;
+                let df = convert_units(&df, &self.format.column_units)?;
+                let predicate = column_filters_predicate(df.schema(), &self.column_filters);
+                let mut lf = df.lazy().with_row_index(row_key, None);
+                if let Some(predicate) = predicate {
+                    lf = lf.filter(predicate);
+                }
+                lf.collect()?
+            }
+        };
+        if let Some(sort_expression) = &self.sort_expression {
+            rows = sort_expression.sort(&rows)?;
+        }
+        if !self.sort.is_empty() {
+            rows = sort_by_criteria(&rows, &self.sort)?;
+        }
+        Ok(rows)
+    }
+
+    /// The row of `df_unfiltered` shown at each row of `df` (see `displayed`).
+    fn unfiltered_rows(&self) -> PolarsViewResult<Vec<usize>> {
+        let rows = self.displayed(&self.df_unfiltered, Some(ROW_KEY_COLUMN))?;
+        if rows.height() != self.df.height() {
+            return Err(PolarsViewError::CellEdit(format!(
+                "the displayed rows ({}) no longer match the data ({}): reload the file",
+                self.df.height(),
+                rows.height()
+            )));
+        }
+        Ok(rows
+            .column(ROW_KEY_COLUMN)?
+            .idx()?
+            .into_no_null_iter()
+            .map(|row| row as usize)
+            .collect())
+    }
+
+    /// Returns a copy of this container whose `df` keeps only the visible columns
+    /// (used by "Export Selected Columns..."). The row order (sorting) is kept.
+    ///
+    /// Returns an error if all columns are hidden.
+    pub fn visible_columns_projection(&self) -> PolarsViewResult<Self> {
+        let names: Vec<PlSmallStr> = self
+            .visible_columns()
+            .map(|column| column.name().clone())
+            .collect();
+
+        if names.is_empty() {
+            return Err(PolarsViewError::Other(
+                "No columns selected: check at least one column in the \"Columns\" panel."
+                    .to_string(),
+            ));
+        }
+
+        let mut container = self.clone();
+        container.df = Arc::new(self.df.select(names)?);
+        Ok(container)
+    }
+
+    /// Names of the key columns, in export order: the row index column (see
+    /// `row_index_column`), then the frozen columns (see `DataFormat::frozen_columns`),
+    /// those present in `df`.
+    pub fn key_columns(&self) -> Vec<PlSmallStr> {
+        let mut keys: Vec<PlSmallStr> = self.row_index_column.iter().cloned().collect();
+        for name in &self.format.frozen_columns {
+            let name = PlSmallStr::from(name.as_str());
+            if !keys.contains(&name) {
+                keys.push(name);
+            }
+        }
+
+        keys.retain(|name| self.df.schema().contains(name));
+        keys
+    }
+
+    /// Returns a copy of this container whose `df` has the key columns (see `key_columns`)
+    /// first, the other columns following in their current order (used by the exports when
+    /// "Export key columns first" is checked). The row order is kept.
+    pub fn with_key_columns_first(&self) -> PolarsViewResult<Self> {
+        let keys = self.key_columns();
+        let names: Vec<PlSmallStr> = keys
+            .iter()
+            .cloned()
+            .chain(
+                self.df
+                    .get_column_names()
+                    .into_iter()
+                    .filter(|name| !keys.contains(*name))
+                    .cloned(),
+            )
+            .collect();
+
+        let mut container = self.clone();
+        container.df = Arc::new(self.df.select(names)?);
+        Ok(container)
+    }
+
+    /// Writes the cells of `selection` (over the visible columns) as delimited text,
+    /// TSV with `separator = '\t'` or CSV with `','` (see `CellSelection::to_delimited`).
+    pub fn selection_to_text(
+        &self,
+        selection: &CellSelection,
+        separator: char,
+    ) -> PolarsViewResult<String> {
+        let columns: Vec<&str> = self
+            .visible_columns()
+            .map(|column| column.name().as_str())
+            .collect();
+
+        Ok(selection.to_delimited(&self.df, &columns, separator)?)
+    }
+
+    /// The rows of `selection` as a JSON array of objects (see `CellSelection::to_json`),
+    /// with the visible columns.
+    pub fn selection_to_json(&self, selection: &CellSelection) -> PolarsViewResult<String> {
+        let columns: Vec<&str> = self
+            .visible_columns()
+            .map(|column| column.name().as_str())
+            .collect();
+
+        selection.to_json(&self.df, &columns)
+    }
+
+    /// Renders the header row, creating clickable cells for sorting.
+    /// Reads the current sort state (`self.sort`), including nulls_last. On click,
+    /// reads the modifier keys (`HeaderClick::from_modifiers`) and computes the *new*
+    /// sort criteria via `apply_header_click`:
+    /// - Plain click: single-column sort cycling Asc -> Desc -> None, clearing other criteria.
+    /// - Shift + click: same cycle, but appended to (or updated within) the multi-column sort.
+    /// - Alt + click: toggles nulls first/last for an already sorted column.
+    ///
+    /// The new `Vec` is signalled back via `events.sort`.
+    ///
+    /// If `format.show_filter_row` is set, a filter input is shown under each header
+    /// (see `render_column_filter`).
+    ///
+    /// ### Arguments
+    /// * `table_row`: Egui context for the header row.
+    /// * `part`: The columns of this table (frozen or scrolling, see `part_columns`).
+    /// * `events`: Output parameter. `sort` is set to `Some(new_criteria)` if a click occurred
+    ///   that requires updating the sort state; `column_filters` if a filter was changed.
+    fn render_table_header(
+        &self,
+        table_row: &mut TableRow<'_, '_>,
+        part: TablePart,
+        events: &mut TableEvents,
+    ) {
+        if self.has_row_gutter(part) {
+            table_row.col(|ui| {
+                ui.label(RichText::new("#").weak());
             });
         }
+
+        for (_, column) in self.part_columns(part) {
+            let column_name = column.name();
+            table_row.col(|ui| {
+                if self.format.show_filter_row {
+                    ui.vertical(|ui| {
+                        self.render_header_cell(ui, column_name, events);
+                        self.render_column_filter(ui, column_name, events);
+                    });
+                } else {
+                    self.render_header_cell(ui, column_name, events);
+                }
+            }); // End cell definition
+        } // End loop over columns
     }
 
-    /// Retrieves and formats a single cell's `AnyValue` into a displayable `String`.
-    /// Called repeatedly by `render_table_row`.
+    /// Renders the sortable header of one column (see `render_table_header`).
     ///
-    /// Logic:
-    /// 1. Get `AnyValue` from `column` at `row_index` using `column.get()`.
-    /// 2. Handle `Result`: Return error string on `Err`.
-    /// 3. On `Ok(any_value)`:
-    ///    - Match on `(any_value, opt_decimal)`:
-    ///      - Floats with `Some(decimal)`: Format using `format!("{:.*}", decimal, f)`.
-    ///      - `AnyValue::Null`: Return `""`.
-    ///      - `AnyValue::String(s)`: Return `s.to_string()`.
-    ///      - Other types (Ints, Bool, Date, etc.) or Floats with `None` decimal: Use `any_value.to_string()`.
+    /// Right-click opens the header context menu (see `render_header_context_menu`).
+    /// The header can be dragged onto another header cell to move the column there
+    /// (signalled via `events.move_column`).
+    fn render_header_cell(&self, ui: &mut Ui, column_name: &PlSmallStr, events: &mut TableEvents) {
+        // The current width of the column (see "Lock width" in the context menu).
+        let width = ui.max_rect().width();
+
+        // 1. Determine current interaction state based on `ascending` and `nulls_last`.
+        let sort_index = self
+            .sort
+            .iter()
+            .position(|criterion| criterion.column_name == *column_name);
+        let current_interaction_state =
+            HeaderSortState::from_sort_by(sort_index.map(|index| &self.sort[index]));
+
+        // 2. Render the sortable header widget (uses the state and get_icon),
+        //    followed by the null badge if enabled. The whole header is a drag source.
+        let drag_id = Id::new("header_drag").with(column_name.as_str());
+        let payload = DraggedColumn(column_name.to_string());
+        let drag = ui.dnd_drag_source(drag_id, payload, |ui| {
+            ui.horizontal(|ui| {
+                let response = ui.render_sortable_header(
+                    column_name,
+                    &current_interaction_state,
+                    sort_index, // Pass index for display (e.g., "1▼")
+                    self.format.use_enhanced_header,
+                );
+                if let Some(unit) = self.format.column_units.get(column_name.as_str()) {
+                    ui.label(RichText::new(format!("[{}]", unit.shown())).weak())
+                        .on_hover_text(match unit.factor() {
+                            Some(_) => format!("Converted from {}", unit.unit),
+                            None => format!("Unit: {}", unit.unit),
+                        });
+                }
+                if self.format.show_null_badges {
+                    self.render_null_badge(ui, column_name);
+                }
+                response
+            })
+            .inner
+        });
+        let response = match self.annotations.description(column_name) {
+            Some(description) => drag.inner.on_hover_text(description),
+            None => drag.inner,
+        };
+
+        // Another column dropped here moves to this position; a line marks the drop target.
+        let is_other = |dragged: &DraggedColumn| dragged.0 != column_name.as_str();
+        if let Some(dragged) = drag.response.dnd_release_payload::<DraggedColumn>()
+            && is_other(&dragged)
+        {
+            events.move_column = Some((dragged.0.clone(), column_name.to_string()));
+        } else if drag
+            .response
+            .dnd_hover_payload::<DraggedColumn>()
+            .is_some_and(|dragged| is_other(&dragged))
+        {
+            let rect = drag.response.rect;
+            let stroke = ui.visuals().selection.stroke;
+            ui.painter().vline(rect.left(), rect.y_range(), stroke);
+        }
+
+        // 3. Handle Click Response.
+        if response.clicked() {
+            let click = HeaderClick::from_modifiers(&ui.input(|i| i.modifiers));
+            tracing::debug!(
+                "Header clicked: '{}' ({:?}). Current state: {:?}, Index: {:?}",
+                column_name,
+                click,
+                current_interaction_state,
+                sort_index
+            );
+
+            // 4. Prepare the *new* list of sort criteria based on the click outcome.
+            let new_sort_criteria = apply_header_click(&self.sort, column_name, click);
+
+            tracing::debug!(
+                "Signaling new sort criteria for async update: {:#?}",
+                new_sort_criteria
+            );
+
+            // 5. Set the output parameter to signal the required action and the new sort state.
+            events.sort = Some(new_sort_criteria);
+        } // end if response.clicked()
+
+        response.context_menu(|ui| {
+            self.render_header_context_menu(ui, column_name, width, events);
+        });
+    }
+
+    /// Context menu of the header of `column_name`: copy its name, all its values or its
+    /// distinct values (one per line, in display order), hide it, filter the rows by null
+    /// presence (in this column or in any column), set its alignment, lock its `width`, or
+    /// sort by it alone.
+    ///
+    /// Hiding, grouping, null filters, alignment, width lock and sorting are signalled via
+    /// `events.hide_column`, `events.group_by`, `events.column_filters`,
+    /// `events.align_column`, `events.lock_width` and `events.sort`.
+    fn render_header_context_menu(
+        &self,
+        ui: &mut Ui,
+        column_name: &PlSmallStr,
+        width: f32,
+        events: &mut TableEvents,
+    ) {
+        if ui.button("Copy column name").clicked() {
+            ui.ctx().copy_text(column_name.to_string());
+            ui.close();
+        }
+        for (label, distinct) in [("Copy all values", false), ("Copy distinct values", true)] {
+            if ui.button(label).clicked() {
+                match self
+                    .df
+                    .column(column_name)
+                    .and_then(|column| column_to_text(column, distinct))
+                {
+                    Ok(text) => ui.ctx().copy_text(text),
+                    Err(error) => tracing::warn!("Failed to copy column '{column_name}': {error}"),
+                }
+                ui.close();
+            }
+        }
+        ui.separator();
+
+        if ui.button("Hide column").clicked() {
+            events.hide_column = Some(column_name.to_string());
+            ui.close();
+        }
+        let frozen = self
+            .format
+            .frozen_columns
+            .iter()
+            .any(|name| name == column_name.as_str());
+        let label = if frozen {
+            "Unfreeze column"
+        } else {
+            "Freeze column"
+        };
+        if ui
+            .button(label)
+            .on_hover_text("Frozen columns stay visible on the left while scrolling horizontally.")
+            .clicked()
+        {
+            events.freeze_column = Some((column_name.to_string(), !frozen));
+            ui.close();
+        }
+        if ui
+            .button("Group by this column")
+            .on_hover_text("Value counts of this column, with optional aggregations.")
+            .clicked()
+        {
+            events.group_by = Some(column_name.to_string());
+            ui.close();
+        }
+        ui.separator();
+
+        // Null presence filters (see `NULL_FILTER`), combined with the other column filters.
+        let null_filters = [
+            (
+                "Show only rows with nulls in this column",
+                column_name.as_str(),
+                NULL_FILTER,
+            ),
+            (
+                "Hide rows with nulls in this column",
+                column_name.as_str(),
+                NOT_NULL_FILTER,
+            ),
+            (
+                "Show only rows with nulls in any column",
+                ANY_COLUMN,
+                NULL_FILTER,
+            ),
+            (
+                "Hide rows with nulls in any column",
+                ANY_COLUMN,
+                NOT_NULL_FILTER,
+            ),
+        ];
+        for (label, name, text) in null_filters {
+            if ui.button(label).clicked() {
+                let mut column_filters = self.column_filters.clone();
+                column_filters.insert(name.to_string(), text.to_string());
+                events.column_filters = Some(column_filters);
+                ui.close();
+            }
+        }
+        if self.column_filters.contains_key(ANY_COLUMN)
+            && ui.button("Clear the any-column null filter").clicked()
+        {
+            let mut column_filters = self.column_filters.clone();
+            column_filters.remove(ANY_COLUMN);
+            events.column_filters = Some(column_filters);
+            ui.close();
+        }
+        ui.separator();
+
+        // Alignment and width of this column only, kept in the view state.
+        let current_align = self.format.column_alignments.get(column_name.as_str());
+        ui.horizontal(|ui| {
+            ui.label("Align:");
+            for (label, align) in [
+                ("Left", Some(Align::LEFT)),
+                ("Center", Some(Align::Center)),
+                ("Right", Some(Align::RIGHT)),
+                ("Default", None),
+            ] {
+                if ui
+                    .selectable_label(current_align == align.as_ref(), label)
+                    .clicked()
+                {
+                    events.align_column = Some((column_name.to_string(), align));
+                    ui.close();
+                }
+            }
+        });
+        match self.format.locked_widths.get(column_name.as_str()) {
+            Some(locked) => {
+                if ui
+                    .button(format!("Unlock width ({locked:.0} pt)"))
+                    .clicked()
+                {
+                    events.lock_width = Some((column_name.to_string(), None));
+                    ui.close();
+                }
+            }
+            None => {
+                if ui
+                    .button("Lock width")
+                    .on_hover_text("Keep the current width of this column: it can't be resized.")
+                    .clicked()
+                {
+                    events.lock_width = Some((column_name.to_string(), Some(width)));
+                    ui.close();
+                }
+            }
+        }
+        ui.separator();
+
+        for (label, ascending) in [("Sort ascending", true), ("Sort descending", false)] {
+            if ui.button(label).clicked() {
+                events.sort = Some(sort_single_column(&self.sort, column_name, ascending));
+                ui.close();
+            }
+        }
+    }
+
+    /// Renders the null badge of one column (see `null_badge`), with the counts on hover.
+    fn render_null_badge(&self, ui: &mut Ui, column_name: &PlSmallStr) {
+        let Ok(column) = self.df.column(column_name) else {
+            return;
+        };
+        let (null_count, rows) = (column.null_count(), self.df.height());
+        let (text, color) = null_badge(null_count, rows);
+
+        ui.label(
+            RichText::new(format!(" {text} "))
+                .small()
+                .color(Color32::BLACK)
+                .background_color(color),
+        )
+        .on_hover_text(format!("{null_count} null values in {rows} rows"));
+    }
+
+    /// Renders the filter input of one column (filter row under the header).
+    ///
+    /// The text being typed is kept in egui's temporary memory, keyed by the column and
+    /// its applied filter, so it is reset whenever the applied filters change. The filter
+    /// is applied (signalled via `events.column_filters`) on Enter or when the input loses focus.
+    ///
+    /// Categorical/Enum columns also get a ⏷ menu of their `categories`: picking one applies
+    /// the exact match filter `=category` at once.
+    fn render_column_filter(
+        &self,
+        ui: &mut Ui,
+        column_name: &PlSmallStr,
+        events: &mut TableEvents,
+    ) {
+        let applied = self
+            .column_filters
+            .get(column_name.as_str())
+            .cloned()
+            .unwrap_or_default();
+        let id = Id::new("column_filter").with(column_name).with(&applied);
+
+        let mut text = ui
+            .data(|data| data.get_temp::<String>(id))
+            .unwrap_or_else(|| applied.clone());
+
+        // Filter picked from the category menu (empty: all categories).
+        let mut picked = None;
+
+        let response = ui
+            .horizontal(|ui| {
+                if let Some(categories) = self.categories.get(column_name.as_str()) {
+                    ui.menu_button("⏷", |ui| {
+                        ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                            if ui.button("(all)").clicked() {
+                                picked = Some(String::new());
+                                ui.close();
+                            }
+                            for category in categories {
+                                if ui.button(category).clicked() {
+                                    picked = Some(format!("={category}"));
+                                    ui.close();
+                                }
+                            }
+                        });
+                    })
+                    .response
+                    .on_hover_text(format!("{} categories", categories.len()));
+                }
+
+                ui.add(
+                    TextEdit::singleline(&mut text)
+                        .id(id)
+                        .hint_text("filter")
+                        .desired_width(f32::INFINITY),
+                )
+                .on_hover_text(COLUMN_FILTER_HELP)
+            })
+            .inner;
+        response.widget_info(|| {
+            WidgetInfo::labeled(
+                WidgetType::TextEdit,
+                true,
+                format!("Filter column {column_name}"),
+            )
+        });
+
+        let submitted = picked.is_some() || response.lost_focus();
+        if let Some(picked) = picked {
+            text = picked;
+        }
+
+        if submitted && text.trim() != applied.trim() {
+            let mut column_filters = self.column_filters.clone();
+            if text.trim().is_empty() {
+                column_filters.remove(column_name.as_str());
+            } else {
+                column_filters.insert(column_name.to_string(), text.trim().to_string());
+            }
+            events.column_filters = Some(column_filters);
+        }
+
+        ui.data_mut(|data| data.insert_temp(id, text));
+    }
+
+    /// Renders a single data row in the table body.
+    ///
+    /// Called by the `analyze_rows` closure (defined in `render_table`) for each row index
+    /// provided by the `egui_extras::TableBuilder`.
+    ///
+    /// For each cell in the row:
+    /// 1. Calls `get_decimal_and_layout` (using `self.format`) to determine the `egui::Layout` (for alignment)
+    ///    and `Option<usize>` (for decimal places, if applicable) based on the column's `DataType`.
+    /// 2. Calls `Self::format_cell_value` to retrieve the `AnyValue` from the DataFrame and format it
+    ///    into a `String`, applying decimal rounding if needed.
+    /// 3. Adds a cell to the `egui` row (`table_row.col`) and draws the value with the column's
+    ///    `CellRenderer` (see `resolve_renderer`; a `Label` of the formatted string by default)
+    ///    within the determined `Layout`.
+    ///
+    /// Cell interactions:
+    /// - Click selects the cell; Shift + click extends the selection (rectangle) from the anchor.
+    /// - Double-click opens the row inspector (via `events.inspect_cell`).
+    /// - Right-click: context menu (see `render_cell_context_menu`).
+    ///
+    /// In edit mode (`edits` is `Some`), the cells of editable columns are text fields
+    /// instead (see `CellEdits::render_cell`); focusing one selects it.
     ///
     /// ### Arguments
-    /// * `column`: Reference to the Polars `Series` (`PColumn`).
-    /// * `row_index`: Row index within the series.
-    /// * `opt_decimal`: `Option<usize>` specifying decimal places for floats (from `get_decimal_and_layout`).
+    /// * `table_row`: The `egui_extras::TableRow` context providing the `row_index` and cell adding methods.
+    /// * `part`: The columns of this table (frozen or scrolling, see `part_columns`).
+    /// * `body_rows`: The first data row of the table and the expanded row, whose cells are
+    ///   wrapped (their height is measured); records the hovered row.
+    /// * `selection`: The cell selection, updated by clicks and shown highlighted.
+    /// * `search`: The global search, whose matching cells are highlighted.
+    /// * `edits`: The pending cell edits, in edit mode.
+    /// * `events`: Output parameter for the double-clicked cell and quick filters.
+    fn render_table_row(
+        &self,
+        table_row: &mut TableRow<'_, '_>,
+        part: TablePart,
+        body_rows: &mut BodyRows,
+        selection: &mut Option<CellSelection>,
+        search: &TableSearch,
+        edits: &mut Option<CellEdits>,
+        events: &mut TableEvents,
+    ) {
+        // The 0-based data row index (table rows start at `body_rows.offset` in paged mode).
+        let row_index = body_rows.offset + table_row.index();
+        let expanded = body_rows.expanded == Some(row_index);
+
+        // Highlight the row if its `highlight_column` value is `false`.
+        let highlight = self
+            .highlight_column
+            .as_ref()
+            .and_then(|name| self.df.column(name).ok())
+            .is_some_and(|column| matches!(column.get(row_index), Ok(AnyValue::Boolean(false))));
+
+        let (height, width) = (self.df.height(), self.visible_columns().count());
+
+        if self.has_row_gutter(part) {
+            table_row.col(|ui| {
+                if ui.rect_contains_pointer(ui.max_rect()) {
+                    body_rows.hovered = Some(row_index);
+                }
+                self.render_row_number(ui, row_index, selection);
+            });
+        }
+
+        // Iterate through each visible column (Polars Series) of this table.
+        for (column_index, column_series) in self.part_columns(part) {
+            // Determine alignment and decimal places using the feature-flagged helper.
+            // Passes the Series and the current format settings Arc.
+            let (opt_decimal, layout) = get_decimal_and_layout(column_series, &self.format);
+            let renderer = resolve_renderer(&self.format, column_series);
+
+            // Get the raw AnyValue and format it into a display String.
+            let value_str = self.format_cell_value(column_series, row_index, opt_decimal);
+            let accessible_label =
+                cell_accessible_label(row_index, column_index, column_series.name(), &value_str);
+
+            // Add a cell to the egui row.
+            table_row.col(|ui| {
+                if ui.rect_contains_pointer(ui.max_rect()) {
+                    body_rows.hovered = Some(row_index);
+                }
+                if highlight {
+                    let color = ui.visuals().error_fg_color.gamma_multiply(0.25);
+                    ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+                }
+                let selected = selection.is_some_and(|selection| {
+                    selection.contains(row_index, column_index, height, width)
+                });
+                if selected {
+                    let color = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+                    ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+                }
+                let name = column_series.name().as_str();
+                // With "Highlight text", the matched text is highlighted instead of the cell
+                // (the current match keeps a light cell highlight, to be found at a glance).
+                let highlighted = search.highlighted_text(ui, row_index, name, &value_str);
+                let is_current = search.is_current(row_index, name);
+                if search.is_match(row_index, name) && (highlighted.is_none() || is_current) {
+                    let strong = is_current && highlighted.is_none();
+                    let color = TableSearch::highlight_color(strong);
+                    ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+                }
+                // Apply the determined layout (alignment) to the cell content. Prevent wrapping,
+                // except in the expanded row (`format.expand_hovered_row`).
+                ui.with_layout(layout.with_main_wrap(false), |ui| {
+                    // Edit mode: a text field with the edited text or the value.
+                    if let Some(edits) = edits.as_mut()
+                        && is_editable(column_series.dtype())
+                        && !self.is_computed_column(column_series.name())
+                    {
+                        let response = edits.render_cell(ui, row_index, column_series);
+                        if response.gained_focus() {
+                            *selection = Some(CellSelection::cell(row_index, column_index));
+                        }
+                        return;
+                    }
+
+                    // Draw the value with the column's renderer (see `cell_renderer.rs`);
+                    // clicks select, a double-click opens the row inspector.
+                    let cell = CellContent {
+                        column: column_series,
+                        row: row_index,
+                        text: match highlighted {
+                            Some(job) => WidgetText::from(job),
+                            None => WidgetText::from(value_str),
+                        },
+                        wrap: expanded,
+                    };
+                    let response = renderer.render(ui, &cell);
+                    if expanded {
+                        body_rows.expanded_height =
+                            body_rows.expanded_height.max(response.rect.height());
+                    }
+                    response.widget_info(|| {
+                        WidgetInfo::selected(WidgetType::Label, true, selected, &accessible_label)
+                    });
+
+                    // Keyboard navigation (Tab / Shift + Tab): the focused cell is the selection,
+                    // and Ctrl+C copies it (the global shortcut skips focused widgets).
+                    if response.gained_focus() && !selected {
+                        *selection = Some(CellSelection::cell(row_index, column_index));
+                    }
+                    if response.has_focus()
+                        && ui.input(|i| i.events.iter().any(|e| matches!(e, egui::Event::Copy)))
+                        && let Some(selection) = selection
+                    {
+                        match self.selection_to_text(selection, '\t') {
+                            Ok(text) => ui.ctx().copy_text(text),
+                            Err(error) => tracing::warn!("Failed to copy the selection: {error}"),
+                        }
+                    }
+
+                    if response.double_clicked() {
+                        events.inspect_cell = Some((row_index, column_series.name().to_string()));
+                    } else if response.clicked() {
+                        let shift = ui.input(|i| i.modifiers.shift);
+                        match selection.as_mut() {
+                            Some(selection) if shift => {
+                                selection.extend_to(row_index, column_index);
+                            }
+                            _ => *selection = Some(CellSelection::cell(row_index, column_index)),
+                        }
+                    } else if response.secondary_clicked() && !selected {
+                        *selection = Some(CellSelection::cell(row_index, column_index));
+                    }
+
+                    response.context_menu(|ui| {
+                        self.render_cell_context_menu(
+                            ui,
+                            (row_index, column_index),
+                            column_series,
+                            selection,
+                            events,
+                        );
+                    });
+                });
+            });
+        }
+    }
+
+    /// Renders the number of the row `row_index` in the row number gutter (1-based, counting
+    /// from the first row of the scan in lazy mode).
     ///
-    /// ### Returns
-    /// `String`: The formatted cell value.
-    fn format_cell_value(
+    /// Click selects the row; Shift + click extends a row selection to it.
+    fn render_row_number(
         &self,
-        column: &PColumn,
+        ui: &mut Ui,
         row_index: usize,
-        opt_decimal: Option<usize>, // Info comes from get_decimal_and_layout which uses self.format
-    ) -> String {
-        match column.get(row_index) {
-            Ok(any_value) => {
-                // Format based on the AnyValue variant and decimal setting.
-                match (any_value, opt_decimal) {
-                    // Float with specific decimal request: Apply precision formatting.
-                    (AnyValue::Float32(value), Some(decimal)) => format!("{value:.decimal$}"),
-                    (AnyValue::Float64(value), Some(decimal)) => format!("{value:.decimal$}"),
-
-                    // Null value: Display as empty string.
-                    (AnyValue::Null, _) => String::new(),
-
-                    // String value: Convert inner &str to String.
-                    (AnyValue::String(value), _) => value.to_string(), // Handle StringOwned too if necessary.
-
-                    // Other AnyValue types OR Float without specific decimal: Use default Polars to_string().
-                    (other_anyvalue, _) => other_anyvalue.to_string(),
+        selection: &mut Option<CellSelection>,
+    ) {
+        let selected = selection.is_some_and(|selection| {
+            selection.kind == SelectionKind::Rows
+                && selection.contains(row_index, 0, self.df.height(), 1)
+        });
+        if selected {
+            let color = ui.visuals().selection.bg_fill.gamma_multiply(0.5);
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+
+        let first_row = self.lazy.as_ref().map_or(0, |table| table.offset);
+        let number = RichText::new((first_row + row_index + 1).to_string()).weak();
+        let response = ui
+            .with_layout(Layout::right_to_left(Align::Center), |ui| {
+                ui.add(Label::new(number).selectable(false).sense(Sense::click()))
+            })
+            .inner
+            .on_hover_text("Click: select the row. Shift + click: select a range of rows.");
+
+        if response.clicked() {
+            let shift = ui.input(|i| i.modifiers.shift);
+            match selection.as_mut() {
+                Some(selection) if shift && selection.kind == SelectionKind::Rows => {
+                    selection.extend_to(row_index, 0);
                 }
+                _ => *selection = Some(CellSelection::row(row_index)),
             }
-            Err(e) => {
-                // Handle error retrieving value (e.g., index out of bounds, though unlikely with TableBuilder).
-                tracing::warn!(
-                    "format_cell_value: Failed get value col '{}' row {}: {}",
-                    column.name(),
-                    row_index,
-                    e
-                );
-                "⚠ Err".to_string() // Return placeholder error string for display.
+        }
+    }
+
+    /// Context menu of a data cell at (`row_index`, `column_index`) of `column`:
+    /// quick filters on its value, select its row/column, copy the selection.
+    fn render_cell_context_menu(
+        &self,
+        ui: &mut Ui,
+        (row_index, column_index): (usize, usize),
+        column: &PColumn,
+        selection: &mut Option<CellSelection>,
+        events: &mut TableEvents,
+    ) {
+        self.render_quick_filters(ui, row_index, column, events);
+
+        if ui.button("Select row").clicked() {
+            *selection = Some(CellSelection::row(row_index));
+            ui.close();
+        }
+        if ui.button("Select column").clicked() {
+            *selection = Some(CellSelection::column(column_index));
+            ui.close();
+        }
+        ui.separator();
+
+        for (label, separator) in [("Copy (Ctrl+C)", '\t'), ("Copy as CSV", ',')] {
+            if ui.button(label).clicked() {
+                if let Some(selection) = selection {
+                    match self.selection_to_text(selection, separator) {
+                        Ok(text) => ui.ctx().copy_text(text),
+                        Err(error) => tracing::warn!("Failed to copy the selection: {error}"),
+                    }
+                }
+                ui.close();
+            }
+        }
+
+        if ui
+            .button("Copy selected rows as JSON")
+            .on_hover_text("Array of objects, one per row, with typed values.")
+            .clicked()
+        {
+            if let Some(selection) = selection {
+                match self.selection_to_json(selection) {
+                    Ok(text) => ui.ctx().copy_text(text),
+                    Err(error) => tracing::warn!("Failed to copy the rows as JSON: {error}"),
+                }
             }
+            ui.close();
         }
     }
 
+    /// Quick filter entries of the cell context menu: "Filter: column = value",
+    /// "Filter: column ≠ value" (replacing the query) and "Add to filter" (filtering the
+    /// result of the current query, see `compose_quick_filter`). Sets `events.query`.
+    ///
+    /// Nothing is shown for values without a SQL literal (e.g., nested values).
+    fn render_quick_filters(
+        &self,
+        ui: &mut Ui,
+        row_index: usize,
+        column: &PColumn,
+        events: &mut TableEvents,
+    ) {
+        let Ok(value) = column.get(row_index) else {
+            return;
+        };
+
+        let name = column.name().as_str();
+        let Some(equal) = quick_filter_condition(name, &value, QuickFilterOp::Equal) else {
+            return;
+        };
+        let Some(not_equal) = quick_filter_condition(name, &value, QuickFilterOp::NotEqual) else {
+            return;
+        };
+
+        // Shortened value for the menu labels.
+        let text = match &value {
+            AnyValue::Null => "null".to_string(),
+            other => other
+                .get_str()
+                .map(str::to_string)
+                .unwrap_or(other.to_string()),
+        };
+        let short: String = text.chars().take(MAX_MENU_VALUE_CHARS).collect();
+        let short = if short.len() < text.len() {
+            format!("{short}…")
+        } else {
+            short
+        };
+
+        let filter = &self.filter;
+        let entries = [
+            (format!("Filter: {name} = {short}"), &equal, false),
+            (format!("Filter: {name} ≠ {short}"), &not_equal, false),
+            (format!("Add to filter: {name} = {short}"), &equal, true),
+        ];
+
+        for (label, condition, add) in entries {
+            if ui.button(label).clicked() {
+                events.query = Some(compose_quick_filter(
+                    &filter.query,
+                    &filter.table_name,
+                    condition,
+                    add,
+                ));
+                ui.close();
+            }
+        }
+        ui.separator();
+    }
+
+    /// Formats a single cell of `column` for display (see `format_cell`).
+    /// Called repeatedly by `render_table_row`.
+    fn format_cell_value(
+        &self,
+        column: &PColumn,
+        row_index: usize,
+        opt_decimal: Option<usize>, // Info comes from get_decimal_and_layout which uses self.format
+    ) -> String {
+        format_cell(column, row_index, opt_decimal, &self.format)
+    }
+
     /// Prepares configuration values needed for `TableBuilder`.
     /// Encapsulates calculations for sizes, strategies, and IDs based on current format and UI state.
     ///
     /// Called by `build_configured_table`.
-    fn prepare_table_build_config(&self, ui: &Ui) -> TableBuildConfig {
+    fn prepare_table_build_config(&self, ui: &Ui, part: TablePart) -> TableBuildConfig {
         // --- Calculate Style and Dimensions ---
         let style = ui.style();
         let text_height = TextStyle::Body.resolve(style).size; // Standard row height
-        let num_columns = self.df.width().max(1); // Ensure at least 1 column logically
+        let num_columns = match part {
+            // The gutter alone is a valid frozen table.
+            TablePart::Frozen => self.part_columns(part).count(),
+            TablePart::Scrolling => self.part_columns(part).count().max(1), // Ensure at least 1 column logically
+        };
         let suggested_width = 150.0; // A sensible starting point for auto/initial width
 
         // --- Calculate Column Widths ---
@@ -578,6 +2454,13 @@ impl DataContainer {
                            + 2.0 * style.spacing.item_spacing.y // Top/bottom internal spacing
                            + padding; // Add configured extra padding
 
+        // Room for the filter row inputs under the column names.
+        let header_height = if self.format.show_filter_row {
+            header_height + style.spacing.interact_size.y + style.spacing.item_spacing.y
+        } else {
+            header_height
+        };
+
         // --- Determine Column Sizing Strategy ---
         let column_sizing_strategy = if self.format.auto_col_width {
             // Automatic: sizes based on content, potentially slower
@@ -599,11 +2482,34 @@ impl DataContainer {
         .resizable(true) // Allow user resizing
         .clip(true); // Clip content within cell bounds
 
+        // Columns locked from the header context menu keep their width.
+        let locked_widths = self
+            .part_columns(part)
+            .map(|(_, column)| {
+                self.format
+                    .locked_widths
+                    .get(column.name().as_str())
+                    .copied()
+            })
+            .collect();
+
+        // --- Row Number Gutter ---
+        // Wide enough for the largest row number (digits are about 0.6 em wide).
+        let gutter_width = self.has_row_gutter(part).then(|| {
+            let digits = self.total_rows().max(1).ilog10() as f32 + 1.0;
+            digits * 0.6 * text_height + 2.0 * style.spacing.item_spacing.x
+        });
+
         // --- Generate Table ID ---
         // **Key**: ID incorporates `auto_col_width`. Changing this flag results in a *different* ID,
         // forcing egui to discard cached layout state (like manually resized widths)
         // and recompute the layout using the new column sizing strategy.
         let table_id = Id::new("data_table_view").with(self.format.auto_col_width);
+        // The frozen columns table keeps its own column widths.
+        let table_id = match part {
+            TablePart::Frozen => table_id.with("frozen"),
+            TablePart::Scrolling => table_id,
+        };
         tracing::trace!(
             "prepare_table_build_config: Using table_id: {:?} based on auto_col_width={}",
             table_id,
@@ -626,6 +2532,8 @@ impl DataContainer {
             num_columns,
             header_height,
             column_sizing_strategy,
+            locked_widths,
+            gutter_width,
             table_id,
         }
     }
@@ -638,36 +2546,180 @@ impl DataContainer {
     ///
     /// ### Arguments
     /// * `ui`: The `egui::Ui` context for drawing.
+    /// * `part`: The columns of this table. The frozen columns table has no remainder column
+    ///   and no scroll bar, and shrinks to the width of its columns.
+    /// * `scroll_to_row`: Row to bring into view (centered), e.g., the current search match.
+    /// * `offset`: Vertical scroll offset to apply (synchronizes the frozen columns table).
+    /// * `expanded_row`: Table row drawn taller, with the height of its wrapped cells
+    ///   (see `BodyRows::expanded`).
     /// * `analyze_header`: Closure for rendering the header row content.
     /// * `analyze_rows`: Closure for rendering data row content.
+    ///
+    /// ### Returns
+    /// The vertical scroll offset of the table after this frame.
     fn build_configured_table(
         &self,
         ui: &mut Ui,
+        part: TablePart,
+        num_rows: usize,
+        (scroll_to_row, offset, expanded_row): (Option<usize>, Option<f32>, Option<(usize, f32)>),
         analyze_header: impl FnMut(TableRow<'_, '_>), // Closure to draw the header.
         analyze_rows: impl FnMut(TableRow<'_, '_>),   // Closure to draw data rows.
-    ) {
+    ) -> f32 {
         // 1. Get the calculated configuration values.
-        let config = self.prepare_table_build_config(ui);
+        let config = self.prepare_table_build_config(ui, part);
 
         // 2. Configure and Build the Table using values from `config`.
-        TableBuilder::new(ui)
+        let mut builder = TableBuilder::new(ui);
+        if let Some(row) = scroll_to_row {
+            builder = builder.scroll_to_row(row, Some(egui::Align::Center));
+        } else if let Some(offset) = offset {
+            builder = builder.vertical_scroll_offset(offset);
+        }
+        builder = builder
             // Set the ID controlling layout persistence (crucial for `auto_col_width` toggle).
             .id_salt(config.table_id)
-            .striped(true) // Alternate row backgrounds.
-            // Define sizing strategy for data columns using config.
-            .columns(config.column_sizing_strategy, config.num_columns)
-            // Add a final 'remainder' column to fill unused space.
-            .column(Column::remainder())
+            .striped(true); // Alternate row backgrounds.
+        if let Some(width) = config.gutter_width {
+            builder = builder.column(Column::exact(width).resizable(false));
+        }
+        // Define sizing strategy for data columns using config (locked columns: exact width).
+        for locked in &config.locked_widths {
+            builder = builder.column(match locked {
+                Some(width) => Column::exact(*width).resizable(false).clip(true),
+                None => config.column_sizing_strategy,
+            });
+        }
+        // An empty scrolling table still has one (logical) column.
+        builder = builder.columns(
+            config.column_sizing_strategy,
+            config.num_columns - config.locked_widths.len(),
+        );
+        builder = match part {
+            TablePart::Frozen => builder
+                .auto_shrink([true, false]) // As wide as its columns.
+                .scroll_bar_visibility(ScrollBarVisibility::AlwaysHidden),
+            TablePart::Scrolling => builder
+                // Add a final 'remainder' column to fill unused space.
+                .column(Column::remainder())
+                .auto_shrink([false, false]), // Don't shrink horizontally or vertically.
+        };
+        builder
             .resizable(true) // Allow resizing via separators.
-            .auto_shrink([false, false]) // Don't shrink horizontally or vertically.
             // Define the header section using calculated height and the provided closure.
             .header(config.header_height, analyze_header)
             // Define the body section.
             .body(|body| {
-                let num_rows = self.df.height(); // Get total rows from the DataFrame.
+                // `num_rows`: all rows of the DataFrame, or those of the current page.
                 // Use `body.rows` for efficient virtual scrolling.
                 // Provide row height, total rows, and the row drawing closure.
-                body.rows(config.text_height, num_rows, analyze_rows);
-            }); // End table configuration. Egui draws the table.
+                match expanded_row {
+                    // One taller row: heights are given row by row.
+                    Some((expanded, height)) => {
+                        let heights = (0..num_rows).map(|row| {
+                            if row == expanded {
+                                height.max(config.text_height)
+                            } else {
+                                config.text_height
+                            }
+                        });
+                        body.heterogeneous_rows(heights, analyze_rows);
+                    }
+                    None => body.rows(config.text_height, num_rows, analyze_rows),
+                }
+            }) // End table configuration. Egui draws the table.
+            .state
+            .offset
+            .y
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_data_container`
+#[cfg(test)]
+mod tests_data_container {
+    use super::*;
+    use crate::VirtualColumn;
+
+    fn container() -> PolarsViewResult<DataContainer> {
+        let df = Arc::new(df!("id" => &[1i64, 2, 3], "price" => &[1.5, 2.25, 3.0])?);
+        Ok(DataContainer {
+            df: df.clone(),
+            df_original: df.clone(),
+            df_unfiltered: df,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn pure_view_changes_share_the_data() -> PolarsViewResult<()> {
+        let container = container()?;
+
+        let mut format = container.format.as_ref().clone();
+        format.hidden_columns.insert("price".to_string());
+        format.frozen_columns = vec!["id".to_string()];
+        format.show_row_numbers = !format.show_row_numbers;
+        format.decimal += 1;
+
+        let viewed = container
+            .with_format(format.clone())
+            .expect("a pure view change");
+        assert!(Arc::ptr_eq(&viewed.df, &container.df));
+        assert_eq!(viewed.format.as_ref(), &format);
+        Ok(())
+    }
+
+    #[test]
+    fn data_changes_need_update_format() -> PolarsViewResult<()> {
+        let container = container()?;
+
+        // Decimal places inferred from the data.
+        let mut format = container.format.as_ref().clone();
+        format.auto_decimal = !format.auto_decimal;
+        assert!(container.with_format(format).is_none());
+
+        // A virtual column changes the displayed rows.
+        let mut format = container.format.as_ref().clone();
+        format.virtual_columns.push(VirtualColumn {
+            name: "total".to_string(),
+            expression: "id * price".to_string(),
+        });
+        assert!(container.with_format(format).is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn key_columns_are_exported_first() -> PolarsViewResult<()> {
+        let df = Arc::new(df!(
+            "name" => &["a", "b"],
+            "row" => &[0u32, 1],
+            "city" => &["x", "y"],
+            "price" => &[1.5, 2.0]
+        )?);
+        let format = DataFormat {
+            frozen_columns: vec!["price".to_string(), "missing".to_string()],
+            ..Default::default()
+        };
+        let container = DataContainer {
+            df: df.clone(),
+            df_original: df.clone(),
+            df_unfiltered: df,
+            format: Arc::new(format),
+            row_index_column: Some("row".into()),
+            ..Default::default()
+        };
+
+        assert_eq!(container.key_columns(), ["row", "price"]);
+        let exported = container.with_key_columns_first()?;
+        assert_eq!(
+            exported.df.get_column_names_str(),
+            ["row", "price", "name", "city"]
+        );
+        assert_eq!(exported.df.height(), 2);
+        Ok(())
     }
 }
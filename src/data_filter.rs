@@ -1,17 +1,28 @@
 use crate::{
-    Arguments, DEFAULT_OVERRIDE_REGEX, DEFAULT_QUERY, FileExtension, PathExtension,
-    PolarsViewError, PolarsViewResult, UniqueElements, sql_commands,
+    Arguments, CancelToken, ColumnType, CsvDialect, CsvInput, DEFAULT_CATEGORICAL_RATIO,
+    DEFAULT_DATE_FORMAT, DEFAULT_HEAD_ROWS, DEFAULT_OVERRIDE_REGEX, DEFAULT_QUERY, DataFormat,
+    DataSource, DialectOverride, DropRegexCache, ExampleColumns, FileExtension, LazyTable,
+    NamedQuery, PARQUET_KEY_ENV, ParquetKey, PathExtension, PolarsViewError, PolarsViewResult,
+    QueryRecord, RecordGroupsCache, SharedBytes, SnippetSource, TextEncoding, UniqueElements,
+    cancel_token, check_cancelled, extract_zip_entry, format_has_time, format_sql,
+    is_encrypted_parquet, is_multi_record_file, limit_rows, list_zip_entries,
+    read_encrypted_parquet, read_excel_sheet, read_iceberg_table, read_sqlite_table,
+    render_sql_editor, search_sql_snippets, sql_commands, sql_identifier, transcode_to_utf8,
+    validate_rename,
 };
 use egui::{
-    Align, CollapsingHeader, Color32, DragValue, Frame, Grid, Layout, Stroke, TextEdit, Ui, Vec2,
+    Align, CollapsingHeader, Color32, ComboBox, DragValue, Frame, Grid, Key, Label, Layout,
+    ScrollArea, Sense, Stroke, TextEdit, Ui, Vec2,
 };
 use polars::{io::RowIndex, prelude::*};
 use regex::Regex;
 use tokio::task::spawn_blocking;
 
 use std::{
+    collections::BTreeMap,
     fmt::Debug,
     fs::File,
+    io::Cursor,
     num::NonZero,
     path::{Path, PathBuf},
     sync::Arc,
@@ -36,6 +47,9 @@ const DEFAULT_NORM_REGEX: &str = "^Val.*$";
 /// Default drop regex
 const DEFAULT_DROP_REGEX: &str = "^Temp.*$";
 
+/// Default regex of the columns parsed as dates
+const DEFAULT_DATE_REGEX: &str = "^Dat.*$";
+
 /// Default starting offset for the row index column (e.g., 1 for 1-based).
 const DEFAULT_INDEX_COLUMN_OFFSET: u32 = 1;
 
@@ -44,6 +58,12 @@ const DEFAULT_INFER_SCHEMA_ROWS: usize = 200;
 // Prevent potential infinite loops (e.g., schema keeps changing).
 pub const MAX_ATTEMPTS: u32 = 1000;
 
+/// Maximum number of successfully applied SQL queries kept in `DataFilter::query_history`.
+pub const MAX_QUERY_HISTORY: usize = 50;
+
+/// Maximum number of matches listed below the SQL search box.
+const MAX_SEARCH_RESULTS: usize = 8;
+
 // --- DataFilter Struct ---
 
 /// Holds configuration parameters related to **loading and querying** data.
@@ -61,6 +81,18 @@ pub struct DataFilter {
     pub table_name: String,
     /// The character used to separate columns in a CSV file.
     pub csv_delimiter: String,
+    /// The quote character of CSV fields (empty: sniffed, see `csv_sniffer.rs`).
+    pub csv_quote_char: String,
+    /// The escape character of quotes inside CSV fields (empty: sniffed; the quote
+    /// character itself: doubled quotes).
+    pub csv_escape_char: String,
+    /// CSV lines starting with this prefix are skipped as comments (empty: none).
+    pub csv_comment_prefix: String,
+    /// Rows skipped at the start of a CSV file, before the header.
+    pub csv_skip_rows: usize,
+    /// Text encoding of a CSV file; other than UTF-8, the file is transcoded before
+    /// being read (see `encoding.rs`).
+    pub csv_encoding: TextEncoding,
     /// Read data from file
     pub read_data_from_file: bool,
     /// The schema (column names and data types) of the most recently loaded DataFrame.
@@ -72,6 +104,43 @@ pub struct DataFilter {
     pub exclude_null_cols: bool,
     /// Comma-separated string of values to interpret as nulls during CSV parsing.
     pub null_values: String,
+    /// Keep CSV/Parquet files as a `LazyFrame` and collect only the displayed page
+    /// (see `LazyTable`), for files too large to load into memory.
+    pub lazy_scan: bool,
+    /// Preview mode: read only the first N rows of the file (`--head N`, see `preview.rs`);
+    /// `None`: the whole file.
+    pub head_rows: Option<usize>,
+    /// Key of an encrypted Parquet file, typed in the "Encrypted Parquet" dialog
+    /// (see `encryption.rs`). Kept for the session only; `None`: `PARQUET_KEY_ENV`.
+    pub parquet_key: Option<ParquetKey>,
+
+    // --- Excel Workbooks ---
+    /// The worksheet to read from an Excel workbook (`None`: the first sheet).
+    pub sheet_name: Option<String>,
+    /// Sheet names of the loaded workbook (empty for other file types).
+    pub sheet_names: Vec<String>,
+
+    // --- SQLite Databases ---
+    /// The table (or view) to read from a SQLite database (`None`: the first table).
+    pub db_table: Option<String>,
+    /// Table names of the loaded database (empty for other file types).
+    pub db_tables: Vec<String>,
+
+    // --- Zip Archives ---
+    /// The entry to read from a zip archive (`None`: the first supported entry).
+    pub zip_entry: Option<String>,
+    /// Supported entries of the loaded archive (empty for other file types).
+    pub zip_entries: Vec<String>,
+
+    // --- Multi-Record Files ---
+    /// The record type to show from a multi-record file (`None`: the first one).
+    pub record_type: Option<String>,
+    /// Record types (and their number of records) of the loaded multi-record file,
+    /// in order of first appearance (empty for other files).
+    pub record_types: Vec<(String, usize)>,
+    /// The record groups of the loaded multi-record file, split once for all its record
+    /// types (see `RecordGroupsCache`).
+    pub record_groups: RecordGroupsCache,
 
     /// Regex patterns matching columns to force read as String type.
     ///
@@ -84,6 +153,14 @@ pub struct DataFilter {
     pub apply_sql: bool,
     /// The SQL query string entered by the user.
     pub query: String,
+    /// Named queries run before `query`, each registered as a table under its name, so
+    /// later steps and `query` can select from it (see `execute_sql`).
+    pub query_steps: Vec<NamedQuery>,
+    /// Successfully applied SQL queries, most recent first (capped at `MAX_QUERY_HISTORY`),
+    /// with their time and result dimensions. Loaded from (and saved to) the saved
+    /// histories of the data source (see `QueryHistory`). Searched together with the
+    /// generated examples by the SQL search box, and listed by the "History" dropdown.
+    pub query_history: Vec<QueryRecord>,
 
     // --- NEW FIELDS for Index Column ---
     /// Flag indicating if a row index column should be added.
@@ -103,6 +180,42 @@ pub struct DataFilter {
     // --- Drop Columns ---
     pub drop: bool,
     pub drop_regex: String,
+    /// The compiled `drop_regex` of the SQL examples (see `example_columns`).
+    pub drop_regex_cache: DropRegexCache,
+
+    // --- Parse Dates ---
+    /// Flag indicating whether String columns are parsed as dates (see `parse_dates_by_regex`).
+    pub parse_dates: bool,
+    /// Regex pattern to select the String columns parsed as dates.
+    pub date_regex: String,
+    /// Date format in `strftime` syntax (e.g., `%d/%m/%Y`); with a time of day, the columns
+    /// become Datetime.
+    pub date_format: String,
+    /// Time zone of the parsed datetimes: empty keeps the local time as written,
+    /// otherwise `UTC` or an IANA name.
+    pub date_time_zone: String,
+
+    // --- Data Types ---
+    /// Data type of each column picked in "Columns" > "Data Types", keyed by the column
+    /// name as read (see `column_types.rs`). Changing it reads the file again.
+    pub column_types: BTreeMap<String, ColumnType>,
+
+    // --- Column Names ---
+    /// New name of each column renamed in "Columns" > "Rename Columns", keyed by the
+    /// column name as read (see `column_renames.rs`). Applied after the type overrides,
+    /// so the SQL query and the exports see the new names.
+    pub column_renames: BTreeMap<String, String>,
+
+    // --- Nested Data ---
+    /// Flag indicating whether Struct columns are replaced by their fields (dotted names,
+    /// see `flatten_struct_columns`). Offered when a JSON file reads as one Struct column.
+    pub flatten_structs: bool,
+
+    // --- Categorical Strings ---
+    /// Flag indicating whether high-duplication String columns are converted to Categorical.
+    pub categorical: bool,
+    /// Maximum ratio of unique values to rows for a String column to be converted.
+    pub categorical_ratio: f64,
 }
 
 impl Default for DataFilter {
@@ -112,16 +225,38 @@ impl Default for DataFilter {
             absolute_path: PathBuf::new(),
             table_name: "AllData".to_string(),
             csv_delimiter: DEFAULT_CSV_DELIMITER.to_string(),
+            csv_quote_char: String::new(),
+            csv_escape_char: String::new(),
+            csv_comment_prefix: String::new(),
+            csv_skip_rows: 0,
+            csv_encoding: TextEncoding::default(),
             read_data_from_file: true,
             schema: Schema::default().into(),
             infer_schema_rows: DEFAULT_INFER_SCHEMA_ROWS,
             exclude_null_cols: false,
             null_values: NULL_VALUES.to_string(),
+            lazy_scan: false,
+            head_rows: None,
+            parquet_key: None,
+
+            sheet_name: None,
+            sheet_names: Vec::new(),
+
+            db_table: None,
+            db_tables: Vec::new(),
+            zip_entry: None,
+            zip_entries: Vec::new(),
+
+            record_type: None,
+            record_types: Vec::new(),
+            record_groups: RecordGroupsCache::default(),
 
             force_string_patterns: DEFAULT_OVERRIDE_REGEX.map(ToString::to_string),
 
             apply_sql: false,
             query: DEFAULT_QUERY.to_string(),
+            query_steps: Vec::new(),
+            query_history: Vec::new(),
 
             // --- NEW DEFAULTS ---
             add_row_index: false, // Default to false
@@ -135,6 +270,16 @@ impl Default for DataFilter {
             // --- END NEW FIELDS ---
             drop: false,
             drop_regex: DEFAULT_DROP_REGEX.to_string(),
+            drop_regex_cache: DropRegexCache::default(),
+            parse_dates: false,
+            date_regex: DEFAULT_DATE_REGEX.to_string(),
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            date_time_zone: String::new(),
+            column_types: BTreeMap::new(),
+            column_renames: BTreeMap::new(),
+            flatten_structs: false,
+            categorical: false,
+            categorical_ratio: DEFAULT_CATEGORICAL_RATIO,
         }
     }
 }
@@ -182,12 +327,17 @@ impl DataFilter {
             absolute_path,
             table_name: args.table_name.clone(),
             csv_delimiter: args.delimiter.clone(),
+            csv_quote_char: args.quote.map(String::from).unwrap_or_default(),
+            csv_escape_char: args.escape.map(String::from).unwrap_or_default(),
+            csv_comment_prefix: args.comment.clone().unwrap_or_default(),
+            csv_skip_rows: args.skip_rows,
 
             apply_sql, // Directly set based on CLI argument presence
             query,     // Directly set based on CLI argument value (or default)
 
             exclude_null_cols: args.exclude_null_cols,
             null_values: args.null_values.clone(), // Use user-provided nulls.
+            head_rows: args.head,
 
             force_string_patterns,
 
@@ -200,10 +350,39 @@ impl DataFilter {
     /// Sets the data source path, canonicalizing it.
     pub fn set_path(&mut self, path: &Path) -> PolarsViewResult<()> {
         self.absolute_path = path.canonicalize()?;
+        // Sheets, tables, zip entries, record types and the Parquet key belong to the
+        // previous file.
+        self.sheet_name = None;
+        self.sheet_names.clear();
+        self.db_table = None;
+        self.db_tables.clear();
+        self.zip_entry = None;
+        self.zip_entries.clear();
+        self.record_type = None;
+        self.record_types.clear();
+        self.record_groups = RecordGroupsCache::default();
+        self.parquet_key = None;
         tracing::debug!("absolute_path set to: {:#?}", self.absolute_path);
         Ok(())
     }
 
+    /// Records the current `query`, run now with a result of `rows` x `columns`, at the
+    /// front of `query_history`. Duplicates are moved to the front, the default query is
+    /// skipped and the history is capped at `MAX_QUERY_HISTORY` entries.
+    pub fn add_query_to_history(&mut self, rows: usize, columns: usize) {
+        let query = self.query.trim();
+
+        if query.is_empty() || query == DEFAULT_QUERY.trim() {
+            return;
+        }
+
+        self.query_history
+            .retain(|record| record.query.trim() != query);
+        self.query_history
+            .insert(0, QueryRecord::now(query, rows, columns));
+        self.query_history.truncate(MAX_QUERY_HISTORY);
+    }
+
     /// Gets the file extension from `absolute_path` in lowercase.
     pub fn get_extension(&self) -> Option<String> {
         self.absolute_path.extension_as_lowercase()
@@ -255,16 +434,36 @@ impl DataFilter {
     /// A `PolarsViewResult` containing a tuple: `(DataFrame, FileExtension)` on success,
     /// or a `PolarsViewError` (e.g., `FileType`, `CsvParsing`) on failure.
     pub async fn get_df_and_extension(&mut self) -> PolarsViewResult<(DataFrame, FileExtension)> {
+        // Zip archives: list the supported entries; the selected one is read below.
+        if FileExtension::from_path(&self.absolute_path) == FileExtension::Zip {
+            self.select_zip_entry().await?;
+        }
+
         // Determine the file extension type using the helper from `extension.rs`.
-        let extension = FileExtension::from_path(&self.absolute_path);
+        let extension = self.source_extension();
 
         // Match on the determined extension to call the correct reader function.
         let (df, detected_delimiter) = match &extension {
+            // Multi-record text files (e.g., SPED `|C100|...|`): one table per record type.
+            FileExtension::Csv | FileExtension::Unknown(_)
+                if is_multi_record_file(&self.entry_path().await?)? =>
+            {
+                self.read_record_type_data().await?
+            }
             FileExtension::Csv => self.read_csv_data().await?,
             FileExtension::Json => self.read_json_data().await?,
             FileExtension::NDJson => self.read_ndjson_data().await?,
             FileExtension::Parquet => self.read_parquet_data().await?,
+            FileExtension::Excel => self.read_excel_data().await?,
+            FileExtension::Sqlite => self.read_sqlite_data().await?,
+            FileExtension::Iceberg => self.read_iceberg_data().await?,
             // Handle unsupported or missing extensions with specific errors.
+            FileExtension::Zip => {
+                return Err(PolarsViewError::FileType(format!(
+                    "Nested zip archives are not supported: `{}`",
+                    self.absolute_path.display()
+                )));
+            }
             FileExtension::Unknown(ext) => {
                 return Err(PolarsViewError::FileType(format!(
                     "Unsupported extension: `{}` for file: `{}`",
@@ -280,6 +479,9 @@ impl DataFilter {
             }
         };
 
+        // Preview mode: CSV, NDJSON and Parquet stop reading early, the others are cut here.
+        let df = limit_rows(df, self.head_rows);
+
         // If reading a CSV successfully detected a working delimiter, update the filters state.
         // This ensures the UI reflects the delimiter actually used.
         if let Some(byte) = detected_delimiter {
@@ -294,6 +496,38 @@ impl DataFilter {
         Ok((df, extension)) // Return the loaded DataFrame and the detected extension.
     }
 
+    /// The `FileExtension` of the data read: of the selected entry for a zip archive,
+    /// of `absolute_path` otherwise.
+    pub fn source_extension(&self) -> FileExtension {
+        match &self.zip_entry {
+            Some(entry) => FileExtension::from_path(Path::new(entry)),
+            None => FileExtension::from_path(&self.absolute_path),
+        }
+    }
+
+    /// Lists the supported entries of the zip archive and updates `zip_entries`/`zip_entry`
+    /// (the first entry, unless the selected one is in the archive).
+    async fn select_zip_entry(&mut self) -> PolarsViewResult<()> {
+        let path = self.absolute_path.clone();
+        self.zip_entries = spawn_blocking(move || list_zip_entries(&path)).await??;
+
+        if !self
+            .zip_entry
+            .as_ref()
+            .is_some_and(|entry| self.zip_entries.contains(entry))
+        {
+            self.zip_entry = self.zip_entries.first().cloned();
+        }
+
+        if self.zip_entry.is_none() {
+            return Err(PolarsViewError::FileType(format!(
+                "No supported files in zip archive: `{}`",
+                self.absolute_path.display()
+            )));
+        }
+        Ok(())
+    }
+
     // --- Data Reading Helper Methods ---
 
     /// Reads a standard JSON file into a Polars DataFrame.
@@ -303,16 +537,30 @@ impl DataFilter {
     /// A `PolarsViewResult` containing `(DataFrame, None)` (delimiter is not applicable to JSON).
     async fn read_json_data(&self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
         tracing::debug!("Reading JSON data from: {}", self.absolute_path.display());
-        let file = File::open(&self.absolute_path)?;
-        let infer_schema_rows_for_task = self.infer_schema_rows;
+        let infer_schema_len = NonZero::new(self.infer_schema_rows);
 
         // Execute the blocking read operation on a separate thread
-        let df = execute_polars_blocking(move || {
-            JsonReader::new(file)
-                .infer_schema_len(NonZero::new(infer_schema_rows_for_task))
-                .finish()
-        })
-        .await?;
+        // A JSON array is read whole: a truncated array doesn't parse.
+        let df = match self.data_source(None).await? {
+            DataSource::File(path) => {
+                let file = File::open(path)?;
+                execute_polars_blocking(move || {
+                    JsonReader::new(file)
+                        .infer_schema_len(infer_schema_len)
+                        .finish()
+                })
+                .await?
+            }
+            DataSource::Memory(bytes) => {
+                let cursor = Cursor::new(SharedBytes(bytes));
+                execute_polars_blocking(move || {
+                    JsonReader::new(cursor)
+                        .infer_schema_len(infer_schema_len)
+                        .finish()
+                })
+                .await?
+            }
+        };
 
         tracing::debug!("JSON read complete. Shape: {:?}", df.shape());
 
@@ -328,8 +576,27 @@ impl DataFilter {
         tracing::debug!("Reading NDJSON data from: {}", self.absolute_path.display());
 
         // Clone data from self needed for the task closure.
-        let pl_ref_path = PlRefPath::try_from_path(&self.absolute_path)?;
         let infer_schema_rows_for_task = self.infer_schema_rows;
+        let head_rows = self.head_rows;
+
+        let pl_ref_path = match self.data_source(self.head_lines()).await? {
+            DataSource::File(path) => PlRefPath::try_from_path(&path)?,
+            // Compressed file: the decompressed lines, read in memory.
+            DataSource::Memory(bytes) => {
+                let cursor = Cursor::new(SharedBytes(bytes));
+                let df = execute_polars_blocking(move || {
+                    JsonReader::new(cursor)
+                        .with_json_format(JsonFormat::JsonLines)
+                        .infer_schema_len(NonZero::new(infer_schema_rows_for_task))
+                        .with_ignore_errors(true)
+                        .finish()
+                })
+                .await?;
+
+                tracing::debug!("NDJSON read complete. Shape: {:?}", df.shape());
+                return Ok((df, None));
+            }
+        };
 
         // *** Use the helper function ***
         let df = execute_polars_blocking(move || {
@@ -339,6 +606,7 @@ impl DataFilter {
                 .low_memory(false) // Option to optimize for memory.
                 .with_infer_schema_length(NonZero::new(infer_schema_rows_for_task))
                 .with_ignore_errors(true)
+                .with_n_rows(head_rows)
                 .finish()?; // Returns PolarsResult<LazyFrame> (this finish() isn't the main blocking part)
 
             // Collect the lazy frame - THIS IS THE BLOCKING PART
@@ -352,6 +620,9 @@ impl DataFilter {
 
     /// Reads an Apache Parquet file into a Polars DataFrame.
     ///
+    /// Encrypted files are read with `parquet_key` (or the `PARQUET_KEY_ENV` key); without
+    /// a key, or with a wrong one, `PolarsViewError::ParquetKeyRequired` asks for it.
+    ///
     /// ### Returns
     /// A `PolarsViewResult` containing `(DataFrame, None)`.
     async fn read_parquet_data(&self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
@@ -360,17 +631,28 @@ impl DataFilter {
             self.absolute_path.display()
         );
 
+        let path = self.entry_path().await?;
+        if is_encrypted_parquet(&path)? {
+            return self.read_encrypted_parquet_data().await;
+        }
+
         // Clone data from self needed for the task closure.
-        let pl_ref_path = PlRefPath::try_from_path(&self.absolute_path)?;
+        let pl_ref_path = PlRefPath::try_from_path(&path)?;
         let args = ScanArgsParquet {
             // ScanArgsParquet should be Send
             low_memory: false, // Configure scan arguments as needed.
             ..Default::default()
         };
+        let head_rows = self.head_rows;
 
         let df = execute_polars_blocking(move || {
             // Use `LazyFrame::scan_parquet` for efficient scanning.
             let lazyframe = LazyFrame::scan_parquet(pl_ref_path, args)?; // Returns PolarsResult<LazyFrame>
+            // Preview mode: only the row groups of the first rows are read.
+            let lazyframe = match head_rows {
+                Some(rows) => lazyframe.limit(rows as IdxSize),
+                None => lazyframe,
+            };
 
             // Collect into an eager DataFrame - THIS IS THE BLOCKING/COMPUTE PART.
             lazyframe.with_streaming(true).collect() // Returns PolarsResult<DataFrame>
@@ -382,8 +664,153 @@ impl DataFilter {
         Ok((df, None))
     }
 
+    /// Reads an encrypted Parquet file (see `read_parquet_data`).
+    async fn read_encrypted_parquet_data(&self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
+        // Without the feature, no key would help: don't ask for one.
+        if !cfg!(feature = "parquet-encryption") {
+            return Err(PolarsViewError::FeatureDisabled("parquet-encryption"));
+        }
+
+        let key_required = |reason: String| PolarsViewError::ParquetKeyRequired {
+            path: self.absolute_path.clone(),
+            reason,
+        };
+
+        let Some(key) = self.parquet_key.clone().or_else(ParquetKey::from_env) else {
+            return Err(key_required(format!(
+                "a key is required (or set {PARQUET_KEY_ENV})"
+            )));
+        };
+
+        let path = self.entry_path().await?;
+        let head_rows = self.head_rows;
+        let df = spawn_blocking(move || read_encrypted_parquet(&path, &key, head_rows))
+            .await?
+            .map_err(|error| match error {
+                PolarsViewError::ParquetEncryption(reason) => key_required(reason),
+                error => error,
+            })?;
+
+        Ok((df, None))
+    }
+
+    /// Reads one worksheet of an Excel/OpenDocument workbook into a Polars DataFrame.
+    ///
+    /// Reads `sheet_name` (or the first sheet) and updates `sheet_name`/`sheet_names`
+    /// so the Query panel can offer a sheet selector.
+    ///
+    /// ### Returns
+    /// A `PolarsViewResult` containing `(DataFrame, None)`.
+    async fn read_excel_data(&mut self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
+        tracing::debug!("Reading Excel data from: {}", self.absolute_path.display());
+
+        let path = self.entry_path().await?;
+        let sheet_name = self.sheet_name.clone();
+
+        let sheet =
+            spawn_blocking(move || read_excel_sheet(&path, sheet_name.as_deref())).await??;
+
+        self.sheet_name = Some(sheet.sheet_name);
+        self.sheet_names = sheet.sheet_names;
+
+        tracing::debug!("Excel read complete. Shape: {:?}", sheet.df.shape());
+        Ok((sheet.df, None))
+    }
+
+    /// Reads one table of a SQLite database into a Polars DataFrame.
+    ///
+    /// Reads `db_table` (or the first table) and updates `db_table`/`db_tables`
+    /// so the Query panel can offer a table selector.
+    ///
+    /// ### Returns
+    /// A `PolarsViewResult` containing `(DataFrame, None)`.
+    async fn read_sqlite_data(&mut self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
+        tracing::debug!("Reading SQLite data from: {}", self.absolute_path.display());
+
+        let path = self.entry_path().await?;
+        let db_table = self.db_table.clone();
+
+        let table = spawn_blocking(move || read_sqlite_table(&path, db_table.as_deref())).await??;
+
+        self.db_table = Some(table.table_name);
+        self.db_tables = table.table_names;
+
+        tracing::debug!("SQLite read complete. Shape: {:?}", table.df.shape());
+        Ok((table.df, None))
+    }
+
+    /// Reads the current snapshot of an Apache Iceberg table (see `iceberg.rs`).
+    ///
+    /// ### Returns
+    /// A `PolarsViewResult` containing `(DataFrame, None)`.
+    async fn read_iceberg_data(&self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
+        tracing::debug!(
+            "Reading Iceberg table from: {}",
+            self.absolute_path.display()
+        );
+
+        let path = self.absolute_path.clone();
+        let head_rows = self.head_rows;
+        let df = spawn_blocking(move || read_iceberg_table(&path, head_rows)).await??;
+
+        Ok((df, None))
+    }
+
+    /// Reads a multi-record file (see `record_types.rs`), split by record type.
+    ///
+    /// Returns the records of `record_type` (or of the first record type) and updates
+    /// `record_type`/`record_types` so the table can offer one sub-tab per record type.
+    /// The file is split once; another record type is taken from `record_groups`.
+    ///
+    /// ### Returns
+    /// A `PolarsViewResult` containing `(DataFrame, None)`.
+    async fn read_record_type_data(&mut self) -> PolarsViewResult<(DataFrame, Option<u8>)> {
+        tracing::debug!(
+            "Reading multi-record data from: {}",
+            self.absolute_path.display()
+        );
+
+        let path = self.entry_path().await?;
+        let mut cache = std::mem::take(&mut self.record_groups);
+        let (cache, groups) = spawn_blocking(move || {
+            let groups = cache.groups(&path);
+            (cache, groups)
+        })
+        .await?;
+        self.record_groups = cache;
+        let groups = groups?;
+
+        self.record_types = groups
+            .iter()
+            .map(|group| (group.record_type.clone(), group.df.height()))
+            .collect();
+
+        let group = groups
+            .iter()
+            .enumerate()
+            .find(|(index, group)| match &self.record_type {
+                Some(record_type) => &group.record_type == record_type,
+                None => *index == 0,
+            })
+            .map(|(_index, group)| group)
+            .ok_or_else(|| {
+                PolarsViewError::FileType(format!(
+                    "Record type `{}` not found in file: `{}`",
+                    self.record_type.as_deref().unwrap_or_default(),
+                    self.absolute_path.display()
+                ))
+            })?;
+
+        self.record_type = Some(group.record_type.clone());
+
+        tracing::debug!("Multi-record read complete. Shape: {:?}", group.df.shape());
+        Ok((group.df.clone(), None))
+    }
+
     /// Reads a CSV file, attempting automatic delimiter detection if the initial one fails.
     /// Iterates through common delimiters and tries reading a small chunk first for efficiency.
+    /// For each delimiter, the quote character and the escape style are sniffed from the
+    /// start of the file (see `csv_sniffer.rs`).
     ///
     /// ### Returns
     /// A `PolarsViewResult` containing `(DataFrame, Option<u8>)` where `Option<u8>` is the
@@ -405,6 +832,10 @@ impl DataFilter {
                 .collect::<Vec<_>>()
         );
 
+        // The data (decompressed and transcoded to UTF-8) and its start, read once for all
+        // the delimiters.
+        let input = self.csv_input().await?;
+
         // Look at the next element of the iterator without consuming it.
         let mut iterator = delimiters_to_try.iter().peekable();
 
@@ -413,10 +844,13 @@ impl DataFilter {
             // If peek() returns None, it means the current item was the last one
             let is_last_element = iterator.peek().is_none();
 
+            // Quote character and escape style, sniffed once for both reads.
+            let (source, dialect) = self.csv_dialect(&input, delimiter).await?;
+
             // 1. Quick Check: Try reading only a small number of rows (NROWS_CHECK).
             // This fails fast if the delimiter is fundamentally wrong (e.g., results in 1 column).
             if let Ok(schema) = self
-                .attempt_csv_parse_structure(delimiter, is_last_element)
+                .attempt_csv_parse_structure(&source, dialect, delimiter, is_last_element)
                 .await
             {
                 // 2. Full Read: If the quick check passed, attempt to read the entire file.
@@ -424,7 +858,10 @@ impl DataFilter {
                     "Trying to read full CSV file with delimiter: '{}'",
                     delimiter as char
                 );
-                match self.attempt_read_csv(delimiter, &schema).await {
+                match self
+                    .attempt_read_csv(&source, dialect, delimiter, &schema)
+                    .await
+                {
                     Ok(lazyframe) => {
                         // Success! Return the DataFrame and the delimiter that worked.
                         tracing::info!(
@@ -465,12 +902,77 @@ impl DataFilter {
         Err(error)
     }
 
+    /// The CSV file prepared for reading (see `CsvInput`): its data (see `data_source` for
+    /// compressed files and zip entries), transcoded to UTF-8 if `csv_encoding` is another
+    /// encoding, and its start, sniffed for each delimiter.
+    pub(crate) async fn csv_input(&self) -> PolarsViewResult<CsvInput> {
+        let source = self.data_source(self.head_lines()).await?;
+        let encoding = self.csv_encoding;
+        spawn_blocking(move || -> PolarsViewResult<CsvInput> {
+            let utf8_source = transcode_to_utf8(source, encoding)?;
+            Ok(CsvInput::new(utf8_source)?)
+        })
+        .await?
+    }
+
+    /// Sniffs the quote character and escape style of the CSV `input` for `delimiter`
+    /// (the characters set in `csv_quote_char`/`csv_escape_char` replace the sniffed ones).
+    ///
+    /// Returns the data to read and the dialect (see `CsvInput::prepare`).
+    pub(crate) async fn csv_dialect(
+        &self,
+        input: &CsvInput,
+        delimiter: u8,
+    ) -> PolarsViewResult<(DataSource, CsvDialect)> {
+        let input = input.clone();
+        let dialect_override = DialectOverride {
+            quote_char: self.csv_quote_char.bytes().next(),
+            escape_char: self.csv_escape_char.bytes().next(),
+        };
+        spawn_blocking(move || input.prepare(delimiter, dialect_override)).await?
+    }
+
+    /// The file to read: `absolute_path`, or the extracted `zip_entry` of a zip archive.
+    async fn entry_path(&self) -> PolarsViewResult<PathBuf> {
+        let path = self.absolute_path.clone();
+        let zip_entry = self.zip_entry.clone();
+        spawn_blocking(move || match zip_entry {
+            Some(entry) => extract_zip_entry(&path, &entry),
+            None => Ok(path),
+        })
+        .await?
+    }
+
+    /// The data of the text file to read (see `entry_path`): the file itself, or its
+    /// bytes decompressed in memory for a compressed file (e.g., `data.csv.gz`), up to
+    /// `max_lines` lines (see `head_lines`).
+    async fn data_source(&self, max_lines: Option<usize>) -> PolarsViewResult<DataSource> {
+        let path = self.entry_path().await?;
+        spawn_blocking(move || DataSource::open(&path, max_lines)).await?
+    }
+
+    /// The lines of a compressed text file decompressed for a preview (`head_rows`): the
+    /// skipped rows, the header and twice the previewed rows, a margin for comment lines and
+    /// records spanning several lines. `None`: the whole file.
+    fn head_lines(&self) -> Option<usize> {
+        self.head_rows.map(|rows| {
+            rows.saturating_mul(2)
+                .saturating_add(self.csv_skip_rows)
+                .saturating_add(1)
+        })
+    }
+
+    /// The comment prefix of CSV lines (`None`: no comments).
+    fn csv_comment(&self) -> Option<&str> {
+        Some(self.csv_comment_prefix.as_str()).filter(|prefix| !prefix.is_empty())
+    }
+
     /// Retrieves the CSV separator byte from the `csv_delimiter` String configuration.
     ///
     /// ### Returns
     /// `Ok(u8)` containing the first byte, or `Err(PolarsViewError::InvalidDelimiter)`
     /// if the string is empty or contains multi-byte characters (only first byte is used).
-    fn get_csv_separator(&self) -> PolarsViewResult<u8> {
+    pub(crate) fn get_csv_separator(&self) -> PolarsViewResult<u8> {
         self.csv_delimiter
             .as_bytes() // Convert String to byte slice.
             .first() // Get the first byte.
@@ -483,6 +985,8 @@ impl DataFilter {
     /// using a specific delimiter and validates the result.
     async fn attempt_csv_parse_structure(
         &self,
+        source: &DataSource,
+        dialect: CsvDialect,
         delimiter: u8,
         is_last_element: bool,
     ) -> PolarsViewResult<Arc<Schema>> {
@@ -496,10 +1000,15 @@ impl DataFilter {
             delimiter as char,
         );
 
-        let file_path = &self.absolute_path;
-
-        // Perform a partial read from the file using the given delimiter.
-        let data_frame = read_csv_partial_from_path(delimiter, ROW_LIMIT, file_path).await?;
+        // Perform a partial read from the file using the given delimiter and sniffed quotes.
+        let data_frame = read_csv_partial(
+            delimiter,
+            dialect.quote_char,
+            (self.csv_comment(), self.csv_skip_rows),
+            ROW_LIMIT,
+            source,
+        )
+        .await?;
 
         // **Basic Validation**: Check resulting width (important for delimiter detection loops)
         // it's highly likely the delimiter was incorrect. Return an error early.
@@ -530,8 +1039,10 @@ impl DataFilter {
         Ok(data_frame.schema().clone())
     }
 
-    async fn attempt_read_csv(
+    pub(crate) async fn attempt_read_csv(
         &self,
+        source: &DataSource,
+        dialect: CsvDialect,
         delimiter: u8,
         previous_scheme: &Arc<Schema>,
     ) -> PolarsViewResult<LazyFrame> {
@@ -570,7 +1081,58 @@ impl DataFilter {
             };
         }
 
-        let pl_ref_path = PlRefPath::try_from_path(&self.absolute_path)?;
+        // Column types picked in the type editor that the reader parses directly.
+        let picked_types: Vec<(&String, ColumnType)> = self
+            .column_types
+            .iter()
+            .filter(|(name, column_type)| {
+                column_type.is_read_type() && previous_scheme.contains(name.as_str())
+            })
+            .map(|(name, column_type)| (name, *column_type))
+            .collect();
+        if !picked_types.is_empty() {
+            let mut override_schema = dtypes_opt
+                .map(|schema| schema.as_ref().clone())
+                .unwrap_or_default();
+            for (name, column_type) in picked_types {
+                override_schema.insert(name.as_str().into(), column_type.dtype());
+            }
+            dtypes_opt = Some(Arc::new(override_schema));
+        }
+
+        // Quote character and escape style (backslash escapes are read from an unescaped copy).
+        tracing::debug!(
+            "CSV dialect for delimiter '{}': {:?}",
+            delimiter as char,
+            dialect
+        );
+
+        let path = match source {
+            DataSource::File(path) => path,
+            // Compressed file: the decompressed bytes are read eagerly, with the same options.
+            DataSource::Memory(bytes) => {
+                let parse_options = CsvParseOptions::default()
+                    .with_encoding(CsvEncoding::LossyUtf8)
+                    .with_try_parse_dates(true)
+                    .with_separator(delimiter)
+                    .with_quote_char(Some(dialect.quote_char))
+                    .with_comment_prefix(self.csv_comment())
+                    .with_missing_is_null(true);
+                let reader = CsvReadOptions::default()
+                    .with_parse_options(parse_options)
+                    .with_has_header(true)
+                    .with_skip_rows(self.csv_skip_rows)
+                    .with_infer_schema_length(Some(self.infer_schema_rows))
+                    .with_schema_overwrite(dtypes_opt)
+                    .with_ignore_errors(true)
+                    .with_n_rows(self.head_rows)
+                    .with_rechunk(true)
+                    .into_reader_with_file_handle(Cursor::new(SharedBytes(bytes.clone())));
+                let df = execute_polars_blocking(move || reader.finish()).await?;
+                return Ok(df.lazy());
+            }
+        };
+        let pl_ref_path = PlRefPath::try_from_path(path)?;
 
         // Configure the LazyCsvReader using settings from `self`.
         let lazyframe = LazyCsvReader::new(pl_ref_path)
@@ -579,12 +1141,15 @@ impl DataFilter {
             .with_has_header(true) // Assume a header row.
             .with_try_parse_dates(true) // Attempt automatic date parsing.
             .with_separator(delimiter) // Use the specified delimiter.
+            .with_quote_char(Some(dialect.quote_char)) // Use the sniffed quote character.
+            .with_comment_prefix(self.csv_comment().map(PlSmallStr::from)) // Skip comment lines.
+            .with_skip_rows(self.csv_skip_rows) // Skip the rows before the header.
             .with_infer_schema_length(Some(self.infer_schema_rows)) // Use filter setting for inference.
             .with_dtype_overwrite(dtypes_opt)
             .with_ignore_errors(true) // Rows with parsing errors become nulls instead of stopping the read.
             .with_missing_is_null(true) // Treat missing fields as null.
             .with_null_values(None) // Apply fn replace_values_with_null()
-            .with_n_rows(None) // Apply row limit if specified.
+            .with_n_rows(self.head_rows) // Preview mode: only the first rows.
             .with_decimal_comma(false) // If files use ',' as decimal separator.
             .with_row_index(None) // Apply fn add_row_index_column()
             .with_rechunk(true) // Rechunk the memory to contiguous chunks when parsing is done.
@@ -648,13 +1213,15 @@ impl DataFilter {
     ///
     /// ### Arguments
     /// * `ui`: The `egui::Ui` context for drawing the widgets.
+    /// * `format`: The display settings; its frozen and hidden columns guide the SQL examples.
     ///
     /// ### Returns
     /// * `Some(DataFilter)`: If any filter setting was changed by the user in this frame.
     /// * `None`: If no changes were detected.
-    pub fn render_query(&mut self, ui: &mut Ui) -> Option<DataFilter> {
+    pub fn render_query(&mut self, ui: &mut Ui, format: &DataFormat) -> Option<DataFilter> {
         // Clone the state *before* rendering UI widgets to detect changes later.
         let filters_before_render = self.clone();
+        let columns = self.example_columns(format);
         let mut result = None;
 
         let width_min = 450.0; // Minimum width for the grid area.
@@ -684,26 +1251,62 @@ impl DataFilter {
 
                     self.render_normalize_numbers(ui);
 
+                    self.render_parse_dates(ui);
+
+                    self.render_categorical_strings(ui);
+
+                    self.render_flatten_structs(ui);
+
                     self.render_null_values(ui);
 
-                    // Input for schema inference length (only for relevant file types).
+                    // Input for schema inference length (only for relevant file types,
+                    // compressed or not: `data.csv.gz` is a CSV file).
+                    let extension = self.source_extension();
                     if matches!(
-                        self.get_extension().as_deref(), // Get extension as &str
-                        Some("csv" | "json" | "ndjson")  // Check if it's one of these
+                        extension,
+                        FileExtension::Csv | FileExtension::Json | FileExtension::NDJson
                     ) {
                         self.render_schema_length_input(ui);
                     }
 
-                    // CSV-specific settings: delimiter.
-                    if self.get_extension().as_deref() == Some("csv") {
+                    // CSV-specific settings: delimiter, quoting, comments and skipped rows.
+                    if extension == FileExtension::Csv {
                         self.render_csv_delimiter(ui);
+                        self.render_csv_quoting(ui);
+                        self.render_csv_encoding(ui);
+                    }
+
+                    // Lazy scan (only for files that can be scanned, see `LazyTable`).
+                    if LazyTable::supports(&self.absolute_path) {
+                        self.render_lazy_scan(ui);
+                    }
+
+                    // Preview mode: read only the first rows.
+                    self.render_head_rows(ui);
+
+                    // Excel-specific settings: worksheet (only for multi-sheet workbooks).
+                    if self.sheet_names.len() > 1 {
+                        self.render_sheet_selector(ui);
+                    }
+
+                    // SQLite-specific settings: table of the database.
+                    if !self.db_tables.is_empty() {
+                        self.render_db_table_selector(ui);
+                    }
+
+                    // Zip-specific settings: entry of the archive.
+                    if !self.zip_entries.is_empty() {
+                        self.render_zip_entry_selector(ui);
                     }
 
                     // Input for table name used in SQL.
                     self.render_table_name_input(ui);
 
-                    // Multiline input for the SQL query.
-                    self.render_sql_query_input(ui);
+                    // Named queries run before the main query.
+                    self.render_query_steps(ui);
+
+                    // Multiline input for the SQL query (and its history).
+                    let run_from_history = self.render_sql_query_input(ui, &columns);
 
                     // --- Change Detection & Apply Button ---
 
@@ -715,11 +1318,27 @@ impl DataFilter {
                     }
 
                     if (self.csv_delimiter != filters_before_render.csv_delimiter)
+                        || (self.csv_quote_char != filters_before_render.csv_quote_char)
+                        || (self.csv_escape_char != filters_before_render.csv_escape_char)
+                        || (self.csv_comment_prefix != filters_before_render.csv_comment_prefix)
+                        || (self.csv_skip_rows != filters_before_render.csv_skip_rows)
+                        || (self.csv_encoding != filters_before_render.csv_encoding)
                         || (self.infer_schema_rows != filters_before_render.infer_schema_rows)
+                        || (self.sheet_name != filters_before_render.sheet_name)
+                        || (self.db_table != filters_before_render.db_table)
+                        || (self.zip_entry != filters_before_render.zip_entry)
+                        || (self.lazy_scan != filters_before_render.lazy_scan)
+                        || (self.head_rows != filters_before_render.head_rows)
                     {
                         self.read_data_from_file = true;
                     }
 
+                    // A query re-run from the history is applied at once.
+                    if run_from_history {
+                        self.apply_sql = true;
+                        result = Some(self.clone());
+                    }
+
                     // Add the "Apply SQL commands" button.
                     ui.label(""); // For alignment.
                     ui.with_layout(Layout::top_down(Align::Center), |ui| {
@@ -738,7 +1357,7 @@ impl DataFilter {
         ); // End allocation
 
         // Display the SQL examples section (collapsible).
-        self.render_sql_examples(ui);
+        self.render_sql_examples(ui, &columns);
 
         result // Return the potentially updated filters.
     }
@@ -787,6 +1406,37 @@ impl DataFilter {
         ui.end_row();
     }
 
+    /// Renders the checkbox for the "Lazy Scan" option.
+    /// Modifies `self.lazy_scan` directly.
+    fn render_lazy_scan(&mut self, ui: &mut Ui) {
+        ui.label("Lazy Scan:");
+        ui.checkbox(&mut self.lazy_scan, "").on_hover_text(
+            "Read only the rows of the displayed page (paged table), for files larger than memory.\n\
+            Statistics, charts and search then apply to the loaded page.",
+        );
+        ui.end_row();
+    }
+
+    /// Renders the "Preview Rows" option: a checkbox and the number of rows to read.
+    /// Modifies `self.head_rows` directly.
+    fn render_head_rows(&mut self, ui: &mut Ui) {
+        ui.label("Preview Rows:");
+        ui.horizontal(|ui| {
+            let mut preview = self.head_rows.is_some();
+            if ui
+                .checkbox(&mut preview, "")
+                .on_hover_text("Read only the first rows of the file, for an instant preview.")
+                .changed()
+            {
+                self.head_rows = preview.then_some(DEFAULT_HEAD_ROWS);
+            }
+            if let Some(rows) = &mut self.head_rows {
+                ui.add(DragValue::new(rows).speed(100).range(1..=usize::MAX));
+            }
+        });
+        ui.end_row();
+    }
+
     fn render_exclude_columns(&mut self, ui: &mut Ui) {
         // --- Row 1: Feature Checkbox ---
         ui.label("Remove Columns:");
@@ -868,6 +1518,244 @@ Example Patterns:
         }
     }
 
+    /// Renders the checkbox and inputs (regex, format, time zone) for parsing String
+    /// columns as dates. Modifies the `parse_dates` and `date_*` fields directly.
+    fn render_parse_dates(&mut self, ui: &mut Ui) {
+        ui.label("Parse Dates:");
+        ui.checkbox(&mut self.parse_dates, "").on_hover_text(
+            "Parse String columns selected by name (via regex) as dates with a given format,\n\
+            for the regional formats not recognized automatically.\n\
+            Example: '31/01/2024' (String) to 2024-01-31 (Date).",
+        );
+        ui.end_row();
+
+        if self.parse_dates {
+            ui.label("\tRegex:");
+            ui.add(TextEdit::singleline(&mut self.date_regex).desired_width(f32::INFINITY))
+                .on_hover_text(
+                    "Regex pattern to select String columns by name.\n\
+                    Use '*' for ALL String columns or '^PATTERN$' for specific names.\n\
+                    Example: ^(Data|Date).*$",
+                );
+            ui.end_row();
+
+            ui.label("\tFormat:");
+            ui.add(TextEdit::singleline(&mut self.date_format).desired_width(f32::INFINITY))
+                .on_hover_text(
+                    "Date format (strftime syntax). Values that don't match become null.\n\
+                    Examples:\n\
+                    %d/%m/%Y          31/01/2024\n\
+                    %m/%d/%Y          01/31/2024\n\
+                    %d.%m.%Y %H:%M    31.01.2024 08:30 (Datetime)\n\
+                    %Y%m%d            20240131",
+                );
+            ui.end_row();
+
+            if format_has_time(&self.date_format) {
+                ui.label("\tTime Zone:");
+                ui.add(
+                    TextEdit::singleline(&mut self.date_time_zone)
+                        .hint_text("Local (as written)")
+                        .desired_width(f32::INFINITY),
+                )
+                .on_hover_text(
+                    "Time zone of the written times.\n\
+                    Empty: local time, kept as written.\n\
+                    UTC, or an IANA name (e.g., America/Sao_Paulo): the times are in that zone.",
+                );
+                ui.end_row();
+            }
+        }
+    }
+
+    /// Renders the data type editor ("Columns" > "Data Types"): one dropdown per column of
+    /// `schema` (the columns as read), "Inferred" keeping the type found by the reader.
+    ///
+    /// ### Returns
+    /// `Some(updated_filter)` if a type was changed: the file must be read again.
+    pub fn render_column_types(&mut self, ui: &mut Ui, schema: &Schema) -> Option<DataFilter> {
+        let types_before = self.column_types.clone();
+
+        ScrollArea::vertical()
+            .id_salt("column_types")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                Grid::new("column_types_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (name, dtype) in schema.iter() {
+                            ui.label(name.as_str());
+                            let mut picked = self.column_types.get(name.as_str()).copied();
+                            let inferred = format!("Inferred ({dtype})");
+                            ComboBox::from_id_salt(("column_type", name.as_str()))
+                                .selected_text(picked.map_or(inferred.as_str(), ColumnType::label))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut picked, None, inferred.as_str());
+                                    for column_type in ColumnType::ALL {
+                                        ui.selectable_value(
+                                            &mut picked,
+                                            Some(column_type),
+                                            column_type.label(),
+                                        );
+                                    }
+                                });
+                            match picked {
+                                Some(column_type) => {
+                                    self.column_types.insert(name.to_string(), column_type);
+                                }
+                                None => {
+                                    self.column_types.remove(name.as_str());
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if self.column_types == types_before {
+            return None;
+        }
+        self.read_data_from_file = true;
+        self.apply_sql = true;
+        Some(self.clone())
+    }
+
+    /// Renders the column rename editor ("Columns" > "Rename Columns"): the current name of
+    /// each column of `schema` (the columns as read). Double-clicking a name edits it in
+    /// place (Enter renames, Escape cancels); "↺" gives a renamed column its name back.
+    ///
+    /// ### Returns
+    /// `Some(updated_filter)` if a column was renamed: the SQL query must run again.
+    pub fn render_column_renames(&mut self, ui: &mut Ui, schema: &Schema) -> Option<DataFilter> {
+        let renames_before = self.column_renames.clone();
+        let names: Vec<&str> = schema.iter_names().map(|name| name.as_str()).collect();
+
+        // Column being edited (name as read), its draft name and the last error.
+        let edit_id = ui.id().with("column_rename_edit");
+        let (mut editing, mut draft, mut error): (Option<String>, String, String) =
+            ui.data(|data| data.get_temp(edit_id).unwrap_or_default());
+
+        ScrollArea::vertical()
+            .id_salt("column_renames")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                Grid::new("column_renames_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for &name in &names {
+                            let current = self
+                                .column_renames
+                                .get(name)
+                                .map_or(name, String::as_str)
+                                .to_string();
+
+                            if editing.as_deref() == Some(name) {
+                                let response =
+                                    ui.add(TextEdit::singleline(&mut draft).desired_width(200.0));
+                                response.request_focus();
+                                if ui.input(|i| i.key_pressed(Key::Escape)) {
+                                    editing = None;
+                                    error.clear();
+                                } else if response.lost_focus()
+                                    && ui.input(|i| i.key_pressed(Key::Enter))
+                                {
+                                    match validate_rename(
+                                        &names,
+                                        &self.column_renames,
+                                        name,
+                                        &draft,
+                                    ) {
+                                        Ok(new_name) if new_name == name => {
+                                            self.column_renames.remove(name);
+                                            editing = None;
+                                            error.clear();
+                                        }
+                                        Ok(new_name) => {
+                                            self.column_renames.insert(name.to_string(), new_name);
+                                            editing = None;
+                                            error.clear();
+                                        }
+                                        Err(reason) => error = reason,
+                                    }
+                                }
+                            } else if ui
+                                .add(Label::new(current.as_str()).sense(Sense::click()))
+                                .on_hover_text("Double-click to rename.")
+                                .double_clicked()
+                            {
+                                editing = Some(name.to_string());
+                                draft = current.clone();
+                                error.clear();
+                            }
+
+                            ui.horizontal(|ui| {
+                                if current != name {
+                                    ui.weak(format!("(was {name})"));
+                                    if ui
+                                        .small_button("↺")
+                                        .on_hover_text("Restore the name")
+                                        .clicked()
+                                    {
+                                        self.column_renames.remove(name);
+                                    }
+                                }
+                            });
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if !error.is_empty() {
+            ui.colored_label(Color32::RED, &error);
+        }
+        ui.data_mut(|data| data.insert_temp(edit_id, (editing, draft, error)));
+
+        if self.column_renames == renames_before {
+            return None;
+        }
+        self.apply_sql = true;
+        Some(self.clone())
+    }
+
+    /// Renders the checkbox for flattening Struct columns.
+    /// Modifies `self.flatten_structs` directly.
+    fn render_flatten_structs(&mut self, ui: &mut Ui) {
+        ui.label("Flatten Structs:");
+        ui.checkbox(&mut self.flatten_structs, "").on_hover_text(
+            "Replace nested (Struct) columns by one column per field,
+            named with dots (e.g., `customer.address.city`).",
+        );
+        ui.end_row();
+    }
+
+    /// Renders the checkbox and ratio threshold for converting String columns to Categorical.
+    /// Modifies `self.categorical` and `self.categorical_ratio` directly.
+    fn render_categorical_strings(&mut self, ui: &mut Ui) {
+        ui.label("Categorical Strings:");
+        ui.checkbox(&mut self.categorical, "").on_hover_text(
+            "Convert String columns with many repeated values to Categorical to cut memory.\n\
+            Memory saved per column is reported in the Info panel.",
+        );
+        ui.end_row();
+
+        if self.categorical {
+            ui.label("\tMax Unique Ratio:");
+            ui.add(
+                DragValue::new(&mut self.categorical_ratio)
+                    .speed(0.01)
+                    .range(0.0..=1.0)
+                    .max_decimals(2),
+            )
+            .on_hover_text(
+                "Convert a String column only if (unique values / rows) <= this ratio.\n\
+                Example: 0.5 converts columns where at least half of the values are repeats.",
+            );
+            ui.end_row();
+        }
+    }
+
     /// Renders the `TextEdit` widget for specifying custom null values.
     /// Modifies `self.null_values` directly based on user input.
     fn render_null_values(&mut self, ui: &mut Ui) {
@@ -916,6 +1804,149 @@ Example Patterns:
         ui.end_row();
     }
 
+    /// Renders the CSV quote, escape and comment characters and the rows skipped before the
+    /// header, for messy real-world files. Empty characters are sniffed (or unused).
+    fn render_csv_quoting(&mut self, ui: &mut Ui) {
+        ui.label("CSV Quote:");
+        ui.add(
+            TextEdit::singleline(&mut self.csv_quote_char)
+                .char_limit(1)
+                .hint_text("Auto")
+                .desired_width(f32::INFINITY),
+        )
+        .on_hover_text("Quote character of the fields (empty: detected, \" or ').");
+        ui.end_row();
+
+        ui.label("CSV Escape:");
+        ui.add(
+            TextEdit::singleline(&mut self.csv_escape_char)
+                .char_limit(1)
+                .hint_text("Auto")
+                .desired_width(f32::INFINITY),
+        )
+        .on_hover_text(
+            "Character written before a quote inside a quoted field (empty: detected).\n\
+            Example: \\ for \"a \\\"b\\\" c\". The quote character itself: doubled quotes.",
+        );
+        ui.end_row();
+
+        ui.label("CSV Comment:");
+        ui.add(
+            TextEdit::singleline(&mut self.csv_comment_prefix)
+                .hint_text("None")
+                .desired_width(f32::INFINITY),
+        )
+        .on_hover_text("Lines starting with this prefix are skipped (e.g., #).");
+        ui.end_row();
+
+        ui.label("CSV Skip Rows:");
+        ui.add(DragValue::new(&mut self.csv_skip_rows).range(0..=usize::MAX))
+            .on_hover_text("Rows skipped at the start of the file, before the header.");
+        ui.end_row();
+    }
+
+    /// Renders the `ComboBox` for choosing the text encoding of a CSV file.
+    /// Modifies `self.csv_encoding` directly; a change triggers a reload from file.
+    fn render_csv_encoding(&mut self, ui: &mut Ui) {
+        ui.label("Encoding:");
+        ComboBox::from_id_salt("csv_encoding")
+            .selected_text(self.csv_encoding.label())
+            .show_ui(ui, |ui| {
+                for encoding in TextEncoding::ALL {
+                    ui.selectable_value(&mut self.csv_encoding, encoding, encoding.label());
+                }
+            })
+            .response
+            .on_hover_text(
+                "Text encoding of the file. Legacy exports with garbled accents (�)\n\
+                are usually Latin-1 or Windows-1252.",
+            );
+        ui.end_row();
+    }
+
+    /// Renders the `ComboBox` for choosing the worksheet of an Excel workbook.
+    /// Modifies `self.sheet_name` directly; a change triggers a reload from file.
+    fn render_sheet_selector(&mut self, ui: &mut Ui) {
+        ui.label("Sheet:");
+        let selected = self.sheet_name.clone().unwrap_or_default();
+        ComboBox::from_id_salt("excel_sheet")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for name in &self.sheet_names {
+                    ui.selectable_value(&mut self.sheet_name, Some(name.clone()), name);
+                }
+            })
+            .response
+            .on_hover_text("Worksheet of the workbook to load.");
+        ui.end_row();
+    }
+
+    /// Renders the `ComboBox` for choosing the table of a SQLite database.
+    /// Modifies `self.db_table` directly; a change triggers a reload from file.
+    fn render_db_table_selector(&mut self, ui: &mut Ui) {
+        ui.label("DB Table:");
+        let selected = self.db_table.clone().unwrap_or_default();
+        ComboBox::from_id_salt("sqlite_table")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for name in &self.db_tables {
+                    ui.selectable_value(&mut self.db_table, Some(name.clone()), name);
+                }
+            })
+            .response
+            .on_hover_text("Table (or view) of the database to load; the SQL query runs on it.");
+        ui.end_row();
+    }
+
+    /// Renders the `ComboBox` for choosing the entry of a zip archive.
+    /// Modifies `self.zip_entry` directly; a change triggers a reload from file.
+    fn render_zip_entry_selector(&mut self, ui: &mut Ui) {
+        ui.label("Zip Entry:");
+        let selected = self.zip_entry.clone().unwrap_or_default();
+        ComboBox::from_id_salt("zip_entry")
+            .selected_text(selected)
+            .show_ui(ui, |ui| {
+                for name in &self.zip_entries {
+                    ui.selectable_value(&mut self.zip_entry, Some(name.clone()), name);
+                }
+            })
+            .response
+            .on_hover_text("File of the archive to load (extracted to a temporary copy).");
+        ui.end_row();
+    }
+
+    /// Renders one sub-tab per record type of a multi-record file (above the table).
+    ///
+    /// ### Returns
+    /// `true` if the user selected another record type; `record_type` is updated and
+    /// `read_data_from_file`/`apply_sql` are set so the caller can reload.
+    pub fn render_record_type_tabs(&mut self, ui: &mut Ui) -> bool {
+        let mut selected = None;
+
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Record type:");
+            for (record_type, count) in &self.record_types {
+                let is_active = self.record_type.as_ref() == Some(record_type);
+                if ui
+                    .selectable_label(is_active, record_type)
+                    .on_hover_text(format!("{count} records"))
+                    .clicked()
+                    && !is_active
+                {
+                    selected = Some(record_type.clone());
+                }
+            }
+        });
+
+        let changed = selected.is_some();
+        if changed {
+            self.record_type = selected;
+            self.read_data_from_file = true;
+            self.apply_sql = true;
+        }
+        changed
+    }
+
     /// Renders the `TextEdit` widget for the SQL table name.
     /// Modifies `self.table_name` directly.
     fn render_table_name_input(&mut self, ui: &mut Ui) {
@@ -949,10 +1980,13 @@ Example Patterns:
     /// 2. Manage selected tab index using `egui::Memory`.
     /// 3. Render **wrapping horizontal tabs** for examples using `ui.horizontal_wrapped`.
     /// 4. On tab click: update index, copy example to `self.query`.
-    /// 5. Render multiline `TextEdit` bound to `&mut self.query`.
+    /// 5. Render the SQL editor bound to `&mut self.query` (see `render_sql_editor`).
     ///
     /// Note: Actual *triggering* of reload happens in `render_query` based on overall state change detection or Apply click.
-    fn render_sql_query_input(&mut self, ui: &mut Ui) {
+    ///
+    /// Returns `true` if a query of the history should be re-run (see `render_query_history`).
+    fn render_sql_query_input(&mut self, ui: &mut Ui, columns: &ExampleColumns) -> bool {
+        let mut run = false;
         ui.label("SQL Query:"); // Label for the whole section
         ui.vertical(|ui| {
             // Group the examples and editor vertically
@@ -960,15 +1994,19 @@ Example Patterns:
             ui.set_min_width(300.0);
 
             // 1. Generate examples based on the current schema
-            let examples = sql_commands(&self.schema);
+            let examples = sql_commands(&self.schema, columns);
+
+            // Fuzzy search over query history and examples.
+            self.render_sql_search(ui, &examples);
+
+            // Past queries of this table, to edit or re-run.
+            run = self.render_query_history(ui);
+
             if examples.is_empty() {
                 // If no schema or examples, just show the editor
-                ui.add(
-                    TextEdit::multiline(&mut self.query)
-                        .desired_width(f32::INFINITY)
-                        .desired_rows(8) // Slightly more rows if no examples
-                        .font(egui::TextStyle::Monospace),
-                );
+                // Slightly more rows if no examples
+                render_sql_editor(ui, &mut self.query, 8, &self.table_name, &self.schema);
+                self.render_format_sql_button(ui);
                 return; // Skip rendering examples if none exist
             }
 
@@ -1024,25 +2062,249 @@ Example Patterns:
 
             ui.separator(); // Separator between tabs and editor
 
-            // 5. Render the ACTIVE query editor below the tabs
-            ui.add(
-                TextEdit::multiline(&mut self.query)
-                    .desired_width(f32::INFINITY) // Take full available width
-                    .desired_rows(6) // Set preferred number of visible lines
-                    .font(egui::TextStyle::Monospace), // Use a monospace font for SQL
-            )
-            .on_hover_text(
-                "Enter SQL query (Polars SQL).\n\
-                Click Example tabs above.\n\
-                Changes trigger reload on Apply/focus change.",
-            );
+            // 5. Render the ACTIVE query editor below the tabs (with completions)
+            render_sql_editor(ui, &mut self.query, 6, &self.table_name, &self.schema)
+                .on_hover_text(
+                    "Enter SQL query (Polars SQL).\n\
+                    Click Example tabs above.\n\
+                    Tab completes table, column and keyword names.\n\
+                    Changes trigger reload on Apply/focus change.",
+                );
+
+            // 6. Pretty-print the query
+            self.render_format_sql_button(ui);
         }); // End vertical group
         ui.end_row(); // End the row in the parent Grid layout
+        run
+    }
+
+    /// Renders the named queries run before the main query (see `query_steps.rs`): a name
+    /// and an SQL editor per step, with buttons to reorder, remove and add steps.
+    /// Modifies `self.query_steps` directly.
+    fn render_query_steps(&mut self, ui: &mut Ui) {
+        ui.label("Steps:");
+        ui.vertical(|ui| {
+            CollapsingHeader::new(format!("Named Queries ({})", self.query_steps.len()))
+                .id_salt("query_steps")
+                .show(ui, |ui| {
+                    ui.label(
+                        "Each step is registered as a table under its name: later steps \
+                        and the SQL query below can select from it.",
+                    );
+
+                    let count = self.query_steps.len();
+                    let mut swap = None;
+                    let mut remove = None;
+                    for (index, step) in self.query_steps.iter_mut().enumerate() {
+                        ui.push_id(index, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{}.", index + 1));
+                                ui.add(
+                                    TextEdit::singleline(&mut step.name)
+                                        .hint_text("table name")
+                                        .desired_width(150.0),
+                                );
+                                let up = egui::Button::new("⏶").small();
+                                if ui
+                                    .add_enabled(index > 0, up)
+                                    .on_hover_text("Move up")
+                                    .clicked()
+                                {
+                                    swap = Some(index - 1);
+                                }
+                                let down = egui::Button::new("⏷").small();
+                                if ui
+                                    .add_enabled(index + 1 < count, down)
+                                    .on_hover_text("Move down")
+                                    .clicked()
+                                {
+                                    swap = Some(index);
+                                }
+                                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                                    remove = Some(index);
+                                }
+                            });
+                            render_sql_editor(
+                                ui,
+                                &mut step.query,
+                                3,
+                                &self.table_name,
+                                &self.schema,
+                            );
+                        });
+                        ui.add_space(4.0);
+                    }
+
+                    if let Some(index) = swap {
+                        self.query_steps.swap(index, index + 1);
+                    } else if let Some(index) = remove {
+                        self.query_steps.remove(index);
+                    }
+
+                    if ui.button("Add step").clicked() {
+                        // A new step selects from the previous one (or the data).
+                        let source = self
+                            .query_steps
+                            .last()
+                            .map_or(self.table_name.as_str(), |step| step.name.trim());
+                        let query = format!("SELECT *\nFROM {};", sql_identifier(source));
+                        self.query_steps.push(NamedQuery {
+                            name: format!("step{}", count + 1),
+                            query,
+                        });
+                    }
+                });
+        });
+        ui.end_row();
+    }
+
+    /// Renders the "Format SQL" button, which rewrites `self.query` via `format_sql`
+    /// (one clause per line, indented items, upper-case keywords).
+    /// The change is detected by `render_query` like any other edit.
+    fn render_format_sql_button(&mut self, ui: &mut Ui) {
+        let enabled = !self.query.trim().is_empty();
+        let response = ui
+            .add_enabled(enabled, egui::Button::new("Format SQL"))
+            .on_hover_text(
+                "Reformat the query: one clause per line, indented items, upper-case keywords.",
+            );
+
+        if response.clicked() {
+            let formatted = format_sql(&self.query);
+            if formatted != self.query {
+                tracing::debug!("SQL query reformatted.");
+                self.query = formatted;
+            }
+        }
+    }
+
+    /// Renders the "History" dropdown of `query_history` (nothing if empty): each query
+    /// with its time and result dimensions, to copy into the editor ("✏") or re-run ("▶").
+    ///
+    /// Returns `true` if the query copied into `self.query` should be re-run.
+    fn render_query_history(&mut self, ui: &mut Ui) -> bool {
+        if self.query_history.is_empty() {
+            return false;
+        }
+
+        // The chosen query, and whether to re-run it.
+        let mut chosen: Option<(String, bool)> = None;
+        ui.menu_button(
+            format!("🕘 History ({})", self.query_history.len()),
+            |ui| {
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for record in &self.query_history {
+                            ui.horizontal(|ui| {
+                                if ui.small_button("▶").on_hover_text("Run").clicked() {
+                                    chosen = Some((record.query.clone(), true));
+                                    ui.close();
+                                }
+                                if ui.small_button("✏").on_hover_text("Edit").clicked() {
+                                    chosen = Some((record.query.clone(), false));
+                                    ui.close();
+                                }
+                                ui.vertical(|ui| {
+                                    ui.monospace(record.preview());
+                                    ui.weak(record.summary());
+                                })
+                                .response
+                                .on_hover_text(&record.query);
+                            });
+                        }
+                    });
+            },
+        )
+        .response
+        .on_hover_text(
+            "Previously applied queries of this table, most recent first,\n\
+            with when they ran and their result size (rows × columns).",
+        );
+
+        match chosen {
+            Some((query, run)) => {
+                self.query = query;
+                run
+            }
+            None => false,
+        }
+    }
+
+    /// Renders a fuzzy search box over `query_history` and the generated `examples`.
+    /// Matches (by text or by referenced column names) are listed below the box;
+    /// clicking one copies it into `self.query`.
+    ///
+    /// The search text is kept in egui temporary memory, as it is pure UI state.
+    fn render_sql_search(&mut self, ui: &mut Ui, examples: &[String]) {
+        let search_id = ui.id().with("sql_search_text");
+        let mut search_text =
+            ui.memory_mut(|mem| mem.data.get_temp::<String>(search_id).unwrap_or_default());
+
+        ui.add(
+            TextEdit::singleline(&mut search_text)
+                .hint_text("🔍 Search history and examples")
+                .desired_width(f32::INFINITY),
+        )
+        .on_hover_text(
+            "Fuzzy search over previously applied queries and the examples below.\n\
+            Matches query text or referenced column names.\n\
+            Click a result to copy it into the editor.",
+        );
+
+        let history: Vec<String> = self
+            .query_history
+            .iter()
+            .map(|record| record.query.clone())
+            .collect();
+        let snippets = search_sql_snippets(&search_text, &history, examples);
+
+        for snippet in snippets.iter().take(MAX_SEARCH_RESULTS) {
+            let badge = match snippet.source {
+                SnippetSource::History => "🕘",
+                SnippetSource::Example => "📄",
+            };
+            let label = format!("{badge} {}", snippet.preview());
+
+            if ui
+                .selectable_label(false, label)
+                .on_hover_text(&snippet.text)
+                .clicked()
+            {
+                self.query = snippet.text.clone();
+                search_text.clear();
+                tracing::debug!("SQL search: selected {:?} snippet.", snippet.source);
+            }
+        }
+
+        if !search_text.trim().is_empty() && snippets.is_empty() {
+            ui.weak("No matches.");
+        }
+
+        ui.memory_mut(|mem| mem.data.insert_temp(search_id, search_text));
+    }
+
+    /// Returns the column preferences of the SQL examples (see `sql_commands`): the frozen
+    /// columns of `format` first, never its hidden columns nor the columns matching the drop
+    /// regex (when dropping is on and the regex is valid). The regex is compiled once per
+    /// pattern (see `DropRegexCache`).
+    pub fn example_columns<'a>(&mut self, format: &'a DataFormat) -> ExampleColumns<'a> {
+        let dropped = if self.drop {
+            self.drop_regex_cache.regex(&self.drop_regex)
+        } else {
+            None
+        };
+
+        ExampleColumns {
+            frozen: &format.frozen_columns,
+            hidden: Some(&format.hidden_columns),
+            dropped,
+        }
     }
 
     /// Renders the collapsible section displaying SQL command examples.
     /// Uses `sql_commands` to generate examples relevant to the current `self.schema`.
-    fn render_sql_examples(&self, ui: &mut Ui) {
+    fn render_sql_examples(&self, ui: &mut Ui, columns: &ExampleColumns) {
         CollapsingHeader::new("SQL Command Examples")
             .default_open(false)
             .show(ui, |ui| {
@@ -1065,7 +2327,7 @@ Example Patterns:
 
                         // Generate and display SQL examples based on the current schema.
                         // The `sql_commands` function (in `sqls.rs`) dynamically creates these.
-                        let examples = sql_commands(&self.schema);
+                        let examples = sql_commands(&self.schema, columns);
                         let mut ex_num = Vec::new();
                         for (index, example) in examples.iter().enumerate() {
                             ex_num.push(format!("Example {count}:\n{example}", count = index + 1));
@@ -1078,6 +2340,38 @@ Example Patterns:
     }
 }
 
+/// Reads the first `n_rows` rows of the CSV `source` (see `read_csv_partial_from_path`):
+/// a file, or the decompressed bytes of a compressed file.
+pub async fn read_csv_partial(
+    delimiter: u8,
+    quote_char: u8,
+    (comment_prefix, skip_rows): (Option<&str>, usize),
+    n_rows: usize,
+    source: &DataSource,
+) -> PolarsViewResult<DataFrame> {
+    let bytes = match source {
+        DataSource::File(path) => {
+            return read_csv_partial_from_path(
+                delimiter,
+                quote_char,
+                (comment_prefix, skip_rows),
+                n_rows,
+                path,
+            )
+            .await;
+        }
+        DataSource::Memory(bytes) => SharedBytes(bytes.clone()),
+    };
+
+    let csv_reader =
+        partial_csv_options(delimiter, quote_char, (comment_prefix, skip_rows), n_rows)
+            .into_reader_with_file_handle(Cursor::new(bytes));
+    let df = execute_polars_blocking(move || csv_reader.finish()).await?;
+
+    tracing::debug!("Partial CSV read complete. Shape: {:?}", df.shape());
+    Ok(df)
+}
+
 /// Reads a CSV file from the specified path using Polars, applying given options
 /// and limiting the number of data rows read.
 ///
@@ -1095,33 +2389,51 @@ Example Patterns:
 /// - `with_n_rows(n_rows)`: Limits the number of *data* rows parsed after the header.
 /// - `ignore_errors(true)`: Skips rows/fields with parsing errors rather than stopping.
 /// - `missing_is_null(true)`: Treats empty fields (`""`) as null values.
+/// - `quote_char`: The quote character of the file (see `sniff_dialect`).
+/// - `comment_prefix`, `skip_rows`: Comment lines and rows before the header are skipped.
 pub async fn read_csv_partial_from_path(
     delimiter: u8,
+    quote_char: u8,
+    (comment_prefix, skip_rows): (Option<&str>, usize),
     n_rows: usize,
     path: &Path,
 ) -> PolarsViewResult<DataFrame> {
     tracing::debug!("Read a CSV file using Polars limited to {} rows.", n_rows,);
 
+    let csv_read_options =
+        partial_csv_options(delimiter, quote_char, (comment_prefix, skip_rows), n_rows)
+            .try_into_reader_with_file_path(Some(path.to_path_buf()))?;
+
+    // Execute the blocking read operation on a separate thread
+    let df = execute_polars_blocking(move || csv_read_options.finish()).await?;
+
+    tracing::debug!("Partial CSV read complete. Shape: {:?}", df.shape());
+    Ok(df)
+}
+
+/// The options of `read_csv_partial_from_path` (see there).
+fn partial_csv_options(
+    delimiter: u8,
+    quote_char: u8,
+    (comment_prefix, skip_rows): (Option<&str>, usize),
+    n_rows: usize,
+) -> CsvReadOptions {
     // 1. Define the CSV parsing options.
     let csv_parse_options = CsvParseOptions::default()
         .with_encoding(CsvEncoding::LossyUtf8) // Handle potentially non-strict UTF8
         .with_missing_is_null(true) // Treat empty fields as nulls
-        .with_separator(delimiter); // Set the chosen delimiter
+        .with_separator(delimiter) // Set the chosen delimiter
+        .with_quote_char(Some(quote_char)) // Set the quote character
+        .with_comment_prefix(comment_prefix); // Skip comment lines
 
     // 2. Define the main CSV reading options.
-    let csv_read_options = CsvReadOptions::default()
+    CsvReadOptions::default()
         .with_parse_options(csv_parse_options) // Apply the parsing sub-options
         .with_has_header(true) // File has a header row
+        .with_skip_rows(skip_rows) // Skip the rows before the header
         .with_infer_schema_length(Some(0)) // Number of rows to use for schema inference (0 means header only)
         .with_ignore_errors(true) // Allow skipping rows/fields that fail to parse
         .with_n_rows(Some(n_rows)) // Limits the number of rows to read.
-        .try_into_reader_with_file_path(Some(path.to_path_buf()))?;
-
-    // 3. Execute the blocking read operation on a separate thread
-    let df = execute_polars_blocking(move || csv_read_options.finish()).await?;
-
-    tracing::debug!("Partial CSV read complete. Shape: {:?}", df.shape());
-    Ok(df)
 }
 
 /// Builds a Polars Schema specifying DataType::String overrides for columns
@@ -1234,6 +2546,9 @@ fn resolve_unique_column_name(base_name: &str, schema: &Schema) -> PolarsResult<
 /// runs it with `spawn_blocking`, awaits the result, and maps both the
 /// `JoinError` and the inner `PolarsError` to `PolarsViewError`.
 ///
+/// In a cancelled data task (see `CancelToken`), `op` is skipped if it hasn't started,
+/// and its result is discarded otherwise: both return `PolarsViewError::Cancelled`.
+///
 /// ### Arguments
 /// * `op`: A closure that performs the blocking work and returns `PolarsResult<T>`.
 ///   It must be `Send` and have a `'static` lifetime, meaning it must
@@ -1242,9 +2557,9 @@ fn resolve_unique_column_name(base_name: &str, schema: &Schema) -> PolarsResult<
 ///
 /// ### Returns
 /// A `PolarsViewResult<T>` containing the result of the operation `T` on success,
-/// or a mapped `PolarsViewError` if the spawned task fails (`TokioJoin`) or
-/// the Polars operation itself fails (`Polars`).
-async fn execute_polars_blocking<T, F>(op: F) -> PolarsViewResult<T>
+/// or a mapped `PolarsViewError` if the spawned task fails (`TokioJoin`),
+/// the Polars operation itself fails (`Polars`) or the data task was cancelled.
+pub(crate) async fn execute_polars_blocking<T, F>(op: F) -> PolarsViewResult<T>
 where
     // F is the type of the closure
     F: FnOnce() -> Result<T, PolarsError> + Send + 'static, // The closure trait bounds
@@ -1252,15 +2567,30 @@ where
     T: Debug + Send + 'static, // The success type must be Send and have static lifetime
                                // PolarsError: Debug,
 {
-    // Spawn the blocking task
-    let result_from_task = spawn_blocking(op).await; // Result<Result<T, PolarsError>, JoinError>
+    // A cancelled data task doesn't start more work (see `CancelToken`).
+    check_cancelled()?;
+    let token = cancel_token();
+
+    // Spawn the blocking task; skipped if the task is cancelled while it waits for a thread.
+    let result_from_task = spawn_blocking(move || {
+        if token.as_ref().is_some_and(CancelToken::is_cancelled) {
+            return None;
+        }
+        Some(op())
+    })
+    .await; // Result<Option<Result<T, PolarsError>>, JoinError>
 
     // Map JoinError to PolarsViewError::TokioJoin
-    let polars_result = result_from_task.map_err(PolarsViewError::from)?; // Requires PolarsViewError::from(JoinError)
+    let polars_result = result_from_task
+        .map_err(PolarsViewError::from)? // Requires PolarsViewError::from(JoinError)
+        .ok_or(PolarsViewError::Cancelled)?;
 
     // Map PolarsError to PolarsViewError::Polars
     let final_result = polars_result.map_err(PolarsViewError::from)?; // Requires PolarsViewError::from(PolarsError)
 
+    // Work that ran to completion for a cancelled task is discarded.
+    check_cancelled()?;
+
     Ok(final_result) // Return the successfully extracted value or the mapped PolarsError
 }
 
@@ -1333,13 +2663,17 @@ long_id;value;text
         let (_temp_file, filter) = // Keep _temp_file handle!
                 setup_test_csv(csv_content, delimiter, Some(col_regex))?;
 
+        let input = filter.csv_input().await?;
+        let (source, dialect) = filter.csv_dialect(&input, delimiter as u8).await?;
         let schema = filter
-            .attempt_csv_parse_structure(delimiter as u8, false)
+            .attempt_csv_parse_structure(&source, dialect, delimiter as u8, false)
             .await?;
         println!("schema: {schema:#?}");
 
         // 4. Execute the function under test
-        let lazyframe = filter.attempt_read_csv(delimiter as u8, &schema).await?;
+        let lazyframe = filter
+            .attempt_read_csv(&source, dialect, delimiter as u8, &schema)
+            .await?;
         println!("get lazyframe");
 
         // Execute the lazy plan and collect into an eager DataFrame
@@ -1398,13 +2732,17 @@ long_id;value;text
         let col_regex = "^Col Name$".to_string();
         let (_temp_file, filter) = setup_test_csv(csv_content, delimiter, Some(col_regex))?;
 
+        let input = filter.csv_input().await?;
+        let (source, dialect) = filter.csv_dialect(&input, delimiter as u8).await?;
         let schema = filter
-            .attempt_csv_parse_structure(delimiter as u8, false)
+            .attempt_csv_parse_structure(&source, dialect, delimiter as u8, false)
             .await?;
         println!("schema: {schema:#?}");
 
         // 4. Execute the function under test
-        let lazyframe = filter.attempt_read_csv(delimiter as u8, &schema).await?;
+        let lazyframe = filter
+            .attempt_read_csv(&source, dialect, delimiter as u8, &schema)
+            .await?;
         println!("get lazyframe");
 
         // Execute the lazy plan and collect into an eager DataFrame
@@ -1436,4 +2774,26 @@ long_id;value;text
 
         Ok(())
     }
+
+    // --- Test Case 3: Single quotes with backslash escapes are sniffed ---
+    #[tokio::test]
+    async fn test_csv_read_sniffs_quotes_and_escapes() -> PolarsViewResult<()> {
+        let csv_content = "\
+id;name;city
+1;'O\\'Brien; Ltd';'Porto'
+2;'Plain';'Lisbon; PT'";
+
+        let (_temp_file, filter) = setup_test_csv(csv_content, ';', None)?;
+        let (df_output, delimiter) = filter.read_csv_data().await?;
+        println!("Output DF (Actual Read):\n{df_output}");
+
+        assert_eq!(delimiter, Some(b';'));
+        assert_eq!(df_output.shape(), (2, 3));
+        let names: Vec<Option<&str>> = df_output.column("name")?.str()?.into_iter().collect();
+        assert_eq!(names, [Some("O'Brien; Ltd"), Some("Plain")]);
+        let cities: Vec<Option<&str>> = df_output.column("city")?.str()?.into_iter().collect();
+        assert_eq!(cities, [Some("Porto"), Some("Lisbon; PT")]);
+
+        Ok(())
+    }
 }
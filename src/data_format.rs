@@ -1,7 +1,21 @@
-use egui::{Align, DragValue, Grid, Layout, Ui, Vec2};
+use crate::{
+    CellRenderer, ColumnUnit, TextRenderer, VirtualColumn, compatible_units, decimal_cell_value,
+    renderers_for,
+};
+
+use egui::{
+    Align, Button, Color32, ComboBox, DragValue, Grid, Layout, RichText, ScrollArea, TextEdit, Ui,
+    Vec2,
+};
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
 
-use std::{collections::HashMap, fmt::Debug, sync::LazyLock};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    ops::Range,
+    sync::LazyLock,
+};
 
 // --- Constants ---
 
@@ -22,6 +36,7 @@ pub static DEFAULT_ALIGNMENTS: LazyLock<HashMap<DataType, Align>> = LazyLock::ne
         (DataType::Int16, Align::Center),
         (DataType::Int32, Align::Center),
         (DataType::Int64, Align::Center),
+        (DataType::Int128, Align::Center),
         (DataType::UInt8, Align::Center),
         (DataType::UInt16, Align::Center),
         (DataType::UInt32, Align::Center),
@@ -45,8 +60,258 @@ pub static DEFAULT_ALIGNMENTS: LazyLock<HashMap<DataType, Align>> = LazyLock::ne
     ])
 });
 
+/// Default number of items shown by `ListDisplay::FirstN`.
+pub const DEFAULT_LIST_ITEMS: usize = 3;
+
+/// Maximum number of decimal places inferred by `DataFormat::auto_decimal`.
+pub const AUTO_DECIMAL_MAX: usize = 6;
+
+/// Number of rows scanned (per column) to infer decimal places.
+pub const AUTO_DECIMAL_SAMPLE_ROWS: usize = 100_000;
+
+/// Default number of rows per page in paged mode (`DataFormat::paged`).
+pub const DEFAULT_ROWS_PER_PAGE: usize = 10_000;
+
 // --- Data Structures ---
 
+/// How the cells of a List (or Array) column are displayed in the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ListDisplay {
+    /// The whole list as text (Polars' default).
+    #[default]
+    Full,
+    /// Only the number of items.
+    Length,
+    /// The first N items, followed by `…` if the list is longer.
+    FirstN(usize),
+    /// The sum of the items (numeric lists).
+    Sum,
+    /// The mean of the items (numeric lists).
+    Mean,
+}
+
+impl ListDisplay {
+    /// Label shown in the "Format" panel.
+    pub fn label(&self) -> &'static str {
+        match self {
+            ListDisplay::Full => "Full",
+            ListDisplay::Length => "Length",
+            ListDisplay::FirstN(_) => "First N",
+            ListDisplay::Sum => "Sum",
+            ListDisplay::Mean => "Mean",
+        }
+    }
+}
+
+/// Formats the items of a List/Array cell according to `display`.
+///
+/// `Sum`/`Mean` use `decimal` places; for non-numeric items they fall back to `Full`.
+pub fn format_list_value(items: &Series, display: ListDisplay, decimal: usize) -> String {
+    let aggregate = |mean: bool| {
+        let values = items.cast(&DataType::Float64).ok()?;
+        let values = values.f64().ok()?;
+        if mean { values.mean() } else { values.sum() }
+    };
+
+    let item_text = |value: AnyValue| match value {
+        AnyValue::String(text) => text.to_string(),
+        other => other.to_string(),
+    };
+
+    match display {
+        ListDisplay::Length => items.len().to_string(),
+        ListDisplay::FirstN(n) => {
+            let mut text: Vec<String> = items.iter().take(n).map(item_text).collect();
+            if items.len() > n {
+                text.push("…".to_string());
+            }
+            format!("[{}]", text.join(", "))
+        }
+        ListDisplay::Sum | ListDisplay::Mean if items.dtype().is_primitive_numeric() => {
+            match aggregate(display == ListDisplay::Mean) {
+                Some(value) => format!("{value:.decimal$}"),
+                None => String::new(), // Empty list (mean) or only nulls.
+            }
+        }
+        _ => format!(
+            "[{}]",
+            items.iter().map(item_text).collect::<Vec<_>>().join(", ")
+        ),
+    }
+}
+
+/// Retrieves and formats a single cell's `AnyValue` into a displayable `String`.
+/// Used by the table (`DataContainer::render_table_row`) and the snapshots (`render_snapshot`).
+///
+/// Logic:
+/// 1. Get `AnyValue` from `column` at `row_index` using `column.get()`.
+/// 2. Handle `Result`: Return error string on `Err`.
+/// 3. On `Ok(any_value)`:
+///    - Match on `(any_value, opt_decimal)`:
+///      - Floats with `Some(decimal)`: Format using `format!("{:.*}", decimal, f)`.
+///      - `AnyValue::Null`: Return `""`.
+///      - `AnyValue::String(s)`: Return `s.to_string()`.
+///      - Lists/Arrays with a `ListDisplay` other than `Full`: Use `format_list_value`.
+///      - Other types (Ints, Bool, Date, etc.) or Floats with `None` decimal: Use `any_value.to_string()`.
+///
+/// ### Arguments
+/// * `column`: Reference to the Polars `Column`.
+/// * `row_index`: Row index within the series.
+/// * `opt_decimal`: `Option<usize>` specifying decimal places for floats (from `get_decimal_and_layout`).
+/// * `format`: The display settings (list displays, decimal scale).
+///
+/// ### Returns
+/// `String`: The formatted cell value.
+pub fn format_cell(
+    column: &Column,
+    row_index: usize,
+    opt_decimal: Option<usize>,
+    format: &DataFormat,
+) -> String {
+    // Display mode of List/Array columns (see `DataFormat::list_displays`).
+    let list_display = format
+        .list_displays
+        .get(column.name().as_str())
+        .copied()
+        .unwrap_or_default();
+
+    // Decimal columns: exact formatting of the underlying integer (see `format_decimal`).
+    if column.dtype().is_decimal() {
+        return decimal_cell_value(column, row_index, format.decimal_scale)
+            .unwrap_or_else(|e| {
+                tracing::warn!(
+                    "format_cell: Failed to format decimal col '{}' row {}: {}",
+                    column.name(),
+                    row_index,
+                    e
+                );
+                String::new()
+            })
+            .unwrap_or_default(); // Null: empty string.
+    }
+
+    match column.get(row_index) {
+        Ok(any_value) => {
+            // Format based on the AnyValue variant and decimal setting.
+            match (any_value, opt_decimal) {
+                // Float with specific decimal request: Apply precision formatting.
+                (AnyValue::Float32(value), Some(decimal)) => format!("{value:.decimal$}"),
+                (AnyValue::Float64(value), Some(decimal)) => format!("{value:.decimal$}"),
+
+                // Null value: Display as empty string.
+                (AnyValue::Null, _) => String::new(),
+
+                // String value: Convert inner &str to String.
+                (AnyValue::String(value), _) => value.to_string(), // Handle StringOwned too if necessary.
+
+                // List/Array with a display option: length, first N items or aggregate.
+                (AnyValue::List(items) | AnyValue::Array(items, _), _)
+                    if list_display != ListDisplay::Full =>
+                {
+                    format_list_value(&items, list_display, format.decimal)
+                }
+
+                // Other AnyValue types OR Float without specific decimal: Use default Polars to_string().
+                (other_anyvalue, _) => other_anyvalue.to_string(),
+            }
+        }
+        Err(e) => {
+            // Handle error retrieving value (e.g., index out of bounds, though unlikely with TableBuilder).
+            tracing::warn!(
+                "format_cell: Failed get value col '{}' row {}: {}",
+                column.name(),
+                row_index,
+                e
+            );
+            "⚠ Err".to_string() // Return placeholder error string for display.
+        }
+    }
+}
+
+/// Number of fractional digits of the shortest text that reads back as `value`
+/// (e.g., `1.25` -> 2, `3.0` -> 0).
+pub fn fractional_digits(value: impl ToString) -> usize {
+    let text = value.to_string();
+    match text.split_once('.') {
+        Some((_, fraction)) => fraction.trim_end_matches('0').len(),
+        None => 0,
+    }
+}
+
+/// Infers the decimal places of each Float column of `df`: the largest number of
+/// fractional digits among its first `AUTO_DECIMAL_SAMPLE_ROWS` values, capped at `max`.
+pub fn infer_decimals(df: &DataFrame, max: usize) -> HashMap<String, usize> {
+    /// Largest of `digits`, capped at `max` (stops scanning at the cap).
+    fn max_digits(mut digits: impl Iterator<Item = usize>, max: usize) -> usize {
+        let mut largest = 0;
+        while largest < max {
+            match digits.next() {
+                Some(n) => largest = largest.max(n.min(max)),
+                None => break,
+            }
+        }
+        largest
+    }
+
+    let sample = df.head(Some(AUTO_DECIMAL_SAMPLE_ROWS));
+
+    sample
+        .get_columns()
+        .iter()
+        .filter_map(|column| {
+            // Float32 values are formatted as f32: casting to f64 would add noise digits.
+            let decimal = match column.dtype() {
+                DataType::Float32 => {
+                    let values = column.f32().ok()?.into_iter().flatten();
+                    max_digits(values.map(fractional_digits), max)
+                }
+                DataType::Float64 => {
+                    let values = column.f64().ok()?.into_iter().flatten();
+                    max_digits(values.map(fractional_digits), max)
+                }
+                _ => return None,
+            };
+            Some((column.name().to_string(), decimal))
+        })
+        .collect()
+}
+
+/// Text and color of the null badge of a column header (`DataFormat::show_null_badges`):
+/// the percentage of null values, green (no nulls), yellow (up to 10%) or red (more).
+pub fn null_badge(null_count: usize, rows: usize) -> (String, Color32) {
+    let percent = match rows {
+        0 => 0.0,
+        rows => 100.0 * null_count as f64 / rows as f64,
+    };
+
+    let text = match null_count {
+        0 => "0%".to_string(),
+        n if n == rows => "100%".to_string(),
+        _ if percent < 0.1 => "<0.1%".to_string(),
+        _ if percent > 99.9 => ">99.9%".to_string(),
+        _ => format!("{percent:.1}%"),
+    };
+
+    let color = if null_count == 0 {
+        Color32::from_rgb(90, 170, 90)
+    } else if percent <= 10.0 {
+        Color32::from_rgb(210, 170, 50)
+    } else {
+        Color32::from_rgb(210, 80, 80)
+    };
+
+    (text, color)
+}
+
+/// Display overrides for a single column (by name), taking precedence over
+/// `DataFormat::decimal` and the per-`DataType` `alignments`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColumnFormat {
+    /// Decimal places for Float columns.
+    pub decimal: Option<usize>,
+    pub align: Option<Align>,
+}
+
 /// Holds user-configurable settings for data presentation in the table.
 ///
 /// ## State Management & Interaction:
@@ -54,9 +319,11 @@ pub static DEFAULT_ALIGNMENTS: LazyLock<HashMap<DataType, Align>> = LazyLock::ne
 ///   current UI configuration. `render_format` modifies this instance directly.
 /// - **Data State**: An `Arc<DataFormat>` is stored within each `DataFrameContainer` (`container.rs`),
 ///   capturing the format settings active when that data state was created (e.g., after load or sort).
-/// - **Update Flow**: Changes in `render_format` are detected, triggering an async `DataFrameContainer::update_format`
-///   call in `layout.rs`. This creates a new `DataFrameContainer` with the updated `Arc<DataFormat>`,
-///   ensuring the table re-renders with the new settings.
+/// - **Update Flow**: Changes in `render_format` are detected and applied by `layout.rs::apply_format`.
+///   Pure view changes swap the `Arc<DataFormat>` of a copy of the container at once
+///   (`DataContainer::with_format`, the data `Arc`s are shared); the others (e.g., toggling
+///   `auto_decimal`) run the async `DataContainer::update_format`. Either way, a new container
+///   with the updated settings is displayed.
 #[derive(Debug, Clone, PartialEq)]
 pub struct DataFormat {
     /// Stores the *current* alignment setting for each `DataType`, overriding `DEFAULT_ALIGNMENTS`.
@@ -77,6 +344,21 @@ pub struct DataFormat {
     ///   (Note: `decimal_and_layout_v2` might override for specific columns).
     pub decimal: usize,
 
+    /// Infers the decimal places of each Float column from the data (see `infer_decimals`),
+    /// instead of using `decimal` for all of them.
+    /// - Modified by the checkbox in `render_decimal_input`.
+    /// - Read by `decimal_for`.
+    pub auto_decimal: bool,
+
+    /// Decimal places of `Decimal` columns: `None` uses each column's own scale.
+    /// - Modified by `render_decimal_scale` (shown only if there are Decimal columns).
+    /// - Read by `data_container.rs::format_cell_value` (see `format_decimal`).
+    pub decimal_scale: Option<usize>,
+
+    /// Decimal places inferred per Float column, keyed by column name (used if `auto_decimal`).
+    /// - Computed by `infer_decimals_from` when data is loaded or `auto_decimal` is enabled.
+    pub inferred_decimals: HashMap<String, usize>,
+
     /// User-configurable *additional* vertical padding for the table header row.
     /// - Applied in `container.rs::build_table` when calculating header height.
     /// - Modified by `DragValue` in `render_header_padding_input` (if `use_enhanced_header`).
@@ -88,6 +370,95 @@ pub struct DataFormat {
     ///   - `true` (Default): Enhanced (styled text, wrapping, icon-only sort click).
     ///   - `false`: Simple (plain button, non-wrapping, full button sort click).
     pub use_enhanced_header: bool,
+
+    /// Per-column display overrides, keyed by column name.
+    /// - Set when a SPED/EFD layout preset is detected on load (`format-special` feature).
+    /// - Read by `decimal_and_layout_v2::get_decimal_and_layout`.
+    pub column_formats: HashMap<String, ColumnFormat>,
+
+    /// Alignment of single columns, keyed by column name, overriding `alignments` and
+    /// `column_formats` (for the one column that needs it).
+    /// - Modified by the header context menu ("Align"), see `layout.rs`.
+    /// - Read by `get_decimal_and_layout` (both versions).
+    pub column_alignments: HashMap<String, Align>,
+
+    /// Locked column widths (in points), keyed by column name: these columns keep their
+    /// width and can't be resized (the others follow `auto_col_width`).
+    /// - Modified by the header context menu ("Lock width"), see `layout.rs`.
+    /// - Read by `data_container.rs::prepare_table_build_config`.
+    pub locked_widths: HashMap<String, f32>,
+
+    /// Display mode of List/Array columns, keyed by column name (absent: `ListDisplay::Full`).
+    /// - Modified by `render_list_display`.
+    /// - Read by `data_container.rs::format_cell_value`.
+    pub list_displays: HashMap<String, ListDisplay>,
+
+    /// Cell renderer of each column (`CellRenderer::id`), keyed by column name
+    /// (absent: `TextRenderer`).
+    /// - Modified by `render_cell_renderers`.
+    /// - Read by `data_container.rs::render_table_row` (see `resolve_renderer`).
+    pub cell_renderers: HashMap<String, String>,
+
+    /// Unit of each column (shown in the header), and the unit its values are converted
+    /// to for display/export (see `units.rs`), keyed by column name.
+    /// - Modified by `render_column_units`.
+    /// - Read by `data_container.rs::render_header_cell` and `convert_units`.
+    pub column_units: HashMap<String, ColumnUnit>,
+
+    /// Computed columns added to the displayed rows only (see `virtual_column.rs`).
+    /// - Modified by `render_virtual_columns` ("Columns" side panel section).
+    /// - Read by `data_container.rs` when `df` is derived from `df_unfiltered`.
+    pub virtual_columns: Vec<VirtualColumn>,
+
+    /// Names of the columns hidden from the table (the `DataFrame` is not changed).
+    /// - Modified by `render_column_visibility` ("Columns" side panel section).
+    /// - Read by `data_container.rs::visible_columns`.
+    pub hidden_columns: HashSet<String>,
+
+    /// Names of the frozen columns, kept visible on the left while the table scrolls
+    /// horizontally (drawn as a separate table, in their table order).
+    /// - Modified by the header context menu ("Freeze column"), see `layout.rs`.
+    /// - Read by `data_container.rs::render_table`.
+    pub frozen_columns: Vec<String>,
+
+    /// Display order of the columns (see `ordered_indices`); empty: the `DataFrame` order.
+    /// - Modified by dragging a header cell onto another one, see `layout.rs`.
+    /// - Read by `data_container.rs::visible_columns` and the exports (`with_column_order`).
+    pub column_order: Vec<String>,
+
+    /// Shows a row number gutter on the left of the table, kept visible while the table
+    /// scrolls horizontally (independent of the row index column of `DataFilter`).
+    /// Clicking a number selects the row.
+    /// - Modified by checkbox in `render_row_numbers`.
+    /// - Read by `data_container.rs::render_table`.
+    pub show_row_numbers: bool,
+
+    /// Expands the hovered row (or the selected row, if nothing is hovered) to show the
+    /// full, wrapped content of its cells; it collapses when the pointer leaves it.
+    /// - Modified by checkbox in `render_expand_row`.
+    /// - Read by `data_container.rs::render_table`.
+    pub expand_hovered_row: bool,
+
+    /// Shows a filter input under each column header (see `column_filter.rs`).
+    /// - Modified by checkbox in `render_filter_row`.
+    /// - Read by `data_container.rs::render_table_header` and `prepare_table_build_config`.
+    pub show_filter_row: bool,
+
+    /// Shows a badge with the percentage of null values in each column header (see `null_badge`).
+    /// Null counts are kept by Polars with each column chunk: no scan of the data.
+    /// - Modified by checkbox in `render_null_badges`.
+    /// - Read by `data_container.rs::render_header_cell`.
+    pub show_null_badges: bool,
+
+    /// Paged mode: the table shows `rows_per_page` rows at a time, with page navigation
+    /// in the bottom panel (see `render_page_navigation`). Reduces the render cost of very
+    /// large `DataFrame`s (e.g., content-based column widths).
+    /// - Modified by `render_paging`.
+    /// - Read by `data_container.rs::render_table` via `page_rows`.
+    pub paged: bool,
+
+    /// Number of rows per page in paged mode.
+    pub rows_per_page: usize,
 }
 
 // --- Implementations ---
@@ -100,13 +471,79 @@ impl Default for DataFormat {
             alignments: DEFAULT_ALIGNMENTS.clone(), // Clone defaults for this instance.
             auto_col_width: true,                   // Default automatic content-based sizing.
             decimal: 2,                             // Default float precision.
+            auto_decimal: false,                    // Same precision for all float columns.
+            decimal_scale: None,                    // Decimal columns use their own scale.
+            inferred_decimals: HashMap::new(),      // Computed on load if `auto_decimal`.
             header_padding: 5.0,                    // Default extra padding for enhanced header.
             use_enhanced_header: true,              // Default to enhanced header style.
+            column_formats: HashMap::new(),         // No per-column overrides.
+            column_alignments: HashMap::new(),      // Alignment by data type only.
+            locked_widths: HashMap::new(),          // All columns resizable.
+            list_displays: HashMap::new(),          // Lists shown in full.
+            cell_renderers: HashMap::new(),         // All cells as text.
+            column_units: HashMap::new(),           // No unit annotations.
+            virtual_columns: Vec::new(),            // No computed columns.
+            hidden_columns: HashSet::new(),         // All columns visible.
+            frozen_columns: Vec::new(),             // No frozen columns.
+            column_order: Vec::new(),               // The DataFrame column order.
+            show_row_numbers: false,                // No row number gutter.
+            expand_hovered_row: false,              // Fixed row height.
+            show_filter_row: false,                 // No filter row under the header.
+            show_null_badges: false,                // No null badges in the header.
+            paged: false,                           // All rows in one scrollable table.
+            rows_per_page: DEFAULT_ROWS_PER_PAGE,   // Used only if `paged`.
         }
     }
 }
 
 impl DataFormat {
+    /// Decimal places of the Float column `column_name`: the inferred value if
+    /// `auto_decimal` is set (and the column was seen), otherwise `decimal`.
+    pub fn decimal_for(&self, column_name: &str) -> usize {
+        self.auto_decimal
+            .then(|| self.inferred_decimals.get(column_name).copied())
+            .flatten()
+            .unwrap_or(self.decimal)
+    }
+
+    /// Recomputes `inferred_decimals` from `df` if `auto_decimal` is set (clears it otherwise).
+    pub fn infer_decimals_from(&mut self, df: &DataFrame) {
+        self.inferred_decimals = if self.auto_decimal {
+            infer_decimals(df, AUTO_DECIMAL_MAX)
+        } else {
+            HashMap::new()
+        };
+    }
+
+    /// Number of pages of a table with `total_rows` rows (at least 1; 1 if not `paged`).
+    pub fn page_count(&self, total_rows: usize) -> usize {
+        if self.paged {
+            total_rows.div_ceil(self.rows_per_page.max(1)).max(1)
+        } else {
+            1
+        }
+    }
+
+    /// The 0-based page containing `row` (0 if not `paged`).
+    pub fn page_of_row(&self, row: usize) -> usize {
+        if self.paged {
+            row / self.rows_per_page.max(1)
+        } else {
+            0
+        }
+    }
+
+    /// Rows shown on `page` (0-based; past the last page means the last page).
+    /// All rows if not `paged`.
+    pub fn page_rows(&self, page: usize, total_rows: usize) -> Range<usize> {
+        if !self.paged {
+            return 0..total_rows;
+        }
+        let page = page.min(self.page_count(total_rows) - 1);
+        let start = page * self.rows_per_page.max(1);
+        start..(start + self.rows_per_page.max(1)).min(total_rows)
+    }
+
     /// Gets the default header padding value defined in `DataFormat::default()`.
     /// Used internally, e.g., by container.rs when `use_enhanced_header` is false.
     pub fn get_default_padding(&self) -> f32 {
@@ -128,10 +565,11 @@ impl DataFormat {
     ///
     /// ### Arguments
     /// * `ui`: Mutable reference to the `egui::Ui` context for drawing.
+    /// * `schema`: Schema of the displayed data (for the per-column List display options).
     ///
     /// ### Returns
     /// * `Option<DataFormat>`: `Some(updated_format)` if a setting was changed, otherwise `None`.
-    pub fn render_format(&mut self, ui: &mut Ui) -> Option<DataFormat> {
+    pub fn render_format(&mut self, ui: &mut Ui, schema: &Schema) -> Option<DataFormat> {
         // 1. Capture the state *before* potential modifications.
         let format_former = self.clone();
         let mut result = None; // Assume no change initially.
@@ -154,6 +592,7 @@ impl DataFormat {
 
                     self.render_alignment_panel(ui); // Modifies `self.alignments`.
                     self.render_decimal_input(ui); // Modifies `self.decimal`.
+                    self.render_decimal_scale(ui, schema); // Modifies `self.decimal_scale`.
                     self.render_auto_col(ui); // Modifies `self.auto_col_width`.
                     self.render_header(ui); // Modifies `self.use_enhanced_header`.
 
@@ -162,6 +601,15 @@ impl DataFormat {
                         self.render_header_padding_input(ui); // Modifies `self.header_padding`.
                     }
 
+                    self.render_row_numbers(ui); // Modifies `self.show_row_numbers`.
+                    self.render_expand_row(ui); // Modifies `self.expand_hovered_row`.
+                    self.render_filter_row(ui); // Modifies `self.show_filter_row`.
+                    self.render_null_badges(ui); // Modifies `self.show_null_badges`.
+                    self.render_paging(ui); // Modifies `self.paged` and `self.rows_per_page`.
+                    self.render_list_display(ui, schema); // Modifies `self.list_displays`.
+                    self.render_cell_renderers(ui, schema); // Modifies `self.cell_renderers`.
+                    self.render_column_units(ui, schema); // Modifies `self.column_units`.
+
                     // 3. Detect Changes after all widgets rendered for this frame.
                     if *self != format_former {
                         result = Some(self.clone()); // Signal the change with the new state.
@@ -178,6 +626,133 @@ impl DataFormat {
         result
     }
 
+    /// Renders the column visibility manager ("Columns" side panel section):
+    /// "Show all"/"Hide all" buttons and a show/hide checkbox per column of `schema`.
+    ///
+    /// ### Returns
+    /// `Some(updated_format)` if the visibility changed, otherwise `None`.
+    pub fn render_column_visibility(&mut self, ui: &mut Ui, schema: &Schema) -> Option<DataFormat> {
+        let hidden_before = self.hidden_columns.clone();
+
+        let visible = schema
+            .iter_names()
+            .filter(|name| !self.hidden_columns.contains(name.as_str()))
+            .count();
+
+        ui.horizontal(|ui| {
+            if ui.button("Show all").clicked() {
+                self.hidden_columns.clear();
+            }
+            if ui.button("Hide all").clicked() {
+                self.hidden_columns = schema.iter_names().map(|name| name.to_string()).collect();
+            }
+            ui.label(format!("{visible} of {} visible", schema.len()));
+        });
+
+        ScrollArea::vertical()
+            .id_salt("column_visibility")
+            .max_height(300.0)
+            .show(ui, |ui| {
+                for (name, dtype) in schema.iter() {
+                    let mut shown = !self.hidden_columns.contains(name.as_str());
+                    if ui
+                        .checkbox(&mut shown, name.as_str())
+                        .on_hover_text(dtype.to_string())
+                        .changed()
+                    {
+                        if shown {
+                            self.hidden_columns.remove(name.as_str());
+                        } else {
+                            self.hidden_columns.insert(name.to_string());
+                        }
+                    }
+                }
+            });
+
+        (self.hidden_columns != hidden_before).then(|| self.clone())
+    }
+
+    /// Renders the virtual columns manager ("Columns" side panel section): the defined
+    /// virtual columns (edit/remove buttons) and the inputs of a new one, validated on the
+    /// first rows of `df` (the data columns, see `VirtualColumn::validate`) when added.
+    ///
+    /// ### Returns
+    /// `Some(updated_format)` if a virtual column was added or removed, otherwise `None`.
+    pub fn render_virtual_columns(&mut self, ui: &mut Ui, df: &DataFrame) -> Option<DataFormat> {
+        // The column being defined, with the error of its last validation.
+        let draft_id = ui.id().with("virtual_column_draft");
+        let (mut draft, mut error): (VirtualColumn, Option<String>) =
+            ui.data(|data| data.get_temp(draft_id).unwrap_or_default());
+        let columns_before = self.virtual_columns.clone();
+
+        let mut edit = None;
+        let mut remove = None;
+        for (index, column) in self.virtual_columns.iter().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    remove = Some(index);
+                }
+                if ui
+                    .small_button("✏")
+                    .on_hover_text("Edit (moves it to the inputs below)")
+                    .clicked()
+                {
+                    edit = Some(index);
+                }
+                ui.label(
+                    RichText::new(format!("{} = {}", column.name, column.expression)).monospace(),
+                );
+            });
+        }
+        if let Some(index) = edit {
+            draft = self.virtual_columns.remove(index);
+            error = None;
+        } else if let Some(index) = remove {
+            self.virtual_columns.remove(index);
+        }
+
+        Grid::new("virtual_column_grid")
+            .num_columns(2)
+            .spacing([10.0, 6.0])
+            .show(ui, |ui| {
+                ui.label("Name:");
+                ui.add(TextEdit::singleline(&mut draft.name).desired_width(f32::INFINITY));
+                ui.end_row();
+
+                ui.label("Expression:");
+                ui.add(
+                    TextEdit::singleline(&mut draft.expression)
+                        .hint_text("price * quantity")
+                        .desired_width(f32::INFINITY)
+                        .font(egui::TextStyle::Monospace),
+                )
+                .on_hover_text(
+                    "Polars SQL expression over the columns, computed for the displayed rows\n\
+                    only (the SQL query does not see it). Examples: price * quantity,\n\
+                    upper(name), round(`Valor Total` / 1000, 1)",
+                );
+                ui.end_row();
+            });
+
+        if ui.button("Add virtual column").clicked() {
+            match draft.validate(df, &self.virtual_columns) {
+                Ok(dtype) => {
+                    tracing::debug!("Virtual column {:?} ({dtype})", draft.name);
+                    draft.name = draft.name.trim().to_string();
+                    self.virtual_columns.push(std::mem::take(&mut draft));
+                    error = None;
+                }
+                Err(validation_error) => error = Some(validation_error.to_string()),
+            }
+        }
+        if let Some(error) = &error {
+            ui.colored_label(Color32::RED, error);
+        }
+
+        ui.data_mut(|data| data.insert_temp(draft_id, (draft, error)));
+        (self.virtual_columns != columns_before).then(|| self.clone())
+    }
+
     /// Renders the collapsible UI section for configuring text alignment per `DataType`.
     ///
     /// Uses a nested `egui::Grid` within a `CollapsingHeader`. Calls `show_alignment_row`
@@ -197,6 +772,7 @@ impl DataFormat {
                     // Render rows for relevant DataTypes.
                     self.show_alignment_row(ui, &DataType::Float64);
                     self.show_alignment_row(ui, &DataType::Float32);
+                    self.show_alignment_row(ui, &DataType::Int128);
                     self.show_alignment_row(ui, &DataType::Int64);
                     self.show_alignment_row(ui, &DataType::Int32);
                     self.show_alignment_row(ui, &DataType::Int16);
@@ -253,6 +829,41 @@ impl DataFormat {
             Maximum decimal places: {decimal_max}"
         ));
         ui.end_row();
+
+        ui.label("Auto Decimals:");
+        ui.checkbox(&mut self.auto_decimal, "")
+            .on_hover_text(format!(
+                "Enable: each float column gets the largest number of decimal places\n\
+            found in its values (at most {AUTO_DECIMAL_MAX}).\n\
+            Disable: all float columns use \"Decimals\"."
+            ));
+        ui.end_row();
+    }
+
+    /// Renders the scale of `Decimal` columns (only if `schema` has any): the column's
+    /// own scale, or a fixed number of decimal places. Modifies `self.decimal_scale`.
+    fn render_decimal_scale(&mut self, ui: &mut Ui, schema: &Schema) {
+        if !schema.iter_values().any(|dtype| dtype.is_decimal()) {
+            return;
+        }
+
+        ui.label("Decimal Scale:");
+        ui.horizontal(|ui| {
+            let mut fixed = self.decimal_scale.is_some();
+            ui.checkbox(&mut fixed, "Fixed").on_hover_text(
+                "Enable: Decimal columns are rounded (or padded) to this number of places.\n\
+                Disable: each Decimal column is shown exactly, with its own scale.",
+            );
+            self.decimal_scale = match (fixed, self.decimal_scale) {
+                (true, Some(mut scale)) => {
+                    ui.add(DragValue::new(&mut scale).speed(1).range(0..=38));
+                    Some(scale)
+                }
+                (true, None) => Some(self.decimal),
+                (false, _) => None,
+            };
+        });
+        ui.end_row();
     }
 
     /// Renders the checkbox for toggling automatic column width (`self.auto_col_width`).
@@ -284,6 +895,261 @@ impl DataFormat {
         ui.end_row();
     }
 
+    /// Renders the checkbox for toggling the row number gutter (`self.show_row_numbers`).
+    fn render_row_numbers(&mut self, ui: &mut Ui) {
+        ui.label("Row Numbers:");
+        ui.checkbox(&mut self.show_row_numbers, "").on_hover_text(
+            "Show the row numbers on the left of the table, also while scrolling horizontally.\n\
+            Click a number to select its row (Shift + click: a range of rows).",
+        );
+        ui.end_row();
+    }
+
+    /// Renders the checkbox for toggling the expansion of the hovered row (`self.expand_hovered_row`).
+    fn render_expand_row(&mut self, ui: &mut Ui) {
+        ui.label("Expand Row:");
+        ui.checkbox(&mut self.expand_hovered_row, "").on_hover_text(
+            "Expand the row under the pointer (or the selected row) to show the full,\n\
+            wrapped content of its cells. It collapses when the pointer leaves it.",
+        );
+        ui.end_row();
+    }
+
+    /// Renders the checkbox for toggling the filter row under the header (`self.show_filter_row`).
+    fn render_filter_row(&mut self, ui: &mut Ui) {
+        ui.label("Filter Row:");
+        ui.checkbox(&mut self.show_filter_row, "").on_hover_text(
+            "Show a filter input under each column header.\n\
+            Filters apply to the query result, without changing the SQL query.",
+        );
+        ui.end_row();
+    }
+
+    /// Renders the checkbox for toggling the null badges in the header (`self.show_null_badges`).
+    fn render_null_badges(&mut self, ui: &mut Ui) {
+        ui.label("Null Badges:");
+        ui.checkbox(&mut self.show_null_badges, "").on_hover_text(
+            "Show the percentage of null values in each column header:\n\
+            green: none, yellow: up to 10%, red: more than 10%.",
+        );
+        ui.end_row();
+    }
+
+    /// Renders the paged mode checkbox (`self.paged`) and, if enabled, the rows per page.
+    fn render_paging(&mut self, ui: &mut Ui) {
+        ui.label("Paged:");
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.paged, "").on_hover_text(
+                "Show the rows one page at a time, with page navigation in the bottom panel.\n\
+                Recommended for very large tables.",
+            );
+            if self.paged {
+                ui.add(
+                    DragValue::new(&mut self.rows_per_page)
+                        .speed(100)
+                        .range(10..=1_000_000)
+                        .suffix(" rows"),
+                )
+                .on_hover_text("Rows per page.");
+            }
+        });
+        ui.end_row();
+    }
+
+    /// Renders the per-column display mode of List/Array columns (only if there are any).
+    /// Modifies `self.list_displays` directly; `Full` removes the column's entry.
+    fn render_list_display(&mut self, ui: &mut Ui, schema: &Schema) {
+        let list_columns: Vec<(&PlSmallStr, &DataType)> = schema
+            .iter()
+            .filter(|(_name, dtype)| matches!(dtype, DataType::List(_) | DataType::Array(..)))
+            .collect();
+
+        if list_columns.is_empty() {
+            return;
+        }
+
+        ui.label("List Columns:");
+        ui.collapsing("Display", |ui| {
+            Grid::new("list_display_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, dtype) in list_columns {
+                        let numeric = dtype
+                            .inner_dtype()
+                            .is_some_and(|inner| inner.is_primitive_numeric());
+                        let mut display = self
+                            .list_displays
+                            .get(name.as_str())
+                            .copied()
+                            .unwrap_or_default();
+                        let n = match display {
+                            ListDisplay::FirstN(n) => n,
+                            _ => DEFAULT_LIST_ITEMS,
+                        };
+
+                        ui.label(name.as_str()).on_hover_text(dtype.to_string());
+                        ui.horizontal(|ui| {
+                            ComboBox::from_id_salt(("list_display", name.as_str()))
+                                .selected_text(display.label())
+                                .show_ui(ui, |ui| {
+                                    let mut options = vec![
+                                        ListDisplay::Full,
+                                        ListDisplay::Length,
+                                        ListDisplay::FirstN(n),
+                                    ];
+                                    if numeric {
+                                        options.extend([ListDisplay::Sum, ListDisplay::Mean]);
+                                    }
+                                    for option in options {
+                                        ui.selectable_value(&mut display, option, option.label());
+                                    }
+                                });
+
+                            if let ListDisplay::FirstN(n) = &mut display {
+                                ui.add(DragValue::new(n).speed(1).range(1..=100))
+                                    .on_hover_text("Number of items shown.");
+                            }
+                        });
+                        ui.end_row();
+
+                        if display == ListDisplay::Full {
+                            self.list_displays.remove(name.as_str());
+                        } else {
+                            self.list_displays.insert(name.to_string(), display);
+                        }
+                    }
+                });
+        });
+        ui.end_row();
+    }
+
+    /// Renders the cell renderer of each column that has more than the text renderer
+    /// (see `renderers_for`). Modifies `self.cell_renderers` directly; `TextRenderer`
+    /// removes the column's entry.
+    fn render_cell_renderers(&mut self, ui: &mut Ui, schema: &Schema) {
+        let columns: Vec<(&PlSmallStr, &DataType)> = schema
+            .iter()
+            .filter(|(_name, dtype)| renderers_for(dtype).len() > 1)
+            .collect();
+
+        if columns.is_empty() {
+            return;
+        }
+
+        ui.label("Cell Renderers:");
+        ui.collapsing("Render", |ui| {
+            Grid::new("cell_renderer_grid")
+                .num_columns(2)
+                .spacing([10.0, 10.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    for (name, dtype) in columns {
+                        let renderers = renderers_for(dtype);
+                        let current = self.cell_renderers.get(name.as_str());
+                        let renderer = renderers
+                            .iter()
+                            .find(|renderer| current.is_some_and(|id| id == renderer.id()))
+                            .copied()
+                            .unwrap_or(&TextRenderer);
+                        let mut id = renderer.id();
+                        let selected = renderer.label();
+
+                        ui.label(name.as_str()).on_hover_text(dtype.to_string());
+                        ComboBox::from_id_salt(("cell_renderer", name.as_str()))
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for renderer in &renderers {
+                                    ui.selectable_value(&mut id, renderer.id(), renderer.label());
+                                }
+                            });
+                        ui.end_row();
+
+                        if id == TextRenderer.id() {
+                            self.cell_renderers.remove(name.as_str());
+                        } else {
+                            self.cell_renderers.insert(name.to_string(), id.to_string());
+                        }
+                    }
+                });
+        });
+        ui.end_row();
+    }
+
+    /// Renders the units table: the unit of each numeric column (free text) and, for the
+    /// units of `UNITS`, the compatible unit its values are shown in. Modifies
+    /// `self.column_units` directly; an empty unit removes the column's entry.
+    fn render_column_units(&mut self, ui: &mut Ui, schema: &Schema) {
+        let columns: Vec<(&PlSmallStr, &DataType)> = schema
+            .iter()
+            .filter(|(_name, dtype)| dtype.is_primitive_numeric() || dtype.is_decimal())
+            .collect();
+
+        if columns.is_empty() {
+            return;
+        }
+
+        ui.label("Units:");
+        ui.collapsing("Columns", |ui| {
+            Grid::new("column_unit_grid")
+                .num_columns(3)
+                .spacing([10.0, 10.0])
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Column");
+                    ui.label("Unit");
+                    ui.label("Show as");
+                    ui.end_row();
+
+                    for (name, dtype) in columns {
+                        let mut column_unit = self
+                            .column_units
+                            .get(name.as_str())
+                            .cloned()
+                            .unwrap_or_default();
+
+                        ui.label(name.as_str()).on_hover_text(dtype.to_string());
+                        ui.add(
+                            TextEdit::singleline(&mut column_unit.unit)
+                                .hint_text("kg")
+                                .desired_width(60.0),
+                        )
+                        .on_hover_text("Unit of the data, e.g., kg, km, BRL, %.");
+
+                        let units = compatible_units(&column_unit.unit);
+                        if units.is_empty() {
+                            column_unit.display_unit = None;
+                            ui.label("");
+                        } else {
+                            let mut shown = column_unit.shown().to_string();
+                            ComboBox::from_id_salt(("column_unit", name.as_str()))
+                                .selected_text(shown.as_str())
+                                .show_ui(ui, |ui| {
+                                    for unit in &units {
+                                        ui.selectable_value(
+                                            &mut shown,
+                                            unit.symbol.to_string(),
+                                            unit.symbol,
+                                        );
+                                    }
+                                });
+                            column_unit.display_unit =
+                                (shown.trim() != column_unit.unit.trim()).then_some(shown);
+                        }
+                        ui.end_row();
+
+                        if column_unit.unit.trim().is_empty() {
+                            self.column_units.remove(name.as_str());
+                        } else {
+                            self.column_units.insert(name.to_string(), column_unit);
+                        }
+                    }
+                });
+        });
+        ui.end_row();
+    }
+
     /// Renders the `DragValue` widget for adjusting header padding (`self.header_padding`).
     /// Shown conditionally based on `self.use_enhanced_header`.
     /// Modifies `self.header_padding` directly. Affects header height calculation in `container.rs::build_table`.
@@ -306,6 +1172,69 @@ impl DataFormat {
     }
 }
 
+/// Renders the page navigation of paged mode (bottom panel): first/previous/next/last
+/// buttons, the page number and the rows shown. `page` (0-based) is clamped to the last page.
+pub fn render_page_navigation(
+    ui: &mut Ui,
+    page: &mut usize,
+    format: &DataFormat,
+    total_rows: usize,
+) {
+    let page_count = format.page_count(total_rows);
+    *page = (*page).min(page_count - 1);
+    let last = page_count - 1;
+
+    if ui
+        .add_enabled(*page > 0, Button::new("⏮"))
+        .on_hover_text("First page")
+        .clicked()
+    {
+        *page = 0;
+    }
+    if ui
+        .add_enabled(*page > 0, Button::new("◀"))
+        .on_hover_text("Previous page")
+        .clicked()
+    {
+        *page -= 1;
+    }
+
+    let mut number = *page + 1;
+    ui.add(
+        DragValue::new(&mut number)
+            .range(1..=page_count)
+            .prefix("Page "),
+    );
+    ui.label(format!("of {page_count}"));
+    *page = number - 1;
+
+    if ui
+        .add_enabled(*page < last, Button::new("▶"))
+        .on_hover_text("Next page")
+        .clicked()
+    {
+        *page += 1;
+    }
+    if ui
+        .add_enabled(*page < last, Button::new("⏭"))
+        .on_hover_text("Last page")
+        .clicked()
+    {
+        *page = last;
+    }
+
+    let rows = format.page_rows(*page, total_rows);
+    if rows.is_empty() {
+        ui.label("No rows");
+    } else {
+        ui.label(format!(
+            "Rows {}–{} of {total_rows}",
+            rows.start + 1,
+            rows.end
+        ));
+    }
+}
+
 //----------------------------------------------------------------------------//
 //                                   Tests                                    //
 //----------------------------------------------------------------------------//
@@ -314,7 +1243,7 @@ impl DataFormat {
 /// `cargo test -- --show-output tests_format`
 #[cfg(test)]
 mod tests_format {
-    use polars::prelude::*;
+    use super::*;
 
     #[test]
     fn test_quoted_bool_ints() -> PolarsResult<()> {
@@ -339,4 +1268,82 @@ foo,bar,baz
         assert!(df.equals_missing(&expected));
         Ok(())
     }
+
+    #[test]
+    fn test_list_display() {
+        let numbers = Series::new("".into(), &[1.5, 2.0, 3.5, 4.0]);
+        let words = Series::new("".into(), &["a", "b"]);
+
+        assert_eq!(format_list_value(&numbers, ListDisplay::Length, 2), "4");
+        assert_eq!(
+            format_list_value(&numbers, ListDisplay::FirstN(2), 2),
+            "[1.5, 2.0, …]"
+        );
+        assert_eq!(format_list_value(&numbers, ListDisplay::Sum, 2), "11.00");
+        assert_eq!(format_list_value(&numbers, ListDisplay::Mean, 1), "2.8");
+
+        assert_eq!(
+            format_list_value(&words, ListDisplay::FirstN(3), 2),
+            "[a, b]"
+        );
+        // Non-numeric lists cannot be aggregated: shown in full.
+        assert_eq!(format_list_value(&words, ListDisplay::Sum, 2), "[a, b]");
+    }
+
+    #[test]
+    fn test_page_rows() {
+        let format = DataFormat {
+            paged: true,
+            rows_per_page: 10,
+            ..Default::default()
+        };
+
+        assert_eq!(format.page_count(25), 3);
+        assert_eq!(format.page_count(0), 1);
+        assert_eq!(format.page_rows(0, 25), 0..10);
+        assert_eq!(format.page_rows(2, 25), 20..25);
+        assert_eq!(format.page_rows(9, 25), 20..25); // Clamped to the last page.
+        assert_eq!(format.page_of_row(19), 1);
+
+        let unpaged = DataFormat::default();
+        assert_eq!(unpaged.page_rows(3, 25), 0..25);
+    }
+
+    #[test]
+    fn test_null_badge() {
+        assert_eq!(null_badge(0, 10).0, "0%");
+        assert_eq!(null_badge(0, 0).0, "0%");
+        assert_eq!(null_badge(1, 8).0, "12.5%");
+        assert_eq!(null_badge(1, 10_000).0, "<0.1%");
+        assert_eq!(null_badge(9_999, 10_000).0, ">99.9%");
+        assert_eq!(null_badge(5, 5).0, "100%");
+
+        assert_ne!(null_badge(0, 10).1, null_badge(1, 10).1);
+        assert_ne!(null_badge(1, 10).1, null_badge(2, 10).1); // 10% vs 20%.
+    }
+
+    #[test]
+    fn test_infer_decimals() -> PolarsResult<()> {
+        let df = df![
+            "price" => [Some(1.5), Some(2.25), None, Some(3.0)],
+            "rate" => [0.1f32, 0.125, 0.5, 1.0],
+            "noisy" => [0.1 + 0.2, 1.0, 2.0, 3.0],
+            "count" => [1, 2, 3, 4],
+        ]?;
+
+        let decimals = infer_decimals(&df, AUTO_DECIMAL_MAX);
+        assert_eq!(decimals.get("price"), Some(&2));
+        assert_eq!(decimals.get("rate"), Some(&3)); // f32: no noise digits.
+        assert_eq!(decimals.get("noisy"), Some(&AUTO_DECIMAL_MAX)); // Capped.
+        assert_eq!(decimals.get("count"), None); // Not a float column.
+
+        let mut format = DataFormat {
+            auto_decimal: true,
+            ..Default::default()
+        };
+        format.infer_decimals_from(&df);
+        assert_eq!(format.decimal_for("price"), 2);
+        assert_eq!(format.decimal_for("unknown"), format.decimal);
+        Ok(())
+    }
 }
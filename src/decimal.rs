@@ -0,0 +1,114 @@
+//! Display of `Decimal` columns: the `i128` value is formatted exactly with integer
+//! arithmetic (no conversion to `f64`, no overflow), at the column's own scale or at
+//! the scale chosen in the "Format" panel (`DataFormat::decimal_scale`).
+
+use polars::prelude::*;
+
+/// Data type used for `Float64` alignment (see `alignment_dtype`).
+static FLOAT64: DataType = DataType::Float64;
+
+/// Data type whose alignment (`DataFormat::alignments`) applies to `dtype`:
+/// `Decimal` columns (any precision and scale) are aligned like `Float64`.
+pub fn alignment_dtype(dtype: &DataType) -> &DataType {
+    if dtype.is_decimal() { &FLOAT64 } else { dtype }
+}
+
+/// Formats the decimal `value × 10^-scale` with `digits` decimal places (default: `scale`).
+///
+/// Fewer digits than `scale` round half away from zero; more digits pad with zeros.
+pub fn format_decimal(value: i128, scale: usize, digits: Option<usize>) -> String {
+    let digits = digits.unwrap_or(scale);
+    let mut abs = value.unsigned_abs();
+    let mut shown_scale = scale;
+
+    if digits < scale {
+        // `10^(scale - digits)` does not fit in `u128` beyond 38 digits: everything rounds to 0.
+        let divisor = u32::try_from(scale - digits)
+            .ok()
+            .and_then(|exponent| 10u128.checked_pow(exponent));
+        abs = match divisor {
+            Some(divisor) => {
+                let (quotient, remainder) = (abs / divisor, abs % divisor);
+                // `remainder < divisor <= 10^38`, so `2 * remainder` fits in `u128`.
+                quotient + u128::from(remainder >= divisor - remainder)
+            }
+            None => 0,
+        };
+        shown_scale = digits;
+    }
+
+    let mut text = format!("{abs:0>width$}", width = shown_scale + 1);
+    if shown_scale > 0 {
+        text.insert(text.len() - shown_scale, '.');
+    }
+    if digits > shown_scale {
+        text.extend(std::iter::repeat_n('0', digits - shown_scale));
+    }
+    if value < 0 && abs != 0 {
+        text.insert(0, '-');
+    }
+    text
+}
+
+/// Formats the value at `row` of a `Decimal` column (`None` for null).
+pub fn decimal_cell_value(
+    column: &Column,
+    row: usize,
+    digits: Option<usize>,
+) -> PolarsResult<Option<String>> {
+    let values = column.as_materialized_series().decimal()?;
+    let scale = values.scale();
+    Ok(values
+        .physical()
+        .get(row)
+        .map(|value| format_decimal(value, scale, digits)))
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_decimal`
+#[cfg(test)]
+mod tests_decimal {
+    use super::*;
+
+    #[test]
+    fn formats_exactly() {
+        assert_eq!(format_decimal(123_456, 2, None), "1234.56");
+        assert_eq!(format_decimal(-5, 3, None), "-0.005");
+        assert_eq!(format_decimal(42, 0, None), "42");
+
+        // Rounding half away from zero, and padding.
+        assert_eq!(format_decimal(123_455, 3, Some(2)), "123.46");
+        assert_eq!(format_decimal(-123_455, 3, Some(2)), "-123.46");
+        assert_eq!(format_decimal(-4, 3, Some(2)), "0.00"); // No "-0.00".
+        assert_eq!(format_decimal(15, 1, Some(0)), "2");
+        assert_eq!(format_decimal(15, 1, Some(4)), "1.5000");
+
+        // The extremes of i128 do not overflow.
+        assert_eq!(
+            format_decimal(i128::MIN, 10, None),
+            "-17014118346046923173168730371.5884105728"
+        );
+        assert_eq!(format_decimal(i128::MAX, 38, Some(0)), "2");
+    }
+
+    #[test]
+    fn formats_decimal_columns() -> PolarsResult<()> {
+        let column =
+            Column::new("amount".into(), &["10.25", "-3.10"]).cast(&DataType::Decimal(10, 2))?;
+
+        assert_eq!(
+            decimal_cell_value(&column, 0, None)?.as_deref(),
+            Some("10.25")
+        );
+        assert_eq!(
+            decimal_cell_value(&column, 1, Some(1))?.as_deref(),
+            Some("-3.1")
+        );
+        assert_eq!(alignment_dtype(column.dtype()), &DataType::Float64);
+        Ok(())
+    }
+}
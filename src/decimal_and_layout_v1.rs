@@ -1,4 +1,4 @@
-use crate::DataFormat;
+use crate::{DataFormat, alignment_dtype};
 use egui::{Align, Direction, Layout};
 use polars::prelude::Column;
 use std::sync::Arc;
@@ -9,14 +9,21 @@ pub const DEFAULT_OVERRIDE_REGEX: Option<&str> = None;
 
 /// Determines the layout for a given column based on its data type and,
 /// crucially, the alignment settings from DataFilter.
+///
+/// An alignment set for the column itself (`format.column_alignments`, from the header
+/// context menu) takes precedence over the alignment of its data type.
 pub fn get_decimal_and_layout(
     column: &Column,
     format: &Arc<DataFormat>,
 ) -> (Option<usize>, Layout) {
     let dtype = column.dtype();
-    let decimal = format.decimal;
+    let decimal = format.decimal_for(column.name());
 
-    let align = format.alignments.get(dtype).unwrap_or(&Align::LEFT);
+    let align = format
+        .column_alignments
+        .get(column.name().as_str())
+        .or_else(|| format.alignments.get(alignment_dtype(dtype)))
+        .unwrap_or(&Align::LEFT);
 
     let layout = match *align {
         Align::LEFT => Layout::left_to_right(Align::Center),
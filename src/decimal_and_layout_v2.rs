@@ -1,4 +1,4 @@
-use crate::DataFormat;
+use crate::{DataFormat, alignment_dtype};
 use egui::{Align, Direction, Layout};
 use polars::prelude::Column;
 use std::sync::Arc;
@@ -17,18 +17,54 @@ const COL_DECIMAL: usize = 4; // Default number of decimal places to use for flo
 ///
 /// Columns in `COL_SPECIAL` are centered, and their floating-point values have `COL_DECIMAL` decimal places.
 /// Other columns' floating-point values use the `decimal` argument for determining the number of decimal places.
+///
+/// Per-column overrides in `format.column_formats` (e.g., from a SPED/EFD layout preset,
+/// see `sped_presets.rs`) take precedence over all of the above, and an alignment set for
+/// the column from the header context menu (`format.column_alignments`) over its layout.
 pub fn get_decimal_and_layout(
     column: &Column,
     format: &Arc<DataFormat>,
 ) -> (Option<usize>, Layout) {
+    let (decimal, layout) = preset_decimal_and_layout(column, format);
+    let layout = match format.column_alignments.get(column.name().as_str()) {
+        Some(Align::LEFT) => Layout::left_to_right(Align::Center),
+        Some(Align::Center) => Layout::centered_and_justified(Direction::LeftToRight),
+        Some(Align::RIGHT) => Layout::right_to_left(Align::Center),
+        None => layout,
+    };
+    (decimal, layout)
+}
+
+/// The decimal places and layout of `column`, before the alignment of the header menu.
+fn preset_decimal_and_layout(column: &Column, format: &Arc<DataFormat>) -> (Option<usize>, Layout) {
     let column_name = column.name();
     let dtype = column.dtype(); // Get the data type of the column.
-    let decimal = format.decimal;
+    let decimal = format.decimal_for(column_name);
+
+    // Per-column overrides (layout presets) come first.
+    if let Some(column_format) = format.column_formats.get(column_name.as_str()) {
+        let align = column_format
+            .align
+            .or_else(|| format.alignments.get(alignment_dtype(dtype)).copied())
+            .unwrap_or(Align::LEFT);
+        let layout = match align {
+            Align::LEFT => Layout::left_to_right(Align::Center),
+            Align::Center => Layout::centered_and_justified(Direction::LeftToRight),
+            Align::RIGHT => Layout::right_to_left(Align::Center),
+        };
+        let selected_decimal = dtype
+            .is_float()
+            .then(|| column_format.decimal.unwrap_or(decimal));
+        return (selected_decimal, layout);
+    }
     let is_special = COL_SPECIAL
         .iter()
         .any(|&special_name| column_name.contains(special_name)); // Check if the current column is one of the special columns.
 
-    let align = format.alignments.get(dtype).unwrap_or(&Align::LEFT);
+    let align = format
+        .alignments
+        .get(alignment_dtype(dtype))
+        .unwrap_or(&Align::LEFT);
 
     // Determine decimal places and layout based on data type and column name.
     if dtype.is_float() {
@@ -0,0 +1,651 @@
+//! "Compare" window (File menu): the rows of a baseline — the source data before the
+//! query, or another file — matched with the displayed rows on a key column, listing the
+//! added, removed and changed rows (changed cells highlighted). Computed with a lazy full
+//! join off the UI thread (see `execute_polars_blocking`).
+
+use crate::{
+    DataContainer, DataFilter, PolarsViewError, PolarsViewResult, execute_polars_blocking,
+    open_file,
+};
+
+use egui::{Color32, ComboBox, Context, RichText, Ui, Window};
+use egui_extras::{Column as TableColumn, TableBuilder};
+use polars::prelude::*;
+use std::{path::PathBuf, sync::Arc};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// Name of the row status column of the diff (see `DiffStatus`).
+pub const DIFF_STATUS_COLUMN: &str = "__diff_status";
+
+/// Suffix of the compared (displayed) values of a column in the diff.
+pub const DIFF_RIGHT_SUFFIX: &str = "__right";
+
+/// Marker columns of the rows of each side, null after the join for missing rows.
+const IN_LEFT: &str = "__in_left";
+const IN_RIGHT: &str = "__in_right";
+
+/// Status of a row of the diff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffStatus {
+    /// Key only in the displayed data.
+    Added,
+    /// Key only in the baseline.
+    Removed,
+    /// Key in both, with different values.
+    Changed,
+    Unchanged,
+}
+
+impl DiffStatus {
+    pub const ALL: [DiffStatus; 4] = [
+        DiffStatus::Added,
+        DiffStatus::Removed,
+        DiffStatus::Changed,
+        DiffStatus::Unchanged,
+    ];
+
+    /// Value of the status in `DIFF_STATUS_COLUMN`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiffStatus::Added => "added",
+            DiffStatus::Removed => "removed",
+            DiffStatus::Changed => "changed",
+            DiffStatus::Unchanged => "unchanged",
+        }
+    }
+
+    /// The status of a `DIFF_STATUS_COLUMN` value.
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|status| status.label() == label)
+    }
+
+    /// Highlight color of the rows (or changed cells) with this status.
+    pub fn color(&self) -> Option<Color32> {
+        match self {
+            DiffStatus::Added => Some(Color32::from_rgba_unmultiplied(60, 170, 60, 60)),
+            DiffStatus::Removed => Some(Color32::from_rgba_unmultiplied(200, 60, 60, 60)),
+            DiffStatus::Changed => Some(Color32::from_rgba_unmultiplied(220, 170, 40, 70)),
+            DiffStatus::Unchanged => None,
+        }
+    }
+}
+
+/// Result of `diff_frames`.
+#[derive(Debug, Clone)]
+pub struct FrameDiff {
+    /// One row per key: the key, `DIFF_STATUS_COLUMN`, then for each compared column its
+    /// baseline value and its displayed value (`<name>__right`), as strings. Sorted by key.
+    pub df: DataFrame,
+    pub key: String,
+    /// Columns of both sides compared (the key excluded).
+    pub columns: Vec<String>,
+    /// Columns only in the baseline.
+    pub only_left: Vec<String>,
+    /// Columns only in the displayed data.
+    pub only_right: Vec<String>,
+    /// Number of rows of each status (in `DiffStatus::ALL` order), before `only_changes`.
+    pub counts: [usize; 4],
+    /// Whether a side has duplicate keys (each pair of matching rows is then compared).
+    pub duplicate_keys: bool,
+}
+
+/// Compares the baseline `left` with `right`, matching their rows on the column `key`
+/// (present in both, compared as strings). With `only_changes`, unchanged rows are left out.
+pub fn diff_frames(
+    left: &DataFrame,
+    right: &DataFrame,
+    key: &str,
+    only_changes: bool,
+) -> PolarsResult<FrameDiff> {
+    let (left_schema, right_schema) = (left.schema(), right.schema());
+    if !left_schema.contains(key) || !right_schema.contains(key) {
+        polars_bail!(ColumnNotFound: "key column `{key}` must be in both tables");
+    }
+
+    let names = |schema: &Schema| -> Vec<String> {
+        schema
+            .iter_names()
+            .filter(|name| name.as_str() != key)
+            .map(|name| name.to_string())
+            .collect()
+    };
+    let (left_names, right_names) = (names(left_schema), names(right_schema));
+    let columns: Vec<String> = left_names
+        .iter()
+        .filter(|name| right_names.contains(name))
+        .cloned()
+        .collect();
+    let only_left = left_names
+        .iter()
+        .filter(|name| !columns.contains(name))
+        .cloned()
+        .collect();
+    let only_right = right_names
+        .iter()
+        .filter(|name| !columns.contains(name))
+        .cloned()
+        .collect();
+
+    let has_duplicates =
+        |df: &DataFrame| -> PolarsResult<bool> { Ok(df.column(key)?.n_unique()? < df.height()) };
+    let duplicate_keys = has_duplicates(left)? || has_duplicates(right)?;
+
+    // Both sides as strings, so columns of different types (e.g., two files) compare.
+    let side = |df: &DataFrame, marker: &str| {
+        let mut exprs = vec![col(key).cast(DataType::String)];
+        exprs.extend(
+            columns
+                .iter()
+                .map(|name| col(name.as_str()).cast(DataType::String)),
+        );
+        exprs.push(lit(true).alias(marker));
+        df.clone().lazy().select(exprs)
+    };
+
+    let right_name = |name: &str| format!("{name}{DIFF_RIGHT_SUFFIX}");
+    let changed = columns
+        .iter()
+        .map(|name| col(name.as_str()).neq_missing(col(right_name(name))))
+        .reduce(|a, b| a.or(b))
+        .unwrap_or(lit(false));
+    let status = when(col(IN_LEFT).is_null())
+        .then(lit(DiffStatus::Added.label()))
+        .when(col(IN_RIGHT).is_null())
+        .then(lit(DiffStatus::Removed.label()))
+        .when(changed)
+        .then(lit(DiffStatus::Changed.label()))
+        .otherwise(lit(DiffStatus::Unchanged.label()))
+        .alias(DIFF_STATUS_COLUMN);
+
+    let mut select = vec![col(key), status];
+    for name in &columns {
+        select.push(col(name.as_str()));
+        select.push(col(right_name(name)));
+    }
+
+    let joined = side(left, IN_LEFT)
+        .join(
+            side(right, IN_RIGHT),
+            [col(key)],
+            [col(key)],
+            JoinArgs::new(JoinType::Full)
+                .with_coalesce(JoinCoalesce::CoalesceColumns)
+                .with_suffix(Some(DIFF_RIGHT_SUFFIX.into())),
+        )
+        .select(select)
+        .sort([key], SortMultipleOptions::default().with_nulls_last(true))
+        .collect()?;
+
+    let mut counts = [0; 4];
+    for label in joined
+        .column(DIFF_STATUS_COLUMN)?
+        .str()?
+        .into_no_null_iter()
+    {
+        if let Some(index) = DiffStatus::ALL.iter().position(|s| s.label() == label) {
+            counts[index] += 1;
+        }
+    }
+
+    let df = if only_changes {
+        joined
+            .lazy()
+            .filter(col(DIFF_STATUS_COLUMN).neq(lit(DiffStatus::Unchanged.label())))
+            .collect()?
+    } else {
+        joined
+    };
+
+    Ok(FrameDiff {
+        df,
+        key: key.to_string(),
+        columns,
+        only_left,
+        only_right,
+        counts,
+        duplicate_keys,
+    })
+}
+
+/// The data the displayed rows are compared with.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum DiffBaseline {
+    /// The data as read from the file, before the SQL query and the other transforms
+    /// (`DataContainer::df_original`).
+    #[default]
+    SourceData,
+    /// Another file, read with the default load settings.
+    File(PathBuf),
+}
+
+/// Parameters of a computation (a change triggers a new one).
+#[derive(Debug, Clone, PartialEq)]
+struct DiffRequest {
+    key: String,
+    only_changes: bool,
+    /// Addresses of the baseline and displayed `DataFrame`s.
+    frames: (usize, usize),
+}
+
+/// State of the "Compare" window: the baseline, the key column, and the result of the
+/// last (background) computation.
+#[derive(Debug, Default)]
+pub struct DiffView {
+    /// Whether the window is shown.
+    pub open: bool,
+    pub baseline: DiffBaseline,
+    /// The key column matching the rows of both sides.
+    pub key: String,
+    /// Leaves the unchanged rows out of the table.
+    pub only_changes: bool,
+    /// The other file, once read (`DiffBaseline::File`).
+    file_data: Option<Arc<DataFrame>>,
+    loading: Option<oneshot::Receiver<PolarsViewResult<DataFrame>>>,
+    result: Option<FrameDiff>,
+    error: Option<String>,
+    requested: Option<DiffRequest>,
+    pending: Option<oneshot::Receiver<PolarsResult<FrameDiff>>>,
+}
+
+impl DiffView {
+    /// Whether a file read or a comparison is still running (receives the results that
+    /// arrived). Used for the busy indicator of the tabs, including inactive ones.
+    pub fn is_pending(&mut self) -> bool {
+        self.poll();
+        self.loading.is_some() || self.pending.is_some()
+    }
+
+    /// Receives the results of the pending file read and comparison, if available.
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.loading {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.loading = None;
+                    match result {
+                        Ok(df) => self.file_data = Some(Arc::new(df)),
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.loading = None;
+                    self.error = Some("file read ended unexpectedly".to_string());
+                }
+            }
+        }
+        if let Some(rx) = &mut self.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.pending = None;
+                    match result {
+                        Ok(diff) => self.result = Some(diff),
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.pending = None;
+                    self.error = Some("comparison ended unexpectedly".to_string());
+                }
+            }
+        }
+    }
+
+    /// Asks for the baseline file (native dialog) and starts reading it on `runtime`.
+    fn choose_file(&mut self, runtime: &Handle, ctx: &Context) {
+        let path = match runtime.block_on(open_file()) {
+            Ok(path) => path,
+            Err(PolarsViewError::FileNotFound(_)) => return, // Dialog cancelled.
+            Err(error) => {
+                self.error = Some(error.to_string());
+                return;
+            }
+        };
+
+        let (tx, rx) = oneshot::channel();
+        let ctx = ctx.clone();
+        let file = path.clone();
+        runtime.spawn(async move {
+            let read = async {
+                let mut filter = DataFilter::default();
+                filter.set_path(&file)?;
+                Ok::<_, PolarsViewError>(filter.get_df_and_extension().await?.0)
+            };
+            if tx.send(read.await).is_err() {
+                tracing::debug!("Compare file receiver dropped.");
+            }
+            ctx.request_repaint();
+        });
+
+        self.baseline = DiffBaseline::File(path);
+        self.file_data = None;
+        self.result = None;
+        self.error = None;
+        self.requested = None;
+        self.loading = Some(rx);
+    }
+
+    /// Starts `diff_frames` on `runtime` (via `execute_polars_blocking`).
+    /// A comparison still pending is abandoned (its result is dropped).
+    fn spawn_compute(
+        &mut self,
+        frames: (Arc<DataFrame>, Arc<DataFrame>),
+        runtime: &Handle,
+        ctx: &Context,
+    ) {
+        let (tx, rx) = oneshot::channel();
+        let (key, only_changes) = (self.key.clone(), self.only_changes);
+        let ctx = ctx.clone();
+
+        runtime.spawn(async move {
+            let (left, right) = frames;
+            let result =
+                execute_polars_blocking(move || diff_frames(&left, &right, &key, only_changes))
+                    .await;
+            if tx.send(result).is_err() {
+                tracing::debug!("Compare receiver dropped.");
+            }
+            ctx.request_repaint();
+        });
+
+        self.result = None;
+        self.error = None;
+        self.pending = Some(rx);
+    }
+
+    /// The baseline `DataFrame` (`None` while the other file is not read).
+    fn baseline_data(&self, container: &DataContainer) -> Option<Arc<DataFrame>> {
+        match self.baseline {
+            DiffBaseline::SourceData => Some(container.df_original.clone()),
+            DiffBaseline::File(_) => self.file_data.clone(),
+        }
+    }
+
+    /// Shows the window (if open), comparing the baseline with the displayed rows of
+    /// `container`. The comparison is recomputed on `runtime` when its parameters change.
+    pub fn show(&mut self, ctx: &Context, container: &DataContainer, runtime: &Handle) {
+        self.poll();
+        if !self.open {
+            return;
+        }
+
+        let right = container.df.clone();
+        let left = self.baseline_data(container);
+
+        // Key candidates: the columns of both sides (the first one by default).
+        let keys: Vec<String> = left
+            .as_ref()
+            .map(|left| {
+                right
+                    .get_column_names()
+                    .into_iter()
+                    .filter(|name| left.schema().contains(name))
+                    .map(|name| name.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        if !keys.contains(&self.key)
+            && let Some(first) = keys.first()
+        {
+            self.key = first.clone();
+        }
+
+        if let Some(left) = left.as_ref().filter(|_| keys.contains(&self.key)) {
+            let request = DiffRequest {
+                key: self.key.clone(),
+                only_changes: self.only_changes,
+                frames: (Arc::as_ptr(left) as usize, Arc::as_ptr(&right) as usize),
+            };
+            if self.requested.as_ref() != Some(&request) {
+                self.spawn_compute((left.clone(), right.clone()), runtime, ctx);
+                self.requested = Some(request);
+            }
+        }
+
+        let mut open = self.open;
+        Window::new("Compare")
+            .id(egui::Id::new("diff_view_window"))
+            .open(&mut open)
+            .default_width(640.0)
+            .default_height(480.0)
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.render_controls(ui, &keys, runtime);
+                ui.separator();
+
+                if self.loading.is_some() || self.pending.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Comparing...");
+                    });
+                } else if let Some(error) = &self.error {
+                    ui.colored_label(Color32::RED, error);
+                } else if keys.is_empty() && left.is_some() {
+                    ui.label("The tables have no column in common to match their rows.");
+                } else if let Some(diff) = &self.result {
+                    render_summary(ui, diff);
+                    render_diff(ui, diff);
+                }
+            });
+        self.open = open;
+    }
+
+    /// Renders the baseline choice, the key column and the "Only differences" toggle.
+    fn render_controls(&mut self, ui: &mut Ui, keys: &[String], runtime: &Handle) {
+        ui.horizontal(|ui| {
+            ui.label("Baseline:");
+            if ui
+                .radio(self.baseline == DiffBaseline::SourceData, "Source data")
+                .on_hover_text("The data as read from the file, before the SQL query.")
+                .clicked()
+            {
+                self.baseline = DiffBaseline::SourceData;
+            }
+            let file_label = match &self.baseline {
+                DiffBaseline::File(path) => path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                DiffBaseline::SourceData => "Other file...".to_string(),
+            };
+            if ui
+                .radio(matches!(self.baseline, DiffBaseline::File(_)), file_label)
+                .on_hover_text("Choose another file to compare with the displayed rows.")
+                .clicked()
+            {
+                self.choose_file(runtime, ui.ctx());
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Key column:");
+            ComboBox::from_id_salt("diff_view_key")
+                .selected_text(self.key.as_str())
+                .show_ui(ui, |ui| {
+                    for key in keys {
+                        ui.selectable_value(&mut self.key, key.clone(), key.as_str());
+                    }
+                });
+            ui.checkbox(&mut self.only_changes, "Only differences");
+        });
+    }
+}
+
+/// Renders the row counts per status and the columns of a single side.
+fn render_summary(ui: &mut Ui, diff: &FrameDiff) {
+    ui.horizontal_wrapped(|ui| {
+        for (status, count) in DiffStatus::ALL.iter().zip(diff.counts) {
+            let text = RichText::new(format!("{count} {}", status.label()));
+            match status.color() {
+                Some(color) => ui.label(text.background_color(color)),
+                None => ui.label(text),
+            };
+        }
+    });
+    if !diff.only_left.is_empty() {
+        ui.label(format!(
+            "Only in the baseline: {}",
+            diff.only_left.join(", ")
+        ));
+    }
+    if !diff.only_right.is_empty() {
+        ui.label(format!(
+            "Only in the displayed data: {}",
+            diff.only_right.join(", ")
+        ));
+    }
+    if diff.duplicate_keys {
+        ui.colored_label(
+            Color32::YELLOW,
+            format!(
+                "`{}` is not unique: rows with the same key are paired.",
+                diff.key
+            ),
+        );
+    }
+}
+
+/// Renders the diff as a table: the status and the key, then one column per compared
+/// column, showing `baseline → displayed` for the changed cells (highlighted).
+fn render_diff(ui: &mut Ui, diff: &FrameDiff) {
+    let df = &diff.df;
+    let (Ok(keys), Ok(statuses)) = (
+        df.column(&diff.key)
+            .and_then(|column| column.str().cloned()),
+        df.column(DIFF_STATUS_COLUMN)
+            .and_then(|column| column.str().cloned()),
+    ) else {
+        return;
+    };
+    let values: Vec<(StringChunked, StringChunked)> = diff
+        .columns
+        .iter()
+        .filter_map(|name| {
+            let left = df.column(name).ok()?.str().ok()?.clone();
+            let right = df
+                .column(&format!("{name}{DIFF_RIGHT_SUFFIX}"))
+                .ok()?
+                .str()
+                .ok()?
+                .clone();
+            Some((left, right))
+        })
+        .collect();
+
+    let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+    let highlight = |ui: &mut Ui, status: DiffStatus| {
+        if let Some(color) = status.color() {
+            ui.painter().rect_filled(ui.max_rect(), 0.0, color);
+        }
+    };
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .columns(
+            TableColumn::auto().at_least(60.0).clip(true),
+            values.len() + 2,
+        )
+        .header(text_height + 6.0, |mut header| {
+            let names = ["status", diff.key.as_str()]
+                .into_iter()
+                .chain(diff.columns.iter().map(String::as_str));
+            for name in names {
+                header.col(|ui| {
+                    ui.label(RichText::new(name).strong());
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(text_height + 4.0, df.height(), |mut row| {
+                let index = row.index();
+                let status = statuses
+                    .get(index)
+                    .and_then(DiffStatus::from_label)
+                    .unwrap_or(DiffStatus::Unchanged);
+                let row_status = match status {
+                    DiffStatus::Changed => DiffStatus::Unchanged, // Only the changed cells.
+                    other => other,
+                };
+
+                row.col(|ui| {
+                    highlight(ui, status);
+                    ui.label(status.label());
+                });
+                row.col(|ui| {
+                    highlight(ui, row_status);
+                    ui.label(keys.get(index).unwrap_or("null"));
+                });
+                for (left, right) in &values {
+                    let (left, right) = (left.get(index), right.get(index));
+                    row.col(|ui| {
+                        let (text, cell_status) = match status {
+                            DiffStatus::Added => (right.unwrap_or("null").to_string(), row_status),
+                            DiffStatus::Removed => (left.unwrap_or("null").to_string(), row_status),
+                            _ if left != right => (
+                                format!("{} → {}", left.unwrap_or("null"), right.unwrap_or("null")),
+                                DiffStatus::Changed,
+                            ),
+                            _ => (left.unwrap_or("null").to_string(), DiffStatus::Unchanged),
+                        };
+                        highlight(ui, cell_status);
+                        ui.label(text);
+                    });
+                }
+            });
+        });
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_diff_view`
+#[cfg(test)]
+mod tests_diff_view {
+    use super::*;
+
+    #[test]
+    fn rows_matched_on_key() -> PolarsResult<()> {
+        let before = df!(
+            "id" => &[1, 2, 3],
+            "name" => &["a", "b", "c"],
+            "old" => &[true, false, true]
+        )?;
+        let after = df!(
+            "id" => &[2, 3, 4],
+            "name" => &["b", "C", "d"]
+        )?;
+
+        let diff = diff_frames(&before, &after, "id", false)?;
+        dbg!(&diff.df);
+
+        let statuses: Vec<Option<&str>> = diff
+            .df
+            .column(DIFF_STATUS_COLUMN)?
+            .str()?
+            .into_iter()
+            .collect();
+        assert_eq!(
+            statuses,
+            [
+                Some("removed"),
+                Some("unchanged"),
+                Some("changed"),
+                Some("added")
+            ]
+        );
+        assert_eq!(diff.counts, [1, 1, 1, 1]);
+        assert_eq!(diff.columns, ["name"]);
+        assert_eq!(diff.only_left, ["old"]);
+        assert!(!diff.duplicate_keys);
+
+        let changes = diff_frames(&before, &after, "id", true)?;
+        assert_eq!(changes.df.height(), 3);
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+//! Per-directory configuration: a `.polars-view.toml` file next to the data, with the
+//! conventions of a dataset (CSV delimiter, null values, columns read as String, startup
+//! SQL query), applied to the files opened from that directory.
+//!
+//! Example (all keys are optional):
+//! ```toml
+//! delimiter = "|"
+//! null_values = "\"\", NA, <N/D>"
+//! force_string_cols = "^(Chave|CNPJ).*$"
+//! table_name = "Sales"
+//! query = "SELECT * FROM Sales WHERE Valor > 0;"
+//! ```
+
+use crate::{DataFilter, PolarsViewResult, is_safe_mode};
+
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// Name of the per-directory configuration file.
+pub const DIR_CONFIG_FILE_NAME: &str = ".polars-view.toml";
+
+/// Settings of a `.polars-view.toml` file (unknown keys are rejected, to catch typos).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DirConfig {
+    /// CSV delimiter.
+    pub delimiter: Option<String>,
+    /// Comma-separated values read as null (CSV).
+    pub null_values: Option<String>,
+    /// Regex of the columns read as String (see `--force-string-cols`).
+    pub force_string_cols: Option<String>,
+    /// Table name used in the SQL query.
+    pub table_name: Option<String>,
+    /// SQL query applied after loading.
+    pub query: Option<String>,
+}
+
+impl DirConfig {
+    /// Path of the configuration file of the directory containing `file`.
+    pub fn path_for(file: &Path) -> Option<PathBuf> {
+        file.parent().map(|dir| dir.join(DIR_CONFIG_FILE_NAME))
+    }
+
+    /// Reads the configuration of the directory containing `file`.
+    ///
+    /// Returns `None` if the directory has no `.polars-view.toml`, or in safe mode.
+    pub fn for_file(file: &Path) -> PolarsViewResult<Option<Self>> {
+        if is_safe_mode() {
+            return Ok(None);
+        }
+        let Some(path) = Self::path_for(file) else {
+            return Ok(None);
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(text) => {
+                tracing::info!(target: "polars_view", "Applying directory config: {}", path.display());
+                Ok(Some(toml::from_str(&text)?))
+            }
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Overrides the settings of `filter` present in this configuration.
+    /// A `query` is marked to be applied after loading.
+    pub fn apply_to(&self, filter: &mut DataFilter) {
+        if let Some(delimiter) = &self.delimiter {
+            filter.csv_delimiter = delimiter.clone();
+        }
+        if let Some(null_values) = &self.null_values {
+            filter.null_values = null_values.clone();
+        }
+        if let Some(patterns) = &self.force_string_cols {
+            filter.force_string_patterns = Some(patterns.clone());
+        }
+        if let Some(table_name) = &self.table_name {
+            filter.table_name = table_name.clone();
+        }
+        if let Some(query) = &self.query {
+            filter.query = query.clone();
+            filter.apply_sql = true;
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_dir_config`
+#[cfg(test)]
+mod tests_dir_config {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reads_and_applies_the_directory_config() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let file = dir.path().join("sales.csv");
+
+        // No config file.
+        assert_eq!(DirConfig::for_file(&file)?, None);
+
+        std::fs::write(
+            dir.path().join(DIR_CONFIG_FILE_NAME),
+            "delimiter = \"|\"\nforce_string_cols = \"^Chave\"\nquery = \"SELECT id FROM AllData;\"\n",
+        )?;
+        let config = DirConfig::for_file(&file)?.expect("config file");
+        assert_eq!(config.delimiter.as_deref(), Some("|"));
+        assert_eq!(config.null_values, None);
+
+        let mut filter = DataFilter::default();
+        let null_values = filter.null_values.clone();
+        config.apply_to(&mut filter);
+        assert_eq!(filter.csv_delimiter, "|");
+        assert_eq!(filter.null_values, null_values); // Not in the config: unchanged.
+        assert_eq!(filter.force_string_patterns.as_deref(), Some("^Chave"));
+        assert_eq!(filter.query, "SELECT id FROM AllData;");
+        assert!(filter.apply_sql);
+
+        // Typos are errors.
+        std::fs::write(dir.path().join(DIR_CONFIG_FILE_NAME), "delimeter = \";\"\n")?;
+        assert!(DirConfig::for_file(&file).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,385 @@
+//! Value distribution of a column for the "Distribution" side panel section:
+//! a histogram for numeric columns, a top-N bar chart of value counts otherwise.
+//! Computed off the UI thread (see `execute_polars_blocking`) and drawn with `egui_plot`.
+
+use crate::{PolarsViewResult, execute_polars_blocking};
+
+use egui::{ComboBox, DragValue, Ui};
+use egui_plot::{Bar, BarChart, Plot};
+use polars::prelude::*;
+use std::sync::Arc;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// Name of the count column of the value counts (unlikely to clash with data columns).
+const COUNT_COLUMN: &str = "__value_count";
+
+/// A histogram bin: values in `[start, end)` (the last bin also includes `end`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HistogramBin {
+    pub start: f64,
+    pub end: f64,
+    pub count: usize,
+}
+
+/// Distribution of the values of a column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    /// Numeric (and `Decimal`) columns: equal-width bins between the minimum and maximum (nulls,
+    /// NaN and infinite values are skipped).
+    Histogram(Vec<HistogramBin>),
+    /// Other columns: the most frequent values (nulls as `null`), most frequent first,
+    /// and the number of rows with any other value.
+    TopValues {
+        values: Vec<(String, usize)>,
+        others: usize,
+    },
+}
+
+/// Splits the finite `values` into `bins` equal-width bins.
+///
+/// All values equal (zero width): a single bin. No finite values: no bins.
+pub fn histogram(values: impl Iterator<Item = f64>, bins: usize) -> Vec<HistogramBin> {
+    let values: Vec<f64> = values.filter(|value| value.is_finite()).collect();
+    let (min, max) = values
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &v| {
+            (min.min(v), max.max(v))
+        });
+
+    if values.is_empty() || bins == 0 {
+        return Vec::new();
+    }
+    if min == max {
+        return vec![HistogramBin {
+            start: min,
+            end: max,
+            count: values.len(),
+        }];
+    }
+
+    let width = (max - min) / bins as f64;
+    let mut counts = vec![0; bins];
+    for value in values {
+        let index = (((value - min) / width) as usize).min(bins - 1);
+        counts[index] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| HistogramBin {
+            start: min + index as f64 * width,
+            end: min + (index + 1) as f64 * width,
+            count,
+        })
+        .collect()
+}
+
+/// Computes the distribution of the column `name` of `df`: a histogram with `bins` bins
+/// for numeric columns, otherwise the `top_n` most frequent values.
+pub fn column_distribution(
+    df: &DataFrame,
+    name: &str,
+    bins: usize,
+    top_n: usize,
+) -> PolarsResult<Distribution> {
+    let column = df.column(name)?;
+
+    if column.dtype().is_primitive_numeric() || column.dtype().is_decimal() {
+        let values = column.cast(&DataType::Float64)?;
+        let values = values.f64()?.into_iter().flatten();
+        return Ok(Distribution::Histogram(histogram(values, bins)));
+    }
+
+    let counts = df
+        .clone()
+        .lazy()
+        .select([col(name).cast(DataType::String)])
+        .group_by([col(name)])
+        .agg([len().cast(DataType::UInt64).alias(COUNT_COLUMN)])
+        .sort_by_exprs(
+            [col(COUNT_COLUMN), col(name)],
+            SortMultipleOptions::default()
+                .with_order_descending_multi([true, false])
+                .with_nulls_last(true),
+        )
+        .collect()?;
+
+    let labels = counts.column(name)?.str()?;
+    let values: Vec<(String, usize)> = labels
+        .into_iter()
+        .zip(counts.column(COUNT_COLUMN)?.u64()?)
+        .map(|(label, count)| {
+            (
+                label.unwrap_or("null").to_string(),
+                count.unwrap_or(0) as usize,
+            )
+        })
+        .collect();
+
+    let others = values.iter().skip(top_n).map(|(_, count)| count).sum();
+    Ok(Distribution::TopValues {
+        values: values.into_iter().take(top_n).collect(),
+        others,
+    })
+}
+
+/// Parameters of a distribution computation (a change triggers a new one).
+#[derive(Debug, Clone, PartialEq)]
+struct DistributionRequest {
+    column_name: String,
+    bins: usize,
+    top_n: usize,
+    /// Address of the `DataFrame`.
+    df: usize,
+}
+
+/// State of the "Distribution" side panel section: column, number of bins / top values,
+/// and the chart of the last (background) computation.
+#[derive(Debug)]
+pub struct DistributionChart {
+    /// The column to chart.
+    pub column_name: String,
+    /// Number of histogram bins (numeric columns).
+    pub bins: usize,
+    /// Number of values shown (other columns).
+    pub top_n: usize,
+    distribution: Option<Distribution>,
+    error: Option<String>,
+    requested: Option<DistributionRequest>,
+    pending: Option<oneshot::Receiver<PolarsViewResult<Distribution>>>,
+}
+
+impl Default for DistributionChart {
+    fn default() -> Self {
+        DistributionChart {
+            column_name: String::new(),
+            bins: 20,
+            top_n: 15,
+            distribution: None,
+            error: None,
+            requested: None,
+            pending: None,
+        }
+    }
+}
+
+impl DistributionChart {
+    /// Whether a computation is still running (receives its result if it arrived).
+    ///
+    /// Used for the busy indicator of the tabs, including inactive ones.
+    pub fn is_pending(&mut self) -> bool {
+        self.poll();
+        self.pending.is_some()
+    }
+
+    /// Receives the result of a pending computation, if available.
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.pending = None;
+                    match result {
+                        Ok(distribution) => self.distribution = Some(distribution),
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.pending = None;
+                    self.error = Some("distribution computation ended unexpectedly".to_string());
+                }
+            }
+        }
+    }
+
+    /// Starts `column_distribution` on `runtime` (via `execute_polars_blocking`).
+    /// A computation still pending is abandoned (its result is dropped).
+    fn spawn_compute(&mut self, df: Arc<DataFrame>, runtime: &Handle, ctx: &egui::Context) {
+        let (tx, rx) = oneshot::channel();
+        let (column_name, bins, top_n) = (self.column_name.clone(), self.bins, self.top_n);
+        let ctx = ctx.clone();
+
+        runtime.spawn(async move {
+            let result = execute_polars_blocking(move || {
+                column_distribution(&df, &column_name, bins, top_n)
+            })
+            .await;
+            if tx.send(result).is_err() {
+                tracing::debug!("Distribution receiver dropped.");
+            }
+            ctx.request_repaint();
+        });
+
+        self.distribution = None;
+        self.error = None;
+        self.pending = Some(rx);
+    }
+
+    /// Renders the controls and the chart of the selected column of `df`.
+    ///
+    /// The distribution is recomputed on `runtime` when the column, the parameters or
+    /// `df` change.
+    pub fn render(&mut self, ui: &mut Ui, df: &Arc<DataFrame>, runtime: &Handle) {
+        self.poll();
+
+        let schema = df.schema();
+        let columns: Vec<&PlSmallStr> = schema
+            .iter()
+            .filter(|(_name, dtype)| !dtype.is_nested())
+            .map(|(name, _dtype)| name)
+            .collect();
+
+        let Some(first) = columns.first() else {
+            ui.label("No columns to chart.");
+            return;
+        };
+        if !schema.contains(&self.column_name) {
+            self.column_name = first.to_string();
+        }
+        let is_numeric = schema
+            .get(&self.column_name)
+            .is_some_and(|dtype| dtype.is_primitive_numeric() || dtype.is_decimal());
+
+        ui.horizontal(|ui| {
+            ui.label("Column:");
+            ComboBox::from_id_salt("distribution_column")
+                .selected_text(&self.column_name)
+                .show_ui(ui, |ui| {
+                    for name in &columns {
+                        ui.selectable_value(&mut self.column_name, name.to_string(), name.as_str());
+                    }
+                });
+        });
+        ui.horizontal(|ui| {
+            if is_numeric {
+                ui.label("Bins:");
+                ui.add(DragValue::new(&mut self.bins).range(1..=200));
+            } else {
+                ui.label("Top values:");
+                ui.add(DragValue::new(&mut self.top_n).range(1..=100));
+            }
+        });
+
+        let request = DistributionRequest {
+            column_name: self.column_name.clone(),
+            bins: self.bins,
+            top_n: self.top_n,
+            df: Arc::as_ptr(df) as usize,
+        };
+        if self.requested.as_ref() != Some(&request) {
+            self.spawn_compute(df.clone(), runtime, ui.ctx());
+            self.requested = Some(request);
+        }
+
+        if self.pending.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Computing distribution...");
+            });
+        } else if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        } else if let Some(distribution) = &self.distribution {
+            render_chart(ui, &self.column_name, distribution);
+        }
+    }
+}
+
+/// Draws `distribution` as a bar chart (histogram bars touch; value bars are horizontal,
+/// most frequent on top, with the value as the bar name shown on hover).
+fn render_chart(ui: &mut Ui, column_name: &str, distribution: &Distribution) {
+    let plot = Plot::new("distribution_plot")
+        .height(220.0)
+        .allow_scroll(false)
+        .allow_drag(false);
+
+    match distribution {
+        Distribution::Histogram(bins) if bins.is_empty() => {
+            ui.label("No finite values.");
+        }
+        Distribution::Histogram(bins) => {
+            let bars = bins
+                .iter()
+                .map(|bin| {
+                    let width = (bin.end - bin.start).max(f64::EPSILON);
+                    Bar::new((bin.start + bin.end) / 2.0, bin.count as f64)
+                        .width(width)
+                        .name(format!("[{:.4}, {:.4}]", bin.start, bin.end))
+                })
+                .collect();
+            plot.show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(column_name, bars));
+            });
+        }
+        Distribution::TopValues { values, others } => {
+            let bars = values
+                .iter()
+                .enumerate()
+                .map(|(index, (label, count))| {
+                    // Most frequent at the top.
+                    Bar::new((values.len() - index) as f64, *count as f64)
+                        .width(0.8)
+                        .name(label)
+                })
+                .collect();
+            plot.show(ui, |plot_ui| {
+                plot_ui.bar_chart(BarChart::new(column_name, bars).horizontal());
+            });
+            if *others > 0 {
+                ui.label(format!("Other values: {others} rows"));
+            }
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_distribution`
+#[cfg(test)]
+mod tests_distribution {
+    use super::*;
+
+    #[test]
+    fn histogram_bins() {
+        let bins = histogram([0.0, 1.0, 2.5, 5.0, 10.0, f64::NAN].into_iter(), 2);
+        assert_eq!(
+            bins,
+            vec![
+                HistogramBin {
+                    start: 0.0,
+                    end: 5.0,
+                    count: 3
+                },
+                HistogramBin {
+                    start: 5.0,
+                    end: 10.0,
+                    count: 2
+                },
+            ]
+        );
+
+        assert_eq!(histogram([3.0, 3.0].into_iter(), 10).len(), 1);
+        assert!(histogram(std::iter::empty(), 10).is_empty());
+    }
+
+    #[test]
+    fn top_values_of_strings() -> PolarsResult<()> {
+        let df = df!("city" => &[Some("b"), Some("a"), Some("b"), None, Some("c"), Some("b")])?;
+
+        let distribution = column_distribution(&df, "city", 10, 2)?;
+        assert_eq!(
+            distribution,
+            Distribution::TopValues {
+                values: vec![("b".to_string(), 3), ("a".to_string(), 1)],
+                others: 2,
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,444 @@
+//! Fuzzy duplicate detection for String columns.
+//! Finds near-duplicate values (e.g., supplier names typed in different ways) and groups
+//! them for review in the "Duplicates" side panel section.
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use egui::{Align, ComboBox, DragValue, Frame, Grid, Layout, ScrollArea, Ui};
+use polars::prelude::*;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// Maximum number of distinct values compared pairwise by `find_near_duplicates`.
+pub const MAX_FUZZY_VALUES: usize = 5_000;
+
+/// Similarity measure used to compare two (normalized) values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimilarityMetric {
+    /// `1 - levenshtein distance / longest length`: catches typos and small variations.
+    Levenshtein,
+    /// Jaccard index of the word sets: catches reordered words (`Acme Ltda` / `Ltda Acme`).
+    TokenSet,
+}
+
+impl SimilarityMetric {
+    /// Returns the similarity of two normalized values, in `[0, 1]`.
+    pub fn similarity(&self, a: &str, b: &str) -> f64 {
+        match self {
+            SimilarityMetric::Levenshtein => normalized_levenshtein(a, b),
+            SimilarityMetric::TokenSet => token_set_similarity(a, b),
+        }
+    }
+}
+
+/// A group of near-duplicate values, with their number of occurrences.
+/// Members are sorted by count (most frequent first).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateGroup {
+    pub members: Vec<(String, usize)>,
+}
+
+impl DuplicateGroup {
+    /// Total number of rows covered by the group.
+    pub fn total_count(&self) -> usize {
+        self.members.iter().map(|(_value, count)| count).sum()
+    }
+}
+
+/// Normalizes a value for comparison: lowercase, punctuation replaced by spaces,
+/// whitespace collapsed (e.g., `" ACME  Ltda. "` -> `"acme ltda"`).
+pub fn normalize_value(value: &str) -> String {
+    value
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Levenshtein similarity: `1 - distance / max(len)`, computed over chars.
+/// Two empty strings are identical (`1.0`).
+pub fn normalized_levenshtein(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let max_len = a.len().max(b.len());
+
+    if max_len == 0 {
+        return 1.0;
+    }
+
+    // Two-row dynamic programming.
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, char_a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(char_a != char_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+
+    1.0 - previous[b.len()] as f64 / max_len as f64
+}
+
+/// Token-set similarity: Jaccard index of the whitespace-separated words.
+/// Two values without words are identical (`1.0`).
+pub fn token_set_similarity(a: &str, b: &str) -> f64 {
+    let tokens_a: HashSet<&str> = a.split_whitespace().collect();
+    let tokens_b: HashSet<&str> = b.split_whitespace().collect();
+    let union = tokens_a.union(&tokens_b).count();
+
+    if union == 0 {
+        return 1.0;
+    }
+
+    tokens_a.intersection(&tokens_b).count() as f64 / union as f64
+}
+
+/// Finds groups of near-duplicate values in a String (or Categorical) column.
+///
+/// ### Logic
+/// 1. Count the distinct non-null values.
+/// 2. Compare the normalized values pairwise (`normalize_value`, then `metric`).
+/// 3. Link pairs with `similarity >= threshold` (union-find), so groups are transitive.
+/// 4. Return groups with more than one distinct value, largest total count first.
+///
+/// ### Errors
+/// * `PolarsViewError::Polars`: If the column does not exist or cannot be read as String.
+/// * `PolarsViewError::Other`: If the column has more than `MAX_FUZZY_VALUES` distinct values.
+pub fn find_near_duplicates(
+    df: &DataFrame,
+    column_name: &str,
+    metric: SimilarityMetric,
+    threshold: f64,
+) -> PolarsViewResult<Vec<DuplicateGroup>> {
+    let column = df.column(column_name)?.cast(&DataType::String)?;
+
+    // 1. Count distinct values.
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for value in column.as_materialized_series().str()?.into_iter().flatten() {
+        *counts.entry(value).or_default() += 1;
+    }
+
+    if counts.len() > MAX_FUZZY_VALUES {
+        return Err(PolarsViewError::Other(format!(
+            "Column `{column_name}` has {} distinct values; fuzzy duplicate detection is limited to {MAX_FUZZY_VALUES}. \
+            Filter the data first (e.g., with a SQL query).",
+            counts.len()
+        )));
+    }
+
+    let values: Vec<(&str, usize)> = counts.into_iter().collect();
+    let normalized: Vec<String> = values.iter().map(|(v, _)| normalize_value(v)).collect();
+
+    // 2-3. Pairwise comparison with union-find.
+    let mut parent: Vec<usize> = (0..values.len()).collect();
+
+    fn find(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]]; // Path halving.
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..values.len() {
+        for j in (i + 1)..values.len() {
+            if metric == SimilarityMetric::Levenshtein {
+                // Length bound: the distance is at least the length difference.
+                let (len_i, len_j) = (normalized[i].chars().count(), normalized[j].chars().count());
+                let max_len = len_i.max(len_j).max(1);
+                if 1.0 - len_i.abs_diff(len_j) as f64 / (max_len as f64) < threshold {
+                    continue;
+                }
+            }
+
+            if metric.similarity(&normalized[i], &normalized[j]) >= threshold {
+                let (root_i, root_j) = (find(&mut parent, i), find(&mut parent, j));
+                if root_i != root_j {
+                    parent[root_j] = root_i;
+                }
+            }
+        }
+    }
+
+    // 4. Collect groups.
+    let mut groups_by_root: HashMap<usize, Vec<(String, usize)>> = HashMap::new();
+    for (i, (value, count)) in values.iter().enumerate() {
+        let root = find(&mut parent, i);
+        groups_by_root
+            .entry(root)
+            .or_default()
+            .push((value.to_string(), *count));
+    }
+
+    let mut groups: Vec<DuplicateGroup> = groups_by_root
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .map(|mut members| {
+            members.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            DuplicateGroup { members }
+        })
+        .collect();
+
+    groups.sort_by(|a, b| {
+        b.total_count()
+            .cmp(&a.total_count())
+            .then_with(|| a.members[0].0.cmp(&b.members[0].0))
+    });
+
+    Ok(groups)
+}
+
+/// State of the "Duplicates" side panel section: column, metric and threshold,
+/// plus the result of the last (background) search.
+pub struct DuplicateFinder {
+    /// The String column to analyze.
+    pub column_name: String,
+    pub metric: SimilarityMetric,
+    /// Minimum similarity (`0.5..=1.0`) for two values to be grouped.
+    pub threshold: f64,
+    groups: Vec<DuplicateGroup>,
+    error: Option<String>,
+    pending: Option<oneshot::Receiver<PolarsViewResult<Vec<DuplicateGroup>>>>,
+}
+
+impl Default for DuplicateFinder {
+    fn default() -> Self {
+        DuplicateFinder {
+            column_name: String::new(),
+            metric: SimilarityMetric::Levenshtein,
+            threshold: 0.85,
+            groups: Vec::new(),
+            error: None,
+            pending: None,
+        }
+    }
+}
+
+impl DuplicateFinder {
+    /// Whether a search is still running (receives its result if it arrived).
+    ///
+    /// Used for the busy indicator of the tabs, including inactive ones.
+    pub fn is_pending(&mut self) -> bool {
+        self.poll();
+        self.pending.is_some()
+    }
+
+    /// Receives the result of a pending search, if available.
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.pending = None;
+                    match result {
+                        Ok(groups) => self.groups = groups,
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.pending = None;
+                    self.error = Some("duplicate search ended unexpectedly".to_string());
+                }
+            }
+        }
+    }
+
+    /// Starts `find_near_duplicates` on a blocking thread of `runtime`.
+    fn spawn_search(&mut self, df: Arc<DataFrame>, runtime: &Handle, ctx: &egui::Context) {
+        let (tx, rx) = oneshot::channel();
+        let (column_name, metric, threshold) =
+            (self.column_name.clone(), self.metric, self.threshold);
+        let ctx = ctx.clone();
+
+        runtime.spawn_blocking(move || {
+            let result = find_near_duplicates(&df, &column_name, metric, threshold);
+            if tx.send(result).is_err() {
+                tracing::debug!("Duplicate search receiver dropped.");
+            }
+            ctx.request_repaint();
+        });
+
+        self.groups.clear();
+        self.error = None;
+        self.pending = Some(rx);
+    }
+
+    /// Renders the "Duplicates" side panel controls and the groups found.
+    ///
+    /// The search runs in the background on `runtime`; the panel shows a spinner meanwhile.
+    pub fn render(&mut self, ui: &mut Ui, df: &Arc<DataFrame>, runtime: &Handle) {
+        self.poll();
+
+        let string_columns: Vec<String> = df
+            .schema()
+            .iter()
+            .filter(|(_name, dtype)| dtype.is_string() || dtype.is_categorical())
+            .map(|(name, _dtype)| name.to_string())
+            .collect();
+
+        if string_columns.is_empty() {
+            ui.label("No String columns to analyze.");
+            return;
+        }
+
+        if !string_columns.contains(&self.column_name) {
+            self.column_name = string_columns[0].clone();
+        }
+
+        Grid::new("duplicates_grid")
+            .num_columns(2)
+            .spacing([10.0, 20.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Column:");
+                ComboBox::from_id_salt("duplicates_column")
+                    .selected_text(&self.column_name)
+                    .show_ui(ui, |ui| {
+                        for name in &string_columns {
+                            ui.selectable_value(&mut self.column_name, name.clone(), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Metric:");
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.metric,
+                        SimilarityMetric::Levenshtein,
+                        "Levenshtein",
+                    )
+                    .on_hover_text("Typos and small variations (e.g., 'Acme Ltda' / 'Acme Ltd').");
+                    ui.radio_value(&mut self.metric, SimilarityMetric::TokenSet, "Token Set")
+                        .on_hover_text(
+                            "Same words in any order (e.g., 'Acme Ltda' / 'Ltda Acme').",
+                        );
+                });
+                ui.end_row();
+
+                ui.label("Threshold:");
+                ui.add(
+                    DragValue::new(&mut self.threshold)
+                        .speed(0.01)
+                        .range(0.5..=1.0)
+                        .max_decimals(2),
+                )
+                .on_hover_text("Minimum similarity (0.5 to 1.0) for two values to be grouped.");
+                ui.end_row();
+
+                ui.label(""); // For alignment.
+                ui.with_layout(Layout::top_down(Align::Center), |ui| {
+                    let searching = self.pending.is_some();
+                    if ui
+                        .add_enabled(!searching, egui::Button::new("Find Duplicates"))
+                        .clicked()
+                    {
+                        self.spawn_search(df.clone(), runtime, ui.ctx());
+                    }
+                });
+                ui.end_row();
+            });
+
+        if self.pending.is_some() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("Comparing values...");
+            });
+        } else if let Some(error) = &self.error {
+            ui.colored_label(egui::Color32::RED, error);
+        } else if !self.groups.is_empty() {
+            ui.label(format!(
+                "{} groups of near-duplicate values:",
+                self.groups.len()
+            ));
+            ScrollArea::vertical()
+                .id_salt("duplicates_scroll")
+                .max_height(300.0)
+                .show(ui, |ui| {
+                    for group in &self.groups {
+                        Frame::group(ui.style()).show(ui, |ui| {
+                            for (value, count) in &group.members {
+                                ui.add(
+                                    egui::Label::new(format!("{value}  ({count})"))
+                                        .selectable(true),
+                                );
+                            }
+                        });
+                    }
+                });
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_duplicates`
+#[cfg(test)]
+mod tests_duplicates {
+    use super::*;
+
+    #[test]
+    fn test_similarities() {
+        assert_eq!(normalize_value(" ACME  Ltda. "), "acme ltda");
+        assert!((normalized_levenshtein("kitten", "sitting") - (1.0 - 3.0 / 7.0)).abs() < 1e-9);
+        assert_eq!(normalized_levenshtein("", ""), 1.0);
+        assert_eq!(token_set_similarity("acme ltda", "ltda acme"), 1.0);
+        assert_eq!(token_set_similarity("acme ltda", "acme corp"), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_find_near_duplicates() -> PolarsViewResult<()> {
+        let df = df!(
+            "supplier" => &[
+                Some("ACME Ltda"), Some("Acme Ltda."), Some("ACME LTDA"), Some("ACME Ltda"),
+                Some("Globex"), Some("Globex Corp"), Some("Initech"), Some("Initek"), None,
+            ]
+        )?;
+
+        let groups = find_near_duplicates(&df, "supplier", SimilarityMetric::Levenshtein, 0.85)?;
+        dbg!(&groups);
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(
+            groups[0].members,
+            vec![
+                ("ACME Ltda".to_string(), 2),
+                ("ACME LTDA".to_string(), 1),
+                ("Acme Ltda.".to_string(), 1),
+            ]
+        );
+
+        // "initech" vs "initek": 1 - 2/7 ≈ 0.71; grouped with a lower threshold.
+        let groups = find_near_duplicates(&df, "supplier", SimilarityMetric::Levenshtein, 0.7)?;
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[1].members.len(), 2);
+        assert!(groups[1].members.iter().all(|(v, _)| v.starts_with("Init")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_token_set_groups_reordered_words() -> PolarsViewResult<()> {
+        let df = df!("name" => &["Silva Joao", "Joao Silva", "Maria Souza"])?;
+
+        let groups = find_near_duplicates(&df, "name", SimilarityMetric::TokenSet, 0.9)?;
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].members.len(), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Central panel state for an empty result (0 rows or 0 columns, e.g., a SQL query or
+//! column filters matching nothing): what was applied, and how to get the data back.
+//! With columns, the table header (and its filter row) stays visible under this state.
+
+use crate::{DEFAULT_QUERY, DataContainer};
+
+use egui::{Align, Layout, RichText, Ui};
+
+/// Action requested from the empty result state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmptyResultAction {
+    /// Restore the default SQL query (`DEFAULT_QUERY`) and reload.
+    ResetQuery,
+    /// Clear the filters of the filter row.
+    ClearColumnFilters,
+}
+
+/// The message shown for a result of `rows` x `columns`, or `None` if it is not empty.
+pub fn empty_result_message(rows: usize, columns: usize) -> Option<String> {
+    (rows == 0 || columns == 0).then(|| {
+        let plural = |count: usize, noun: &str| match count {
+            1 => format!("1 {noun}"),
+            _ => format!("{count} {noun}s"),
+        };
+        format!(
+            "Query returned {} / {}",
+            plural(rows, "row"),
+            plural(columns, "column")
+        )
+    })
+}
+
+/// Renders the empty result state of `container` (see `empty_result_message`): the
+/// message, the applied operations (see `DataContainer::applied_operations`) and the
+/// reset buttons that apply.
+///
+/// Renders nothing if the result is not empty.
+pub fn render_empty_result(ui: &mut Ui, container: &DataContainer) -> Option<EmptyResultAction> {
+    let message = empty_result_message(container.total_rows(), container.df.width())?;
+    let mut action = None;
+
+    let has_query = container.filter.query.trim() != DEFAULT_QUERY.trim();
+    let has_column_filters = container
+        .column_filters
+        .values()
+        .any(|text| !text.trim().is_empty());
+
+    ui.with_layout(Layout::top_down(Align::Center), |ui| {
+        ui.add_space(12.0);
+        ui.label(RichText::new(message).strong().size(16.0));
+
+        let operations = container.applied_operations();
+        if !operations.is_empty() {
+            ui.add_space(6.0);
+            ui.label(RichText::new("Applied:").weak());
+            for operation in operations {
+                ui.label(RichText::new(operation).weak());
+            }
+        }
+
+        ui.add_space(8.0);
+        if has_query
+            && ui
+                .button("Reset query")
+                .on_hover_text(format!("Restore the default query:\n{DEFAULT_QUERY}"))
+                .clicked()
+        {
+            action = Some(EmptyResultAction::ResetQuery);
+        }
+        if has_column_filters
+            && ui
+                .button("Clear column filters")
+                .on_hover_text("Clear the filters of the filter row under the header.")
+                .clicked()
+        {
+            action = Some(EmptyResultAction::ClearColumnFilters);
+        }
+        ui.add_space(12.0);
+    });
+
+    action
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_empty_result`
+#[cfg(test)]
+mod tests_empty_result {
+    use super::*;
+
+    #[test]
+    fn messages_of_empty_results() {
+        assert_eq!(empty_result_message(10, 3), None);
+        assert_eq!(
+            empty_result_message(0, 3).as_deref(),
+            Some("Query returned 0 rows / 3 columns")
+        );
+        assert_eq!(
+            empty_result_message(1, 0).as_deref(),
+            Some("Query returned 1 row / 0 columns")
+        );
+    }
+}
@@ -0,0 +1,238 @@
+//! Text encodings of CSV files ("Encoding" in the Query panel): legacy exports in Latin-1,
+//! Windows-1252 or UTF-16 are transcoded to a UTF-8 copy before being read, since Polars
+//! only reads UTF-8 (invalid bytes would show as `�` instead of accented characters).
+//!
+//! The copy is decoded by chunks (the whole file is never in memory) and kept in the
+//! private temporary folder, named after the source file (see `temp_files.rs`).
+
+use crate::{DataSource, PolarsViewResult, derived_file};
+
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE, WINDOWS_1252};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{self, BufReader, Read, Write},
+    sync::Arc,
+};
+
+/// Size of the chunks read by `TextEncoding::transcode`.
+const TRANSCODE_CHUNK_BYTES: usize = 64 * 1024;
+
+/// Text encoding of a CSV file.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TextEncoding {
+    /// Read as is (invalid bytes are replaced).
+    #[default]
+    Utf8,
+    /// ISO-8859-1.
+    Latin1,
+    /// Windows-1252 (Latin-1 with `€`, `“`, `”`... in 0x80-0x9F).
+    Windows1252,
+    /// UTF-16, little endian (e.g., "Unicode text" exports of Excel).
+    Utf16Le,
+    /// UTF-16, big endian.
+    Utf16Be,
+}
+
+impl TextEncoding {
+    /// All the encodings, in the order of the dropdown.
+    pub const ALL: [TextEncoding; 5] = [
+        TextEncoding::Utf8,
+        TextEncoding::Latin1,
+        TextEncoding::Windows1252,
+        TextEncoding::Utf16Le,
+        TextEncoding::Utf16Be,
+    ];
+
+    /// Name shown in the dropdown.
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Latin1 => "Latin-1 (ISO-8859-1)",
+            TextEncoding::Windows1252 => "Windows-1252",
+            TextEncoding::Utf16Le => "UTF-16 LE",
+            TextEncoding::Utf16Be => "UTF-16 BE",
+        }
+    }
+
+    /// The `encoding_rs` encoding; `None` for Latin-1, where every byte is the code point of
+    /// the same value (`encoding_rs` reads ISO-8859-1 as Windows-1252).
+    fn encoding(self) -> Option<&'static Encoding> {
+        match self {
+            TextEncoding::Utf8 => Some(UTF_8),
+            TextEncoding::Latin1 => None,
+            TextEncoding::Windows1252 => Some(WINDOWS_1252),
+            TextEncoding::Utf16Le => Some(UTF_16LE),
+            TextEncoding::Utf16Be => Some(UTF_16BE),
+        }
+    }
+
+    /// Decodes `bytes` to UTF-8 text. A byte order mark, if any, is removed.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self.encoding() {
+            Some(encoding) => encoding.decode_with_bom_removal(bytes).0.into_owned(),
+            None => encoding_rs::mem::decode_latin1(bytes).into_owned(),
+        }
+    }
+
+    /// Decodes the text of `reader` to UTF-8 into `writer`, by chunks. A byte order mark,
+    /// if any, is removed; invalid bytes are replaced by `�`.
+    pub fn transcode(self, mut reader: impl Read, writer: &mut dyn Write) -> io::Result<()> {
+        let mut decoder = self.encoding().map(Encoding::new_decoder_with_bom_removal);
+        let mut input = vec![0; TRANSCODE_CHUNK_BYTES];
+        let mut output = Vec::new();
+
+        loop {
+            let read = match reader.read(&mut input) {
+                Ok(read) => read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error),
+            };
+            let last = read == 0;
+
+            match decoder.as_mut() {
+                Some(decoder) => {
+                    // Room for the whole chunk: a single call decodes it.
+                    let capacity = decoder
+                        .max_utf8_buffer_length(read)
+                        .ok_or_else(|| io::Error::other("text chunk too large"))?;
+                    output.resize(capacity, 0);
+                    let (_result, _read, written, _had_errors) =
+                        decoder.decode_to_utf8(&input[..read], &mut output, last);
+                    writer.write_all(&output[..written])?;
+                }
+                None => {
+                    let text = encoding_rs::mem::decode_latin1(&input[..read]);
+                    writer.write_all(text.as_bytes())?;
+                }
+            }
+
+            if last {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Returns the data to read for the CSV `source` in `encoding`: `source` itself for UTF-8;
+/// otherwise, for a file, a UTF-8 copy in the private temporary folder (reused while the
+/// file is unchanged), and for data in memory, its UTF-8 copy in memory.
+pub fn transcode_to_utf8(
+    source: DataSource,
+    encoding: TextEncoding,
+) -> PolarsViewResult<DataSource> {
+    if encoding == TextEncoding::Utf8 {
+        return Ok(source);
+    }
+
+    let path = match source {
+        DataSource::File(path) => path,
+        DataSource::Memory(bytes) => {
+            let mut utf8 = Vec::with_capacity(bytes.len());
+            encoding.transcode(&bytes[..], &mut utf8)?;
+            return Ok(DataSource::Memory(Arc::new(utf8)));
+        }
+    };
+
+    let copy = derived_file(&format!("utf8-{encoding:?}"), &path, |writer| {
+        let reader = BufReader::new(File::open(&path)?);
+        encoding.transcode(reader, writer)?;
+        tracing::info!(
+            "CSV '{}' transcoded from {} to UTF-8",
+            path.display(),
+            encoding.label()
+        );
+        Ok(())
+    })?;
+    Ok(DataSource::File(copy))
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_encoding`
+#[cfg(test)]
+mod tests_encoding {
+    use super::*;
+    use crate::PolarsViewError;
+    use std::path::Path;
+
+    #[test]
+    fn legacy_encodings_are_decoded() {
+        // "Ação" in each encoding; Latin-1 has no euro sign (0x80 is a control character).
+        assert_eq!(TextEncoding::Latin1.decode(b"A\xe7\xe3o"), "Ação");
+        assert_eq!(
+            TextEncoding::Windows1252.decode(b"A\xe7\xe3o \x805"),
+            "Ação €5"
+        );
+        let utf16le: Vec<u8> = "\u{feff}Ação"
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        assert_eq!(TextEncoding::Utf16Le.decode(&utf16le), "Ação");
+        let utf16be: Vec<u8> = "Ação"
+            .encode_utf16()
+            .flat_map(|unit| unit.to_be_bytes())
+            .collect();
+        assert_eq!(TextEncoding::Utf16Be.decode(&utf16be), "Ação");
+        assert_eq!(TextEncoding::Utf8.decode("Ação".as_bytes()), "Ação");
+
+        // By chunks: a UTF-16 character split between two chunks is kept whole.
+        let text = "Ação;".repeat(TRANSCODE_CHUNK_BYTES / 5 + 1);
+        let utf16le: Vec<u8> = text
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        // A first read of 1 byte: the next chunks end in the middle of a character.
+        let reader = utf16le[..1].chain(&utf16le[1..]);
+        let mut transcoded = Vec::new();
+        TextEncoding::Utf16Le
+            .transcode(reader, &mut transcoded)
+            .unwrap();
+        assert_eq!(String::from_utf8(transcoded).unwrap(), text);
+    }
+
+    #[test]
+    fn transcoded_copy() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("legacy-export.csv");
+        std::fs::write(&path, b"nome;valor\nJo\xe3o;1\n")?;
+
+        let copy_of = |path: &Path, encoding| match transcode_to_utf8(
+            DataSource::File(path.to_path_buf()),
+            encoding,
+        )? {
+            DataSource::File(copy) => Ok::<_, PolarsViewError>(copy),
+            DataSource::Memory(_) => panic!("a file is transcoded to a file"),
+        };
+
+        assert_eq!(copy_of(&path, TextEncoding::Utf8)?, path);
+        let copy = copy_of(&path, TextEncoding::Windows1252)?;
+        assert_eq!(std::fs::read_to_string(&copy)?, "nome;valor\nJoão;1\n");
+
+        // Same name, other folder: its own copy.
+        let other = dir.path().join("other");
+        std::fs::create_dir_all(&other)?;
+        std::fs::write(other.join("legacy-export.csv"), b"nome;valor\nJos\xe9;2\n")?;
+        let other_copy = copy_of(&other.join("legacy-export.csv"), TextEncoding::Windows1252)?;
+        assert_ne!(other_copy, copy);
+        assert_eq!(
+            std::fs::read_to_string(&other_copy)?,
+            "nome;valor\nJosé;2\n"
+        );
+
+        // Data in memory (a decompressed file) is transcoded in memory.
+        let memory = DataSource::Memory(Arc::new(b"Jo\xe3o".to_vec()));
+        match transcode_to_utf8(memory, TextEncoding::Latin1)? {
+            DataSource::Memory(bytes) => assert_eq!(&bytes[..], "João".as_bytes()),
+            DataSource::File(path) => panic!("unexpected copy: {}", path.display()),
+        }
+
+        for copy in [copy, other_copy] {
+            std::fs::remove_file(copy)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,256 @@
+//! Encrypted Parquet files (Parquet modular encryption, encrypted footer).
+//!
+//! The Polars reader can't decrypt them, so they are detected by their magic bytes (`PARE`
+//! instead of `PAR1`) and read with the `parquet` crate, which hands the decrypted record
+//! batches to Polars as an Arrow IPC stream.
+//!
+//! The key is taken from the `POLARS_VIEW_PARQUET_KEY` environment variable or, if unset,
+//! asked by the "Encrypted Parquet" dialog. It is kept for the session only.
+//! Files whose columns use keys other than the footer key are not supported.
+//!
+//! Reading needs the `parquet-encryption` cargo feature; without it, encrypted files are
+//! still detected and reported.
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use egui::{Context, TextEdit, Window};
+use polars::prelude::*;
+use std::{
+    fmt,
+    fs::File,
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable holding the key of encrypted Parquet files.
+pub const PARQUET_KEY_ENV: &str = "POLARS_VIEW_PARQUET_KEY";
+
+/// Magic bytes of a Parquet file with an encrypted footer.
+const ENCRYPTED_MAGIC: &[u8; 4] = b"PARE";
+
+/// Key of an encrypted Parquet file: 16, 24 or 32 bytes (AES-128/192/256), written as hex
+/// or as plain text. Its `Debug` output hides the key, so it never reaches the logs.
+#[derive(Clone, PartialEq)]
+pub struct ParquetKey(String);
+
+impl ParquetKey {
+    /// The key written as `text` (surrounding spaces are ignored).
+    pub fn new(text: impl Into<String>) -> Self {
+        ParquetKey(text.into().trim().to_string())
+    }
+
+    /// The key from `PARQUET_KEY_ENV`, if set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(PARQUET_KEY_ENV)
+            .ok()
+            .filter(|text| !text.trim().is_empty())
+            .map(ParquetKey::new)
+    }
+
+    /// The key bytes: hex text (32, 48 or 64 digits) is decoded, other text is used as is.
+    pub fn bytes(&self) -> PolarsViewResult<Vec<u8>> {
+        let text = self.0.as_str();
+        let is_hex = text.bytes().all(|byte| byte.is_ascii_hexdigit());
+        let bytes = if is_hex && matches!(text.len(), 32 | 48 | 64) {
+            (0..text.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&text[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|error| PolarsViewError::ParquetEncryption(error.to_string()))?
+        } else {
+            text.as_bytes().to_vec()
+        };
+
+        if !matches!(bytes.len(), 16 | 24 | 32) {
+            return Err(PolarsViewError::ParquetEncryption(format!(
+                "the key must have 16, 24 or 32 bytes (or 32, 48 or 64 hex digits), not {}",
+                bytes.len()
+            )));
+        }
+        Ok(bytes)
+    }
+}
+
+impl fmt::Debug for ParquetKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ParquetKey(***)")
+    }
+}
+
+/// Returns `true` if `path` is a Parquet file with an encrypted footer.
+pub fn is_encrypted_parquet(path: impl AsRef<Path>) -> PolarsViewResult<bool> {
+    let mut magic = [0u8; 4];
+    let mut file = File::open(path)?;
+    Ok(file.read_exact(&mut magic).is_ok() && &magic == ENCRYPTED_MAGIC)
+}
+
+/// Reads the encrypted Parquet file `path` with `key` (the first `head_rows` rows, if set).
+///
+/// ### Returns
+/// The `DataFrame`, or `PolarsViewError::ParquetEncryption` if the key is invalid or wrong.
+#[cfg(feature = "parquet-encryption")]
+pub fn read_encrypted_parquet(
+    path: impl AsRef<Path>,
+    key: &ParquetKey,
+    head_rows: Option<usize>,
+) -> PolarsViewResult<DataFrame> {
+    use arrow_ipc::writer::StreamWriter;
+    use parquet::{
+        arrow::arrow_reader::{ArrowReaderOptions, ParquetRecordBatchReaderBuilder},
+        encryption::decrypt::FileDecryptionProperties,
+    };
+    use std::io::Cursor;
+
+    let decryption_error = |error: &dyn fmt::Display| {
+        PolarsViewError::ParquetEncryption(format!("{}: {error}", path.as_ref().display()))
+    };
+
+    let properties = FileDecryptionProperties::builder(key.bytes()?)
+        .build()
+        .map_err(|error| decryption_error(&error))?;
+    let options = ArrowReaderOptions::default().with_file_decryption_properties(properties);
+    let mut builder =
+        ParquetRecordBatchReaderBuilder::try_new_with_options(File::open(&path)?, options)
+            .map_err(|error| decryption_error(&error))?;
+    if let Some(rows) = head_rows {
+        builder = builder.with_limit(rows);
+    }
+    let reader = builder.build().map_err(|error| decryption_error(&error))?;
+
+    // Arrow IPC stream: the bridge between the `parquet` crate and Polars.
+    let mut stream = Vec::new();
+    let mut writer = StreamWriter::try_new(&mut stream, &reader.schema())
+        .map_err(|error| decryption_error(&error))?;
+    for batch in reader {
+        let batch = batch.map_err(|error| decryption_error(&error))?;
+        writer
+            .write(&batch)
+            .map_err(|error| decryption_error(&error))?;
+    }
+    writer.finish().map_err(|error| decryption_error(&error))?;
+    drop(writer);
+
+    let df = IpcStreamReader::new(Cursor::new(stream)).finish()?;
+    tracing::debug!("Encrypted Parquet read complete. Shape: {:?}", df.shape());
+    Ok(df)
+}
+
+/// Built without the `parquet-encryption` feature: encrypted files can't be read.
+#[cfg(not(feature = "parquet-encryption"))]
+pub fn read_encrypted_parquet(
+    _path: impl AsRef<Path>,
+    _key: &ParquetKey,
+    _head_rows: Option<usize>,
+) -> PolarsViewResult<DataFrame> {
+    Err(PolarsViewError::FeatureDisabled("parquet-encryption"))
+}
+
+/// State of the "Encrypted Parquet" dialog, opened when an encrypted file is read
+/// without a key (or with a wrong one).
+#[derive(Debug, Default)]
+pub struct ParquetKeyDialog {
+    /// Whether the dialog is shown.
+    pub open: bool,
+    /// The encrypted file.
+    pub path: PathBuf,
+    /// The key typed by the user.
+    pub key: String,
+    /// Why the key is asked again (e.g., the previous one was wrong).
+    pub error: Option<String>,
+}
+
+impl ParquetKeyDialog {
+    /// Opens the dialog for the encrypted file `path`.
+    pub fn ask(&mut self, path: PathBuf, error: Option<String>) {
+        self.open = true;
+        self.path = path;
+        self.key.clear();
+        self.error = error;
+    }
+
+    /// Shows the dialog (if open). Returns the key when "Open" is clicked.
+    pub fn show(&mut self, ctx: &Context) -> Option<ParquetKey> {
+        if !self.open {
+            return None;
+        }
+
+        let mut key = None;
+        let mut open = self.open;
+
+        Window::new("Encrypted Parquet")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(format!("{} is encrypted.", self.path.display()));
+                ui.horizontal(|ui| {
+                    ui.label("Key:").on_hover_text(format!(
+                        "AES key: 16, 24 or 32 bytes, as text or hex digits.\n\
+                        Also read from the {PARQUET_KEY_ENV} environment variable."
+                    ));
+                    ui.add(
+                        TextEdit::singleline(&mut self.key)
+                            .password(true)
+                            .desired_width(320.0),
+                    );
+                });
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                let typed = ParquetKey::new(self.key.as_str());
+                if ui
+                    .add_enabled(!self.key.trim().is_empty(), egui::Button::new("Open"))
+                    .clicked()
+                {
+                    match typed.bytes() {
+                        Ok(_) => key = Some(typed),
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+            });
+
+        self.open = open && key.is_none();
+        if !self.open {
+            self.key.clear();
+        }
+        key
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_encryption`
+#[cfg(test)]
+mod tests_encryption {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn keys_are_parsed_and_hidden() -> PolarsViewResult<()> {
+        let hex = ParquetKey::new("000102030405060708090a0b0c0d0e0f");
+        assert_eq!(hex.bytes()?, (0u8..16).collect::<Vec<u8>>());
+        assert_eq!(ParquetKey::new("0123456789012345").bytes()?.len(), 16);
+        assert!(ParquetKey::new("short").bytes().is_err());
+        assert_eq!(format!("{hex:?}"), "ParquetKey(***)");
+        Ok(())
+    }
+
+    #[test]
+    fn encrypted_footer_is_detected() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let plain = dir.path().join("plain.parquet");
+        let mut df = df!("a" => &[1, 2])?;
+        ParquetWriter::new(File::create(&plain)?).finish(&mut df)?;
+        assert!(!is_encrypted_parquet(&plain)?);
+
+        let encrypted = dir.path().join("encrypted.parquet");
+        std::fs::write(&encrypted, b"PARE...")?;
+        assert!(is_encrypted_parquet(&encrypted)?);
+        Ok(())
+    }
+}
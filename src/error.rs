@@ -43,6 +43,33 @@ pub enum PolarsViewError {
     #[error("IO error: {0}")]
     Io(#[from] io::Error),
 
+    // Wrapper for JSON serialization errors (e.g., writing the export manifest).
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+
+    // Errors reading Excel/OpenDocument workbooks.
+    #[error("Excel error: {0}")]
+    Excel(#[from] calamine::Error),
+
+    // Errors reading SQLite databases.
+    #[cfg(feature = "sqlite")]
+    #[error("SQLite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    // Errors connecting to or querying a PostgreSQL server.
+    #[cfg(feature = "postgres")]
+    #[error("PostgreSQL error: {0}")]
+    Postgres(#[from] postgres::Error),
+
+    // Errors connecting to or querying a MySQL/MariaDB server.
+    #[cfg(feature = "mysql")]
+    #[error("MySQL error: {0}")]
+    MySql(#[from] mysql::Error),
+
+    // A data source whose cargo feature was not enabled at build time (see `Cargo.toml`).
+    #[error("This build does not read this source: rebuild with `--features {0}`")]
+    FeatureDisabled(&'static str),
+
     // Erros de inicialização
     #[error("Initialization error: {0}")]
     Initialization(String),
@@ -92,16 +119,72 @@ pub enum PolarsViewError {
     #[error("Tokio JoinError: {0}")]
     TokioJoin(#[from] JoinError),
 
+    // Errors parsing TOML configuration files (e.g., `.polars-view.toml`).
+    #[error("TOML error: {0}")]
+    Toml(#[from] toml::de::Error),
+
+    // Errors downloading remote files (http, https, S3).
+    #[cfg(feature = "remote")]
+    #[error("Remote file error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+
+    // A data task superseded by a newer one, or cancelled by the user (see `CancelToken`).
+    #[error("Task cancelled")]
+    Cancelled,
+
+    // A background task panicked (caught by `run_data_future`).
+    #[error("Task \"{task}\" panicked: {message}")]
+    TaskPanicked { task: String, message: String },
+
+    // Errors writing Excel workbooks ("Save As..." `.xlsx`).
+    #[error("Excel writer error: {0}")]
+    Xlsx(#[from] rust_xlsxwriter::XlsxError),
+
     // Indicates that a provided file extension or file type are not supported.
     #[error("Unsupported file type: {0}")]
     UnsupportedFileType(String),
 
+    // An encrypted Parquet file read without a key, or with a wrong one (the key is asked).
+    #[error("Encrypted Parquet file {path:#?}: {reason}")]
+    ParquetKeyRequired { path: PathBuf, reason: String },
+
+    // Errors decrypting Parquet files (e.g., a key of the wrong size, see `encryption.rs`).
+    #[error("Parquet decryption error: {0}")]
+    ParquetEncryption(String),
+
+    // Errors starting the watch of a file ("Auto-reload on change").
+    #[error("File watcher error: {0}")]
+    Notify(#[from] notify::Error),
+
+    // Errors reading or writing zip archives (e.g., session bundles).
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    // Apache Iceberg tables that can't be read (bad manifests, row-level deletes...).
+    #[error("Iceberg table error: {0}")]
+    Iceberg(String),
+
+    // Typed cell values that don't convert to the column type ("Edit Cells").
+    #[error("Cell edit error: {0}")]
+    CellEdit(String),
+
     // A catch-all for other, less specific errors not covered by specific variants.
     // Uses a String to describe the error. Consider using this sparingly.
     #[error("Other error: {0}")]
     Other(String),
 }
 
+/// The message of a panic payload (`panic!` with a literal or a formatted message).
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
 // Implementation of the From trait to convert a String into a PolarsViewError.
 // This allows us to easily convert generic error strings into our custom error type.
 impl From<String> for PolarsViewError {
@@ -110,3 +193,24 @@ impl From<String> for PolarsViewError {
         PolarsViewError::Other(err)
     }
 }
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_error`
+#[cfg(test)]
+mod tests_error {
+    use super::*;
+
+    #[test]
+    fn panic_messages() {
+        let payload = std::panic::catch_unwind(|| panic!("literal")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "literal");
+
+        let column = "price";
+        let payload = std::panic::catch_unwind(|| panic!("bad column {column}")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "bad column price");
+    }
+}
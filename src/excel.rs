@@ -0,0 +1,466 @@
+//! Excel/OpenDocument workbook reading (`.xlsx`, `.xlsm`, `.xlsb`, `.xls`, `.ods`) via `calamine`,
+//! and `.xlsx` writing ("Save As...") via `rust_xlsxwriter`.
+//!
+//! One sheet is read at a time: the first row is the header and each column type is
+//! inferred from its cells (Boolean, Int64, Float64, Date, Datetime or String).
+
+use crate::{PolarsViewError, PolarsViewResult, write_atomically};
+
+use calamine::{Data, Range, Reader, open_workbook_auto};
+use polars::prelude::*;
+use rust_xlsxwriter::{Format, Workbook, Worksheet};
+use std::{collections::HashSet, io::Write, path::Path};
+
+/// Days between the Excel epoch (1899-12-30) and the Unix epoch (1970-01-01).
+const EXCEL_UNIX_EPOCH_DAYS: f64 = 25_569.0;
+
+/// Milliseconds per day.
+const MS_PER_DAY: f64 = 86_400_000.0;
+
+/// Maximum number of rows of an Excel worksheet (header included).
+pub const EXCEL_MAX_ROWS: usize = 1_048_576;
+
+/// Maximum number of characters of an Excel cell.
+const EXCEL_MAX_CHARS: usize = 32_767;
+
+/// Largest integer stored exactly by an Excel number (`f64`); larger ones are written as text.
+const EXCEL_MAX_EXACT_INT: f64 = 9_007_199_254_740_992.0; // 2^53
+
+/// A sheet read from a workbook.
+#[derive(Debug, Clone)]
+pub struct ExcelSheet {
+    /// The sheet contents.
+    pub df: DataFrame,
+    /// All sheet names of the workbook, in workbook order.
+    pub sheet_names: Vec<String>,
+    /// The name of the sheet that was read.
+    pub sheet_name: String,
+}
+
+/// Reads the sheet `sheet_name` of the workbook at `path`.
+///
+/// If `sheet_name` is `None` (or not found in the workbook), the first sheet is read.
+/// This is a blocking operation; call it from `spawn_blocking`.
+pub fn read_excel_sheet(path: &Path, sheet_name: Option<&str>) -> PolarsViewResult<ExcelSheet> {
+    let mut workbook = open_workbook_auto(path)?;
+    let sheet_names = workbook.sheet_names();
+
+    let sheet_name = match sheet_name {
+        Some(name) if sheet_names.iter().any(|sheet| sheet == name) => name.to_string(),
+        _ => sheet_names.first().cloned().ok_or_else(|| {
+            PolarsViewError::FileType(format!("Workbook without sheets: `{}`", path.display()))
+        })?,
+    };
+
+    let range = workbook.worksheet_range(&sheet_name)?;
+    let df = range_to_dataframe(&range)?;
+
+    tracing::debug!(
+        "Sheet `{}` read from {}. Shape: {:?}",
+        sheet_name,
+        path.display(),
+        df.shape()
+    );
+
+    Ok(ExcelSheet {
+        df,
+        sheet_names,
+        sheet_name,
+    })
+}
+
+/// Converts a sheet range into a `DataFrame`, using the first row as header.
+///
+/// Empty header cells become `column_{n}` (1-based) and repeated names get a `_{n}` suffix.
+pub fn range_to_dataframe(range: &Range<Data>) -> PolarsResult<DataFrame> {
+    let mut rows = range.rows();
+
+    let Some(header) = rows.next() else {
+        return Ok(DataFrame::empty());
+    };
+
+    let names = header_names(header);
+    let body: Vec<&[Data]> = rows.collect();
+
+    let columns = names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let cells: Vec<&Data> = body.iter().map(|row| &row[index]).collect();
+            cells_to_column(name, &cells)
+        })
+        .collect::<PolarsResult<Vec<Column>>>()?;
+
+    DataFrame::new(body.len(), columns)
+}
+
+/// Builds unique, non-empty column names from the header row.
+fn header_names(header: &[Data]) -> Vec<String> {
+    let mut seen = HashSet::new();
+
+    header
+        .iter()
+        .enumerate()
+        .map(|(index, cell)| {
+            let name = match cell.to_string().trim() {
+                "" => format!("column_{}", index + 1),
+                name => name.to_string(),
+            };
+
+            let mut unique = name.clone();
+            let mut suffix = 2;
+            while !seen.insert(unique.clone()) {
+                unique = format!("{name}_{suffix}");
+                suffix += 1;
+            }
+            unique
+        })
+        .collect()
+}
+
+/// Returns `true` for cells read as null (empty or `#N/A`-like errors).
+fn is_null(cell: &Data) -> bool {
+    matches!(cell, Data::Empty | Data::Error(_))
+}
+
+/// Returns the Excel serial date of a date/time cell.
+fn excel_serial(cell: &Data) -> Option<f64> {
+    match cell {
+        Data::DateTime(datetime) => Some(datetime.as_f64()),
+        _ => None,
+    }
+}
+
+/// Returns the numeric value of an integer or float cell.
+fn numeric_value(cell: &Data) -> Option<f64> {
+    match cell {
+        Data::Int(value) => Some(*value as f64),
+        Data::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Builds a column from its cells, inferring the narrowest type that fits all non-null cells.
+fn cells_to_column(name: &str, cells: &[&Data]) -> PolarsResult<Column> {
+    let values: Vec<&Data> = cells.iter().copied().filter(|c| !is_null(c)).collect();
+    let name = PlSmallStr::from(name);
+
+    let all =
+        |predicate: fn(&Data) -> bool| !values.is_empty() && values.iter().all(|c| predicate(c));
+
+    let column = if all(|c| matches!(c, Data::Bool(_))) {
+        cells
+            .iter()
+            .map(|c| match c {
+                Data::Bool(value) => Some(*value),
+                _ => None,
+            })
+            .collect::<BooleanChunked>()
+            .with_name(name)
+            .into_column()
+    } else if all(|c| numeric_value(c).is_some_and(|v| v.fract() == 0.0 && v.abs() < 9.0e15)) {
+        cells
+            .iter()
+            .map(|c| numeric_value(c).map(|v| v as i64))
+            .collect::<Int64Chunked>()
+            .with_name(name)
+            .into_column()
+    } else if all(|c| numeric_value(c).is_some()) {
+        cells
+            .iter()
+            .map(|c| numeric_value(c))
+            .collect::<Float64Chunked>()
+            .with_name(name)
+            .into_column()
+    } else if all(|c| excel_serial(c).is_some_and(|v| v.fract() == 0.0)) {
+        cells
+            .iter()
+            .map(|c| excel_serial(c).map(|v| (v - EXCEL_UNIX_EPOCH_DAYS) as i32))
+            .collect::<Int32Chunked>()
+            .with_name(name)
+            .into_date()
+            .into_column()
+    } else if all(|c| excel_serial(c).is_some()) {
+        cells
+            .iter()
+            .map(|c| {
+                excel_serial(c).map(|v| ((v - EXCEL_UNIX_EPOCH_DAYS) * MS_PER_DAY).round() as i64)
+            })
+            .collect::<Int64Chunked>()
+            .with_name(name)
+            .into_datetime(TimeUnit::Milliseconds, None)
+            .into_column()
+    } else {
+        cells
+            .iter()
+            .map(|c| (!is_null(c)).then(|| c.to_string()))
+            .collect::<StringChunked>()
+            .with_name(name)
+            .into_column()
+    };
+
+    Ok(column)
+}
+
+/// Writes `df` to a new `.xlsx` workbook at `path` (one sheet, named "Data"), in the
+/// order of its rows (i.e., with the applied sort).
+///
+/// The header is bold and frozen with an autofilter. Numbers keep their type (floats
+/// with `decimal` places), dates and datetimes are Excel dates, other types are text.
+/// Columns named in `hidden_columns` are written but hidden in Excel.
+/// This is a blocking operation; call it from `spawn_blocking`.
+pub fn write_xlsx(
+    df: &DataFrame,
+    path: &Path,
+    hidden_columns: &HashSet<String>,
+    decimal: usize,
+) -> PolarsViewResult<()> {
+    if df.height() >= EXCEL_MAX_ROWS {
+        return Err(PolarsViewError::UnsupportedFileType(format!(
+            "Excel worksheets hold at most {} rows; the table has {} rows",
+            EXCEL_MAX_ROWS - 1,
+            df.height()
+        )));
+    }
+
+    let mut workbook = Workbook::new();
+    let worksheet = workbook.add_worksheet();
+    worksheet.set_name("Data")?;
+
+    let header = Format::new().set_bold();
+    let float = Format::new().set_num_format(match decimal {
+        0 => "0".to_string(),
+        places => format!("0.{}", "0".repeat(places)),
+    });
+    let date = Format::new().set_num_format("yyyy-mm-dd");
+    let datetime = Format::new().set_num_format("yyyy-mm-dd hh:mm:ss");
+
+    for (index, column) in df.get_columns().iter().enumerate() {
+        let col = index as u16;
+        worksheet.write_string_with_format(0, col, column.name().as_str(), &header)?;
+
+        let dtype = column.dtype();
+        if dtype.is_bool() {
+            for (row, value) in column.bool()?.into_iter().enumerate() {
+                if let Some(value) = value {
+                    worksheet.write_boolean(row as u32 + 1, col, value)?;
+                }
+            }
+        } else if dtype.is_primitive_numeric() || dtype.is_decimal() {
+            let format = dtype.is_float() || dtype.is_decimal();
+            let values = column.cast(&DataType::Float64)?;
+            for (row, value) in values.f64()?.into_iter().enumerate() {
+                let row = row as u32 + 1;
+                match value {
+                    Some(value) if value.abs() > EXCEL_MAX_EXACT_INT && !format => {
+                        // Keep all digits of large integers (e.g., keys).
+                        let text = column.get(row as usize - 1)?.to_string();
+                        worksheet.write_string(row, col, text)?;
+                    }
+                    Some(value) if format => {
+                        worksheet.write_number_with_format(row, col, value, &float)?;
+                    }
+                    Some(value) => {
+                        worksheet.write_number(row, col, value)?;
+                    }
+                    None => {}
+                }
+            }
+        } else if matches!(dtype, DataType::Date) {
+            let days = column.cast(&DataType::Int32)?;
+            for (row, value) in days.i32()?.into_iter().enumerate() {
+                if let Some(days) = value {
+                    let serial = days as f64 + EXCEL_UNIX_EPOCH_DAYS;
+                    worksheet.write_number_with_format(row as u32 + 1, col, serial, &date)?;
+                }
+            }
+        } else if matches!(dtype, DataType::Datetime(..)) {
+            let millis = column
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?
+                .cast(&DataType::Int64)?;
+            for (row, value) in millis.i64()?.into_iter().enumerate() {
+                if let Some(millis) = value {
+                    let serial = millis as f64 / MS_PER_DAY + EXCEL_UNIX_EPOCH_DAYS;
+                    worksheet.write_number_with_format(row as u32 + 1, col, serial, &datetime)?;
+                }
+            }
+        } else {
+            write_text_column(worksheet, col, column)?;
+        }
+
+        if hidden_columns.contains(column.name().as_str()) {
+            worksheet.set_column_hidden(col)?;
+        }
+    }
+
+    if df.width() > 0 {
+        worksheet.set_freeze_panes(1, 0)?;
+        worksheet.autofilter(0, 0, df.height() as u32, (df.width() - 1) as u16)?;
+        worksheet.autofit();
+    }
+
+    // Built in memory, then written to a temporary file renamed to `path` once complete.
+    let buffer = workbook.save_to_buffer()?;
+    write_atomically(path, |writer| Ok(writer.write_all(&buffer)?))?;
+    tracing::debug!(
+        "Excel written to {}. Shape: {:?}",
+        path.display(),
+        df.shape()
+    );
+    Ok(())
+}
+
+/// Writes `column` as text (nulls as empty cells), truncated to the Excel cell limit.
+fn write_text_column(worksheet: &mut Worksheet, col: u16, column: &Column) -> PolarsViewResult<()> {
+    let write = |worksheet: &mut Worksheet, row: usize, text: &str| {
+        let text: String = text.chars().take(EXCEL_MAX_CHARS).collect();
+        worksheet
+            .write_string(row as u32 + 1, col, text)
+            .map(|_| ())
+    };
+
+    match column.cast(&DataType::String) {
+        Ok(strings) => {
+            for (row, value) in strings.str()?.into_iter().enumerate() {
+                if let Some(text) = value {
+                    write(worksheet, row, text)?;
+                }
+            }
+        }
+        // Nested types (lists, structs): their displayed value.
+        Err(_) => {
+            for row in 0..column.len() {
+                let value = column.get(row)?;
+                if !value.is_null() {
+                    write(worksheet, row, &value.to_string())?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_excel`
+#[cfg(test)]
+mod tests_excel {
+    use super::*;
+
+    /// Builds a range from rows of cells, starting at A1.
+    fn range_from_rows(rows: Vec<Vec<Data>>) -> Range<Data> {
+        let height = rows.len() as u32;
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0) as u32;
+        let mut range = Range::new((0, 0), (height - 1, width - 1));
+
+        for (row, cells) in rows.into_iter().enumerate() {
+            for (col, cell) in cells.into_iter().enumerate() {
+                range.set_value((row as u32, col as u32), cell);
+            }
+        }
+        range
+    }
+
+    #[test]
+    fn header_names_are_unique_and_non_empty() {
+        let header = [
+            Data::String("Name".to_string()),
+            Data::Empty,
+            Data::String("Name".to_string()),
+            Data::String(" Name ".to_string()),
+        ];
+
+        assert_eq!(
+            header_names(&header),
+            vec!["Name", "column_2", "Name_2", "Name_3"]
+        );
+    }
+
+    #[test]
+    fn infers_column_types() -> PolarsResult<()> {
+        let range = range_from_rows(vec![
+            vec![
+                Data::String("id".to_string()),
+                Data::String("value".to_string()),
+                Data::String("flag".to_string()),
+                Data::String("text".to_string()),
+            ],
+            vec![
+                Data::Float(1.0),
+                Data::Float(1.5),
+                Data::Bool(true),
+                Data::String("a".to_string()),
+            ],
+            vec![
+                Data::Int(2),
+                Data::Empty,
+                Data::Bool(false),
+                Data::Float(3.0),
+            ],
+        ]);
+
+        let df = range_to_dataframe(&range)?;
+        dbg!(&df);
+
+        assert_eq!(df.shape(), (2, 4));
+        assert_eq!(df.column("id")?.dtype(), &DataType::Int64);
+        assert_eq!(df.column("value")?.dtype(), &DataType::Float64);
+        assert_eq!(df.column("flag")?.dtype(), &DataType::Boolean);
+        assert_eq!(df.column("text")?.dtype(), &DataType::String);
+
+        let values: Vec<Option<f64>> = df.column("value")?.f64()?.into_iter().collect();
+        assert_eq!(values, vec![Some(1.5), None]);
+
+        let texts: Vec<Option<&str>> = df.column("text")?.str()?.into_iter().collect();
+        assert_eq!(texts, vec![Some("a"), Some("3")]);
+        Ok(())
+    }
+
+    #[test]
+    fn empty_column_is_string() -> PolarsResult<()> {
+        let range = range_from_rows(vec![
+            vec![Data::String("a".to_string()), Data::String("b".to_string())],
+            vec![Data::Int(1), Data::Empty],
+        ]);
+
+        let df = range_to_dataframe(&range)?;
+
+        assert_eq!(df.column("b")?.dtype(), &DataType::String);
+        assert_eq!(df.column("b")?.null_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn write_xlsx_round_trip() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("export.xlsx");
+
+        let df = df!(
+            "id" => &[3i64, 1, 2],
+            "value" => &[Some(0.5), None, Some(2.25)],
+            "flag" => &[true, false, true],
+            "name" => &["c", "a", "b"],
+        )?;
+        let hidden = HashSet::from(["flag".to_string()]);
+        write_xlsx(&df, &path, &hidden, 2)?;
+
+        let sheet = read_excel_sheet(&path, None)?;
+        dbg!(&sheet.df);
+
+        assert_eq!(sheet.sheet_names, vec!["Data".to_string()]);
+        assert_eq!(sheet.df.shape(), (3, 4));
+        assert_eq!(sheet.df.column("id")?.dtype(), &DataType::Int64);
+        assert_eq!(sheet.df.column("flag")?.dtype(), &DataType::Boolean);
+
+        // Row order (e.g., the applied sort) is kept.
+        let ids: Vec<Option<i64>> = sheet.df.column("id")?.i64()?.into_iter().collect();
+        assert_eq!(ids, vec![Some(3), Some(1), Some(2)]);
+
+        let values: Vec<Option<f64>> = sheet.df.column("value")?.f64()?.into_iter().collect();
+        assert_eq!(values, vec![Some(0.5), None, Some(2.25)]);
+        Ok(())
+    }
+}
@@ -1,9 +1,16 @@
-use crate::{DataContainer, FileExtension, PolarsViewError, PolarsViewResult};
+use crate::{
+    Compression, DataContainer, ExportManifest, FileExtension, PolarsViewError, PolarsViewResult,
+    write_atomically, write_xlsx,
+};
 
 use egui::Context;
 use polars::prelude::*;
 use rfd::AsyncFileDialog;
-use std::{fs::File, io::BufWriter, path::PathBuf, sync::Arc};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use tokio::sync::oneshot;
 use tracing::error;
 
@@ -32,6 +39,67 @@ pub async fn open_file() -> PolarsViewResult<PathBuf> {
         .ok_or_else(|| PolarsViewError::FileNotFound(PathBuf::new())) // Convert None to error.
 }
 
+/// Opens a folder dialog to choose a folder to browse (see `FolderBrowser`).
+///
+/// Returns `PolarsViewError::FileNotFound` if the user cancels the dialog.
+pub async fn open_folder() -> PolarsViewResult<PathBuf> {
+    AsyncFileDialog::new()
+        .pick_folder()
+        .await
+        .map(|folder| folder.path().to_path_buf())
+        .ok_or_else(|| PolarsViewError::FileNotFound(PathBuf::new()))
+}
+
+/// Opens a file dialog to choose a `ViewState` JSON file to import.
+///
+/// Returns `PolarsViewError::FileNotFound` if the user cancels the dialog.
+pub async fn open_view_state_file() -> PolarsViewResult<PathBuf> {
+    AsyncFileDialog::new()
+        .add_filter("View (JSON)", &["json"])
+        .pick_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+        .ok_or_else(|| PolarsViewError::FileNotFound(PathBuf::new()))
+}
+
+/// Opens a file dialog to choose a currency rate table (CSV with `date`, `currency`, `rate`).
+///
+/// Returns `PolarsViewError::FileNotFound` if the user cancels the dialog.
+pub async fn open_rates_file() -> PolarsViewResult<PathBuf> {
+    AsyncFileDialog::new()
+        .add_filter("Rates (CSV)", &["csv", "txt"])
+        .pick_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+        .ok_or_else(|| PolarsViewError::FileNotFound(PathBuf::new()))
+}
+
+/// Opens a save dialog to choose where to export a `ViewState` JSON file.
+///
+/// Returns `PolarsViewError::FileNotFound` if the user cancels the dialog.
+pub async fn save_view_state_file(default_file_name: &str) -> PolarsViewResult<PathBuf> {
+    AsyncFileDialog::new()
+        .add_filter("View (JSON)", &["json"])
+        .set_file_name(default_file_name)
+        .save_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+        .ok_or_else(|| PolarsViewError::FileNotFound(PathBuf::new()))
+}
+
+/// Opens a save dialog to choose where to export a session bundle (zip).
+///
+/// Returns `PolarsViewError::FileNotFound` if the user cancels the dialog.
+pub async fn save_session_file(default_file_name: &str) -> PolarsViewResult<PathBuf> {
+    AsyncFileDialog::new()
+        .add_filter("Session (zip)", &["zip"])
+        .set_file_name(default_file_name)
+        .save_file()
+        .await
+        .map(|file| file.path().to_path_buf())
+        .ok_or_else(|| PolarsViewError::FileNotFound(PathBuf::new()))
+}
+
 /// Saves the DataFrame contained in `DataContainer` to a file.
 ///
 /// The file format is determined by the provided `FileExtension`. Supported formats are CSV, Json,
@@ -43,10 +111,12 @@ pub async fn open_file() -> PolarsViewResult<PathBuf> {
 /// * `container`: A `DataContainer` holding the DataFrame to be saved, along with
 ///   metadata including the user's intended file extension and the original file's extension.
 /// * `ctx`: The `egui::Context` for UI interaction, needed for repainting.
+/// * `write_manifest`: If `true`, also writes an `ExportManifest` sidecar (`<file>.manifest.json`).
+/// * `pretty_json`: If `true`, Json files are indented (see `write_json`).
 ///
 /// ### Returns
 ///
-/// A `PolarsViewResult` indicating success or the type of error encountered.
+/// The path of the written file, or the type of error encountered.
 ///
 /// ### Errors
 ///
@@ -55,10 +125,25 @@ pub async fn open_file() -> PolarsViewResult<PathBuf> {
 /// * If the chosen file format is not supported.
 /// * If the file type and file extension do not match (e.g., saving a CSV file with a .parquet extension).
 ///   This is handled by `PolarsViewError::UnsupportedFileType`.
+/// * If the file is compressed (e.g., `data.csv.gz`): it is read-only, use "Save As...".
 /// * If the filename cannot be determined.
-pub async fn save(container: Arc<DataContainer>, ctx: Context) -> PolarsViewResult<()> {
+pub async fn save(
+    container: Arc<DataContainer>,
+    ctx: Context,
+    write_manifest: bool,
+    pretty_json: bool,
+) -> PolarsViewResult<PathBuf> {
     // Get the file path from the container's filter.
     let path = container.filter.absolute_path.clone();
+    let saved_path = path.clone();
+
+    // Compressed files would be overwritten with plain text (see `compression.rs`).
+    if let Some(compression) = Compression::from_path(&path) {
+        return Err(PolarsViewError::UnsupportedFileType(format!(
+            "`{}` is compressed ({compression:?}): use \"Save As...\" to write a new file",
+            path.display()
+        )));
+    }
     // Determine the file extension from the path.
     let file_extension = FileExtension::from_path(&path);
 
@@ -67,11 +152,6 @@ pub async fn save(container: Arc<DataContainer>, ctx: Context) -> PolarsViewResu
 
     // 5. Spawn a blocking task for file I/O. This is crucial to prevent blocking the main UI thread.
     let _handle = tokio::task::spawn_blocking(move || {
-        // Create the output file.  `File::create` will create the file if it doesn't
-        // exist, or truncate it if it does. The `?` operator handles potential I/O errors.
-        let file = File::create(&path)?;
-        let mut writer = BufWriter::new(file);
-
         // Validate that the file extension matches the intended file format.
         //
         // We compare the *intended* extension (from the user, based on the file name
@@ -82,49 +162,57 @@ pub async fn save(container: Arc<DataContainer>, ctx: Context) -> PolarsViewResu
         // We handle the `Missing` extension case: if the *original* file didn't have
         // an extension (unlikely, but possible), we assume the user's save selection
         // is the intended format.
+        //
+        // The data goes to a temporary file next to the original, which replaces it only
+        // once completely written (see `write_atomically`): an error or an interrupted
+        // save leaves the original file unchanged.
+        let result: PolarsViewResult<()> = write_atomically(&path, |writer| {
+            match (&file_extension, container.extension.as_ref()) {
+                // CSV cases.
+                (FileExtension::Csv, FileExtension::Csv)    // CSV -> CSV:  OK.
+                | (FileExtension::Csv, FileExtension::Missing) => // Missing original -> CSV:  OK, user probably wants CSV now.
+                    CsvWriter::new(writer)
+                    .finish(&mut container.df.as_ref().clone())  // Write as CSV
+                    .map_err(PolarsViewError::from),   // Convert PolarsError to our error type
+               // Json
+                (FileExtension::Json, FileExtension::Json)    // Json -> Json:  OK.
+                | (FileExtension::Json, FileExtension::Missing) => // Missing original -> Json:  OK, user probably wants Json now.
+                    write_json(&mut container.df.as_ref().clone(), writer, JsonFormat::Json, pretty_json), // Write as Json
+               // NDJson cases
+                (FileExtension::NDJson, FileExtension::NDJson)    // NDJson -> NDJson:  OK.
+                | (FileExtension::NDJson, FileExtension::Missing) => // Missing original -> NDJson:  OK, user probably wants NDJson now.
+                    write_json(&mut container.df.as_ref().clone(), writer, JsonFormat::JsonLines, false), // Write as NDJson
+               // Parquet
+                (FileExtension::Parquet, FileExtension::Parquet)    // Parquet -> Parquet: OK
+                | (FileExtension::Parquet, FileExtension::Missing) => {
+                    // Missing original -> Parquet: OK, assume user knows what they're doing.
+                    ParquetWriter::new(writer).finish(&mut container.df.as_ref().clone())?; // Propagate errors
+                    Ok(())
+                }
+                _ => {
+                    // Mismatch. Construct a detailed error message, including the problematic filename.
+                    // The `?` inside `ok_or_else` and `to_str` propagates errors if those operations fail
+                    // Get filename with extension
+                    let file_name = path
+                        .file_name()
+                        .ok_or_else(|| PolarsViewError::Other("Could not get file name".into()))? // Convert OsStr to String
+                        .to_str() // Convert to &str
+                        .ok_or_else(|| PolarsViewError::Other("Invalid UTF-8 in file name.".into()))?; // Propagate conversion errors
 
-        let result: PolarsViewResult<()> = match (&file_extension, container.extension.as_ref()) {
-            // CSV cases.
-            (FileExtension::Csv, FileExtension::Csv)    // CSV -> CSV:  OK.
-            | (FileExtension::Csv, FileExtension::Missing) => // Missing original -> CSV:  OK, user probably wants CSV now.
-                CsvWriter::new(&mut writer)
-                .finish(&mut container.df.as_ref().clone())  // Write as CSV
-                .map_err(PolarsViewError::from),   // Convert PolarsError to our error type
-           // Json
-            (FileExtension::Json, FileExtension::Json)    // Json -> Json:  OK.
-            | (FileExtension::Json, FileExtension::Missing) => // Missing original -> Json:  OK, user probably wants Json now.
-                JsonWriter::new(&mut writer)
-                .finish(&mut container.df.as_ref().clone()) // Write as Json
-                .map_err(PolarsViewError::from),   // Convert PolarsError to our error type.
-           // NDJson cases
-            (FileExtension::NDJson, FileExtension::NDJson)    // NDJson -> NDJson:  OK.
-            | (FileExtension::NDJson, FileExtension::Missing) => // Missing original -> NDJson:  OK, user probably wants NDJson now.
-                // Corrected: NDJson uses JsonLineWriter, not JsonWriter
-                JsonWriter::new(&mut writer)
-                .finish(&mut container.df.as_ref().clone())  // Write as NDJson
-                .map_err(PolarsViewError::from),   // Convert PolarsError to our error type
-           // Parquet
-            (FileExtension::Parquet, FileExtension::Parquet)    // Parquet -> Parquet: OK
-            | (FileExtension::Parquet, FileExtension::Missing) => {
-                // Missing original -> Parquet: OK, assume user knows what they're doing.
-                ParquetWriter::new(&mut writer).finish(&mut container.df.as_ref().clone())?; // Propagate errors
-                Ok(())
+                    Err(PolarsViewError::UnsupportedFileType(format!(
+                        "`{file_name}`: file type and extension do not match",
+                    )))?
+                }
             }
-            _ => {
-                // Mismatch. Construct a detailed error message, including the problematic filename.
-                // The `?` inside `ok_or_else` and `to_str` propagates errors if those operations fail
-                // Get filename with extension
-                let file_name = path
-                    .file_name()
-                    .ok_or_else(|| PolarsViewError::Other("Could not get file name".into()))? // Convert OsStr to String
-                    .to_str() // Convert to &str
-                    .ok_or_else(|| PolarsViewError::Other("Invalid UTF-8 in file name.".into()))?; // Propagate conversion errors
-
-                Err(PolarsViewError::UnsupportedFileType(format!(
-                    "`{file_name}`: file type and extension do not match",
-                )))?
+        });
+
+        // Record provenance next to the written file, if requested.
+        let result = result.and_then(|()| {
+            if write_manifest {
+                ExportManifest::new(&container, &path)?.write_sidecar()?;
             }
-        };
+            Ok(())
+        });
 
         // 6. Send the result and request repaint *within* spawn_blocking.
         if tx.send(result).is_err() {
@@ -140,26 +228,36 @@ pub async fn save(container: Arc<DataContainer>, ctx: Context) -> PolarsViewResu
     //    file-writing operation *and* from the channel communication (if the
     //    sender was dropped before sending, which indicates a problem).  The `?`
     //    at the end propagates these errors to the caller of `save`.
-    let _ = rx
-        .await
-        .map_err(|e| PolarsViewError::ChannelReceive(e.to_string()))?; // Handle and wrap error.
+    rx.await
+        .map_err(|e| PolarsViewError::ChannelReceive(e.to_string()))??; // Handle and wrap error.
 
-    Ok(()) // If we get here, everything succeeded.
+    Ok(saved_path) // If we get here, everything succeeded.
 }
 
-/// Saves the DataFrame to a file asynchronously, handling CSV, Json, NDJson and Parquet formats.
-/// The user is presented with a file dialog to choose the save location and format.
+/// Saves the DataFrame to a file asynchronously, handling CSV, Json, NDJson, Parquet and
+/// Excel (`.xlsx`) formats. The user is presented with a file dialog to choose the save
+/// location and format.
+///
+/// The displayed data is saved, so the applied sort is kept. Excel workbooks also keep
+/// hidden columns hidden (see `write_xlsx`).
 ///
 /// ### Arguments
 ///
 /// * `container`: The `DataContainer` holding the DataFrame to save, wrapped in an `Arc` for shared ownership.
 /// * `ctx`: The `egui::Context` for UI interaction, needed for repainting the UI after the save operation.
+/// * `write_manifest`: If `true`, also writes an `ExportManifest` sidecar (`<file>.manifest.json`).
+/// * `pretty_json`: If `true`, Json files are indented (see `write_json`).
 ///
 /// ### Returns
 ///
-/// A `PolarsViewResult<()>` indicating success or failure of the save operation.  The empty tuple `()`
-/// signifies successful completion.
-pub async fn save_as(container: Arc<DataContainer>, ctx: Context) -> PolarsViewResult<()> {
+/// `Some(path)` of the written file, `None` if the user cancelled the dialog,
+/// or the error encountered.
+pub async fn save_as(
+    container: Arc<DataContainer>,
+    ctx: Context,
+    write_manifest: bool,
+    pretty_json: bool,
+) -> PolarsViewResult<Option<PathBuf>> {
     // 1. Determine the default file name from the original file's name (if available).
     //    If the original file is "data.csv", the default save name will be "data.csv".
     //    If there's no original file, it defaults to "dataframe.csv".
@@ -176,103 +274,226 @@ pub async fn save_as(container: Arc<DataContainer>, ctx: Context) -> PolarsViewR
         .add_filter("Json", &["json"]) // Add a filter for Json files.
         .add_filter("NDJson", &["ndjson"]) // Add a filter for NDJson files.
         .add_filter("Parquet", &["parquet"]) // Add a filter for Parquet files.
+        .add_filter("Excel", &["xlsx"]) // Add a filter for Excel workbooks.
         .set_file_name(default_file_name) // Set the default file name.
         .save_file() // Show the dialog and get the chosen file (if any).
         .await;
 
     // 3. Handle the user's file selection (if any).  `file` is an `Option<FileHandle>`.
-    if let Some(file) = file {
-        // Clone the DataFrame. Needed for thread safety in the blocking task.
-        let mut df = container.df.as_ref().clone();
-
-        // 4. Create a channel for communicating the write result *before* spawning the blocking task.
-        //    This ensures the receiving end is ready before sending.  The channel carries a `PolarsViewResult<()>`
-        //    to signal success or propagate errors from the file writing operation.
-        let (tx, rx) = oneshot::channel::<PolarsViewResult<()>>();
-
-        // 5. Spawn a blocking task for the file-writing operation to avoid blocking the UI thread.
-        // `spawn_blocking` is necessary because file I/O operations are typically blocking.
-        let _handle = tokio::task::spawn_blocking(move || {
-            // Determine the file extension and write the DataFrame to the file.
-            let result: PolarsViewResult<()> = match FileExtension::from_path(file.path()) {
-                FileExtension::Csv => {
-                    // Use CSV separator from DataFilters
-                    let delimiter = match container.filter.csv_delimiter.as_bytes().first() {
-                        Some(byte) => *byte,
-                        None => {
-                            return Err(PolarsViewError::InvalidDelimiter(
-                                container.filter.csv_delimiter.clone(),
-                            ));
-                        }
-                    };
-                    // Create the file (overwrites if it exists, creates if it doesn't).
-                    let mut file = File::create(file.path())?;
+    let Some(file) = file else {
+        return Ok(None); // The user cancelled the dialog (no file selected).
+    };
+    let saved_path = file.path().to_path_buf();
+
+    // Clone the DataFrame. Needed for thread safety in the blocking task.
+    let mut df = container.df.as_ref().clone();
+
+    // 4. Create a channel for communicating the write result *before* spawning the blocking task.
+    //    This ensures the receiving end is ready before sending.  The channel carries a `PolarsViewResult<()>`
+    //    to signal success or propagate errors from the file writing operation.
+    let (tx, rx) = oneshot::channel::<PolarsViewResult<()>>();
+
+    // 5. Spawn a blocking task for the file-writing operation to avoid blocking the UI thread.
+    // `spawn_blocking` is necessary because file I/O operations are typically blocking.
+    let _handle = tokio::task::spawn_blocking(move || {
+        // Determine the file extension and write the DataFrame to the file.
+        let result: PolarsViewResult<()> = match FileExtension::from_path(file.path()) {
+            FileExtension::Csv => {
+                // Use CSV separator from DataFilters
+                let delimiter = match container.filter.csv_delimiter.as_bytes().first() {
+                    Some(byte) => *byte,
+                    None => {
+                        return Err(PolarsViewError::InvalidDelimiter(
+                            container.filter.csv_delimiter.clone(),
+                        ));
+                    }
+                };
+                // Write to a temporary file, renamed over the chosen file once complete.
+                write_atomically(file.path(), |writer| {
                     // Create a CSV writer and write the DataFrame.
-                    CsvWriter::new(&mut file)
+                    CsvWriter::new(writer)
                         .with_separator(delimiter) // Set the CSV delimiter/separator
                         .finish(&mut df) // Write the data and handle errors.
                         .map_err(PolarsViewError::from) // Convert PolarsError to PolarsViewError.
-                }
-                FileExtension::Json => {
-                    // Added json
-                    // Create the file
-                    let mut file = File::create(file.path())?;
-                    // Create a Json writer and write the DataFrame.
-                    JsonWriter::new(&mut file)
-                        .with_json_format(JsonFormat::Json)
-                        .finish(&mut df)
-                        .map_err(PolarsViewError::from) // Convert PolarsError to PolarsViewError
-                }
-                FileExtension::NDJson => {
-                    // Added ndjson
-                    // https://docs.pola.rs/user-guide/io/json/#write
-                    // Create the file
-                    let mut file = File::create(file.path())?;
-                    // Create a Json writer and write the DataFrame.
-                    JsonWriter::new(&mut file)
-                        .with_json_format(JsonFormat::JsonLines) // Use JsonLines for NDJson
-                        .finish(&mut df)
-                        .map_err(PolarsViewError::from) // Convert PolarsError to PolarsViewError
-                }
-                FileExtension::Parquet => {
-                    // Create the file.
-                    let mut file = File::create(file.path())?;
-                    // Create a Parquet writer and write the DataFrame.
-                    ParquetWriter::new(&mut file)
+                })
+            }
+            FileExtension::Json => {
+                // Added json
+                // Write the DataFrame as an array of records.
+                write_atomically(file.path(), |writer| {
+                    write_json(&mut df, writer, JsonFormat::Json, pretty_json)
+                })
+            }
+            FileExtension::NDJson => {
+                // Added ndjson
+                // https://docs.pola.rs/user-guide/io/json/#write
+                // Write the DataFrame as one record per line.
+                write_atomically(file.path(), |writer| {
+                    write_json(&mut df, writer, JsonFormat::JsonLines, false)
+                })
+            }
+            FileExtension::Parquet => {
+                // Create a Parquet writer and write the DataFrame.
+                write_atomically(file.path(), |writer| {
+                    ParquetWriter::new(writer)
                         .finish(&mut df)
                         .map_err(PolarsViewError::from)?; // Convert and propagate errors.
                     Ok(()) // Explicit Ok for clarity.
-                }
-                // Handle Unknown or Missing extension (this is now exhaustive).  If the user
-                // doesn't select a filter, rfd defaults to the first filter (CSV in this case),
-                // so this error should rarely, if ever, occur with the current setup.  It's
-                // more relevant for the `save` function, where the user might not have an
-                // extension in the original file path.
-                FileExtension::Unknown(_) | FileExtension::Missing => {
-                    Err(PolarsViewError::UnsupportedFileType(
-                        "Unsupported file extension for saving".to_string(),
-                    ))
-                }
-            };
+                })
+            }
+            // Only `.xlsx` can be written (not `.xls`, `.xlsm`, `.xlsb` or `.ods`).
+            FileExtension::Excel if has_extension(file.path(), "xlsx") => write_xlsx(
+                &df,
+                file.path(),
+                &container.format.hidden_columns,
+                container.format.decimal,
+            ),
+            // Handle Unknown or Missing extension (this is now exhaustive).  If the user
+            // doesn't select a filter, rfd defaults to the first filter (CSV in this case),
+            // so this error should rarely, if ever, occur with the current setup.  It's
+            // more relevant for the `save` function, where the user might not have an
+            // extension in the original file path.
+            FileExtension::Excel
+            | FileExtension::Sqlite
+            | FileExtension::Zip
+            | FileExtension::Iceberg
+            | FileExtension::Unknown(_)
+            | FileExtension::Missing => Err(PolarsViewError::UnsupportedFileType(
+                "Unsupported file extension for saving".to_string(),
+            )),
+        };
 
-            // 6. Send the result of the file-writing operation and request a UI repaint *within*
-            //    the `spawn_blocking` closure. This ensures the message is sent even if the
-            //    receiver has been dropped.
-            if tx.send(result).is_err() {
-                error!("The receiver has been dropped."); // Log a warning if sending fails.
+        // Record provenance next to the exported file, if requested.
+        let result = result.and_then(|()| {
+            if write_manifest {
+                ExportManifest::new(&container, file.path())?.write_sidecar()?;
             }
+            Ok(())
+        });
 
-            ctx.request_repaint(); // Request a repaint of the UI. Essential for updates.
+        // 6. Send the result of the file-writing operation and request a UI repaint *within*
+        //    the `spawn_blocking` closure. This ensures the message is sent even if the
+        //    receiver has been dropped.
+        if tx.send(result).is_err() {
+            error!("The receiver has been dropped."); // Log a warning if sending fails.
+        }
 
-            Ok::<(), PolarsViewError>(()) //  Explicitly return Ok, even if void, for clarity
-        });
+        ctx.request_repaint(); // Request a repaint of the UI. Essential for updates.
+
+        Ok::<(), PolarsViewError>(()) //  Explicitly return Ok, even if void, for clarity
+    });
+
+    // 7. Await the result from the channel and handle potential errors. The `?` operator
+    //    propagates any errors that occurred during the send or during the async task.
+    rx.await
+        .map_err(|e| PolarsViewError::ChannelReceive(e.to_string()))??; // Handle and wrap error.
+
+    Ok(Some(saved_path))
+}
 
-        // 7. Await the result from the channel and handle potential errors. The `?` operator
-        //    propagates any errors that occurred during the send or during the async task.
-        let _ = rx
-            .await
-            .map_err(|e| PolarsViewError::ChannelReceive(e.to_string()))?; // Handle and wrap error.
+/// Writes `df` to `writer` as records (one object per row, keys in column order):
+/// a Json array (`JsonFormat::Json`) or one object per line (`JsonFormat::JsonLines`).
+///
+/// With `pretty`, a Json array is indented (two spaces); it is built in memory first.
+/// `pretty` is ignored for `JsonFormat::JsonLines`, which must stay one record per line.
+pub fn write_json<W: Write>(
+    df: &mut DataFrame,
+    mut writer: W,
+    format: JsonFormat,
+    pretty: bool,
+) -> PolarsViewResult<()> {
+    if pretty && matches!(format, JsonFormat::Json) {
+        let mut buffer = Vec::new();
+        JsonWriter::new(&mut buffer)
+            .with_json_format(JsonFormat::Json)
+            .finish(df)?;
+        // `preserve_order`: the keys keep the column order.
+        let records: serde_json::Value = serde_json::from_slice(&buffer)?;
+        serde_json::to_writer_pretty(&mut writer, &records)?;
+        writer.write_all(b"\n")?;
+    } else {
+        JsonWriter::new(&mut writer)
+            .with_json_format(format)
+            .finish(df)?;
     }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Whether the extension of `path` is `extension` (case-insensitive).
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+/// Opens `path` with the operating system's default application
+/// (a directory opens in the file manager).
+pub fn open_with_system(path: &Path) -> PolarsViewResult<()> {
+    #[cfg(target_os = "windows")]
+    let mut command = {
+        let mut command = std::process::Command::new("explorer");
+        command.arg(path);
+        command
+    };
+
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        let mut command = std::process::Command::new("open");
+        command.arg(path);
+        command
+    };
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let mut command = {
+        let mut command = std::process::Command::new("xdg-open");
+        command.arg(path);
+        command
+    };
+
+    command.spawn()?;
+    Ok(())
+}
 
-    Ok(()) // Return Ok even if the user cancelled the dialog (no file selected).
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_file_dialog`
+#[cfg(test)]
+mod tests_file_dialog {
+    use super::*;
+
+    fn sample() -> PolarsResult<DataFrame> {
+        df!("name" => &["a", "b"], "id" => &[Some(1), None])
+    }
+
+    #[test]
+    fn write_json_records() -> PolarsViewResult<()> {
+        let mut buffer = Vec::new();
+        write_json(&mut sample()?, &mut buffer, JsonFormat::Json, false)?;
+        let json = String::from_utf8(buffer).unwrap();
+        assert_eq!(json, r#"[{"name":"a","id":1},{"name":"b","id":null}]"#);
+
+        let mut buffer = Vec::new();
+        write_json(&mut sample()?, &mut buffer, JsonFormat::JsonLines, true)?;
+        let ndjson = String::from_utf8(buffer).unwrap();
+        assert_eq!(
+            ndjson,
+            "{\"name\":\"a\",\"id\":1}\n{\"name\":\"b\",\"id\":null}\n"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn write_pretty_json_keeps_column_order() -> PolarsViewResult<()> {
+        let mut buffer = Vec::new();
+        write_json(&mut sample()?, &mut buffer, JsonFormat::Json, true)?;
+        let json = String::from_utf8(buffer).unwrap();
+        println!("{json}");
+
+        assert!(json.starts_with("[\n  {\n    \"name\": \"a\",\n    \"id\": 1\n  },"));
+        assert!(json.ends_with("]\n"));
+        Ok(())
+    }
 }
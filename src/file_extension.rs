@@ -1,4 +1,4 @@
-use crate::PathExtension;
+use crate::{Compression, PathExtension, inner_path, is_iceberg_table};
 use std::path::Path;
 
 /// Represents the extension of a file.
@@ -12,6 +12,14 @@ pub enum FileExtension {
     NDJson,
     /// Parquet file extension.
     Parquet,
+    /// Excel/OpenDocument workbook extension (xlsx, xlsm, xlsb, xls, ods).
+    Excel,
+    /// SQLite database extension (sqlite, sqlite3, db).
+    Sqlite,
+    /// Zip archive extension: one of its entries is read (see `zip_archive.rs`).
+    Zip,
+    /// Apache Iceberg table: a table directory or a `*.metadata.json` file (see `iceberg.rs`).
+    Iceberg,
     /// Unknown file extension, storing the extension as a string.
     Unknown(String),
     /// Missing file extension, when no extension is present in the path.
@@ -20,7 +28,29 @@ pub enum FileExtension {
 
 impl FileExtension {
     /// Determines the file extension from a given path.
+    ///
+    /// Iceberg table directories and metadata files are `Iceberg`. Compressed text files
+    /// are classified by their inner extension (`data.csv.gz` is `Csv`); other compressed
+    /// files (e.g., `data.parquet.gz`) are `Unknown`.
     pub fn from_path(path: &Path) -> Self {
+        if is_iceberg_table(path) {
+            return FileExtension::Iceberg;
+        }
+
+        if Compression::from_path(path).is_some() {
+            return match FileExtension::from_path(&inner_path(path)) {
+                extension @ (FileExtension::Csv | FileExtension::Json | FileExtension::NDJson) => {
+                    extension
+                }
+                _ => FileExtension::Unknown(
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .and_then(|name| name.split_once('.'))
+                        .map_or_else(String::new, |(_stem, extensions)| extensions.to_lowercase()),
+                ),
+            };
+        }
+
         match path
             .extension_as_lowercase()
             .as_deref() // Converts `Option<String>` to `Option<&str>` for matching.
@@ -29,6 +59,9 @@ impl FileExtension {
             Some("json") => FileExtension::Json,
             Some("ndjson") => FileExtension::NDJson,
             Some("parquet") => FileExtension::Parquet,
+            Some("xlsx" | "xlsm" | "xlsb" | "xls" | "ods") => FileExtension::Excel,
+            Some("sqlite" | "sqlite3" | "db") => FileExtension::Sqlite,
+            Some("zip") => FileExtension::Zip,
             Some(ext) => FileExtension::Unknown(ext.to_owned()),
             None => FileExtension::Missing,
         }
@@ -1,7 +1,85 @@
-use crate::DataContainer;
+use crate::{CategoricalSaving, DataContainer, PolarsViewResult, format_bytes, sha256_file};
 
-use egui::{Color32, Frame, Grid, Stroke, Ui};
+use egui::{Color32, Context, Frame, Grid, RichText, Stroke, Ui};
 use polars::prelude::*;
+use std::{
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// State of a background checksum computation.
+enum ChecksumState {
+    /// Still hashing; the result arrives through the channel.
+    Pending(oneshot::Receiver<PolarsViewResult<String>>),
+    /// The SHA-256, as a lowercase hexadecimal string.
+    Ready(String),
+    /// The error message, if the file could not be hashed.
+    Failed(String),
+}
+
+/// SHA-256 of the loaded file, computed in the background (see `sha256_file`).
+///
+/// Kept across `FileInfo` regenerations (sort, format, query changes) as long as
+/// the file path and modification time are unchanged, so each file is hashed once.
+pub struct FileChecksum {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+    state: ChecksumState,
+}
+
+impl FileChecksum {
+    /// Spawns the hash computation on a blocking thread of `runtime`.
+    /// Requests a repaint of `ctx` when done.
+    pub fn spawn(path: &Path, runtime: &Handle, ctx: &Context) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let task_path = path.to_path_buf();
+        let ctx = ctx.clone();
+
+        runtime.spawn_blocking(move || {
+            let result = sha256_file(&task_path);
+            if tx.send(result).is_err() {
+                tracing::debug!("Checksum receiver dropped for {}", task_path.display());
+            }
+            ctx.request_repaint();
+        });
+
+        FileChecksum {
+            path: path.to_path_buf(),
+            modified: modified_time(path),
+            state: ChecksumState::Pending(rx),
+        }
+    }
+
+    /// Returns `true` if this checksum was computed for the current contents of `path`.
+    fn is_current(&self, path: &Path) -> bool {
+        self.path == path && self.modified == modified_time(path)
+    }
+
+    /// Moves a pending computation to `Ready`/`Failed` once its result has arrived.
+    fn poll(&mut self) {
+        if let ChecksumState::Pending(rx) = &mut self.state {
+            match rx.try_recv() {
+                Ok(Ok(hash)) => self.state = ChecksumState::Ready(hash),
+                Ok(Err(error)) => self.state = ChecksumState::Failed(error.to_string()),
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.state = ChecksumState::Failed("checksum task ended unexpectedly".into())
+                }
+            }
+        }
+    }
+}
+
+/// Returns the last modification time of `path`, if available.
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
 
 /// Represents file information.
 pub struct FileInfo {
@@ -11,6 +89,16 @@ pub struct FileInfo {
     col_count: usize,
     /// Schema of the dataset. Used for both Parquet and CSV.
     schema: SchemaRef,
+    /// SHA-256 of the source file (see `with_checksum`).
+    checksum: Option<FileChecksum>,
+    /// Number of chunks of each column, in schema order.
+    chunk_counts: Vec<usize>,
+    /// Number of categories of each column, in schema order (`None`: not Categorical/Enum).
+    category_counts: Vec<Option<usize>>,
+    /// Estimated heap size of the displayed DataFrame, in bytes.
+    estimated_size: usize,
+    /// String columns converted to Categorical, with the memory saved.
+    categorical_savings: Vec<CategoricalSaving>,
 }
 
 impl FileInfo {
@@ -19,16 +107,50 @@ impl FileInfo {
         let row_count = container.df.height();
         let col_count = container.df.width();
         let schema = container.df.schema().clone();
+        let category_counts = schema
+            .iter_names()
+            .map(|name| container.categories.get(name.as_str()).map(Vec::len))
+            .collect();
 
         Some(FileInfo {
             row_count,
             col_count,
             schema,
+            checksum: None,
+            chunk_counts: container
+                .df
+                .columns()
+                .iter()
+                .map(|c| c.n_chunks())
+                .collect(),
+            category_counts,
+            estimated_size: container.df.estimated_size(),
+            categorical_savings: container.categorical_savings.clone(),
         })
     }
 
-    /// Renders the file file_info (row count, column count) to the UI.
-    pub fn render_metadata(&self, ui: &mut Ui) {
+    /// Attaches the SHA-256 of `path`: reuses the checksum of `previous` if it is
+    /// still current, otherwise starts a new background computation.
+    pub fn with_checksum(
+        mut self,
+        previous: Option<FileInfo>,
+        path: &Path,
+        runtime: &Handle,
+        ctx: &Context,
+    ) -> Self {
+        if !path.is_file() {
+            return self;
+        }
+
+        self.checksum = match previous.and_then(|info| info.checksum) {
+            Some(checksum) if checksum.is_current(path) => Some(checksum),
+            _ => Some(FileChecksum::spawn(path, runtime, ctx)),
+        };
+        self
+    }
+
+    /// Renders the file file_info (row count, column count, memory, chunks, SHA-256) to the UI.
+    pub fn render_metadata(&mut self, ui: &mut Ui) {
         Frame::default()
             .stroke(Stroke::new(1.0, Color32::GRAY))
             .outer_margin(2.0)
@@ -46,6 +168,48 @@ impl FileInfo {
                         ui.label("Rows:");
                         ui.label(self.row_count.to_string());
                         ui.end_row();
+
+                        ui.label("Memory:");
+                        ui.label(format_bytes(self.estimated_size));
+                        ui.end_row();
+
+                        let max_chunks = self.chunk_counts.iter().copied().max().unwrap_or(0);
+                        ui.label("Chunks:");
+                        ui.label(format!(
+                            "max {max_chunks} per column ({} total)",
+                            self.chunk_counts.iter().sum::<usize>()
+                        ))
+                        .on_hover_text("Chunks per column are listed in the \"Columns\" panel.");
+                        ui.end_row();
+
+                        if !self.categorical_savings.is_empty() {
+                            let total: usize = self
+                                .categorical_savings
+                                .iter()
+                                .map(CategoricalSaving::bytes_saved)
+                                .sum();
+                            ui.label("Categorical:");
+                            ui.label(format!("saved {}", format_bytes(total)));
+                            ui.end_row();
+
+                            for saving in &self.categorical_savings {
+                                ui.label(format!("\t{}", saving.column_name));
+                                ui.label(format!(
+                                    "{} -> {} ({} unique)",
+                                    format_bytes(saving.bytes_before),
+                                    format_bytes(saving.bytes_after),
+                                    saving.unique_values
+                                ));
+                                ui.end_row();
+                            }
+                        }
+
+                        if let Some(checksum) = &mut self.checksum {
+                            checksum.poll();
+                            ui.label("SHA-256:");
+                            render_checksum(ui, &checksum.state);
+                            ui.end_row();
+                        }
                     });
             });
     }
@@ -64,6 +228,12 @@ impl FileInfo {
                 // Inside the collapsing section, display the column index and data type.
                 ui.label(format!("index: {index}"));
                 ui.label(format!("type: {dtype}"));
+                if let Some(chunks) = self.chunk_counts.get(index) {
+                    ui.label(format!("chunks: {chunks}"));
+                }
+                if let Some(Some(categories)) = self.category_counts.get(index) {
+                    ui.label(format!("categories: {categories}"));
+                }
             });
 
             // Check if the header was clicked (specifically with the right mouse button).
@@ -77,3 +247,34 @@ impl FileInfo {
         }
     }
 }
+
+/// Renders the checksum value: a spinner while pending, the abbreviated hash
+/// (full value on hover) with a copy button when ready, or the error.
+fn render_checksum(ui: &mut Ui, state: &ChecksumState) {
+    match state {
+        ChecksumState::Pending(_) => {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.label("computing...");
+            });
+        }
+        ChecksumState::Ready(hash) => {
+            ui.horizontal(|ui| {
+                let short = format!("{}…{}", &hash[..12], &hash[hash.len() - 12..]);
+                ui.label(RichText::new(short).monospace())
+                    .on_hover_text(hash);
+                if ui
+                    .small_button("📋")
+                    .on_hover_text("Copy SHA-256 to clipboard")
+                    .clicked()
+                {
+                    ui.ctx().copy_text(hash.clone());
+                }
+            });
+        }
+        ChecksumState::Failed(error) => {
+            ui.colored_label(Color32::RED, "unavailable")
+                .on_hover_text(error);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! Auto-reload: a `notify` watcher on the file of the active tab ("File" >
+//! "Auto-reload on change"). When the file changes on disk, the data is read again,
+//! keeping the current sort, column filters and format (see `DataContainer::reload`).
+
+use crate::PolarsViewResult;
+
+use egui::{Color32, Context, RichText, Ui};
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc::{Receiver, channel},
+    time::{Duration, Instant},
+};
+
+/// Watches one file for changes (see the module documentation).
+///
+/// The parent directory is watched, since editors often save by writing a new file and
+/// renaming it over the old one (which would end a watch on the file itself).
+pub struct FileWatcher {
+    /// The watched file.
+    path: PathBuf,
+    /// Kept alive: dropping it stops the watch.
+    _watcher: RecommendedWatcher,
+    /// Events sent by the watcher thread.
+    events: Receiver<notify::Result<Event>>,
+    /// When the last change of the file was seen (`None`: no change pending).
+    last_change: Option<Instant>,
+}
+
+impl FileWatcher {
+    /// Delay after the last change before reloading, so a file written in several steps
+    /// is read once, complete.
+    pub const DEBOUNCE: Duration = Duration::from_millis(500);
+
+    /// Starts watching `path`. The watcher thread requests a repaint of `ctx` on each
+    /// event, so changes are noticed without user input.
+    pub fn new(path: &Path, ctx: &Context) -> PolarsViewResult<Self> {
+        let (tx, events) = channel();
+        let ctx = ctx.clone();
+        let mut watcher = notify::recommended_watcher(move |event| {
+            if tx.send(event).is_ok() {
+                ctx.request_repaint();
+            }
+        })?;
+
+        let directory = path.parent().unwrap_or(Path::new("."));
+        watcher.watch(directory, RecursiveMode::NonRecursive)?;
+
+        Ok(FileWatcher {
+            path: path.to_path_buf(),
+            _watcher: watcher,
+            events,
+            last_change: None,
+        })
+    }
+
+    /// The watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns `true` once the file changed and then stayed unchanged for `DEBOUNCE`.
+    /// While a change is pending, a repaint of `ctx` is requested at the end of the delay.
+    pub fn poll(&mut self, ctx: &Context) -> bool {
+        for event in self.events.try_iter() {
+            match event {
+                Ok(event) if self.is_change(&event) => self.last_change = Some(Instant::now()),
+                Ok(_) => {}
+                Err(error) => tracing::warn!("File watcher error: {error}"),
+            }
+        }
+
+        let Some(last_change) = self.last_change else {
+            return false;
+        };
+        let elapsed = last_change.elapsed();
+        if elapsed < Self::DEBOUNCE {
+            ctx.request_repaint_after(Self::DEBOUNCE - elapsed);
+            return false;
+        }
+
+        self.last_change = None;
+        self.path.exists() // Not while the file is being replaced.
+    }
+
+    /// Whether `event` modifies (or re-creates) the watched file.
+    fn is_change(&self, event: &Event) -> bool {
+        (event.kind.is_modify() || event.kind.is_create())
+            && event.paths.iter().any(|path| same_file(path, &self.path))
+    }
+}
+
+/// Compares paths reported by the watcher (possibly canonicalized) with the watched path.
+fn same_file(reported: &Path, watched: &Path) -> bool {
+    reported == watched
+        || (reported.file_name() == watched.file_name()
+            && reported.parent().and_then(|dir| dir.canonicalize().ok())
+                == watched.parent().and_then(|dir| dir.canonicalize().ok()))
+}
+
+/// A short message shown in the status bar (bottom panel) for `StatusToast::TIMEOUT`.
+pub struct StatusToast {
+    pub text: String,
+    pub created: Instant,
+}
+
+impl StatusToast {
+    /// How long the message stays visible.
+    pub const TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub fn new(text: impl Into<String>) -> Self {
+        StatusToast {
+            text: text.into(),
+            created: Instant::now(),
+        }
+    }
+
+    /// Renders the message; returns `false` once it timed out.
+    pub fn show(&self, ui: &mut Ui) -> bool {
+        let elapsed = self.created.elapsed();
+        if elapsed >= Self::TIMEOUT {
+            return false;
+        }
+        ui.label(RichText::new(&self.text).color(Color32::from_rgb(80, 180, 80)));
+        ui.ctx().request_repaint_after(Self::TIMEOUT - elapsed);
+        true
+    }
+}
@@ -0,0 +1,349 @@
+//! Brazilian fiscal document validators (CPF, CNPJ and NF-e access keys), available
+//! with the tax-oriented build (`--features format-special`).
+
+use egui::{Align, ComboBox, Grid, Layout, Ui};
+use polars::prelude::*;
+
+/// Suffix of the Boolean column added by `add_validity_column` (e.g., `CNPJ_valid`).
+pub const VALIDITY_SUFFIX: &str = "_valid";
+
+/// Brazilian fiscal document identifiers with check digits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FiscalDocument {
+    /// CPF: 11 digits (individual taxpayer).
+    Cpf,
+    /// CNPJ: 14 characters (legal entity), numeric or alphanumeric (from July 2026).
+    Cnpj,
+    /// CPF or CNPJ, chosen by length (columns such as `CPF/CNPJ do Participante`).
+    CpfOrCnpj,
+    /// NF-e access key (Chave de Acesso): 44 digits.
+    NfeKey,
+}
+
+impl FiscalDocument {
+    /// All document kinds, in UI order.
+    pub const ALL: [FiscalDocument; 4] = [
+        FiscalDocument::Cpf,
+        FiscalDocument::Cnpj,
+        FiscalDocument::CpfOrCnpj,
+        FiscalDocument::NfeKey,
+    ];
+
+    /// Display name of the document kind.
+    pub fn label(&self) -> &'static str {
+        match self {
+            FiscalDocument::Cpf => "CPF",
+            FiscalDocument::Cnpj => "CNPJ",
+            FiscalDocument::CpfOrCnpj => "CPF/CNPJ",
+            FiscalDocument::NfeKey => "NF-e Key",
+        }
+    }
+
+    /// Guesses the document kind from a column name
+    /// (e.g., `CNPJ do Emitente`, `CPF/CNPJ`, `Chave da NFe`).
+    pub fn detect(column_name: &str) -> Option<Self> {
+        let name = column_name.to_uppercase();
+        let (cpf, cnpj) = (name.contains("CPF"), name.contains("CNPJ"));
+
+        match (cpf, cnpj) {
+            (true, true) => Some(FiscalDocument::CpfOrCnpj),
+            (true, false) => Some(FiscalDocument::Cpf),
+            (false, true) => Some(FiscalDocument::Cnpj),
+            _ if name.contains("CHAVE") => Some(FiscalDocument::NfeKey),
+            _ => None,
+        }
+    }
+
+    /// Checks the length and check digits of `value`.
+    /// Punctuation (`.`, `/`, `-`) and spaces are ignored.
+    pub fn is_valid(&self, value: &str) -> bool {
+        let chars = clean_identifier(value);
+
+        match self {
+            FiscalDocument::Cpf => is_valid_cpf(&chars),
+            FiscalDocument::Cnpj => is_valid_cnpj(&chars),
+            FiscalDocument::CpfOrCnpj => match chars.len() {
+                11 => is_valid_cpf(&chars),
+                _ => is_valid_cnpj(&chars),
+            },
+            FiscalDocument::NfeKey => is_valid_nfe_key(&chars),
+        }
+    }
+}
+
+/// Removes formatting characters and converts letters to uppercase.
+fn clean_identifier(value: &str) -> Vec<char> {
+    value
+        .chars()
+        .filter(|c| !matches!(c, '.' | '/' | '-') && !c.is_whitespace())
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+/// Modulo 11 check digit with the given weights: `0` if the remainder is below 2,
+/// otherwise `11 - remainder`. Characters are valued as `ASCII code - 48`
+/// (so `'0'..='9'` are 0 to 9 and `'A'..='Z'` are 17 to 42, as in the alphanumeric CNPJ).
+fn mod11_check_digit(chars: &[char], weights: impl Iterator<Item = u32>) -> u32 {
+    let sum: u32 = chars
+        .iter()
+        .zip(weights)
+        .map(|(c, weight)| (*c as u32 - 48) * weight)
+        .sum();
+
+    match sum % 11 {
+        0 | 1 => 0,
+        remainder => 11 - remainder,
+    }
+}
+
+/// Returns `true` if all characters are the same (e.g., `00000000000`), which
+/// passes the check digit test but is not a valid number.
+fn all_same(chars: &[char]) -> bool {
+    chars.windows(2).all(|pair| pair[0] == pair[1])
+}
+
+/// The value of the check digit at `index`, if it is a decimal digit.
+fn digit_at(chars: &[char], index: usize) -> Option<u32> {
+    chars.get(index)?.to_digit(10)
+}
+
+fn is_valid_cpf(chars: &[char]) -> bool {
+    if chars.len() != 11 || !chars.iter().all(char::is_ascii_digit) || all_same(chars) {
+        return false;
+    }
+
+    // CPF weights: 10..=2 for the first digit, 11..=2 for the second; remainder 10 -> 0.
+    let check = |n: usize| {
+        let sum: u32 = chars[..n]
+            .iter()
+            .zip((2..=(n as u32 + 1)).rev())
+            .map(|(c, weight)| c.to_digit(10).unwrap_or(0) * weight)
+            .sum();
+        (sum * 10 % 11) % 10
+    };
+
+    digit_at(chars, 9) == Some(check(9)) && digit_at(chars, 10) == Some(check(10))
+}
+
+fn is_valid_cnpj(chars: &[char]) -> bool {
+    let base_ok = chars
+        .iter()
+        .take(12)
+        .all(|c| c.is_ascii_digit() || c.is_ascii_uppercase());
+
+    if chars.len() != 14 || !base_ok || all_same(chars) {
+        return false;
+    }
+
+    // Weights 2..=9 cycling from the right.
+    let weights = |n: usize| (0..n).rev().map(|i| (i % 8) as u32 + 2);
+
+    digit_at(chars, 12) == Some(mod11_check_digit(&chars[..12], weights(12)))
+        && digit_at(chars, 13) == Some(mod11_check_digit(&chars[..13], weights(13)))
+}
+
+fn is_valid_nfe_key(chars: &[char]) -> bool {
+    if chars.len() != 44 || !chars.iter().all(char::is_ascii_digit) {
+        return false;
+    }
+
+    // Weights 2..=9 cycling from the right over the first 43 digits.
+    let weights = (0..43).rev().map(|i| (i % 8) as u32 + 2);
+
+    digit_at(chars, 43) == Some(mod11_check_digit(&chars[..43], weights))
+}
+
+/// Appends a Boolean column `<column_name>_valid` with the check digit validation
+/// of `column_name` (see `FiscalDocument::is_valid`). Null values stay null.
+///
+/// ### Returns
+/// The new `DataFrame` and the number of invalid (non-null) values.
+pub fn add_validity_column(
+    mut df: DataFrame,
+    column_name: &str,
+    document: FiscalDocument,
+) -> PolarsResult<(DataFrame, usize)> {
+    let values = df.column(column_name)?.cast(&DataType::String)?;
+
+    let validity: BooleanChunked = values
+        .as_materialized_series()
+        .str()?
+        .into_iter()
+        .map(|value| value.map(|value| document.is_valid(value)))
+        .collect();
+
+    let invalid = validity
+        .into_iter()
+        .filter(|valid| *valid == Some(false))
+        .count();
+
+    df.with_column(
+        validity
+            .with_name(format!("{column_name}{VALIDITY_SUFFIX}").into())
+            .into_column(),
+    )?;
+
+    Ok((df, invalid))
+}
+
+/// State of the "Validators" side panel section: the column to check and its document kind.
+#[derive(Debug, Clone, Default)]
+pub struct FiscalValidator {
+    pub column_name: String,
+    /// The document kind (auto-detected from the column name when `None`).
+    pub document: Option<FiscalDocument>,
+}
+
+impl FiscalValidator {
+    /// Renders the column and document kind selection.
+    ///
+    /// Returns `Some((column_name, document))` when "Validate" is clicked.
+    pub fn render(&mut self, ui: &mut Ui, schema: &Schema) -> Option<(String, FiscalDocument)> {
+        let columns: Vec<String> = schema
+            .iter()
+            .filter(|(name, dtype)| {
+                (dtype.is_string() || dtype.is_integer()) && !name.ends_with(VALIDITY_SUFFIX)
+            })
+            .map(|(name, _dtype)| name.to_string())
+            .collect();
+
+        if !columns.contains(&self.column_name) {
+            // Prefer a column whose name identifies a document.
+            self.column_name = columns
+                .iter()
+                .find(|name| FiscalDocument::detect(name).is_some())
+                .or(columns.first())
+                .cloned()
+                .unwrap_or_default();
+            self.document = FiscalDocument::detect(&self.column_name);
+        }
+
+        if columns.is_empty() {
+            ui.label("No String columns to validate.");
+            return None;
+        }
+
+        let mut request = None;
+        let previous_column = self.column_name.clone();
+
+        Grid::new("fiscal_validator_grid")
+            .num_columns(2)
+            .spacing([10.0, 20.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Column:");
+                ComboBox::from_id_salt("fiscal_column")
+                    .selected_text(&self.column_name)
+                    .show_ui(ui, |ui| {
+                        for name in &columns {
+                            ui.selectable_value(&mut self.column_name, name.clone(), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Document:");
+                ComboBox::from_id_salt("fiscal_document")
+                    .selected_text(self.document.map_or("Select...", |doc| doc.label()))
+                    .show_ui(ui, |ui| {
+                        for document in FiscalDocument::ALL {
+                            ui.selectable_value(&mut self.document, Some(document), document.label());
+                        }
+                    });
+                ui.end_row();
+
+                ui.label(""); // For alignment.
+                ui.with_layout(Layout::top_down(Align::Center), |ui| {
+                    if ui
+                        .add_enabled(self.document.is_some(), egui::Button::new("Validate"))
+                        .on_hover_text(format!(
+                            "Append a Boolean `<column>{VALIDITY_SUFFIX}` column with the check digit validation\n\
+                            and highlight the rows with invalid values."
+                        ))
+                        .clicked()
+                        && let Some(document) = self.document
+                    {
+                        request = Some((self.column_name.clone(), document));
+                    }
+                });
+                ui.end_row();
+            });
+
+        if self.column_name != previous_column {
+            self.document = FiscalDocument::detect(&self.column_name).or(self.document);
+        }
+
+        request
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test --features format-special -- --show-output tests_fiscal`
+#[cfg(test)]
+mod tests_fiscal {
+    use super::*;
+
+    #[test]
+    fn validate_cpf() {
+        assert!(FiscalDocument::Cpf.is_valid("529.982.247-25"));
+        assert!(FiscalDocument::Cpf.is_valid("52998224725"));
+        assert!(!FiscalDocument::Cpf.is_valid("529.982.247-26"));
+        assert!(!FiscalDocument::Cpf.is_valid("111.111.111-11"));
+        assert!(!FiscalDocument::Cpf.is_valid("5299822472"));
+    }
+
+    #[test]
+    fn validate_cnpj() {
+        assert!(FiscalDocument::Cnpj.is_valid("11.222.333/0001-81"));
+        assert!(!FiscalDocument::Cnpj.is_valid("11.222.333/0001-82"));
+        assert!(!FiscalDocument::Cnpj.is_valid("00000000000000"));
+        // Alphanumeric CNPJ (example published by the Receita Federal).
+        assert!(FiscalDocument::Cnpj.is_valid("12.ABC.345/01DE-35"));
+        assert!(FiscalDocument::Cnpj.is_valid("12abc34501de35"));
+        assert!(!FiscalDocument::Cnpj.is_valid("12.ABC.345/01DE-36"));
+    }
+
+    #[test]
+    fn validate_cpf_or_cnpj_and_nfe_key() {
+        assert!(FiscalDocument::CpfOrCnpj.is_valid("529.982.247-25"));
+        assert!(FiscalDocument::CpfOrCnpj.is_valid("11.222.333/0001-81"));
+        assert!(FiscalDocument::NfeKey.is_valid("35240111222333000181550010000001231000000029"));
+        assert!(!FiscalDocument::NfeKey.is_valid("35240111222333000181550010000001231000000028"));
+        assert!(!FiscalDocument::NfeKey.is_valid("3524011122233300018155001000000123100000002"));
+    }
+
+    #[test]
+    fn detect_from_column_name() {
+        assert_eq!(
+            FiscalDocument::detect("CNPJ do Emitente"),
+            Some(FiscalDocument::Cnpj)
+        );
+        assert_eq!(
+            FiscalDocument::detect("CPF/CNPJ"),
+            Some(FiscalDocument::CpfOrCnpj)
+        );
+        assert_eq!(
+            FiscalDocument::detect("Chave da NFe"),
+            Some(FiscalDocument::NfeKey)
+        );
+        assert_eq!(FiscalDocument::detect("Valor Total"), None);
+    }
+
+    #[test]
+    fn add_validity() -> PolarsResult<()> {
+        let df = df!(
+            "CNPJ" => &[Some("11.222.333/0001-81"), Some("11.222.333/0001-82"), None],
+        )?;
+
+        let (df, invalid) = add_validity_column(df, "CNPJ", FiscalDocument::Cnpj)?;
+        dbg!(&df);
+
+        let validity: Vec<Option<bool>> = df.column("CNPJ_valid")?.bool()?.into_iter().collect();
+        assert_eq!(validity, vec![Some(true), Some(false), None]);
+        assert_eq!(invalid, 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,359 @@
+//! Directory-as-dataset browsing: a folder dropped onto the window (or chosen with
+//! "File" > "Open Folder...") is listed in the "Folder" side panel section, where one of
+//! its data files can be opened, or several of them concatenated into one table
+//! (optionally with a `source_file` column naming the file of each row).
+//!
+//! The folder is kept while other files are opened, for quick switching between them.
+
+use crate::{DataFilter, FileExtension, PolarsViewError, PolarsViewResult, new_temp_file};
+
+use egui::{ComboBox, RichText, Ui};
+use polars::{functions::concat_df_diagonal, prelude::*};
+use std::{
+    collections::BTreeSet,
+    path::{Path, PathBuf},
+};
+use tempfile::TempPath;
+
+/// Name of the provenance column added by `concatenate_files` (see `SourceColumn`).
+pub const SOURCE_FILE_COLUMN: &str = "source_file";
+
+/// Provenance column appended to concatenated files, so filtered or aggregated rows
+/// can be traced back to their file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SourceColumn {
+    /// No provenance column.
+    #[default]
+    None,
+    /// The full path of the file.
+    Path,
+    /// The file name without directories and extension (e.g., `sales_2024`).
+    Stem,
+}
+
+impl SourceColumn {
+    /// All options, in menu order.
+    pub const ALL: [SourceColumn; 3] = [SourceColumn::None, SourceColumn::Path, SourceColumn::Stem];
+
+    /// Menu label.
+    pub fn label(self) -> &'static str {
+        match self {
+            SourceColumn::None => "No source column",
+            SourceColumn::Path => "Source: file path",
+            SourceColumn::Stem => "Source: file name",
+        }
+    }
+
+    /// The value of the provenance column for the rows of `path` (`None`: no column).
+    pub fn value(self, path: &Path) -> Option<String> {
+        match self {
+            SourceColumn::None => None,
+            SourceColumn::Path => Some(path.display().to_string()),
+            SourceColumn::Stem => Some(
+                path.file_stem()
+                    .map(|stem| stem.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+}
+
+/// Whether `path` has the extension of a data file polars-view can read.
+pub fn is_data_file(path: &Path) -> bool {
+    matches!(
+        FileExtension::from_path(path),
+        FileExtension::Csv
+            | FileExtension::Json
+            | FileExtension::NDJson
+            | FileExtension::Parquet
+            | FileExtension::Excel
+            | FileExtension::Sqlite
+    )
+}
+
+/// The data files (see `is_data_file`) directly inside `folder`, sorted by name.
+pub fn list_data_files(folder: &Path) -> PolarsViewResult<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(folder)? {
+        let path = entry?.path();
+        if path.is_file() && is_data_file(&path) {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Reads the files `paths` with the read options of `template` (delimiter, null values,
+/// schema inference) and concatenates their rows. Columns are matched by name; a column
+/// missing from a file is filled with nulls.
+///
+/// With `source_column`, a last String column `source_file` holds the path or name of
+/// each row's file (`source_file_1`, ... if a file already has a `source_file` column).
+///
+/// The result is written as Parquet to a new temporary file; returns its path (the file is
+/// removed when it is dropped).
+pub async fn concatenate_files(
+    paths: &[PathBuf],
+    template: &DataFilter,
+    source_column: SourceColumn,
+) -> PolarsViewResult<TempPath> {
+    let mut frames = Vec::with_capacity(paths.len());
+    for path in paths {
+        let mut filter = template.clone();
+        filter.sheet_name = None; // Sheets, tables and record types belong to the template's file.
+        filter.db_table = None;
+        filter.record_type = None;
+        filter.set_path(path)?;
+        let (df, _extension) = filter.get_df_and_extension().await?;
+        frames.push((df, source_column.value(path)));
+    }
+
+    if frames.is_empty() {
+        return Err(PolarsViewError::Other(
+            "No files to concatenate.".to_string(),
+        ));
+    }
+
+    // A name not used by any file.
+    let mut source_name = SOURCE_FILE_COLUMN.to_string();
+    for suffix in 1.. {
+        if !frames
+            .iter()
+            .any(|(df, _)| df.schema().contains(&source_name))
+        {
+            break;
+        }
+        source_name = format!("{SOURCE_FILE_COLUMN}_{suffix}");
+    }
+
+    let frames = frames
+        .into_iter()
+        .map(|(df, source)| match source {
+            Some(source) => df
+                .lazy()
+                .with_column(lit(source).alias(source_name.as_str()))
+                .collect(),
+            None => Ok(df),
+        })
+        .collect::<PolarsResult<Vec<DataFrame>>>()?;
+
+    let mut df = concat_df_diagonal(&frames)?;
+    if source_column != SourceColumn::None {
+        // Last column, after the columns found only in later files.
+        let source = df.drop_in_place(&source_name)?;
+        df.with_column(source)?;
+    }
+    let mut file = new_temp_file(".parquet")?;
+    ParquetWriter::new(file.as_file_mut()).finish(&mut df)?;
+    Ok(file.into_temp_path())
+}
+
+/// Action requested from the "Folder" side panel section.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FolderAction {
+    /// Open one file of the folder.
+    Open(PathBuf),
+    /// Concatenate the checked files (see `concatenate_files`) and open the result.
+    Concatenate(Vec<PathBuf>, SourceColumn),
+}
+
+/// State of the "Folder" side panel section (shared by all tabs).
+#[derive(Debug, Default)]
+pub struct FolderBrowser {
+    /// The listed folder (`None`: the section is hidden).
+    pub folder: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    /// Files checked for concatenation.
+    checked: BTreeSet<PathBuf>,
+    /// Provenance column of concatenations.
+    pub source_column: SourceColumn,
+    error: Option<String>,
+}
+
+impl FolderBrowser {
+    /// Lists the data files of `folder`.
+    pub fn open(&mut self, folder: &Path) {
+        self.folder = Some(folder.to_path_buf());
+        self.checked.clear();
+        self.refresh();
+    }
+
+    /// Lists the data files of the folder again (files may have been added or removed).
+    fn refresh(&mut self) {
+        let Some(folder) = &self.folder else {
+            return;
+        };
+
+        match list_data_files(folder) {
+            Ok(files) => {
+                self.files = files;
+                self.error = None;
+            }
+            Err(error) => {
+                self.files = Vec::new();
+                self.error = Some(format!("Failed to list {}: {error}", folder.display()));
+            }
+        }
+        self.checked.retain(|path| self.files.contains(path));
+    }
+
+    /// Renders the file list: click a name to open it (`current` is highlighted), check
+    /// several files to concatenate them.
+    pub fn render(&mut self, ui: &mut Ui, current: &Path) -> Option<FolderAction> {
+        let folder = self.folder.clone()?;
+        let mut action = None;
+
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(folder.display().to_string()).strong());
+            if ui.small_button("⟳").on_hover_text("Refresh").clicked() {
+                self.refresh();
+            }
+            if ui
+                .small_button("✖")
+                .on_hover_text("Close the folder")
+                .clicked()
+            {
+                *self = FolderBrowser::default();
+            }
+        });
+        if self.folder.is_none() {
+            return None;
+        }
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+        } else if self.files.is_empty() {
+            ui.label("No CSV, JSON, Parquet, Excel or SQLite files in this folder.");
+        }
+
+        for path in &self.files {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            ui.horizontal(|ui| {
+                let mut checked = self.checked.contains(path);
+                if ui
+                    .checkbox(&mut checked, "")
+                    .on_hover_text("Select for concatenation")
+                    .changed()
+                {
+                    if checked {
+                        self.checked.insert(path.clone());
+                    } else {
+                        self.checked.remove(path);
+                    }
+                }
+                if ui.selectable_label(path == current, name).clicked() {
+                    action = Some(FolderAction::Open(path.clone()));
+                }
+            });
+        }
+
+        if self.files.len() > 1 {
+            ui.horizontal(|ui| {
+                if ui.button("All").clicked() {
+                    self.checked = self.files.iter().cloned().collect();
+                }
+                if ui.button("None").clicked() {
+                    self.checked.clear();
+                }
+                if ui
+                    .add_enabled(
+                        self.checked.len() > 1,
+                        egui::Button::new(format!("Concatenate {} files", self.checked.len())),
+                    )
+                    .on_hover_text(
+                        "Stack the rows of the selected files into one table \
+                        (columns matched by name, missing ones filled with nulls).",
+                    )
+                    .clicked()
+                {
+                    action = Some(FolderAction::Concatenate(
+                        self.checked.iter().cloned().collect(),
+                        self.source_column,
+                    ));
+                }
+            });
+            ComboBox::from_id_salt("folder_source_column")
+                .selected_text(self.source_column.label())
+                .show_ui(ui, |ui| {
+                    for option in SourceColumn::ALL {
+                        ui.selectable_value(&mut self.source_column, option, option.label());
+                    }
+                })
+                .response
+                .on_hover_text(
+                    "Append a `source_file` column with the path or name of each row's file.",
+                );
+        }
+
+        action
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_folder_browser`
+#[cfg(test)]
+mod tests_folder_browser {
+    use super::*;
+    use std::fs::File;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn lists_and_concatenates_data_files() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let mut january = df!("id" => &[1, 2], "amount" => &[10.0, 20.0])?;
+        let mut february = df!("id" => &[3], "region" => &["north"])?;
+        ParquetWriter::new(File::create(dir.path().join("b_february.parquet"))?)
+            .finish(&mut february)?;
+        ParquetWriter::new(File::create(dir.path().join("a_january.parquet"))?)
+            .finish(&mut january)?;
+        std::fs::write(dir.path().join("notes.txt"), "not data")?;
+
+        let files = list_data_files(dir.path())?;
+        let names: Vec<_> = files.iter().filter_map(|path| path.file_name()).collect();
+        assert_eq!(names, ["a_january.parquet", "b_february.parquet"]);
+
+        let path = concatenate_files(&files, &DataFilter::default(), SourceColumn::None).await?;
+        let df = ParquetReader::new(File::open(&path)?).finish()?;
+        assert_eq!(df.shape(), (3, 3));
+        assert_eq!(df.column("region")?.null_count(), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn concatenation_with_source_column() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let mut january = df!("id" => &[1, 2], "source_file" => &["x", "y"])?;
+        let mut february = df!("id" => &[3], "region" => &["north"])?;
+        let files = [
+            dir.path().join("january.parquet"),
+            dir.path().join("february.parquet"),
+        ];
+        ParquetWriter::new(File::create(&files[0])?).finish(&mut january)?;
+        ParquetWriter::new(File::create(&files[1])?).finish(&mut february)?;
+
+        let path = concatenate_files(&files, &DataFilter::default(), SourceColumn::Stem).await?;
+        let df = ParquetReader::new(File::open(&path)?).finish()?;
+        dbg!(&df);
+
+        // The existing `source_file` column is kept; the provenance column comes last.
+        assert_eq!(
+            df.get_column_names_str(),
+            ["id", "source_file", "region", "source_file_1"]
+        );
+        let sources: Vec<_> = df.column("source_file_1")?.str()?.into_iter().collect();
+        assert_eq!(
+            sources,
+            [Some("january"), Some("january"), Some("february")]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,165 @@
+//! Frame-time watchdog: when rendering the table gets slow (e.g., automatic column widths
+//! on 500 columns), the view is simplified step by step instead of letting the UI freeze:
+//! first fixed column widths, then only the first `DEGRADED_MAX_COLUMNS` columns.
+//!
+//! The CPU time of each frame comes from `eframe::Frame::info`. A banner above the table
+//! tells what was changed and offers to restore the full view. The watchdog can be turned
+//! off in the "Settings" window (`AppSettings::disable_frame_watchdog`).
+
+use egui::{Color32, RichText, Ui};
+
+/// CPU time (seconds) above which a frame is slow.
+pub const SLOW_FRAME_SECONDS: f32 = 0.15;
+
+/// Consecutive slow frames before the view is simplified (one step at a time).
+pub const SLOW_FRAMES_TO_DEGRADE: usize = 10;
+
+/// Columns kept visible by `Degradation::FewerColumns`.
+pub const DEGRADED_MAX_COLUMNS: usize = 50;
+
+/// A simplification of the table view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Degradation {
+    /// Automatic column widths replaced by fixed widths.
+    FixedWidths,
+    /// Only the first `DEGRADED_MAX_COLUMNS` columns shown.
+    FewerColumns,
+}
+
+impl Degradation {
+    /// What was changed, for the banner.
+    pub fn description(self) -> String {
+        match self {
+            Degradation::FixedWidths => "fixed column widths".to_string(),
+            Degradation::FewerColumns => format!("first {DEGRADED_MAX_COLUMNS} columns only"),
+        }
+    }
+}
+
+/// Watches the frame times and picks the simplifications of the table view.
+#[derive(Debug, Default)]
+pub struct FrameWatchdog {
+    /// Consecutive slow frames.
+    slow_frames: usize,
+    /// Off for the session (disabled in Settings, or the full view was restored).
+    pub off: bool,
+    /// Simplifications applied to the current view, in order.
+    pub applied: Vec<Degradation>,
+    /// Columns hidden by `Degradation::FewerColumns` (shown again on restore).
+    pub hidden_columns: Vec<String>,
+}
+
+impl FrameWatchdog {
+    /// Records the CPU time of the last frame. After `SLOW_FRAMES_TO_DEGRADE` consecutive
+    /// slow frames, returns the next simplification, if any is left: fixed widths (if
+    /// `auto_col_width`), then fewer columns (if more than `DEGRADED_MAX_COLUMNS` are
+    /// `visible_columns`). The caller applies it.
+    pub fn record(
+        &mut self,
+        frame_seconds: f32,
+        auto_col_width: bool,
+        visible_columns: usize,
+    ) -> Option<Degradation> {
+        if self.off || frame_seconds < SLOW_FRAME_SECONDS {
+            self.slow_frames = 0;
+            return None;
+        }
+
+        self.slow_frames += 1;
+        if self.slow_frames < SLOW_FRAMES_TO_DEGRADE {
+            return None;
+        }
+        self.slow_frames = 0;
+
+        let step = if auto_col_width && !self.applied.contains(&Degradation::FixedWidths) {
+            Degradation::FixedWidths
+        } else if visible_columns > DEGRADED_MAX_COLUMNS
+            && !self.applied.contains(&Degradation::FewerColumns)
+        {
+            Degradation::FewerColumns
+        } else {
+            return None;
+        };
+
+        tracing::warn!("Slow frames ({frame_seconds:.3} s): {}", step.description());
+        self.applied.push(step);
+        Some(step)
+    }
+
+    /// Forgets the simplifications (a new file was opened).
+    pub fn reset(&mut self) {
+        self.slow_frames = 0;
+        self.applied.clear();
+        self.hidden_columns.clear();
+    }
+
+    /// Renders the banner listing the simplifications (nothing if none).
+    ///
+    /// Returns `true` if "Restore full view" was clicked.
+    pub fn render_banner(&self, ui: &mut Ui) -> bool {
+        if self.applied.is_empty() {
+            return false;
+        }
+
+        let changes: Vec<String> = self.applied.iter().map(|step| step.description()).collect();
+        let mut restore = false;
+        ui.horizontal(|ui| {
+            ui.label(
+                RichText::new(format!(" Rendering was slow: {} ", changes.join(", ")))
+                    .color(Color32::BLACK)
+                    .background_color(Color32::from_rgb(230, 190, 80)),
+            )
+            .on_hover_text(
+                "The table was simplified to keep the UI responsive.\n\
+                Turn this off in \"Settings\".",
+            );
+            restore = ui
+                .button("Restore full view")
+                .on_hover_text("Undo the simplifications (no more for this session).")
+                .clicked();
+        });
+        ui.separator();
+        restore
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_frame_watchdog`
+#[cfg(test)]
+mod tests_frame_watchdog {
+    use super::*;
+
+    #[test]
+    fn slow_frames_degrade_step_by_step() {
+        let mut watchdog = FrameWatchdog::default();
+        let slow = SLOW_FRAME_SECONDS * 2.0;
+
+        // A fast frame resets the count.
+        for _ in 1..SLOW_FRAMES_TO_DEGRADE {
+            assert_eq!(watchdog.record(slow, true, 500), None);
+        }
+        assert_eq!(watchdog.record(0.01, true, 500), None);
+
+        let mut steps = Vec::new();
+        for _ in 0..3 * SLOW_FRAMES_TO_DEGRADE {
+            steps.extend(watchdog.record(slow, true, 500));
+        }
+        assert_eq!(steps, [Degradation::FixedWidths, Degradation::FewerColumns]);
+
+        // Nothing left to simplify with few columns and fixed widths.
+        watchdog.reset();
+        for _ in 0..SLOW_FRAMES_TO_DEGRADE {
+            assert_eq!(watchdog.record(slow, false, 20), None);
+        }
+
+        // Off: never degrades.
+        watchdog.off = true;
+        for _ in 0..SLOW_FRAMES_TO_DEGRADE {
+            assert_eq!(watchdog.record(slow, true, 500), None);
+        }
+    }
+}
@@ -0,0 +1,469 @@
+//! Geospatial quick-look: detects latitude/longitude column pairs and plots them
+//! as a scatter over an offline degree grid (no map tiles), with row details on hover.
+
+use egui::{Align2, Color32, ComboBox, Context, FontId, Pos2, Rect, Sense, Stroke, Ui, Window};
+use polars::prelude::*;
+use std::sync::Arc;
+
+/// Maximum number of points drawn by the map (larger datasets are sampled evenly).
+pub const MAX_MAP_POINTS: usize = 50_000;
+
+/// Name tokens identifying latitude columns (e.g., `lat`, `pickup_latitude`).
+const LATITUDE_TOKENS: [&str; 2] = ["lat", "latitude"];
+
+/// Name tokens identifying longitude columns (e.g., `lon`, `store_lng`).
+const LONGITUDE_TOKENS: [&str; 4] = ["lon", "lng", "long", "longitude"];
+
+/// Maximum number of columns listed in the hover tooltip of a point.
+const MAX_TOOLTIP_COLUMNS: usize = 20;
+
+/// A pair of latitude/longitude columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LatLonColumns {
+    pub latitude: String,
+    pub longitude: String,
+}
+
+/// A plotted point: its row in the `DataFrame` and its coordinates (degrees).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GeoPoint {
+    pub row: usize,
+    pub lat: f64,
+    pub lon: f64,
+}
+
+/// Splits a column name into lowercase alphanumeric tokens
+/// (e.g., `"Pickup_Lat"` -> `["pickup", "lat"]`).
+fn name_tokens(name: &str) -> Vec<String> {
+    name.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// If `name` contains one of `tokens`, returns the remaining tokens (the "prefix key"
+/// used to pair `pickup_lat` with `pickup_lon`).
+fn strip_token(name: &str, tokens: &[&str]) -> Option<Vec<String>> {
+    let parts = name_tokens(name);
+    let position = parts
+        .iter()
+        .position(|part| tokens.contains(&part.as_str()))?;
+    let mut key = parts;
+    key.remove(position);
+    Some(key)
+}
+
+/// Detects a latitude/longitude column pair among the numeric columns of `schema`.
+///
+/// Columns are recognized by name tokens (`lat`/`latitude` and `lon`/`lng`/`long`/`longitude`).
+/// Pairs sharing the remaining tokens (e.g., `pickup_lat` / `pickup_lon`) are preferred;
+/// otherwise the first latitude and the first longitude columns are paired.
+pub fn detect_lat_lon_columns(schema: &Schema) -> Option<LatLonColumns> {
+    let numeric: Vec<&str> = schema
+        .iter()
+        .filter(|(_name, dtype)| dtype.is_primitive_numeric())
+        .map(|(name, _dtype)| name.as_str())
+        .collect();
+
+    let latitudes: Vec<(&str, Vec<String>)> = numeric
+        .iter()
+        .filter_map(|name| strip_token(name, &LATITUDE_TOKENS).map(|key| (*name, key)))
+        .collect();
+    let longitudes: Vec<(&str, Vec<String>)> = numeric
+        .iter()
+        .filter_map(|name| strip_token(name, &LONGITUDE_TOKENS).map(|key| (*name, key)))
+        .collect();
+
+    let pair = latitudes
+        .iter()
+        .find_map(|(lat, lat_key)| {
+            longitudes
+                .iter()
+                .find(|(_lon, lon_key)| lon_key == lat_key)
+                .map(|(lon, _)| (*lat, *lon))
+        })
+        .or_else(|| Some((latitudes.first()?.0, longitudes.first()?.0)))?;
+
+    Some(LatLonColumns {
+        latitude: pair.0.to_string(),
+        longitude: pair.1.to_string(),
+    })
+}
+
+/// Extracts the valid points of `columns` from `df`.
+///
+/// Rows with nulls, NaN or out-of-range coordinates (`|lat| > 90`, `|lon| > 180`) are skipped.
+/// When more than `max_points` points are valid, every n-th point is kept.
+///
+/// ### Returns
+/// The points and the number of rows skipped as invalid.
+pub fn extract_points(
+    df: &DataFrame,
+    columns: &LatLonColumns,
+    max_points: usize,
+) -> PolarsResult<(Vec<GeoPoint>, usize)> {
+    let lat = df.column(&columns.latitude)?.cast(&DataType::Float64)?;
+    let lon = df.column(&columns.longitude)?.cast(&DataType::Float64)?;
+
+    let mut points = Vec::new();
+    let mut invalid = 0;
+
+    let lats = lat.as_materialized_series().f64()?;
+    let lons = lon.as_materialized_series().f64()?;
+
+    for (row, (lat, lon)) in lats.into_iter().zip(lons.into_iter()).enumerate() {
+        match (lat, lon) {
+            (Some(lat), Some(lon)) if lat.abs() <= 90.0 && lon.abs() <= 180.0 => {
+                points.push(GeoPoint { row, lat, lon });
+            }
+            _ => invalid += 1,
+        }
+    }
+
+    if max_points > 0 && points.len() > max_points {
+        let step = points.len().div_ceil(max_points);
+        points = points.into_iter().step_by(step).collect();
+    }
+
+    Ok((points, invalid))
+}
+
+/// Chooses a "nice" grid spacing (degrees) giving at most ~10 lines over `span`.
+fn grid_step(span: f64) -> f64 {
+    const STEPS: [f64; 12] = [
+        0.001, 0.002, 0.005, 0.01, 0.02, 0.05, 0.1, 0.2, 0.5, 1.0, 5.0, 10.0,
+    ];
+    STEPS
+        .into_iter()
+        .find(|step| span / step <= 10.0)
+        .unwrap_or(30.0)
+}
+
+/// State of the map quick-look: selected columns, window visibility and cached points.
+#[derive(Default)]
+pub struct MapView {
+    /// Whether the "Map" window is shown.
+    pub open: bool,
+    /// The plotted columns (auto-detected when `None`).
+    pub columns: Option<LatLonColumns>,
+    points: Vec<GeoPoint>,
+    invalid: usize,
+    /// The `DataFrame` and columns the cached points were extracted from.
+    source: Option<(Arc<DataFrame>, LatLonColumns)>,
+}
+
+impl MapView {
+    /// Renders the "Map" side panel controls: latitude/longitude column selection
+    /// (auto-detected by name) and the "Show Map" toggle.
+    pub fn render_controls(&mut self, ui: &mut Ui, df: &Arc<DataFrame>) {
+        let schema = df.schema();
+        let numeric: Vec<String> = schema
+            .iter()
+            .filter(|(_name, dtype)| dtype.is_primitive_numeric())
+            .map(|(name, _dtype)| name.to_string())
+            .collect();
+
+        // Re-detect when nothing is selected or the selection no longer exists.
+        let valid = self.columns.as_ref().is_some_and(|columns| {
+            numeric.contains(&columns.latitude) && numeric.contains(&columns.longitude)
+        });
+        if !valid {
+            self.columns = detect_lat_lon_columns(schema);
+        }
+
+        let Some(columns) = &mut self.columns else {
+            ui.label("No latitude/longitude columns detected.");
+            self.open = false;
+            return;
+        };
+
+        egui::Grid::new("map_grid")
+            .num_columns(2)
+            .spacing([10.0, 20.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Latitude:");
+                ComboBox::from_id_salt("map_latitude")
+                    .selected_text(&columns.latitude)
+                    .show_ui(ui, |ui| {
+                        for name in &numeric {
+                            ui.selectable_value(&mut columns.latitude, name.clone(), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Longitude:");
+                ComboBox::from_id_salt("map_longitude")
+                    .selected_text(&columns.longitude)
+                    .show_ui(ui, |ui| {
+                        for name in &numeric {
+                            ui.selectable_value(&mut columns.longitude, name.clone(), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Show Map:");
+                ui.checkbox(&mut self.open, "").on_hover_text(
+                    "Plot the points over a latitude/longitude grid (offline, no tiles).",
+                );
+                ui.end_row();
+            });
+    }
+
+    /// Refreshes the cached points if the `DataFrame` or the columns changed.
+    fn refresh(&mut self, df: &Arc<DataFrame>, columns: &LatLonColumns) {
+        let current = self
+            .source
+            .as_ref()
+            .is_some_and(|(source_df, source_columns)| {
+                Arc::ptr_eq(source_df, df) && source_columns == columns
+            });
+
+        if current {
+            return;
+        }
+
+        match extract_points(df, columns, MAX_MAP_POINTS) {
+            Ok((points, invalid)) => {
+                self.points = points;
+                self.invalid = invalid;
+            }
+            Err(error) => {
+                tracing::warn!("Failed to extract map points: {error}");
+                self.points.clear();
+                self.invalid = df.height();
+            }
+        }
+        self.source = Some((df.clone(), columns.clone()));
+    }
+
+    /// Shows the "Map" window (if open), plotting the points of `df`.
+    pub fn show(&mut self, ctx: &Context, df: &Arc<DataFrame>) {
+        let Some(columns) = self.columns.clone() else {
+            return;
+        };
+        if !self.open {
+            return;
+        }
+
+        self.refresh(df, &columns);
+
+        let mut open = self.open;
+        Window::new("Map")
+            .default_size([640.0, 420.0])
+            .resizable(true)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} points ({} × {}){}",
+                    self.points.len(),
+                    columns.latitude,
+                    columns.longitude,
+                    if self.invalid > 0 {
+                        format!(", {} rows skipped (null or out of range)", self.invalid)
+                    } else {
+                        String::new()
+                    }
+                ));
+                self.render_plot(ui, df);
+            });
+        self.open = open;
+    }
+
+    /// Draws the grid and the points (equirectangular projection), with a tooltip
+    /// listing the row of the point under the pointer.
+    fn render_plot(&self, ui: &mut Ui, df: &DataFrame) {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::hover());
+        let rect = response.rect.shrink(24.0);
+        let visuals = ui.visuals();
+
+        painter.rect_filled(response.rect, 0.0, visuals.extreme_bg_color);
+
+        if self.points.is_empty() || rect.width() <= 0.0 || rect.height() <= 0.0 {
+            return;
+        }
+
+        // Bounds with a small margin (at least ~0.01° so single points are visible).
+        let (mut min_lon, mut max_lon, mut min_lat, mut max_lat) = self.points.iter().fold(
+            (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+            |(min_lon, max_lon, min_lat, max_lat), p| {
+                (
+                    min_lon.min(p.lon),
+                    max_lon.max(p.lon),
+                    min_lat.min(p.lat),
+                    max_lat.max(p.lat),
+                )
+            },
+        );
+        let margin = ((max_lon - min_lon).max(max_lat - min_lat) * 0.05).max(0.005);
+        min_lon -= margin;
+        max_lon += margin;
+        min_lat -= margin;
+        max_lat += margin;
+
+        // Same scale on both axes, centered.
+        let scale = (rect.width() as f64 / (max_lon - min_lon))
+            .min(rect.height() as f64 / (max_lat - min_lat));
+        let center = rect.center();
+        let (mid_lon, mid_lat) = ((min_lon + max_lon) / 2.0, (min_lat + max_lat) / 2.0);
+        let to_screen = |lat: f64, lon: f64| {
+            Pos2::new(
+                center.x + ((lon - mid_lon) * scale) as f32,
+                center.y - ((lat - mid_lat) * scale) as f32,
+            )
+        };
+        let plot_rect =
+            Rect::from_two_pos(to_screen(min_lat, min_lon), to_screen(max_lat, max_lon));
+
+        // Grid lines and labels.
+        let grid_stroke = Stroke::new(1.0, visuals.weak_text_color().gamma_multiply(0.4));
+        let font = FontId::monospace(10.0);
+        let step = grid_step((max_lon - min_lon).max(max_lat - min_lat));
+
+        let mut lon = (min_lon / step).ceil() * step;
+        while lon <= max_lon {
+            let x = to_screen(mid_lat, lon).x;
+            painter.line_segment(
+                [
+                    Pos2::new(x, plot_rect.top()),
+                    Pos2::new(x, plot_rect.bottom()),
+                ],
+                grid_stroke,
+            );
+            painter.text(
+                Pos2::new(x, plot_rect.bottom() + 2.0),
+                Align2::CENTER_TOP,
+                format!("{lon:.3}"),
+                font.clone(),
+                visuals.weak_text_color(),
+            );
+            lon += step;
+        }
+
+        let mut lat = (min_lat / step).ceil() * step;
+        while lat <= max_lat {
+            let y = to_screen(lat, mid_lon).y;
+            painter.line_segment(
+                [
+                    Pos2::new(plot_rect.left(), y),
+                    Pos2::new(plot_rect.right(), y),
+                ],
+                grid_stroke,
+            );
+            painter.text(
+                Pos2::new(plot_rect.left() - 2.0, y),
+                Align2::RIGHT_CENTER,
+                format!("{lat:.3}"),
+                font.clone(),
+                visuals.weak_text_color(),
+            );
+            lat += step;
+        }
+
+        // Points.
+        let color = Color32::from_rgb(0, 150, 255);
+        for point in &self.points {
+            painter.circle_filled(to_screen(point.lat, point.lon), 2.5, color);
+        }
+
+        // Hover: nearest point within 6 px.
+        let Some(pointer) = response.hover_pos() else {
+            return;
+        };
+        let nearest = self
+            .points
+            .iter()
+            .map(|point| (point, to_screen(point.lat, point.lon).distance(pointer)))
+            .filter(|(_point, distance)| *distance <= 6.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+
+        if let Some((point, _distance)) = nearest {
+            painter.circle_stroke(
+                to_screen(point.lat, point.lon),
+                5.0,
+                Stroke::new(2.0, Color32::YELLOW),
+            );
+            response.on_hover_ui_at_pointer(|ui| {
+                ui.strong(format!("Row {}", point.row));
+                egui::Grid::new("map_tooltip_grid").show(ui, |ui| {
+                    for column in df.columns().iter().take(MAX_TOOLTIP_COLUMNS) {
+                        ui.label(column.name().as_str());
+                        let value = column
+                            .get(point.row)
+                            .map(|value| value.to_string())
+                            .unwrap_or_default();
+                        ui.label(value);
+                        ui.end_row();
+                    }
+                });
+            });
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_geo`
+#[cfg(test)]
+mod tests_geo {
+    use super::*;
+
+    #[test]
+    fn detect_prefers_matching_prefixes() -> PolarsResult<()> {
+        let df = df!(
+            "dropoff_lat" => &[1.0],
+            "pickup_latitude" => &[2.0],
+            "pickup_lng" => &[3.0],
+            "dropoff_lon" => &[4.0],
+            "name" => &["x"],
+        )?;
+
+        let columns = detect_lat_lon_columns(df.schema());
+        assert_eq!(
+            columns,
+            Some(LatLonColumns {
+                latitude: "dropoff_lat".to_string(),
+                longitude: "dropoff_lon".to_string(),
+            })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn detect_requires_numeric_pair() -> PolarsResult<()> {
+        let df = df!("Latitude" => &["-23.5"], "Longitude" => &[-46.6], "latency" => &[1])?;
+        assert_eq!(detect_lat_lon_columns(df.schema()), None);
+
+        let df = df!("Latitude" => &[-23.5], "Longitude" => &[-46.6])?;
+        assert!(detect_lat_lon_columns(df.schema()).is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn extract_skips_invalid_and_samples() -> PolarsResult<()> {
+        let df = df!(
+            "lat" => &[Some(-23.5), None, Some(95.0), Some(10.0), Some(f64::NAN)],
+            "lon" => &[Some(-46.6), Some(1.0), Some(0.0), Some(20.0), Some(0.0)],
+        )?;
+        let columns = detect_lat_lon_columns(df.schema()).unwrap();
+
+        let (points, invalid) = extract_points(&df, &columns, MAX_MAP_POINTS)?;
+        assert_eq!(invalid, 3);
+        assert_eq!(points.iter().map(|p| p.row).collect::<Vec<_>>(), vec![0, 3]);
+
+        let (points, _invalid) = extract_points(&df, &columns, 1)?;
+        assert_eq!(points.len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn grid_step_is_nice() {
+        assert_eq!(grid_step(0.04), 0.005);
+        assert_eq!(grid_step(3.0), 0.5);
+        assert_eq!(grid_step(360.0), 30.0);
+    }
+}
@@ -0,0 +1,283 @@
+//! Group explorer: an expandable tree of the values of one or more columns
+//! (e.g., state -> city -> district) with row counts. Clicking a node filters
+//! the table to that group (via the SQL query, see `compose_quick_filter`).
+
+use crate::{
+    DataFilter, PolarsViewError, PolarsViewResult, QuickFilterOp, compose_quick_filter,
+    quick_filter_condition,
+};
+
+use egui::{ComboBox, Id, RichText, ScrollArea, Ui, collapsing_header::CollapsingState};
+use polars::prelude::*;
+
+/// Name of the row count column of the group-by (unlikely to clash with data columns).
+const GROUP_COUNT_COLUMN: &str = "__group_count";
+
+/// Maximum number of children rendered per node (the rest is summarized).
+const MAX_GROUP_CHILDREN: usize = 500;
+
+/// A group value in the tree, with its row count and subgroups (next level).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupNode {
+    /// Displayed value (`null` for nulls).
+    pub label: String,
+    /// SQL condition selecting this value at its level (e.g., `` `state` = 'SP' ``).
+    pub condition: String,
+    /// Number of rows in the group.
+    pub count: usize,
+    /// Subgroups by the next column (empty at the last level).
+    pub children: Vec<GroupNode>,
+}
+
+/// Builds the group tree of `df` by `columns` (one level per column, in order),
+/// with values sorted at each level.
+///
+/// Columns whose values have no SQL literal (nested types) cannot be used.
+pub fn build_group_tree(df: &DataFrame, columns: &[String]) -> PolarsViewResult<Vec<GroupNode>> {
+    if columns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let keys: Vec<Expr> = columns.iter().map(|name| col(name.as_str())).collect();
+    let counts = df
+        .clone()
+        .lazy()
+        .group_by(keys.clone())
+        .agg([len().alias(GROUP_COUNT_COLUMN)])
+        .sort_by_exprs(keys, SortMultipleOptions::default().with_nulls_last(true))
+        .collect()?;
+
+    let count_column = counts.column(GROUP_COUNT_COLUMN)?.cast(&DataType::UInt64)?;
+    let count_values = count_column.u64()?;
+    let level_columns = columns
+        .iter()
+        .map(|name| counts.column(name))
+        .collect::<PolarsResult<Vec<&Column>>>()?;
+
+    let mut roots: Vec<GroupNode> = Vec::new();
+
+    for row in 0..counts.height() {
+        let count = count_values.get(row).unwrap_or(0) as usize;
+        let mut level = &mut roots;
+
+        for column in &level_columns {
+            let value = column.get(row)?;
+            let label = match &value {
+                AnyValue::Null => "null".to_string(),
+                other => other
+                    .get_str()
+                    .map(str::to_string)
+                    .unwrap_or_else(|| other.to_string()),
+            };
+
+            // Rows are sorted by the group columns: equal values are contiguous.
+            if level.last().is_none_or(|node| node.label != label) {
+                let condition =
+                    quick_filter_condition(column.name().as_str(), &value, QuickFilterOp::Equal)
+                        .ok_or_else(|| {
+                            PolarsViewError::Other(format!(
+                                "Column `{}` ({}) cannot be grouped.",
+                                column.name(),
+                                column.dtype()
+                            ))
+                        })?;
+                level.push(GroupNode {
+                    label,
+                    condition,
+                    count: 0,
+                    children: Vec::new(),
+                });
+            }
+
+            let node = level.last_mut().expect("node was just pushed");
+            node.count += count;
+            level = &mut node.children;
+        }
+    }
+
+    Ok(roots)
+}
+
+/// State of the "Group Explorer" side panel section.
+#[derive(Debug, Clone, Default)]
+pub struct GroupExplorer {
+    /// Grouping columns, one tree level each.
+    pub columns: Vec<String>,
+    tree: Vec<GroupNode>,
+    /// Query when the tree was built: group filters are composed onto it.
+    base_query: String,
+    /// Conditions of the node that filtered the table, if any.
+    selected: Option<Vec<String>>,
+    error: Option<String>,
+}
+
+impl GroupExplorer {
+    /// Renders the column picker and the group tree of `df`.
+    ///
+    /// Returns `Some(new_query)` when a node is clicked: the query the tree was built
+    /// from, restricted to the node's group (all conditions from the root to the node).
+    pub fn render(&mut self, ui: &mut Ui, df: &DataFrame, filter: &DataFilter) -> Option<String> {
+        let schema = df.schema();
+        self.columns.retain(|name| schema.contains(name));
+
+        ui.label("Levels:");
+        let mut remove = None;
+        for (index, name) in self.columns.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}. {name}", index + 1));
+                if ui.small_button("✖").on_hover_text("Remove level").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.columns.remove(index);
+        }
+
+        let mut added = None;
+        ComboBox::from_id_salt("group_explorer_add")
+            .selected_text("Add level…")
+            .show_ui(ui, |ui| {
+                for (name, dtype) in schema.iter() {
+                    let groupable = !dtype.is_nested() && !matches!(dtype, DataType::Binary);
+                    if groupable
+                        && !self.columns.iter().any(|c| c == name.as_str())
+                        && ui.selectable_label(false, name.as_str()).clicked()
+                    {
+                        added = Some(name.to_string());
+                    }
+                }
+            });
+        if let Some(name) = added {
+            self.columns.push(name);
+        }
+
+        if ui
+            .add_enabled(!self.columns.is_empty(), egui::Button::new("Build tree"))
+            .clicked()
+        {
+            self.selected = None;
+            self.base_query = filter.query.clone();
+            match build_group_tree(df, &self.columns) {
+                Ok(tree) => {
+                    self.tree = tree;
+                    self.error = None;
+                }
+                Err(error) => {
+                    self.tree.clear();
+                    self.error = Some(error.to_string());
+                }
+            }
+        }
+
+        if let Some(error) = &self.error {
+            ui.colored_label(ui.visuals().error_fg_color, error);
+            return None;
+        }
+
+        if self.tree.is_empty() {
+            return None;
+        }
+
+        let mut clicked = None;
+        ScrollArea::vertical()
+            .id_salt("group_explorer_scroll")
+            .max_height(400.0)
+            .show(ui, |ui| {
+                let id = Id::new("group_explorer").with(&self.base_query);
+                render_nodes(ui, id, &self.tree, &[], &self.selected, &mut clicked);
+            });
+
+        let conditions = clicked?;
+        let query = compose_quick_filter(
+            &self.base_query,
+            &filter.table_name,
+            &conditions.join(" AND "),
+            true,
+        );
+        self.selected = Some(conditions);
+
+        Some(query)
+    }
+}
+
+/// Renders `nodes` (children of the node at `path`): inner nodes as collapsible
+/// entries, leaves as labels. A clicked node sets `clicked` to its conditions.
+fn render_nodes(
+    ui: &mut Ui,
+    id: Id,
+    nodes: &[GroupNode],
+    path: &[String],
+    selected: &Option<Vec<String>>,
+    clicked: &mut Option<Vec<String>>,
+) {
+    for node in nodes.iter().take(MAX_GROUP_CHILDREN) {
+        let mut conditions = path.to_vec();
+        conditions.push(node.condition.clone());
+
+        let is_selected = selected.as_ref() == Some(&conditions);
+        let text = format!("{}  ({})", node.label, node.count);
+        let node_id = id.with(&node.condition);
+
+        if node.children.is_empty() {
+            if ui.selectable_label(is_selected, text).clicked() {
+                *clicked = Some(conditions);
+            }
+            continue;
+        }
+
+        CollapsingState::load_with_default_open(ui.ctx(), node_id, false)
+            .show_header(ui, |ui| {
+                if ui
+                    .selectable_label(is_selected, text)
+                    .on_hover_text("Click to filter the table to this group")
+                    .clicked()
+                {
+                    *clicked = Some(conditions.clone());
+                }
+            })
+            .body(|ui| render_nodes(ui, node_id, &node.children, &conditions, selected, clicked));
+    }
+
+    if nodes.len() > MAX_GROUP_CHILDREN {
+        ui.label(RichText::new(format!("… {} more", nodes.len() - MAX_GROUP_CHILDREN)).weak());
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_group_explorer`
+#[cfg(test)]
+mod tests_group_explorer {
+    use super::*;
+
+    #[test]
+    fn builds_hierarchical_counts() -> PolarsViewResult<()> {
+        let df = df!(
+            "state" => &[Some("SP"), Some("RJ"), Some("SP"), Some("SP"), None],
+            "city" => &["Santos", "Niterói", "Campinas", "Santos", "Unknown"],
+        )?;
+        let columns = vec!["state".to_string(), "city".to_string()];
+
+        let tree = build_group_tree(&df, &columns)?;
+        dbg!(&tree);
+
+        let states: Vec<(&str, usize)> = tree.iter().map(|n| (n.label.as_str(), n.count)).collect();
+        assert_eq!(states, vec![("RJ", 1), ("SP", 3), ("null", 1)]);
+
+        let sp = &tree[1];
+        assert_eq!(sp.condition, "`state` = 'SP'");
+        let cities: Vec<(&str, usize)> = sp
+            .children
+            .iter()
+            .map(|n| (n.label.as_str(), n.count))
+            .collect();
+        assert_eq!(cities, vec![("Campinas", 1), ("Santos", 2)]);
+
+        assert_eq!(tree[2].condition, "`state` IS NULL");
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+//! Group summary of the selected cell: when a single cell of a Categorical/Enum column is
+//! selected, the status bar shows how many rows share its value and their share of the
+//! total (e.g., `city = Porto: 1,234 rows (12.3%)`).
+//!
+//! The counts of all the values of the column are computed once per displayed `DataFrame`
+//! and column (see `GroupSummaryCache`), so browsing the cells of a column is instant.
+
+use crate::{CellSelection, PolarsViewResult};
+
+use polars::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+/// Rows sharing the value of the selected cell.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupSummary {
+    pub column: String,
+    /// The value, as text (`None`: null).
+    pub value: Option<String>,
+    /// Rows with this value.
+    pub count: usize,
+    /// Rows of the `DataFrame`.
+    pub total: usize,
+}
+
+impl GroupSummary {
+    /// Share of the total, in percent.
+    pub fn percent(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            100.0 * self.count as f64 / self.total as f64
+        }
+    }
+
+    /// Status bar text, e.g., `city = Porto: 1,234 rows (12.3%)`.
+    pub fn text(&self) -> String {
+        let value = self.value.as_deref().unwrap_or("null");
+        let rows = if self.count == 1 { "row" } else { "rows" };
+        format!(
+            "{} = {value}: {} {rows} ({:.1}%)",
+            self.column,
+            thousands(self.count),
+            self.percent()
+        )
+    }
+}
+
+/// Writes `n` with a comma every three digits (e.g., `1234567` -> `1,234,567`).
+pub fn thousands(n: usize) -> String {
+    let digits = n.to_string();
+    let mut text = String::with_capacity(digits.len() + digits.len() / 3);
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            text.push(',');
+        }
+        text.push(digit);
+    }
+    text
+}
+
+/// Returns `true` for the columns summarized on selection (Categorical/Enum).
+pub fn is_summarized(dtype: &DataType) -> bool {
+    dtype.is_categorical() || dtype.is_enum()
+}
+
+/// Value counts of one column of a `DataFrame`.
+struct ColumnCounts {
+    /// The values of the column, as text (to look up the value of a row).
+    values: StringChunked,
+    counts: HashMap<Option<String>, usize>,
+}
+
+impl ColumnCounts {
+    fn new(df: &DataFrame, column: &str) -> PolarsViewResult<Self> {
+        let values = df.column(column)?.cast(&DataType::String)?;
+        let values = values.str()?.clone();
+
+        let mut counts: HashMap<Option<String>, usize> = HashMap::new();
+        for value in values.iter() {
+            *counts.entry(value.map(str::to_string)).or_default() += 1;
+        }
+
+        Ok(ColumnCounts { values, counts })
+    }
+}
+
+/// Value counts of the selected column, computed once per `DataFrame` and column.
+#[derive(Default)]
+pub struct GroupSummaryCache {
+    /// The `DataFrame` and column the counts were computed from.
+    source: Option<(Arc<DataFrame>, String)>,
+    counts: Option<ColumnCounts>,
+}
+
+impl GroupSummaryCache {
+    /// The summary of the cell selected by `selection` in `df` (`columns`: the visible
+    /// column names, in display order), if it is a single cell of a summarized column
+    /// (see `is_summarized`).
+    pub fn summary(
+        &mut self,
+        df: &Arc<DataFrame>,
+        columns: &[&str],
+        selection: &CellSelection,
+    ) -> Option<GroupSummary> {
+        if !selection.is_single_cell() {
+            return None;
+        }
+        let (row, column_index) = selection.anchor;
+        let column = *columns.get(column_index)?;
+        if row >= df.height() || !is_summarized(df.column(column).ok()?.dtype()) {
+            return None;
+        }
+
+        self.refresh(df, column);
+        let counts = self.counts.as_ref()?;
+        let value = counts.values.get(row).map(str::to_string);
+
+        Some(GroupSummary {
+            column: column.to_string(),
+            count: counts.counts.get(&value).copied().unwrap_or_default(),
+            value,
+            total: df.height(),
+        })
+    }
+
+    /// Recomputes the counts if the `DataFrame` or the column changed.
+    fn refresh(&mut self, df: &Arc<DataFrame>, column: &str) {
+        let current = self
+            .source
+            .as_ref()
+            .is_some_and(|(source_df, source_column)| {
+                Arc::ptr_eq(source_df, df) && source_column == column
+            });
+
+        if current {
+            return;
+        }
+
+        self.counts = ColumnCounts::new(df, column)
+            .inspect_err(|error| {
+                tracing::warn!("Failed to count the values of '{column}': {error}");
+            })
+            .ok();
+        self.source = Some((df.clone(), column.to_string()));
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_group_summary`
+#[cfg(test)]
+mod tests_group_summary {
+    use super::*;
+
+    #[test]
+    fn summary_of_a_categorical_cell() -> PolarsViewResult<()> {
+        let categorical = DataType::from_categories(Categories::global());
+        let df = df!(
+            "city" => &[Some("Porto"), Some("Lisbon"), Some("Porto"), None],
+            "sales" => &[1, 2, 3, 4]
+        )?
+        .lazy()
+        .with_column(col("city").cast(categorical))
+        .collect()?;
+        let df = Arc::new(df);
+        let columns = ["city", "sales"];
+        let mut cache = GroupSummaryCache::default();
+
+        let summary = cache
+            .summary(&df, &columns, &CellSelection::cell(2, 0))
+            .expect("categorical cell");
+        assert_eq!(summary.value.as_deref(), Some("Porto"));
+        assert_eq!((summary.count, summary.total), (2, 4));
+        assert_eq!(summary.text(), "city = Porto: 2 rows (50.0%)");
+
+        let summary = cache
+            .summary(&df, &columns, &CellSelection::cell(3, 0))
+            .expect("null cell");
+        assert_eq!(summary.text(), "city = null: 1 row (25.0%)");
+
+        // Not a categorical column, or not a single cell.
+        assert!(
+            cache
+                .summary(&df, &columns, &CellSelection::cell(0, 1))
+                .is_none()
+        );
+        assert!(
+            cache
+                .summary(&df, &columns, &CellSelection::row(0))
+                .is_none()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn thousands_separators() {
+        assert_eq!(thousands(0), "0");
+        assert_eq!(thousands(999), "999");
+        assert_eq!(thousands(1_234), "1,234");
+        assert_eq!(thousands(1_234_567), "1,234,567");
+    }
+}
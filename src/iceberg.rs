@@ -0,0 +1,382 @@
+//! Apache Iceberg tables (basic, read-only): a table directory (with its `metadata/`
+//! folder) or one of its `*.metadata.json` files opens the current snapshot of the table.
+//!
+//! The table metadata (JSON) names the current snapshot, whose manifest list (Avro) names
+//! the manifests (Avro), which list the data files (Parquet). The data files are scanned
+//! with Polars and concatenated by column name.
+//!
+//! Paths in the metadata under the table `location` are resolved against the opened
+//! directory, so tables copied from a warehouse (e.g., `s3://bucket/db/table`) open
+//! locally. Row-level deletes (delete files of format v2) are not supported.
+//!
+//! Reading the manifests needs the `iceberg` cargo feature; without it, tables are still
+//! detected, and opening one reports the feature to build with.
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use polars::prelude::*;
+use serde::{Deserialize, de::DeserializeOwned};
+use std::{
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+};
+
+/// Suffix of the table metadata files (`v3.metadata.json`, `00003-<uuid>.metadata.json`).
+const METADATA_SUFFIX: &str = ".metadata.json";
+
+/// Status of a manifest entry whose data file was removed from the table.
+const DELETED_STATUS: i32 = 2;
+
+/// Content of data files (`DataFile::content`) and of data manifests (`ManifestFile::content`).
+const DATA_CONTENT: i32 = 0;
+
+/// The fields of the table metadata used to find the current snapshot.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct TableMetadata {
+    /// Base location of the table (e.g., `s3://bucket/db/table`).
+    location: String,
+    /// `None` (or -1 in format v1): the table has no snapshot yet.
+    #[serde(default)]
+    current_snapshot_id: Option<i64>,
+    #[serde(default)]
+    snapshots: Vec<Snapshot>,
+}
+
+/// A snapshot of the table.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct Snapshot {
+    snapshot_id: i64,
+    /// The manifest list (format v2, and most v1 tables).
+    manifest_list: Option<String>,
+    /// The manifests, listed inline (older v1 tables).
+    #[serde(default)]
+    manifests: Vec<String>,
+}
+
+/// An entry of a manifest list.
+#[derive(Debug, Deserialize)]
+struct ManifestFile {
+    manifest_path: String,
+    /// `DATA_CONTENT`, or 1 for delete files (absent in format v1).
+    #[serde(default)]
+    content: i32,
+}
+
+/// An entry of a manifest.
+#[derive(Debug, Deserialize)]
+struct ManifestEntry {
+    /// 0: existing, 1: added, `DELETED_STATUS`: deleted.
+    status: i32,
+    data_file: DataFile,
+}
+
+/// The fields of a data file used to read it.
+#[derive(Debug, Deserialize)]
+struct DataFile {
+    /// `DATA_CONTENT`, or 1/2 for position/equality deletes (absent in format v1).
+    #[serde(default)]
+    content: i32,
+    file_path: String,
+    file_format: String,
+}
+
+/// Returns `true` if `path` is an Iceberg table: a directory with a `metadata` folder
+/// holding `*.metadata.json` files, or one of those files.
+pub fn is_iceberg_table(path: &Path) -> bool {
+    if path.is_dir() {
+        return latest_metadata_file(&path.join("metadata")).is_some();
+    }
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(METADATA_SUFFIX))
+}
+
+/// The current metadata file of the `metadata_dir` folder: the version named by
+/// `version-hint.text`, or else the highest version.
+fn latest_metadata_file(metadata_dir: &Path) -> Option<PathBuf> {
+    if let Ok(hint) = std::fs::read_to_string(metadata_dir.join("version-hint.text")) {
+        let version = hint.trim().trim_start_matches('v');
+        let hinted = metadata_dir.join(format!("v{version}{METADATA_SUFFIX}"));
+        if hinted.is_file() {
+            return Some(hinted);
+        }
+    }
+
+    std::fs::read_dir(metadata_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && is_iceberg_table(path))
+        .max_by_key(|path| (metadata_version(path), path.clone()))
+}
+
+/// Version of a metadata file: 3 for `v3.metadata.json` and `00003-<uuid>.metadata.json`.
+fn metadata_version(path: &Path) -> u64 {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    name.trim_start_matches('v')
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect::<String>()
+        .parse()
+        .unwrap_or(0)
+}
+
+/// Resolves `uri`, a path written in the table metadata, to a local path: paths under the
+/// table `location` are taken relative to `table_dir`, `file:` URIs as local paths.
+fn local_path(uri: &str, location: &str, table_dir: &Path) -> PathBuf {
+    let location = location.trim_end_matches('/');
+    if let Some(relative) = uri.strip_prefix(location) {
+        return table_dir.join(relative.trim_start_matches('/'));
+    }
+    let path = uri
+        .strip_prefix("file://")
+        .or_else(|| uri.strip_prefix("file:"))
+        .unwrap_or(uri);
+    PathBuf::from(path)
+}
+
+/// Reads the records of the Avro file `path` (a manifest list or a manifest).
+#[cfg(feature = "iceberg")]
+fn read_avro<T: DeserializeOwned>(path: &Path) -> PolarsViewResult<Vec<T>> {
+    use apache_avro::{Reader, from_value};
+
+    let avro_error = |error: apache_avro::Error| {
+        PolarsViewError::Iceberg(format!("{}: {error}", path.display()))
+    };
+
+    let reader = Reader::new(BufReader::new(File::open(path)?)).map_err(avro_error)?;
+    reader
+        .map(|value| from_value::<T>(&value.map_err(avro_error)?).map_err(avro_error))
+        .collect()
+}
+
+/// Built without the `iceberg` feature: the manifests can't be read.
+#[cfg(not(feature = "iceberg"))]
+fn read_avro<T: DeserializeOwned>(_path: &Path) -> PolarsViewResult<Vec<T>> {
+    Err(PolarsViewError::FeatureDisabled("iceberg"))
+}
+
+/// The Parquet data files of the current snapshot of the Iceberg table at `path`
+/// (a table directory or a metadata file). Empty if the table has no snapshot.
+pub fn iceberg_data_files(path: &Path) -> PolarsViewResult<Vec<PathBuf>> {
+    let metadata_file = if path.is_dir() {
+        latest_metadata_file(&path.join("metadata")).ok_or_else(|| {
+            PolarsViewError::Iceberg(format!("no metadata files in `{}`", path.display()))
+        })?
+    } else {
+        path.to_path_buf()
+    };
+    // `<table>/metadata/<version>.metadata.json`
+    let table_dir = metadata_file
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
+
+    let metadata: TableMetadata =
+        serde_json::from_reader(BufReader::new(File::open(&metadata_file)?))?;
+    let local = |uri: &str| local_path(uri, &metadata.location, table_dir);
+    let deletes_error = || {
+        PolarsViewError::Iceberg(format!(
+            "`{}` has row-level deletes (delete files), which are not supported",
+            path.display()
+        ))
+    };
+
+    let Some(snapshot) = metadata.current_snapshot_id.and_then(|id| {
+        metadata
+            .snapshots
+            .iter()
+            .find(|snapshot| snapshot.snapshot_id == id)
+    }) else {
+        tracing::info!("Iceberg table without snapshots: {}", path.display());
+        return Ok(Vec::new());
+    };
+
+    let manifests: Vec<String> = match &snapshot.manifest_list {
+        Some(manifest_list) => read_avro::<ManifestFile>(&local(manifest_list))?
+            .into_iter()
+            .map(|manifest| match manifest.content {
+                DATA_CONTENT => Ok(manifest.manifest_path),
+                _ => Err(deletes_error()),
+            })
+            .collect::<PolarsViewResult<_>>()?,
+        None => snapshot.manifests.clone(),
+    };
+
+    let mut data_files = Vec::new();
+    for manifest in &manifests {
+        for entry in read_avro::<ManifestEntry>(&local(manifest))? {
+            let data_file = entry.data_file;
+            if entry.status == DELETED_STATUS {
+                continue;
+            }
+            if data_file.content != DATA_CONTENT {
+                return Err(deletes_error());
+            }
+            if !data_file.file_format.eq_ignore_ascii_case("parquet") {
+                return Err(PolarsViewError::Iceberg(format!(
+                    "only Parquet data files are supported, not {}: `{}`",
+                    data_file.file_format, data_file.file_path
+                )));
+            }
+            data_files.push(local(&data_file.file_path));
+        }
+    }
+
+    tracing::debug!(
+        "Iceberg snapshot {} of {}: {} data files",
+        snapshot.snapshot_id,
+        path.display(),
+        data_files.len()
+    );
+    Ok(data_files)
+}
+
+/// Reads the current snapshot of the Iceberg table at `path` (the first `head_rows` rows,
+/// if set). The data files are concatenated by column name (missing columns are null).
+/// This is a blocking operation; call it from `spawn_blocking`.
+pub fn read_iceberg_table(path: &Path, head_rows: Option<usize>) -> PolarsViewResult<DataFrame> {
+    let data_files = iceberg_data_files(path)?;
+    if data_files.is_empty() {
+        return Ok(DataFrame::empty());
+    }
+
+    let scans = data_files
+        .iter()
+        .map(|file| {
+            let file = PlRefPath::try_from_path(file)?;
+            LazyFrame::scan_parquet(file, ScanArgsParquet::default())
+        })
+        .collect::<PolarsResult<Vec<LazyFrame>>>()?;
+
+    let mut lf = concat_lf_diagonal(scans, UnionArgs::default())?;
+    if let Some(rows) = head_rows {
+        lf = lf.limit(rows as IdxSize);
+    }
+    let df = lf.with_streaming(true).collect()?;
+
+    tracing::debug!("Iceberg read complete. Shape: {:?}", df.shape());
+    Ok(df)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test --features iceberg -- --show-output tests_iceberg`
+#[cfg(all(test, feature = "iceberg"))]
+mod tests_iceberg {
+    use super::*;
+    use apache_avro::{Schema, Writer, types::Value};
+
+    /// Writes `records` to the Avro file `path` with the schema `schema`.
+    fn write_avro(path: &Path, schema: &str, records: Vec<Value>) -> PolarsViewResult<()> {
+        let avro_error = |error: apache_avro::Error| PolarsViewError::Iceberg(error.to_string());
+        let schema = Schema::parse_str(schema).map_err(avro_error)?;
+        let mut writer = Writer::new(&schema, Vec::new());
+        for record in records {
+            writer.append(record).map_err(avro_error)?;
+        }
+        std::fs::write(path, writer.into_inner().map_err(avro_error)?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn current_snapshot_is_read() -> PolarsViewResult<()> {
+        // A table copied from `s3://warehouse/db/events` to a local folder.
+        let dir = tempfile::tempdir()?;
+        let table = dir.path().join("events");
+        std::fs::create_dir_all(table.join("data"))?;
+        std::fs::create_dir_all(table.join("metadata"))?;
+        let location = "s3://warehouse/db/events";
+
+        let mut january = df!("id" => &[1i64, 2], "kind" => &["a", "b"])?;
+        let mut february = df!("id" => &[3i64], "amount" => &[9.5])?;
+        ParquetWriter::new(File::create(table.join("data/january.parquet"))?)
+            .finish(&mut january)?;
+        ParquetWriter::new(File::create(table.join("data/february.parquet"))?)
+            .finish(&mut february)?;
+
+        let entry = |status: i32, file: &str| {
+            Value::Record(vec![
+                ("status".into(), Value::Int(status)),
+                (
+                    "data_file".into(),
+                    Value::Record(vec![
+                        ("content".into(), Value::Int(DATA_CONTENT)),
+                        (
+                            "file_path".into(),
+                            Value::String(format!("{location}/{file}")),
+                        ),
+                        ("file_format".into(), Value::String("PARQUET".into())),
+                    ]),
+                ),
+            ])
+        };
+        write_avro(
+            &table.join("metadata/manifest-1.avro"),
+            r#"{"type": "record", "name": "manifest_entry", "fields": [
+                {"name": "status", "type": "int"},
+                {"name": "data_file", "type": {"type": "record", "name": "data_file", "fields": [
+                    {"name": "content", "type": "int"},
+                    {"name": "file_path", "type": "string"},
+                    {"name": "file_format", "type": "string"}
+                ]}}
+            ]}"#,
+            vec![
+                entry(1, "data/january.parquet"),
+                entry(1, "data/february.parquet"),
+                entry(DELETED_STATUS, "data/removed.parquet"),
+            ],
+        )?;
+        write_avro(
+            &table.join("metadata/snap-7.avro"),
+            r#"{"type": "record", "name": "manifest_file", "fields": [
+                {"name": "manifest_path", "type": "string"},
+                {"name": "content", "type": "int"}
+            ]}"#,
+            vec![Value::Record(vec![
+                (
+                    "manifest_path".into(),
+                    Value::String(format!("{location}/metadata/manifest-1.avro")),
+                ),
+                ("content".into(), Value::Int(DATA_CONTENT)),
+            ])],
+        )?;
+
+        let metadata = serde_json::json!({
+            "format-version": 2,
+            "location": location,
+            "current-snapshot-id": 7,
+            "snapshots": [{
+                "snapshot-id": 7,
+                "manifest-list": format!("{location}/metadata/snap-7.avro")
+            }]
+        });
+        std::fs::write(table.join("metadata/v1.metadata.json"), "{}")?;
+        std::fs::write(
+            table.join("metadata/v2.metadata.json"),
+            metadata.to_string(),
+        )?;
+
+        assert!(is_iceberg_table(&table));
+        assert!(!is_iceberg_table(dir.path()));
+        assert_eq!(
+            latest_metadata_file(&table.join("metadata")),
+            Some(table.join("metadata/v2.metadata.json"))
+        );
+
+        let df = read_iceberg_table(&table, None)?;
+        println!("{df}");
+        assert_eq!(df.shape(), (3, 3));
+        assert_eq!(df.column("amount")?.null_count(), 2);
+        assert_eq!(read_iceberg_table(&table, Some(1))?.height(), 1);
+        Ok(())
+    }
+}
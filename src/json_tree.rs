@@ -0,0 +1,249 @@
+//! Collapsible tree view of a JSON value (used by the row inspector for Struct, List
+//! and JSON-string cells), with key/value search and "Copy path".
+
+use crate::PolarsViewResult;
+
+use egui::{CollapsingHeader, Id, RichText, TextEdit, Ui};
+use polars::prelude::*;
+use serde_json::Value;
+
+/// Parses a String cell as JSON, if it looks like a JSON object or array.
+pub fn parse_json_text(text: &str) -> Option<Value> {
+    let text = text.trim();
+    let is_container = (text.starts_with('{') && text.ends_with('}'))
+        || (text.starts_with('[') && text.ends_with(']'));
+
+    if is_container {
+        serde_json::from_str(text).ok()
+    } else {
+        None
+    }
+}
+
+/// Returns the cell at (`row`, `column`) as JSON, for nested (`Struct`, `List`, `Array`)
+/// columns and String cells holding a JSON object/array. Other cells return `None`.
+pub fn cell_to_json(df: &DataFrame, column: &str, row: usize) -> PolarsViewResult<Option<Value>> {
+    let series = df.column(column)?.as_materialized_series();
+
+    match series.dtype() {
+        DataType::Struct(_) | DataType::List(_) | DataType::Array(..) => {
+            // Serialize the single cell with Polars' JSON writer: `[{"column": value}]`.
+            let mut cell = df.select([column])?.slice(row as i64, 1);
+            let mut buffer = Vec::new();
+            JsonWriter::new(&mut buffer)
+                .with_json_format(JsonFormat::Json)
+                .finish(&mut cell)?;
+
+            let mut rows: Value = serde_json::from_slice(&buffer)?;
+            Ok(rows
+                .get_mut(0)
+                .and_then(|object| object.get_mut(column))
+                .map(Value::take))
+        }
+        DataType::String => Ok(series.str()?.get(row).and_then(parse_json_text)),
+        _ => Ok(None),
+    }
+}
+
+/// Path of a child node: `$.key`, `$["a key"]` or `$[0]`.
+pub fn child_path(parent: &str, key: &str, is_index: bool) -> String {
+    let is_identifier = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    if is_index {
+        format!("{parent}[{key}]")
+    } else if is_identifier {
+        format!("{parent}.{key}")
+    } else {
+        format!("{parent}[{}]", Value::from(key))
+    }
+}
+
+/// Text of a scalar value (strings without quotes).
+fn scalar_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Returns `true` if `key` or any key/value below `value` contains `query` (lowercase).
+pub fn subtree_matches(key: &str, value: &Value, query: &str) -> bool {
+    if query.is_empty() || key.to_lowercase().contains(query) {
+        return true;
+    }
+
+    match value {
+        Value::Object(map) => map.iter().any(|(k, v)| subtree_matches(k, v, query)),
+        Value::Array(items) => items.iter().any(|v| subtree_matches("", v, query)),
+        scalar => scalar_text(scalar).to_lowercase().contains(query),
+    }
+}
+
+/// Adds the "Copy path"/"Copy value" context menu to a node.
+fn node_context_menu(response: &egui::Response, path: &str, value: &Value) {
+    response.context_menu(|ui| {
+        if ui.button("Copy path").clicked() {
+            ui.ctx().copy_text(path.to_string());
+            ui.close();
+        }
+        if ui.button("Copy value").clicked() {
+            let text = match value {
+                Value::Object(_) | Value::Array(_) => {
+                    serde_json::to_string_pretty(value).unwrap_or_default()
+                }
+                scalar => scalar_text(scalar),
+            };
+            ui.ctx().copy_text(text);
+            ui.close();
+        }
+    });
+}
+
+/// State of a JSON tree view (the search text).
+#[derive(Debug, Clone, Default)]
+pub struct JsonTree {
+    /// Case-insensitive search on keys and values; non-matching branches are hidden.
+    pub search: String,
+}
+
+impl JsonTree {
+    /// Shows the search box and the tree of `value` (root path `$`).
+    pub fn show(&mut self, ui: &mut Ui, id_salt: impl std::hash::Hash, value: &Value) {
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            ui.add(TextEdit::singleline(&mut self.search).hint_text("Search keys and values"));
+            if ui.button("✖").on_hover_text("Clear search").clicked() {
+                self.search.clear();
+            }
+        });
+
+        let query = self.search.trim().to_lowercase();
+        if !subtree_matches("", value, &query) {
+            ui.label(RichText::new("No matches.").weak());
+            return;
+        }
+
+        render_node(ui, Id::new(id_salt), "$", "$", value, &query);
+    }
+}
+
+/// Renders a node: objects/arrays as collapsing headers, scalars as `key: value` labels.
+///
+/// With a non-empty `query`, only matching branches are shown (expanded); the whole
+/// subtree of a node whose key matches is shown.
+fn render_node(ui: &mut Ui, id: Id, label: &str, path: &str, value: &Value, query: &str) {
+    let query = if label.to_lowercase().contains(query) {
+        "" // Key matches: show everything below it.
+    } else {
+        query
+    };
+
+    let children: Vec<(String, bool, &Value)> = match value {
+        Value::Object(map) => map.iter().map(|(k, v)| (k.clone(), false, v)).collect(),
+        Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(index, item)| (index.to_string(), true, item))
+            .collect(),
+        scalar => {
+            let response = ui
+                .horizontal(|ui| {
+                    ui.label(format!("{label}:"));
+                    ui.label(RichText::new(scalar_text(scalar)).strong())
+                })
+                .response
+                .on_hover_text(path);
+            node_context_menu(&response, path, value);
+            return;
+        }
+    };
+
+    let summary = match value {
+        Value::Object(_) => format!("{label} {{{}}}", children.len()),
+        _ => format!("{label} [{}]", children.len()),
+    };
+
+    let mut header = CollapsingHeader::new(summary).id_salt(id.with(path));
+    if !query.is_empty() {
+        header = header.open(Some(true));
+    }
+
+    let response = header.show(ui, |ui| {
+        for (key, is_index, child) in children {
+            // Array indices are not searched as keys.
+            if subtree_matches(if is_index { "" } else { &key }, child, query) {
+                let child_label = if is_index {
+                    format!("[{key}]")
+                } else {
+                    key.clone()
+                };
+                let path = child_path(path, &key, is_index);
+                render_node(ui, id, &child_label, &path, child, query);
+            }
+        }
+    });
+
+    node_context_menu(&response.header_response.on_hover_text(path), path, value);
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_json_tree`
+#[cfg(test)]
+mod tests_json_tree {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn parses_only_json_containers() {
+        assert_eq!(
+            parse_json_text(r#" {"a": [1, 2]} "#),
+            Some(json!({"a": [1, 2]}))
+        );
+        assert_eq!(parse_json_text("[true]"), Some(json!([true])));
+        assert_eq!(parse_json_text("42"), None);
+        assert_eq!(parse_json_text("{not json}"), None);
+    }
+
+    #[test]
+    fn builds_child_paths() {
+        assert_eq!(child_path("$", "items", false), "$.items");
+        assert_eq!(child_path("$.items", "0", true), "$.items[0]");
+        assert_eq!(child_path("$", "a key", false), r#"$["a key"]"#);
+        assert_eq!(child_path("$", "1st", false), r#"$["1st"]"#);
+    }
+
+    #[test]
+    fn search_matches_keys_and_values() {
+        let value = json!({"customer": {"name": "Ana", "tags": ["vip", "new"]}, "total": 10});
+
+        assert!(subtree_matches("", &value, ""));
+        assert!(subtree_matches("", &value, "vip"));
+        assert!(subtree_matches("", &value, "name"));
+        assert!(!subtree_matches("total", &json!(10), "vip"));
+        assert!(!subtree_matches("", &value, "missing"));
+    }
+
+    #[test]
+    fn struct_cell_to_json() -> PolarsViewResult<()> {
+        let df = df!("id" => &[1, 2], "name" => &["a", "b"])?
+            .lazy()
+            .select([
+                col("id"),
+                as_struct(vec![col("id"), col("name")]).alias("payload"),
+            ])
+            .collect()?;
+
+        assert_eq!(
+            cell_to_json(&df, "payload", 1)?,
+            Some(json!({"id": 2, "name": "b"}))
+        );
+        assert_eq!(cell_to_json(&df, "id", 0)?, None);
+        Ok(())
+    }
+}
@@ -1,13 +1,33 @@
 use crate::{
-    DataContainer, DataFilter, DataFormat, Error, FileInfo, MyStyle, Notification, PolarsViewError,
-    PolarsViewResult, Settings, SortBy, open_file, save, save_as,
+    AppSettings, CancelToken, CellEditAction, CellEdits, CellSelection, ColumnFilters,
+    CurrencyAction, CurrencyDialog, DEFAULT_QUERY, DEFAULT_SAMPLE_ROWS, DEGRADED_MAX_COLUMNS,
+    DataContainer, DataFilter, DataFormat, DatabaseDialog, Degradation, DiffView, DirConfig,
+    DistributionChart, DownloadProgress, DuplicateFinder, EmptyResultAction, Error, FileInfo,
+    FileWatcher, FolderAction, FolderBrowser, FrameWatchdog, GroupExplorer, GroupSummaryCache,
+    LastSession, MapView, MyStyle, Notification, OnboardingAction, ParquetKeyDialog,
+    PolarsViewError, PolarsViewResult, PresetPanel, ProgressReceiver, QueryHistory, RecentFiles,
+    RowInspector, SaveConfirmation, SavedFile, Settings, SortExpression, StatisticsPanel,
+    StatusToast, SyntheticDialog, TableEvents, TableSearch, UrlDialog, ViewState,
+    concatenate_files, download_url, format_bytes, is_iceberg_table, is_private_temp_path,
+    move_column, open_file, open_folder, open_rates_file, open_view_state_file, panic_message,
+    render_empty_result, render_onboarding, render_page_navigation, render_preview_banner,
+    render_sort_hint, render_sort_legend, save, save_as, save_session_file, save_view_state_file,
+    table_key, with_cancel, with_progress, write_database_query, write_sample_dataset,
+    write_session_bundle, write_synthetic_dataset,
 };
 
 use egui::{
     CentralPanel, Color32, Context, FontId, Frame, Grid, Key, KeyboardShortcut, Layout, MenuBar,
     Modifiers, Panel, RichText, ScrollArea, Stroke, ViewportCommand, style::Visuals,
 };
-use std::{future::Future, sync::Arc};
+use futures::FutureExt;
+use std::{
+    future::Future,
+    panic::AssertUnwindSafe,
+    path::PathBuf,
+    sync::{Arc, Mutex, PoisonError},
+    time::Duration,
+};
 use tokio::sync::oneshot::{self, Receiver, error::TryRecvError};
 use tracing::error;
 
@@ -26,12 +46,101 @@ pub type ContainerResult = PolarsViewResult<DataContainer>;
 /// - `+ Send + 'static`: Necessary bounds for futures used across threads (like with `tokio::spawn`).
 pub type DataFuture = Box<dyn Future<Output = ContainerResult> + Unpin + Send + 'static>;
 
+/// Delay before a debounced data task starts (see `run_debounced_data_future`).
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(250);
+
+/// Time given to background tasks to finish when the app closes (see `Drop for PolarsViewApp`).
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Serializes the writes of the recent files, last session and query history (see
+/// `record_history`).
+static RECORD_LOCK: Mutex<()> = Mutex::new(());
+
+/// Result of a "Save"/"Save As..." task: the written path, or `None` if the user cancelled.
+pub type SaveResult = PolarsViewResult<Option<PathBuf>>;
+
 // --- Constants ---
 
 // Define keyboard shortcuts for common actions using `egui`'s `KeyboardShortcut`.
 const CTRL_O: KeyboardShortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::O); // Ctrl+O for Open File
 const CTRL_S: KeyboardShortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::S); // Ctrl+S for Save File
 const CTRL_A: KeyboardShortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::A); // Ctrl+A for Save As...
+const CTRL_T: KeyboardShortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::T); // Ctrl+T for New Tab
+const CTRL_F: KeyboardShortcut = KeyboardShortcut::new(Modifiers::CTRL, Key::F); // Ctrl+F for Search
+const F5: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F5); // F5 for Reload
+
+// --- Tabs ---
+
+/// Per-file state of a tab (see `PolarsViewApp::tabs`).
+///
+/// The *active* tab's state lives in the corresponding `PolarsViewApp` fields, so the
+/// rest of the app works on a single file; switching tabs swaps these fields with the
+/// stored `TabState`s (`PolarsViewApp::swap_tab_state`).
+#[derive(Default)]
+pub struct TabState {
+    pub data_container: Option<Arc<DataContainer>>,
+    pub applied_filter: DataFilter,
+    pub applied_format: DataFormat,
+    pub applied_sort_expression: SortExpression,
+    pub file_info: Option<FileInfo>,
+    pub statistics_panel: StatisticsPanel,
+    pub distribution_chart: DistributionChart,
+    pub duplicate_finder: DuplicateFinder,
+    pub group_explorer: GroupExplorer,
+    pub map_view: MapView,
+    pub currency_dialog: CurrencyDialog,
+    pub row_inspector: RowInspector,
+    pub value_counts_view: ValueCountsView,
+    pub diff_view: DiffView,
+    pub cell_selection: Option<CellSelection>,
+    pub cell_edits: Option<CellEdits>,
+    pub table_search: TableSearch,
+    pub table_page: usize,
+    #[cfg(feature = "format-special")]
+    pub fiscal_validator: crate::FiscalValidator,
+}
+
+impl TabState {
+    /// Tab title: the file name of the loaded data, or "New Tab".
+    fn title(data_container: &Option<Arc<DataContainer>>) -> String {
+        data_container
+            .as_ref()
+            .and_then(|container| container.filter.absolute_path.file_name())
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| "New Tab".to_string())
+    }
+
+    /// Whether background work of a tab's side panels (statistics, distribution,
+    /// duplicate search) or of its group-by and compare windows is still running.
+    fn panels_pending(
+        statistics_panel: &mut StatisticsPanel,
+        distribution_chart: &mut DistributionChart,
+        duplicate_finder: &mut DuplicateFinder,
+        value_counts_view: &mut ValueCountsView,
+        diff_view: &mut DiffView,
+    ) -> bool {
+        // No short-circuit: every panel receives its finished result.
+        let pending = [
+            statistics_panel.is_pending(),
+            distribution_chart.is_pending(),
+            duplicate_finder.is_pending(),
+            value_counts_view.is_pending(),
+            diff_view.is_pending(),
+        ];
+        pending.contains(&true)
+    }
+
+    /// Estimated memory of the tab's data (see `DataContainer::estimated_memory`), cached
+    /// in egui's temporary memory by container address: containers are immutable and
+    /// every update creates a new one.
+    fn memory(ctx: &Context, data_container: &Option<Arc<DataContainer>>) -> Option<usize> {
+        let container = data_container.as_ref()?;
+        let id = egui::Id::new("tab_memory").with(Arc::as_ptr(container) as usize);
+        Some(ctx.data_mut(|data| {
+            *data.get_temp_mut_or_insert_with(id, || container.estimated_memory())
+        }))
+    }
+}
 
 // --- Main Application Struct ---
 
@@ -50,12 +159,127 @@ pub struct PolarsViewApp {
     /// Used by the side panel UI (`render_format`) to detect changes to display settings.
     pub applied_format: DataFormat,
 
+    /// Stores the state of the "Sort" side panel (sort by SQL expression).
+    pub applied_sort_expression: SortExpression,
+
     /// Info extracted from the currently loaded file.
     pub file_info: Option<FileInfo>,
 
     /// Optional Notification window for displaying errors or settings dialogs.
     pub notification: Option<Box<dyn Notification + 'static>>,
 
+    /// Read-only protection: when `true`, "Save" to the original path asks for
+    /// confirmation, listing the operations that would be baked into the file.
+    pub protect_source_file: bool,
+
+    /// When `true`, "Save"/"Save As..." also write an `ExportManifest` sidecar
+    /// (`<file>.manifest.json`) recording the provenance of the exported data.
+    pub write_export_manifest: bool,
+
+    /// When `true`, Json files written by "Save"/"Save As..." are indented
+    /// (see `write_json`); NDJson stays one record per line.
+    pub pretty_json: bool,
+
+    /// When `true`, the exports ("Save", "Save As...", "Export Selected Columns...") write
+    /// the key columns (row index and frozen columns, see `DataContainer::key_columns`)
+    /// first, whatever their order on screen.
+    pub export_keys_first: bool,
+
+    /// When `true`, "Export Session..." includes a sample of the source data
+    /// (see `write_session_bundle`).
+    pub session_include_sample: bool,
+
+    /// When `true`, the file of the active tab is read again when it changes on disk,
+    /// keeping the sort and format (see `file_watch.rs`).
+    pub auto_reload: bool,
+
+    /// Named query/filter/format presets saved under the user config directory
+    /// (shared by all tabs).
+    pub preset_panel: PresetPanel,
+
+    /// Recently opened files (shown by the onboarding screen), saved under the
+    /// user config directory.
+    pub recent_files: RecentFiles,
+
+    /// Shows the first-use hint on header sorting above the table, until it is
+    /// dismissed (remembered in `AppSettings::sort_hint_dismissed`).
+    pub show_sort_hint: bool,
+
+    /// Simplifies the table view when frames are slow (see `frame_watchdog.rs`).
+    pub frame_watchdog: FrameWatchdog,
+
+    /// State of the "New Synthetic Dataset" dialog (opened from the "File" menu).
+    pub synthetic_dialog: SyntheticDialog,
+
+    /// State of the "Connect to Database" dialog (opened from the "File" menu).
+    pub database_dialog: DatabaseDialog,
+    /// "Encrypted Parquet" dialog, asking for the key of an encrypted file.
+    pub parquet_key_dialog: ParquetKeyDialog,
+
+    /// State of the "Open URL" dialog (opened from the "File" menu).
+    pub url_dialog: UrlDialog,
+
+    /// Progress of the remote file being downloaded (shown in the status bar).
+    pub download: Option<Arc<DownloadProgress>>,
+
+    /// The folder listed by the "Folder" side panel section (shared by all tabs).
+    pub folder_browser: FolderBrowser,
+
+    /// State of the "Statistics" side panel section (summary of one column).
+    pub statistics_panel: StatisticsPanel,
+
+    /// State of the "Distribution" side panel section (histogram / top values chart).
+    pub distribution_chart: DistributionChart,
+
+    /// State of the "Duplicates" side panel (fuzzy duplicate detection).
+    pub duplicate_finder: DuplicateFinder,
+
+    /// State of the "Group Explorer" side panel (drill-down row counts by group).
+    pub group_explorer: GroupExplorer,
+
+    /// State of the "Map" side panel and window (latitude/longitude quick-look).
+    pub map_view: MapView,
+
+    /// State of the "Currency Conversion" dialog.
+    pub currency_dialog: CurrencyDialog,
+
+    /// State of the "Row Inspector" window (opened by double-clicking a cell).
+    pub row_inspector: RowInspector,
+
+    /// State of the "Group by" window (value counts, opened from the header context menu).
+    pub value_counts_view: ValueCountsView,
+
+    /// State of the "Compare" window (diff with the source data or another file).
+    pub diff_view: DiffView,
+
+    /// Selected table cells (click, Shift + click, or a whole row/column), copied by Ctrl+C.
+    pub cell_selection: Option<CellSelection>,
+
+    /// Pending cell edits while the edit mode ("File" > "Edit Cells") is on; `None` when
+    /// it is off (see `cell_edit.rs`).
+    pub cell_edits: Option<CellEdits>,
+
+    /// Value counts of the column of the selected cell, for the status bar summary
+    /// (see `group_summary.rs`). Shared by all tabs: recomputed when the data changes.
+    pub group_summary: GroupSummaryCache,
+
+    /// State of the global search bar above the table (Ctrl+F).
+    pub table_search: TableSearch,
+
+    /// Current page (0-based) of the table in paged mode (`DataFormat::paged`).
+    pub table_page: usize,
+
+    /// State of the "Validators" side panel (CPF/CNPJ/NF-e key check digits).
+    #[cfg(feature = "format-special")]
+    pub fiscal_validator: crate::FiscalValidator,
+
+    /// One entry per open tab. The entry at `active_tab` is a placeholder: the active
+    /// tab's state is held by the fields above (see `TabState`).
+    tabs: Vec<TabState>,
+
+    /// Index of the active tab in `tabs`.
+    active_tab: usize,
+
     /// Tokio runtime instance for managing asynchronous operations.
     runtime: tokio::runtime::Runtime,
 
@@ -63,25 +287,129 @@ pub struct PolarsViewApp {
     /// back from async `DataFuture` tasks onto the UI thread.
     pipe: Option<Receiver<ContainerResult>>,
 
-    /// Vector to keep track of active `tokio` task handles. (Mainly for potential future management)
+    /// Receiving end of the channel of a pending "Save"/"Save As..." task (see `check_save_pending`).
+    save_pipe: Option<Receiver<SaveResult>>,
+
+    /// Handles of the spawned data tasks; superseded ones are aborted (see `run_data_future`).
     tasks: Vec<tokio::task::JoinHandle<()>>,
+
+    /// Cancellation flag of the latest data task, set when it is superseded or cancelled,
+    /// so its Polars work on blocking threads stops too (see `progress.rs`).
+    cancel: CancelToken,
+
+    /// Progress reports of the pending data task (see `progress.rs`), shown in the status
+    /// bar with a "Cancel" button (see `cancel_data_task`).
+    progress: Option<ProgressReceiver>,
+
+    /// Watcher of the active tab's file while `auto_reload` is on (see `check_file_changes`).
+    file_watcher: Option<FileWatcher>,
+
+    /// Short message shown in the status bar (e.g., after an automatic reload).
+    status_toast: Option<StatusToast>,
 }
 
 impl Default for PolarsViewApp {
     /// Creates a default `PolarsViewApp` instance. Initializes the runtime and sets initial state.
     fn default() -> Self {
         Self {
-            data_container: None,                  // No data loaded initially.
+            data_container: None,                               // No data loaded initially.
             applied_filter: DataFilter::default(), // Start with default filter settings.
             applied_format: DataFormat::default(), // Start with default format settings.
+            applied_sort_expression: SortExpression::default(), // No sort expression initially.
             file_info: None,                       // No file_info initially.
             notification: None,                    // No notification initially.
+            protect_source_file: true,             // Safety first: confirm overwrites.
+            write_export_manifest: false,          // Sidecar manifests are opt-in.
+            pretty_json: false,                    // Compact Json by default.
+            export_keys_first: false,              // Columns exported in table order.
+            session_include_sample: true,          // Bundles are self-contained by default.
+            auto_reload: false,                    // Files are read once by default.
+            preset_panel: PresetPanel::from_config_dir(), // Presets saved by previous runs.
+            recent_files: RecentFiles::from_config_dir(), // Files opened by previous runs.
+            show_sort_hint: !AppSettings::from_config_dir().sort_hint_dismissed, // Until dismissed.
+            frame_watchdog: FrameWatchdog::default(), // Nothing simplified initially.
+            synthetic_dialog: SyntheticDialog::default(), // Dialog closed initially.
+            database_dialog: DatabaseDialog::default(), // Dialog closed initially.
+            parquet_key_dialog: ParquetKeyDialog::default(), // Dialog closed initially.
+            url_dialog: UrlDialog::default(),      // Dialog closed initially.
+            download: None,                        // No download initially.
+            folder_browser: FolderBrowser::default(), // No folder initially.
+            statistics_panel: StatisticsPanel::default(), // First column selected on render.
+            distribution_chart: DistributionChart::default(), // 20 bins, top 15 values.
+            duplicate_finder: DuplicateFinder::default(), // No duplicate search initially.
+            group_explorer: GroupExplorer::default(), // No grouping columns initially.
+            map_view: MapView::default(),          // Map window closed initially.
+            currency_dialog: CurrencyDialog::default(), // Dialog closed initially.
+            row_inspector: RowInspector::default(), // Window closed initially.
+            value_counts_view: ValueCountsView::default(), // Window closed initially.
+            diff_view: DiffView::default(),        // Window closed initially.
+            cell_selection: None,                  // Nothing selected initially.
+            cell_edits: None,                      // Edit mode off initially.
+            group_summary: GroupSummaryCache::default(), // Nothing counted initially.
+            table_search: TableSearch::default(),  // Search bar closed initially.
+            table_page: 0,                         // First page.
+            #[cfg(feature = "format-special")]
+            fiscal_validator: crate::FiscalValidator::default(), // Column auto-detected.
+            tabs: vec![TabState::default()],       // A single (empty) tab.
+            active_tab: 0,
             runtime: tokio::runtime::Builder::new_multi_thread() // Essential: Use a multi-threaded runtime.
                 .enable_all() // Enable necessary Tokio features (I/O, time, etc.).
                 .build()
                 .expect("Failed to build Tokio runtime"), // Runtime creation is critical.
-            pipe: None,                            // No async operation pending at start.
-            tasks: Vec::new(),                     // No tasks running at start.
+            pipe: None,                     // No async operation pending at start.
+            save_pipe: None,                // No save pending at start.
+            tasks: Vec::new(),              // No tasks running at start.
+            cancel: CancelToken::default(), // No task to cancel at start.
+            progress: None,                 // No progress to report at start.
+            file_watcher: None,             // Auto-reload is off at start.
+            status_toast: None,             // No status message at start.
+        }
+    }
+}
+
+impl Drop for PolarsViewApp {
+    /// Closing the window while a load, sort or save is in flight: a pending save is waited
+    /// for (it writes the user's data), then the data tasks are cancelled and the runtime
+    /// shut down, waiting at most `SHUTDOWN_TIMEOUT` for read-only work that cannot be
+    /// cancelled (e.g., a Polars `collect` on a blocking thread), which is abandoned.
+    ///
+    /// Dropping the runtime instead would wait for such work to finish, so the process
+    /// would hang after the window closed.
+    fn drop(&mut self) {
+        // The final view (sorting, format...) of the last session: `record_history` only
+        // saves it when the file or query changes.
+        if let Some(session) = self
+            .data_container
+            .as_ref()
+            .and_then(|container| LastSession::from_container(container))
+        {
+            let _guard = RECORD_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            session.record();
+        }
+
+        // An unconfirmed save (see `SaveConfirmation`) is dropped with the notification,
+        // which closes its channel.
+        self.notification = None;
+        if let Some(save) = self.save_pipe.take() {
+            tracing::info!("Waiting for the pending save to finish...");
+            if let Ok(Err(error)) = self.runtime.block_on(save) {
+                tracing::error!("Save failed: {error}");
+            }
+        }
+
+        self.cancel.cancel();
+        for handle in self.tasks.drain(..) {
+            handle.abort();
+        }
+        self.pipe = None;
+        self.progress = None;
+
+        // `shutdown_timeout` takes the runtime by value: swap in an idle one (no threads).
+        let idle = tokio::runtime::Builder::new_current_thread().build();
+        if let Ok(idle) = idle {
+            let runtime = std::mem::replace(&mut self.runtime, idle);
+            runtime.shutdown_timeout(SHUTDOWN_TIMEOUT);
+            tracing::debug!("Tokio runtime shut down.");
         }
     }
 }
@@ -92,6 +420,8 @@ impl PolarsViewApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> PolarsViewResult<Self> {
         // Apply custom styles and dark theme (defined via `MyStyle` trait in `traits.rs`).
         cc.egui_ctx.set_style_init(Visuals::dark());
+        // Image loaders of the thumbnail cell renderer (`ThumbnailRenderer`).
+        egui_extras::install_image_loaders(&cc.egui_ctx);
 
         cc.egui_ctx.memory_mut(|mem| {
             mem.data.clear();
@@ -108,6 +438,7 @@ impl PolarsViewApp {
         future: DataFuture,
     ) -> PolarsViewResult<Self> {
         cc.egui_ctx.set_style_init(Visuals::dark()); // Apply style.
+        egui_extras::install_image_loaders(&cc.egui_ctx);
 
         cc.egui_ctx.memory_mut(|mem| {
             mem.data.clear();
@@ -115,7 +446,7 @@ impl PolarsViewApp {
 
         let mut app: Self = Default::default(); // Create default app instance.
         // Initiate the asynchronous data loading process.
-        app.run_data_future(future, &cc.egui_ctx);
+        app.run_data_future("Load file", future, &cc.egui_ctx);
         Ok(app) // Return the app (data loading will happen in the background).
     }
 
@@ -131,13 +462,81 @@ impl PolarsViewApp {
         }
     }
 
+    /// Checks the `oneshot` channel (`save_pipe`) for the result of a pending save task.
+    /// Success shows a `SavedFile` toast; failure shows the error to the user.
+    fn check_save_pending(&mut self) {
+        let Some(mut output) = self.save_pipe.take() else {
+            return;
+        };
+
+        match output.try_recv() {
+            Ok(Ok(Some(path))) => {
+                tracing::info!("File saved: {}", path.display());
+                self.notification = Some(Box::new(SavedFile::new(path)));
+            }
+            Ok(Ok(None)) => tracing::debug!("Save cancelled by user."),
+            Ok(Err(err)) => {
+                error!("Failed to save file: {}", err);
+                self.notification = Some(Box::new(Error {
+                    message: format!("Failed to save file: {err}"),
+                }));
+            }
+            Err(TryRecvError::Empty) => self.save_pipe = Some(output),
+            // Sender dropped without a result (e.g., the overwrite confirmation was cancelled).
+            Err(TryRecvError::Closed) => tracing::debug!("Save task ended without a result."),
+        }
+    }
+
+    /// Saves the recent files, the last session and the query history after a data
+    /// update (errors are logged).
+    ///
+    /// Only a new file, table or query is recorded (not a sort or a format change; the
+    /// final view is saved at exit, see `Drop`), and the files are written on a blocking
+    /// thread of the runtime. Private temporary files (see `is_private_temp_path`) are
+    /// not remembered.
+    fn record_history(&mut self, container: &DataContainer) {
+        let filter = &container.filter;
+        let key = table_key(filter);
+        let unchanged = self.data_container.as_ref().is_some_and(|previous| {
+            table_key(&previous.filter) == key
+                && previous.filter.query == filter.query
+                && previous.filter.query_history == filter.query_history
+        });
+        if unchanged {
+            return;
+        }
+
+        let path = &filter.absolute_path;
+        let remembered = !path.as_os_str().is_empty() && !is_private_temp_path(path);
+        let recent_files =
+            (remembered && self.recent_files.push(path)).then(|| self.recent_files.clone());
+        let session = LastSession::from_container(container);
+        let records = if remembered {
+            filter.query_history.clone()
+        } else {
+            Vec::new()
+        };
+
+        self.runtime.spawn_blocking(move || {
+            // Two updates in a row must not interleave their read-modify-write of the files.
+            let _guard = RECORD_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+            if let Some(recent_files) = recent_files {
+                recent_files.save();
+            }
+            if let Some(session) = session {
+                session.record();
+            }
+            QueryHistory::record(&key, &records);
+        });
+    }
+
     /// Checks the `oneshot` channel (`pipe`) for the result of a pending async data operation.
     /// This function is called repeatedly in the `logic` loop.
     ///
     /// Returns:
     /// - `true`: If an operation is still pending (channel was empty).
     /// - `false`: If a result was received (success or error) or the channel was closed.
-    fn check_data_pending(&mut self) -> bool {
+    fn check_data_pending(&mut self, ctx: &Context) -> bool {
         // Take the receiver out of the Option to check it.
         let Some(mut output) = self.pipe.take() else {
             return false; // No receiver means no operation is pending.
@@ -153,21 +552,36 @@ impl PolarsViewApp {
                         // A new `DataContainer` was successfully produced.
                         // Update the application state:
 
-                        // 1. Update `applied_filter` to match the filter *used* in the new container.
-                        //    This ensures the UI reflects the state of the currently displayed data.
-                        self.applied_filter = container.filter.as_ref().clone();
-
-                        // 2. Update `applied_format` similarly. Crucial for changes like `expand_cols`.
-                        self.applied_format = container.format.as_ref().clone();
+                        // 1-2. Update `applied_filter` and `applied_format` to match the
+                        //      settings *used* in the new container.
+                        self.sync_applied_settings(&container);
+
+                        // 3. Regenerate file_info based on the new container,
+                        //    keeping the source file checksum if still valid.
+                        let previous = self.file_info.take();
+                        self.file_info = FileInfo::from_container(&container).map(|info| {
+                            info.with_checksum(
+                                previous,
+                                &container.filter.absolute_path,
+                                self.runtime.handle(),
+                                ctx,
+                            )
+                        });
 
-                        // 3. Regenerate file_info based on the new container.
-                        self.file_info = FileInfo::from_container(&container);
+                        // 4. Remember the file for the onboarding screen ("Recent files"),
+                        //    the session and the query history.
+                        self.record_history(&container);
 
-                        // 4. Store the new `DataContainer`, wrapped in `Arc`.
+                        // 5. Store the new `DataContainer`, wrapped in `Arc`.
                         self.data_container = Some(Arc::new(container));
 
                         false // Indicate loading/update is complete.
                     }
+                    // --- Encrypted Parquet File: Ask for the Key ---
+                    Err(PolarsViewError::ParquetKeyRequired { path, reason }) => {
+                        self.parquet_key_dialog.ask(path, Some(reason));
+                        false
+                    }
                     // --- Async Operation Failed ---
                     Err(err) => {
                         // The async task returned an error.
@@ -206,13 +620,56 @@ impl PolarsViewApp {
         }
     }
 
+    /// Whether there are pending cell edits, which `task` would lose: they are keyed by the
+    /// rows of the displayed data, which the task replaces, so they must be applied (or
+    /// discarded) first. If so, the error is shown, and the caller must neither start the
+    /// task nor change any state (e.g., `applied_filter`) for it.
+    fn edits_pending(&mut self, task: &str) -> bool {
+        let Some(edits) = self.cell_edits.as_ref().filter(|edits| !edits.is_empty()) else {
+            return false;
+        };
+        tracing::debug!(
+            "\"{task}\" not started: {} pending cell edits.",
+            edits.len()
+        );
+        self.notification = Some(Box::new(Error {
+            message: format!(
+                "There are {} pending cell edits: \"Apply\" or \"Discard\" them first.",
+                edits.len()
+            ),
+        }));
+        true
+    }
+
     /// Spawns a `DataFuture` onto the shared `tokio` runtime.
     /// Sets up the `oneshot` channel to receive the result.
+    /// `task`: Name of the operation, shown if it panics (e.g., "Sort").
     /// `future`: The async operation (boxed Future) to execute.
     /// `ctx`: The `egui::Context` used to request repaints from the background task.
-    fn run_data_future(&mut self, future: DataFuture, ctx: &Context) {
-        // Basic cleanup: remove completed task handles (optional but good practice).
-        self.tasks.retain(|task| !task.is_finished());
+    ///
+    /// A panic in `future` is caught and reported as `PolarsViewError::TaskPanicked`.
+    fn run_data_future(&mut self, task: &str, future: DataFuture, ctx: &Context) {
+        // Callers check first, before changing any state for the task; this is the backstop.
+        if self.edits_pending(task) {
+            return;
+        }
+
+        // Only the latest request completes: a task still running is superseded (its
+        // result would be discarded with the old `pipe`), so it is cancelled. Aborting
+        // doesn't stop its blocking Polars work, which checks the flag instead.
+        self.cancel.cancel();
+        self.cancel = CancelToken::default();
+        for handle in self.tasks.drain(..) {
+            if !handle.is_finished() {
+                tracing::debug!("Cancelling a superseded data task.");
+                handle.abort();
+            }
+        }
+        self.download = None; // A cancelled download no longer shows its progress.
+
+        // The stages of the task are reported through its own progress channel.
+        let (future, progress) = with_progress(with_cancel(future, self.cancel.clone()));
+        self.progress = Some(progress);
 
         // Create the single-use channel for sending the result back to the UI thread.
         let (tx, rx) = oneshot::channel::<PolarsViewResult<DataContainer>>();
@@ -221,12 +678,21 @@ impl PolarsViewApp {
 
         // Clone the egui context so the background task can request UI repaints.
         let ctx_clone = ctx.clone();
+        let task = task.to_string();
 
         // Spawn the future onto the application's Tokio runtime.
         // The task runs in the background, managed by the runtime's thread pool.
         let handle = self.runtime.spawn(async move {
             // Await the completion of the provided async operation.
-            let data = future.await;
+            // A panic becomes an error, instead of silently closing the channel.
+            let data = AssertUnwindSafe(future)
+                .catch_unwind()
+                .await
+                .unwrap_or_else(|payload| {
+                    let message = panic_message(payload.as_ref());
+                    error!("Task \"{task}\" panicked: {message}");
+                    Err(PolarsViewError::TaskPanicked { task, message })
+                });
 
             // Send the result (Ok or Err) back through the oneshot channel.
             // Ignore the result of `send`; if it fails, the receiver (`pipe`) was dropped,
@@ -246,22 +712,169 @@ impl PolarsViewApp {
         self.tasks.push(handle);
     }
 
+    /// Sets the panel settings (`applied_filter`, `applied_format` and the sort expression)
+    /// to those of `container`, so the UI reflects the state of the displayed data (crucial
+    /// for changes like `expand_cols`).
+    fn sync_applied_settings(&mut self, container: &DataContainer) {
+        self.applied_filter = container.filter.as_ref().clone();
+
+        self.applied_format = container.format.as_ref().clone();
+
+        // Keep the "Sort" panel in sync (e.g., after importing a view).
+        if let Some(sort_expression) = &container.sort_expression {
+            self.applied_sort_expression = sort_expression.clone();
+        }
+    }
+
+    /// Cancels the pending data task ("Cancel" in the status bar): the task is aborted and
+    /// the displayed data stays as it was, and so do the panels: the settings changed for
+    /// the task (e.g., the path and query of a load) are set back to those of the data.
+    ///
+    /// Polars work already running on a blocking thread (e.g., a `collect`) can't be
+    /// interrupted: it runs to completion in the background, its result is discarded, and
+    /// the task stops there (see `CancelToken`); work not started yet is skipped.
+    fn cancel_data_task(&mut self) {
+        self.cancel.cancel();
+        for handle in self.tasks.drain(..) {
+            handle.abort();
+        }
+        self.pipe = None;
+        self.progress = None;
+        self.download = None;
+        if let Some(container) = self.data_container.clone() {
+            self.sync_applied_settings(&container);
+        }
+        self.status_toast = Some(StatusToast::new("Cancelled"));
+        tracing::info!("Data task cancelled by the user.");
+    }
+
+    /// Like `run_data_future`, but starts the work after `DEBOUNCE_DELAY`: while a value is
+    /// being dragged or typed, each change cancels the previous one, so only the last runs.
+    fn run_debounced_data_future(&mut self, task: &str, future: DataFuture, ctx: &Context) {
+        let future = async move {
+            tokio::time::sleep(DEBOUNCE_DELAY).await;
+            future.await
+        };
+        self.run_data_future(task, Box::new(Box::pin(future)), ctx);
+    }
+
+    /// Applies `self.applied_format` to the displayed data (`task` names it in the logs).
+    ///
+    /// Pure view changes replace the container at once (see `DataContainer::with_format`).
+    /// Changes that need data work, or that would race a pending data task (whose result
+    /// carries its own format), run `DataContainer::update_format` in the background,
+    /// debounced if `debounce` (e.g., for `DragValue` edits).
+    fn apply_format(&mut self, task: &str, debounce: bool, ctx: &Context) {
+        let Some(data_container) = &self.data_container else {
+            return;
+        };
+        let format = self.applied_format.clone();
+
+        if self.pipe.is_none()
+            && let Some(container) = data_container.with_format(format.clone())
+        {
+            tracing::debug!("{task}: view-only format change, applied at once.");
+            self.data_container = Some(Arc::new(container));
+            return;
+        }
+
+        let future = data_container.as_ref().clone().update_format(format);
+        if debounce {
+            self.run_debounced_data_future(task, Box::new(Box::pin(future)), ctx);
+        } else {
+            self.run_data_future(task, Box::new(Box::pin(future)), ctx);
+        }
+    }
+
+    /// Feeds the CPU time of the last frame to the watchdog and applies the simplification
+    /// it returns (see `FrameWatchdog::record`), unless disabled in Settings.
+    /// Frames of pending data tasks are not counted.
+    fn check_frame_time(&mut self, frame_seconds: f32, ctx: &Context) {
+        let Some(data_container) = &self.data_container else {
+            return;
+        };
+        if self.pipe.is_some() {
+            return;
+        }
+
+        let visible_columns: Vec<String> = data_container
+            .visible_columns()
+            .map(|column| column.name().to_string())
+            .collect();
+        let Some(step) = self.frame_watchdog.record(
+            frame_seconds,
+            self.applied_format.auto_col_width,
+            visible_columns.len(),
+        ) else {
+            return;
+        };
+
+        // Read only now: slow frames are rare, and the setting may have just changed.
+        if AppSettings::from_config_dir().disable_frame_watchdog {
+            self.frame_watchdog.off = true;
+            self.frame_watchdog.reset();
+            return;
+        }
+
+        match step {
+            Degradation::FixedWidths => {
+                self.applied_format.auto_col_width = false;
+            }
+            Degradation::FewerColumns => {
+                let hidden = visible_columns.into_iter().skip(DEGRADED_MAX_COLUMNS);
+                for column in hidden {
+                    self.applied_format.hidden_columns.insert(column.clone());
+                    self.frame_watchdog.hidden_columns.push(column);
+                }
+            }
+        }
+        self.apply_format("Simplify slow table", false, ctx);
+    }
+
+    /// Undoes the simplifications of the watchdog ("Restore full view"), which stays off
+    /// for the session.
+    fn restore_full_view(&mut self, ctx: &Context) {
+        if self
+            .frame_watchdog
+            .applied
+            .contains(&Degradation::FixedWidths)
+        {
+            self.applied_format.auto_col_width = true;
+        }
+        for column in &self.frame_watchdog.hidden_columns {
+            self.applied_format.hidden_columns.remove(column);
+        }
+        self.frame_watchdog.reset();
+        self.frame_watchdog.off = true;
+        self.apply_format("Restore full view", false, ctx);
+    }
+
     // --- Event Handlers ---
 
     /// Centralized logic to initiate data loading from a filesystem path.
     fn load_file_from_path(&mut self, path: std::path::PathBuf, ctx: &Context) {
+        if self.edits_pending("Load file") {
+            return;
+        }
         let path = std::fs::canonicalize(&path).unwrap_or(path);
 
         tracing::info!(target: "polars_view", "Loading path: {}", path.display());
+        self.table_page = 0; // A new file starts on the first page.
+        self.frame_watchdog.reset();
 
         self.applied_filter
             .set_path(&path)
-            .map(|_| {
+            .and_then(|_| DirConfig::for_file(&path))
+            .map(|config| {
+                // Conventions of the file's directory (`.polars-view.toml`).
+                if let Some(config) = config {
+                    config.apply_to(&mut self.applied_filter);
+                }
                 self.applied_filter.read_data_from_file = true;
                 let future = DataContainer::default()
                     .load_data(self.applied_filter.clone(), self.applied_format.clone());
 
-                self.run_data_future(Box::new(Box::pin(future)), ctx);
+                self.run_data_future("Load file", Box::new(Box::pin(future)), ctx);
 
                 self.notification = None;
             })
@@ -275,6 +888,37 @@ impl PolarsViewApp {
         ctx.request_repaint();
     }
 
+    /// Downloads a remote file (http, https or S3) in the background, showing the progress
+    /// in the status bar, then loads it like a local file.
+    pub fn load_url(&mut self, url: String, ctx: &Context) {
+        if self.edits_pending("Download") {
+            return;
+        }
+        tracing::info!(target: "polars_view", "Loading URL: {url}");
+        self.table_page = 0;
+
+        let progress = Arc::new(DownloadProgress::new(&url));
+        let task_progress = progress.clone();
+
+        let filter = self.applied_filter.clone();
+        let format = self.applied_format.clone();
+        let future = async move {
+            let temp_file = download_url(&url, &task_progress).await?;
+            DataContainer::default()
+                .load_temp_file(temp_file, filter, format)
+                .await
+        };
+        self.run_data_future("Download", Box::new(Box::pin(future)), ctx);
+        self.download = Some(progress);
+    }
+
+    /// Shows the "Open URL" dialog; on "Open", downloads and loads the file (see `load_url`).
+    fn handle_url_dialog(&mut self, ctx: &Context) {
+        if let Some(url) = self.url_dialog.show(ctx) {
+            self.load_url(url, ctx);
+        }
+    }
+
     /// Handles the "Open File" action via native dialog.
     fn handle_open_file(&mut self, ctx: &Context) {
         match self.runtime.block_on(open_file()) {
@@ -290,7 +934,190 @@ impl PolarsViewApp {
         }
     }
 
+    /// Handles the "Reload" action (F5): reads the file of the active tab again with the
+    /// applied load options, SQL query and format, keeping the sort and column filters
+    /// (see `DataContainer::reload`). Useful when an external pipeline regenerates the file.
+    fn handle_reload(&mut self, ctx: &Context) {
+        let Some(container) = &self.data_container else {
+            return;
+        };
+        let future = container
+            .as_ref()
+            .clone()
+            .reload(self.applied_filter.clone(), self.applied_format.clone());
+        self.run_data_future("Reload", Box::new(Box::pin(future)), ctx);
+    }
+
+    /// Handles the "Open Folder" action via native dialog: lists the folder's data files
+    /// in the "Folder" side panel section, or opens it if it is an Iceberg table.
+    fn handle_open_folder(&mut self, ctx: &Context) {
+        match self.runtime.block_on(open_folder()) {
+            Ok(folder) if is_iceberg_table(&folder) => self.load_file_from_path(folder, ctx),
+            Ok(folder) => self.folder_browser.open(&folder),
+            Err(PolarsViewError::FileNotFound(_)) => {
+                tracing::debug!("Folder dialog cancelled by user.");
+            }
+            Err(e) => {
+                self.notification = Some(Box::new(Error {
+                    message: e.to_string(),
+                }));
+            }
+        }
+    }
+
+    /// Handles an action of the "Folder" side panel section: opens a file of the folder,
+    /// or concatenates the checked files (read with the current read options) and opens
+    /// the result.
+    fn handle_folder_action(&mut self, action: FolderAction, ctx: &Context) {
+        match action {
+            FolderAction::Open(path) => self.load_file_from_path(path, ctx),
+            FolderAction::Concatenate(paths, source_column) => {
+                if self.edits_pending("Concatenate files") {
+                    return;
+                }
+                self.table_page = 0;
+                let filter = self.applied_filter.clone();
+                let format = self.applied_format.clone();
+                let future = async move {
+                    let temp_file = concatenate_files(&paths, &filter, source_column).await?;
+                    DataContainer::default()
+                        .load_temp_file(temp_file, filter, format)
+                        .await
+                };
+                self.run_data_future("Concatenate files", Box::new(Box::pin(future)), ctx);
+            }
+        }
+    }
+
+    // --- Tab Management ---
+
+    /// Auto-reload: keeps `file_watcher` on the file of the active tab while `auto_reload`
+    /// is on, and reads the file again (see `DataContainer::reload`) once it changed on disk.
+    ///
+    /// If the file cannot be watched, `auto_reload` is turned off and the error is shown.
+    fn check_file_changes(&mut self, ctx: &Context) {
+        let path = self
+            .data_container
+            .as_ref()
+            .filter(|_| self.auto_reload)
+            .map(|container| container.filter.absolute_path.clone());
+
+        // (Re)start or stop the watch to follow the active file.
+        if self.file_watcher.as_ref().map(FileWatcher::path) != path.as_deref() {
+            self.file_watcher = None;
+            if let Some(path) = path {
+                match FileWatcher::new(&path, ctx) {
+                    Ok(watcher) => self.file_watcher = Some(watcher),
+                    Err(err) => {
+                        error!("Failed to watch '{}': {}", path.display(), err);
+                        self.auto_reload = false;
+                        self.notification = Some(Box::new(Error {
+                            message: format!("Auto-reload disabled: {err}"),
+                        }));
+                    }
+                }
+            }
+        }
+
+        // A pending operation (e.g., the previous reload) finishes first.
+        if self.pipe.is_some() {
+            return;
+        }
+
+        let changed = self
+            .file_watcher
+            .as_mut()
+            .is_some_and(|watcher| watcher.poll(ctx));
+        if changed && let Some(container) = &self.data_container {
+            tracing::info!(
+                "File changed on disk: {}",
+                container.filter.absolute_path.display()
+            );
+            let future = container
+                .as_ref()
+                .clone()
+                .reload(self.applied_filter.clone(), self.applied_format.clone());
+            self.run_data_future("Reload changed file", Box::new(Box::pin(future)), ctx);
+            self.status_toast = Some(StatusToast::new("⟳ File changed on disk: reloaded"));
+        }
+    }
+
+    /// Swaps the active state (the per-file fields of `self`) with `self.tabs[index]`.
+    fn swap_tab_state(&mut self, index: usize) {
+        let tab = &mut self.tabs[index];
+        std::mem::swap(&mut self.data_container, &mut tab.data_container);
+        std::mem::swap(&mut self.applied_filter, &mut tab.applied_filter);
+        std::mem::swap(&mut self.applied_format, &mut tab.applied_format);
+        std::mem::swap(
+            &mut self.applied_sort_expression,
+            &mut tab.applied_sort_expression,
+        );
+        std::mem::swap(&mut self.file_info, &mut tab.file_info);
+        std::mem::swap(&mut self.statistics_panel, &mut tab.statistics_panel);
+        std::mem::swap(&mut self.distribution_chart, &mut tab.distribution_chart);
+        std::mem::swap(&mut self.duplicate_finder, &mut tab.duplicate_finder);
+        std::mem::swap(&mut self.group_explorer, &mut tab.group_explorer);
+        std::mem::swap(&mut self.map_view, &mut tab.map_view);
+        std::mem::swap(&mut self.currency_dialog, &mut tab.currency_dialog);
+        std::mem::swap(&mut self.row_inspector, &mut tab.row_inspector);
+        std::mem::swap(&mut self.value_counts_view, &mut tab.value_counts_view);
+        std::mem::swap(&mut self.diff_view, &mut tab.diff_view);
+        std::mem::swap(&mut self.cell_selection, &mut tab.cell_selection);
+        std::mem::swap(&mut self.cell_edits, &mut tab.cell_edits);
+        std::mem::swap(&mut self.table_search, &mut tab.table_search);
+        std::mem::swap(&mut self.table_page, &mut tab.table_page);
+        #[cfg(feature = "format-special")]
+        std::mem::swap(&mut self.fiscal_validator, &mut tab.fiscal_validator);
+    }
+
+    /// Whether a data update (`pipe`) or a save (`save_pipe`) of the current tab is pending.
+    fn task_pending(&self) -> bool {
+        self.pipe.is_some() || self.save_pipe.is_some()
+    }
+
+    /// Activates the tab at `index`, storing the state of the current one.
+    ///
+    /// Ignored while an async operation is pending, since its result belongs to the current tab.
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() || self.task_pending() {
+            return;
+        }
+        self.swap_tab_state(self.active_tab); // Store the current tab.
+        self.active_tab = index;
+        self.swap_tab_state(index); // Restore the selected tab.
+    }
+
+    /// Opens a new, empty tab and activates it.
+    fn new_tab(&mut self) {
+        if self.task_pending() {
+            return;
+        }
+        self.tabs.push(TabState::default());
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Closes the tab at `index` (the last remaining tab cannot be closed).
+    fn close_tab(&mut self, index: usize) {
+        if self.tabs.len() <= 1 || index >= self.tabs.len() || self.task_pending() {
+            return;
+        }
+
+        if index == self.active_tab {
+            // Activate a neighbor first, so the closed tab's state is in `tabs[index]`.
+            self.switch_tab(if index > 0 { index - 1 } else { 1 });
+        }
+
+        self.tabs.remove(index);
+        if index < self.active_tab {
+            self.active_tab -= 1;
+        }
+    }
+
     /// Detects and processes files dropped onto the application window.
+    /// A dropped file opens in a new tab if the current tab already shows data;
+    /// a dropped folder is listed in the "Folder" side panel section.
+    /// A file dropped while a load or save is pending is refused with a notification,
+    /// since the new tab can't be opened until the task finishes.
     fn handle_dropped_files(&mut self, ctx: &Context) {
         // Collect the first valid path if a drop occurred
         let dropped_path = ctx.input(|i| i.raw.dropped_files.iter().find_map(|f| f.path.clone()));
@@ -301,6 +1128,26 @@ impl PolarsViewApp {
             // Log with tracing (standard Rust idiomatic way)
             tracing::info!(target: "polars_view", "File dropped: {}", path.display());
 
+            // Iceberg table directories are opened like files.
+            if path.is_dir() && !is_iceberg_table(&path) {
+                self.folder_browser.open(&path);
+                return;
+            }
+
+            if self.task_pending() {
+                self.notification = Some(Box::new(Error {
+                    message: format!(
+                        "'{}' was not opened: wait for the current operation to finish, \
+                        then drop the file again.",
+                        path.display()
+                    ),
+                }));
+                return;
+            }
+
+            if self.data_container.is_some() {
+                self.new_tab();
+            }
             self.load_file_from_path(path, ctx);
 
             // Request repaint ensures the UI updates to show loading state immediately
@@ -323,59 +1170,464 @@ impl PolarsViewApp {
                 // Save As...
                 self.handle_save_as(ctx);
             }
+            if i.consume_shortcut(&CTRL_T) {
+                // New Tab
+                self.new_tab();
+            }
+            if i.consume_shortcut(&CTRL_F) {
+                // Search the table
+                self.table_search.open();
+            }
+            if i.consume_shortcut(&F5) {
+                // Reload the file
+                self.handle_reload(ctx);
+            }
+        });
+
+        // Ctrl+C arrives as a `Copy` event; text fields with focus handle it themselves.
+        let copy = ctx.input(|i| {
+            i.events
+                .iter()
+                .any(|event| matches!(event, egui::Event::Copy))
         });
+        if copy && ctx.memory(|memory| memory.focused().is_none()) {
+            self.copy_cell_selection(ctx);
+        }
+    }
+
+    /// Copies the selected table cells to the clipboard as TSV (pastes into spreadsheets).
+    fn copy_cell_selection(&mut self, ctx: &Context) {
+        let (Some(container), Some(selection)) = (&self.data_container, &self.cell_selection)
+        else {
+            return;
+        };
+
+        match container.selection_to_text(selection, '\t') {
+            Ok(text) => ctx.copy_text(text),
+            Err(error) => {
+                self.notification = Some(Box::new(Error {
+                    message: format!("Failed to copy the selection: {error}"),
+                }));
+            }
+        }
     }
 
     /// Handles the "Save" action (Ctrl+S). Saves to the *original* file path.
+    ///
+    /// With read-only protection enabled (`protect_source_file`), a `SaveConfirmation`
+    /// is shown instead, and the save runs only if the user confirms.
     fn handle_save_file(&mut self, ctx: &Context) {
+        // The pending cell edits are saved too.
+        if !self.commit_cell_edits() {
+            return;
+        }
         // Only proceed if data is loaded.
-        if let Some(container) = &self.data_container {
-            // Clone the Arc (cheap) to pass to the async task.
-            let container_clone = container.clone();
-            // Clone context for repaint request within the task.
-            let ctx_clone = ctx.clone();
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        // Clone the Arc (cheap) to pass to the async task.
+        let container_clone = match container
+            .check_source_saveable()
+            .and_then(|()| self.export_container(container))
+        {
+            Ok(container) => container,
+            Err(err) => {
+                self.notification = Some(Box::new(Error {
+                    message: err.to_string(),
+                }));
+                return;
+            }
+        };
+        // Clone context for repaint request within the task.
+        let ctx_clone = ctx.clone();
+        let runtime = self.runtime.handle().clone();
+        let write_manifest = self.write_export_manifest;
+        let pretty_json = self.pretty_json;
+
+        // The result is sent back to the UI thread (see `check_save_pending`).
+        let (tx, rx) = oneshot::channel::<SaveResult>();
+        self.save_pipe = Some(rx);
+
+        let save_task = move || {
             // Spawn the save operation onto the runtime to avoid blocking the UI.
-            self.runtime.spawn(async move {
-                if let Err(err) = save(container_clone, ctx_clone).await {
-                    // Log error if saving fails. Error notification could also be added here
-                    // via a channel back to the main thread if more user feedback is desired.
-                    error!("Failed to save file: {}", err);
-                    // TODO: Notify user of save failure via channel?
+            runtime.spawn(async move {
+                let result = save(
+                    container_clone,
+                    ctx_clone.clone(),
+                    write_manifest,
+                    pretty_json,
+                )
+                .await
+                .map(Some);
+                if tx.send(result).is_err() {
+                    error!("Receiver dropped before the save result could be sent.");
                 }
-                // Note: `save` itself now requests repaint upon completion/error.
+                ctx_clone.request_repaint();
             });
+        };
+
+        if self.protect_source_file {
+            self.notification = Some(Box::new(SaveConfirmation {
+                path: container.filter.absolute_path.clone(),
+                operations: container.applied_operations(),
+                on_confirm: Some(Box::new(save_task)),
+            }));
+        } else {
+            save_task();
         }
     }
 
     /// Handles the "Save As..." action (Ctrl+A). Prompts user for a new location/format.
     fn handle_save_as(&mut self, ctx: &Context) {
+        // The pending cell edits are saved too.
+        if !self.commit_cell_edits() {
+            return;
+        }
         // Only proceed if data is loaded.
-        if let Some(container) = &self.data_container {
-            // Clone Arc and context for the async task.
-            let container_clone = container.clone();
-            let ctx_clone = ctx.clone();
-            // Spawn the save_as operation onto the runtime.
-            self.runtime.spawn(async move {
-                if let Err(err) = save_as(container_clone, ctx_clone).await {
-                    // Log error if saving fails. Similar notification strategy as `handle_save_file` applies.
-                    error!("Failed to save file using 'Save As': {}", err);
-                }
-                // Note: `save_as` itself now requests repaint upon completion/error.
-            });
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        match self.export_container(container) {
+            Ok(container) => self.spawn_save_as(container, ctx),
+            Err(err) => {
+                self.notification = Some(Box::new(Error {
+                    message: err.to_string(),
+                }));
+            }
         }
     }
 
-    // --- UI Rendering Methods ---
+    /// The container to export: `container` with its columns in display order
+    /// (see `DataFormat::column_order`), and the key columns first if `export_keys_first` is set.
+    /// Fails if `container` doesn't hold all the rows (see `DataContainer::check_exportable`).
+    fn export_container(
+        &self,
+        container: &Arc<DataContainer>,
+    ) -> PolarsViewResult<Arc<DataContainer>> {
+        container.check_exportable()?;
+        let container = container.with_column_order()?;
+        if !self.export_keys_first {
+            return Ok(Arc::new(container));
+        }
+        Ok(Arc::new(container.with_key_columns_first()?))
+    }
 
-    /// Renders the top menu bar (`Panel::top`).
-    fn render_menu_bar(&mut self, ui: &mut egui::Ui) {
-        MenuBar::new().ui(ui, |ui| {
-            ui.horizontal(|ui| {
-                self.render_file_menu(ui); // "File" menu
-                self.render_help_menu(ui); // "Help" menu
-                // Add space and theme switch aligned to the right.
-                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
-                    self.render_theme(ui);
+    /// Handles "Export Selected Columns...": "Save As..." restricted to the columns
+    /// checked in the "Columns" panel (see `DataFormat::hidden_columns`).
+    fn handle_export_selected_columns(&mut self, ctx: &Context) {
+        if !self.commit_cell_edits() {
+            return;
+        }
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        match container
+            .visible_columns_projection()
+            .and_then(|projection| self.export_container(&Arc::new(projection)))
+        {
+            Ok(projection) => self.spawn_save_as(projection, ctx),
+            Err(err) => {
+                self.notification = Some(Box::new(Error {
+                    message: err.to_string(),
+                }));
+            }
+        }
+    }
+
+    /// Turns the edit mode ("File" > "Edit Cells") on or off; leaving it applies the
+    /// pending edits (and stays on if they don't convert).
+    fn set_edit_mode(&mut self, on: bool) {
+        if on {
+            self.cell_edits.get_or_insert_default();
+        } else if self.commit_cell_edits() {
+            self.cell_edits = None;
+        }
+    }
+
+    /// Applies the pending cell edits to the displayed data (see `DataContainer::with_cell_edits`)
+    /// and clears them.
+    ///
+    /// Returns `false` if a typed value doesn't convert to its column type: the error is
+    /// shown, and the edits stay pending to be corrected.
+    fn commit_cell_edits(&mut self) -> bool {
+        let (Some(container), Some(edits)) = (&self.data_container, &mut self.cell_edits) else {
+            return true;
+        };
+        if edits.is_empty() {
+            return true;
+        }
+
+        match container.with_cell_edits(edits) {
+            Ok(container) => {
+                tracing::info!("{} cell edits applied.", edits.len());
+                edits.clear();
+                self.data_container = Some(Arc::new(container));
+                true
+            }
+            Err(error) => {
+                self.notification = Some(Box::new(Error {
+                    message: error.to_string(),
+                }));
+                false
+            }
+        }
+    }
+
+    /// Handles an action of the edit toolbar above the table (see `CellEdits::render_toolbar`).
+    fn handle_cell_edit_action(&mut self, action: CellEditAction, ctx: &Context) {
+        match action {
+            CellEditAction::Apply => {
+                self.commit_cell_edits();
+            }
+            CellEditAction::Discard => {
+                if let Some(edits) = &mut self.cell_edits {
+                    edits.clear();
+                }
+            }
+            CellEditAction::Save => self.handle_save_file(ctx),
+            CellEditAction::SaveAs => self.handle_save_as(ctx),
+            CellEditAction::Close => self.set_edit_mode(false),
+        }
+    }
+
+    /// Runs `save_as` for `container` in the background; the result is reported by `check_save_pending`.
+    fn spawn_save_as(&mut self, container: Arc<DataContainer>, ctx: &Context) {
+        // Clone context for the async task.
+        let ctx_clone = ctx.clone();
+        let write_manifest = self.write_export_manifest;
+        let pretty_json = self.pretty_json;
+
+        // The result is sent back to the UI thread (see `check_save_pending`).
+        let (tx, rx) = oneshot::channel::<SaveResult>();
+        self.save_pipe = Some(rx);
+
+        // Spawn the save_as operation onto the runtime.
+        self.runtime.spawn(async move {
+            let result = save_as(container, ctx_clone.clone(), write_manifest, pretty_json).await;
+            if tx.send(result).is_err() {
+                error!("Receiver dropped before the 'Save As' result could be sent.");
+            }
+            ctx_clone.request_repaint();
+        });
+    }
+
+    /// Handles "Export View...": saves the current `ViewState` (settings, sorting, format) as JSON.
+    fn handle_export_view(&mut self) {
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        let view = ViewState::from_container(container);
+        let default_file_name = container
+            .filter
+            .absolute_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| format!("{stem}.view.json"))
+            .unwrap_or_else(|| "view.json".to_string());
+
+        let result = self
+            .runtime
+            .block_on(save_view_state_file(&default_file_name))
+            .and_then(|path| view.save(path));
+
+        match result {
+            Ok(()) => tracing::info!("View state exported."),
+            Err(PolarsViewError::FileNotFound(_)) => {
+                tracing::debug!("Export view dialog cancelled by user.");
+            }
+            Err(e) => {
+                self.notification = Some(Box::new(Error {
+                    message: e.to_string(),
+                }));
+            }
+        }
+    }
+
+    /// Handles "Export Session...": saves a zip bundle with the view state, the query
+    /// history and (optionally) a sample of the source data.
+    fn handle_export_session(&mut self) {
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        let default_file_name = container
+            .filter
+            .absolute_path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .map(|stem| format!("{stem}.session.zip"))
+            .unwrap_or_else(|| "session.zip".to_string());
+        let sample_rows = self.session_include_sample.then_some(DEFAULT_SAMPLE_ROWS);
+
+        let result = self
+            .runtime
+            .block_on(save_session_file(&default_file_name))
+            .and_then(|path| write_session_bundle(path, container, sample_rows));
+
+        match result {
+            Ok(()) => tracing::info!("Session exported."),
+            Err(PolarsViewError::FileNotFound(_)) => {
+                tracing::debug!("Export session dialog cancelled by user.");
+            }
+            Err(e) => {
+                self.notification = Some(Box::new(Error {
+                    message: e.to_string(),
+                }));
+            }
+        }
+    }
+
+    /// Handles "Import View...": reads a `ViewState` JSON and applies it to the loaded data.
+    fn handle_import_view(&mut self, ctx: &Context) {
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        let result = self
+            .runtime
+            .block_on(open_view_state_file())
+            .and_then(ViewState::load);
+
+        match result {
+            Ok(view) => {
+                let filter = container.filter.as_ref().clone();
+                let future = container
+                    .as_ref()
+                    .clone()
+                    .load_with_view_state(filter, view);
+                self.run_data_future("Import view", Box::new(Box::pin(future)), ctx);
+            }
+            Err(PolarsViewError::FileNotFound(_)) => {
+                tracing::debug!("Import view dialog cancelled by user.");
+            }
+            Err(e) => {
+                self.notification = Some(Box::new(Error {
+                    message: e.to_string(),
+                }));
+            }
+        }
+    }
+
+    /// Shows the "New Synthetic Dataset" dialog; on "Generate", writes the dataset to a
+    /// temporary file and opens it like any other file.
+    fn handle_synthetic_dialog(&mut self, ctx: &Context) {
+        let Some(spec) = self.synthetic_dialog.show(ctx) else {
+            return;
+        };
+        if self.edits_pending("Generate dataset") {
+            return;
+        }
+
+        match write_synthetic_dataset(&spec) {
+            Ok(temp_file) => {
+                self.table_page = 0;
+                let future = DataContainer::default().load_temp_file(
+                    temp_file,
+                    self.applied_filter.clone(),
+                    self.applied_format.clone(),
+                );
+                self.run_data_future("Generate dataset", Box::new(Box::pin(future)), ctx);
+            }
+            Err(e) => {
+                self.notification = Some(Box::new(Error {
+                    message: format!("Failed to generate the synthetic dataset: {e}"),
+                }));
+            }
+        }
+    }
+
+    /// Shows the "Connect to Database" dialog; on "Run", runs the query on the server
+    /// (async), writes its result to a temporary file and opens it like any other file.
+    fn handle_database_dialog(&mut self, ctx: &Context) {
+        let Some(request) = self.database_dialog.show(ctx) else {
+            return;
+        };
+        if self.edits_pending("Database query") {
+            return;
+        }
+
+        self.table_page = 0;
+        let filter = self.applied_filter.clone();
+        let format = self.applied_format.clone();
+        let future = async move {
+            let temp_file = tokio::task::spawn_blocking(move || {
+                write_database_query(&request.url, &request.query)
+            })
+            .await??;
+            DataContainer::default()
+                .load_temp_file(temp_file, filter, format)
+                .await
+        };
+        self.run_data_future("Database query", Box::new(Box::pin(future)), ctx);
+    }
+
+    /// Shows the "Encrypted Parquet" dialog; on "Open", reads the file again with the key.
+    fn handle_parquet_key_dialog(&mut self, ctx: &Context) {
+        let Some(key) = self.parquet_key_dialog.show(ctx) else {
+            return;
+        };
+        if self.edits_pending("Encrypted Parquet") {
+            return;
+        }
+
+        self.table_page = 0;
+        let mut filter = self.applied_filter.clone();
+        let format = self.applied_format.clone();
+        let path = self.parquet_key_dialog.path.clone();
+        let future = async move {
+            filter.set_path(&path)?;
+            filter.parquet_key = Some(key);
+            filter.read_data_from_file = true;
+            DataContainer::default().load_data(filter, format).await
+        };
+        self.run_data_future("Encrypted Parquet", Box::new(Box::pin(future)), ctx);
+    }
+
+    /// Shows the "Currency Conversion" dialog and handles its actions: choosing the
+    /// rate table file, or appending the converted column (async, see `DataContainer::convert_currency`).
+    fn handle_currency_dialog(&mut self, ctx: &Context) {
+        let Some(container) = &self.data_container else {
+            return;
+        };
+
+        match self.currency_dialog.show(ctx, container.df.schema()) {
+            Some(CurrencyAction::PickRatesFile) => match self.runtime.block_on(open_rates_file()) {
+                Ok(path) => self.currency_dialog.rates_path = Some(path),
+                Err(PolarsViewError::FileNotFound(_)) => {
+                    tracing::debug!("Rates file dialog cancelled by user.");
+                }
+                Err(e) => {
+                    self.notification = Some(Box::new(Error {
+                        message: e.to_string(),
+                    }));
+                }
+            },
+            Some(CurrencyAction::Apply(rates_path, conversion)) => {
+                let future = container
+                    .as_ref()
+                    .clone()
+                    .convert_currency(rates_path, conversion);
+                self.run_data_future("Currency conversion", Box::new(Box::pin(future)), ctx);
+            }
+            None => {}
+        }
+    }
+
+    // --- UI Rendering Methods ---
+
+    /// Renders the top menu bar (`Panel::top`).
+    fn render_menu_bar(&mut self, ui: &mut egui::Ui) {
+        MenuBar::new().ui(ui, |ui| {
+            ui.horizontal(|ui| {
+                self.render_file_menu(ui); // "File" menu
+                self.render_help_menu(ui); // "Help" menu
+                // Add space and theme switch aligned to the right.
+                ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    self.render_theme(ui);
                 });
             });
         });
@@ -398,10 +1650,89 @@ impl PolarsViewApp {
                     ui.label("Ctrl + O");
                     ui.end_row();
 
-                    // "Save" button (enabled only if data is loaded)
-                    let save_enabled = self.data_container.is_some();
+                    // Read the file again (e.g., regenerated by an external pipeline).
+                    if ui
+                        .add_enabled(self.data_container.is_some(), egui::Button::new("Reload"))
+                        .on_hover_text("Read the file again, keeping the SQL query, sort, column filters and format.")
+                        .clicked()
+                    {
+                        self.handle_reload(ui.ctx());
+                        ui.close();
+                    }
+                    ui.label("F5");
+                    ui.end_row();
+
+                    // Download a remote file (http, https or S3).
+                    if ui
+                        .button("Open URL...")
+                        .on_hover_text("Download and open a CSV, JSON, Parquet or Excel file from http, https or S3.")
+                        .clicked()
+                    {
+                        self.url_dialog.open = true;
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Browse the data files of a folder (side panel).
+                    if ui
+                        .button("Open Folder...")
+                        .on_hover_text(
+                            "List the data files of a folder, to open or concatenate them\n\
+                            (an Apache Iceberg table folder is opened).",
+                        )
+                        .clicked()
+                    {
+                        self.handle_open_folder(ui.ctx());
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Query result of a PostgreSQL/MySQL server.
+                    if ui
+                        .button("Connect to Database...")
+                        .on_hover_text("Run a SQL query on a PostgreSQL or MySQL server and open its result.")
+                        .clicked()
+                    {
+                        self.database_dialog.open = true;
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Generated data, to demo features or test exports.
+                    if ui
+                        .button("New Synthetic Dataset...")
+                        .on_hover_text("Generate a dataset with configurable columns, types and distributions.")
+                        .clicked()
+                    {
+                        self.synthetic_dialog.open = true;
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // "New Tab" button
+                    if ui
+                        .add_enabled(self.pipe.is_none(), egui::Button::new("New Tab"))
+                        .clicked()
+                    {
+                        self.new_tab();
+                        ui.close();
+                    }
+                    ui.label("Ctrl + T");
+                    ui.end_row();
+
+                    // "Save" button (enabled only if all the rows are loaded: not with "Lazy
+                    // Scan" nor in preview mode)
+                    let save_enabled = self
+                        .data_container
+                        .as_ref()
+                        .is_some_and(|container| container.check_source_saveable().is_ok());
                     if ui
                         .add_enabled(save_enabled, egui::Button::new("Save"))
+                        .on_disabled_hover_text("Not available with \"Lazy Scan\" nor in preview mode.")
                         .clicked()
                     {
                         self.handle_save_file(ui.ctx());
@@ -410,10 +1741,14 @@ impl PolarsViewApp {
                     ui.label("Ctrl + S");
                     ui.end_row();
 
-                    // "Save As..." button (enabled only if data is loaded)
-                    let save_as_enabled = self.data_container.is_some();
+                    // "Save As..." button (enabled only if all the rows are loaded: not with "Lazy Scan")
+                    let save_as_enabled = self
+                        .data_container
+                        .as_ref()
+                        .is_some_and(|container| container.check_exportable().is_ok());
                     if ui
                         .add_enabled(save_as_enabled, egui::Button::new("Save As..."))
+                        .on_disabled_hover_text("Not available with \"Lazy Scan\".")
                         .clicked()
                     {
                         self.handle_save_as(ui.ctx());
@@ -421,6 +1756,143 @@ impl PolarsViewApp {
                     }
                     ui.label("Ctrl + A");
                     ui.end_row();
+
+                    // Edit mode: the table cells become text fields (see `cell_edit.rs`).
+                    let mut editing = self.cell_edits.is_some();
+                    if ui
+                        .add_enabled(save_enabled, egui::Checkbox::new(&mut editing, "Edit Cells"))
+                        .on_hover_text("Edit the table cells; \"Save\" writes the edited data back to the file.\nNot available with \"Lazy Scan\" nor in preview mode.")
+                        .changed()
+                    {
+                        self.set_edit_mode(editing);
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Slim extract: only the columns checked in the "Columns" panel.
+                    if ui
+                        .add_enabled(save_as_enabled, egui::Button::new("Export Selected Columns..."))
+                        .on_hover_text("\"Save As...\" with only the columns checked in the \"Columns\" panel.")
+                        .clicked()
+                    {
+                        self.handle_export_selected_columns(ui.ctx());
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Diff of the displayed rows with the source data or another file.
+                    let compare_enabled = self.data_container.is_some();
+                    if ui
+                        .add_enabled(compare_enabled, egui::Button::new("Compare..."))
+                        .on_hover_text("Compare the displayed rows with the source data (before the query) or another file, matched on a key column.")
+                        .clicked()
+                    {
+                        self.diff_view.open = true;
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // View state export/import (enabled only if data is loaded)
+                    let view_enabled = self.data_container.is_some();
+                    if ui
+                        .add_enabled(view_enabled, egui::Button::new("Export View..."))
+                        .on_hover_text("Save settings, SQL query, sorting and format as JSON.")
+                        .clicked()
+                    {
+                        self.handle_export_view();
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    if ui
+                        .add_enabled(view_enabled, egui::Button::new("Import View..."))
+                        .on_hover_text("Apply a view JSON exported by \"Export View...\".")
+                        .clicked()
+                    {
+                        self.handle_import_view(ui.ctx());
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Shareable bundle: view, query history and a data sample.
+                    if ui
+                        .add_enabled(view_enabled, egui::Button::new("Export Session..."))
+                        .on_hover_text(
+                            "Save a zip with the view JSON, the query history and (optionally)\n\
+                            a sample of the source data, to reproduce this view elsewhere.",
+                        )
+                        .clicked()
+                    {
+                        self.handle_export_session();
+                        ui.close();
+                    }
+                    ui.checkbox(&mut self.session_include_sample, "with data sample")
+                        .on_hover_text(format!(
+                            "Include the first {DEFAULT_SAMPLE_ROWS} rows of the source data."
+                        ));
+                    ui.end_row();
+
+                    // Currency conversion dialog (enabled only if data is loaded)
+                    if ui
+                        .add_enabled(view_enabled, egui::Button::new("Currency Conversion..."))
+                        .on_hover_text("Append a converted-amount column using a rate table (date, currency, rate).")
+                        .clicked()
+                    {
+                        self.currency_dialog.open = true;
+                        ui.close();
+                    }
+                    ui.label("");
+                    ui.end_row();
+
+                    // Read-only protection toggle
+                    ui.checkbox(&mut self.protect_source_file, "Protect source file")
+                        .on_hover_text(
+                            "Ask for confirmation before \"Save\" overwrites the original file,\n\
+                            listing the operations (SQL, sort, ...) that would be baked into it.",
+                        );
+                    ui.label("");
+                    ui.end_row();
+
+                    // Auto-reload toggle
+                    ui.checkbox(&mut self.auto_reload, "Auto-reload on change")
+                        .on_hover_text(
+                            "Watch the file of the active tab and read it again when it changes on disk,\n\
+                            keeping the SQL query, sort, column filters and format.",
+                        );
+                    ui.label("");
+                    ui.end_row();
+
+                    // Export manifest toggle
+                    ui.checkbox(&mut self.write_export_manifest, "Write export manifest")
+                        .on_hover_text(
+                            "Also write `<file>.manifest.json` when saving: source path, load options,\n\
+                            applied SQL and transforms, row counts and the SHA-256 of the written file.",
+                        );
+                    ui.label("");
+                    ui.end_row();
+
+                    // Pretty Json toggle
+                    ui.checkbox(&mut self.pretty_json, "Pretty-print JSON")
+                        .on_hover_text(
+                            "Indent the records of exported `.json` files (one key per line).\n\
+                            `.ndjson` files always keep one record per line.",
+                        );
+                    ui.label("");
+                    ui.end_row();
+
+                    // Key columns first toggle
+                    ui.checkbox(&mut self.export_keys_first, "Export key columns first")
+                        .on_hover_text(
+                            "Write the row index column and the frozen columns first\n\
+                            (column A onwards), whatever their order on screen.",
+                        );
+                    ui.label("");
+                    ui.end_row();
                 });
 
             ui.separator(); // Visual separator.
@@ -430,9 +1902,9 @@ impl PolarsViewApp {
                 .num_columns(2) // Simplified for fewer items.
                 .spacing([20.0, 10.0])
                 .show(ui, |ui| {
-                    // "Settings" button (Placeholder - shows a basic notification)
+                    // "Settings" button (application settings window)
                     if ui.button("Settings").clicked() {
-                        self.notification = Some(Box::new(Settings {})); // Show placeholder.
+                        self.notification = Some(Box::new(Settings::from_config_dir()));
                         ui.close();
                     }
                     ui.label(""); // Placeholder for alignment.
@@ -552,44 +2024,367 @@ impl PolarsViewApp {
         }
     }
 
+    /// Renders the tab bar: one tab per file (click to switch, 🗙 to close) and ➕ for a new tab.
+    /// Disabled while an async operation is pending.
+    fn render_tab_bar(&mut self, ui: &mut egui::Ui) {
+        let mut switch_to = None;
+        let mut close = None;
+        let mut open_new = false;
+
+        ui.add_enabled_ui(!self.task_pending(), |ui| {
+            ui.horizontal_wrapped(|ui| {
+                for index in 0..self.tabs.len() {
+                    let (title, memory, busy) = if index == self.active_tab {
+                        let busy = self.task_pending()
+                            || TabState::panels_pending(
+                                &mut self.statistics_panel,
+                                &mut self.distribution_chart,
+                                &mut self.duplicate_finder,
+                                &mut self.value_counts_view,
+                                &mut self.diff_view,
+                            );
+                        (
+                            TabState::title(&self.data_container),
+                            TabState::memory(ui.ctx(), &self.data_container),
+                            busy,
+                        )
+                    } else {
+                        let tab = &mut self.tabs[index];
+                        let busy = TabState::panels_pending(
+                            &mut tab.statistics_panel,
+                            &mut tab.distribution_chart,
+                            &mut tab.duplicate_finder,
+                            &mut tab.value_counts_view,
+                            &mut tab.diff_view,
+                        );
+                        (
+                            TabState::title(&tab.data_container),
+                            TabState::memory(ui.ctx(), &tab.data_container),
+                            busy,
+                        )
+                    };
+
+                    if busy {
+                        ui.spinner().on_hover_text("Background work in progress");
+                    }
+
+                    let label = match memory {
+                        Some(bytes) => format!("{title} · {}", format_bytes(bytes)),
+                        None => title,
+                    };
+                    let response = ui.selectable_label(index == self.active_tab, label);
+                    let response = match memory {
+                        Some(_) => response.on_hover_text(
+                            "Estimated memory of the loaded data (original, filtered and displayed).",
+                        ),
+                        None => response,
+                    };
+                    if response.clicked() {
+                        switch_to = Some(index);
+                    }
+
+                    if self.tabs.len() > 1
+                        && ui.small_button("🗙").on_hover_text("Close tab").clicked()
+                    {
+                        close = Some(index);
+                    }
+
+                    ui.separator();
+                }
+
+                if ui
+                    .small_button("➕")
+                    .on_hover_text("New tab (Ctrl+T)")
+                    .clicked()
+                {
+                    open_new = true;
+                }
+            });
+        });
+
+        if let Some(index) = close {
+            self.close_tab(index);
+        } else if let Some(index) = switch_to {
+            self.switch_tab(index);
+        } else if open_new {
+            self.new_tab();
+        }
+    }
+
     /// Renders the left side panel content.
     fn render_side_panel_content(&mut self, ui: &mut egui::Ui) {
         ScrollArea::vertical().show(ui, |ui| {
-            if let Some(file_info) = &self.file_info {
+            if self.folder_browser.folder.is_some() {
+                let mut folder_action = None;
+                egui::CollapsingHeader::new("Folder")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        folder_action = self
+                            .folder_browser
+                            .render(ui, &self.applied_filter.absolute_path);
+                    });
+                if let Some(action) = folder_action {
+                    self.handle_folder_action(action, ui.ctx());
+                }
+            }
+
+            let mut compact_requested = false;
+            let mut reinfer_requested = false;
+            let is_lazy = self
+                .data_container
+                .as_ref()
+                .is_some_and(|data_container| data_container.lazy.is_some());
+            if let Some(file_info) = &mut self.file_info {
                 ui.collapsing("Info", |ui| {
                     file_info.render_metadata(ui);
+
+                    compact_requested = ui
+                        .button("Compact memory")
+                        .on_hover_text(
+                            "Rechunk all columns into contiguous memory and shrink over-allocated buffers.\n\
+                            Speeds up subsequent sorts and scans after heavy filtering.",
+                        )
+                        .clicked();
+
+                    reinfer_requested = ui
+                        .add_enabled(!is_lazy, egui::Button::new("Re-infer types"))
+                        .on_hover_text(
+                            "Convert String columns whose values all parse as numbers, booleans,\n\
+                            dates or datetimes (e.g., after a SQL query or text transforms).",
+                        )
+                        .on_disabled_hover_text("Not available for lazily scanned files.")
+                        .clicked();
+                });
+            }
+
+            if reinfer_requested && let Some(data_container) = &self.data_container {
+                let future = data_container.as_ref().clone().reinfer_types();
+                self.run_data_future("Re-infer types", Box::new(Box::pin(future)), ui.ctx());
+            }
+
+            if compact_requested && let Some(data_container) = &self.data_container {
+                let future = data_container.as_ref().clone().compact_memory();
+                self.run_data_future("Compact memory", Box::new(Box::pin(future)), ui.ctx());
+            }
+
+            if self.data_container.is_some() {
+                ui.collapsing("Presets", |ui| {
+                    if let Some(data_container) = &self.data_container
+                        && let Some(view) = self.preset_panel.render(ui, data_container)
+                    {
+                        let filter = data_container.filter.as_ref().clone();
+                        let future = data_container
+                            .as_ref()
+                            .clone()
+                            .load_with_view_state(filter, view);
+                        self.run_data_future("Load preset", Box::new(Box::pin(future)), ui.ctx());
+                    }
                 });
             }
 
             ui.collapsing("Format", |ui| {
-                if let Some(new_format) = self.applied_format.render_format(ui)
+                let schema = self
+                    .data_container
+                    .as_ref()
+                    .map(|container| container.df.schema().clone())
+                    .unwrap_or_default();
+                if self.applied_format.render_format(ui, &schema).is_some() {
+                    self.apply_format("Update format", true, ui.ctx());
+                }
+            });
+
+            ui.collapsing("Sort", |ui| {
+                ui.menu_button("Header sort legend", render_sort_legend);
+                if let Some(sort_expression) =
+                    self.applied_sort_expression.render_sort_expression(ui)
                     && let Some(data_container) = &self.data_container
                 {
-                    let future = data_container.as_ref().clone().update_format(new_format);
-                    self.run_data_future(Box::new(Box::pin(future)), ui.ctx());
+                    let future = data_container
+                        .as_ref()
+                        .clone()
+                        .apply_sort_expression(sort_expression);
+                    self.run_data_future("Sort", Box::new(Box::pin(future)), ui.ctx());
                 }
             });
 
             ui.collapsing("Query", |ui| {
-                if let Some(new_filter) = self.applied_filter.render_query(ui)
+                if let Some(new_filter) = self.applied_filter.render_query(ui, &self.applied_format)
                     && let Some(data_container) = &self.data_container
                 {
                     let future = data_container
                         .as_ref()
                         .clone()
                         .load_data(new_filter, self.applied_format.clone());
-                    self.run_data_future(Box::new(Box::pin(future)), ui.ctx());
+                    self.run_data_future("Apply query", Box::new(Box::pin(future)), ui.ctx());
                 }
             });
 
-            if let Some(file_info) = &self.file_info {
+            if let Some(data_container) = &self.data_container {
+                ui.collapsing("Statistics", |ui| {
+                    self.statistics_panel.render(
+                        ui,
+                        &data_container.df,
+                        self.runtime.handle(),
+                        self.applied_format.decimal,
+                    );
+                });
+            }
+
+            if let Some(data_container) = &self.data_container {
+                ui.collapsing("Distribution", |ui| {
+                    self.distribution_chart
+                        .render(ui, &data_container.df, self.runtime.handle());
+                });
+            }
+
+            if let Some(data_container) = &self.data_container {
+                ui.collapsing("Duplicates", |ui| {
+                    self.duplicate_finder
+                        .render(ui, &data_container.df, self.runtime.handle());
+                });
+            }
+
+            if self.data_container.is_some() {
+                ui.collapsing("Group Explorer", |ui| {
+                    if let Some(data_container) = &self.data_container
+                        && let Some(query) = self.group_explorer.render(
+                            ui,
+                            &data_container.df,
+                            &data_container.filter,
+                        )
+                    {
+                        // A group was clicked: filter the table to it via the SQL query.
+                        if self.edits_pending("Group filter") {
+                            return;
+                        }
+                        self.applied_filter.query = query;
+                        self.applied_filter.apply_sql = true;
+                        let future = data_container
+                            .as_ref()
+                            .clone()
+                            .load_data(self.applied_filter.clone(), self.applied_format.clone());
+                        self.run_data_future("Group filter", Box::new(Box::pin(future)), ui.ctx());
+                    }
+                });
+            }
+
+            if let Some(data_container) = &self.data_container {
+                ui.collapsing("Map", |ui| {
+                    self.map_view.render_controls(ui, &data_container.df);
+                });
+            }
+
+            #[cfg(feature = "format-special")]
+            if self.data_container.is_some() {
+                ui.collapsing("Validators", |ui| {
+                    if let Some(data_container) = &self.data_container
+                        && let Some((column_name, document)) = self
+                            .fiscal_validator
+                            .render(ui, data_container.df.schema())
+                    {
+                        let future = data_container
+                            .as_ref()
+                            .clone()
+                            .validate_fiscal_document(column_name, document);
+                        self.run_data_future("Fiscal validation", Box::new(Box::pin(future)), ui.ctx());
+                    }
+                });
+            }
+
+            if self.file_info.is_some() {
                 ui.collapsing("Columns", |ui| {
-                    file_info.render_schema(ui);
+                    if let Some(data_container) = &self.data_container
+                        && self
+                            .applied_format
+                            .render_column_visibility(ui, data_container.df.schema())
+                            .is_some()
+                    {
+                        self.apply_format("Column visibility", true, ui.ctx());
+                    }
+                    ui.collapsing("Virtual Columns", |ui| {
+                        if let Some(data_container) = &self.data_container
+                            && self
+                                .applied_format
+                                .render_virtual_columns(ui, &data_container.df_unfiltered)
+                                .is_some()
+                        {
+                            self.apply_format("Virtual columns", false, ui.ctx());
+                        }
+                    });
+                    ui.collapsing("Data Types", |ui| {
+                        if let Some(data_container) = &self.data_container
+                            && let Some(filter) = self
+                                .applied_filter
+                                .render_column_types(ui, data_container.df_original.schema())
+                        {
+                            let future = data_container
+                                .as_ref()
+                                .clone()
+                                .load_data(filter, self.applied_format.clone());
+                            self.run_data_future(
+                                "Column types",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                        }
+                    });
+                    ui.collapsing("Rename Columns", |ui| {
+                        if let Some(data_container) = &self.data_container
+                            && let Some(filter) = self
+                                .applied_filter
+                                .render_column_renames(ui, data_container.df_original.schema())
+                        {
+                            let future = data_container
+                                .as_ref()
+                                .clone()
+                                .load_data(filter, self.applied_format.clone());
+                            self.run_data_future(
+                                "Column names",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                        }
+                    });
+                    ui.collapsing("Notes & Descriptions", |ui| {
+                        self.render_annotations(ui);
+                    });
+                    if ui
+                        .button("Export selected columns…")
+                        .on_hover_text("Write only the checked columns to a new file.")
+                        .clicked()
+                    {
+                        self.handle_export_selected_columns(ui.ctx());
+                    }
+                    ui.separator();
+                    if let Some(file_info) = &self.file_info {
+                        file_info.render_schema(ui);
+                    }
                 });
             }
         });
     }
 
+    /// Renders the notes and column descriptions editor ("Columns" > "Notes & Descriptions").
+    /// Each edit is saved to the sidecar of the file (see `Annotations::save`).
+    fn render_annotations(&mut self, ui: &mut egui::Ui) {
+        let Some(data_container) = &self.data_container else {
+            return;
+        };
+        let mut annotations = data_container.annotations.as_ref().clone();
+        if !annotations.render(ui, data_container.df.schema()) {
+            return;
+        }
+
+        if let Err(error) = annotations.save(&data_container.filter.absolute_path) {
+            self.notification = Some(Box::new(Error {
+                message: format!("Failed to save the notes: {error}"),
+            }));
+        }
+        let mut container = data_container.as_ref().clone();
+        container.annotations = Arc::new(annotations);
+        self.data_container = Some(Arc::new(container));
+    }
+
     /// Renders the bottom status bar content.
     fn render_status_bar_content(&mut self, ui: &mut egui::Ui) {
         ui.horizontal(|ui| {
@@ -600,15 +2395,93 @@ impl PolarsViewApp {
                 ));
                 ui.separator();
                 ui.label(format!("Sort: {} active criteria", container.sort.len()));
+
+                // Rows sharing the value of the selected categorical cell.
+                if let Some(selection) = &self.cell_selection {
+                    let columns: Vec<&str> = container
+                        .visible_columns()
+                        .map(|column| column.name().as_str())
+                        .collect();
+                    if let Some(summary) =
+                        self.group_summary
+                            .summary(&container.df, &columns, selection)
+                    {
+                        ui.separator();
+                        ui.label(summary.text())
+                            .on_hover_text("Rows sharing the value of the selected cell.");
+                    }
+                }
+
+                if container.format.paged {
+                    ui.separator();
+                    render_page_navigation(
+                        ui,
+                        &mut self.table_page,
+                        &container.format,
+                        container.total_rows(),
+                    );
+                }
             } else {
                 ui.label("No file loaded.");
             }
 
-            if self.pipe.is_some() {
+            if let Some(toast) = &self.status_toast {
+                ui.separator();
+                if !toast.show(ui) {
+                    self.status_toast = None;
+                }
+            }
+
+            if self.pipe.is_none() {
+                self.download = None; // The download (if any) and its loading are done.
+            }
+
+            // While bytes are arriving; then "Processing..." (loading the local copy).
+            if let Some(progress) = self.download.as_ref().filter(|p| p.fraction() != Some(1.0)) {
                 ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    let megabytes = |bytes: u64| bytes as f64 / 1_048_576.0;
+                    let text = match progress.total() {
+                        Some(total) => format!(
+                            "{:.1} / {:.1} MB",
+                            megabytes(progress.downloaded()),
+                            megabytes(total)
+                        ),
+                        None => format!("{:.1} MB", megabytes(progress.downloaded())),
+                    };
+                    ui.add(
+                        egui::ProgressBar::new(progress.fraction().unwrap_or(0.0))
+                            .desired_width(160.0)
+                            .text(text),
+                    )
+                    .on_hover_text(&progress.url);
                     ui.spinner();
-                    ui.label("Processing... ");
+                    ui.label("Downloading... ");
                 });
+            } else if self.pipe.is_some() {
+                let progress = self.progress.as_ref().and_then(|rx| *rx.borrow());
+                let cancel = ui
+                    .with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                        let cancel = ui
+                            .button("Cancel")
+                            .on_hover_text("Stop this operation and keep the current data.")
+                            .clicked();
+                        ui.spinner();
+                        match progress {
+                            Some(progress) => {
+                                ui.label(progress.text());
+                                // Refresh the elapsed time.
+                                ui.ctx().request_repaint_after(Duration::from_secs(1));
+                            }
+                            None => {
+                                ui.label("Processing... ");
+                            }
+                        }
+                        cancel
+                    })
+                    .inner;
+                if cancel {
+                    self.cancel_data_task();
+                }
             }
         });
     }
@@ -627,18 +2500,32 @@ impl eframe::App for PolarsViewApp {
     }
 
     /// Primary UI rendering loop.
-    fn ui(&mut self, ui: &mut egui::Ui, _frame: &mut eframe::Frame) {
+    fn ui(&mut self, ui: &mut egui::Ui, frame: &mut eframe::Frame) {
         // Clone context for use in closures to satisfy the borrow checker
         let ctx = ui.ctx().clone();
 
-        // Check visual notifications
+        // Simplify the table if the last frames were slow
+        if let Some(frame_seconds) = frame.info().cpu_usage {
+            self.check_frame_time(frame_seconds, &ctx);
+        }
+
+        // Report finished save tasks, then check visual notifications
+        self.check_save_pending();
         self.check_notification(&ctx);
 
+        // Auto-reload: read the file again if it changed on disk
+        self.check_file_changes(&ctx);
+
         // Define top panel layout
         Panel::top("top_panel").show_inside(ui, |ui| {
             self.render_menu_bar(ui);
         });
 
+        // Define tab bar layout
+        Panel::top("tab_bar").show_inside(ui, |ui| {
+            self.render_tab_bar(ui);
+        });
+
         // Define bottom panel layout
         Panel::bottom("bottom_panel").show_inside(ui, |ui| {
             self.render_status_bar_content(ui);
@@ -656,38 +2543,295 @@ impl eframe::App for PolarsViewApp {
         CentralPanel::default().show_inside(ui, |ui| {
             egui::warn_if_debug_build(ui);
 
-            let is_pending = self.check_data_pending();
+            let is_pending = self.check_data_pending(&ctx);
+            let mut onboarding_action = None;
 
             ui.add_enabled_ui(!is_pending, |ui| {
                 match &self.data_container {
                     Some(data_container) => {
-                        // Variable to capture the new sort criteria requested by header clicks
-                        let mut opt_new_sort_criteria: Option<Vec<SortBy>> = None;
+                        // The table was simplified because rendering was slow.
+                        if self.frame_watchdog.render_banner(ui) {
+                            self.restore_full_view(ui.ctx());
+                            return;
+                        }
 
-                        ScrollArea::horizontal()
-                            .id_salt("central_scroll")
-                            .auto_shrink([false, false])
-                            .show(ui, |ui| {
-                                opt_new_sort_criteria = data_container.render_table(ui);
-                            });
+                        // Preview mode: only the first rows of the file were read.
+                        if render_preview_banner(ui, data_container)
+                            && !self.edits_pending("Load full file")
+                        {
+                            self.applied_filter.head_rows = None;
+                            self.applied_filter.read_data_from_file = true;
+                            self.applied_filter.apply_sql = true;
+                            let future = data_container.as_ref().clone().load_data(
+                                self.applied_filter.clone(),
+                                self.applied_format.clone(),
+                            );
+                            self.run_data_future(
+                                "Load full file",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                            return;
+                        }
+
+                        // A JSON array of nested objects: offer a flat table.
+                        if data_container.render_flatten_offer(ui)
+                            && !self.edits_pending("Flatten structs")
+                        {
+                            self.applied_filter.flatten_structs = true;
+                            self.applied_filter.apply_sql = true;
+                            let future = data_container.as_ref().clone().load_data(
+                                self.applied_filter.clone(),
+                                self.applied_format.clone(),
+                            );
+                            self.run_data_future(
+                                "Flatten structs",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                            return;
+                        }
+
+                        // Multi-record files: one sub-tab per record type (fixed while
+                        // cell edits are pending, see `edits_pending`).
+                        let no_pending_edits =
+                            self.cell_edits.as_ref().is_none_or(CellEdits::is_empty);
+                        if self.applied_filter.record_types.len() > 1
+                            && ui
+                                .add_enabled_ui(no_pending_edits, |ui| {
+                                    self.applied_filter.render_record_type_tabs(ui)
+                                })
+                                .inner
+                        {
+                            let future = data_container.as_ref().clone().load_data(
+                                self.applied_filter.clone(),
+                                self.applied_format.clone(),
+                            );
+                            self.run_data_future(
+                                "Load record type",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                            return;
+                        }
+
+                        // Edit mode: the pending cell edits and their actions.
+                        if let Some(action) = self
+                            .cell_edits
+                            .as_ref()
+                            .and_then(|edits| edits.render_toolbar(ui))
+                        {
+                            self.handle_cell_edit_action(action, ui.ctx());
+                            return;
+                        }
+
+                        // Interactions requested by the table (header clicks, filters, cells).
+                        let mut events = TableEvents::default();
+
+                        self.table_search.render_bar(ui, data_container);
+
+                        if self.show_sort_hint
+                            && data_container.df.width() > 0
+                            && render_sort_hint(ui)
+                        {
+                            self.show_sort_hint = false;
+                            let mut settings = AppSettings::from_config_dir();
+                            settings.sort_hint_dismissed = true;
+                            if let Err(error) = settings.save() {
+                                tracing::warn!("Failed to save the settings: {error}");
+                            }
+                        }
+
+                        // 0 rows or 0 columns: explain why, above the header (if any).
+                        let empty_action = render_empty_result(ui, data_container);
+                        if empty_action == Some(EmptyResultAction::ResetQuery) {
+                            self.applied_filter.query = DEFAULT_QUERY.to_string();
+                            self.applied_filter.apply_sql = true;
+                            let future = data_container.as_ref().clone().load_data(
+                                self.applied_filter.clone(),
+                                self.applied_format.clone(),
+                            );
+                            self.run_data_future(
+                                "Reset query",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                            return;
+                        }
+
+                        // Scrolls horizontally (except the frozen columns).
+                        // Without columns, there is no header to show.
+                        if data_container.df.width() > 0 {
+                            // No edit mode in lazy mode: `df` holds only the current page.
+                            let mut no_edits = None;
+                            let edits = if data_container.lazy.is_none() {
+                                &mut self.cell_edits
+                            } else {
+                                &mut no_edits
+                            };
+                            events = data_container.render_table(
+                                ui,
+                                &mut self.cell_selection,
+                                &mut self.table_search,
+                                &mut self.table_page,
+                                edits,
+                            );
+                        }
+                        if empty_action == Some(EmptyResultAction::ClearColumnFilters) {
+                            events.column_filters = Some(ColumnFilters::new());
+                        }
 
-                        if let Some(new_criteria) = opt_new_sort_criteria {
-                            tracing::debug!("Sort action requested. New criteria: {:#?}", new_criteria);
+                        if let Some((row, column)) = events.inspect_cell {
+                            self.row_inspector.inspect(row, &column);
+                        }
+                        if let Some(column) = &events.group_by {
+                            self.value_counts_view.open_for(column);
+                        }
+
+                        if let Some(query) = events.query
+                            && !self.edits_pending("Quick filter")
+                        {
+                            // Quick filter from the cell context menu: re-apply the SQL query.
+                            tracing::debug!("Quick filter requested. New query: {query}");
+                            self.applied_filter.query = query;
+                            self.applied_filter.apply_sql = true;
+                            let future = data_container.as_ref().clone().load_data(
+                                self.applied_filter.clone(),
+                                self.applied_format.clone(),
+                            );
+                            self.run_data_future(
+                                "Quick filter",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                        } else if let Some(column_filters) = events.column_filters {
+                            tracing::debug!("Column filters changed: {:#?}", column_filters);
+                            let future = data_container
+                                .as_ref()
+                                .clone()
+                                .apply_column_filters(column_filters);
+                            self.run_debounced_data_future(
+                                "Column filters",
+                                Box::new(Box::pin(future)),
+                                ui.ctx(),
+                            );
+                        } else if let Some(column_name) = events.hide_column {
+                            // Header context menu: same as unchecking it in "Columns".
+                            tracing::debug!("Hide column requested: '{column_name}'");
+                            self.applied_format.hidden_columns.insert(column_name);
+                            self.apply_format("Column visibility", false, ui.ctx());
+                        } else if let Some((column_name, freeze)) = events.freeze_column {
+                            tracing::debug!("Freeze column requested: '{column_name}' ({freeze})");
+                            let frozen = &mut self.applied_format.frozen_columns;
+                            frozen.retain(|name| *name != column_name);
+                            if freeze {
+                                frozen.push(column_name);
+                            }
+                            self.apply_format("Frozen columns", false, ui.ctx());
+                        } else if let Some((column_name, align)) = events.align_column {
+                            tracing::debug!("Align column requested: '{column_name}' ({align:?})");
+                            let alignments = &mut self.applied_format.column_alignments;
+                            match align {
+                                Some(align) => alignments.insert(column_name, align),
+                                None => alignments.remove(&column_name),
+                            };
+                            self.apply_format("Column alignment", false, ui.ctx());
+                        } else if let Some((column_name, width)) = events.lock_width {
+                            tracing::debug!("Lock width requested: '{column_name}' ({width:?})");
+                            let locked_widths = &mut self.applied_format.locked_widths;
+                            match width {
+                                Some(width) => locked_widths.insert(column_name, width),
+                                None => locked_widths.remove(&column_name),
+                            };
+                            self.apply_format("Column width", false, ui.ctx());
+                        } else if let Some((moved, target)) = events.move_column {
+                            tracing::debug!("Move column requested: '{moved}' to '{target}'");
+                            let names: Vec<&str> = data_container
+                                .ordered_columns()
+                                .map(|column| column.name().as_str())
+                                .collect();
+                            if let Some(order) = move_column(&names, &moved, &target) {
+                                self.applied_format.column_order = order;
+                                self.apply_format("Column order", false, ui.ctx());
+                            }
+                        } else if let Some(new_criteria) = events.sort {
+                            tracing::debug!(
+                                "Sort action requested. New criteria: {:#?}",
+                                new_criteria
+                            );
                             let future = data_container.as_ref().clone().apply_sort(new_criteria);
-                            self.run_data_future(Box::new(Box::pin(future)), ui.ctx());
+                            self.run_data_future("Sort", Box::new(Box::pin(future)), ui.ctx());
+                        } else if !is_pending
+                            && data_container.lazy.as_ref().is_some_and(|table| {
+                                data_container.format.page_of_row(table.offset) != self.table_page
+                            })
+                        {
+                            // Lazy scan: the page navigation moved to a page not loaded yet.
+                            let future = data_container
+                                .as_ref()
+                                .clone()
+                                .load_lazy_page(self.table_page);
+                            self.run_data_future("Load page", Box::new(Box::pin(future)), ui.ctx());
                         }
                     }
-                    None => {
-                        ui.centered_and_justified(|ui| {
-                            if is_pending {
-                                ui.spinner();
-                            } else {
-                                ui.label("Open a file (File > Open or Ctrl+O) or drag & drop CSV, JSON, or Parquet files here.");
+                    None if is_pending => {
+                        let progress = self.progress.as_ref().and_then(|rx| *rx.borrow());
+                        ui.vertical_centered(|ui| {
+                            ui.add_space(ui.available_height() / 3.0);
+                            ui.spinner();
+                            if let Some(progress) = progress {
+                                ui.label(progress.text());
                             }
                         });
                     }
+                    None => {
+                        onboarding_action = render_onboarding(ui, &self.recent_files);
+                    }
                 }
             });
+
+            match onboarding_action {
+                Some(OnboardingAction::OpenDialog) => self.handle_open_file(&ctx),
+                Some(OnboardingAction::Open(path)) => self.load_file_from_path(path, &ctx),
+                Some(OnboardingAction::LoadSample) => match write_sample_dataset() {
+                    Ok(path) => self.load_file_from_path(path, &ctx),
+                    Err(e) => {
+                        self.notification = Some(Box::new(Error {
+                            message: format!("Failed to write the sample dataset: {e}"),
+                        }));
+                    }
+                },
+                None => {}
+            }
         });
+
+        // Map quick-look window (floats above the panels).
+        if let Some(data_container) = &self.data_container {
+            self.map_view.show(&ctx, &data_container.df);
+        }
+
+        // Row inspector window (opened by clicking a cell).
+        if let Some(data_container) = &self.data_container {
+            self.row_inspector.show(&ctx, &data_container.df);
+        }
+
+        // Group-by window (opened from the header context menu).
+        if let Some(data_container) = &self.data_container {
+            self.value_counts_view
+                .show(&ctx, &data_container.df, self.runtime.handle());
+        }
+
+        // Compare window (opened from the "File" menu).
+        if let Some(data_container) = &self.data_container {
+            self.diff_view
+                .show(&ctx, data_container, self.runtime.handle());
+        }
+
+        // Currency conversion dialog.
+        self.handle_currency_dialog(&ctx);
+        self.handle_synthetic_dialog(&ctx);
+        self.handle_database_dialog(&ctx);
+        self.handle_parquet_key_dialog(&ctx);
+        self.handle_url_dialog(&ctx);
     }
 }
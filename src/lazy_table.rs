@@ -0,0 +1,161 @@
+//! Lazy table backend (the "Lazy Scan" load option, see `DataFilter::lazy_scan`).
+//!
+//! The file is scanned as a `LazyFrame` (with the SQL query and the header sort applied
+//! lazily), and only the rows of the current page are collected, so files larger than
+//! RAM (e.g., 10+ GB Parquet) can be browsed. `DataContainer::df` then holds just that
+//! page; side panel tools (statistics, charts, search) work on the loaded page.
+
+use crate::{
+    Compression, DataFilter, FileExtension, PolarsViewError, PolarsViewResult, SortBy,
+    execute_polars_blocking, execute_sql,
+};
+
+use polars::prelude::*;
+use std::{fmt, path::Path, sync::Arc};
+
+/// A scanned file with the SQL query (and sort) applied, collected one window at a time.
+#[derive(Clone)]
+pub struct LazyTable {
+    /// The scan with the SQL query applied (no sort).
+    query: LazyFrame,
+    /// `query` with the header sort criteria applied.
+    lf: LazyFrame,
+    /// Number of rows of the query result.
+    pub total_rows: usize,
+    /// First row (0-based) of the collected window.
+    pub offset: usize,
+}
+
+impl fmt::Debug for LazyTable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LazyTable")
+            .field("total_rows", &self.total_rows)
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+impl LazyTable {
+    /// Whether the file at `path` can be scanned lazily (Parquet and CSV files; compressed
+    /// files are decompressed in memory, see `DataSource`).
+    pub fn supports(path: &Path) -> bool {
+        Compression::from_path(path).is_none()
+            && matches!(
+                FileExtension::from_path(path),
+                FileExtension::Parquet | FileExtension::Csv
+            )
+    }
+
+    /// Scans the file of `filter` and applies its SQL query; counts the rows of the result
+    /// (the only full pass over the data).
+    pub async fn scan(filter: &DataFilter) -> PolarsViewResult<(Self, FileExtension)> {
+        let extension = FileExtension::from_path(&filter.absolute_path);
+
+        let scan = match extension {
+            FileExtension::Parquet => {
+                let path = PlRefPath::try_from_path(&filter.absolute_path)?;
+                let args = ScanArgsParquet {
+                    low_memory: true,
+                    ..Default::default()
+                };
+                LazyFrame::scan_parquet(path, args)?
+            }
+            FileExtension::Csv => {
+                let delimiter = filter.get_csv_separator()?;
+                let input = filter.csv_input().await?;
+                let (source, dialect) = filter.csv_dialect(&input, delimiter).await?;
+                let schema = Arc::new(Schema::default());
+                filter
+                    .attempt_read_csv(&source, dialect, delimiter, &schema)
+                    .await?
+            }
+            _ => {
+                return Err(PolarsViewError::FileType(format!(
+                    "Lazy scan supports only Parquet and CSV files: `{}`",
+                    filter.absolute_path.display()
+                )));
+            }
+        };
+
+        let query = execute_sql(&filter.table_name, scan, &filter.query_steps, &filter.query)?;
+
+        let count = query
+            .clone()
+            .select([len().cast(DataType::UInt64).alias("rows")]);
+        let counted = execute_polars_blocking(move || count.with_streaming(true).collect()).await?;
+        let total_rows = counted.column("rows")?.u64()?.get(0).unwrap_or(0) as usize;
+
+        let table = LazyTable {
+            lf: query.clone(),
+            query,
+            total_rows,
+            offset: 0,
+        };
+        Ok((table, extension))
+    }
+
+    /// A copy of this table with the header `sort` criteria applied (none: the query order).
+    pub fn sorted(&self, sort: &[SortBy]) -> Self {
+        let lf = if sort.is_empty() {
+            self.query.clone()
+        } else {
+            let names: Vec<PlSmallStr> = sort
+                .iter()
+                .map(|sort| sort.column_name.clone().into())
+                .collect();
+            let options = SortMultipleOptions::default()
+                .with_order_descending_multi(sort.iter().map(|sort| !sort.ascending))
+                .with_nulls_last_multi(sort.iter().map(|sort| sort.nulls_last))
+                .with_maintain_order(true);
+            self.query.clone().sort(names, options)
+        };
+
+        LazyTable { lf, ..self.clone() }
+    }
+
+    /// Collects `len` rows starting at `offset` and records `offset` as the current window.
+    pub async fn window(&mut self, offset: usize, len: usize) -> PolarsViewResult<DataFrame> {
+        let lf = self.lf.clone().slice(offset as i64, len as IdxSize);
+        let df = execute_polars_blocking(move || lf.with_streaming(true).collect()).await?;
+        self.offset = offset;
+        Ok(df)
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_lazy_table`
+#[cfg(test)]
+mod tests_lazy_table {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn collects_windows_of_the_query() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("data.parquet");
+        let mut df = df!("id" => (0..100).collect::<Vec<i64>>())?;
+        ParquetWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+
+        let mut filter = DataFilter::default();
+        filter.set_path(&path)?;
+        filter.query = "SELECT * FROM AllData WHERE id >= 10".to_string();
+
+        let (table, extension) = LazyTable::scan(&filter).await?;
+        assert_eq!(extension, FileExtension::Parquet);
+        assert_eq!(table.total_rows, 90);
+
+        let mut sorted = table.sorted(&[SortBy {
+            column_name: "id".to_string(),
+            ascending: false,
+            nulls_last: false,
+        }]);
+        let window = sorted.window(5, 3).await?;
+        assert_eq!(sorted.offset, 5);
+        assert!(window.equals(&df!("id" => [94i64, 93, 92])?));
+        Ok(())
+    }
+}
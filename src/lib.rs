@@ -2,49 +2,173 @@
 #![doc = include_str!("../README.md")]
 
 // Modules that make up the PolarsView library.
+mod annotations;
 mod args;
+mod cell_edit;
+mod cell_renderer;
+mod cell_selection;
+mod column_filter;
+mod column_order;
+mod column_renames;
+mod column_stats;
+mod column_types;
+mod compression;
+mod csv_sniffer;
+mod currency;
 mod data_container;
 mod data_filter;
 mod data_format;
+mod database;
+mod decimal;
+mod diff_view;
+mod dir_config;
+mod distribution;
+mod duplicates;
+mod empty_result;
+mod encoding;
+mod encryption;
 mod error;
+mod excel;
 mod file_dialog;
 mod file_extension;
 mod file_info;
+mod file_watch;
+mod folder_browser;
+mod frame_watchdog;
+mod geo;
+mod group_explorer;
+mod group_summary;
+mod iceberg;
+mod json_tree;
 mod layout;
+mod lazy_table;
+mod manifest;
+mod onboarding;
+mod parquet_pushdown;
 mod polars;
+mod presets;
+mod preview;
+mod progress;
+mod query_history;
+mod query_steps;
+mod record_types;
+mod remote;
+mod row_inspector;
+mod session_bundle;
+mod session_restore;
+mod snapshot;
 mod sort;
+mod sql_completion;
+mod sqlite;
 mod sqls;
+mod stdin;
+mod synthetic;
+mod table_search;
+mod temp_files;
 mod traits;
+mod units;
+mod value_counts;
+mod view_state;
+mod virtual_column;
+mod zip_archive;
 
 // Publicly expose the contents of these modules.
 pub use self::{
     // add to lib
+    annotations::*,
     args::Arguments,
+    cell_edit::*,
+    cell_renderer::*,
+    cell_selection::*,
+    column_filter::*,
+    column_order::*,
+    column_renames::*,
+    column_stats::*,
+    column_types::*,
+    compression::*,
+    csv_sniffer::*,
+    currency::*,
     data_container::*,
     data_filter::*,
     data_format::*,
+    database::*,
+    decimal::*,
+    diff_view::*,
+    dir_config::*,
+    distribution::*,
+    duplicates::*,
+    empty_result::*,
+    encoding::*,
+    encryption::*,
     error::*,
+    excel::*,
     file_dialog::*,
     file_extension::*,
     file_info::*,
+    file_watch::*,
+    folder_browser::*,
+    frame_watchdog::*,
+    geo::*,
+    group_explorer::*,
+    group_summary::*,
+    iceberg::*,
+    json_tree::*,
     layout::*,
+    lazy_table::*,
+    manifest::*,
+    onboarding::*,
+    parquet_pushdown::*,
     polars::add::*,
+    polars::categorical::*,
+    polars::dates::*,
     polars::drop::*,
+    polars::flatten::*,
+    polars::infer::*,
     polars::normalize::*,
     polars::remove::*,
     polars::replace::*,
+    presets::*,
+    preview::*,
+    progress::*,
+    query_history::*,
+    query_steps::*,
+    record_types::*,
+    remote::*,
+    row_inspector::*,
+    session_bundle::*,
+    session_restore::*,
+    snapshot::*,
     sort::*,
+    sql_completion::*,
+    sqlite::*,
     sqls::*,
+    stdin::*,
+    synthetic::*,
+    table_search::*,
+    temp_files::*,
     traits::*,
+    units::*,
+    value_counts::*,
+    view_state::*,
+    virtual_column::*,
+    zip_archive::*,
 };
 
+// Brazilian fiscal document validators (tax-oriented build).
+#[cfg(feature = "format-special")]
+mod fiscal;
+#[cfg(feature = "format-special")]
+pub use self::fiscal::*;
+
 // Use simple or special format.
 // A special format is applied to the "Alíquota" (Tax Rate) column.
 cfg_select! {
     // Se a feature "format-special" estiver ativa
     feature = "format-special" => {
         mod decimal_and_layout_v2;
+        mod sped_presets;
         pub use decimal_and_layout_v2::*;
+        pub use sped_presets::*;
     }
     // default: "simple"
     _ => {
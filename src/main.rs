@@ -1,7 +1,11 @@
 #![warn(clippy::all)]
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
-use polars_view::{Arguments, DataContainer, DataFilter, DataFormat, PolarsViewApp};
+use polars_view::{
+    AppSettings, Arguments, DataContainer, DataFilter, DataFormat, DataFuture, DirConfig,
+    LastSession, PolarsViewApp, ViewState, is_iceberg_table, is_remote_url, prune_derived_files,
+    set_safe_mode, write_stdin_data, write_synthetic_dataset,
+};
 use tracing::error;
 
 /*
@@ -22,12 +26,64 @@ fn main() -> eframe::Result<()> {
     tracing_subscriber::fmt::init();
 
     // Parse command-line arguments into the Arguments struct.
-    let args = Arguments::build();
+    let mut args = Arguments::build();
+
+    // Temporary files derived by previous runs and no longer used are removed.
+    std::thread::spawn(prune_derived_files);
+
+    // Safe mode: persisted state is ignored (see `set_safe_mode`).
+    if args.safe_mode {
+        tracing::info!(target: "polars_view", "Safe mode: ignoring persisted state.");
+        set_safe_mode(true);
+    }
+
+    // With `--generate`, the synthetic dataset is written to a temporary file and opened.
+    // The file is kept until the application exits.
+    let mut _synthetic_file = None;
+    if let Some(spec) = &args.generate {
+        match write_synthetic_dataset(spec) {
+            Ok(temp_file) => {
+                args.path = Some(temp_file.to_path_buf());
+                _synthetic_file = Some(temp_file);
+            }
+            Err(err) => error!("Failed to generate the synthetic dataset: {}", err),
+        }
+    }
+
+    // With `-` or `--stdin`, the piped data is written to a temporary file and opened.
+    // The file is kept until the application exits.
+    let mut _stdin_file = None;
+    if args.stdin || args.path.as_deref() == Some(std::path::Path::new("-")) {
+        args.path = match write_stdin_data(args.format) {
+            Ok(temp_path) => {
+                let path = temp_path.to_path_buf();
+                _stdin_file = Some(temp_path);
+                Some(path)
+            }
+            Err(err) => {
+                error!("Failed to read the data from stdin: {}", err);
+                None
+            }
+        };
+    }
+
+    // Register user SPED/EFD layout presets (applied automatically on load).
+    #[cfg(feature = "format-special")]
+    if let Some(presets_path) = &args.presets {
+        match polars_view::load_presets(presets_path) {
+            Ok(presets) => polars_view::set_user_presets(presets),
+            Err(err) => error!(
+                "Failed to load presets from {}: {}",
+                presets_path.display(),
+                err
+            ),
+        }
+    }
 
     // Configure the native options for the eframe/egui application.
     let native_options = eframe::NativeOptions {
         centered: true,
-        persist_window: true,
+        persist_window: !args.safe_mode, // Safe mode: default window position and size.
         vsync: true,
         viewport: egui::ViewportBuilder::default()
             .with_drag_and_drop(true)
@@ -37,34 +93,106 @@ fn main() -> eframe::Result<()> {
     };
 
     // Start the native eframe application.
+    let title = if args.safe_mode {
+        "PolarsView (safe mode)"
+    } else {
+        "PolarsView"
+    };
+
     eframe::run_native(
-        "PolarsView",
+        title,
         native_options,
         Box::new(move |creation_context| {
+            // Without a file path, the last session is reopened if requested
+            // (`--restore-session` or "Settings > Restore last session on startup").
+            let last_session = if args.path.is_none()
+                && (args.restore_session || AppSettings::from_config_dir().restore_session)
+            {
+                LastSession::from_config_dir()
+            } else {
+                None
+            };
+
             // Determine the application's initial state based on provided command-line arguments.
             let app_result = match &args.path {
-                // If a path was provided and it points to a valid file, initiate immediate loading.
-                Some(path) if path.is_file() => {
+                // A remote file (http, https, S3): downloaded in the background, then loaded.
+                Some(path) if is_remote_url(&path.to_string_lossy()) => {
+                    let url = path.to_string_lossy().to_string();
+                    let local_args = Arguments {
+                        path: None,
+                        ..args.clone()
+                    };
+                    let data_filter = DataFilter::new(&local_args)?;
+                    PolarsViewApp::new(creation_context).map(|mut app| {
+                        app.applied_filter = data_filter;
+                        app.load_url(url, &creation_context.egui_ctx);
+                        app
+                    })
+                }
+                // If a path was provided and it points to a valid file (or an Iceberg table
+                // directory), initiate immediate loading.
+                Some(path) if path.is_file() || is_iceberg_table(path) => {
                     tracing::info!(target: "polars_view", "Loading path: {}", path.display());
 
                     // Initialize data filters from command line arguments (e.g., delimiter, null values).
                     // The '?' operator propagates errors to the outer Result block.
-                    let data_filter = DataFilter::new(&args)?;
+                    let mut data_filter = DataFilter::new(&args)?;
+
+                    // Conventions of the file's directory (`.polars-view.toml`);
+                    // an explicit `--query` takes precedence over the startup query.
+                    if let Some(config) = DirConfig::for_file(&data_filter.absolute_path)? {
+                        config.apply_to(&mut data_filter);
+                        if let Some(query) = &args.query {
+                            data_filter.query = query.clone();
+                        }
+                    }
 
                     tracing::debug!("Initialization DataFilter state: {data_filter:#?}");
 
                     // Initialize the DataContainer and prepare the asynchronous loading future.
+                    // With `--view`, the view's settings, sorting and format are applied on load.
                     let dc = DataContainer::default();
-                    let future = dc.load_data(data_filter, DataFormat::default());
+                    let future: DataFuture = match &args.view {
+                        Some(view_path) => {
+                            let view = ViewState::load(view_path)?;
+                            Box::new(Box::pin(dc.load_with_view_state(data_filter, view)))
+                        }
+                        None => {
+                            Box::new(Box::pin(dc.load_data(data_filter, DataFormat::default())))
+                        }
+                    };
 
                     // Create the application instance with the pending data loading task.
-                    PolarsViewApp::new_with_future(creation_context, Box::new(Box::pin(future)))
+                    PolarsViewApp::new_with_future(creation_context, future)
                 }
-                // Default case: Open the application with an empty state (no file loaded).
-                _ => {
-                    tracing::info!(target: "polars_view", "No valid file path provided. Opening empty application.");
-                    PolarsViewApp::new(creation_context)
+                // A folder (not an Iceberg table): list its data files in the "Folder"
+                // side panel section.
+                Some(path) if path.is_dir() && !is_iceberg_table(path) => {
+                    tracing::info!(target: "polars_view", "Browsing folder: {}", path.display());
+                    PolarsViewApp::new(creation_context).map(|mut app| {
+                        app.folder_browser.open(path);
+                        app
+                    })
                 }
+                _ => match last_session {
+                    // Reopen the last session (file and view), if requested and available.
+                    Some(session) => {
+                        tracing::info!(target: "polars_view", "Restoring last session: {}", session.path.display());
+
+                        let mut data_filter = DataFilter::new(&args)?;
+                        data_filter.set_path(&session.path)?;
+
+                        let dc = DataContainer::default();
+                        let future: DataFuture =
+                            Box::new(Box::pin(dc.load_with_view_state(data_filter, session.view)));
+                        PolarsViewApp::new_with_future(creation_context, future)
+                    }
+                    // Default case: Open the application with an empty state (no file loaded).
+                    None => {
+                        tracing::info!(target: "polars_view", "No valid file path provided. Opening empty application.");
+                        PolarsViewApp::new(creation_context)
+                    }
+                },
             };
 
             // Handle the result of the application initialization.
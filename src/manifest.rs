@@ -0,0 +1,199 @@
+use crate::{DEFAULT_QUERY, DataContainer, PolarsViewResult};
+
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::{
+    collections::BTreeMap,
+    fs::File,
+    io::BufReader,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Suffix appended to the exported file name to build the sidecar path
+/// (e.g., `extract.csv` -> `extract.csv.manifest.json`).
+pub const MANIFEST_SUFFIX: &str = ".manifest.json";
+
+/// Computes the SHA-256 of a file, streaming its contents.
+///
+/// Returns the digest as a lowercase hexadecimal string.
+pub fn sha256_file(path: impl AsRef<Path>) -> PolarsViewResult<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Options used to read the source file (see `DataFilter`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LoadOptions {
+    pub table_name: String,
+    pub csv_delimiter: String,
+    pub infer_schema_rows: usize,
+    pub null_values: String,
+    pub force_string_patterns: Option<String>,
+}
+
+/// Provenance record written next to an exported file, so extracts remain auditable:
+/// where the data came from, how it was loaded and transformed, and what was written.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExportManifest {
+    /// Application name and version (e.g., `polars-view 0.54.1`).
+    pub created_by: String,
+    /// Export time, in seconds since the Unix epoch.
+    pub exported_at_unix_secs: u64,
+    /// The file the data was loaded from.
+    pub source_path: PathBuf,
+    /// The exported file.
+    pub output_path: PathBuf,
+    pub load_options: LoadOptions,
+    /// The applied SQL query (`None` if the default query was used).
+    pub sql_query: Option<String>,
+    /// Human-readable list of operations (see `DataContainer::applied_operations`).
+    pub transforms: Vec<String>,
+    pub source_rows: usize,
+    pub source_columns: usize,
+    pub exported_rows: usize,
+    pub exported_columns: usize,
+    /// SHA-256 of the exported file contents.
+    pub output_sha256: String,
+    /// Notes about the dataset (see `Annotations`), if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Descriptions of the exported columns, keyed by column name.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub column_descriptions: BTreeMap<String, String>,
+}
+
+impl ExportManifest {
+    /// Builds the manifest for `container` exported to `output_path`.
+    ///
+    /// The exported file must already be written, as its content hash is computed here.
+    pub fn new(container: &DataContainer, output_path: impl AsRef<Path>) -> PolarsViewResult<Self> {
+        let output_path = output_path.as_ref();
+        let filter = &container.filter;
+        let (source_rows, source_columns) = container.df_original.shape();
+        let (exported_rows, exported_columns) = container.df.shape();
+
+        let exported_at_unix_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        Ok(ExportManifest {
+            created_by: format!("{} {}", env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION")),
+            exported_at_unix_secs,
+            source_path: filter.absolute_path.clone(),
+            output_path: output_path.to_path_buf(),
+            load_options: LoadOptions {
+                table_name: filter.table_name.clone(),
+                csv_delimiter: filter.csv_delimiter.clone(),
+                infer_schema_rows: filter.infer_schema_rows,
+                null_values: filter.null_values.clone(),
+                force_string_patterns: filter.force_string_patterns.clone(),
+            },
+            sql_query: (filter.query.trim() != DEFAULT_QUERY.trim()).then(|| filter.query.clone()),
+            transforms: container.applied_operations(),
+            source_rows,
+            source_columns,
+            exported_rows,
+            exported_columns,
+            output_sha256: sha256_file(output_path)?,
+            notes: Some(container.annotations.notes.trim())
+                .filter(|notes| !notes.is_empty())
+                .map(str::to_string),
+            column_descriptions: container.annotations.descriptions_of(container.df.schema()),
+        })
+    }
+
+    /// Returns the sidecar path for an exported file: `<output_path>.manifest.json`.
+    pub fn sidecar_path(output_path: impl AsRef<Path>) -> PathBuf {
+        let mut path = output_path.as_ref().as_os_str().to_owned();
+        path.push(MANIFEST_SUFFIX);
+        PathBuf::from(path)
+    }
+
+    /// Writes the manifest as pretty-printed JSON to `sidecar_path(output_path)`.
+    ///
+    /// Returns the path of the written sidecar.
+    pub fn write_sidecar(&self) -> PolarsViewResult<PathBuf> {
+        let sidecar = Self::sidecar_path(&self.output_path);
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&sidecar, json)?;
+        tracing::info!("Export manifest written to {}", sidecar.display());
+        Ok(sidecar)
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_manifest`
+#[cfg(test)]
+mod tests_manifest {
+    use super::*;
+    use crate::DataFilter;
+    use polars::prelude::*;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sha256_file() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("abc.txt");
+        std::fs::write(&path, "abc")?;
+
+        assert_eq!(
+            sha256_file(&path)?,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_sidecar_path() {
+        assert_eq!(
+            ExportManifest::sidecar_path("/tmp/extract.csv"),
+            PathBuf::from("/tmp/extract.csv.manifest.json")
+        );
+    }
+
+    #[test]
+    fn test_write_sidecar() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let output_path = dir.path().join("extract.csv");
+        std::fs::write(&output_path, "a\n1\n")?;
+
+        let df_original = df!("a" => [1, 2, 3])?;
+        let filter = DataFilter {
+            absolute_path: dir.path().join("source.csv"),
+            query: "SELECT * FROM AllData WHERE a = 1".to_string(),
+            ..Default::default()
+        };
+        let container = DataContainer {
+            df: Arc::new(df!("a" => [1])?),
+            df_original: Arc::new(df_original),
+            filter: Arc::new(filter),
+            ..Default::default()
+        };
+
+        let manifest = ExportManifest::new(&container, &output_path)?;
+        assert_eq!(manifest.source_rows, 3);
+        assert_eq!(manifest.exported_rows, 1);
+        assert_eq!(
+            manifest.sql_query.as_deref(),
+            Some("SELECT * FROM AllData WHERE a = 1")
+        );
+        assert_eq!(manifest.output_sha256, sha256_file(&output_path)?);
+
+        let sidecar = manifest.write_sidecar()?;
+        let json: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(sidecar)?)?;
+        println!("{json:#}");
+        assert_eq!(json["exported_rows"], 1);
+        assert_eq!(json["load_options"]["table_name"], "AllData");
+
+        Ok(())
+    }
+}
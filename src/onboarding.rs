@@ -0,0 +1,199 @@
+//! Empty-state (onboarding) screen of the central panel: open a file, load the bundled
+//! sample dataset, reopen a recent file, and pointers to drag & drop and SQL examples.
+
+use crate::{PolarsViewResult, app_config_dir};
+
+use egui::{Align, Button, Layout, RichText, Ui};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Small sales dataset bundled with the binary (dates, text, numbers, nulls, coordinates).
+pub const SAMPLE_CSV: &str = include_str!("../samples/sales.csv");
+
+/// File name of the sample dataset, written to the temporary directory when loaded.
+pub const SAMPLE_FILE_NAME: &str = "polars-view-sample-sales.csv";
+
+/// Maximum number of files kept in `RecentFiles`.
+pub const MAX_RECENT_FILES: usize = 10;
+
+/// Name of the recent files list, in `app_config_dir()`.
+pub const RECENT_FILES_FILE_NAME: &str = "recent_files.json";
+
+/// Polars SQL reference, linked from the onboarding screen.
+const SQL_DOCS_URL: &str = "https://docs.pola.rs/api/python/stable/reference/sql/index.html";
+
+/// Writes `SAMPLE_CSV` to the temporary directory and returns its path.
+pub fn write_sample_dataset() -> PolarsViewResult<PathBuf> {
+    let path = std::env::temp_dir().join(SAMPLE_FILE_NAME);
+    std::fs::write(&path, SAMPLE_CSV)?;
+    Ok(path)
+}
+
+/// Recently opened files, most recent first, saved under the user config directory.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RecentFiles {
+    pub paths: Vec<PathBuf>,
+}
+
+impl RecentFiles {
+    /// Path of the saved list (`None` if the config directory is unknown).
+    fn config_path() -> Option<PathBuf> {
+        app_config_dir().map(|dir| dir.join(RECENT_FILES_FILE_NAME))
+    }
+
+    /// Reads the list saved by previous runs; errors are logged and give an empty list.
+    pub fn from_config_dir() -> Self {
+        let Some(path) = Self::config_path() else {
+            return Self::default();
+        };
+
+        match std::fs::read_to_string(&path) {
+            Ok(json) => serde_json::from_str(&json).unwrap_or_else(|error| {
+                tracing::warn!("Invalid recent files list {path:?}: {error}");
+                Self::default()
+            }),
+            Err(_) => Self::default(), // Not saved yet.
+        }
+    }
+
+    /// Moves (or adds) `path` to the front, keeping at most `MAX_RECENT_FILES`.
+    ///
+    /// Returns `true` if the list changed.
+    pub fn push(&mut self, path: &Path) -> bool {
+        if self.paths.first().is_some_and(|first| first == path) {
+            return false;
+        }
+
+        self.paths.retain(|p| p != path);
+        self.paths.insert(0, path.to_path_buf());
+        self.paths.truncate(MAX_RECENT_FILES);
+        true
+    }
+
+    /// Saves the list under the user config directory (errors are logged).
+    pub fn save(&self) {
+        let Some(config_path) = Self::config_path() else {
+            return;
+        };
+        let result = config_path
+            .parent()
+            .map_or(Ok(()), std::fs::create_dir_all)
+            .and_then(|_| {
+                let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+                std::fs::write(&config_path, json)
+            });
+        if let Err(error) = result {
+            tracing::warn!("Failed to save the recent files list {config_path:?}: {error}");
+        }
+    }
+}
+
+/// Action requested from the onboarding screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OnboardingAction {
+    /// Show the "Open File" dialog.
+    OpenDialog,
+    /// Open a recent file.
+    Open(PathBuf),
+    /// Load the bundled sample dataset (see `write_sample_dataset`).
+    LoadSample,
+}
+
+/// Renders the onboarding screen shown while no file is loaded.
+pub fn render_onboarding(ui: &mut Ui, recent: &RecentFiles) -> Option<OnboardingAction> {
+    let mut action = None;
+
+    ui.with_layout(Layout::top_down(Align::Center), |ui| {
+        ui.add_space((ui.available_height() * 0.15).min(120.0));
+        ui.heading("PolarsView");
+        ui.label("A fast viewer for CSV, JSON, Parquet, Excel and SQLite data.");
+        ui.add_space(20.0);
+
+        if ui
+            .add(Button::new("📂 Open File...").min_size([220.0, 32.0].into()))
+            .on_hover_text("Ctrl+O")
+            .clicked()
+        {
+            action = Some(OnboardingAction::OpenDialog);
+        }
+        if ui
+            .add(Button::new("📊 Load sample dataset").min_size([220.0, 32.0].into()))
+            .on_hover_text("A small sales table (60 rows) to try sorting, SQL, filters and charts.")
+            .clicked()
+        {
+            action = Some(OnboardingAction::LoadSample);
+        }
+
+        ui.add_space(8.0);
+        ui.label(RichText::new("…or drag & drop files anywhere on this window.").weak());
+        ui.add_space(20.0);
+
+        if !recent.paths.is_empty() {
+            ui.label(RichText::new("Recent files").strong());
+            for path in &recent.paths {
+                let name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string());
+                let exists = path.exists();
+                let response = ui
+                    .add_enabled(exists, egui::Link::new(name))
+                    .on_hover_text(path.display().to_string())
+                    .on_disabled_hover_text(format!("Not found: {}", path.display()));
+                if response.clicked() {
+                    action = Some(OnboardingAction::Open(path.clone()));
+                }
+            }
+            ui.add_space(20.0);
+        }
+
+        ui.label(RichText::new("SQL").strong());
+        ui.label("Once a file is open, \"Query\" > \"SQL Command Examples\" lists queries for its columns.");
+        ui.hyperlink_to("Polars SQL reference", SQL_DOCS_URL);
+    });
+
+    action
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_onboarding`
+#[cfg(test)]
+mod tests_onboarding {
+    use super::*;
+    use polars::prelude::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn recent_files_are_deduplicated_and_capped() {
+        let mut recent = RecentFiles::default();
+        for index in 0..12 {
+            assert!(recent.push(Path::new(&format!("/data/{index}.csv"))));
+        }
+        assert_eq!(recent.paths.len(), MAX_RECENT_FILES);
+        assert_eq!(recent.paths[0], Path::new("/data/11.csv"));
+
+        // Reopening moves the file to the front; the front file is unchanged.
+        assert!(recent.push(Path::new("/data/5.csv")));
+        assert_eq!(recent.paths[0], Path::new("/data/5.csv"));
+        assert_eq!(recent.paths.len(), MAX_RECENT_FILES);
+        assert!(!recent.push(Path::new("/data/5.csv")));
+    }
+
+    #[test]
+    fn sample_dataset_parses() -> PolarsResult<()> {
+        let df = CsvReadOptions::default()
+            .with_has_header(true)
+            .into_reader_with_file_handle(Cursor::new(SAMPLE_CSV))
+            .finish()?;
+
+        assert_eq!(df.height(), 60);
+        assert!(df.column("latitude")?.dtype().is_float());
+        assert!(df.column("discount")?.null_count() > 0);
+        Ok(())
+    }
+}
@@ -0,0 +1,236 @@
+//! Predicate pushdown for Parquet files (the default, in-memory load).
+//!
+//! Instead of reading the whole file and then running the SQL query on it, the query runs
+//! on the Parquet scan: Polars decodes only the columns it needs and skips the row groups
+//! whose statistics can't match the `WHERE` clause. The `LazyFrame` of the query is kept
+//! (`DataContainer::pushdown`).
+//!
+//! The column filters of the filter row that are simple predicates (see
+//! `is_pushable_filter`) run on the scan too, so the filtered-out rows are never loaded;
+//! changing them reads the file again. The other filters run in memory on the loaded rows,
+//! as for the other files. No filter runs on the scan when a load step after the query
+//! needs its whole result (null column removal, row index, categorical conversion), nor on
+//! a column converted to a display unit.
+//!
+//! Used when no load option needs the whole file before the query (see `supports`).
+
+use crate::{
+    ColumnFilters, DataFilter, DataFormat, FileExtension, NamedQuery, PolarsViewResult,
+    SeriesExtension, build_null_expression, column_filters_predicate, execute_polars_blocking,
+    execute_sql, is_encrypted_parquet, is_pushable_filter, unit_conversions,
+};
+
+use polars::prelude::*;
+use std::{fmt, path::PathBuf};
+
+/// The load options a pushed-down query depends on (see `ParquetPushdown::scans`).
+#[derive(Debug, Clone, PartialEq)]
+struct ScanOptions {
+    path: PathBuf,
+    head_rows: Option<usize>,
+    null_values: String,
+    table_name: String,
+    query_steps: Vec<NamedQuery>,
+    query: String,
+}
+
+impl ScanOptions {
+    fn new(filter: &DataFilter) -> Self {
+        ScanOptions {
+            path: filter.absolute_path.clone(),
+            head_rows: filter.head_rows,
+            null_values: filter.null_values.clone(),
+            table_name: filter.table_name.clone(),
+            query_steps: filter.query_steps.clone(),
+            query: filter.query.clone(),
+        }
+    }
+}
+
+/// The SQL query of a Parquet file, run on the scan.
+#[derive(Clone)]
+pub struct ParquetPushdown {
+    /// The scan with the null values and the SQL query applied.
+    query: LazyFrame,
+    /// The schema of the query result.
+    schema: SchemaRef,
+    /// The load options of `query`.
+    options: ScanOptions,
+    /// The column filters run on the scan by the last `collect`.
+    pub filters: ColumnFilters,
+}
+
+impl fmt::Debug for ParquetPushdown {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParquetPushdown").finish_non_exhaustive()
+    }
+}
+
+impl ParquetPushdown {
+    /// Whether the query of `filter` can run on the scan: a plain Parquet file (not in a
+    /// zip archive, not encrypted) without the load steps that need the whole data before
+    /// the query (struct flattening, type overrides, renames, column removal, normalization,
+    /// dates).
+    pub fn supports(filter: &DataFilter) -> bool {
+        filter.source_extension() == FileExtension::Parquet
+            && filter.zip_entry.is_none()
+            && !filter.flatten_structs
+            && filter.column_types.is_empty()
+            && filter.column_renames.is_empty()
+            && !filter.drop
+            && !filter.normalize
+            && !filter.parse_dates
+            && !is_encrypted_parquet(&filter.absolute_path).unwrap_or(true)
+    }
+
+    /// Scans the Parquet file of `filter` (its first `head_rows` rows, in preview mode)
+    /// and applies the null values and the SQL query (with its named steps), lazily.
+    pub fn scan(filter: &DataFilter) -> PolarsViewResult<Self> {
+        let path = PlRefPath::try_from_path(&filter.absolute_path)?;
+        let mut lf = LazyFrame::scan_parquet(path, ScanArgsParquet::default())?;
+
+        if let Some(rows) = filter.head_rows {
+            lf = lf.limit(rows as IdxSize);
+        }
+
+        // Same replacement as `ReplaceNullsTransform` (String columns only).
+        let null_values = filter.parse_null_values();
+        if !null_values.is_empty() {
+            let list = Series::build_from_list(&null_values).lit();
+            lf = lf.with_columns([build_null_expression(list, false)]);
+        }
+
+        let mut query = execute_sql(&filter.table_name, lf, &filter.query_steps, &filter.query)?;
+        let schema = query.collect_schema()?;
+        Ok(ParquetPushdown {
+            query,
+            schema,
+            options: ScanOptions::new(filter),
+            filters: ColumnFilters::new(),
+        })
+    }
+
+    /// Whether this is the scan of the file and the query of `filter` (only the load steps
+    /// after the query changed, so its result can be reused).
+    pub fn scans(&self, filter: &DataFilter) -> bool {
+        self.options == ScanOptions::new(filter)
+    }
+
+    /// The `column_filters` that run on the scan: the simple predicates (see
+    /// `is_pushable_filter`) on the columns of the query result, unless a load step of
+    /// `filter` needs the whole result or the column is converted to a unit of `format`.
+    pub fn pushable(
+        &self,
+        filter: &DataFilter,
+        format: &DataFormat,
+        column_filters: &ColumnFilters,
+    ) -> ColumnFilters {
+        if filter.exclude_null_cols || filter.add_row_index || filter.categorical {
+            return ColumnFilters::new();
+        }
+
+        let converted = unit_conversions(&format.column_units);
+        column_filters
+            .iter()
+            .filter(|(name, text)| {
+                !converted.contains_key(name.as_str())
+                    && self
+                        .schema
+                        .get(name)
+                        .is_some_and(|dtype| is_pushable_filter(dtype, text))
+            })
+            .map(|(name, text)| (name.clone(), text.clone()))
+            .collect()
+    }
+
+    /// The query with `filters` applied on the scan.
+    fn filtered(&self, filters: &ColumnFilters) -> LazyFrame {
+        match column_filters_predicate(&self.schema, filters) {
+            Some(predicate) => self.query.clone().filter(predicate),
+            None => self.query.clone(),
+        }
+    }
+
+    /// Collects the query result with `filters` (see `pushable`) applied on the scan, so
+    /// the filtered-out rows are never loaded.
+    pub async fn collect(&mut self, filters: ColumnFilters) -> PolarsViewResult<DataFrame> {
+        let lf = self.filtered(&filters);
+        let df = execute_polars_blocking(move || lf.with_streaming(true).collect()).await?;
+        tracing::debug!(
+            "Parquet pushdown collected with filters {filters:?}. Shape: {:?}",
+            df.shape()
+        );
+        self.filters = filters;
+        Ok(df)
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_parquet_pushdown`
+#[cfg(test)]
+mod tests_parquet_pushdown {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn query_and_filters_run_on_the_scan() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("sales.parquet");
+        let mut df = df!(
+            "id" => (0..100).collect::<Vec<i64>>(),
+            "city" => (0..100).map(|i| if i % 2 == 0 { "a" } else { "" }).collect::<Vec<_>>()
+        )?;
+        // Row groups of 10 rows: the statistics let the scan skip the groups out of range.
+        ParquetWriter::new(std::fs::File::create(&path)?)
+            .with_row_group_size(Some(10))
+            .finish(&mut df)?;
+
+        let mut filter = DataFilter::default();
+        filter.set_path(&path)?;
+        filter.query = "SELECT * FROM AllData WHERE id >= 90".to_string();
+        assert!(ParquetPushdown::supports(&filter));
+
+        let mut pushdown = ParquetPushdown::scan(&filter)?;
+        assert!(pushdown.scans(&filter));
+        let result = pushdown.collect(ColumnFilters::new()).await?;
+        assert_eq!(result.height(), 10);
+        // Empty strings are null values by default.
+        assert_eq!(result.column("city")?.null_count(), 5);
+
+        // Simple predicates run on the scan; the null filter runs in memory.
+        let format = DataFormat::default();
+        let column_filters = ColumnFilters::from([
+            ("id".to_string(), "<95".to_string()),
+            ("city".to_string(), "is null".to_string()),
+        ]);
+        let pushed = pushdown.pushable(&filter, &format, &column_filters);
+        assert_eq!(
+            pushed,
+            ColumnFilters::from([("id".to_string(), "<95".to_string())])
+        );
+        let plan = pushdown.filtered(&pushed).explain(true)?;
+        assert!(plan.contains("SELECTION"), "{plan}");
+        let result = pushdown.collect(pushed).await?;
+        assert_eq!(result.height(), 5);
+
+        // A load step that needs the whole query result: the filters run in memory.
+        filter.add_row_index = true;
+        assert!(
+            pushdown
+                .pushable(&filter, &format, &column_filters)
+                .is_empty()
+        );
+        assert!(pushdown.scans(&filter));
+        filter.query = "SELECT * FROM AllData".to_string();
+        assert!(!pushdown.scans(&filter));
+
+        // Normalization needs the whole file before the query.
+        filter.normalize = true;
+        assert!(!ParquetPushdown::supports(&filter));
+        Ok(())
+    }
+}
@@ -0,0 +1,234 @@
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// Default maximum ratio of unique values to rows for a String column
+/// to be converted to Categorical.
+pub const DEFAULT_CATEGORICAL_RATIO: f64 = 0.5;
+
+/// Memory report for a String column converted to Categorical.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoricalSaving {
+    /// The converted column.
+    pub column_name: String,
+    /// Number of distinct values (categories).
+    pub unique_values: usize,
+    /// Estimated heap size of the String column, in bytes.
+    pub bytes_before: usize,
+    /// Estimated heap size of the Categorical column, in bytes.
+    pub bytes_after: usize,
+}
+
+impl CategoricalSaving {
+    /// Estimated number of bytes saved by the conversion.
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
+
+/// Formats a byte count with binary units (e.g., `1.5 MiB`).
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+/// Converts high-duplication String columns to Categorical to reduce memory usage.
+///
+/// A String column is converted when `unique values / rows <= max_unique_ratio`
+/// and the estimated size actually shrinks; other columns are kept unchanged.
+///
+/// ### Arguments
+/// * `df`: The input `DataFrame`.
+/// * `max_unique_ratio`: Threshold in `[0, 1]`. Lower values convert fewer columns.
+///
+/// ### Returns
+/// The new `DataFrame` and one `CategoricalSaving` per converted column (in column order).
+pub fn strings_to_categorical(
+    df: DataFrame,
+    max_unique_ratio: f64,
+) -> PolarsResult<(DataFrame, Vec<CategoricalSaving>)> {
+    let height = df.height();
+    let mut savings = Vec::new();
+
+    if height == 0 {
+        return Ok((df, savings));
+    }
+
+    let categorical = DataType::from_categories(Categories::global());
+    let mut columns: Vec<Column> = Vec::with_capacity(df.width());
+
+    for column in df.columns() {
+        if column.dtype().is_string() {
+            let unique_values = column.n_unique()?;
+
+            if unique_values as f64 / height as f64 <= max_unique_ratio {
+                let converted = column.cast(&categorical)?;
+                let bytes_before = column.as_materialized_series().estimated_size();
+                let bytes_after = converted.as_materialized_series().estimated_size();
+
+                if bytes_after < bytes_before {
+                    savings.push(CategoricalSaving {
+                        column_name: column.name().to_string(),
+                        unique_values,
+                        bytes_before,
+                        bytes_after,
+                    });
+                    columns.push(converted);
+                    continue;
+                }
+            }
+        }
+        columns.push(column.clone());
+    }
+
+    tracing::debug!(
+        converted_columns = ?savings.iter().map(|s| &s.column_name).collect::<Vec<_>>(),
+        "{} String columns converted to Categorical",
+        savings.len()
+    );
+
+    Ok((DataFrame::new(height, columns)?, savings))
+}
+
+/// The categories of a Categorical or Enum column (`None` for other types).
+///
+/// - Enum: the declared categories, in their (sort) order, including unused ones.
+///   Read from the data type: no scan of the data.
+/// - Categorical: the categories in use, sorted (the unique physical codes are mapped
+///   back to their strings; no string comparisons).
+pub fn column_categories(column: &Column) -> PolarsResult<Option<Vec<String>>> {
+    match column.dtype() {
+        DataType::Enum(categories, _) => Ok(Some(
+            categories
+                .categories()
+                .values_iter()
+                .map(str::to_string)
+                .collect(),
+        )),
+        DataType::Categorical(..) => {
+            let unique = column.drop_nulls().unique()?.cast(&DataType::String)?;
+            let mut values: Vec<String> = unique
+                .str()?
+                .into_iter()
+                .flatten()
+                .map(str::to_string)
+                .collect();
+            values.sort_unstable();
+            Ok(Some(values))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The categories (see `column_categories`) of each Categorical/Enum column of `df`.
+pub fn categories_by_column(df: &DataFrame) -> PolarsResult<HashMap<String, Vec<String>>> {
+    let mut categories = HashMap::new();
+    for column in df.columns() {
+        if let Some(values) = column_categories(column)? {
+            categories.insert(column.name().to_string(), values);
+        }
+    }
+    Ok(categories)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_strings_to_categorical`
+#[cfg(test)]
+mod tests_strings_to_categorical {
+    use super::*;
+
+    #[test]
+    fn convert_only_high_duplication_columns() -> PolarsResult<()> {
+        let rows = 1000;
+        let repeated: Vec<String> = (0..rows)
+            .map(|i| format!("a long repeated category value {}", i % 3))
+            .collect();
+        let unique: Vec<String> = (0..rows).map(|i| format!("id-{i}")).collect();
+        let numbers: Vec<i64> = (0..rows).collect();
+
+        let df_input = df!(
+            "repeated" => &repeated,
+            "unique" => &unique,
+            "numbers" => &numbers
+        )?;
+
+        let (df_output, savings) = strings_to_categorical(df_input.clone(), 0.5)?;
+        dbg!(&df_output);
+        dbg!(&savings);
+
+        assert!(df_output.column("repeated")?.dtype().is_categorical());
+        assert!(df_output.column("unique")?.dtype().is_string());
+        assert_eq!(df_output.column("numbers")?.dtype(), &DataType::Int64);
+
+        assert_eq!(savings.len(), 1);
+        assert_eq!(savings[0].column_name, "repeated");
+        assert_eq!(savings[0].unique_values, 3);
+        assert!(savings[0].bytes_saved() > 0);
+
+        // Values are preserved.
+        let back = df_output.column("repeated")?.cast(&DataType::String)?;
+        assert!(
+            back.as_materialized_series()
+                .equals(df_input.column("repeated")?.as_materialized_series())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn zero_ratio_converts_nothing() -> PolarsResult<()> {
+        let df_input = df!("a" => &["x", "x", "y"])?;
+        let (df_output, savings) = strings_to_categorical(df_input.clone(), 0.0)?;
+
+        assert!(savings.is_empty());
+        assert!(df_output.equals(&df_input));
+        Ok(())
+    }
+
+    #[test]
+    fn categories_of_categorical_and_enum_columns() -> PolarsResult<()> {
+        let categorical = DataType::from_categories(Categories::global());
+        let levels =
+            DataType::from_frozen_categories(FrozenCategories::new(["low", "mid", "high"])?);
+
+        let df = df!(
+            "city" => &[Some("Porto"), Some("Lisbon"), None, Some("Porto")],
+            "level" => &["high", "low", "high", "low"],
+            "name" => &["a", "b", "c", "d"],
+        )?
+        .lazy()
+        .with_columns([col("city").cast(categorical), col("level").cast(levels)])
+        .collect()?;
+
+        // Sorting keeps the data types, hence the categories.
+        let df = df.sort(["level"], SortMultipleOptions::default())?;
+        let categories = categories_by_column(&df)?;
+
+        assert_eq!(categories.len(), 2);
+        assert_eq!(categories["city"], ["Lisbon", "Porto"]);
+        assert_eq!(categories["level"], ["low", "mid", "high"]); // Declared order, "mid" unused.
+        Ok(())
+    }
+
+    #[test]
+    fn format_bytes_units() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
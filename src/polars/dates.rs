@@ -0,0 +1,154 @@
+use crate::{PolarsViewError, PolarsViewResult};
+use polars::prelude::*;
+use regex::Regex;
+
+/// Default date format (`strftime` syntax) of the date parsing step: day/month/year.
+pub const DEFAULT_DATE_FORMAT: &str = "%d/%m/%Y";
+
+/// Format specifiers that carry a time of day: a format with one of them is parsed to
+/// `Datetime`, a format without them to `Date`.
+const TIME_SPECIFIERS: [&str; 9] = ["%H", "%I", "%M", "%S", "%T", "%R", "%c", "%s", "%f"];
+
+/// Returns `true` if the `strftime` format `date_format` has a time of day
+/// (e.g., `%d/%m/%Y %H:%M`), so the columns are parsed to `Datetime`.
+pub fn format_has_time(date_format: &str) -> bool {
+    TIME_SPECIFIERS
+        .iter()
+        .any(|specifier| date_format.contains(specifier))
+}
+
+/// Parses the String columns whose names match `regex_pattern` as dates, with the
+/// `strftime` format `date_format` (e.g., `%d/%m/%Y`), since the automatic date parsing of
+/// Polars only knows the ISO formats.
+///
+/// A format with a time of day (see `format_has_time`) gives `Datetime` columns
+/// (milliseconds), the others `Date` columns. Values that don't match the format become null.
+///
+/// ### Time Zone
+/// `time_zone` tells in which zone the written times are: empty keeps them as written (local
+/// time, no zone), `UTC` or an IANA name (e.g., `America/Sao_Paulo`) tags them with that
+/// zone. It doesn't apply to `Date` columns.
+///
+/// ### Regex Pattern Requirements
+/// Same as `normalize_float_strings_by_regex`: `"*"` (all String columns) or a regex that
+/// starts with `^` and ends with `$`. Matching columns that are not String (e.g., already
+/// parsed) are left unchanged.
+///
+/// ### Returns
+/// The `DataFrame` with the parsed columns, or:
+/// * `PolarsViewError::InvalidRegexPattern` / `InvalidRegexSyntax` for a bad pattern.
+/// * `PolarsViewError::Polars` for an invalid time zone or a failed parse.
+pub fn parse_dates_by_regex(
+    df: DataFrame,
+    regex_pattern: &str,
+    date_format: &str,
+    time_zone: &str,
+) -> PolarsViewResult<DataFrame> {
+    // --- 1. Compile Regex and Validate Pattern ---
+    let compiled_regex: Option<Regex> = match regex_pattern {
+        "*" => None,
+        pattern => {
+            if !(pattern.starts_with('^') && pattern.ends_with('$')) {
+                return Err(PolarsViewError::InvalidRegexPattern(pattern.to_string()));
+            }
+            let re = Regex::new(pattern).map_err(|e| PolarsViewError::InvalidRegexSyntax {
+                pattern: pattern.to_string(),
+                error: e.to_string(),
+            })?;
+            Some(re)
+        }
+    };
+
+    // --- 2. Identify the String Columns to Parse ---
+    let columns_to_parse: Vec<PlSmallStr> = df
+        .schema()
+        .iter()
+        .filter(|(col_name, dtype)| {
+            *dtype == &DataType::String
+                && compiled_regex
+                    .as_ref()
+                    .is_none_or(|re| re.is_match(col_name))
+        })
+        .map(|(col_name, _dtype)| col_name.clone())
+        .collect();
+
+    if columns_to_parse.is_empty() {
+        tracing::debug!("No String columns matching regex '{regex_pattern}' to parse as dates.");
+        return Ok(df);
+    }
+
+    tracing::debug!(
+        "Parsing {:?} as dates with format '{date_format}' (time zone '{time_zone}')",
+        columns_to_parse
+    );
+
+    // --- 3. Build and Run the Parse Expressions ---
+    let options = StrptimeOptions {
+        format: Some(date_format.into()),
+        strict: false, // Values that don't match become null.
+        exact: true,
+        cache: true,
+    };
+
+    let time_zone = match time_zone.trim() {
+        "" => None,
+        zone => TimeZone::opt_try_new(Some(zone))?,
+    };
+
+    let expressions: Vec<Expr> = columns_to_parse
+        .iter()
+        .map(|col_name| {
+            let column = col(col_name.clone()).str();
+            if format_has_time(date_format) {
+                column.to_datetime(
+                    Some(TimeUnit::Milliseconds),
+                    time_zone.clone(),
+                    options.clone(),
+                    lit("raise"),
+                )
+            } else {
+                column.to_date(options.clone())
+            }
+        })
+        .collect();
+
+    Ok(df.lazy().with_columns(expressions).collect()?)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_dates`
+#[cfg(test)]
+mod tests_dates {
+    use super::*;
+
+    #[test]
+    fn regional_dates_are_parsed() -> PolarsViewResult<()> {
+        let df = df!(
+            "Data Emissão" => &["31/01/2024", "29/02/2024", "n/d"],
+            "Data Hora" => &["31/01/2024 08:30", "29/02/2024 17:05", "31/12/2024 23:59"],
+            "Valor" => &["1", "2", "3"]
+        )?;
+
+        let dates = parse_dates_by_regex(df.clone(), "^Data Emissão$", "%d/%m/%Y", "")?;
+        println!("{dates}");
+        assert_eq!(dates.column("Data Emissão")?.dtype(), &DataType::Date);
+        assert_eq!(dates.column("Data Emissão")?.null_count(), 1);
+        assert_eq!(dates.column("Valor")?.dtype(), &DataType::String);
+
+        let times = parse_dates_by_regex(df, "^Data Hora$", "%d/%m/%Y %H:%M", "UTC")?;
+        println!("{times}");
+        assert!(matches!(
+            times.column("Data Hora")?.dtype(),
+            DataType::Datetime(TimeUnit::Milliseconds, Some(zone)) if zone.as_str() == "UTC"
+        ));
+        assert_eq!(times.column("Data Hora")?.null_count(), 0);
+
+        assert!(format_has_time("%Y-%m-%dT%H:%M:%S"));
+        assert!(!format_has_time("%d/%m/%Y"));
+        Ok(())
+    }
+}
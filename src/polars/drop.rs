@@ -2,6 +2,57 @@ use crate::{PolarsViewError, PolarsViewResult};
 use polars::prelude::*;
 use regex::Regex;
 
+/// Compiles the pattern of the dropped columns: the wildcard `"*"` (all columns) or a
+/// regex enclosed in `^` and `$` anchors (see `drop_columns_by_regex`).
+///
+/// # Errors
+/// * `PolarsViewError::InvalidRegexPattern`: If `regex_pattern` is not `"*"` and does not
+///   start with `^` and end with `$`.
+/// * `PolarsViewError::InvalidRegexSyntax`: If `regex_pattern` has invalid regex syntax.
+pub fn compile_drop_regex(regex_pattern: &str) -> PolarsViewResult<Regex> {
+    let pattern = match regex_pattern {
+        // The wildcard matches all column names.
+        "*" => "(?s)^.*$",
+        // Validate the required ^...$ format *before* compiling
+        pattern if pattern.starts_with('^') && pattern.ends_with('$') => pattern,
+        pattern => return Err(PolarsViewError::InvalidRegexPattern(pattern.to_string())),
+    };
+
+    Regex::new(pattern).map_err(|e| PolarsViewError::InvalidRegexSyntax {
+        pattern: regex_pattern.to_string(),
+        error: e.to_string(),
+    })
+}
+
+/// The compiled drop regex of a `DataFilter`, compiled again only when the pattern changes
+/// (`DataFilter::example_columns` runs every frame).
+///
+/// Kept in `DataFilter`, whose change detection it does not affect: two caches are always
+/// equal.
+#[derive(Debug, Clone, Default)]
+pub struct DropRegexCache(Option<(String, Option<Regex>)>);
+
+impl PartialEq for DropRegexCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl DropRegexCache {
+    /// The compiled `regex_pattern` (see `compile_drop_regex`), `None` if invalid.
+    pub fn regex(&mut self, regex_pattern: &str) -> Option<Regex> {
+        if self
+            .0
+            .as_ref()
+            .is_none_or(|(pattern, _regex)| pattern != regex_pattern)
+        {
+            let regex = compile_drop_regex(regex_pattern).ok();
+            self.0 = Some((regex_pattern.to_string(), regex));
+        }
+        self.0.as_ref().and_then(|(_pattern, regex)| regex.clone())
+    }
+}
+
 /// Drops columns from a DataFrame whose names match the provided regex pattern.
 ///
 /// The pattern must either be the wildcard `"*"` (to drop all columns)
@@ -23,45 +74,16 @@ use regex::Regex;
 /// * `PolarsViewError::Polars`: If the underlying Polars `drop` or `collect` operation fails.
 pub fn drop_columns_by_regex(df: DataFrame, regex_pattern: &str) -> PolarsViewResult<DataFrame> {
     // --- 1. Compile Regex and Validate Pattern ---
-    // Handles validation and compilation in one step.
-    let compiled_regex: Option<Regex> = match regex_pattern {
-        // Handle the wildcard case separately
-        "*" => None,
-        // Handle specific regex patterns
-        pattern => {
-            // Validate the required ^...$ format *before* compiling
-            if pattern.starts_with('^') && pattern.ends_with('$') {
-                // Attempt to compile the regex
-                match Regex::new(pattern) {
-                    Ok(re) => Some(re),
-                    Err(e) => {
-                        // Return specific error for invalid syntax
-                        return Err(PolarsViewError::InvalidRegexSyntax {
-                            pattern: pattern.to_string(),
-                            error: e.to_string(),
-                        });
-                    }
-                }
-            } else {
-                return Err(PolarsViewError::InvalidRegexPattern(pattern.to_string()));
-            }
-        }
-    };
+    let compiled_regex = compile_drop_regex(regex_pattern)?;
 
-    tracing::debug!("Compiled regex (None for wildcard): {:?}", compiled_regex);
+    tracing::debug!("Compiled regex: {:?}", compiled_regex);
 
     // --- 2. Identify Columns to Drop ---
     // Efficiently collect names matching the pattern directly into the vector.
     let columns_to_drop: Vec<PlSmallStr> = df // Iterate over columns directly from the DataFrame
         .get_column_names_owned()
         .into_iter() // Convert Vec into an iterator
-        .filter(|col_name| {
-            // Match against the compiled regex or wildcard
-            match &compiled_regex {
-                Some(re) => re.is_match(col_name), // Check regex match
-                None => true,                      // Wildcard "*" matches all columns
-            }
-        })
+        .filter(|col_name| compiled_regex.is_match(col_name)) // Check regex match
         .collect(); // Collect the PlSmallStr into a Vec
 
     // --- 3. Deterministic Handling of Results ---
@@ -332,4 +354,18 @@ mod tests_drop_cols {
         assert!(df_output.columns().is_empty()); // Double check it's empty
         Ok(())
     }
+
+    #[test]
+    /// cargo test -- --show-output test_drop_regex_cache
+    fn test_drop_regex_cache() {
+        let mut cache = DropRegexCache::default();
+
+        let regex = cache.regex("^Value_.*$").expect("valid pattern");
+        assert!(regex.is_match("Value_A"));
+        assert!(cache.regex("^Value_.*$").is_some());
+
+        // A new pattern is compiled again; an invalid one gives `None`.
+        assert!(cache.regex("Value_A").is_none());
+        assert!(cache.regex("*").is_some_and(|regex| regex.is_match("ID")));
+    }
 }
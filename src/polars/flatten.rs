@@ -0,0 +1,99 @@
+use polars::prelude::*;
+
+/// Separator between the parent and the field names of flattened Struct columns
+/// (e.g., `address.city`).
+pub const FLATTEN_SEPARATOR: &str = ".";
+
+/// Returns `true` if `df` has a single column of type Struct: a standard JSON file with
+/// an array of nested objects is read like this, and is better shown flattened
+/// (see `flatten_struct_columns`).
+pub fn is_single_struct(df: &DataFrame) -> bool {
+    matches!(df.columns(), [column] if matches!(column.dtype(), DataType::Struct(_)))
+}
+
+/// Replaces every Struct column of `df` by its fields, recursively, named with the path
+/// of the field joined by `FLATTEN_SEPARATOR` (e.g., `customer` with a nested `address`
+/// struct gives `customer.address.city`). The other columns keep their position.
+///
+/// ### Returns
+/// The flattened `DataFrame`, or a `PolarsError` if the flattened names collide.
+pub fn flatten_struct_columns(df: DataFrame) -> PolarsResult<DataFrame> {
+    let mut columns = Vec::with_capacity(df.width());
+    for column in df.columns() {
+        flatten_column(column.as_materialized_series(), &mut columns)?;
+    }
+    tracing::debug!(
+        "flatten_struct_columns: width {} -> {}",
+        df.width(),
+        columns.len()
+    );
+    DataFrame::new(df.height(), columns)
+}
+
+/// Pushes `series` (or its fields, recursively, if it is a Struct) to `columns`.
+fn flatten_column(series: &Series, columns: &mut Vec<Column>) -> PolarsResult<()> {
+    if !matches!(series.dtype(), DataType::Struct(_)) {
+        columns.push(series.clone().into_column());
+        return Ok(());
+    }
+
+    let parent = series.name();
+    for field in series.struct_()?.fields_as_series() {
+        let name = format!("{parent}{FLATTEN_SEPARATOR}{}", field.name());
+        flatten_column(&field.with_name(name.into()), columns)?;
+    }
+    Ok(())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_flatten`
+#[cfg(test)]
+mod tests_flatten {
+    use super::*;
+    use crate::PolarsViewResult;
+
+    #[test]
+    fn nested_structs_are_flattened() -> PolarsViewResult<()> {
+        // Like `[{"id": 1, "customer": {"name": "Ana", "address": {"city": "Porto"}}}, ...]`.
+        let df = df!(
+            "id" => &[1, 2],
+            "name" => &["Ana", "Rui"],
+            "city" => &["Porto", "Braga"]
+        )?
+        .lazy()
+        .select([as_struct(vec![
+            col("id"),
+            as_struct(vec![
+                col("name"),
+                as_struct(vec![col("city")]).alias("address"),
+            ])
+            .alias("customer"),
+        ])
+        .alias("order")])
+        .collect()?;
+        assert!(is_single_struct(&df));
+
+        let flat = flatten_struct_columns(df)?;
+        println!("{flat}");
+        assert!(!is_single_struct(&flat));
+        assert_eq!(
+            flat.get_column_names(),
+            [
+                "order.id",
+                "order.customer.name",
+                "order.customer.address.city"
+            ]
+        );
+        let cities: Vec<Option<&str>> = flat
+            .column("order.customer.address.city")?
+            .str()?
+            .into_iter()
+            .collect();
+        assert_eq!(cities, [Some("Porto"), Some("Braga")]);
+        Ok(())
+    }
+}
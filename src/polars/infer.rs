@@ -0,0 +1,172 @@
+use polars::prelude::*;
+
+/// Data types tried, in order, for String columns (see `infer_string_dtypes`).
+///
+/// Integers come before floats and dates before datetimes, so the narrowest type wins.
+pub const INFERRED_DTYPES: [DataType; 5] = [
+    DataType::Int64,
+    DataType::Float64,
+    DataType::Boolean,
+    DataType::Date,
+    DataType::Datetime(TimeUnit::Microseconds, None),
+];
+
+/// The values of the String column `name`, trimmed, with empty strings as nulls.
+fn trimmed_values(name: &str) -> Expr {
+    let value = col(name).str().strip_chars(lit(NULL));
+    when(value.clone().eq(lit("")))
+        .then(lit(NULL).cast(DataType::String))
+        .otherwise(value)
+}
+
+/// Parses the String column `name` as `dtype` (one of `INFERRED_DTYPES`);
+/// values that do not parse become null.
+///
+/// Date/datetime formats are inferred by Polars from the values.
+fn parse_expr(name: &str, dtype: &DataType) -> Expr {
+    let value = trimmed_values(name);
+    let temporal = StrptimeOptions {
+        format: None,
+        strict: false,
+        exact: true,
+        ..Default::default()
+    };
+
+    match dtype {
+        DataType::Boolean => {
+            let lower = value.str().to_lowercase();
+            when(lower.clone().eq(lit("true")))
+                .then(lit(true))
+                .when(lower.eq(lit("false")))
+                .then(lit(false))
+                .otherwise(lit(NULL).cast(DataType::Boolean))
+        }
+        DataType::Date => value.str().to_date(temporal),
+        DataType::Datetime(time_unit, _) => {
+            value
+                .str()
+                .to_datetime(Some(*time_unit), None, temporal, lit("raise"))
+        }
+        dtype => value.cast(dtype.clone()),
+    }
+}
+
+/// Whether every (trimmed, non-empty) value of the String column `name` of `df`
+/// parses as `dtype`, with at least one such value.
+fn parses_as(df: &DataFrame, name: &str, dtype: &DataType) -> bool {
+    let counts = df
+        .clone()
+        .lazy()
+        .select([
+            trimmed_values(name).null_count().alias("values"),
+            parse_expr(name, dtype).null_count().alias("parsed"),
+        ])
+        .collect();
+
+    // Formats that cannot be inferred are errors: the type does not apply.
+    let Ok(counts) = counts else {
+        return false;
+    };
+    let get = |field: &str| {
+        counts
+            .column(field)
+            .ok()
+            .and_then(|column| column.cast(&DataType::UInt64).ok())
+            .and_then(|column| column.u64().ok()?.get(0))
+    };
+
+    match (get("values"), get("parsed")) {
+        (Some(values), Some(parsed)) => values == parsed && (values as usize) < df.height(),
+        _ => false,
+    }
+}
+
+/// Finds the String columns of `df` whose values all parse as a more specific type
+/// (see `INFERRED_DTYPES`), e.g. after a SQL query or text transforms produced text.
+///
+/// Values are trimmed; empty strings count as nulls. Columns with only nulls are skipped.
+///
+/// ### Returns
+/// The column names and their inferred types, in column order.
+pub fn infer_string_dtypes(df: &DataFrame) -> Vec<(String, DataType)> {
+    df.columns()
+        .iter()
+        .filter(|column| column.dtype().is_string())
+        .filter_map(|column| {
+            let name = column.name().as_str();
+            INFERRED_DTYPES
+                .iter()
+                .find(|dtype| parses_as(df, name, dtype))
+                .map(|dtype| (name.to_string(), dtype.clone()))
+        })
+        .collect()
+}
+
+/// Converts the String columns of `df` to the `dtypes` found by `infer_string_dtypes`.
+///
+/// Columns missing from `df` are skipped; values that do not parse become null.
+pub fn cast_string_columns(
+    df: DataFrame,
+    dtypes: &[(String, DataType)],
+) -> PolarsResult<DataFrame> {
+    let schema = df.schema().clone();
+    let exprs: Vec<Expr> = dtypes
+        .iter()
+        .filter(|(name, _)| schema.get(name).is_some_and(DataType::is_string))
+        .map(|(name, dtype)| parse_expr(name, dtype).alias(name.as_str()))
+        .collect();
+
+    if exprs.is_empty() {
+        return Ok(df);
+    }
+    df.lazy().with_columns(exprs).collect()
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_infer`
+#[cfg(test)]
+mod tests_infer {
+    use super::*;
+
+    #[test]
+    fn infer_and_cast_string_columns() -> PolarsResult<()> {
+        let df_input = df!(
+            "int" => &[Some(" 1"), Some("20"), None, Some("")],
+            "float" => &[Some("1.5"), Some("2"), Some("-3e2"), None],
+            "bool" => &[Some("true"), Some("False"), Some("TRUE"), None],
+            "date" => &[Some("2024-01-31"), Some("2024-02-29"), None, Some("2025-12-01")],
+            "text" => &[Some("1"), Some("x"), Some("2"), Some("3")],
+            "empty" => &[None::<&str>, Some(""), None, None],
+            "number" => &[1, 2, 3, 4]
+        )?;
+
+        let dtypes = infer_string_dtypes(&df_input);
+        dbg!(&dtypes);
+
+        assert_eq!(
+            dtypes,
+            vec![
+                ("int".to_string(), DataType::Int64),
+                ("float".to_string(), DataType::Float64),
+                ("bool".to_string(), DataType::Boolean),
+                ("date".to_string(), DataType::Date),
+            ]
+        );
+
+        let df_output = cast_string_columns(df_input, &dtypes)?;
+        dbg!(&df_output);
+
+        let ints: Vec<Option<i64>> = df_output.column("int")?.i64()?.into_iter().collect();
+        assert_eq!(ints, [Some(1), Some(20), None, None]);
+        assert_eq!(df_output.column("float")?.f64()?.get(2), Some(-300.0));
+        assert_eq!(df_output.column("bool")?.bool()?.get(1), Some(false));
+        assert_eq!(df_output.column("date")?.dtype(), &DataType::Date);
+        assert!(df_output.column("text")?.dtype().is_string());
+        assert!(df_output.column("empty")?.dtype().is_string());
+        Ok(())
+    }
+}
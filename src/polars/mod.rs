@@ -1,5 +1,9 @@
 pub mod add;
+pub mod categorical;
+pub mod dates;
 pub mod drop;
+pub mod flatten;
+pub mod infer;
 pub mod normalize;
 pub mod remove;
 pub mod replace;
@@ -1,8 +1,9 @@
 use crate::{
-    DataFilter, PolarsViewError, PolarsViewResult, add_row_index_column, drop_columns_by_regex,
-    normalize_float_strings_by_regex, remove_null_columns, replace_values_with_null,
+    DataFilter, PolarsViewError, PolarsViewResult, cast_column_types, drop_columns_by_regex,
+    execute_sql, flatten_struct_columns, normalize_float_strings_by_regex, parse_dates_by_regex,
+    remove_null_columns, rename_columns, replace_values_with_null,
 };
-use polars::{prelude::*, sql::SQLContext};
+use polars::prelude::*;
 
 /// Represents a single data transformation step applied to a DataFrame.
 /// Implementors define a specific transformation and apply it.
@@ -19,6 +20,52 @@ pub trait DataFrameTransform: Send + Sync {
 
 // --- Concrete Transformation Strategy Structs ---
 
+/// Transformation strategy to flatten Struct columns into one column per field.
+/// Active when `filter.flatten_structs` is true.
+pub struct FlattenStructsTransform;
+
+impl DataFrameTransform for FlattenStructsTransform {
+    /// Replaces the Struct columns by their fields (see `flatten_struct_columns`).
+    /// Assumes this transform is active.
+    fn apply(&self, df: DataFrame, filter: &DataFilter) -> PolarsViewResult<DataFrame> {
+        let _ = filter;
+        tracing::debug!("Applying FlattenStructsTransform...");
+        flatten_struct_columns(df).map_err(PolarsViewError::from)
+    }
+}
+
+/// Transformation strategy to cast columns to the data types picked in the type editor.
+/// Active when `filter.column_types` is not empty.
+pub struct CastColumnTypesTransform;
+
+impl DataFrameTransform for CastColumnTypesTransform {
+    /// Casts the columns of `filter.column_types` (see `cast_column_types`).
+    /// Assumes this transform is active.
+    fn apply(&self, df: DataFrame, filter: &DataFilter) -> PolarsViewResult<DataFrame> {
+        tracing::debug!(
+            "Applying CastColumnTypesTransform: {:?}",
+            filter.column_types
+        );
+        cast_column_types(df, &filter.column_types)
+    }
+}
+
+/// Transformation strategy to rename columns as edited in the Columns panel.
+/// Active when `filter.column_renames` is not empty.
+pub struct RenameColumnsTransform;
+
+impl DataFrameTransform for RenameColumnsTransform {
+    /// Renames the columns of `filter.column_renames` (see `rename_columns`).
+    /// Assumes this transform is active.
+    fn apply(&self, df: DataFrame, filter: &DataFilter) -> PolarsViewResult<DataFrame> {
+        tracing::debug!(
+            "Applying RenameColumnsTransform: {:?}",
+            filter.column_renames
+        );
+        rename_columns(df, &filter.column_renames)
+    }
+}
+
 /// Transformation strategy to drop columns matching a regex.
 /// Active when `filter.drop` is true.
 pub struct DropColumnsTransform;
@@ -51,6 +98,29 @@ impl DataFrameTransform for NormalizeTransform {
     }
 }
 
+/// Transformation strategy to parse String columns as dates with a given format.
+/// Active when `filter.parse_dates` is true.
+pub struct ParseDatesTransform;
+
+impl DataFrameTransform for ParseDatesTransform {
+    /// Parses the columns selected by the filter's date regex (see `parse_dates_by_regex`).
+    /// Assumes this transform is active.
+    fn apply(&self, df: DataFrame, filter: &DataFilter) -> PolarsViewResult<DataFrame> {
+        tracing::debug!(
+            "Applying ParseDatesTransform (regex: '{}', format: '{}', time zone: '{}')",
+            filter.date_regex,
+            filter.date_format,
+            filter.date_time_zone
+        );
+        parse_dates_by_regex(
+            df,
+            &filter.date_regex,
+            &filter.date_format,
+            &filter.date_time_zone,
+        )
+    }
+}
+
 /// Transformation strategy to replace specific string values with nulls.
 /// Always added to the pipeline, but the actual replacement logic inside checks
 /// if there are any values configured in `filter.null_values`.
@@ -85,11 +155,14 @@ impl DataFrameTransform for SqlTransform {
     /// Assumes this transform is active.
     fn apply(&self, df: DataFrame, filter: &DataFilter) -> PolarsViewResult<DataFrame> {
         tracing::debug!("Applying SqlTransform...");
-        let mut ctx = SQLContext::new();
-        ctx.register(&filter.table_name, df.lazy());
-        ctx.execute(&filter.query)?
-            .collect()
-            .map_err(crate::PolarsViewError::from)
+        execute_sql(
+            &filter.table_name,
+            df.lazy(),
+            &filter.query_steps,
+            &filter.query,
+        )?
+        .collect()
+        .map_err(crate::PolarsViewError::from)
     }
 }
 
@@ -112,17 +185,3 @@ impl DataFrameTransform for RemoveNullColumnsTransform {
         Ok(result_df)
     }
 }
-
-/// Transformation strategy to add a row index column.
-/// Active when `filter.add_row_index` is true.
-pub struct AddRowIndexTransform;
-
-impl DataFrameTransform for AddRowIndexTransform {
-    /// Adds a row index column based on filter configuration.
-    /// Assumes this transform is active.
-    fn apply(&self, df: DataFrame, filter: &DataFilter) -> PolarsViewResult<DataFrame> {
-        tracing::debug!("Applying AddRowIndexTransform...");
-        let row_index = filter.get_row_index(df.schema())?;
-        add_row_index_column(df, row_index).map_err(PolarsViewError::from)
-    }
-}
@@ -0,0 +1,244 @@
+//! Named presets of query/filter/format settings (`ViewState`s), saved as JSON under the
+//! user config directory (`<config dir>/polars-view/presets.json`), with Save/Load/Delete
+//! controls in the "Presets" side panel section.
+
+use crate::{DataContainer, PolarsViewResult, ViewState};
+
+use egui::{ComboBox, TextEdit, Ui};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// Name of the presets file, in `app_config_dir()`.
+pub const PRESETS_FILE_NAME: &str = "presets.json";
+
+/// Safe mode (`--safe-mode`, see `set_safe_mode`).
+static SAFE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enables or disables safe mode: persisted state (last session, settings, presets,
+/// recent files and `.polars-view.toml` directory configs) is neither read nor written,
+/// so a corrupted file cannot break startup.
+pub fn set_safe_mode(enabled: bool) {
+    SAFE_MODE.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether safe mode is enabled (see `set_safe_mode`).
+pub fn is_safe_mode() -> bool {
+    SAFE_MODE.load(Ordering::Relaxed)
+}
+
+/// The polars-view configuration directory (not created):
+/// - Linux: `$XDG_CONFIG_HOME/polars-view` or `~/.config/polars-view`
+/// - macOS: `~/Library/Application Support/polars-view`
+/// - Windows: `%APPDATA%\polars-view`
+///
+/// Returns `None` if the home directory cannot be determined, or in safe mode.
+pub fn app_config_dir() -> Option<PathBuf> {
+    if is_safe_mode() {
+        return None;
+    }
+
+    let env_dir = |name: &str| {
+        std::env::var_os(name)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+    };
+
+    let base = if cfg!(target_os = "windows") {
+        env_dir("APPDATA")?
+    } else if cfg!(target_os = "macos") {
+        env_dir("HOME")?.join("Library").join("Application Support")
+    } else {
+        env_dir("XDG_CONFIG_HOME").or_else(|| Some(env_dir("HOME")?.join(".config")))?
+    };
+
+    Some(base.join(env!("CARGO_PKG_NAME")))
+}
+
+/// Saved presets, by name (sorted).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PresetStore {
+    pub presets: BTreeMap<String, ViewState>,
+}
+
+impl PresetStore {
+    /// Reads the presets from the JSON file at `path`. A missing file is an empty store.
+    pub fn load(path: impl AsRef<Path>) -> PolarsViewResult<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(json) => Ok(serde_json::from_str(&json)?),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Writes the presets as JSON to `path`, creating its directory if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> PolarsViewResult<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+/// State of the "Presets" side panel section.
+#[derive(Debug, Default)]
+pub struct PresetPanel {
+    /// Where presets are saved (`None` if the config directory is unknown).
+    pub path: Option<PathBuf>,
+    store: PresetStore,
+    /// Name typed for "Save".
+    name: String,
+    /// Preset chosen for "Load"/"Delete".
+    selected: String,
+    /// Result of the last action (message, is_error).
+    status: Option<(String, bool)>,
+}
+
+impl PresetPanel {
+    /// Loads the presets saved under `app_config_dir()`; read errors are logged
+    /// and leave the panel empty.
+    pub fn from_config_dir() -> Self {
+        let path = app_config_dir().map(|dir| dir.join(PRESETS_FILE_NAME));
+        let store = match &path {
+            Some(path) => PresetStore::load(path).unwrap_or_else(|error| {
+                tracing::warn!("Failed to read presets from {path:?}: {error}");
+                PresetStore::default()
+            }),
+            None => PresetStore::default(),
+        };
+
+        PresetPanel {
+            path,
+            store,
+            ..Default::default()
+        }
+    }
+
+    /// Writes the store to `path`, reporting the outcome `message` in the panel.
+    fn persist(&mut self, message: String) {
+        let result = match &self.path {
+            Some(path) => self.store.save(path),
+            None if is_safe_mode() => Err("safe mode".to_string().into()),
+            None => Err("unknown config directory".to_string().into()),
+        };
+        self.status = Some(match result {
+            Ok(()) => (message, false),
+            Err(error) => (format!("Failed to save presets: {error}"), true),
+        });
+    }
+
+    /// Renders Save (current view of `container` under a name), Load and Delete.
+    ///
+    /// Returns the preset to apply when "Load" is clicked.
+    pub fn render(&mut self, ui: &mut Ui, container: &DataContainer) -> Option<ViewState> {
+        let mut load = None;
+
+        ui.horizontal(|ui| {
+            ui.add(
+                TextEdit::singleline(&mut self.name)
+                    .hint_text("Preset name")
+                    .desired_width(160.0),
+            );
+            let name = self.name.trim().to_string();
+            if ui
+                .add_enabled(!name.is_empty(), egui::Button::new("Save"))
+                .on_hover_text("Save the current SQL query, load options, sorting and format.")
+                .clicked()
+            {
+                let replaced = self
+                    .store
+                    .presets
+                    .insert(name.clone(), ViewState::from_container(container))
+                    .is_some();
+                self.selected = name.clone();
+                let action = if replaced { "updated" } else { "saved" };
+                self.persist(format!("Preset \"{name}\" {action}."));
+            }
+        });
+
+        if self.store.presets.is_empty() {
+            ui.label("No saved presets.");
+        } else {
+            if !self.store.presets.contains_key(&self.selected) {
+                self.selected = self
+                    .store
+                    .presets
+                    .keys()
+                    .next()
+                    .cloned()
+                    .unwrap_or_default();
+            }
+
+            ui.horizontal(|ui| {
+                ComboBox::from_id_salt("presets_selected")
+                    .selected_text(&self.selected)
+                    .show_ui(ui, |ui| {
+                        for name in self.store.presets.keys() {
+                            ui.selectable_value(&mut self.selected, name.clone(), name);
+                        }
+                    });
+
+                if ui.button("Load").clicked() {
+                    load = self.store.presets.get(&self.selected).cloned();
+                    self.status = Some((format!("Preset \"{}\" loaded.", self.selected), false));
+                }
+                if ui.button("Delete").clicked() {
+                    let name = std::mem::take(&mut self.selected);
+                    self.store.presets.remove(&name);
+                    self.persist(format!("Preset \"{name}\" deleted."));
+                }
+            });
+        }
+
+        if let Some((message, is_error)) = &self.status {
+            if *is_error {
+                ui.colored_label(ui.visuals().error_fg_color, message);
+            } else {
+                ui.label(message);
+            }
+        }
+
+        load
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_presets`
+#[cfg(test)]
+mod tests_presets {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn store_round_trip() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("config").join(PRESETS_FILE_NAME);
+
+        // A missing file is an empty store.
+        assert_eq!(PresetStore::load(&path)?, PresetStore::default());
+
+        let mut store = PresetStore::default();
+        store.presets.insert(
+            "monthly".to_string(),
+            ViewState {
+                query: "SELECT * FROM AllData WHERE month = 1;".to_string(),
+                decimal: 4,
+                ..Default::default()
+            },
+        );
+        store.save(&path)?;
+
+        assert_eq!(PresetStore::load(&path)?, store);
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+//! Row-limit preview mode (`--head N`, "Preview Rows" in the Query panel): only the first
+//! rows of the file are read (see `DataFilter::head_rows`), so massive files open at once.
+//! A banner above the table tells the view is partial and offers to load the full file.
+
+use crate::DataContainer;
+
+use egui::{Color32, RichText, Ui};
+use polars::prelude::DataFrame;
+
+/// Rows read when the preview mode is turned on from the Query panel.
+pub const DEFAULT_HEAD_ROWS: usize = 1_000;
+
+/// Limits `df` to the first `head_rows` rows (all rows if `None`), for the readers that
+/// can't stop reading early.
+pub fn limit_rows(df: DataFrame, head_rows: Option<usize>) -> DataFrame {
+    match head_rows {
+        Some(rows) if df.height() > rows => df.head(Some(rows)),
+        _ => df,
+    }
+}
+
+/// The preview text of a container read with a row limit of `rows`,
+/// e.g., `Preview: first 1000 of unknown rows`.
+pub fn preview_message(rows: usize) -> String {
+    format!("Preview: first {rows} of unknown rows")
+}
+
+/// Renders the preview banner if `container` shows only the first rows of its file
+/// (see `DataContainer::preview_rows`).
+///
+/// Returns `true` if "Load full file" was clicked.
+pub fn render_preview_banner(ui: &mut Ui, container: &DataContainer) -> bool {
+    let Some(rows) = container.preview_rows() else {
+        return false;
+    };
+
+    let mut load_full = false;
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new(format!(" {} ", preview_message(rows)))
+                .color(Color32::BLACK)
+                .background_color(Color32::from_rgb(230, 190, 80)),
+        )
+        .on_hover_text("Only these rows were read: queries, sorting and statistics apply to them.");
+        load_full = ui
+            .button("Load full file")
+            .on_hover_text("Read all the rows of the file.")
+            .clicked();
+    });
+    ui.separator();
+    load_full
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_preview`
+#[cfg(test)]
+mod tests_preview {
+    use super::*;
+    use polars::prelude::*;
+
+    #[test]
+    fn rows_are_limited() -> PolarsResult<()> {
+        let df = df!("a" => &[1, 2, 3, 4])?;
+
+        assert_eq!(limit_rows(df.clone(), Some(2)).height(), 2);
+        assert_eq!(limit_rows(df.clone(), Some(10)).height(), 4);
+        assert_eq!(limit_rows(df, None).height(), 4);
+        assert_eq!(preview_message(2), "Preview: first 2 of unknown rows");
+        Ok(())
+    }
+}
@@ -0,0 +1,201 @@
+//! Progress of the background data tasks (see `PolarsViewApp::run_data_future`): the
+//! stage being run (reading, SQL, sorting...) and, once known, the number of rows.
+//!
+//! The task functions report through `report_stage`/`report_rows`, which send to the
+//! `tokio::sync::watch` channel of the running task (a task-local value set by
+//! `with_progress`), so no progress handle is threaded through the `DataContainer` API.
+//! Outside a task with progress (e.g., in tests), reports are ignored.
+//!
+//! The status bar shows the latest report with a "Cancel" button, which aborts the task.
+//!
+//! Aborting a task doesn't stop the Polars work it runs on a blocking thread (e.g., a
+//! `collect`), so each task also gets a `CancelToken` (a task-local value set by
+//! `with_cancel`): a superseded or cancelled task stops at its next stage, and its blocking
+//! work is skipped if it hasn't started yet (see `check_cancelled`).
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use std::{
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+use tokio::sync::watch;
+
+/// Stage of a data task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoadStage {
+    /// Reading the file (or downloading it, see `remote.rs`).
+    Reading,
+    /// Running the SQL query and the other load transformations.
+    Sql,
+    /// Applying the column filters of the filter row.
+    Filtering,
+    /// Sorting the rows.
+    Sorting,
+}
+
+impl LoadStage {
+    /// Label shown in the status bar.
+    pub fn label(self) -> &'static str {
+        match self {
+            LoadStage::Reading => "Reading",
+            LoadStage::Sql => "Running SQL",
+            LoadStage::Filtering => "Filtering",
+            LoadStage::Sorting => "Sorting",
+        }
+    }
+}
+
+/// Latest progress report of a data task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LoadProgress {
+    pub stage: LoadStage,
+    /// Rows produced by the previous stage, if known.
+    pub rows: Option<usize>,
+    /// When the stage started.
+    pub started: Instant,
+}
+
+impl LoadProgress {
+    /// Status bar text, e.g., `Sorting 1,234,567 rows... (3 s)`.
+    pub fn text(&self) -> String {
+        let rows = match self.rows {
+            Some(rows) => format!(" {} rows", crate::thousands(rows)),
+            None => String::new(),
+        };
+        let seconds = self.started.elapsed().as_secs();
+        if seconds == 0 {
+            format!("{}{rows}...", self.stage.label())
+        } else {
+            format!("{}{rows}... ({seconds} s)", self.stage.label())
+        }
+    }
+}
+
+/// Receiving end of the progress channel of a data task (`None`: nothing reported yet).
+pub type ProgressReceiver = watch::Receiver<Option<LoadProgress>>;
+
+/// Cancellation flag of a data task, set by `PolarsViewApp` when the task is superseded by
+/// a newer one or cancelled by the user.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// Cancels the task: it stops at its next `check_cancelled`.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Whether the task was cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+tokio::task_local! {
+    /// Progress channel of the running data task (see `with_progress`).
+    static PROGRESS: watch::Sender<Option<LoadProgress>>;
+
+    /// Cancellation flag of the running data task (see `with_cancel`).
+    static CANCEL: CancelToken;
+}
+
+/// Runs `future` with the cancellation flag `token` (see `check_cancelled`).
+pub fn with_cancel<F: Future>(future: F, token: CancelToken) -> impl Future<Output = F::Output> {
+    CANCEL.scope(token, future)
+}
+
+/// The cancellation flag of the running data task (`None` outside `with_cancel`), to be
+/// moved into its blocking work.
+pub fn cancel_token() -> Option<CancelToken> {
+    CANCEL.try_with(CancelToken::clone).ok()
+}
+
+/// Returns `PolarsViewError::Cancelled` if the running data task was cancelled, so `?`
+/// stops it between stages. Always `Ok` outside `with_cancel`.
+pub fn check_cancelled() -> PolarsViewResult<()> {
+    match cancel_token() {
+        Some(token) if token.is_cancelled() => Err(PolarsViewError::Cancelled),
+        _ => Ok(()),
+    }
+}
+
+/// Runs `future` with a new progress channel: the reports made while it runs (see
+/// `report_stage`) are received by the returned receiver.
+pub fn with_progress<F: Future>(future: F) -> (impl Future<Output = F::Output>, ProgressReceiver) {
+    let (tx, rx) = watch::channel(None);
+    (PROGRESS.scope(tx, future), rx)
+}
+
+/// Reports that the running data task entered `stage`, with the rows it starts from
+/// (if known). Ignored outside `with_progress`.
+pub fn report_stage(stage: LoadStage, rows: Option<usize>) {
+    let _ = PROGRESS.try_with(|tx| {
+        tx.send_replace(Some(LoadProgress {
+            stage,
+            rows,
+            started: Instant::now(),
+        }));
+    });
+}
+
+/// Updates the row count of the current stage of the running data task.
+pub fn report_rows(rows: usize) {
+    let _ = PROGRESS.try_with(|tx| {
+        tx.send_modify(|progress| {
+            if let Some(progress) = progress {
+                progress.rows = Some(rows);
+            }
+        });
+    });
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_progress`
+#[cfg(test)]
+mod tests_progress {
+    use super::*;
+
+    #[tokio::test]
+    async fn reports_reach_the_receiver() {
+        let (future, rx) = with_progress(async {
+            report_stage(LoadStage::Reading, None);
+            report_rows(1_234);
+            report_stage(LoadStage::Sorting, Some(1_234));
+        });
+        assert!(rx.borrow().is_none());
+
+        future.await;
+        let progress = rx.borrow().expect("a report");
+        assert_eq!(progress.stage, LoadStage::Sorting);
+        assert_eq!(progress.text(), "Sorting 1,234 rows...");
+
+        // Outside `with_progress`: ignored.
+        report_stage(LoadStage::Sql, None);
+    }
+
+    #[tokio::test]
+    async fn cancelled_tasks_stop_between_stages() {
+        let token = CancelToken::default();
+        let stages = with_cancel(
+            async {
+                check_cancelled()?;
+                crate::execute_polars_blocking(|| Ok(1)).await
+            },
+            token.clone(),
+        );
+        token.cancel();
+        assert!(matches!(stages.await, Err(PolarsViewError::Cancelled)));
+
+        // Outside `with_cancel`: never cancelled.
+        assert!(check_cancelled().is_ok());
+    }
+}
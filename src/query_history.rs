@@ -0,0 +1,252 @@
+//! History of the applied SQL queries: each query with the time it ran and the
+//! dimensions of its result (`QueryRecord`), most recent first.
+//!
+//! The history of the loaded table is kept in `DataFilter::query_history`; the histories
+//! of all tables are saved under the user config directory (`QUERY_HISTORY_FILE_NAME`),
+//! keyed by data source (see `table_key`), so past queries are recalled when the file
+//! is opened again.
+
+use crate::{DataFilter, PolarsViewResult, app_config_dir, read_json, write_json};
+
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Name of the saved query histories, in `app_config_dir()`.
+pub const QUERY_HISTORY_FILE_NAME: &str = "query_history.json";
+
+/// Maximum number of characters of a query shown by `QueryRecord::preview`.
+const PREVIEW_CHARS: usize = 60;
+
+/// Maximum number of tables whose history is saved (the least recently used are dropped).
+pub const MAX_HISTORY_TABLES: usize = 200;
+
+/// A successfully applied SQL query.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryRecord {
+    pub query: String,
+    /// When the query ran (seconds since the Unix epoch).
+    pub executed_at: u64,
+    /// Number of rows of the result.
+    pub rows: usize,
+    /// Number of columns of the result.
+    pub columns: usize,
+}
+
+impl QueryRecord {
+    /// A record of `query`, run now, with a result of `rows` x `columns`.
+    pub fn now(query: &str, rows: usize, columns: usize) -> Self {
+        let executed_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        QueryRecord {
+            query: query.to_string(),
+            executed_at,
+            rows,
+            columns,
+        }
+    }
+
+    /// The first non-empty line of the query, whitespace collapsed, cut at
+    /// `PREVIEW_CHARS` characters.
+    pub fn preview(&self) -> String {
+        let line = self
+            .query
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        match line.char_indices().nth(PREVIEW_CHARS) {
+            Some((index, _)) => format!("{}…", &line[..index]),
+            None => line,
+        }
+    }
+
+    /// One-line summary shown in the history list, e.g. `2026-01-31 14:05 UTC · 10 × 3`.
+    pub fn summary(&self) -> String {
+        format!(
+            "{} · {} × {}",
+            format_unix_time(self.executed_at),
+            self.rows,
+            self.columns
+        )
+    }
+}
+
+/// Formats `seconds` since the Unix epoch as `YYYY-MM-DD HH:MM UTC`.
+pub fn format_unix_time(seconds: u64) -> String {
+    let (days, time) = (seconds / 86_400, seconds % 86_400);
+
+    // Civil date from days since 1970-01-01 (proleptic Gregorian calendar).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02} UTC",
+        time / 3_600,
+        time % 3_600 / 60
+    )
+}
+
+/// Key of the history of the data source of `filter`: the file path, with the
+/// worksheet or database table, if one is selected (e.g., `/data/sales.xlsx#2024`).
+pub fn table_key(filter: &DataFilter) -> String {
+    let path = filter.absolute_path.display().to_string();
+    match filter.sheet_name.as_ref().or(filter.db_table.as_ref()) {
+        Some(table) => format!("{path}#{table}"),
+        None => path,
+    }
+}
+
+/// The saved query histories of all tables (see the module documentation).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QueryHistory {
+    /// History of each table (most recent query first), keyed by `table_key`.
+    pub tables: BTreeMap<String, Vec<QueryRecord>>,
+    /// Table keys, most recently used last (bounded by `MAX_HISTORY_TABLES`).
+    pub order: Vec<String>,
+}
+
+impl QueryHistory {
+    /// Path of the saved histories (`None` if the config directory is unknown).
+    fn config_path() -> Option<PathBuf> {
+        app_config_dir().map(|dir| dir.join(QUERY_HISTORY_FILE_NAME))
+    }
+
+    /// Reads the histories from the JSON file at `path`. A missing file gives no history.
+    pub fn load(path: impl AsRef<Path>) -> PolarsViewResult<Self> {
+        Ok(read_json(path.as_ref())?.unwrap_or_default())
+    }
+
+    /// Writes the histories as JSON to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> PolarsViewResult<()> {
+        write_json(path.as_ref(), self)
+    }
+
+    /// The history of `key` (empty if none).
+    pub fn table(&self, key: &str) -> Vec<QueryRecord> {
+        self.tables.get(key).cloned().unwrap_or_default()
+    }
+
+    /// Replaces the history of `key` by `records`, marking the table as the most
+    /// recently used one.
+    pub fn set_table(&mut self, key: &str, records: &[QueryRecord]) {
+        self.order.retain(|k| k != key);
+        self.order.push(key.to_string());
+        self.tables.insert(key.to_string(), records.to_vec());
+
+        if self.order.len() > MAX_HISTORY_TABLES {
+            let dropped: Vec<String> = self
+                .order
+                .drain(..self.order.len() - MAX_HISTORY_TABLES)
+                .collect();
+            for key in dropped {
+                self.tables.remove(&key);
+            }
+        }
+    }
+
+    /// The saved history of the data source of `filter` (errors are logged).
+    pub fn for_filter(filter: &DataFilter) -> Vec<QueryRecord> {
+        let Some(path) = Self::config_path() else {
+            return Vec::new();
+        };
+        Self::load(&path)
+            .unwrap_or_else(|error| {
+                tracing::warn!("Invalid query history {path:?}: {error}");
+                Self::default()
+            })
+            .table(&table_key(filter))
+    }
+
+    /// Saves `records` as the history of the table `key` (see `table_key`; errors are
+    /// logged). This reads and writes the history file: call it off the UI thread.
+    ///
+    /// Nothing is saved for an empty history, or if it is already saved.
+    pub fn record(key: &str, records: &[QueryRecord]) {
+        if records.is_empty() || key.is_empty() {
+            return;
+        }
+        let Some(path) = Self::config_path() else {
+            return;
+        };
+
+        let mut history = Self::load(&path).unwrap_or_else(|error| {
+            tracing::warn!("Invalid query history {path:?}: {error}");
+            Self::default()
+        });
+        if history.tables.get(key).map(Vec::as_slice) == Some(records) {
+            return;
+        }
+
+        history.set_table(key, records);
+        if let Err(error) = history.save(&path) {
+            tracing::warn!("Failed to save the query history {path:?}: {error}");
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_query_history`
+#[cfg(test)]
+mod tests_query_history {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn unix_time_format() {
+        assert_eq!(format_unix_time(0), "1970-01-01 00:00 UTC");
+        assert_eq!(format_unix_time(951_782_400), "2000-02-29 00:00 UTC");
+        assert_eq!(format_unix_time(1_769_868_300), "2026-01-31 14:05 UTC");
+    }
+
+    #[test]
+    fn history_round_trip() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join(QUERY_HISTORY_FILE_NAME);
+
+        // A missing file is an empty history.
+        assert_eq!(QueryHistory::load(&path)?, QueryHistory::default());
+
+        let records = vec![QueryRecord {
+            query: "SELECT * FROM AllData WHERE id > 1;".to_string(),
+            executed_at: 1_769_868_300,
+            rows: 10,
+            columns: 3,
+        }];
+        assert_eq!(records[0].summary(), "2026-01-31 14:05 UTC · 10 × 3");
+        assert_eq!(records[0].preview(), "SELECT * FROM AllData WHERE id > 1;");
+
+        let mut history = QueryHistory::default();
+        history.set_table("/data/a.csv", &records);
+        history.set_table("/data/b.csv", &[]);
+        history.set_table("/data/a.csv", &records);
+        assert_eq!(history.order, ["/data/b.csv", "/data/a.csv"]);
+        history.save(&path)?;
+
+        let loaded = QueryHistory::load(&path)?;
+        assert_eq!(loaded.table("/data/a.csv"), records);
+        assert!(loaded.table("/data/c.csv").is_empty());
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+//! Named SQL queries ("steps") run before the main query of the "Query" panel: each step
+//! is registered as a table under its name, so later steps and the main query can select
+//! from it (like a `WITH` clause shared by all of them). Steps are chained lazily, so the
+//! intermediate results are not collected.
+
+use polars::{prelude::*, sql::SQLContext};
+use serde::{Deserialize, Serialize};
+
+/// A SQL query whose result is registered as the table `name` (see the module documentation).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NamedQuery {
+    /// Table name of the result.
+    pub name: String,
+    pub query: String,
+}
+
+impl NamedQuery {
+    /// Returns `true` if the step has no name or no query (it is then skipped).
+    pub fn is_empty(&self) -> bool {
+        self.name.trim().is_empty() || self.query.trim().is_empty()
+    }
+}
+
+/// Runs `query` on the data `lf` registered as `table_name`, after the `steps` (in order),
+/// each registered under its name. Empty steps are skipped.
+///
+/// ### Errors
+/// * `PolarsError`: If a step or the query cannot be parsed or planned; the error of a step
+///   is prefixed by its name.
+pub fn execute_sql(
+    table_name: &str,
+    lf: LazyFrame,
+    steps: &[NamedQuery],
+    query: &str,
+) -> PolarsResult<LazyFrame> {
+    let mut ctx = SQLContext::new();
+    ctx.register(table_name, lf);
+
+    for step in steps.iter().filter(|step| !step.is_empty()) {
+        let name = step.name.trim();
+        let result = ctx
+            .execute(&step.query)
+            .map_err(|error| error.context(format!("step `{name}`").into()))?;
+        ctx.register(name, result);
+    }
+
+    ctx.execute(query)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_query_steps`
+#[cfg(test)]
+mod tests_query_steps {
+    use super::*;
+
+    #[test]
+    fn steps_are_chained() -> PolarsResult<()> {
+        let df = df!(
+            "city" => &["a", "b", "a", "c"],
+            "sales" => &[10, 20, 30, 40]
+        )?;
+        let steps = [
+            NamedQuery {
+                name: "big".to_string(),
+                query: "SELECT * FROM AllData WHERE sales > 15".to_string(),
+            },
+            NamedQuery::default(),
+            NamedQuery {
+                name: "by_city".to_string(),
+                query: "SELECT city, SUM(sales) AS total FROM big GROUP BY city".to_string(),
+            },
+        ];
+
+        let result = execute_sql(
+            "AllData",
+            df.lazy(),
+            &steps,
+            "SELECT * FROM by_city ORDER BY total DESC",
+        )?
+        .collect()?;
+        dbg!(&result);
+
+        let cities: Vec<Option<&str>> = result.column("city")?.str()?.into_iter().collect();
+        assert_eq!(cities, [Some("c"), Some("a"), Some("b")]);
+        Ok(())
+    }
+
+    #[test]
+    fn step_errors_name_the_step() {
+        let steps = [NamedQuery {
+            name: "broken".to_string(),
+            query: "SELEC nonsense".to_string(),
+        }];
+        let df = df!("a" => &[1]).unwrap();
+
+        let error = execute_sql("AllData", df.lazy(), &steps, "SELECT * FROM broken")
+            .and_then(|lf| lf.collect())
+            .unwrap_err();
+        assert!(error.to_string().contains("broken"), "{error}");
+    }
+}
@@ -0,0 +1,276 @@
+//! Multi-record files: text files where the leading field of each line identifies the
+//! record type (e.g., SPED/EFD `|C100|...|` layouts), so each record type has its own
+//! number of fields.
+//!
+//! Such files are split into one `DataFrame` per record type instead of a single ragged table.
+
+use crate::{PolarsViewResult, open_decompressed};
+
+use polars::prelude::*;
+use std::{
+    collections::HashMap,
+    io::Read,
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+/// Field delimiter of multi-record files (lines are wrapped in it: `|C100|0|1|...|`).
+pub const RECORD_DELIMITER: char = '|';
+
+/// Name of the first column (the record type), as in the SPED layouts.
+pub const RECORD_TYPE_COLUMN: &str = "REG";
+
+/// Bytes read from the start of a file to detect a multi-record layout.
+const SAMPLE_BYTES: u64 = 64 * 1024;
+
+/// Maximum number of lines checked to detect a multi-record layout.
+const SAMPLE_LINES: usize = 200;
+
+/// The records of one record type.
+#[derive(Debug, Clone)]
+pub struct RecordGroup {
+    /// The record type (first field of each line, e.g., `C100`).
+    pub record_type: String,
+    /// One row per record: `REG`, `field_02`, `field_03`, ... (String columns).
+    pub df: DataFrame,
+}
+
+/// Splits a line into fields, dropping the wrapping delimiters.
+/// Returns `None` if the line is not wrapped in `RECORD_DELIMITER`.
+fn split_fields(line: &str) -> Option<Vec<&str>> {
+    let inner = line
+        .trim_end_matches(['\r', '\n'])
+        .strip_prefix(RECORD_DELIMITER)?
+        .strip_suffix(RECORD_DELIMITER)?;
+
+    Some(inner.split(RECORD_DELIMITER).collect())
+}
+
+/// Returns `true` if `sample` looks like a multi-record file: every non-blank line is
+/// wrapped in `|`, there are several record types and the number of fields varies.
+///
+/// A regular pipe-delimited table (constant width) is not considered multi-record.
+pub fn is_multi_record(sample: &str) -> bool {
+    let mut record_types = HashMap::new();
+
+    for line in sample
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .take(SAMPLE_LINES)
+    {
+        match split_fields(line) {
+            Some(fields) if !fields[0].trim().is_empty() => {
+                record_types.insert(fields[0].trim(), fields.len());
+            }
+            _ => return false,
+        }
+    }
+
+    let mut widths: Vec<usize> = record_types.values().copied().collect();
+    widths.sort_unstable();
+    widths.dedup();
+
+    record_types.len() > 1 && widths.len() > 1
+}
+
+/// Reads the start of the file at `path` (decompressed, if compressed) and checks it with
+/// `is_multi_record`.
+pub fn is_multi_record_file(path: &Path) -> PolarsViewResult<bool> {
+    let mut bytes = Vec::new();
+    open_decompressed(path)?
+        .take(SAMPLE_BYTES)
+        .read_to_end(&mut bytes)?;
+
+    // The sample may end in the middle of a line: ignore the last (partial) line.
+    let sample = String::from_utf8_lossy(&bytes);
+    let sample = match sample.rfind('\n') {
+        Some(end) if bytes.len() as u64 == SAMPLE_BYTES => &sample[..end],
+        _ => &sample[..],
+    };
+
+    Ok(is_multi_record(sample))
+}
+
+/// Splits the lines of a multi-record file into one `DataFrame` per record type,
+/// in order of first appearance.
+///
+/// Each `DataFrame` has `REG` followed by `field_02`, `field_03`, ... (numbered by field
+/// position, as in the layout documentation). Empty fields become null and short lines
+/// are padded with nulls. Lines not wrapped in `|` are ignored.
+pub fn split_record_types(text: &str) -> PolarsResult<Vec<RecordGroup>> {
+    let mut groups: Vec<(String, Vec<Vec<&str>>)> = Vec::new();
+    let mut positions: HashMap<&str, usize> = HashMap::new();
+
+    for fields in text.lines().filter_map(split_fields) {
+        let record_type = fields[0].trim();
+        if record_type.is_empty() {
+            continue;
+        }
+
+        let position = *positions.entry(record_type).or_insert_with(|| {
+            groups.push((record_type.to_string(), Vec::new()));
+            groups.len() - 1
+        });
+        groups[position].1.push(fields);
+    }
+
+    groups
+        .into_iter()
+        .map(|(record_type, rows)| {
+            let width = rows.iter().map(Vec::len).max().unwrap_or(1);
+
+            let columns = (0..width)
+                .map(|index| {
+                    let name = match index {
+                        0 => RECORD_TYPE_COLUMN.to_string(),
+                        _ => format!("field_{:02}", index + 1),
+                    };
+
+                    rows.iter()
+                        .map(|fields| fields.get(index).copied().filter(|f| !f.is_empty()))
+                        .collect::<StringChunked>()
+                        .with_name(name.into())
+                        .into_column()
+                })
+                .collect();
+
+            Ok(RecordGroup {
+                df: DataFrame::new(rows.len(), columns)?,
+                record_type,
+            })
+        })
+        .collect()
+}
+
+/// Reads the file at `path` (decompressed, if compressed) and splits it with
+/// `split_record_types`.
+///
+/// Invalid UTF-8 bytes (e.g., Latin-1 accents) are replaced, not rejected.
+/// This is a blocking operation; call it from `spawn_blocking`.
+pub fn read_record_types(path: &Path) -> PolarsViewResult<Vec<RecordGroup>> {
+    let mut bytes = Vec::new();
+    open_decompressed(path)?.read_to_end(&mut bytes)?;
+    let groups = split_record_types(&String::from_utf8_lossy(&bytes))?;
+
+    tracing::debug!(
+        "Split {} into {} record types",
+        path.display(),
+        groups.len()
+    );
+
+    Ok(groups)
+}
+
+/// The record groups of the last multi-record file read, keyed by its path and
+/// modification time: selecting another record type slices them instead of reading and
+/// splitting the file again.
+///
+/// Kept in `DataFilter`, whose change detection it does not affect: two caches are always
+/// equal.
+#[derive(Debug, Clone, Default)]
+pub struct RecordGroupsCache(Option<(PathBuf, SystemTime, Arc<Vec<RecordGroup>>)>);
+
+impl PartialEq for RecordGroupsCache {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl RecordGroupsCache {
+    /// The record groups of `path`, read by `read_record_types` unless cached for the
+    /// current version of the file.
+    ///
+    /// This is a blocking operation; call it from `spawn_blocking`.
+    pub fn groups(&mut self, path: &Path) -> PolarsViewResult<Arc<Vec<RecordGroup>>> {
+        let modified = std::fs::metadata(path)?.modified().ok();
+
+        if let (Some((cached_path, cached_modified, groups)), Some(modified)) = (&self.0, modified)
+            && cached_path == path
+            && *cached_modified == modified
+        {
+            tracing::debug!("Reusing the record types of {}", path.display());
+            return Ok(Arc::clone(groups));
+        }
+
+        let groups = Arc::new(read_record_types(path)?);
+        self.0 = modified.map(|modified| (path.to_path_buf(), modified, Arc::clone(&groups)));
+        Ok(groups)
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_record_types`
+#[cfg(test)]
+mod tests_record_types {
+    use super::*;
+
+    const SPED: &str = "\
+|0000|017|0|01012024|31012024|EMPRESA|\r
+|C100|0|1|PART01|55|00|\r
+|C170|1|ITEM01|10,5|\r
+|C100|1|0|PART02|55|00|\r
+|C170|1|ITEM02||\r
+|C170|2|ITEM03|1,25|\r
+";
+
+    #[test]
+    fn detects_multi_record_layout() {
+        assert!(is_multi_record(SPED));
+
+        // Constant width: a regular pipe-delimited table.
+        assert!(!is_multi_record("|a|b|\n|1|2|\n|3|4|\n"));
+
+        // Lines not wrapped in `|`.
+        assert!(!is_multi_record("C100|0|1\nC170|1\n"));
+    }
+
+    #[test]
+    fn splits_by_record_type() -> PolarsResult<()> {
+        let groups = split_record_types(SPED)?;
+
+        let record_types: Vec<&str> = groups.iter().map(|g| g.record_type.as_str()).collect();
+        assert_eq!(record_types, vec!["0000", "C100", "C170"]);
+
+        let c170 = &groups[2].df;
+        dbg!(c170);
+
+        assert_eq!(c170.shape(), (3, 4));
+        assert_eq!(
+            c170.get_column_names_str(),
+            vec!["REG", "field_02", "field_03", "field_04"]
+        );
+
+        let values: Vec<Option<&str>> = c170.column("field_04")?.str()?.into_iter().collect();
+        assert_eq!(values, vec![Some("10,5"), None, Some("1,25")]);
+        Ok(())
+    }
+
+    #[test]
+    fn caches_record_groups() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("sped.txt");
+        std::fs::write(&path, SPED)?;
+
+        let mut cache = RecordGroupsCache::default();
+        let first = cache.groups(&path)?;
+        assert!(Arc::ptr_eq(&first, &cache.groups(&path)?));
+
+        // A newer version of the file is split again.
+        std::fs::write(&path, "|0000|017|\n|C100|0|1|\n")?;
+        let later = SystemTime::now() + std::time::Duration::from_secs(60);
+        std::fs::File::options()
+            .write(true)
+            .open(&path)?
+            .set_modified(later)?;
+
+        let second = cache.groups(&path)?;
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(second.len(), 2);
+        Ok(())
+    }
+}
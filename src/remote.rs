@@ -0,0 +1,212 @@
+//! Remote files: `http://`, `https://` and `s3://` URLs (command line or "File" >
+//! "Open URL...") are downloaded with `object_store` to a temporary file of their own (see
+//! `new_temp_file`), showing the progress in the status bar, and then opened like any local
+//! file. The copy is removed when its tab is closed.
+//!
+//! S3 credentials and region come from the usual `AWS_*` environment variables.
+//!
+//! Downloading needs the `remote` cargo feature; without it, opening a URL reports the
+//! feature to build with.
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use egui::{Context, TextEdit, Window};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tempfile::TempPath;
+use url::Url;
+
+/// Prefix of the name of the downloaded files, after the random part of the temporary file.
+pub const REMOTE_FILE_PREFIX: &str = "remote-";
+
+/// Whether `text` is a URL that can be downloaded (`http://`, `https://` or `s3://`).
+pub fn is_remote_url(text: &str) -> bool {
+    let lower = text.trim().to_lowercase();
+    ["http://", "https://", "s3://"]
+        .iter()
+        .any(|scheme| lower.starts_with(scheme))
+}
+
+/// Progress of a download, shared between the download task and the status bar.
+#[derive(Debug, Default)]
+pub struct DownloadProgress {
+    /// The URL being downloaded.
+    pub url: String,
+    downloaded: AtomicU64,
+    /// Size of the object (0: unknown yet).
+    total: AtomicU64,
+}
+
+impl DownloadProgress {
+    pub fn new(url: &str) -> Self {
+        DownloadProgress {
+            url: url.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Bytes downloaded so far.
+    pub fn downloaded(&self) -> u64 {
+        self.downloaded.load(Ordering::Relaxed)
+    }
+
+    /// Size of the object, once known.
+    pub fn total(&self) -> Option<u64> {
+        Some(self.total.load(Ordering::Relaxed)).filter(|&total| total > 0)
+    }
+
+    /// Downloaded fraction (0 to 1), once the size is known.
+    pub fn fraction(&self) -> Option<f32> {
+        self.total()
+            .map(|total| (self.downloaded() as f64 / total as f64).min(1.0) as f32)
+    }
+}
+
+/// Local file name of `url`: `REMOTE_FILE_PREFIX` and the last path segment (which keeps
+/// the extension, so the file type is detected as for local files).
+pub fn local_file_name(url: &Url) -> String {
+    let name = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|name| !name.is_empty())
+        .unwrap_or("download");
+    format!("{REMOTE_FILE_PREFIX}{name}")
+}
+
+/// Downloads `url` to a new temporary file, updating `progress`, and returns the path of
+/// the local copy (removed when it is dropped).
+#[cfg(feature = "remote")]
+pub async fn download_url(url: &str, progress: &DownloadProgress) -> PolarsViewResult<TempPath> {
+    use crate::new_temp_file;
+    use futures::StreamExt;
+    use object_store::{ObjectStore, aws::AmazonS3Builder, path::Path as ObjectPath};
+    use tokio::io::AsyncWriteExt;
+
+    let url = Url::parse(url.trim())
+        .map_err(|error| PolarsViewError::Other(format!("Invalid URL `{url}`: {error}")))?;
+
+    let (store, path): (Box<dyn ObjectStore>, ObjectPath) = match url.scheme() {
+        // Credentials and region from the environment (`parse_url` ignores them).
+        "s3" => (
+            Box::new(AmazonS3Builder::from_env().with_url(url.as_str()).build()?),
+            ObjectPath::from_url_path(url.path()).map_err(object_store::Error::from)?,
+        ),
+        _ => object_store::parse_url(&url)?,
+    };
+
+    tracing::info!(target: "polars_view", "Downloading {url}");
+    let result = store.get(&path).await?;
+    progress.total.store(result.meta.size, Ordering::Relaxed);
+
+    let (file, local_path) = new_temp_file(&format!("-{}", local_file_name(&url)))?.into_parts();
+    let mut file = tokio::fs::File::from_std(file);
+    let mut stream = result.into_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await?;
+        progress
+            .downloaded
+            .fetch_add(chunk.len() as u64, Ordering::Relaxed);
+    }
+    file.flush().await?;
+
+    tracing::debug!(
+        "Downloaded {} bytes to {}",
+        progress.downloaded(),
+        local_path.display()
+    );
+    Ok(local_path)
+}
+
+/// Built without the `remote` feature: URLs can't be downloaded.
+#[cfg(not(feature = "remote"))]
+pub async fn download_url(_url: &str, _progress: &DownloadProgress) -> PolarsViewResult<TempPath> {
+    Err(PolarsViewError::FeatureDisabled("remote"))
+}
+
+/// State of the "Open URL" dialog (opened from the "File" menu).
+#[derive(Debug, Default)]
+pub struct UrlDialog {
+    /// Whether the dialog is shown.
+    pub open: bool,
+    pub url: String,
+}
+
+impl UrlDialog {
+    /// Shows the dialog (if open). Returns the URL to open when "Open" is clicked.
+    pub fn show(&mut self, ctx: &Context) -> Option<String> {
+        if !self.open {
+            return None;
+        }
+
+        let mut url = None;
+        let mut open = self.open;
+
+        Window::new("Open URL")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("CSV, JSON, Parquet or Excel file (http, https or s3):");
+                let response = ui.add(
+                    TextEdit::singleline(&mut self.url)
+                        .hint_text("https://example.com/data/sales.parquet")
+                        .desired_width(420.0),
+                );
+                let entered =
+                    response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                let valid = is_remote_url(&self.url);
+                if !self.url.trim().is_empty() && !valid {
+                    ui.colored_label(
+                        ui.visuals().error_fg_color,
+                        "The URL must start with http://, https:// or s3://",
+                    );
+                }
+                if (ui.add_enabled(valid, egui::Button::new("Open")).clicked() || entered) && valid
+                {
+                    url = Some(self.url.trim().to_string());
+                }
+            });
+
+        self.open = open && url.is_none();
+        url
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_remote`
+#[cfg(test)]
+mod tests_remote {
+    use super::*;
+
+    #[test]
+    fn recognizes_remote_urls() {
+        assert!(is_remote_url("https://example.com/data.csv"));
+        assert!(is_remote_url("S3://bucket/key/data.parquet"));
+        assert!(!is_remote_url("/home/user/data.csv"));
+        assert!(!is_remote_url("ftp://example.com/data.csv"));
+    }
+
+    #[test]
+    fn local_names_keep_the_extension() -> Result<(), url::ParseError> {
+        let url = Url::parse("https://example.com/files/sales.parquet?version=2")?;
+        assert_eq!(local_file_name(&url), "remote-sales.parquet");
+
+        let url = Url::parse("https://example.com/")?;
+        assert_eq!(local_file_name(&url), "remote-download");
+        Ok(())
+    }
+
+    #[test]
+    fn progress_fraction() {
+        let progress = DownloadProgress::new("https://example.com/a.csv");
+        assert_eq!(progress.fraction(), None);
+        progress.total.store(200, Ordering::Relaxed);
+        progress.downloaded.store(50, Ordering::Relaxed);
+        assert_eq!(progress.fraction(), Some(0.25));
+    }
+}
@@ -0,0 +1,125 @@
+//! Row inspector: a window listing every column of one row (opened by double-clicking a cell),
+//! with a JSON tree view for nested (`Struct`/`List`) and JSON-string cells.
+
+use crate::{JsonTree, cell_to_json};
+
+use egui::{Context, Grid, RichText, ScrollArea, Window};
+use polars::prelude::*;
+
+/// Maximum characters of a value shown in the column list (the full value is on hover).
+const MAX_VALUE_CHARS: usize = 80;
+
+/// State of the "Row Inspector" window.
+#[derive(Debug, Clone, Default)]
+pub struct RowInspector {
+    /// Whether the window is shown.
+    pub open: bool,
+    /// Inspected row index in the displayed `DataFrame`.
+    pub row: usize,
+    /// Column whose value is shown in detail (as a tree, if it holds JSON).
+    pub column: Option<String>,
+    tree: JsonTree,
+}
+
+impl RowInspector {
+    /// Opens the inspector on the cell at (`row`, `column`).
+    pub fn inspect(&mut self, row: usize, column: &str) {
+        self.open = true;
+        self.row = row;
+        self.column = Some(column.to_string());
+    }
+
+    /// Shows the window (if open) for the displayed `DataFrame`.
+    pub fn show(&mut self, ctx: &Context, df: &DataFrame) {
+        if !self.open || df.height() == 0 {
+            return;
+        }
+        self.row = self.row.min(df.height() - 1);
+
+        let mut open = self.open;
+
+        Window::new("Row Inspector")
+            .open(&mut open)
+            .default_width(480.0)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(self.row > 0, egui::Button::new("◀"))
+                        .clicked()
+                    {
+                        self.row -= 1;
+                    }
+                    ui.label(format!("Row {} of {}", self.row + 1, df.height()));
+                    if ui
+                        .add_enabled(self.row + 1 < df.height(), egui::Button::new("▶"))
+                        .clicked()
+                    {
+                        self.row += 1;
+                    }
+                });
+                ui.separator();
+
+                ScrollArea::vertical()
+                    .id_salt("row_inspector_columns")
+                    .max_height(240.0)
+                    .show(ui, |ui| {
+                        Grid::new("row_inspector_grid")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for column in df.columns() {
+                                    let name = column.name().as_str();
+                                    let is_selected = self.column.as_deref() == Some(name);
+                                    if ui
+                                        .selectable_label(is_selected, name)
+                                        .on_hover_text(column.dtype().to_string())
+                                        .clicked()
+                                    {
+                                        self.column = Some(name.to_string());
+                                    }
+
+                                    let value = match column.get(self.row) {
+                                        Ok(AnyValue::Null) | Err(_) => String::new(),
+                                        Ok(AnyValue::String(text)) => text.to_string(),
+                                        Ok(other) => other.to_string(),
+                                    };
+                                    let short: String =
+                                        value.chars().take(MAX_VALUE_CHARS).collect();
+                                    if short.len() < value.len() {
+                                        ui.label(format!("{short}…")).on_hover_text(value);
+                                    } else {
+                                        ui.label(value);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                let Some(column) = self.column.clone() else {
+                    return;
+                };
+                ui.separator();
+                ui.label(RichText::new(&column).strong());
+
+                match cell_to_json(df, &column, self.row) {
+                    Ok(Some(value)) => {
+                        ScrollArea::vertical()
+                            .id_salt("row_inspector_tree")
+                            .show(ui, |ui| {
+                                self.tree
+                                    .show(ui, ("row_inspector", &column, self.row), &value);
+                            });
+                    }
+                    Ok(None) => {
+                        ui.label(RichText::new("Not a Struct, List or JSON value.").weak());
+                    }
+                    Err(error) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error.to_string());
+                    }
+                }
+            });
+
+        self.open = open;
+    }
+}
@@ -0,0 +1,186 @@
+//! Session bundle: a zip archive with everything needed to reproduce the current view
+//! on another machine ("File" > "Export Session...").
+//!
+//! Contents:
+//! - `view.json`: the `ViewState` (settings, SQL query, sorting, display format),
+//!   importable with "File" > "Import View...".
+//! - `query_history.json`: the successfully applied SQL queries, most recent first, with
+//!   their time and result dimensions (see `QueryRecord`).
+//! - `session.json`: a `SessionInfo` (source file name, shape, sample size).
+//! - `sample.parquet` (optional): the first rows of the *source* data, so the view
+//!   (query, sort) can be re-applied to it.
+//! - `README.txt`: how to open the bundle.
+
+use crate::{DataContainer, PolarsViewResult, QueryRecord, ViewState};
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::File,
+    io::{Seek, Write},
+    path::Path,
+};
+use zip::{CompressionMethod, ZipWriter, write::SimpleFileOptions};
+
+/// Default number of source rows in the bundle's data sample.
+pub const DEFAULT_SAMPLE_ROWS: usize = 1_000;
+
+/// Name of the data sample entry of the bundle.
+pub const SAMPLE_ENTRY: &str = "sample.parquet";
+
+const README: &str = "\
+Session exported by polars-view.
+
+- view.json: settings, SQL query, sorting and display format.
+- query_history.json: applied SQL queries, most recent first.
+- session.json: source file name, shape and sample size.
+- sample.parquet (if included): the first rows of the source data.
+
+To reproduce the view: open sample.parquet (or the original file) in polars-view,
+then use \"File\" > \"Import View...\" and choose view.json.
+";
+
+/// Description of an exported session (`session.json`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionInfo {
+    /// Version of polars-view that wrote the bundle.
+    pub app_version: String,
+    /// File name (without directories) of the source data.
+    pub source_file: String,
+    /// Shape of the source data: (rows, columns).
+    pub source_shape: (usize, usize),
+    /// Shape of the displayed data (after query, sort and filters): (rows, columns).
+    pub view_shape: (usize, usize),
+    /// Number of rows in `sample.parquet`, if included.
+    pub sample_rows: Option<usize>,
+}
+
+/// Writes the session bundle of `container` to the zip file at `path`.
+///
+/// With `sample_rows = Some(n)`, the first `n` rows of the source data are included.
+pub fn write_session_bundle(
+    path: impl AsRef<Path>,
+    container: &DataContainer,
+    sample_rows: Option<usize>,
+) -> PolarsViewResult<()> {
+    let sample = sample_rows.map(|rows| container.df_original.head(Some(rows)));
+
+    let info = SessionInfo {
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        source_file: container
+            .filter
+            .absolute_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        source_shape: container.df_original.shape(),
+        view_shape: container.df.shape(),
+        sample_rows: sample.as_ref().map(DataFrame::height),
+    };
+
+    write_bundle(
+        File::create(path)?,
+        &ViewState::from_container(container),
+        &container.filter.query_history,
+        &info,
+        sample,
+    )
+}
+
+/// Writes the bundle entries to `writer` (see the module documentation).
+pub fn write_bundle<W: Write + Seek>(
+    writer: W,
+    view: &ViewState,
+    query_history: &[QueryRecord],
+    info: &SessionInfo,
+    sample: Option<DataFrame>,
+) -> PolarsViewResult<()> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(writer);
+
+    zip.start_file("README.txt", options)?;
+    zip.write_all(README.as_bytes())?;
+
+    zip.start_file("view.json", options)?;
+    zip.write_all(view.to_json()?.as_bytes())?;
+
+    zip.start_file("query_history.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(query_history)?)?;
+
+    zip.start_file("session.json", options)?;
+    zip.write_all(&serde_json::to_vec_pretty(info)?)?;
+
+    if let Some(mut sample) = sample {
+        // Parquet is already compressed: store it as is.
+        let stored = options.compression_method(CompressionMethod::Stored);
+        let mut buffer = Vec::new();
+        ParquetWriter::new(&mut buffer).finish(&mut sample)?;
+
+        zip.start_file(SAMPLE_ENTRY, stored)?;
+        zip.write_all(&buffer)?;
+    }
+
+    zip.finish()?;
+    Ok(())
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_session_bundle`
+#[cfg(test)]
+mod tests_session_bundle {
+    use super::*;
+    use std::io::{Cursor, Read};
+    use zip::ZipArchive;
+
+    #[test]
+    fn writes_all_entries() -> PolarsViewResult<()> {
+        let sample = df!("id" => &[1, 2, 3], "name" => &["a", "b", "c"])?;
+        let view = ViewState {
+            query: "SELECT * FROM AllData WHERE id > 1;".to_string(),
+            ..Default::default()
+        };
+        let history = vec![QueryRecord::now(&view.query, 2, 2)];
+        let info = SessionInfo {
+            app_version: "0.0.0".to_string(),
+            source_file: "data.csv".to_string(),
+            source_shape: (3, 2),
+            view_shape: (2, 2),
+            sample_rows: Some(3),
+        };
+
+        let mut buffer = Cursor::new(Vec::new());
+        write_bundle(&mut buffer, &view, &history, &info, Some(sample.clone()))?;
+
+        let mut archive = ZipArchive::new(buffer)?;
+        let mut names: Vec<&str> = archive.file_names().collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            [
+                "README.txt",
+                "query_history.json",
+                "sample.parquet",
+                "session.json",
+                "view.json"
+            ]
+        );
+
+        let mut json = String::new();
+        archive.by_name("view.json")?.read_to_string(&mut json)?;
+        assert_eq!(ViewState::from_json(&json)?, view);
+
+        let mut json = String::new();
+        archive.by_name("session.json")?.read_to_string(&mut json)?;
+        assert_eq!(serde_json::from_str::<SessionInfo>(&json)?, info);
+
+        let mut bytes = Vec::new();
+        archive.by_name(SAMPLE_ENTRY)?.read_to_end(&mut bytes)?;
+        let read = ParquetReader::new(Cursor::new(bytes)).finish()?;
+        assert!(read.equals(&sample));
+        Ok(())
+    }
+}
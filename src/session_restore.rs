@@ -0,0 +1,179 @@
+//! Session restore: the last opened file and its view (`ViewState`: load options, SQL
+//! query, sorting and display format) are saved under the user config directory and
+//! reopened on launch with `--restore-session` or the "Restore last session on startup"
+//! setting. The window position and size are persisted by eframe (`persist_window`).
+
+use crate::{
+    DataContainer, PolarsViewResult, ViewState, app_config_dir, is_private_temp_path, is_safe_mode,
+};
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Name of the last session file, in `app_config_dir()`.
+pub const LAST_SESSION_FILE_NAME: &str = "last_session.json";
+
+/// Name of the application settings file, in `app_config_dir()`.
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// Reads a JSON value from `path`. A missing file gives `None`.
+pub(crate) fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> PolarsViewResult<Option<T>> {
+    match std::fs::read_to_string(path) {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Writes `value` as JSON to `path`, creating its directory if needed.
+pub(crate) fn write_json<T: Serialize>(path: &Path, value: &T) -> PolarsViewResult<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
+
+/// The last opened file and its view, saved after each successful load or update.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LastSession {
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    pub view: ViewState,
+}
+
+impl LastSession {
+    /// Path of the saved session (`None` if the config directory is unknown).
+    fn config_path() -> Option<PathBuf> {
+        app_config_dir().map(|dir| dir.join(LAST_SESSION_FILE_NAME))
+    }
+
+    /// Reads the session from the JSON file at `path`. A missing file gives `None`.
+    pub fn load(path: impl AsRef<Path>) -> PolarsViewResult<Option<Self>> {
+        read_json(path.as_ref())
+    }
+
+    /// Writes the session as JSON to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> PolarsViewResult<()> {
+        write_json(path.as_ref(), self)
+    }
+
+    /// Reads the session saved by a previous run, if its file still exists
+    /// (errors are logged).
+    pub fn from_config_dir() -> Option<Self> {
+        let path = Self::config_path()?;
+        let session = Self::load(&path).unwrap_or_else(|error| {
+            tracing::warn!("Invalid last session {path:?}: {error}");
+            None
+        })?;
+
+        if session.path.is_file() {
+            Some(session)
+        } else {
+            tracing::info!("Last session file not found: {:?}", session.path);
+            None
+        }
+    }
+
+    /// The file and view of `container` as a session (`None` without a file, or for a
+    /// private temporary file, e.g. a copy of stdin, removed at exit).
+    pub fn from_container(container: &DataContainer) -> Option<Self> {
+        let path = &container.filter.absolute_path;
+        if path.as_os_str().is_empty() || is_private_temp_path(path) {
+            return None;
+        }
+
+        Some(LastSession {
+            path: path.clone(),
+            view: ViewState::from_container(container),
+        })
+    }
+
+    /// Saves the session as the last session (errors are logged).
+    pub fn record(&self) {
+        let Some(config_path) = Self::config_path() else {
+            return;
+        };
+
+        if let Err(error) = self.save(&config_path) {
+            tracing::warn!("Failed to save the last session {config_path:?}: {error}");
+        }
+    }
+}
+
+/// Application settings, saved under the user config directory ("Settings" window).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    /// Reopen the last session on launch when no file is given (see `LastSession`).
+    pub restore_session: bool,
+    /// The first-use hint on header sorting was dismissed (see `render_sort_hint`).
+    pub sort_hint_dismissed: bool,
+    /// Never simplify the table when rendering is slow (see `FrameWatchdog`).
+    pub disable_frame_watchdog: bool,
+}
+
+impl AppSettings {
+    /// Path of the saved settings (`None` if the config directory is unknown).
+    fn config_path() -> Option<PathBuf> {
+        app_config_dir().map(|dir| dir.join(SETTINGS_FILE_NAME))
+    }
+
+    /// Reads the settings saved by previous runs; errors are logged and give the defaults.
+    pub fn from_config_dir() -> Self {
+        let Some(path) = Self::config_path() else {
+            return Self::default();
+        };
+
+        read_json(&path)
+            .unwrap_or_else(|error| {
+                tracing::warn!("Invalid settings {path:?}: {error}");
+                None
+            })
+            .unwrap_or_default()
+    }
+
+    /// Saves the settings under the user config directory.
+    pub fn save(&self) -> PolarsViewResult<()> {
+        match Self::config_path() {
+            Some(path) => write_json(&path, self),
+            None if is_safe_mode() => Err("safe mode".to_string().into()),
+            None => Err("unknown config directory".to_string().into()),
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_session_restore`
+#[cfg(test)]
+mod tests_session_restore {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn last_session_round_trip() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("config").join(LAST_SESSION_FILE_NAME);
+
+        // A missing file is no session.
+        assert_eq!(LastSession::load(&path)?, None);
+
+        let session = LastSession {
+            path: PathBuf::from("/data/sales.csv"),
+            view: ViewState {
+                query: "SELECT * FROM AllData WHERE region = 'North';".to_string(),
+                decimal: 3,
+                ..Default::default()
+            },
+        };
+        session.save(&path)?;
+
+        assert_eq!(LastSession::load(&path)?, Some(session));
+        Ok(())
+    }
+}
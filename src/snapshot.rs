@@ -0,0 +1,321 @@
+//! Deterministic text/HTML snapshots of the table: the visible columns (in display order),
+//! their headers (with units) and the cells formatted and aligned exactly as the table
+//! shows them (`get_decimal_and_layout` + `format_cell`).
+//!
+//! Meant for regression tests of the formatting logic (e.g., `decimal_and_layout_v1` vs
+//! `decimal_and_layout_v2`): render a `DataFrame` with a `DataFormat` and compare the
+//! result with the expected snapshot.
+
+use crate::{DataFormat, format_cell, get_decimal_and_layout, ordered_indices};
+
+use egui::{Direction, Layout};
+use polars::prelude::*;
+use std::{fmt::Write, sync::Arc};
+
+/// Horizontal alignment of a table cell, as given by its `egui::Layout`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellAlignment {
+    Left,
+    Center,
+    Right,
+}
+
+impl CellAlignment {
+    /// The alignment of the layouts built by `get_decimal_and_layout`.
+    pub fn from_layout(layout: &Layout) -> Self {
+        if layout.main_justify {
+            CellAlignment::Center
+        } else if layout.main_dir == Direction::RightToLeft {
+            CellAlignment::Right
+        } else {
+            CellAlignment::Left
+        }
+    }
+
+    /// The Markdown alignment marker of a column of `width` characters (at least 3).
+    fn marker(self, width: usize) -> String {
+        let width = width.max(3);
+        match self {
+            CellAlignment::Left => format!(":{}", "-".repeat(width - 1)),
+            CellAlignment::Center => format!(":{}:", "-".repeat(width - 2)),
+            CellAlignment::Right => format!("{}:", "-".repeat(width - 1)),
+        }
+    }
+
+    /// The value of the HTML `align` attribute.
+    fn html(self) -> &'static str {
+        match self {
+            CellAlignment::Left => "left",
+            CellAlignment::Center => "center",
+            CellAlignment::Right => "right",
+        }
+    }
+}
+
+/// A table rendered as text: one entry per visible column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableSnapshot {
+    /// Column headers: the name, followed by the displayed unit (e.g., `weight [kg]`).
+    pub headers: Vec<String>,
+    /// Data type of each column.
+    pub dtypes: Vec<DataType>,
+    /// Alignment of each column (from the layout of its cells).
+    pub alignments: Vec<CellAlignment>,
+    /// Formatted cells, row by row.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl TableSnapshot {
+    /// Formats the visible columns of `df` (all its rows) as the table does with `format`.
+    pub fn new(df: &DataFrame, format: &Arc<DataFormat>) -> Self {
+        let names: Vec<&str> = df
+            .get_column_names()
+            .into_iter()
+            .map(|name| name.as_str())
+            .collect();
+        let columns: Vec<&Column> = ordered_indices(&names, &format.column_order)
+            .into_iter()
+            .map(|index| &df.columns()[index])
+            .filter(|column| !format.hidden_columns.contains(column.name().as_str()))
+            .collect();
+
+        let headers = columns
+            .iter()
+            .map(
+                |column| match format.column_units.get(column.name().as_str()) {
+                    Some(unit) => format!("{} [{}]", column.name(), unit.shown()),
+                    None => column.name().to_string(),
+                },
+            )
+            .collect();
+        let dtypes = columns
+            .iter()
+            .map(|column| column.dtype().clone())
+            .collect();
+
+        // The decimal places and the layout depend on the column only.
+        let layouts: Vec<(Option<usize>, Layout)> = columns
+            .iter()
+            .map(|column| get_decimal_and_layout(column, format))
+            .collect();
+        let alignments = layouts
+            .iter()
+            .map(|(_decimal, layout)| CellAlignment::from_layout(layout))
+            .collect();
+
+        let rows = (0..df.height())
+            .map(|row| {
+                columns
+                    .iter()
+                    .zip(&layouts)
+                    .map(|(column, (decimal, _layout))| format_cell(column, row, *decimal, format))
+                    .collect()
+            })
+            .collect();
+
+        TableSnapshot {
+            headers,
+            dtypes,
+            alignments,
+            rows,
+        }
+    }
+
+    /// Renders the snapshot as a Markdown table whose columns are padded to a common width,
+    /// with the alignment markers (`:--`, `:-:`, `--:`) in the separator line.
+    ///
+    /// Pipes and line breaks in the cells are escaped (`\|`, `\n`).
+    pub fn to_text(&self) -> String {
+        let escape = |text: &str| text.replace('|', "\\|").replace('\n', "\\n");
+        let headers: Vec<String> = self.headers.iter().map(|header| escape(header)).collect();
+        let rows: Vec<Vec<String>> = self
+            .rows
+            .iter()
+            .map(|row| row.iter().map(|cell| escape(cell)).collect())
+            .collect();
+
+        let widths: Vec<usize> = headers
+            .iter()
+            .enumerate()
+            .map(|(index, header)| {
+                rows.iter()
+                    .map(|row| row[index].chars().count())
+                    .chain([header.chars().count(), 3])
+                    .max()
+                    .unwrap_or(3)
+            })
+            .collect();
+
+        let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+        let pad = |(text, width): (&String, &usize)| {
+            let fill = width.saturating_sub(text.chars().count());
+            format!("{text}{}", " ".repeat(fill))
+        };
+
+        let mut text = line(headers.iter().zip(&widths).map(pad).collect());
+        text += &line(
+            self.alignments
+                .iter()
+                .zip(&widths)
+                .map(|(alignment, width)| alignment.marker(*width))
+                .collect(),
+        );
+        for row in &rows {
+            text += &line(row.iter().zip(&widths).map(pad).collect());
+        }
+        text
+    }
+
+    /// Renders the snapshot as an HTML table: one `th` per column (with its data type in
+    /// `data-dtype`) and one `td` per cell, with the `align` attribute of the column.
+    pub fn to_html(&self) -> String {
+        let mut html = String::from("<table>\n<thead>\n<tr>");
+        for (header, dtype) in self.headers.iter().zip(&self.dtypes) {
+            let _ = write!(
+                html,
+                "<th data-dtype=\"{}\">{}</th>",
+                escape_html(&dtype.to_string()),
+                escape_html(header)
+            );
+        }
+        html += "</tr>\n</thead>\n<tbody>\n";
+        for row in &self.rows {
+            html += "<tr>";
+            for (cell, alignment) in row.iter().zip(&self.alignments) {
+                let _ = write!(
+                    html,
+                    "<td align=\"{}\">{}</td>",
+                    alignment.html(),
+                    escape_html(cell)
+                );
+            }
+            html += "</tr>\n";
+        }
+        html += "</tbody>\n</table>\n";
+        html
+    }
+}
+
+/// Renders `df` with `format` as a Markdown text snapshot (see `TableSnapshot::to_text`).
+///
+/// All rows of `df` are rendered: use `df.head(..)` or `df.slice(..)` for large data.
+pub fn render_snapshot(df: &DataFrame, format: &Arc<DataFormat>) -> String {
+    TableSnapshot::new(df, format).to_text()
+}
+
+/// Renders `df` with `format` as an HTML snapshot (see `TableSnapshot::to_html`).
+pub fn render_snapshot_html(df: &DataFrame, format: &Arc<DataFormat>) -> String {
+    TableSnapshot::new(df, format).to_html()
+}
+
+/// Escapes the HTML special characters of `text`.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_snapshot`
+#[cfg(test)]
+mod tests_snapshot {
+    use super::*;
+    use crate::ColumnUnit;
+
+    fn sample() -> PolarsResult<DataFrame> {
+        df!(
+            "name" => &[Some("apple"), None, Some("a|b")],
+            "count" => &[1i64, 20, 300],
+            "price" => &[1.5, 22.25, 0.5],
+        )
+    }
+
+    fn format() -> DataFormat {
+        DataFormat {
+            decimal: 2,
+            auto_decimal: false,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn text_snapshot() -> PolarsResult<()> {
+        let snapshot = render_snapshot(&sample()?, &Arc::new(format()));
+        println!("{snapshot}");
+
+        let expected = "\
+| name  | count | price |
+| :---- | :---: | ----: |
+| apple | 1     | 1.50  |
+|       | 20    | 22.25 |
+| a\\|b  | 300   | 0.50  |
+";
+        assert_eq!(snapshot, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn hidden_ordered_columns_and_units() -> PolarsResult<()> {
+        let mut format = format();
+        format.hidden_columns.insert("name".to_string());
+        format.column_order = vec!["price".to_string()];
+        format.column_units.insert(
+            "price".to_string(),
+            ColumnUnit {
+                unit: "BRL".to_string(),
+                display_unit: None,
+            },
+        );
+
+        let snapshot = TableSnapshot::new(&sample()?, &Arc::new(format));
+        assert_eq!(snapshot.headers, ["price [BRL]", "count"]);
+        assert_eq!(
+            snapshot.alignments,
+            [CellAlignment::Right, CellAlignment::Center]
+        );
+        assert_eq!(snapshot.rows[1], ["22.25", "20"]);
+
+        let html = snapshot.to_html();
+        println!("{html}");
+        assert!(html.contains("<th data-dtype=\"f64\">price [BRL]</th>"));
+        assert!(html.contains("<td align=\"right\">22.25</td><td align=\"center\">20</td>"));
+        Ok(())
+    }
+
+    /// The alignment set from the header menu overrides the one of the data type.
+    #[test]
+    fn column_alignment_override() -> PolarsResult<()> {
+        let mut format = format();
+        format
+            .column_alignments
+            .insert("price".to_string(), egui::Align::LEFT);
+
+        let snapshot = TableSnapshot::new(&sample()?, &Arc::new(format));
+        assert_eq!(
+            snapshot.alignments,
+            [
+                CellAlignment::Left,
+                CellAlignment::Center,
+                CellAlignment::Left
+            ]
+        );
+        Ok(())
+    }
+
+    /// `decimal_and_layout_v2`: the `COL_SPECIAL` columns are centered, with 4 decimal places.
+    #[cfg(feature = "format-special")]
+    #[test]
+    fn special_columns_v2() -> PolarsResult<()> {
+        let df = df!("Alíquota" => &[0.18, 0.07])?;
+        let snapshot = TableSnapshot::new(&df, &Arc::new(format()));
+
+        assert_eq!(snapshot.alignments, [CellAlignment::Center]);
+        assert_eq!(snapshot.rows, [["0.1800"], ["0.0700"]]);
+        Ok(())
+    }
+}
@@ -1,12 +1,17 @@
 //! Defines the representation of sorting criteria for the table.
 //! This module contains the core types for managing single and multiple sort column states.
 
+use crate::PolarsViewResult;
+
+use egui::{Align, Grid, Layout, Modifiers, TextEdit, Ui};
+use polars::{prelude::*, sql::sql_expr};
+use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 /// Represents a single criterion for sorting.
 /// Used within `DataFrameContainer` to store the cumulative sort order as `Vec<SortBy>`.
 /// The order of criteria in the Vec determines sort precedence.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SortBy {
     /// The name of the column to sort by.
     pub column_name: String,
@@ -16,10 +21,40 @@ pub struct SortBy {
     pub nulls_last: bool,
 }
 
+/// Describes *how* a sortable header was clicked, based on the modifier keys held.
+///
+/// - Plain click: `Replace` (single-column sort, clearing other criteria).
+/// - Shift + click: `Append` (add/cycle this column within the multi-column sort).
+/// - Alt + click: `ToggleNulls` (flip nulls placement of an already sorted column).
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HeaderClick {
+    /// Cycle this column (Asc -> Desc -> None) and drop every other criterion.
+    Replace,
+    /// Cycle this column (Asc -> Desc -> None) while keeping the other criteria.
+    Append,
+    /// Toggle nulls first/last for this column, keeping its direction and precedence.
+    ToggleNulls,
+}
+
+impl HeaderClick {
+    /// Maps the modifier keys held during a header click to a `HeaderClick`.
+    /// Alt takes precedence over Shift.
+    pub fn from_modifiers(modifiers: &Modifiers) -> Self {
+        if modifiers.alt {
+            HeaderClick::ToggleNulls
+        } else if modifiers.shift {
+            HeaderClick::Append
+        } else {
+            HeaderClick::Replace
+        }
+    }
+}
+
 /// Represents the *interaction* state for sorting a specific column header in the UI.
 ///
-/// This enum manages the click cycle:
-/// NotSorted -> DescNullsFirst -> AscNullsFirst -> DescNullsLast -> AscNullsLast -> NotSorted.
+/// The direction cycle (plain or Shift click) is:
+/// NotSorted -> Ascending -> Descending -> NotSorted.
+/// Nulls placement is changed independently with Alt + click.
 /// The actual applied cumulative sort state (`Vec<SortBy>`) is stored and managed separately
 /// in `DataFrameContainer`.
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -27,36 +62,83 @@ pub enum HeaderSortState {
     /// Column is not part of the current sort criteria list.
     NotSorted,
     /// Column sorted descending, nulls appear first.
-    DescendingNullsFirst, // State 1
+    DescendingNullsFirst,
     /// Column sorted ascending, nulls appear first.
-    AscendingNullsFirst, // State 2
+    AscendingNullsFirst,
     /// Column sorted descending, nulls appear last.
-    DescendingNullsLast, // State 3
+    DescendingNullsLast,
     /// Column sorted ascending, nulls appear last.
-    AscendingNullsLast, // State 4
+    AscendingNullsLast,
 }
 
 impl HeaderSortState {
-    /// Calculates the next interaction state in the UI cycle for a header click.
-    ///
-    /// The cycle progresses as follows:
-    /// 1. `NotSorted`            -> `DescendingNullsFirst`
-    /// 2. `DescendingNullsFirst` -> `AscendingNullsFirst`
-    /// 3. `AscendingNullsFirst`  -> `DescendingNullsLast`
-    /// 4. `DescendingNullsLast`  -> `AscendingNullsLast`
-    /// 5. `AscendingNullsLast`   -> `NotSorted` (removes the sort for this column)
-    ///
-    /// Called by `container.rs::render_table_header` when a click is detected.
+    /// All states, in the order of the sort legend (see `render_sort_legend`).
+    pub const ALL: [HeaderSortState; 5] = [
+        HeaderSortState::NotSorted,
+        HeaderSortState::AscendingNullsLast,
+        HeaderSortState::DescendingNullsLast,
+        HeaderSortState::AscendingNullsFirst,
+        HeaderSortState::DescendingNullsFirst,
+    ];
+
+    /// Describes the state in words, for the sort legend.
+    pub fn description(&self) -> &'static str {
+        match self {
+            HeaderSortState::NotSorted => "Not sorted",
+            HeaderSortState::AscendingNullsLast => "Ascending, nulls last",
+            HeaderSortState::DescendingNullsLast => "Descending, nulls last",
+            HeaderSortState::AscendingNullsFirst => "Ascending, nulls first",
+            HeaderSortState::DescendingNullsFirst => "Descending, nulls first",
+        }
+    }
+
+    /// Builds the interaction state from an optional sort criterion.
+    pub fn from_sort_by(criterion: Option<&SortBy>) -> Self {
+        match criterion.map(|c| (c.ascending, c.nulls_last)) {
+            None => HeaderSortState::NotSorted,
+            Some((false, false)) => HeaderSortState::DescendingNullsFirst,
+            Some((true, false)) => HeaderSortState::AscendingNullsFirst,
+            Some((false, true)) => HeaderSortState::DescendingNullsLast,
+            Some((true, true)) => HeaderSortState::AscendingNullsLast,
+        }
+    }
+
+    /// Returns the `(ascending, nulls_last)` flags for sorted states, `None` for `NotSorted`.
+    pub fn flags(&self) -> Option<(bool, bool)> {
+        match self {
+            HeaderSortState::NotSorted => None,
+            HeaderSortState::DescendingNullsFirst => Some((false, false)),
+            HeaderSortState::AscendingNullsFirst => Some((true, false)),
+            HeaderSortState::DescendingNullsLast => Some((false, true)),
+            HeaderSortState::AscendingNullsLast => Some((true, true)),
+        }
+    }
+
+    /// Calculates the next direction state for a plain or Shift click.
     ///
-    /// ### Returns
-    /// The next `HeaderSortState` in the cycle.
-    pub fn cycle_next(&self) -> Self {
+    /// The cycle progresses as follows, preserving the nulls placement:
+    /// 1. `NotSorted`                     -> `AscendingNullsLast`
+    /// 2. `Ascending{NullsFirst,NullsLast}` -> `Descending{NullsFirst,NullsLast}`
+    /// 3. `Descending*`                   -> `NotSorted` (removes the sort for this column)
+    pub fn cycle_direction(&self) -> Self {
         match self {
-            HeaderSortState::NotSorted => HeaderSortState::DescendingNullsFirst,
-            HeaderSortState::DescendingNullsFirst => HeaderSortState::AscendingNullsFirst,
-            HeaderSortState::AscendingNullsFirst => HeaderSortState::DescendingNullsLast,
-            HeaderSortState::DescendingNullsLast => HeaderSortState::AscendingNullsLast,
-            HeaderSortState::AscendingNullsLast => HeaderSortState::NotSorted,
+            HeaderSortState::NotSorted => HeaderSortState::AscendingNullsLast,
+            HeaderSortState::AscendingNullsFirst => HeaderSortState::DescendingNullsFirst,
+            HeaderSortState::AscendingNullsLast => HeaderSortState::DescendingNullsLast,
+            HeaderSortState::DescendingNullsFirst | HeaderSortState::DescendingNullsLast => {
+                HeaderSortState::NotSorted
+            }
+        }
+    }
+
+    /// Flips nulls placement (Alt + click). `NotSorted` stays unsorted.
+    pub fn toggle_nulls(&self) -> Self {
+        match self {
+            HeaderSortState::NotSorted => HeaderSortState::NotSorted,
+            HeaderSortState::DescendingNullsFirst => HeaderSortState::DescendingNullsLast,
+            HeaderSortState::AscendingNullsFirst => HeaderSortState::AscendingNullsLast,
+            HeaderSortState::DescendingNullsLast => HeaderSortState::DescendingNullsFirst,
+            HeaderSortState::AscendingNullsLast => HeaderSortState::AscendingNullsFirst,
         }
     }
 
@@ -91,4 +173,428 @@ impl HeaderSortState {
             None => base_icon.to_string(),
         }
     }
+
+    /// Describes the sort state of `column_name` in words, for screen readers
+    /// (the AccessKit label of the header sort button), e.g.,
+    /// "Sort by price: ascending, nulls last, sort priority 1".
+    ///
+    /// ### Arguments
+    /// * `index`: `Option<usize>` - The 0-based sort precedence. `None` if not sorted.
+    pub fn accessible_label(&self, column_name: &str, index: Option<usize>) -> String {
+        let state = match self.flags() {
+            None => return format!("Sort by {column_name}: not sorted"),
+            Some((ascending, nulls_last)) => format!(
+                "{}, nulls {}",
+                if ascending { "ascending" } else { "descending" },
+                if nulls_last { "last" } else { "first" }
+            ),
+        };
+
+        match index {
+            Some(idx) => format!("Sort by {column_name}: {state}, sort priority {}", idx + 1),
+            None => format!("Sort by {column_name}: {state}"),
+        }
+    }
+}
+
+/// Renders the sort legend: the header icon of each `HeaderSortState`, the multi-sort
+/// numbering and what each click does.
+pub fn render_sort_legend(ui: &mut Ui) {
+    Grid::new("sort_legend_grid")
+        .num_columns(2)
+        .spacing([10.0, 4.0])
+        .show(ui, |ui| {
+            for state in HeaderSortState::ALL {
+                ui.label(state.get_icon(None));
+                ui.label(state.description());
+                ui.end_row();
+            }
+            ui.label(HeaderSortState::AscendingNullsLast.get_icon(Some(1)));
+            ui.label("Second column of a multi-column sort");
+            ui.end_row();
+        });
+
+    ui.separator();
+    ui.label("Click: ascending → descending → not sorted (clears the other columns).");
+    ui.label("Shift + click: same cycle, added to the multi-column sort.");
+    ui.label("Alt + click: nulls first ↔ nulls last (sorted columns only).");
+}
+
+/// Renders the first-use hint above the table: how header sorting works, with the
+/// sort legend (see `render_sort_legend`) in a popup.
+///
+/// Returns `true` when the hint is dismissed ("Got it").
+pub fn render_sort_hint(ui: &mut Ui) -> bool {
+    let mut dismissed = false;
+
+    ui.horizontal_wrapped(|ui| {
+        ui.label(format!(
+            "💡 Click the {} icon of a header to sort: ascending, descending, then not sorted. \
+            Shift + click sorts by several columns; Alt + click moves the nulls.",
+            HeaderSortState::NotSorted.get_icon(None)
+        ));
+        ui.menu_button("Sort legend", render_sort_legend);
+        dismissed = ui.button("Got it").clicked();
+    });
+
+    dismissed
+}
+
+/// Computes the new cumulative sort criteria after a header click.
+///
+/// Called by `data_container.rs::render_table_header`. Pure function so the
+/// click semantics can be tested without a UI.
+///
+/// ### Arguments
+/// * `criteria`: The currently applied sort criteria.
+/// * `column_name`: The clicked column.
+/// * `click`: How the header was clicked (see `HeaderClick`).
+///
+/// ### Returns
+/// The new `Vec<SortBy>` to pass to `DataContainer::apply_sort`.
+pub fn apply_header_click(
+    criteria: &[SortBy],
+    column_name: &str,
+    click: HeaderClick,
+) -> Vec<SortBy> {
+    let current_pos = criteria.iter().position(|c| c.column_name == column_name);
+    let current_state = HeaderSortState::from_sort_by(current_pos.map(|pos| &criteria[pos]));
+
+    let next_state = match click {
+        HeaderClick::Replace | HeaderClick::Append => current_state.cycle_direction(),
+        HeaderClick::ToggleNulls => current_state.toggle_nulls(),
+    };
+
+    let next_criterion = next_state.flags().map(|(ascending, nulls_last)| SortBy {
+        column_name: column_name.to_string(),
+        ascending,
+        nulls_last,
+    });
+
+    match click {
+        // Single-column sort: every other criterion is dropped.
+        HeaderClick::Replace => next_criterion.into_iter().collect(),
+        // Multi-column sort: update in place, append, or remove.
+        HeaderClick::Append | HeaderClick::ToggleNulls => {
+            let mut new_criteria = criteria.to_vec();
+            match (current_pos, next_criterion) {
+                (Some(pos), Some(criterion)) => new_criteria[pos] = criterion,
+                (Some(pos), None) => {
+                    new_criteria.remove(pos);
+                }
+                (None, Some(criterion)) => new_criteria.push(criterion),
+                (None, None) => {}
+            }
+            new_criteria
+        }
+    }
+}
+
+/// Computes the sort criteria for "Sort ascending"/"Sort descending" of the header context
+/// menu: a single-column sort on `column_name` (like a plain header click), keeping its
+/// nulls placement if it was already sorted (nulls last otherwise).
+pub fn sort_single_column(criteria: &[SortBy], column_name: &str, ascending: bool) -> Vec<SortBy> {
+    let nulls_last = criteria
+        .iter()
+        .find(|c| c.column_name == column_name)
+        .is_none_or(|c| c.nulls_last);
+
+    vec![SortBy {
+        column_name: column_name.to_string(),
+        ascending,
+        nulls_last,
+    }]
+}
+
+/// Sorts by an arbitrary Polars SQL expression (e.g., `abs(delta)`, `lower(name)`),
+/// for orderings that header clicks can't express.
+///
+/// Edited in the "Sort" side panel section (`render_sort_expression`) and applied
+/// via `DataContainer::apply_sort_expression`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SortExpression {
+    /// The SQL expression evaluated as a temporary sort key.
+    pub expression: String,
+    /// The sort direction. `true` for ascending, `false` for descending.
+    pub ascending: bool,
+    /// How nulls should be ordered. `true` to place nulls last, `false` for first.
+    pub nulls_last: bool,
+}
+
+impl Default for SortExpression {
+    fn default() -> Self {
+        SortExpression {
+            expression: String::new(),
+            ascending: true,
+            nulls_last: true,
+        }
+    }
+}
+
+impl SortExpression {
+    /// Returns `true` if there is no expression to evaluate.
+    pub fn is_empty(&self) -> bool {
+        self.expression.trim().is_empty()
+    }
+
+    /// Sorts `df` by the evaluated expression. The key is computed on the fly
+    /// and never added to the displayed columns.
+    ///
+    /// ### Errors
+    /// * `PolarsViewError::Polars`: If the expression cannot be parsed or evaluated
+    ///   (e.g., unknown column, invalid function).
+    pub fn sort(&self, df: &DataFrame) -> PolarsViewResult<DataFrame> {
+        let key = sql_expr(self.expression.trim())?;
+
+        let sort_options = SortMultipleOptions::default()
+            .with_order_descending(!self.ascending)
+            .with_nulls_last(self.nulls_last)
+            .with_maintain_order(true)
+            .with_multithreaded(true);
+
+        let df_sorted = df
+            .clone()
+            .lazy()
+            .sort_by_exprs([key], sort_options)
+            .collect()?;
+
+        Ok(df_sorted)
+    }
+
+    /// Renders the "Sort" side panel controls (expression input, direction, nulls placement).
+    ///
+    /// Follows the same pattern as `DataFilter::render_query`: widgets modify `self`
+    /// directly, and the "Apply" button returns `Some(self.clone())` to signal that
+    /// `layout.rs` should trigger an asynchronous sort.
+    pub fn render_sort_expression(&mut self, ui: &mut Ui) -> Option<SortExpression> {
+        let mut result = None;
+
+        Grid::new("sort_expression_grid")
+            .num_columns(2)
+            .spacing([10.0, 20.0])
+            .striped(true)
+            .show(ui, |ui| {
+                ui.label("Expression:");
+                ui.add(
+                    TextEdit::singleline(&mut self.expression)
+                        .hint_text("abs(delta)")
+                        .desired_width(f32::INFINITY)
+                        .font(egui::TextStyle::Monospace),
+                )
+                .on_hover_text(
+                    "Polars SQL expression evaluated as a temporary sort key.\n\
+                    Examples: abs(delta), lower(name), `Valor Total` / `Quantidade`",
+                );
+                ui.end_row();
+
+                ui.label("Ascending:");
+                ui.checkbox(&mut self.ascending, "")
+                    .on_hover_text("Uncheck to sort in descending order.");
+                ui.end_row();
+
+                ui.label("Nulls Last:");
+                ui.checkbox(&mut self.nulls_last, "")
+                    .on_hover_text("Place null keys after all other values.");
+                ui.end_row();
+
+                ui.label(""); // For alignment.
+                ui.with_layout(Layout::top_down(Align::Center), |ui| {
+                    if ui
+                        .add_enabled(!self.is_empty(), egui::Button::new("Sort by Expression"))
+                        .clicked()
+                    {
+                        result = Some(self.clone());
+                    }
+                });
+                ui.end_row();
+            });
+
+        result
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_sort`
+#[cfg(test)]
+mod tests_sort {
+    use super::*;
+
+    fn sort_by(name: &str, ascending: bool, nulls_last: bool) -> SortBy {
+        SortBy {
+            column_name: name.to_string(),
+            ascending,
+            nulls_last,
+        }
+    }
+
+    #[test]
+    fn test_plain_click_cycles_and_clears_others() {
+        let criteria = vec![sort_by("a", true, true), sort_by("b", true, true)];
+
+        // Clicking "b" keeps only "b", now descending.
+        let next = apply_header_click(&criteria, "b", HeaderClick::Replace);
+        assert_eq!(next, vec![sort_by("b", false, true)]);
+
+        // Clicking "b" again removes the sort entirely.
+        let next = apply_header_click(&next, "b", HeaderClick::Replace);
+        assert!(next.is_empty());
+
+        // Clicking an unsorted column starts ascending.
+        let next = apply_header_click(&criteria, "c", HeaderClick::Replace);
+        assert_eq!(next, vec![sort_by("c", true, true)]);
+    }
+
+    #[test]
+    fn test_shift_click_appends_and_keeps_precedence() {
+        let criteria = vec![sort_by("a", true, true)];
+
+        let next = apply_header_click(&criteria, "b", HeaderClick::Append);
+        assert_eq!(
+            next,
+            vec![sort_by("a", true, true), sort_by("b", true, true)]
+        );
+
+        let next = apply_header_click(&next, "a", HeaderClick::Append);
+        assert_eq!(
+            next,
+            vec![sort_by("a", false, true), sort_by("b", true, true)]
+        );
+
+        let next = apply_header_click(&next, "a", HeaderClick::Append);
+        assert_eq!(next, vec![sort_by("b", true, true)]);
+    }
+
+    #[test]
+    fn test_alt_click_toggles_nulls_only() {
+        let criteria = vec![sort_by("a", false, true), sort_by("b", true, true)];
+
+        let next = apply_header_click(&criteria, "b", HeaderClick::ToggleNulls);
+        assert_eq!(
+            next,
+            vec![sort_by("a", false, true), sort_by("b", true, false)]
+        );
+
+        // Direction cycling preserves the toggled nulls placement.
+        let next = apply_header_click(&next, "b", HeaderClick::Append);
+        assert_eq!(
+            next,
+            vec![sort_by("a", false, true), sort_by("b", false, false)]
+        );
+
+        // Alt + click on an unsorted column is a no-op.
+        let next = apply_header_click(&criteria, "c", HeaderClick::ToggleNulls);
+        assert_eq!(next, criteria);
+    }
+
+    #[test]
+    fn test_sort_single_column() {
+        let criteria = vec![sort_by("a", true, true), sort_by("b", true, false)];
+
+        assert_eq!(
+            sort_single_column(&criteria, "b", false),
+            vec![sort_by("b", false, false)]
+        );
+        assert_eq!(
+            sort_single_column(&criteria, "c", true),
+            vec![sort_by("c", true, true)]
+        );
+    }
+
+    #[test]
+    fn test_from_modifiers() {
+        assert_eq!(
+            HeaderClick::from_modifiers(&Modifiers::NONE),
+            HeaderClick::Replace
+        );
+        assert_eq!(
+            HeaderClick::from_modifiers(&Modifiers::SHIFT),
+            HeaderClick::Append
+        );
+        assert_eq!(
+            HeaderClick::from_modifiers(&Modifiers::ALT),
+            HeaderClick::ToggleNulls
+        );
+    }
+
+    #[test]
+    fn test_sort_by_expression() -> PolarsViewResult<()> {
+        let df_input = df!(
+            "name" => &["C", "a", "b"],
+            "delta" => &[Some(1), None, Some(-5)],
+        )?;
+
+        let sort_expr = SortExpression {
+            expression: "abs(delta)".to_string(),
+            ascending: false,
+            nulls_last: true,
+        };
+        let df_output = sort_expr.sort(&df_input)?;
+        println!("df_output: {df_output}");
+
+        let df_expected = df!(
+            "name" => &["b", "C", "a"],
+            "delta" => &[Some(-5), Some(1), None],
+        )?;
+        assert!(df_output.equals_missing(&df_expected));
+
+        let sort_expr = SortExpression {
+            expression: "lower(name)".to_string(),
+            ..Default::default()
+        };
+        let df_output = sort_expr.sort(&df_input)?;
+        let names: Vec<Option<&str>> = df_output.column("name")?.str()?.into_iter().collect();
+        assert_eq!(names, vec![Some("a"), Some("b"), Some("C")]);
+        assert_eq!(
+            df_output.width(),
+            2,
+            "The sort key must not be kept as a column"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_accessible_label() {
+        assert_eq!(
+            HeaderSortState::NotSorted.accessible_label("price", None),
+            "Sort by price: not sorted"
+        );
+        assert_eq!(
+            HeaderSortState::DescendingNullsFirst.accessible_label("price", Some(1)),
+            "Sort by price: descending, nulls first, sort priority 2"
+        );
+    }
+
+    #[test]
+    fn test_sort_by_invalid_expression() {
+        let df_input = df!("a" => &[1, 2]).unwrap();
+        let sort_expr = SortExpression {
+            expression: "unknown_column + 1".to_string(),
+            ..Default::default()
+        };
+        assert!(sort_expr.sort(&df_input).is_err());
+    }
+
+    #[test]
+    fn test_sort_legend_states() {
+        // The legend lists each state once, each with its own icon.
+        let mut icons: Vec<String> = HeaderSortState::ALL
+            .iter()
+            .map(|state| state.get_icon(None))
+            .collect();
+        icons.sort_unstable();
+        icons.dedup();
+        assert_eq!(icons.len(), HeaderSortState::ALL.len());
+
+        for state in HeaderSortState::ALL {
+            let flags = state.flags();
+            let criterion =
+                flags.map(|(ascending, nulls_last)| sort_by("a", ascending, nulls_last));
+            assert_eq!(HeaderSortState::from_sort_by(criterion.as_ref()), state);
+        }
+    }
 }
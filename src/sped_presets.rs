@@ -0,0 +1,342 @@
+//! Column-level layout presets for SPED/EFD files (`format-special` feature).
+//!
+//! A `LayoutPreset` maps known record columns to data types, names, decimals and alignments.
+//! When a loaded file contains all the `detect` columns of a preset, the preset is applied
+//! automatically: types and names when reading (`DataContainer::load_data`), decimals and
+//! alignments through `DataFormat::column_formats` (see `decimal_and_layout_v2`).
+//!
+//! Besides the built-in presets, user presets can be loaded from a JSON file with
+//! `--presets <FILE>`; they take precedence over the built-in ones.
+
+use crate::{ColumnFormat, PolarsViewResult};
+
+use egui::Align;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path, sync::OnceLock};
+
+/// Default date format of SPED/EFD text files (e.g., `31012024`).
+pub const SPED_DATE_FORMAT: &str = "%d%m%Y";
+
+/// User presets loaded with `--presets` (see `set_user_presets`).
+static USER_PRESETS: OnceLock<Vec<LayoutPreset>> = OnceLock::new();
+
+/// Target data type of a preset column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PresetDtype {
+    String,
+    Int64,
+    /// Strings use the SPED decimal comma (`1234,56`).
+    Float64,
+    /// Strings are parsed with `ColumnPreset::date_format` (default `SPED_DATE_FORMAT`).
+    Date,
+}
+
+/// Settings of one column of a `LayoutPreset`. All fields but `column` are optional.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnPreset {
+    /// Column name, as read from the file.
+    pub column: String,
+    /// New column name (e.g., `VL_ITEM` -> `Valor do Item`).
+    pub rename: Option<String>,
+    pub dtype: Option<PresetDtype>,
+    /// Date format for `PresetDtype::Date` (chrono syntax).
+    pub date_format: Option<String>,
+    /// Decimal places (Float columns).
+    pub decimal: Option<usize>,
+    pub align: Option<Align>,
+}
+
+impl ColumnPreset {
+    /// Name of the column after the preset is applied.
+    pub fn final_name(&self) -> &str {
+        self.rename.as_deref().unwrap_or(&self.column)
+    }
+
+    /// Expression casting the column to `self.dtype`, given its current `dtype`.
+    /// Returns `None` if no conversion is needed.
+    fn cast_expr(&self, dtype: &DataType) -> Option<Expr> {
+        let target = self.dtype?;
+        let column = col(self.column.as_str());
+
+        match (target, dtype) {
+            (PresetDtype::String, DataType::String)
+            | (PresetDtype::Int64, DataType::Int64)
+            | (PresetDtype::Float64, DataType::Float64)
+            | (PresetDtype::Date, DataType::Date) => None,
+            (PresetDtype::Float64, DataType::String) => Some(
+                column
+                    .str()
+                    .replace_all(lit(","), lit("."), true)
+                    .cast(DataType::Float64),
+            ),
+            (PresetDtype::Date, DataType::String) => {
+                let format = self.date_format.as_deref().unwrap_or(SPED_DATE_FORMAT);
+                Some(column.str().to_date(StrptimeOptions {
+                    format: Some(format.into()),
+                    strict: false,
+                    ..Default::default()
+                }))
+            }
+            (PresetDtype::String, _) => Some(column.cast(DataType::String)),
+            (PresetDtype::Int64, _) => Some(column.cast(DataType::Int64)),
+            (PresetDtype::Float64, _) => Some(column.cast(DataType::Float64)),
+            (PresetDtype::Date, _) => Some(column.cast(DataType::Date)),
+        }
+    }
+}
+
+/// A named set of column settings for a known file layout.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LayoutPreset {
+    pub name: String,
+    /// Columns that must all be present for the preset to match.
+    pub detect: Vec<String>,
+    pub columns: Vec<ColumnPreset>,
+}
+
+impl LayoutPreset {
+    /// Returns `true` if `schema` contains all the `detect` columns.
+    pub fn matches(&self, schema: &Schema) -> bool {
+        !self.detect.is_empty() && self.detect.iter().all(|name| schema.contains(name))
+    }
+
+    /// Applies the data types, then the names of the preset columns present in `df`.
+    pub fn apply(&self, df: DataFrame) -> PolarsResult<DataFrame> {
+        let schema = df.schema().clone();
+        let present: Vec<&ColumnPreset> = self
+            .columns
+            .iter()
+            .filter(|preset| schema.contains(&preset.column))
+            .collect();
+
+        let casts: Vec<Expr> = present
+            .iter()
+            .filter_map(|preset| preset.cast_expr(schema.get(&preset.column)?))
+            .collect();
+
+        let (old, new): (Vec<&str>, Vec<&str>) = present
+            .iter()
+            .filter(|preset| preset.rename.is_some())
+            .map(|preset| (preset.column.as_str(), preset.final_name()))
+            .unzip();
+
+        df.lazy()
+            .with_columns(casts)
+            .rename(old, new, true)
+            .collect()
+    }
+
+    /// Decimals and alignments of the preset, keyed by final column name.
+    pub fn column_formats(&self) -> HashMap<String, ColumnFormat> {
+        self.columns
+            .iter()
+            .filter(|preset| preset.decimal.is_some() || preset.align.is_some())
+            .map(|preset| {
+                let format = ColumnFormat {
+                    decimal: preset.decimal,
+                    align: preset.align,
+                };
+                (preset.final_name().to_string(), format)
+            })
+            .collect()
+    }
+}
+
+/// Shorthand for a preset column with a data type, decimals and alignment.
+fn column(
+    name: &str,
+    dtype: PresetDtype,
+    decimal: Option<usize>,
+    align: Option<Align>,
+) -> ColumnPreset {
+    ColumnPreset {
+        column: name.to_string(),
+        dtype: Some(dtype),
+        decimal,
+        align,
+        ..Default::default()
+    }
+}
+
+/// Built-in presets: raw SPED records (EFD ICMS/IPI and EFD Contribuições)
+/// and the CSV reports of the `efd_contribuicoes` program.
+pub fn builtin_presets() -> Vec<LayoutPreset> {
+    use PresetDtype::{Date, Float64, Int64};
+
+    let money = |name: &str| column(name, Float64, Some(2), Some(Align::RIGHT));
+    let rate = |name: &str| column(name, Float64, Some(4), Some(Align::Center));
+
+    vec![
+        LayoutPreset {
+            name: "EFD - C100 (Documento - Código 01, 1B, 04, 55 e 65)".to_string(),
+            detect: ["REG", "IND_OPER", "COD_PART", "CHV_NFE", "DT_DOC"]
+                .map(str::to_string)
+                .to_vec(),
+            columns: vec![
+                column("CHV_NFE", PresetDtype::String, None, None),
+                column("DT_DOC", Date, None, Some(Align::Center)),
+                column("DT_E_S", Date, None, Some(Align::Center)),
+                money("VL_DOC"),
+                money("VL_DESC"),
+                money("VL_MERC"),
+                money("VL_BC_ICMS"),
+                money("VL_ICMS"),
+                money("VL_PIS"),
+                money("VL_COFINS"),
+            ],
+        },
+        LayoutPreset {
+            name: "EFD - C170 (Itens do Documento)".to_string(),
+            detect: ["REG", "NUM_ITEM", "COD_ITEM", "VL_ITEM"]
+                .map(str::to_string)
+                .to_vec(),
+            columns: vec![
+                column("NUM_ITEM", Int64, None, Some(Align::Center)),
+                column("CFOP", PresetDtype::String, None, Some(Align::Center)),
+                column("QTD", Float64, Some(5), Some(Align::RIGHT)),
+                money("VL_ITEM"),
+                money("VL_DESC"),
+                money("VL_BC_ICMS"),
+                rate("ALIQ_ICMS"),
+                money("VL_ICMS"),
+                money("VL_BC_PIS"),
+                rate("ALIQ_PIS"),
+                money("VL_PIS"),
+                money("VL_BC_COFINS"),
+                rate("ALIQ_COFINS"),
+                money("VL_COFINS"),
+            ],
+        },
+        LayoutPreset {
+            name: "EFD Contribuições - Itens de Documentos Fiscais".to_string(),
+            detect: [
+                "Registro",
+                "CST",
+                "Alíquota de PIS/PASEP",
+                "Alíquota de COFINS",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            columns: vec![
+                column("CST", Int64, None, Some(Align::Center)),
+                rate("Alíquota de PIS/PASEP"),
+                rate("Alíquota de COFINS"),
+                money("Valor Total do Item"),
+                money("Valor da Base de Cálculo das Contribuições"),
+                money("Valor de PIS/PASEP"),
+                money("Valor de COFINS"),
+            ],
+        },
+    ]
+}
+
+/// Reads user presets (a JSON array of `LayoutPreset`) from `path`.
+pub fn load_presets(path: impl AsRef<Path>) -> PolarsViewResult<Vec<LayoutPreset>> {
+    Ok(serde_json::from_str(&std::fs::read_to_string(path)?)?)
+}
+
+/// Registers the user presets (`--presets`). Only the first call has effect.
+pub fn set_user_presets(presets: Vec<LayoutPreset>) {
+    if USER_PRESETS.set(presets).is_err() {
+        tracing::warn!("User layout presets were already set; ignoring.");
+    }
+}
+
+/// Finds the first preset (user presets first, then built-in) matching `schema`.
+pub fn find_preset(schema: &Schema) -> Option<LayoutPreset> {
+    USER_PRESETS
+        .get()
+        .into_iter()
+        .flatten()
+        .cloned()
+        .chain(builtin_presets())
+        .find(|preset| preset.matches(schema))
+}
+
+/// Finds a preset (user presets first, then built-in) by name.
+pub fn preset_by_name(name: &str) -> Option<LayoutPreset> {
+    USER_PRESETS
+        .get()
+        .into_iter()
+        .flatten()
+        .cloned()
+        .chain(builtin_presets())
+        .find(|preset| preset.name == name)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test --features format-special -- --show-output tests_sped_presets`
+#[cfg(test)]
+mod tests_sped_presets {
+    use super::*;
+
+    #[test]
+    fn apply_c170_preset() -> PolarsViewResult<()> {
+        let df = df!(
+            "REG" => &["C170", "C170"],
+            "NUM_ITEM" => &["1", "2"],
+            "COD_ITEM" => &["A1", "B2"],
+            "VL_ITEM" => &["1234,56", "10"],
+            "ALIQ_PIS" => &["1,65", "0"],
+        )?;
+
+        let preset = find_preset(df.schema()).expect("C170 preset");
+        assert_eq!(preset.name, "EFD - C170 (Itens do Documento)");
+
+        let df = preset.apply(df)?;
+        dbg!(&df);
+
+        assert_eq!(df.column("NUM_ITEM")?.dtype(), &DataType::Int64);
+        let values: Vec<Option<f64>> = df.column("VL_ITEM")?.f64()?.into_iter().collect();
+        assert_eq!(values, vec![Some(1234.56), Some(10.0)]);
+
+        let formats = preset.column_formats();
+        assert_eq!(formats["ALIQ_PIS"].decimal, Some(4));
+        assert_eq!(formats["ALIQ_PIS"].align, Some(Align::Center));
+        Ok(())
+    }
+
+    #[test]
+    fn apply_dates_and_renames() -> PolarsViewResult<()> {
+        let preset = LayoutPreset {
+            name: "test".to_string(),
+            detect: vec!["DT_DOC".to_string()],
+            columns: vec![ColumnPreset {
+                column: "DT_DOC".to_string(),
+                rename: Some("Data".to_string()),
+                dtype: Some(PresetDtype::Date),
+                ..Default::default()
+            }],
+        };
+
+        let df = df!("DT_DOC" => &["31012024", "bad"])?;
+        assert!(preset.matches(df.schema()));
+
+        let df = preset.apply(df)?;
+        let data = df.column("Data")?;
+        assert_eq!(data.dtype(), &DataType::Date);
+        assert_eq!(data.null_count(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn presets_json_round_trip() -> PolarsViewResult<()> {
+        let json = serde_json::to_string(&builtin_presets())?;
+        let presets: Vec<LayoutPreset> = serde_json::from_str(&json)?;
+        assert_eq!(presets, builtin_presets());
+
+        let minimal: Vec<LayoutPreset> =
+            serde_json::from_str(r#"[{ "name": "x", "columns": [{ "column": "a" }] }]"#)?;
+        assert!(minimal[0].detect.is_empty());
+        assert_eq!(minimal[0].columns[0].dtype, None);
+        Ok(())
+    }
+}
@@ -0,0 +1,327 @@
+//! Completion popup of the SQL editor ("Query" panel): while a word is typed, the table
+//! name, the column names (`DataFilter::schema`) and common Polars SQL keywords and
+//! functions starting with it are suggested. Tab or a click inserts the suggestion.
+
+use crate::sql_identifier;
+
+use egui::{
+    Area, Color32, Frame, Key, Modifiers, Order, RichText, TextEdit, Ui,
+    text::{CCursor, CCursorRange},
+};
+use polars::prelude::Schema;
+use std::ops::Range;
+
+/// Maximum number of suggestions shown by the popup.
+pub const MAX_COMPLETIONS: usize = 8;
+
+/// Polars SQL keywords and functions offered by `sql_completions`.
+pub const SQL_COMPLETION_WORDS: &[&str] = &[
+    "SELECT",
+    "FROM",
+    "WHERE",
+    "GROUP BY",
+    "ORDER BY",
+    "HAVING",
+    "LIMIT",
+    "OFFSET",
+    "DISTINCT",
+    "AS",
+    "AND",
+    "OR",
+    "NOT",
+    "IN",
+    "IS NULL",
+    "IS NOT NULL",
+    "LIKE",
+    "ILIKE",
+    "BETWEEN",
+    "CASE",
+    "WHEN",
+    "THEN",
+    "ELSE",
+    "END",
+    "JOIN",
+    "LEFT JOIN",
+    "INNER JOIN",
+    "ON",
+    "USING",
+    "UNION",
+    "EXCEPT",
+    "REPLACE",
+    "RENAME",
+    "WITH",
+    "ASC",
+    "DESC",
+    "NULLS FIRST",
+    "NULLS LAST",
+    "COUNT",
+    "SUM",
+    "AVG",
+    "MIN",
+    "MAX",
+    "MEDIAN",
+    "STDDEV",
+    "VARIANCE",
+    "FIRST",
+    "LAST",
+    "ROUND",
+    "ABS",
+    "CEIL",
+    "FLOOR",
+    "COALESCE",
+    "NULLIF",
+    "CAST",
+    "LOWER",
+    "UPPER",
+    "LENGTH",
+    "TRIM",
+    "LTRIM",
+    "RTRIM",
+    "SUBSTR",
+    "CONCAT",
+    "STARTS_WITH",
+    "ENDS_WITH",
+    "REGEXP_LIKE",
+    "STRFTIME",
+    "STRPTIME",
+    "DATE",
+    "EXTRACT",
+    "DATE_PART",
+];
+
+/// Kind of a completion (shown as a badge).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompletionKind {
+    Table,
+    Column,
+    Keyword,
+}
+
+/// A suggestion of the completion popup.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    pub kind: CompletionKind,
+    /// Text shown in the popup.
+    pub label: String,
+    /// Text inserted in place of the typed word (column names are quoted if needed).
+    pub insert: String,
+}
+
+/// Whether `c` can be part of a word completed by `sql_completions`.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// The word typed before the char index `cursor` of `text`: its char range and text.
+/// The range is empty if the cursor does not follow a word character.
+pub fn completion_prefix(text: &str, cursor: usize) -> (Range<usize>, String) {
+    let before: Vec<char> = text.chars().take(cursor).collect();
+    let start = before
+        .iter()
+        .rposition(|c| !is_word_char(*c))
+        .map_or(0, |index| index + 1);
+    (start..before.len(), before[start..].iter().collect())
+}
+
+/// Suggestions for the typed word `prefix` (case-insensitive prefix match): the table
+/// name, the `columns`, then the keywords; words equal to `prefix` are skipped.
+///
+/// Returns at most `MAX_COMPLETIONS` suggestions (none for an empty prefix).
+pub fn sql_completions<'a>(
+    prefix: &str,
+    table_name: &str,
+    columns: impl IntoIterator<Item = &'a str>,
+) -> Vec<Completion> {
+    if prefix.is_empty() {
+        return Vec::new();
+    }
+    let lower = prefix.to_lowercase();
+    let matches = |word: &str| {
+        let word = word.to_lowercase();
+        word.starts_with(&lower) && word != lower
+    };
+
+    let table = std::iter::once((CompletionKind::Table, table_name));
+    let columns = columns
+        .into_iter()
+        .map(|name| (CompletionKind::Column, name));
+    let keywords = SQL_COMPLETION_WORDS
+        .iter()
+        .map(|word| (CompletionKind::Keyword, *word));
+
+    table
+        .chain(columns)
+        .chain(keywords)
+        .filter(|(_, word)| matches(word))
+        .map(|(kind, word)| {
+            let simple = word.chars().all(is_word_char);
+            Completion {
+                kind,
+                label: word.to_string(),
+                insert: match kind {
+                    CompletionKind::Column if !simple => sql_identifier(word),
+                    _ => word.to_string(),
+                },
+            }
+        })
+        .take(MAX_COMPLETIONS)
+        .collect()
+}
+
+/// Renders the SQL editor bound to `query` (`rows` lines high), with the completion
+/// popup for the word at the cursor (see `sql_completions`), using `table_name` and the
+/// columns of `schema`.
+///
+/// Tab inserts the first suggestion; a click inserts any of them. Escape closes the popup
+/// until the next edit.
+pub fn render_sql_editor(
+    ui: &mut Ui,
+    query: &mut String,
+    rows: usize,
+    table_name: &str,
+    schema: &Schema,
+) -> egui::Response {
+    let editor_id = ui.id().with("sql_editor");
+    let dismissed_id = editor_id.with("completion_dismissed");
+
+    // Suggestions of the last frame: Tab must be consumed before the editor sees it.
+    let previous: Option<(Range<usize>, Vec<Completion>)> =
+        ui.data(|data| data.get_temp(editor_id.with("completions")));
+    let accept_first = previous.as_ref().is_some_and(|(_, list)| !list.is_empty())
+        && ui.memory(|memory| memory.has_focus(editor_id))
+        && ui.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Tab));
+
+    let mut output = TextEdit::multiline(query)
+        .id(editor_id)
+        .desired_width(f32::INFINITY)
+        .desired_rows(rows)
+        .font(egui::TextStyle::Monospace)
+        .show(ui);
+
+    if output.response.changed() {
+        ui.data_mut(|data| data.remove::<bool>(dismissed_id));
+    }
+    if output.response.has_focus() && ui.input(|input| input.key_pressed(Key::Escape)) {
+        ui.data_mut(|data| data.insert_temp(dismissed_id, true));
+    }
+    let dismissed = ui.data(|data| data.get_temp::<bool>(dismissed_id).unwrap_or(false));
+
+    let cursor = output.cursor_range.map(|range| range.primary.index);
+    let completions = match cursor {
+        Some(cursor) if output.response.has_focus() && !dismissed => {
+            let (range, prefix) = completion_prefix(query, cursor);
+            let columns = schema.iter_names().map(|name| name.as_str());
+            (range, sql_completions(&prefix, table_name, columns))
+        }
+        _ => (0..0, Vec::new()),
+    };
+
+    // The completion to insert: Tab (first one) or a click in the popup.
+    let mut chosen = accept_first
+        .then(|| previous.and_then(|(range, list)| Some((range, list.first()?.clone()))))
+        .flatten();
+
+    if !completions.1.is_empty() && chosen.is_none() {
+        Area::new(editor_id.with("completion_popup"))
+            .order(Order::Foreground)
+            .fixed_pos(output.response.rect.left_bottom())
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    for completion in &completions.1 {
+                        let badge = match completion.kind {
+                            CompletionKind::Table => "T",
+                            CompletionKind::Column => "C",
+                            CompletionKind::Keyword => "K",
+                        };
+                        let text =
+                            RichText::new(format!("{badge}  {}", completion.label)).monospace();
+                        if ui.selectable_label(false, text).clicked() {
+                            chosen = Some((completions.0.clone(), completion.clone()));
+                        }
+                    }
+                    ui.label(
+                        RichText::new("Tab: insert the first · Esc: close")
+                            .small()
+                            .color(Color32::GRAY),
+                    );
+                });
+            });
+    }
+
+    if let Some((range, completion)) = chosen {
+        let start = query
+            .char_indices()
+            .nth(range.start)
+            .map_or(query.len(), |(index, _)| index);
+        let end = query
+            .char_indices()
+            .nth(range.end)
+            .map_or(query.len(), |(index, _)| index);
+        query.replace_range(start..end, &completion.insert);
+
+        // Move the cursor after the inserted text and keep editing.
+        let cursor = CCursor::new(range.start + completion.insert.chars().count());
+        output
+            .state
+            .cursor
+            .set_char_range(Some(CCursorRange::one(cursor)));
+        output.state.store(ui.ctx(), editor_id);
+        ui.memory_mut(|memory| memory.request_focus(editor_id));
+        ui.data_mut(|data| {
+            data.remove::<(Range<usize>, Vec<Completion>)>(editor_id.with("completions"))
+        });
+        output.response.mark_changed();
+    } else {
+        ui.data_mut(|data| data.insert_temp(editor_id.with("completions"), completions));
+    }
+
+    output.response
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_sql_completion`
+#[cfg(test)]
+mod tests_sql_completion {
+    use super::*;
+
+    #[test]
+    fn prefix_at_cursor() {
+        let text = "SELECT na FROM AllData";
+        assert_eq!(completion_prefix(text, 9), (7..9, "na".to_string()));
+        assert_eq!(completion_prefix(text, 7), (7..7, String::new()));
+        assert_eq!(completion_prefix("sél", 3), (0..3, "sél".to_string()));
+    }
+
+    #[test]
+    fn completions_of_prefix() {
+        let columns = ["name", "Net Value", "id"];
+
+        let labels = |prefix: &str| -> Vec<String> {
+            sql_completions(prefix, "AllData", columns)
+                .into_iter()
+                .map(|completion| completion.insert)
+                .collect()
+        };
+
+        assert_eq!(
+            labels("n"),
+            [
+                "name",
+                "`Net Value`",
+                "NOT",
+                "NULLS FIRST",
+                "NULLS LAST",
+                "NULLIF"
+            ]
+        );
+        assert_eq!(labels("al"), ["AllData"]);
+        assert_eq!(labels("sel"), ["SELECT"]);
+        // Exact matches are not suggested.
+        assert!(labels("select").is_empty());
+        assert!(labels("").is_empty());
+    }
+}
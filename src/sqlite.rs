@@ -0,0 +1,205 @@
+//! SQLite database reading (`.sqlite`, `.sqlite3`, `.db`) via `rusqlite`.
+//!
+//! One table (or view) is read at a time, like the sheets of a workbook: the Query panel
+//! lists the tables of the database and the SQL query runs on the loaded table.
+//!
+//! SQLite columns are dynamically typed, so each column type is inferred from its values:
+//! Int64 (only integers), Float64 (integers and reals), Binary (only blobs) or String.
+//!
+//! Needs the `sqlite` cargo feature; without it, opening a database reports the feature
+//! to build with.
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use polars::prelude::*;
+#[cfg(feature = "sqlite")]
+use rusqlite::{Connection, OpenFlags, types::Value};
+use std::path::Path;
+
+/// A table read from a SQLite database.
+#[derive(Debug, Clone)]
+pub struct SqliteTable {
+    /// The table contents.
+    pub df: DataFrame,
+    /// All table and view names of the database, sorted.
+    pub table_names: Vec<String>,
+    /// The name of the table that was read.
+    pub table_name: String,
+}
+
+/// Reads the table `table_name` of the SQLite database at `path` (opened read-only).
+///
+/// If `table_name` is `None` (or not found in the database), the first table is read.
+/// This is a blocking operation; call it from `spawn_blocking`.
+#[cfg(feature = "sqlite")]
+pub fn read_sqlite_table(path: &Path, table_name: Option<&str>) -> PolarsViewResult<SqliteTable> {
+    let connection = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let table_names = list_tables(&connection)?;
+
+    let table_name = match table_name {
+        Some(name) if table_names.iter().any(|table| table == name) => name.to_string(),
+        _ => table_names.first().cloned().ok_or_else(|| {
+            PolarsViewError::FileType(format!("Database without tables: `{}`", path.display()))
+        })?,
+    };
+
+    let df = read_table(&connection, &table_name)?;
+
+    tracing::debug!(
+        "Table `{}` read from {}. Shape: {:?}",
+        table_name,
+        path.display(),
+        df.shape()
+    );
+
+    Ok(SqliteTable {
+        df,
+        table_names,
+        table_name,
+    })
+}
+
+/// Built without the `sqlite` feature: databases can't be read.
+#[cfg(not(feature = "sqlite"))]
+pub fn read_sqlite_table(_path: &Path, _table_name: Option<&str>) -> PolarsViewResult<SqliteTable> {
+    Err(PolarsViewError::FeatureDisabled("sqlite"))
+}
+
+/// Names of the tables and views of the database (internal `sqlite_` tables excluded), sorted.
+#[cfg(feature = "sqlite")]
+fn list_tables(connection: &Connection) -> PolarsViewResult<Vec<String>> {
+    let mut statement = connection.prepare(
+        "SELECT name FROM sqlite_master \
+         WHERE type IN ('table', 'view') AND name NOT LIKE 'sqlite_%' \
+         ORDER BY name",
+    )?;
+    let names = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(names)
+}
+
+/// Reads all rows of `table_name` into a `DataFrame` (see `values_to_column`).
+#[cfg(feature = "sqlite")]
+fn read_table(connection: &Connection, table_name: &str) -> PolarsViewResult<DataFrame> {
+    let quoted = format!("\"{}\"", table_name.replace('"', "\"\""));
+    let mut statement = connection.prepare(&format!("SELECT * FROM {quoted}"))?;
+
+    let names: Vec<String> = statement
+        .column_names()
+        .into_iter()
+        .map(str::to_string)
+        .collect();
+    let mut values: Vec<Vec<Value>> = vec![Vec::new(); names.len()];
+
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        for (index, column) in values.iter_mut().enumerate() {
+            column.push(row.get(index)?);
+        }
+    }
+
+    let height = values.first().map_or(0, Vec::len);
+    let columns = names
+        .iter()
+        .zip(values)
+        .map(|(name, values)| values_to_column(name, values))
+        .collect();
+
+    Ok(DataFrame::new(height, columns)?)
+}
+
+/// Builds a column from SQLite values, choosing its type from the non-null values:
+/// only integers: Int64; integers and reals: Float64; only blobs: Binary; otherwise String.
+#[cfg(feature = "sqlite")]
+fn values_to_column(name: &str, values: Vec<Value>) -> Column {
+    let non_null = || values.iter().filter(|value| !matches!(value, Value::Null));
+
+    if non_null().all(|value| matches!(value, Value::Integer(_))) {
+        let ints: Vec<Option<i64>> = values
+            .iter()
+            .map(|value| match value {
+                Value::Integer(int) => Some(*int),
+                _ => None,
+            })
+            .collect();
+        return Column::new(name.into(), ints);
+    }
+
+    if non_null().all(|value| matches!(value, Value::Integer(_) | Value::Real(_))) {
+        let floats: Vec<Option<f64>> = values
+            .iter()
+            .map(|value| match value {
+                Value::Integer(int) => Some(*int as f64),
+                Value::Real(real) => Some(*real),
+                _ => None,
+            })
+            .collect();
+        return Column::new(name.into(), floats);
+    }
+
+    if non_null().all(|value| matches!(value, Value::Blob(_))) {
+        let blobs: Vec<Option<&[u8]>> = values
+            .iter()
+            .map(|value| match value {
+                Value::Blob(blob) => Some(blob.as_slice()),
+                _ => None,
+            })
+            .collect();
+        return Column::new(name.into(), blobs);
+    }
+
+    let strings: Vec<Option<String>> = values
+        .into_iter()
+        .map(|value| match value {
+            Value::Null => None,
+            Value::Integer(int) => Some(int.to_string()),
+            Value::Real(real) => Some(real.to_string()),
+            Value::Text(text) => Some(text),
+            Value::Blob(blob) => Some(String::from_utf8_lossy(&blob).into_owned()),
+        })
+        .collect();
+    Column::new(name.into(), strings)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test --features sqlite -- --show-output tests_sqlite`
+#[cfg(all(test, feature = "sqlite"))]
+mod tests_sqlite {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn reads_tables_with_inferred_types() -> PolarsViewResult<()> {
+        let dir = tempdir()?;
+        let path = dir.path().join("shop.sqlite");
+
+        let connection = Connection::open(&path)?;
+        connection.execute_batch(
+            "CREATE TABLE products (id INTEGER, name TEXT, price REAL, stock);
+             INSERT INTO products VALUES (1, 'pen', 1.5, 10), (2, 'ink', 3, NULL), (3, NULL, NULL, 'n/a');
+             CREATE TABLE customers (id INTEGER);",
+        )?;
+        drop(connection);
+
+        let table = read_sqlite_table(&path, Some("products"))?;
+        assert_eq!(table.table_names, ["customers", "products"]);
+        assert_eq!(table.table_name, "products");
+        assert_eq!(table.df.shape(), (3, 4));
+        assert_eq!(table.df.column("id")?.dtype(), &DataType::Int64);
+        assert_eq!(table.df.column("name")?.dtype(), &DataType::String);
+        assert_eq!(table.df.column("price")?.dtype(), &DataType::Float64);
+        assert_eq!(table.df.column("stock")?.dtype(), &DataType::String); // Mixed values.
+        assert_eq!(table.df.column("price")?.f64()?.get(1), Some(3.0));
+
+        // Unknown table: the first one.
+        let table = read_sqlite_table(&path, Some("missing"))?;
+        assert_eq!(table.table_name, "customers");
+        assert_eq!(table.df.height(), 0);
+        Ok(())
+    }
+}
@@ -1,5 +1,7 @@
 use crate::DEFAULT_INDEX_COLUMN_NAME;
-use polars::prelude::{DataType, Schema};
+use polars::prelude::{AnyValue, DataType, Schema};
+use regex::Regex;
+use std::collections::HashSet;
 
 // Constants for SQL Generation
 
@@ -48,27 +50,79 @@ fn is_filtered_col(name: &str) -> bool {
         .any(|substring| trimmed_name.contains(substring))
 }
 
+/// Column preferences applied by `sql_commands` when picking columns for examples, taken
+/// from the display settings and the load options (see `DataFilter::example_columns`).
+///
+/// - `frozen`: the frozen columns (`DataFormat::frozen_columns`), used first (in their
+///   order), even if their names look auto-generated.
+/// - `hidden`: the hidden columns (`DataFormat::hidden_columns`), never used.
+/// - `dropped`: the drop regex (`DataFilter::drop_regex`, when dropping is on): the
+///   matching columns are never used.
+#[derive(Debug, Clone, Default)]
+pub struct ExampleColumns<'a> {
+    pub frozen: &'a [String],
+    pub hidden: Option<&'a HashSet<String>>,
+    pub dropped: Option<Regex>,
+}
+
+impl ExampleColumns<'_> {
+    /// Whether the column `name` is hidden or dropped.
+    fn is_excluded(&self, name: &str) -> bool {
+        self.hidden.is_some_and(|hidden| hidden.contains(name))
+            || self
+                .dropped
+                .as_ref()
+                .is_some_and(|regex| regex.is_match(name))
+    }
+
+    /// Returns the schema columns in example preference order: frozen columns first
+    /// (in their order), then the remaining columns in schema order.
+    /// Hidden and dropped columns, and names in `COLS_FILTER_OUT` (unless frozen), are skipped.
+    fn ordered<'s>(&self, schema: &'s Schema) -> Vec<(&'s str, &'s DataType)> {
+        let is_frozen = |name: &str| self.frozen.iter().any(|frozen| frozen == name);
+
+        let frozen = self
+            .frozen
+            .iter()
+            .filter_map(|name| schema.get_full(name.as_str()))
+            .map(|(_index, name, dtype)| (name.as_str(), dtype));
+
+        let others = schema
+            .iter()
+            .map(|(name, dtype)| (name.as_str(), dtype))
+            .filter(|(name, _dtype)| !is_frozen(name) && !is_filtered_col(name));
+
+        frozen
+            .chain(others)
+            .filter(|(name, _dtype)| !self.is_excluded(name))
+            .collect()
+    }
+}
+
 /// Finds the name of the Nth (0-based) column in the schema that matches a specified data type.
-/// Columns in `COLS_FILTER_OUT` are ignored.
+/// Columns are taken in `ExampleColumns` preference order (frozen first, hidden skipped).
 ///
 /// # Arguments
 /// * `schema`: The DataFrame schema.
+/// * `columns`: Frozen/hidden/dropped column preferences.
 /// * `n`: The 0-based index (0 for first, 1 for second, etc.).
 /// * `target_dtype`: A closure `Fn(&DataType) -> bool` that defines the type check logic.
 ///
 /// # Returns
 /// * `Some(&'a str)`: The name of the Nth matching column, borrowing from the schema.
-/// * `None`: If fewer than N+1 columns satisfy the target_dtype and are not filtered out.
-fn find_nth_col_name(
-    schema: &Schema,
+/// * `None`: If fewer than N+1 usable columns satisfy the target_dtype.
+fn find_nth_col_name<'s>(
+    schema: &'s Schema,
+    columns: &ExampleColumns,
     n: usize,
     target_dtype: impl Fn(&DataType) -> bool,
-) -> Option<&str> {
-    schema
-        .iter()
-        .filter(|(name, dtype)| !is_filtered_col(name.as_str()) && target_dtype(dtype))
+) -> Option<&'s str> {
+    columns
+        .ordered(schema)
+        .into_iter()
+        .filter(|(_name, dtype)| target_dtype(dtype))
         .nth(n) // nth(0) returns the first value, nth(1) the second, and so on.
-        .map(|(name, _dtype)| name.as_str())
+        .map(|(name, _dtype)| name)
 }
 
 // --- Functions to Generate Specific SQL Examples ---
@@ -558,7 +612,8 @@ FROM AllData;
 
 /// Generates a list of example SQL commands based on the provided DataFrame schema.
 /// Uses helper functions to find suitable columns and generate diverse examples.
-pub fn sql_commands(schema: &Schema) -> Vec<String> {
+/// Frozen columns in `columns` are preferred; hidden and dropped columns are never used.
+pub fn sql_commands(schema: &Schema, columns: &ExampleColumns) -> Vec<String> {
     // Start with the default query
     let mut commands: Vec<String> = vec![DEFAULT_QUERY.to_string()];
 
@@ -568,17 +623,17 @@ pub fn sql_commands(schema: &Schema) -> Vec<String> {
     // Find first few usable columns of different types for examples
     // The index used for finding these columns corresponds to their potential
     // use case in the examples.
-    let opt_str_col = find_nth_col_name(schema, 0, |dtype| dtype.is_string());
-    let opt_int_col = find_nth_col_name(schema, 0, |dtype| dtype.is_integer());
-    let opt_float_col = find_nth_col_name(schema, 0, |dtype| dtype.is_float());
-    let opt_date_col = find_nth_col_name(schema, 0, |dtype| dtype.is_date());
+    let opt_str_col = find_nth_col_name(schema, columns, 0, |dtype| dtype.is_string());
+    let opt_int_col = find_nth_col_name(schema, columns, 0, |dtype| dtype.is_integer());
+    let opt_float_col = find_nth_col_name(schema, columns, 0, |dtype| dtype.is_float());
+    let opt_date_col = find_nth_col_name(schema, columns, 0, |dtype| dtype.is_date());
 
     // Find the Nth available column of *any* type, filtering out specified names.
     // These are used for examples that don't require a specific type,
     // or need distinct columns for comparison/exclusion/etc.
-    let opt_any_col = find_nth_col_name(schema, 0, is_any); // First usable column
-    let opt_any_col_1 = find_nth_col_name(schema, 1, is_any); // Second usable column
-    let opt_any_col_2 = find_nth_col_name(schema, 2, is_any); // Third usable column
+    let opt_any_col = find_nth_col_name(schema, columns, 0, is_any); // First usable column
+    let opt_any_col_1 = find_nth_col_name(schema, columns, 1, is_any); // Second usable column
+    let opt_any_col_2 = find_nth_col_name(schema, columns, 2, is_any); // Third usable column
 
     // Helper options combining types
     let opt_int_or_float_col = opt_int_col.or(opt_float_col);
@@ -626,3 +681,826 @@ pub fn sql_commands(schema: &Schema) -> Vec<String> {
 
     commands
 }
+
+// --- Fuzzy Search over History and Examples ---
+
+/// Origin of a SQL snippet returned by `search_sql_snippets`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnippetSource {
+    /// A previously applied query (`DataFilter::query_history`).
+    History,
+    /// A query generated by `sql_commands`.
+    Example,
+}
+
+/// A SQL snippet matched by `search_sql_snippets`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SqlSnippet {
+    /// Where the snippet comes from.
+    pub source: SnippetSource,
+    /// The full SQL text, copied into the editor when selected.
+    pub text: String,
+    /// Match score. Higher is better.
+    pub score: usize,
+}
+
+impl SqlSnippet {
+    /// Returns a single-line preview: the first non-empty line, whitespace collapsed.
+    pub fn preview(&self) -> String {
+        self.text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Scores `needle` as a case-insensitive subsequence of `haystack`.
+///
+/// Returns `None` if not every character of `needle` appears (in order) in `haystack`.
+/// Consecutive matches and exact substring matches score higher.
+fn fuzzy_score(needle: &str, haystack: &str) -> Option<usize> {
+    let needle = needle.to_lowercase();
+    let haystack = haystack.to_lowercase();
+
+    let mut hay_chars = haystack.chars();
+    let mut score = 0;
+    let mut consecutive = 0;
+
+    for n in needle.chars().filter(|c| !c.is_whitespace()) {
+        let mut matched = false;
+        let mut skipped = false;
+        for h in hay_chars.by_ref() {
+            if h == n {
+                matched = true;
+                break;
+            }
+            skipped = true;
+        }
+        if !matched {
+            return None;
+        }
+        consecutive = if skipped { 1 } else { consecutive + 1 };
+        score += consecutive;
+    }
+
+    // Strong bonus for an exact (contiguous) substring match.
+    if haystack.contains(needle.trim()) {
+        score += 10 * needle.len();
+    }
+
+    Some(score)
+}
+
+/// Extracts column names quoted with backticks or double quotes in a SQL text.
+fn referenced_columns(sql: &str) -> Vec<&str> {
+    ['`', '"']
+        .iter()
+        .flat_map(|&quote| sql.split(quote).skip(1).step_by(2))
+        .filter(|name| !name.trim().is_empty())
+        .collect()
+}
+
+/// Fuzzy searches the query history and the generated examples.
+///
+/// A snippet matches if its text, or one of its referenced (quoted) column names,
+/// fuzzily contains `search`. Column matches get a bonus so that searching for a
+/// column name surfaces the queries that use it.
+///
+/// ### Returns
+/// Matches ordered by score (best first). History entries win ties.
+/// An empty (or whitespace-only) `search` returns no matches.
+pub fn search_sql_snippets(
+    search: &str,
+    history: &[String],
+    examples: &[String],
+) -> Vec<SqlSnippet> {
+    if search.trim().is_empty() {
+        return Vec::new();
+    }
+
+    let history = history.iter().map(|text| (SnippetSource::History, text));
+    let examples = examples.iter().map(|text| (SnippetSource::Example, text));
+
+    let mut snippets: Vec<SqlSnippet> = history
+        .chain(examples)
+        .filter_map(|(source, text)| {
+            let text_score = fuzzy_score(search, text);
+            let column_score = referenced_columns(text)
+                .into_iter()
+                .filter_map(|col| fuzzy_score(search, col))
+                .max()
+                .map(|score| score * 2);
+
+            text_score.max(column_score).map(|score| SqlSnippet {
+                source,
+                text: text.clone(),
+                score,
+            })
+        })
+        .collect();
+
+    // Stable sort: keeps history entries (chained first) ahead on equal scores.
+    snippets.sort_by(|a, b| b.score.cmp(&a.score));
+    snippets
+}
+
+// --- SQL Formatting (Pretty-Print) ---
+
+/// Indentation used by `format_sql` for clause items.
+const SQL_INDENT: &str = "    ";
+
+/// Keywords upper-cased by `format_sql`. Function names (e.g., `count`, `lower`) are kept as typed.
+const SQL_KEYWORDS: &[&str] = &[
+    "ALL", "AND", "AS", "ASC", "BETWEEN", "BY", "CASE", "CAST", "CROSS", "DESC", "DISTINCT",
+    "ELSE", "END", "EXCEPT", "FALSE", "FROM", "FULL", "GROUP", "HAVING", "ILIKE", "IN", "INNER",
+    "IS", "JOIN", "LEFT", "LIKE", "LIMIT", "NOT", "NULL", "OFFSET", "ON", "OR", "ORDER", "OUTER",
+    "RENAME", "REPLACE", "RIGHT", "SELECT", "THEN", "TRUE", "UNION", "USING", "WHEN", "WHERE",
+    "WITH",
+];
+
+/// Keywords that are also function names: followed by `(`, they are function calls, kept as
+/// typed (e.g., `LEFT(name, 3)`, `replace(name, 'a', 'b')`), except `REPLACE (...)` after
+/// `*` or `EXCEPT (...)`, the wildcard option (`SELECT * REPLACE (expr AS col)`).
+const SQL_FUNCTION_KEYWORDS: &[&str] = &["CAST", "LEFT", "REPLACE", "RIGHT"];
+
+/// Clauses that start on a new line, with their items indented on the following lines.
+const SQL_LIST_CLAUSES: &[&str] = &["SELECT", "WHERE", "GROUP BY", "ORDER BY", "HAVING"];
+
+/// Clauses that start on a new line, followed by their arguments on the same line.
+const SQL_INLINE_CLAUSES: &[&str] = &[
+    "FROM", "LIMIT", "OFFSET", "UNION", "EXCEPT", "REPLACE", "RENAME", "WITH", "JOIN", "ON",
+    "USING",
+];
+
+/// A lexical token of a SQL query, as seen by `format_sql`.
+#[derive(Debug, Clone, PartialEq)]
+enum SqlToken {
+    /// Identifier, keyword or number (e.g., `SELECT`, `AllData`, `1.5`).
+    Word(String),
+    /// String literal or quoted identifier, kept verbatim (e.g., `'abc'`, `` `Col Name` ``).
+    Quoted(String),
+    /// Line (`-- ...`) or block (`/* ... */`) comment, kept verbatim.
+    Comment(String),
+    /// Punctuation or operator (e.g., `(`, `,`, `>=`).
+    Symbol(String),
+}
+
+/// Splits a SQL query into `SqlToken`s, respecting quotes and comments.
+fn tokenize_sql(sql: &str) -> Vec<SqlToken> {
+    let mut tokens = Vec::new();
+    let mut chars = sql.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            _ if c.is_whitespace() => {
+                chars.next();
+            }
+            '\'' | '"' | '`' => {
+                let mut text = String::from(c);
+                chars.next();
+                while let Some(ch) = chars.next() {
+                    text.push(ch);
+                    if ch == c {
+                        // Doubled quotes ('' or "") escape the quote character.
+                        if chars.peek() == Some(&c) {
+                            text.extend(chars.next());
+                            continue;
+                        }
+                        break;
+                    }
+                }
+                tokens.push(SqlToken::Quoted(text));
+            }
+            '-' if sql_peek_second(&chars) == Some('-') => {
+                let text: String = chars.by_ref().take_while(|&ch| ch != '\n').collect();
+                tokens.push(SqlToken::Comment(text.trim_end().to_string()));
+            }
+            '/' if sql_peek_second(&chars) == Some('*') => {
+                let mut text = String::new();
+                while let Some(ch) = chars.next() {
+                    text.push(ch);
+                    if text.len() > 2 && text.ends_with("*/") {
+                        break;
+                    }
+                }
+                tokens.push(SqlToken::Comment(text));
+            }
+            _ if c.is_alphanumeric() || c == '_' || c == '.' || c == '$' => {
+                let mut text = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if !(ch.is_alphanumeric() || ch == '_' || ch == '.' || ch == '$') {
+                        break;
+                    }
+                    text.push(ch);
+                    chars.next();
+
+                    // The sign of an exponent belongs to the number: `1.5e-3`.
+                    if matches!(ch, 'e' | 'E')
+                        && is_sql_number(&text[..text.len() - 1])
+                        && matches!(chars.peek(), Some('+' | '-'))
+                        && sql_peek_second(&chars).is_some_and(|next| next.is_ascii_digit())
+                    {
+                        text.extend(chars.next());
+                    }
+                }
+                tokens.push(SqlToken::Word(text));
+            }
+            ':' if sql_peek_second(&chars) == Some(':') => {
+                chars.nth(1);
+                tokens.push(SqlToken::Symbol("::".to_string()));
+            }
+            '<' | '>' | '=' | '!' | '|' => {
+                let mut text = String::new();
+                while let Some(&ch) = chars.peek() {
+                    if !matches!(ch, '<' | '>' | '=' | '!' | '|') {
+                        break;
+                    }
+                    text.push(ch);
+                    chars.next();
+                }
+                tokens.push(SqlToken::Symbol(text));
+            }
+            _ => {
+                chars.next();
+                tokens.push(SqlToken::Symbol(c.to_string()));
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Whether `text` is a number without exponent (e.g., `1`, `1.5`, `.5`).
+fn is_sql_number(text: &str) -> bool {
+    text.chars().any(|c| c.is_ascii_digit()) && text.chars().all(|c| c.is_ascii_digit() || c == '.')
+}
+
+/// Returns the character after the next one, without consuming anything.
+fn sql_peek_second(chars: &std::iter::Peekable<std::str::Chars<'_>>) -> Option<char> {
+    chars.clone().nth(1)
+}
+
+/// Returns the upper-cased text if `token` is a known keyword.
+fn sql_keyword(token: Option<&SqlToken>) -> Option<String> {
+    match token {
+        Some(SqlToken::Word(word)) => {
+            let upper = word.to_uppercase();
+            SQL_KEYWORDS.contains(&upper.as_str()).then_some(upper)
+        }
+        _ => None,
+    }
+}
+
+/// Whether the word `tokens[index]` is a call of a function named like a keyword (see
+/// `SQL_FUNCTION_KEYWORDS`).
+fn is_function_keyword(tokens: &[SqlToken], index: usize, keyword: &str) -> bool {
+    let is_symbol = |offset: Option<usize>, symbols: &[&str]| {
+        matches!(
+            offset.and_then(|i| tokens.get(i)),
+            Some(SqlToken::Symbol(symbol)) if symbols.contains(&symbol.as_str())
+        )
+    };
+
+    SQL_FUNCTION_KEYWORDS.contains(&keyword)
+        && is_symbol(Some(index + 1), &["("])
+        && !(keyword == "REPLACE" && is_symbol(index.checked_sub(1), &["*", ")"]))
+}
+
+/// Reformats a SQL query with consistent indentation and upper-case keywords,
+/// so pasted one-liner queries become reviewable.
+///
+/// ### Rules
+/// - Main clauses (`SELECT`, `FROM`, `WHERE`, `GROUP BY`, ...) start on a new line.
+/// - Items of `SELECT`, `WHERE`, `GROUP BY`, `ORDER BY` and `HAVING` are indented,
+///   one per line (split at top-level commas and `AND`/`OR`).
+/// - Expressions inside parentheses and `CASE ... END` stay on a single line.
+/// - Keywords used as functions (e.g., `LEFT(name, 3)`) are kept inline, as function calls.
+/// - Comments, string literals and quoted identifiers are preserved verbatim.
+pub fn format_sql(sql: &str) -> String {
+    let tokens = tokenize_sql(sql);
+    let mut out = String::new();
+    let mut depth = 0usize; // Parenthesis depth.
+    let mut case_depth = 0usize; // Nesting of `CASE ... END` expressions.
+    let mut in_between = false; // The next AND belongs to `BETWEEN x AND y`.
+    let mut space_before_paren = true;
+    let mut index = 0;
+
+    // Starts a new line (unless already at the start of one).
+    fn new_line(out: &mut String, indent: &str) {
+        let trimmed_len = out.trim_end_matches(' ').len();
+        out.truncate(trimmed_len);
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push('\n');
+        }
+        out.push_str(indent);
+    }
+
+    // Appends `text`, separated by a single space when needed.
+    fn push(out: &mut String, text: &str, space: bool) {
+        if space && !out.is_empty() && !out.ends_with([' ', '\n', '(', ':']) {
+            out.push(' ');
+        }
+        out.push_str(text);
+    }
+
+    while index < tokens.len() {
+        let token = &tokens[index];
+        index += 1;
+
+        match token {
+            SqlToken::Comment(text) => {
+                new_line(&mut out, if depth > 0 { SQL_INDENT } else { "" });
+                out.push_str(text);
+                out.push('\n');
+                space_before_paren = true;
+            }
+            SqlToken::Quoted(text) => {
+                push(&mut out, text, true);
+                space_before_paren = false;
+            }
+            SqlToken::Symbol(symbol) => {
+                match symbol.as_str() {
+                    "(" => {
+                        depth += 1;
+                        push(&mut out, "(", space_before_paren);
+                    }
+                    ")" => {
+                        depth = depth.saturating_sub(1);
+                        push(&mut out, ")", false);
+                    }
+                    "," if depth == 0 => {
+                        push(&mut out, ",", false);
+                        new_line(&mut out, SQL_INDENT);
+                    }
+                    "," => push(&mut out, ", ", false),
+                    ";" => {
+                        push(&mut out, ";", false);
+                        out.push('\n');
+                    }
+                    "::" => push(&mut out, "::", false), // Cast: `a::int`.
+                    other => push(&mut out, other, true),
+                }
+                space_before_paren = symbol != ")";
+            }
+            SqlToken::Word(word) => {
+                let keyword = sql_keyword(Some(token))
+                    .filter(|keyword| !is_function_keyword(&tokens, index - 1, keyword));
+                let Some(mut keyword) = keyword else {
+                    push(&mut out, word, true);
+                    space_before_paren = false; // Function call: `count(*)`.
+                    continue;
+                };
+                space_before_paren = true;
+
+                match keyword.as_str() {
+                    "CASE" => case_depth += 1,
+                    "END" => case_depth = case_depth.saturating_sub(1),
+                    _ => {}
+                }
+
+                // Inside parentheses or `CASE ... END`, keywords stay on the same line.
+                if depth > 0 || case_depth > 0 {
+                    push(&mut out, &keyword, true);
+                    continue;
+                }
+
+                // Merge multi-word clauses: GROUP BY, ORDER BY, LEFT [OUTER] JOIN, SELECT DISTINCT.
+                while let Some(next) = sql_keyword(tokens.get(index)) {
+                    let last = keyword.rsplit(' ').next().unwrap_or_default();
+                    let merge = match last {
+                        "GROUP" | "ORDER" => next == "BY",
+                        "LEFT" | "RIGHT" | "FULL" | "INNER" | "CROSS" | "OUTER" => {
+                            next == "OUTER" || next == "JOIN"
+                        }
+                        "SELECT" | "UNION" => next == "DISTINCT" || next == "ALL",
+                        _ => false,
+                    };
+                    if !merge {
+                        break;
+                    }
+                    keyword = format!("{keyword} {next}");
+                    index += 1;
+                }
+
+                let clause = keyword.split(' ').next().unwrap_or_default();
+                let base = if keyword.ends_with("JOIN") {
+                    "JOIN"
+                } else {
+                    clause
+                };
+
+                if SQL_LIST_CLAUSES.contains(&keyword.as_str())
+                    || SQL_LIST_CLAUSES.contains(&clause)
+                {
+                    new_line(&mut out, "");
+                    out.push_str(&keyword);
+                    new_line(&mut out, SQL_INDENT);
+                } else if SQL_INLINE_CLAUSES.contains(&base) {
+                    new_line(&mut out, "");
+                    out.push_str(&keyword);
+                } else if (keyword == "AND" && !in_between) || keyword == "OR" {
+                    new_line(&mut out, SQL_INDENT);
+                    out.push_str(&keyword);
+                } else {
+                    push(&mut out, &keyword, true);
+                }
+
+                match keyword.as_str() {
+                    "BETWEEN" => in_between = true,
+                    "AND" => in_between = false,
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Drop trailing spaces on every line and end with a single newline.
+    let formatted: Vec<&str> = out.lines().map(str::trim_end).collect();
+    format!("{}\n", formatted.join("\n").trim())
+}
+
+// --- Quick Filters (cell context menu) ---
+
+/// Comparison of a quick filter built from a cell value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickFilterOp {
+    /// `column = value` (`IS NULL` for nulls).
+    Equal,
+    /// `column != value`, keeping nulls (`IS NOT NULL` for nulls).
+    NotEqual,
+}
+
+/// Quotes a column name as a SQL identifier: `` `name` `` (backticks doubled).
+pub fn sql_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Writes a cell value as a SQL literal. Returns `None` for values without a
+/// literal form (nested, binary, NaN and infinite floats, ...).
+pub fn sql_literal(value: &AnyValue) -> Option<String> {
+    let quoted = |text: &str| format!("'{}'", text.replace('\'', "''"));
+
+    let literal = match value {
+        AnyValue::Null => "NULL".to_string(),
+        AnyValue::Boolean(value) => value.to_string(),
+        AnyValue::Int8(value) => value.to_string(),
+        AnyValue::Int16(value) => value.to_string(),
+        AnyValue::Int32(value) => value.to_string(),
+        AnyValue::Int64(value) => value.to_string(),
+        AnyValue::UInt8(value) => value.to_string(),
+        AnyValue::UInt16(value) => value.to_string(),
+        AnyValue::UInt32(value) => value.to_string(),
+        AnyValue::UInt64(value) => value.to_string(),
+        AnyValue::Float32(value) if value.is_finite() => value.to_string(),
+        AnyValue::Float64(value) if value.is_finite() => value.to_string(),
+        AnyValue::Float32(_) | AnyValue::Float64(_) => return None,
+        AnyValue::Date(_) => format!("CAST({} AS DATE)", quoted(&value.to_string())),
+        AnyValue::Datetime(..) | AnyValue::DatetimeOwned(..) => {
+            format!("CAST({} AS TIMESTAMP)", quoted(&value.to_string()))
+        }
+        other => quoted(other.get_str()?),
+    };
+
+    Some(literal)
+}
+
+/// Builds the condition `column <op> value` of a quick filter.
+/// Returns `None` if the value has no SQL literal (see `sql_literal`).
+pub fn quick_filter_condition(column: &str, value: &AnyValue, op: QuickFilterOp) -> Option<String> {
+    let column = sql_identifier(column);
+
+    let condition = match (value, op) {
+        (AnyValue::Null, QuickFilterOp::Equal) => format!("{column} IS NULL"),
+        (AnyValue::Null, QuickFilterOp::NotEqual) => format!("{column} IS NOT NULL"),
+        (value, QuickFilterOp::Equal) => format!("{column} = {}", sql_literal(value)?),
+        (value, QuickFilterOp::NotEqual) => {
+            format!("({column} != {} OR {column} IS NULL)", sql_literal(value)?)
+        }
+    };
+
+    Some(condition)
+}
+
+/// Composes a quick filter `condition` into `query`:
+/// - `add = true`: the result of `query` is filtered, `SELECT * FROM (<query>) WHERE
+///   <condition>`, whatever its clauses (aggregations, joins, `LIMIT`...). Comment lines
+///   are dropped. A query of the whole table (`SELECT * FROM <table_name>`) simply
+///   takes the `WHERE`.
+/// - Otherwise, a new query `SELECT * FROM <table_name> WHERE <condition>` replaces it.
+pub fn compose_quick_filter(query: &str, table_name: &str, condition: &str, add: bool) -> String {
+    let code: Vec<&str> = query
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !line.trim_start().starts_with("--"))
+        .collect();
+    let code = code.join("\n");
+    let code = code.trim().trim_end_matches(';').trim_end();
+
+    let words = code.split_whitespace().collect::<Vec<_>>().join(" ");
+    let whole_table =
+        words.is_empty() || words.eq_ignore_ascii_case(&format!("SELECT * FROM {table_name}"));
+
+    if !add || whole_table {
+        return format!("SELECT *\nFROM {table_name}\nWHERE\n{SQL_INDENT}{condition};\n");
+    }
+
+    let subquery: Vec<String> = code
+        .lines()
+        .map(|line| format!("{SQL_INDENT}{line}"))
+        .collect();
+    format!(
+        "SELECT *\nFROM (\n{}\n)\nWHERE\n{SQL_INDENT}{condition};\n",
+        subquery.join("\n")
+    )
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_sql_search`
+#[cfg(test)]
+mod tests_sql_search {
+    use super::*;
+
+    #[test]
+    fn test_fuzzy_score() {
+        assert!(fuzzy_score("slct", "SELECT *").is_some());
+        assert!(fuzzy_score("xyz", "SELECT *").is_none());
+        // Contiguous matches beat scattered ones.
+        assert!(fuzzy_score("sel", "SELECT") > fuzzy_score("sel", "S_E_L"));
+    }
+
+    #[test]
+    fn test_referenced_columns() {
+        let sql = "SELECT `Valor Total`, \"Ano\" FROM AllData";
+        assert_eq!(referenced_columns(sql), vec!["Valor Total", "Ano"]);
+    }
+
+    #[test]
+    fn test_search_history_and_examples() {
+        let history = vec!["SELECT `Valor Total` FROM AllData".to_string()];
+        let examples = vec![
+            DEFAULT_QUERY.to_string(),
+            "SELECT * FROM AllData LIMIT 50;".to_string(),
+        ];
+
+        let matches = search_sql_snippets("valor", &history, &examples);
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].source, SnippetSource::History);
+
+        let matches = search_sql_snippets("limit", &history, &examples);
+        assert_eq!(matches[0].source, SnippetSource::Example);
+        assert_eq!(matches[0].preview(), "SELECT * FROM AllData LIMIT 50;");
+
+        assert!(search_sql_snippets("  ", &history, &examples).is_empty());
+    }
+}
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_quick_filter`
+#[cfg(test)]
+mod tests_quick_filter {
+    use super::*;
+
+    #[test]
+    fn test_conditions() {
+        assert_eq!(
+            quick_filter_condition("a", &AnyValue::Int64(7), QuickFilterOp::Equal).as_deref(),
+            Some("`a` = 7")
+        );
+        assert_eq!(
+            quick_filter_condition(
+                "my col",
+                &AnyValue::String("O'Neil"),
+                QuickFilterOp::NotEqual
+            )
+            .as_deref(),
+            Some("(`my col` != 'O''Neil' OR `my col` IS NULL)")
+        );
+        assert_eq!(
+            quick_filter_condition("a", &AnyValue::Null, QuickFilterOp::Equal).as_deref(),
+            Some("`a` IS NULL")
+        );
+        // NaN and infinite floats have no SQL literal.
+        assert_eq!(sql_literal(&AnyValue::Float64(1.5)).as_deref(), Some("1.5"));
+        assert_eq!(sql_literal(&AnyValue::Float64(f64::NAN)), None);
+        assert_eq!(sql_literal(&AnyValue::Float32(f32::INFINITY)), None);
+    }
+
+    #[test]
+    fn test_compose() {
+        let replaced = compose_quick_filter(DEFAULT_QUERY, "AllData", "`a` = 7", false);
+        assert_eq!(replaced, "SELECT *\nFROM AllData\nWHERE\n    `a` = 7;\n");
+
+        // The default query (with its comment) reads the whole table: it takes the `WHERE`.
+        let added = compose_quick_filter(DEFAULT_QUERY, "AllData", "`a` = 7", true);
+        assert_eq!(added, "SELECT *\nFROM AllData\nWHERE\n    `a` = 7;\n");
+
+        // Any other query is filtered as a subquery.
+        let added = compose_quick_filter(&added, "AllData", "`b` = 'x'", true);
+        assert_eq!(
+            added,
+            "SELECT *\nFROM (\n    SELECT *\n    FROM AllData\n    WHERE\n        `a` = 7\n)\nWHERE\n    `b` = 'x';\n"
+        );
+
+        let grouped = "SELECT a, count(*) AS n FROM AllData GROUP BY a ORDER BY n DESC;";
+        let added = compose_quick_filter(grouped, "AllData", "`n` > 1", true);
+        assert_eq!(
+            added,
+            "SELECT *\nFROM (\n    SELECT a, count(*) AS n FROM AllData GROUP BY a ORDER BY n DESC\n)\nWHERE\n    `n` > 1;\n"
+        );
+    }
+}
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_format_sql`
+#[cfg(test)]
+mod tests_format_sql {
+    use super::*;
+
+    #[test]
+    fn test_format_one_liner() {
+        let sql = "select a, count(*) as n from AllData where a > 1 and b between 1 and 2 group by a order by n desc;";
+        let expected = "\
+SELECT
+    a,
+    count(*) AS n
+FROM AllData
+WHERE
+    a > 1
+    AND b BETWEEN 1 AND 2
+GROUP BY
+    a
+ORDER BY
+    n DESC;
+";
+        let formatted = format_sql(sql);
+        println!("{formatted}");
+        assert_eq!(formatted, expected);
+    }
+
+    #[test]
+    fn test_format_preserves_quotes_and_comments() {
+        let sql = "-- keep me\nselect `from col`, 'and where' from AllData except (\"x\", y)";
+        let expected = "\
+-- keep me
+SELECT
+    `from col`,
+    'and where'
+FROM AllData
+EXCEPT (\"x\", y)
+";
+        assert_eq!(format_sql(sql), expected);
+    }
+
+    #[test]
+    fn test_format_function_keywords() {
+        let sql = "select left(name, 3), REPLACE(name, 'a', 'b') as r from AllData \
+                   where right(code, 2) = 'BR' order by LEFT(name, 1)";
+        let expected = "\
+SELECT
+    left(name, 3),
+    REPLACE(name, 'a', 'b') AS r
+FROM AllData
+WHERE
+    right(code, 2) = 'BR'
+ORDER BY
+    LEFT(name, 1)
+";
+        assert_eq!(format_sql(sql), expected);
+
+        // After `*`, REPLACE is the wildcard option.
+        let sql = "select * replace (a * 2 as a) from AllData left join t using (id)";
+        let expected = "\
+SELECT
+    *
+REPLACE (a * 2 AS a)
+FROM AllData
+LEFT JOIN t
+USING (id)
+";
+        assert_eq!(format_sql(sql), expected);
+    }
+
+    #[test]
+    fn test_format_round_trips() {
+        let sql = "select a::int, 1.5e-3 * b, 2E+10, \
+                   case when a > 1 and b < 2 or c then 'x' else 'y' end as k \
+                   from AllData where a::text = '1' and b > 1e3";
+        let expected = "\
+SELECT
+    a::int,
+    1.5e-3 * b,
+    2E+10,
+    CASE WHEN a > 1 AND b < 2 OR c THEN 'x' ELSE 'y' END AS k
+FROM AllData
+WHERE
+    a::text = '1'
+    AND b > 1e3
+";
+        let formatted = format_sql(sql);
+        assert_eq!(formatted, expected);
+        assert_eq!(format_sql(&formatted), formatted);
+
+        // Same tokens as the input: nothing is split or merged.
+        let words = |sql: &str| tokenize_sql(sql).len();
+        assert_eq!(words(&formatted), words(sql));
+    }
+
+    #[test]
+    fn test_format_is_idempotent() {
+        for example in sql_commands(&Schema::default(), &ExampleColumns::default()) {
+            let once = format_sql(&example);
+            assert_eq!(format_sql(&once), once);
+        }
+    }
+}
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_example_columns`
+#[cfg(test)]
+mod tests_example_columns {
+    use super::*;
+    use crate::{PolarsViewResult, compile_drop_regex};
+    use polars::prelude::Field;
+
+    fn schema() -> Schema {
+        Schema::from_iter([
+            Field::new("a".into(), DataType::String),
+            Field::new("b".into(), DataType::Int64),
+            Field::new("Total".into(), DataType::Int64),
+            Field::new("c".into(), DataType::String),
+        ])
+    }
+
+    #[test]
+    fn test_default_order() {
+        let schema = schema();
+        let columns = ExampleColumns::default();
+
+        assert_eq!(
+            find_nth_col_name(&schema, &columns, 0, DataType::is_string),
+            Some("a")
+        );
+        assert_eq!(
+            find_nth_col_name(&schema, &columns, 1, DataType::is_integer),
+            None
+        );
+    }
+
+    #[test]
+    fn test_frozen_columns_first() {
+        let schema = schema();
+        let frozen = ["c".to_string(), "Total".to_string()];
+        let columns = ExampleColumns {
+            frozen: &frozen,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            find_nth_col_name(&schema, &columns, 0, DataType::is_string),
+            Some("c")
+        );
+        // Frozen columns are used even if their names look auto-generated.
+        assert_eq!(
+            find_nth_col_name(&schema, &columns, 0, DataType::is_integer),
+            Some("Total")
+        );
+
+        let examples = sql_commands(&schema, &columns);
+        assert!(examples.iter().any(|sql| sql.contains("`c`")));
+    }
+
+    #[test]
+    fn test_hidden_and_dropped_columns_are_skipped() -> PolarsViewResult<()> {
+        let schema = schema();
+        let hidden = HashSet::from(["a".to_string()]);
+        let columns = ExampleColumns {
+            hidden: Some(&hidden),
+            dropped: Some(compile_drop_regex("^b$")?),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            find_nth_col_name(&schema, &columns, 0, DataType::is_string),
+            Some("c")
+        );
+        assert_eq!(
+            find_nth_col_name(&schema, &columns, 0, DataType::is_integer),
+            None
+        );
+
+        let examples = sql_commands(&schema, &columns);
+        assert!(
+            !examples
+                .iter()
+                .any(|sql| sql.contains("`a`") || sql.contains("`b`"))
+        );
+        Ok(())
+    }
+}
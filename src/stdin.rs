@@ -0,0 +1,121 @@
+//! Data piped on standard input (`cat data.csv | polars-view -` or `--stdin`).
+//!
+//! The input is read into memory, written to a temporary file with the extension of its
+//! format (see `new_temp_file`), and opened like any other file. The file is removed when
+//! the application exits.
+
+use crate::{PolarsViewResult, new_temp_file};
+
+use clap::ValueEnum;
+use std::io::{Read, Write};
+use tempfile::TempPath;
+
+/// Format of the data read from standard input (`--format`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StdinFormat {
+    Csv,
+    Json,
+    Ndjson,
+    Parquet,
+}
+
+impl StdinFormat {
+    /// Guesses the format of `bytes`: Parquet (`PAR1` magic), a JSON array, JSON lines
+    /// (first character `{`), otherwise CSV.
+    pub fn sniff(bytes: &[u8]) -> Self {
+        if bytes.starts_with(b"PAR1") {
+            return StdinFormat::Parquet;
+        }
+
+        let text = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes); // Skips a UTF-8 BOM.
+        let first = text
+            .iter()
+            .find(|byte| !byte.is_ascii_whitespace())
+            .copied();
+        match first {
+            Some(b'[') => StdinFormat::Json,
+            Some(b'{') => StdinFormat::Ndjson,
+            _ => StdinFormat::Csv,
+        }
+    }
+
+    /// File extension of the format (see `FileExtension::from_path`).
+    pub fn extension(self) -> &'static str {
+        match self {
+            StdinFormat::Csv => "csv",
+            StdinFormat::Json => "json",
+            StdinFormat::Ndjson => "ndjson",
+            StdinFormat::Parquet => "parquet",
+        }
+    }
+}
+
+/// Writes `bytes` to a new temporary file of `format` (guessed with `StdinFormat::sniff`
+/// if `None`). The file is removed when the returned path is dropped.
+pub fn write_stdin_bytes(bytes: &[u8], format: Option<StdinFormat>) -> PolarsViewResult<TempPath> {
+    let format = format.unwrap_or_else(|| StdinFormat::sniff(bytes));
+    let mut file = new_temp_file(&format!(".{}", format.extension()))?;
+    file.write_all(bytes)?;
+    file.flush()?;
+    tracing::info!(target: "polars_view", "Read {} bytes from stdin as {format:?}.", bytes.len());
+    Ok(file.into_temp_path())
+}
+
+/// Reads standard input to the end and writes it with `write_stdin_bytes`.
+pub fn write_stdin_data(format: Option<StdinFormat>) -> PolarsViewResult<TempPath> {
+    let mut bytes = Vec::new();
+    std::io::stdin().lock().read_to_end(&mut bytes)?;
+    write_stdin_bytes(&bytes, format)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_stdin`
+#[cfg(test)]
+mod tests_stdin {
+    use super::*;
+
+    #[test]
+    fn sniffs_the_format() {
+        assert_eq!(StdinFormat::sniff(b"id;name\n1;a\n"), StdinFormat::Csv);
+        assert_eq!(StdinFormat::sniff(b"  [{\"id\": 1}]"), StdinFormat::Json);
+        assert_eq!(
+            StdinFormat::sniff(b"{\"id\": 1}\n{\"id\": 2}\n"),
+            StdinFormat::Ndjson
+        );
+        assert_eq!(StdinFormat::sniff(b"PAR1\x15\x04"), StdinFormat::Parquet);
+        assert_eq!(StdinFormat::sniff(b""), StdinFormat::Csv);
+
+        // A UTF-8 BOM before the data.
+        assert_eq!(
+            StdinFormat::sniff(b"\xEF\xBB\xBF[{\"id\": 1}]"),
+            StdinFormat::Json
+        );
+        assert_eq!(
+            StdinFormat::sniff(b"\xEF\xBB\xBF{\"id\": 1}\n"),
+            StdinFormat::Ndjson
+        );
+        assert_eq!(
+            StdinFormat::sniff(b"\xEF\xBB\xBFid,name\n"),
+            StdinFormat::Csv
+        );
+    }
+
+    #[test]
+    fn stdin_data_is_a_private_temp_file() -> PolarsViewResult<()> {
+        let first = write_stdin_bytes(b"id\n1\n", None)?;
+        let second = write_stdin_bytes(b"[{\"id\": 1}]", None)?;
+        assert_ne!(first.to_path_buf(), second.to_path_buf());
+        assert!(first.extension().is_some_and(|ext| ext == "csv"));
+        assert!(second.extension().is_some_and(|ext| ext == "json"));
+        assert_eq!(std::fs::read(&first)?, b"id\n1\n");
+
+        let path = first.to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+        Ok(())
+    }
+}
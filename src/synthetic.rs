@@ -0,0 +1,617 @@
+//! Synthetic datasets for demos and for testing exports without real data
+//! ("File" > "New Synthetic Dataset..." and `--generate`).
+//!
+//! A dataset is described by a `SyntheticSpec`, written as text:
+//!
+//! ```text
+//! rows=1000 seed=42 nulls=0.05 schema=id:id,day:date(2024-01-01,365),city:str(Lisbon|Porto|Faro),price:normal(100,15)
+//! ```
+//!
+//! Column kinds (parameters are optional):
+//! - `id`: 1, 2, 3, ...
+//! - `int(min,max)`: uniform integers in `[min, max]` (default `0,100`).
+//! - `uniform(min,max)`: uniform floats in `[min, max)` (default `0,1`).
+//! - `normal(mean,std)`: normally distributed floats (default `0,1`).
+//! - `str(a|b|c)`: uniformly chosen categories (default `A|B|C|D`).
+//! - `bool(p)`: `true` with probability `p` (default `0.5`).
+//! - `date(start,days)`: uniform dates in `[start, start + days)` (default `2024-01-01,365`).
+//!
+//! `nulls` is the fraction of null values in every column but `id`. The same spec
+//! (and `seed`) always generates the same data.
+
+use crate::{PolarsViewResult, new_temp_file};
+
+use egui::{Context, DragValue, Slider, TextEdit, Window};
+use polars::prelude::*;
+use std::{fmt, str::FromStr};
+use tempfile::TempPath;
+
+/// Distribution of the values of a synthetic column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SyntheticKind {
+    Id,
+    Int {
+        min: i64,
+        max: i64,
+    },
+    Uniform {
+        min: f64,
+        max: f64,
+    },
+    Normal {
+        mean: f64,
+        std: f64,
+    },
+    Category(Vec<String>),
+    Bool {
+        p: f64,
+    },
+    /// `start`: days since 1970-01-01.
+    Date {
+        start: i32,
+        days: u32,
+    },
+}
+
+/// A named synthetic column.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticColumn {
+    pub name: String,
+    pub kind: SyntheticKind,
+}
+
+/// Description of a synthetic dataset (see the module documentation for the text form).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SyntheticSpec {
+    pub rows: usize,
+    pub seed: u64,
+    /// Fraction (0..=1) of null values in every column but `id`.
+    pub nulls: f64,
+    pub columns: Vec<SyntheticColumn>,
+}
+
+impl Default for SyntheticSpec {
+    fn default() -> Self {
+        "rows=1000 seed=42 nulls=0.02 schema=id:id,day:date,category:str,quantity:int(1,100),\
+        price:uniform(1,500),score:normal(50,15),active:bool"
+            .parse()
+            .expect("valid default spec")
+    }
+}
+
+/// Days since 1970-01-01 of a `YYYY-MM-DD` date (proleptic Gregorian calendar).
+fn parse_date(text: &str) -> Option<i32> {
+    let mut parts = text.splitn(3, '-');
+    let year: i64 = parts.next()?.parse().ok()?;
+    let month: i64 = parts.next()?.parse().ok()?;
+    let day: i64 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+
+    // Howard Hinnant's `days_from_civil`.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    i32::try_from(era * 146_097 + day_of_era - 719_468).ok()
+}
+
+/// `YYYY-MM-DD` of a number of days since 1970-01-01 (inverse of `parse_date`).
+fn format_date(days: i32) -> String {
+    // Howard Hinnant's `civil_from_days`.
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+impl FromStr for SyntheticKind {
+    type Err = String;
+
+    /// Parses `kind` or `kind(parameters)`.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        let (name, params) = match text.split_once('(') {
+            Some((name, rest)) => {
+                let params = rest
+                    .strip_suffix(')')
+                    .ok_or_else(|| format!("missing ')' in '{text}'"))?;
+                (name.trim(), Some(params))
+            }
+            None => (text, None),
+        };
+
+        let numbers = |defaults: [f64; 2]| -> Result<[f64; 2], String> {
+            let Some(params) = params else {
+                return Ok(defaults);
+            };
+            let values: Vec<f64> = params
+                .split(',')
+                .map(|value| value.trim().parse::<f64>())
+                .collect::<Result<_, _>>()
+                .map_err(|error| format!("invalid parameters '{params}' of '{name}': {error}"))?;
+            <[f64; 2]>::try_from(values)
+                .map_err(|_| format!("'{name}' expects two parameters, got '{params}'"))
+        };
+
+        let kind = match name {
+            "id" => SyntheticKind::Id,
+            "int" => {
+                let [min, max] = numbers([0.0, 100.0])?;
+                SyntheticKind::Int {
+                    min: min as i64,
+                    max: max as i64,
+                }
+            }
+            "uniform" => {
+                let [min, max] = numbers([0.0, 1.0])?;
+                SyntheticKind::Uniform { min, max }
+            }
+            "normal" => {
+                let [mean, std] = numbers([0.0, 1.0])?;
+                SyntheticKind::Normal { mean, std }
+            }
+            "str" => {
+                let categories: Vec<String> = params
+                    .unwrap_or("A|B|C|D")
+                    .split('|')
+                    .map(|category| category.trim().to_string())
+                    .filter(|category| !category.is_empty())
+                    .collect();
+                if categories.is_empty() {
+                    return Err(format!("'{text}' has no categories"));
+                }
+                SyntheticKind::Category(categories)
+            }
+            "bool" => {
+                let p = match params {
+                    Some(p) => p
+                        .trim()
+                        .parse()
+                        .map_err(|error| format!("invalid probability '{p}': {error}"))?,
+                    None => 0.5,
+                };
+                SyntheticKind::Bool { p }
+            }
+            "date" => {
+                let (start, days) = match params {
+                    Some(params) => params
+                        .split_once(',')
+                        .ok_or_else(|| format!("'date' expects 'start,days', got '{params}'"))?,
+                    None => ("2024-01-01", "365"),
+                };
+                SyntheticKind::Date {
+                    start: parse_date(start.trim())
+                        .ok_or_else(|| format!("invalid date '{start}' (expected YYYY-MM-DD)"))?,
+                    days: days
+                        .trim()
+                        .parse()
+                        .map_err(|error| format!("invalid number of days '{days}': {error}"))?,
+                }
+            }
+            _ => {
+                return Err(format!(
+                    "unknown column kind '{name}' (expected id, int, uniform, normal, str, bool or date)"
+                ));
+            }
+        };
+
+        let invalid = match &kind {
+            SyntheticKind::Int { min, max } => min > max,
+            SyntheticKind::Uniform { min, max } => min > max,
+            SyntheticKind::Normal { std, .. } => *std < 0.0,
+            SyntheticKind::Bool { p } => !(0.0..=1.0).contains(p),
+            SyntheticKind::Date { days, .. } => *days == 0,
+            SyntheticKind::Id | SyntheticKind::Category(_) => false,
+        };
+        if invalid {
+            return Err(format!("invalid parameters in '{text}'"));
+        }
+
+        Ok(kind)
+    }
+}
+
+impl fmt::Display for SyntheticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyntheticKind::Id => write!(f, "id"),
+            SyntheticKind::Int { min, max } => write!(f, "int({min},{max})"),
+            SyntheticKind::Uniform { min, max } => write!(f, "uniform({min},{max})"),
+            SyntheticKind::Normal { mean, std } => write!(f, "normal({mean},{std})"),
+            SyntheticKind::Category(categories) => write!(f, "str({})", categories.join("|")),
+            SyntheticKind::Bool { p } => write!(f, "bool({p})"),
+            SyntheticKind::Date { start, days } => {
+                write!(f, "date({},{days})", format_date(*start))
+            }
+        }
+    }
+}
+
+/// Splits `text` at the commas outside parentheses.
+fn split_columns(text: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0usize, 0);
+    for (index, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            ',' if depth == 0 => {
+                parts.push(&text[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Parses the `schema` of a spec: comma-separated `name:kind` columns.
+pub fn parse_schema(text: &str) -> Result<Vec<SyntheticColumn>, String> {
+    let columns: Vec<SyntheticColumn> = split_columns(text)
+        .into_iter()
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .map(|column| {
+            let (name, kind) = column
+                .split_once(':')
+                .ok_or_else(|| format!("column '{column}' must be 'name:kind'"))?;
+            Ok(SyntheticColumn {
+                name: name.trim().to_string(),
+                kind: kind.parse()?,
+            })
+        })
+        .collect::<Result<_, String>>()?;
+
+    if columns.is_empty() {
+        return Err("the schema has no columns".to_string());
+    }
+    let mut names: Vec<&str> = columns.iter().map(|column| column.name.as_str()).collect();
+    names.sort_unstable();
+    if let Some(name) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+        return Err(format!("duplicate column name '{}'", name[0]));
+    }
+    Ok(columns)
+}
+
+/// The `schema` text of `columns` (inverse of `parse_schema`).
+pub fn format_schema(columns: &[SyntheticColumn]) -> String {
+    columns
+        .iter()
+        .map(|column| format!("{}:{}", column.name, column.kind))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl FromStr for SyntheticSpec {
+    type Err = String;
+
+    /// Parses whitespace-separated `rows=`, `seed=`, `nulls=` and `schema=` settings.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (mut rows, mut seed, mut nulls, mut columns) = (1000, 42, 0.0, None);
+
+        for setting in text.split_whitespace() {
+            let (key, value) = setting
+                .split_once('=')
+                .ok_or_else(|| format!("setting '{setting}' must be 'key=value'"))?;
+            let invalid = |error: &dyn fmt::Display| format!("invalid {key} '{value}': {error}");
+            match key {
+                "rows" => rows = value.parse().map_err(|e| invalid(&e))?,
+                "seed" => seed = value.parse().map_err(|e| invalid(&e))?,
+                "nulls" => nulls = value.parse().map_err(|e| invalid(&e))?,
+                "schema" => columns = Some(parse_schema(value)?),
+                _ => {
+                    return Err(format!(
+                        "unknown setting '{key}' (expected rows, seed, nulls or schema)"
+                    ));
+                }
+            }
+        }
+
+        if !(0.0..=1.0).contains(&nulls) {
+            return Err(format!("nulls must be between 0 and 1, got {nulls}"));
+        }
+
+        Ok(SyntheticSpec {
+            rows,
+            seed,
+            nulls,
+            columns: columns.ok_or("missing 'schema=' setting")?,
+        })
+    }
+}
+
+impl fmt::Display for SyntheticSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rows={} seed={} nulls={} schema={}",
+            self.rows,
+            self.seed,
+            self.nulls,
+            format_schema(&self.columns)
+        )
+    }
+}
+
+/// Small deterministic pseudo-random generator (SplitMix64).
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `[0, 1)`.
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform in `[0, n)` (`n > 0`).
+    fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// Standard normal (Box-Muller).
+    fn normal(&mut self) -> f64 {
+        let u1 = 1.0 - self.next_f64(); // (0, 1]: avoids ln(0).
+        let u2 = self.next_f64();
+        (-2.0 * u1.ln()).sqrt() * (std::f64::consts::TAU * u2).cos()
+    }
+}
+
+/// Generates the `DataFrame` described by `spec`.
+pub fn generate(spec: &SyntheticSpec) -> PolarsResult<DataFrame> {
+    let mut rng = Rng(spec.seed);
+    let rows = spec.rows;
+
+    let columns = spec
+        .columns
+        .iter()
+        .map(|column| {
+            let name = column.name.as_str();
+            let is_null = |rng: &mut Rng| spec.nulls > 0.0 && rng.next_f64() < spec.nulls;
+
+            let series = match &column.kind {
+                SyntheticKind::Id => {
+                    Series::new(name.into(), (1..=rows as i64).collect::<Vec<_>>())
+                }
+                SyntheticKind::Int { min, max } => {
+                    let span = max.abs_diff(*min).saturating_add(1);
+                    let values: Vec<Option<i64>> = (0..rows)
+                        .map(|_| {
+                            (!is_null(&mut rng)).then(|| min.wrapping_add(rng.below(span) as i64))
+                        })
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                SyntheticKind::Uniform { min, max } => {
+                    let values: Vec<Option<f64>> = (0..rows)
+                        .map(|_| (!is_null(&mut rng)).then(|| min + (max - min) * rng.next_f64()))
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                SyntheticKind::Normal { mean, std } => {
+                    let values: Vec<Option<f64>> = (0..rows)
+                        .map(|_| (!is_null(&mut rng)).then(|| mean + std * rng.normal()))
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                SyntheticKind::Category(categories) => {
+                    let values: Vec<Option<&str>> = (0..rows)
+                        .map(|_| {
+                            (!is_null(&mut rng)).then(|| {
+                                categories[rng.below(categories.len() as u64) as usize].as_str()
+                            })
+                        })
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                SyntheticKind::Bool { p } => {
+                    let values: Vec<Option<bool>> = (0..rows)
+                        .map(|_| (!is_null(&mut rng)).then(|| rng.next_f64() < *p))
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                SyntheticKind::Date { start, days } => {
+                    let values: Vec<Option<i32>> = (0..rows)
+                        .map(|_| {
+                            (!is_null(&mut rng)).then(|| start + rng.below(u64::from(*days)) as i32)
+                        })
+                        .collect();
+                    Series::new(name.into(), values).cast(&DataType::Date)?
+                }
+            };
+            Ok(series.into_column())
+        })
+        .collect::<PolarsResult<Vec<Column>>>()?;
+
+    DataFrame::new(rows, columns)
+}
+
+/// Generates the dataset of `spec` and writes it as Parquet to a new temporary file,
+/// returning its path (to be opened like any other file; the file is removed when the
+/// path is dropped).
+pub fn write_synthetic_dataset(spec: &SyntheticSpec) -> PolarsViewResult<TempPath> {
+    let mut df = generate(spec)?;
+    let mut file = new_temp_file(".parquet")?;
+    ParquetWriter::new(file.as_file_mut()).finish(&mut df)?;
+    Ok(file.into_temp_path())
+}
+
+/// State of the "New Synthetic Dataset" dialog (opened from the "File" menu).
+pub struct SyntheticDialog {
+    /// Whether the dialog is shown.
+    pub open: bool,
+    pub rows: usize,
+    pub seed: u64,
+    pub nulls: f64,
+    /// The columns, as `parse_schema` text (one column per line is allowed).
+    pub schema: String,
+    /// Error of the last "Generate" click.
+    pub error: Option<String>,
+}
+
+impl Default for SyntheticDialog {
+    fn default() -> Self {
+        let spec = SyntheticSpec::default();
+        SyntheticDialog {
+            open: false,
+            rows: spec.rows,
+            seed: spec.seed,
+            nulls: spec.nulls,
+            schema: format_schema(&spec.columns).replace(',', ",\n"),
+            error: None,
+        }
+    }
+}
+
+impl SyntheticDialog {
+    /// Shows the dialog (if open). Returns the spec to generate when "Generate" is clicked.
+    pub fn show(&mut self, ctx: &Context) -> Option<SyntheticSpec> {
+        if !self.open {
+            return None;
+        }
+
+        let mut generate = None;
+        let mut open = self.open;
+
+        Window::new("New Synthetic Dataset")
+            .collapsible(false)
+            .resizable(false)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                egui::Grid::new("synthetic_grid")
+                    .num_columns(2)
+                    .spacing([10.0, 10.0])
+                    .show(ui, |ui| {
+                        ui.label("Rows:");
+                        ui.add(
+                            DragValue::new(&mut self.rows)
+                                .range(1..=10_000_000)
+                                .speed(100),
+                        );
+                        ui.end_row();
+
+                        ui.label("Seed:");
+                        ui.add(DragValue::new(&mut self.seed));
+                        ui.end_row();
+
+                        ui.label("Nulls:");
+                        ui.add(Slider::new(&mut self.nulls, 0.0..=1.0).fixed_decimals(2))
+                            .on_hover_text("Fraction of null values in every column but `id`.");
+                        ui.end_row();
+
+                        ui.label("Columns:").on_hover_text(
+                            "name:kind, separated by commas. Kinds: id, int(min,max),\n\
+                            uniform(min,max), normal(mean,std), str(a|b|c), bool(p),\n\
+                            date(YYYY-MM-DD,days).",
+                        );
+                        ui.add(
+                            TextEdit::multiline(&mut self.schema)
+                                .code_editor()
+                                .desired_rows(8)
+                                .desired_width(320.0),
+                        );
+                        ui.end_row();
+                    });
+
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.horizontal(|ui| {
+                    if ui.button("Generate").clicked() {
+                        match parse_schema(&self.schema) {
+                            Ok(columns) => {
+                                self.error = None;
+                                generate = Some(SyntheticSpec {
+                                    rows: self.rows,
+                                    seed: self.seed,
+                                    nulls: self.nulls,
+                                    columns,
+                                });
+                            }
+                            Err(error) => self.error = Some(error),
+                        }
+                    }
+                    if ui.button("Reset").clicked() {
+                        *self = SyntheticDialog {
+                            open: true,
+                            ..Default::default()
+                        };
+                    }
+                });
+            });
+
+        self.open = open && generate.is_none();
+        generate
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_synthetic`
+#[cfg(test)]
+mod tests_synthetic {
+    use super::*;
+
+    #[test]
+    fn spec_round_trip() -> Result<(), String> {
+        let text = "rows=10 seed=7 nulls=0.5 schema=id:id,d:date(2024-02-29,10),\
+                    city:str(Lisbon|Porto),x:int(-5,5),p:bool(0.25)";
+        let spec: SyntheticSpec = text.parse()?;
+
+        assert_eq!(spec.columns.len(), 5);
+        assert_eq!(
+            spec.columns[1].kind,
+            SyntheticKind::Date {
+                start: 19_782, // 2024-02-29
+                days: 10
+            }
+        );
+        assert_eq!(spec.to_string().parse::<SyntheticSpec>()?, spec);
+
+        assert!("rows=10".parse::<SyntheticSpec>().is_err()); // No schema.
+        assert!("schema=a:int(5,1)".parse::<SyntheticSpec>().is_err()); // min > max.
+        assert!("schema=a:id,a:bool".parse::<SyntheticSpec>().is_err()); // Duplicate.
+        assert!("schema=a:text".parse::<SyntheticSpec>().is_err()); // Unknown kind.
+        Ok(())
+    }
+
+    #[test]
+    fn generates_reproducible_data() -> PolarsResult<()> {
+        let spec = SyntheticSpec {
+            rows: 500,
+            ..Default::default()
+        };
+
+        let df = generate(&spec)?;
+        assert_eq!(df.shape(), (500, spec.columns.len()));
+        assert_eq!(df.column("id")?.null_count(), 0);
+        assert_eq!(df.column("day")?.dtype(), &DataType::Date);
+
+        let quantity = df.column("quantity")?.i64()?;
+        assert!(quantity.min().unwrap() >= 1 && quantity.max().unwrap() <= 100);
+
+        assert!(generate(&spec)?.equals_missing(&df)); // Same seed, same data.
+        Ok(())
+    }
+}
@@ -0,0 +1,450 @@
+//! Global search (Ctrl+F): finds a substring or regex in the cells of the table,
+//! highlights the matching cells (or the matched text inside them) and navigates between
+//! them (scrolling the table).
+
+use crate::{DataContainer, PolarsViewResult};
+
+use egui::{
+    Color32, Key, RichText, TextEdit, TextFormat, TextStyle, Ui, WidgetInfo, WidgetType,
+    text::LayoutJob,
+};
+use polars::prelude::*;
+use regex::{Regex, RegexBuilder};
+use std::{
+    collections::{HashMap, HashSet},
+    ops::Range,
+};
+
+/// Maximum number of matches kept (the first ones, in row order).
+pub const MAX_SEARCH_MATCHES: usize = 100_000;
+
+/// What and where to search.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchOptions {
+    /// Text (or regular expression) to find.
+    pub query: String,
+    /// Interpret `query` as a regular expression.
+    pub regex: bool,
+    pub case_sensitive: bool,
+    /// Also search non-String columns, by their text representation.
+    pub all_columns: bool,
+    /// Highlight the matched text inside the cells (see `match_ranges`), not only the cells.
+    pub highlight_text: bool,
+}
+
+impl SearchOptions {
+    /// The search as a `Regex` (a literal `query` is escaped), used to locate the matched
+    /// text inside cells. `None` for an empty or invalid query.
+    pub fn to_regex(&self) -> Option<Regex> {
+        if self.query.is_empty() {
+            return None;
+        }
+        let pattern = if self.regex {
+            self.query.clone()
+        } else {
+            regex::escape(&self.query)
+        };
+        RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+            .ok()
+    }
+}
+
+/// Byte ranges of the (non-empty) matches of `regex` in `text`.
+pub fn match_ranges(regex: &Regex, text: &str) -> Vec<Range<usize>> {
+    regex
+        .find_iter(text)
+        .filter(|found| !found.is_empty())
+        .map(|found| found.range())
+        .collect()
+}
+
+/// Finds the cells of `columns` (in `df`) matching `options`, in row order
+/// (then column order). At most `MAX_SEARCH_MATCHES` are returned.
+///
+/// Without `all_columns`, only String columns are searched. Nested columns are never searched.
+pub fn find_matches(
+    df: &DataFrame,
+    columns: &[&str],
+    options: &SearchOptions,
+) -> PolarsViewResult<Vec<(usize, String)>> {
+    if options.query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let schema = df.schema();
+    let searched: Vec<&str> = columns
+        .iter()
+        .copied()
+        .filter(|name| match schema.get(name) {
+            Some(DataType::String) => true,
+            Some(dtype) => options.all_columns && !dtype.is_nested(),
+            None => false,
+        })
+        .collect();
+
+    let masks: Vec<Expr> = searched
+        .iter()
+        .map(|name| {
+            let values = match schema.get(name) {
+                Some(DataType::String) => col(*name),
+                _ => col(*name).cast(DataType::String),
+            };
+
+            let mask = match (options.regex, options.case_sensitive) {
+                (true, true) => values.str().contains(lit(options.query.clone()), true),
+                (true, false) => values
+                    .str()
+                    .contains(lit(format!("(?i){}", options.query)), true),
+                (false, true) => values.str().contains_literal(lit(options.query.clone())),
+                (false, false) => values
+                    .str()
+                    .to_lowercase()
+                    .str()
+                    .contains_literal(lit(options.query.to_lowercase())),
+            };
+            mask.fill_null(lit(false)).alias(*name)
+        })
+        .collect();
+
+    if masks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let masks = df.clone().lazy().select(masks).collect()?;
+
+    let mut matches: Vec<(usize, usize)> = Vec::new();
+    for (column_index, mask) in masks.columns().iter().enumerate() {
+        for (row, is_match) in mask.bool()?.into_iter().enumerate() {
+            if is_match == Some(true) {
+                matches.push((row, column_index));
+            }
+        }
+    }
+    matches.sort_unstable();
+    matches.truncate(MAX_SEARCH_MATCHES);
+
+    Ok(matches
+        .into_iter()
+        .map(|(row, column_index)| (row, searched[column_index].to_string()))
+        .collect())
+}
+
+/// State of the search bar shown above the table (Ctrl+F).
+#[derive(Debug, Clone, Default)]
+pub struct TableSearch {
+    /// Whether the search bar is shown.
+    pub open: bool,
+    pub options: SearchOptions,
+    /// Matches `(row, column)` of the last search, in row order.
+    matches: Vec<(usize, String)>,
+    /// Rows with a match, per column (fast lookup while rendering cells).
+    match_rows: HashMap<String, HashSet<usize>>,
+    /// Index in `matches` of the current match.
+    current: Option<usize>,
+    /// The search as a `Regex`, for `highlight_text` (see `SearchOptions::to_regex`).
+    regex: Option<Regex>,
+    /// Options and `DataFrame` (address) of the last search; a change triggers a new one.
+    searched: Option<(SearchOptions, usize)>,
+    /// Row to scroll the table to (consumed by `DataContainer::render_table`).
+    pub scroll_to_row: Option<usize>,
+    /// Focus the search input on the next frame (set by Ctrl+F).
+    focus: bool,
+    error: Option<String>,
+}
+
+impl TableSearch {
+    /// Opens the search bar and focuses its input.
+    pub fn open(&mut self) {
+        self.open = true;
+        self.focus = true;
+    }
+
+    /// Returns `true` if the cell at (`row`, `column`) matches the last search.
+    pub fn is_match(&self, row: usize, column: &str) -> bool {
+        self.open
+            && self
+                .match_rows
+                .get(column)
+                .is_some_and(|rows| rows.contains(&row))
+    }
+
+    /// Returns `true` if the cell at (`row`, `column`) is the current match.
+    pub fn is_current(&self, row: usize, column: &str) -> bool {
+        self.open
+            && self
+                .current
+                .and_then(|index| self.matches.get(index))
+                .is_some_and(|(r, c)| *r == row && c == column)
+    }
+
+    /// With `highlight_text`: the displayed `text` of the matching cell at (`row`, `column`),
+    /// with the matched parts on a colored background.
+    ///
+    /// `None` if the cell does not match, or if its displayed text (e.g., a formatted
+    /// number) does not contain the match: the whole cell is highlighted instead.
+    pub fn highlighted_text(
+        &self,
+        ui: &Ui,
+        row: usize,
+        column: &str,
+        text: &str,
+    ) -> Option<LayoutJob> {
+        if !self.options.highlight_text || !self.is_match(row, column) {
+            return None;
+        }
+        let ranges = match_ranges(self.regex.as_ref()?, text);
+        if ranges.is_empty() {
+            return None;
+        }
+
+        let plain = TextFormat {
+            font_id: TextStyle::Body.resolve(ui.style()),
+            color: ui.visuals().text_color(),
+            ..Default::default()
+        };
+        let matched = TextFormat {
+            background: Self::highlight_color(true),
+            ..plain.clone()
+        };
+
+        let mut job = LayoutJob::default();
+        let mut end = 0;
+        for range in ranges {
+            job.append(&text[end..range.start], 0.0, plain.clone());
+            job.append(&text[range.clone()], 0.0, matched.clone());
+            end = range.end;
+        }
+        job.append(&text[end..], 0.0, plain);
+        Some(job)
+    }
+
+    /// Background color of a matching cell (stronger for the current match).
+    pub fn highlight_color(is_current: bool) -> Color32 {
+        if is_current {
+            Color32::from_rgba_unmultiplied(255, 170, 0, 150)
+        } else {
+            Color32::from_rgba_unmultiplied(255, 210, 0, 60)
+        }
+    }
+
+    /// Moves to the next (`step = 1`) or previous (`step = -1`) match, wrapping around,
+    /// and requests scrolling to its row.
+    fn step(&mut self, step: isize) {
+        if self.matches.is_empty() {
+            return;
+        }
+        let len = self.matches.len() as isize;
+        let next = match self.current {
+            Some(index) => (index as isize + step).rem_euclid(len),
+            None if step < 0 => len - 1,
+            None => 0,
+        } as usize;
+
+        self.current = Some(next);
+        self.scroll_to_row = Some(self.matches[next].0);
+    }
+
+    /// Runs the search on the data displayed by `container` (visible columns only).
+    fn search(&mut self, container: &DataContainer) {
+        let columns: Vec<&str> = container
+            .visible_columns()
+            .map(|column| column.name().as_str())
+            .collect();
+
+        self.matches.clear();
+        self.match_rows.clear();
+        self.current = None;
+        self.error = None;
+        self.regex = self.options.to_regex();
+
+        match find_matches(&container.df, &columns, &self.options) {
+            Ok(matches) => {
+                for (row, column) in &matches {
+                    self.match_rows
+                        .entry(column.clone())
+                        .or_default()
+                        .insert(*row);
+                }
+                self.matches = matches;
+                self.step(1);
+            }
+            Err(error) => self.error = Some(error.to_string()),
+        }
+    }
+
+    /// Renders the search bar: input, options, match count and navigation.
+    ///
+    /// Enter runs the search (or moves to the next match if nothing changed); the search
+    /// also re-runs when the displayed data changes (sort, filter, query).
+    pub fn render_bar(&mut self, ui: &mut Ui, container: &DataContainer) {
+        if !self.open {
+            return;
+        }
+
+        let mut enter = false;
+        ui.horizontal(|ui| {
+            ui.label("🔍");
+            let response = ui.add(
+                TextEdit::singleline(&mut self.options.query)
+                    .hint_text("Search the table (Enter: next match)")
+                    .desired_width(280.0),
+            );
+            response.widget_info(|| {
+                WidgetInfo::labeled(WidgetType::TextEdit, true, "Search the table")
+            });
+            if std::mem::take(&mut self.focus) {
+                response.request_focus();
+            }
+            enter = response.lost_focus() && ui.input(|i| i.key_pressed(Key::Enter));
+            if enter {
+                response.request_focus(); // Keep typing / pressing Enter.
+            }
+
+            ui.checkbox(&mut self.options.regex, "Regex");
+            ui.checkbox(&mut self.options.case_sensitive, "Aa")
+                .on_hover_text("Case sensitive");
+            ui.checkbox(&mut self.options.all_columns, "All columns")
+                .on_hover_text("Also search non-String columns (numbers, dates, ...) as text.");
+            ui.checkbox(&mut self.options.highlight_text, "Highlight text")
+                .on_hover_text("Highlight the matched text inside the cells, not only the cells.");
+
+            if ui.button("◀").on_hover_text("Previous match").clicked() {
+                self.step(-1);
+            }
+            if ui.button("▶").on_hover_text("Next match").clicked() {
+                self.step(1);
+            }
+
+            if let Some(error) = &self.error {
+                ui.colored_label(ui.visuals().error_fg_color, error);
+            } else if self.searched.is_some() {
+                let position = self.current.map_or(0, |index| index + 1);
+                let mut text = format!("{position} of {}", self.matches.len());
+                if self.matches.len() == MAX_SEARCH_MATCHES {
+                    text.push('+');
+                }
+                ui.label(RichText::new(text).weak());
+            }
+
+            if ui.button("✖").on_hover_text("Close (Esc)").clicked()
+                || ui.input(|i| i.key_pressed(Key::Escape))
+            {
+                self.open = false;
+            }
+        });
+
+        let state = (
+            self.options.clone(),
+            std::sync::Arc::as_ptr(&container.df) as usize,
+        );
+        let changed = self.searched.as_ref() != Some(&state);
+
+        if changed && (enter || self.searched.is_some()) {
+            self.search(container);
+            self.searched = Some(state);
+        } else if enter {
+            self.step(1);
+        }
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_table_search`
+#[cfg(test)]
+mod tests_table_search {
+    use super::*;
+
+    fn options(query: &str) -> SearchOptions {
+        SearchOptions {
+            query: query.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn finds_substrings_in_string_columns() -> PolarsViewResult<()> {
+        let df = df!(
+            "name" => &[Some("Alice"), None, Some("alan"), Some("Bob")],
+            "city" => &["Lisbon", "Paris", "Salvador", "Alagoas"],
+            "code" => &[1, 12, 123, 4],
+        )?;
+        let columns = ["name", "city", "code"];
+
+        let matches = find_matches(&df, &columns, &options("al"))?;
+        let expected: Vec<(usize, String)> = vec![
+            (0, "name".to_string()),
+            (2, "name".to_string()),
+            (2, "city".to_string()),
+            (3, "city".to_string()),
+        ];
+        assert_eq!(matches, expected);
+
+        // Numbers are only searched with `all_columns`.
+        assert!(find_matches(&df, &columns, &options("12"))?.is_empty());
+        let all = SearchOptions {
+            all_columns: true,
+            ..options("12")
+        };
+        assert_eq!(find_matches(&df, &columns, &all)?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn supports_regex_and_case() -> PolarsViewResult<()> {
+        let df = df!("name" => &["Alice", "alan", "Bob"])?;
+        let columns = ["name"];
+
+        let regex = SearchOptions {
+            regex: true,
+            ..options("^al")
+        };
+        assert_eq!(find_matches(&df, &columns, &regex)?.len(), 2);
+
+        let sensitive = SearchOptions {
+            case_sensitive: true,
+            ..options("Al")
+        };
+        assert_eq!(
+            find_matches(&df, &columns, &sensitive)?,
+            vec![(0, "name".to_string())]
+        );
+
+        let invalid = SearchOptions {
+            regex: true,
+            ..options("(")
+        };
+        assert!(find_matches(&df, &columns, &invalid).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn locates_matched_text() {
+        let literal = options("a.");
+        let regex = literal.to_regex().unwrap();
+        assert_eq!(match_ranges(&regex, "xA.yA.a"), vec![1..3, 4..6]);
+        assert!(match_ranges(&regex, "abc").is_empty());
+
+        let pattern = SearchOptions {
+            regex: true,
+            case_sensitive: true,
+            ..options("b+")
+        };
+        let regex = pattern.to_regex().unwrap();
+        assert_eq!(match_ranges(&regex, "abbcB"), vec![1..3]);
+
+        // Empty matches are skipped; invalid patterns give no regex.
+        let regex = Regex::new("x*").unwrap();
+        assert_eq!(match_ranges(&regex, "axb"), vec![1..2]);
+        let invalid = SearchOptions {
+            regex: true,
+            ..options("(")
+        };
+        assert!(invalid.to_regex().is_none());
+    }
+}
@@ -0,0 +1,381 @@
+//! Temporary files: files derived from the opened file and read in its place (transcoded to
+//! UTF-8, see `encoding.rs`; extracted from a zip archive, see `zip_archive.rs`; unescaped,
+//! see `csv_sniffer.rs`), and the data files written for the readers (database query
+//! results, downloads, concatenated folders...).
+//!
+//! They live in a private folder of the temporary directory (`polars-view`, readable by the
+//! user only on Unix), and are written to a new temporary file first, renamed into place
+//! once complete (see `write_atomically`): an interrupted write never leaves a truncated
+//! file, and an existing file with the same name is never written through.
+//!
+//! A derived file is named after its source (canonical path, size and modification time,
+//! see `derived_file`), so another file with the same name, or a newer version of the
+//! same file, never reuses it. Derived files unused for a week are removed at startup, and
+//! so are the least recently used ones beyond a total size (see `prune_derived_files`).
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use std::{
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use tempfile::NamedTempFile;
+
+/// Name of the private folder in the temporary directory.
+const PRIVATE_DIR_NAME: &str = "polars-view";
+
+/// Name prefix of the files of `new_temp_file`.
+const TEMP_FILE_PREFIX: &str = "polars-view-";
+
+/// Derived files unused for longer are removed at startup (see `prune_derived_files`).
+const DERIVED_FILE_MAX_AGE: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Total size of the derived files kept at startup (see `prune_derived_files`).
+const DERIVED_FILES_MAX_BYTES: u64 = 4 * 1024 * 1024 * 1024;
+
+/// The private folder of the temporary files, created if needed (mode 0700 on Unix).
+///
+/// An existing folder must be a folder (not a symbolic link) owned by the user: its
+/// permissions are reset to 0700, which fails for a folder of another user.
+pub fn private_temp_dir() -> PolarsViewResult<PathBuf> {
+    let dir = std::env::temp_dir().join(PRIVATE_DIR_NAME);
+
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+
+    match builder.create(&dir) {
+        Ok(()) => {}
+        Err(error) if error.kind() == io::ErrorKind::AlreadyExists => {
+            if !fs::symlink_metadata(&dir)?.is_dir() {
+                return Err(PolarsViewError::Other(format!(
+                    "'{}' is not a folder",
+                    dir.display()
+                )));
+            }
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+            }
+        }
+        Err(error) => return Err(error.into()),
+    }
+    Ok(dir)
+}
+
+/// Whether `path` is in the private folder (stdin copies, derived files, database query
+/// results...): such a file may be removed at exit, so it is never remembered in the
+/// recent files, the query history or the last session.
+pub fn is_private_temp_path(path: &Path) -> bool {
+    let dir = std::env::temp_dir().join(PRIVATE_DIR_NAME);
+    path.starts_with(&dir) || fs::canonicalize(&dir).is_ok_and(|dir| path.starts_with(dir))
+}
+
+/// A new, empty temporary file in the private folder (mode 0600 on Unix), named
+/// `polars-view-<random><suffix>` (the suffix gives the extension read by the readers).
+/// The file is removed when the returned value is dropped.
+pub fn new_temp_file(suffix: &str) -> PolarsViewResult<NamedTempFile> {
+    Ok(tempfile::Builder::new()
+        .prefix(TEMP_FILE_PREFIX)
+        .suffix(suffix)
+        .tempfile_in(private_temp_dir()?)?)
+}
+
+/// Writes `path` through `write`: the data goes to a new temporary file in the folder of
+/// `path`, renamed to `path` once `write` succeeds. On error, `path` is left unchanged.
+///
+/// An existing `path` keeps its permissions (and, on Unix, its owner when allowed); a
+/// symbolic link is resolved, so the file it points to is replaced, not the link.
+pub fn write_atomically(
+    path: &Path,
+    write: impl FnOnce(&mut dyn Write) -> PolarsViewResult<()>,
+) -> PolarsViewResult<()> {
+    let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let dir = match target.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+
+    let mut writer = BufWriter::new(NamedTempFile::new_in(dir)?);
+    write(&mut writer)?;
+    let temp = writer.into_inner().map_err(|error| error.into_error())?;
+
+    if let Ok(metadata) = fs::metadata(&target) {
+        temp.as_file().set_permissions(metadata.permissions())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::MetadataExt;
+            // Only root can give a file to another user: the owner is kept when allowed.
+            let (uid, gid) = (Some(metadata.uid()), Some(metadata.gid()));
+            if let Err(error) = std::os::unix::fs::fchown(temp.as_file(), uid, gid) {
+                tracing::debug!("Owner of '{}' not kept: {error}", target.display());
+            }
+        }
+    }
+
+    temp.as_file().sync_all()?;
+    temp.persist(&target).map_err(|error| error.error)?;
+    Ok(())
+}
+
+/// Hash of `value`, as 16 hexadecimal digits of a file name.
+fn hash_name(value: impl Hash) -> String {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// The name prefix of the files derived from `source` (and its `entry`) by `kind`, for any
+/// version of `source`, and the full name of the one derived from its current version.
+fn derived_names(
+    kind: &str,
+    source: &Path,
+    entry: Option<&str>,
+) -> PolarsViewResult<(String, String)> {
+    let canonical = source.canonicalize()?;
+    let metadata = canonical.metadata()?;
+    let version = (metadata.len(), metadata.modified().ok());
+    let file_name = match entry {
+        Some(entry) => Path::new(entry).file_name(),
+        None => source.file_name(),
+    }
+    .unwrap_or_default()
+    .to_string_lossy();
+
+    let prefix = format!("{kind}-{}-", hash_name((&canonical, entry)));
+    let name = format!("{prefix}{}-{file_name}", hash_name(version));
+    Ok((prefix, name))
+}
+
+/// Returns the file derived from `source` by `kind` (e.g., `utf8-Windows1252`), written by
+/// `write` if it doesn't exist yet.
+///
+/// The file is named `<kind>-<source hash>-<version hash>-<file name>` in the private
+/// folder: the source hash covers the canonical path of `source`, the version hash its size
+/// and modification time, and the file name of `source` keeps the extension read by the
+/// readers. The files derived from older versions of `source` are removed.
+pub fn derived_file(
+    kind: &str,
+    source: &Path,
+    write: impl FnOnce(&mut dyn Write) -> PolarsViewResult<()>,
+) -> PolarsViewResult<PathBuf> {
+    derive(kind, source, None, write)
+}
+
+/// Returns the file derived from the `entry` of `source` (e.g., a file of a zip archive),
+/// as `derived_file`: the entry name is part of the source hash, and the file keeps the
+/// file name of the entry.
+pub fn derived_entry_file(
+    kind: &str,
+    source: &Path,
+    entry: &str,
+    write: impl FnOnce(&mut dyn Write) -> PolarsViewResult<()>,
+) -> PolarsViewResult<PathBuf> {
+    derive(kind, source, Some(entry), write)
+}
+
+/// See `derived_file` and `derived_entry_file`.
+fn derive(
+    kind: &str,
+    source: &Path,
+    entry: Option<&str>,
+    write: impl FnOnce(&mut dyn Write) -> PolarsViewResult<()>,
+) -> PolarsViewResult<PathBuf> {
+    let dir = private_temp_dir()?;
+    let (prefix, name) = derived_names(kind, source, entry)?;
+    let path = dir.join(&name);
+    if path.is_file() {
+        return Ok(path);
+    }
+
+    for dir_entry in fs::read_dir(&dir)?.flatten() {
+        let entry_name = dir_entry.file_name().to_string_lossy().into_owned();
+        if entry_name.starts_with(&prefix) && entry_name != name {
+            tracing::debug!("Removing an outdated temporary file: '{entry_name}'");
+            let _ = fs::remove_file(dir_entry.path());
+        }
+    }
+
+    write_atomically(&path, write)?;
+    tracing::debug!(
+        "'{}' derived from '{}' ({kind})",
+        path.display(),
+        source.display()
+    );
+    Ok(path)
+}
+
+/// Removes the derived files (see `derived_file`) of the private folder unused for
+/// `DERIVED_FILE_MAX_AGE`, then the least recently used ones beyond
+/// `DERIVED_FILES_MAX_BYTES`, so the folder does not grow without bound across runs
+/// (errors are logged). Called once at startup, on a background thread.
+///
+/// A removed file is derived again when its source is opened.
+pub fn prune_derived_files() {
+    let result = private_temp_dir().and_then(|dir| {
+        let now = SystemTime::now();
+        let removed = prune_dir(&dir, now, DERIVED_FILE_MAX_AGE, DERIVED_FILES_MAX_BYTES)?;
+        Ok(removed)
+    });
+    match result {
+        Ok(0) => {}
+        Ok(removed) => tracing::debug!("Removed {removed} unused temporary files."),
+        Err(error) => tracing::warn!("Failed to remove the unused temporary files: {error}"),
+    }
+}
+
+/// See `prune_derived_files`. The last use of a file is its access or modification time,
+/// whichever is later. Returns the number of removed files.
+fn prune_dir(dir: &Path, now: SystemTime, max_age: Duration, max_bytes: u64) -> io::Result<usize> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(dir)?.flatten() {
+        // The files of `new_temp_file` are removed by their owner, and `.tmp*` files are
+        // being written (see `write_atomically`).
+        let name = entry.file_name().to_string_lossy().into_owned();
+        if name.starts_with(TEMP_FILE_PREFIX) || name.starts_with('.') {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_file() {
+            let used = metadata.accessed().ok().max(metadata.modified().ok());
+            files.push((used.unwrap_or(now), metadata.len(), entry.path()));
+        }
+    }
+
+    // Most recently used first.
+    files.sort_by_key(|(used, ..)| std::cmp::Reverse(*used));
+
+    let (mut kept_bytes, mut removed) = (0, 0);
+    for (used, len, path) in files {
+        let age = now.duration_since(used).unwrap_or_default();
+        if age <= max_age && kept_bytes + len <= max_bytes {
+            kept_bytes += len;
+            continue;
+        }
+        tracing::debug!("Removing an unused temporary file: '{}'", path.display());
+        if fs::remove_file(&path).is_ok() {
+            removed += 1;
+        }
+    }
+    Ok(removed)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_temp_files`
+#[cfg(test)]
+mod tests_temp_files {
+    use super::*;
+
+    #[test]
+    fn derived_files_are_keyed_by_source() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let (a, b) = (dir.path().join("a"), dir.path().join("b"));
+        fs::create_dir_all(&a)?;
+        fs::create_dir_all(&b)?;
+        let (source_a, source_b) = (a.join("data.csv"), b.join("data.csv"));
+        fs::write(&source_a, "from a")?;
+        fs::write(&source_b, "from b")?;
+
+        let upper = |source: &Path| {
+            let text = fs::read_to_string(source)?.to_uppercase();
+            derived_file("test-upper", source, |writer| {
+                Ok(writer.write_all(text.as_bytes())?)
+            })
+        };
+
+        // Same file name, other folder: another derived file.
+        let (copy_a, copy_b) = (upper(&source_a)?, upper(&source_b)?);
+        assert_ne!(copy_a, copy_b);
+        assert_eq!(fs::read_to_string(&copy_a)?, "FROM A");
+        assert_eq!(fs::read_to_string(&copy_b)?, "FROM B");
+
+        // A new version of the source: a new derived file, the old one is removed.
+        fs::write(&source_a, "from a, longer")?;
+        let new_copy_a = upper(&source_a)?;
+        assert_ne!(new_copy_a, copy_a);
+        assert!(!copy_a.exists());
+        assert_eq!(fs::read_to_string(&new_copy_a)?, "FROM A, LONGER");
+
+        // A failed write leaves the target unchanged.
+        let target = dir.path().join("target.txt");
+        fs::write(&target, "kept")?;
+        let failed = write_atomically(&target, |writer| {
+            writer.write_all(b"partial")?;
+            Err(PolarsViewError::Other("interrupted".to_string()))
+        });
+        assert!(failed.is_err());
+        assert_eq!(fs::read_to_string(&target)?, "kept");
+
+        for path in [new_copy_a, copy_b] {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn saving_keeps_the_permissions_and_the_link() -> PolarsViewResult<()> {
+        use std::os::unix::fs::{PermissionsExt, symlink};
+
+        let dir = tempfile::tempdir()?;
+        let target = dir.path().join("data.csv");
+        fs::write(&target, "old")?;
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o644))?;
+        let link = dir.path().join("link.csv");
+        symlink(&target, &link)?;
+
+        write_atomically(&target, |writer| Ok(writer.write_all(b"new")?))?;
+        assert_eq!(fs::metadata(&target)?.permissions().mode() & 0o777, 0o644);
+        assert_eq!(fs::read_to_string(&target)?, "new");
+
+        // Saving through the link replaces the file it points to.
+        write_atomically(&link, |writer| Ok(writer.write_all(b"linked")?))?;
+        assert!(fs::symlink_metadata(&link)?.file_type().is_symlink());
+        assert_eq!(fs::read_to_string(&target)?, "linked");
+        assert_eq!(fs::metadata(&target)?.permissions().mode() & 0o777, 0o644);
+        Ok(())
+    }
+
+    #[test]
+    fn unused_derived_files_are_pruned() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+
+        for (name, size, days) in [
+            ("zip-0-1-old.csv", 10, 30),
+            ("zip-0-2-big.csv", 100, 2),
+            ("utf8-0-3-new.csv", 10, 1),
+            ("polars-view-stdin.csv", 10, 30),
+        ] {
+            let file = fs::File::create(dir.path().join(name))?;
+            file.set_len(size)?;
+            let used = now - day * days;
+            file.set_times(fs::FileTimes::new().set_accessed(used).set_modified(used))?;
+        }
+
+        // Older than a week, then beyond 50 bytes; the stdin copy is not a derived file.
+        assert_eq!(prune_dir(dir.path(), now, day * 7, 50)?, 2);
+
+        let mut names: Vec<String> = fs::read_dir(dir.path())?
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        assert_eq!(names, ["polars-view-stdin.csv", "utf8-0-3-new.csv"]);
+        Ok(())
+    }
+}
@@ -4,18 +4,24 @@
 //! and defines interfaces (`Notification`, `SortableHeaderRenderer`) for common UI patterns.
 //! It interacts primarily with `layout.rs` (for styling, notifications) and `container.rs` (for header rendering).
 
-use crate::HeaderSortState; // Use the interaction enum for UI state
+use crate::{AppSettings, HeaderSortState, open_with_system}; // Use the interaction enum for UI state
 
 use egui::{
-    Color32, Context,
+    Align2, Color32, Context,
     FontFamily::Proportional,
     FontId, Frame, Response, RichText, Sense, Spacing, Stroke, Style,
     TextStyle::{self, Body, Button, Heading, Monospace, Small},
-    Ui, Vec2, Visuals, Window,
+    Ui, Vec2, Visuals, WidgetInfo, WidgetType, Window,
     style::ScrollStyle,
 };
 use polars::prelude::*;
-use std::{collections::HashSet, ffi::OsStr, hash::Hash, path::Path};
+use std::{
+    collections::HashSet,
+    ffi::OsStr,
+    hash::Hash,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
 
 /// Defines custom text styles for the egui context.
 /// Overrides default `egui` font sizes for different logical text styles (Heading, Body, etc.).
@@ -81,17 +87,32 @@ pub trait Notification: Send + Sync + 'static {
     fn show(&mut self, ctx: &Context) -> bool;
 }
 
-/// Placeholder Notification struct for future Settings dialog. Implements `Notification`.
-pub struct Settings {}
+/// Notification struct for the Settings window. Implements `Notification`.
+pub struct Settings {
+    /// The settings being edited, saved on change.
+    pub settings: AppSettings,
+    /// Result of the last save, if it failed.
+    pub error: Option<String>,
+}
+
+impl Settings {
+    /// Opens the window with the settings saved under the user config directory.
+    pub fn from_config_dir() -> Self {
+        Settings {
+            settings: AppSettings::from_config_dir(),
+            error: None,
+        }
+    }
+}
 
 impl Notification for Settings {
-    /// Renders the placeholder Settings window.
+    /// Renders the Settings window.
     ///
     /// ### Logic
     /// 1. Define `open` state (initially `true`).
     /// 2. Create `egui::Window` bound to `open`.
     /// 3. Configure window (e.g., non-collapsible).
-    /// 4. Define content (currently disabled).
+    /// 4. Define content: toggles saved as soon as they change.
     /// 5. Return the `open` state (whether the window is still visible).
     fn show(&mut self, ctx: &Context) -> bool {
         let mut open = true; // 1. Window starts open.
@@ -101,8 +122,34 @@ impl Notification for Settings {
             .collapsible(false) // 3. Configure.
             .open(&mut open)
             .show(ctx, |ui| {
-                ctx.style_ui(ui, egui::Theme::Dark);
-                ui.disable(); // 4. Placeholder content.
+                // 4. Content.
+                let response = ui
+                    .checkbox(
+                        &mut self.settings.restore_session,
+                        "Restore last session on startup",
+                    )
+                    .on_hover_text(
+                        "Reopen the last file with its load options, SQL query, sorting \
+                        and format when no file is given (same as --restore-session).",
+                    );
+                let mut simplify_slow_tables = !self.settings.disable_frame_watchdog;
+                let watchdog_response = ui
+                    .checkbox(
+                        &mut simplify_slow_tables,
+                        "Simplify the table when rendering is slow",
+                    )
+                    .on_hover_text(
+                        "Switch to fixed column widths, then show fewer columns, when frames \
+                        take too long (e.g., hundreds of columns), so the UI doesn't freeze.",
+                    );
+                self.settings.disable_frame_watchdog = !simplify_slow_tables;
+
+                if response.changed() || watchdog_response.changed() {
+                    self.error = self.settings.save().err().map(|error| error.to_string());
+                }
+                if let Some(error) = &self.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
             });
 
         open // 5. Return state.
@@ -143,6 +190,145 @@ impl Notification for Error {
     }
 }
 
+/// Confirmation dialog shown before "Save" overwrites the source file
+/// (read-only protection enabled). Implements `Notification`.
+pub struct SaveConfirmation {
+    /// The file that would be overwritten.
+    pub path: PathBuf,
+    /// The operations that would be baked into the file (see `DataContainer::applied_operations`).
+    pub operations: Vec<String>,
+    /// Action run once if the user confirms. Set by the caller in `layout.rs`.
+    pub on_confirm: Option<Box<dyn FnOnce() + Send + Sync>>,
+}
+
+impl Notification for SaveConfirmation {
+    /// Renders the confirmation window.
+    ///
+    /// ### Logic
+    /// 1. List the path and the operations that would be written.
+    /// 2. "Overwrite" runs `on_confirm` and closes; "Cancel" (or the close button) just closes.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut open = true; // Window starts open.
+        let mut close = false;
+
+        Window::new("Overwrite Source File?")
+            .collapsible(false)
+            .resizable(true)
+            .min_width(500.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                ui.label("Read-only protection is enabled. Saving will overwrite:");
+                ui.monospace(self.path.display().to_string());
+                ui.separator();
+
+                if self.operations.is_empty() {
+                    ui.label("No operations applied: the data is written as loaded.");
+                } else {
+                    ui.label(
+                        RichText::new("The following operations will be baked into the file:")
+                            .color(Color32::from_rgb(230, 160, 40)),
+                    );
+                    for operation in &self.operations {
+                        ui.label(format!("• {operation}"));
+                    }
+                }
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    if ui.button("Overwrite").clicked() {
+                        if let Some(on_confirm) = self.on_confirm.take() {
+                            on_confirm();
+                        }
+                        close = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        close = true;
+                    }
+                });
+            });
+
+        open && !close
+    }
+}
+
+/// Toast shown (bottom-right) after a file is saved, with shortcuts to open the
+/// containing folder or the file itself. Implements `Notification`.
+///
+/// Closes automatically after `SavedFile::TIMEOUT` unless the pointer is over it.
+pub struct SavedFile {
+    /// The written file.
+    pub path: PathBuf,
+    /// When the toast was created.
+    pub created: Instant,
+    /// Error from the last "Open folder"/"Open file" attempt, if any.
+    pub open_error: Option<String>,
+}
+
+impl SavedFile {
+    /// How long the toast stays visible.
+    pub const TIMEOUT: Duration = Duration::from_secs(8);
+
+    pub fn new(path: PathBuf) -> Self {
+        SavedFile {
+            path,
+            created: Instant::now(),
+            open_error: None,
+        }
+    }
+}
+
+impl Notification for SavedFile {
+    /// Renders the toast; returns `false` once it times out or is dismissed.
+    fn show(&mut self, ctx: &Context) -> bool {
+        let mut close = false;
+
+        let response = Window::new("Saved")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .anchor(Align2::RIGHT_BOTTOM, [-12.0, -36.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new("✔ Saved").color(Color32::from_rgb(80, 180, 80)));
+                    ui.monospace(self.path.display().to_string());
+                });
+
+                ui.horizontal(|ui| {
+                    let folder = self.path.parent().map(Path::to_path_buf);
+                    if let Some(folder) = folder
+                        && ui.button("Open folder").clicked()
+                    {
+                        self.open_error = open_with_system(&folder).err().map(|e| e.to_string());
+                    }
+                    if ui.button("Open file").clicked() {
+                        self.open_error = open_with_system(&self.path).err().map(|e| e.to_string());
+                    }
+                    if ui.button("Dismiss").clicked() {
+                        close = true;
+                    }
+                });
+
+                if let Some(error) = &self.open_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+
+        // Keep the toast while hovered (or showing an error); otherwise close after the timeout.
+        let hovered = response.is_some_and(|inner| inner.response.contains_pointer());
+        if hovered || self.open_error.is_some() {
+            self.created = Instant::now();
+        }
+
+        let elapsed = self.created.elapsed();
+        if elapsed >= Self::TIMEOUT {
+            return false;
+        }
+        ctx.request_repaint_after(Self::TIMEOUT - elapsed);
+
+        !close
+    }
+}
+
 /// Trait defining a widget for rendering a sortable table header cell.
 /// Provides a consistent interface for `container.rs::render_table_header`.
 pub trait SortableHeaderRenderer {
@@ -201,14 +387,20 @@ impl SortableHeaderRenderer for Ui {
         let outer_response = self.horizontal_centered(|ui| {
             ui.style_mut().override_text_style = Some(text_style.clone());
             let msg1 = format!("Click to sort by: {column_name:#?}");
-            let msg2 = "↕ Not Sorted";
-            let msg3 = "Sort with Nulls First:";
-            let msg4 = "    ⏷ Sort in Descending order";
-            let msg5 = "    ⏶ Sort in Ascending order";
-            let msg6 = "Sort with Nulls Last:";
-            let msg7 = "    ⬇ Sort in Descending order";
-            let msg8 = "    ⬆ Sort in Ascending order";
-            let msg = [&msg1, "", msg2, msg3, msg4, msg5, msg6, msg7, msg8].join("\n");
+            let msg2 = "Click: Ascending -> Descending -> Not Sorted (clears other columns)";
+            let msg3 = "Shift + Click: same cycle, added to the multi-column sort";
+            let msg4 = "Alt + Click: toggle Nulls First / Nulls Last";
+            let msg5 = "↕ Not Sorted";
+            let msg6 = "Sort with Nulls First:";
+            let msg7 = "    ⏷ Sort in Descending order";
+            let msg8 = "    ⏶ Sort in Ascending order";
+            let msg9 = "Sort with Nulls Last:";
+            let msg10 = "    ⬇ Sort in Descending order";
+            let msg11 = "    ⬆ Sort in Ascending order";
+            let msg = [
+                &msg1, "", msg2, msg3, msg4, "", msg5, msg6, msg7, msg8, msg9, msg10, msg11,
+            ]
+            .join("\n");
 
             // 4. Add sized container and draw the icon/index string inside.
             let icon_response = ui
@@ -222,6 +414,10 @@ impl SortableHeaderRenderer for Ui {
                 // 5. Add hover text to the response from the sized container (which is the Label's response).
                 .on_hover_text(msg);
 
+            // Screen readers announce the sort state instead of the icon glyph.
+            let label = interaction_state.accessible_label(column_name, sort_index);
+            icon_response.widget_info(|| WidgetInfo::labeled(WidgetType::Button, true, &label));
+
             // 6. Add column name label.
             ui.add(if use_enhanced_style {
                 // Enhanced: Use color and enable text wrapping.
@@ -0,0 +1,195 @@
+//! Column units: an annotation per column (kg, km, BRL, %, ...) shown in the table header,
+//! and, for the units of `UNITS`, the conversion to a compatible unit (e.g., g → kg)
+//! applied to the displayed rows (see `convert_units`), so exports get the converted values.
+//!
+//! Configured in the "Format" side panel section (see `DataFormat::column_units`).
+
+use crate::PolarsViewResult;
+
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+/// A unit of `UNITS`: its symbol, its dimension and its value in the base unit of
+/// the dimension (e.g., `km` is 1000 `m`).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UnitDefinition {
+    pub symbol: &'static str,
+    pub dimension: &'static str,
+    pub factor: f64,
+}
+
+const fn unit(symbol: &'static str, dimension: &'static str, factor: f64) -> UnitDefinition {
+    UnitDefinition {
+        symbol,
+        dimension,
+        factor,
+    }
+}
+
+/// The convertible units, grouped by dimension. Other units (e.g., currencies) are
+/// annotations only.
+pub static UNITS: &[UnitDefinition] = &[
+    unit("mg", "mass", 1e-6),
+    unit("g", "mass", 1e-3),
+    unit("kg", "mass", 1.0),
+    unit("t", "mass", 1e3),
+    unit("oz", "mass", 0.028_349_523_125),
+    unit("lb", "mass", 0.453_592_37),
+    unit("mm", "length", 1e-3),
+    unit("cm", "length", 1e-2),
+    unit("m", "length", 1.0),
+    unit("km", "length", 1e3),
+    unit("in", "length", 0.0254),
+    unit("ft", "length", 0.3048),
+    unit("mi", "length", 1_609.344),
+    unit("mL", "volume", 1e-3),
+    unit("L", "volume", 1.0),
+    unit("m³", "volume", 1e3),
+    unit("ms", "time", 1e-3),
+    unit("s", "time", 1.0),
+    unit("min", "time", 60.0),
+    unit("h", "time", 3_600.0),
+    unit("d", "time", 86_400.0),
+    unit("B", "data", 1.0),
+    unit("KB", "data", 1e3),
+    unit("MB", "data", 1e6),
+    unit("GB", "data", 1e9),
+    unit("fraction", "ratio", 1.0),
+    unit("%", "ratio", 1e-2),
+    unit("‰", "ratio", 1e-3),
+];
+
+/// The unit of `UNITS` with the symbol `symbol` (exact match first, then ignoring case).
+pub fn find_unit(symbol: &str) -> Option<&'static UnitDefinition> {
+    let symbol = symbol.trim();
+    UNITS.iter().find(|unit| unit.symbol == symbol).or_else(|| {
+        UNITS
+            .iter()
+            .find(|unit| unit.symbol.eq_ignore_ascii_case(symbol))
+    })
+}
+
+/// The units `symbol` can be converted to (itself included), or none if it is unknown.
+pub fn compatible_units(symbol: &str) -> Vec<&'static UnitDefinition> {
+    match find_unit(symbol) {
+        Some(from) => UNITS
+            .iter()
+            .filter(|unit| unit.dimension == from.dimension)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// The factor converting values in `from` to `to`, if both are units of the same dimension.
+pub fn conversion_factor(from: &str, to: &str) -> Option<f64> {
+    let (from, to) = (find_unit(from)?, find_unit(to)?);
+    (from.dimension == to.dimension).then(|| from.factor / to.factor)
+}
+
+/// The unit of a column, and the unit its values are shown in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColumnUnit {
+    /// Unit of the data (free text, e.g., `kg` or `BRL`).
+    pub unit: String,
+    /// Unit the values are converted to for display/export (`None`: `unit`).
+    pub display_unit: Option<String>,
+}
+
+impl ColumnUnit {
+    /// The unit of the displayed values.
+    pub fn shown(&self) -> &str {
+        self.display_unit.as_deref().unwrap_or(&self.unit)
+    }
+
+    /// The conversion factor to the display unit, if the values are converted.
+    pub fn factor(&self) -> Option<f64> {
+        let display_unit = self.display_unit.as_deref()?;
+        conversion_factor(&self.unit, display_unit).filter(|factor| *factor != 1.0)
+    }
+}
+
+/// The conversion factors of `units` (columns whose values are converted), by column name.
+pub fn unit_conversions(units: &HashMap<String, ColumnUnit>) -> BTreeMap<&str, f64> {
+    units
+        .iter()
+        .filter_map(|(name, unit)| Some((name.as_str(), unit.factor()?)))
+        .collect()
+}
+
+/// Converts the numeric columns of `df` to their display units (see `ColumnUnit`), as
+/// Float64 values. Columns absent from `df` or not numeric are left unchanged.
+pub fn convert_units(
+    df: &DataFrame,
+    units: &HashMap<String, ColumnUnit>,
+) -> PolarsViewResult<DataFrame> {
+    let schema = df.schema();
+    let exprs: Vec<Expr> = unit_conversions(units)
+        .into_iter()
+        .filter(|(name, _factor)| {
+            schema
+                .get(name)
+                .is_some_and(|dtype| dtype.is_primitive_numeric() || dtype.is_decimal())
+        })
+        .map(|(name, factor)| (col(name).cast(DataType::Float64) * lit(factor)).alias(name))
+        .collect();
+
+    if exprs.is_empty() {
+        return Ok(df.clone());
+    }
+    Ok(df.clone().lazy().with_columns(exprs).collect()?)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_units`
+#[cfg(test)]
+mod tests_units {
+    use super::*;
+
+    #[test]
+    fn conversion_factors() {
+        assert_eq!(conversion_factor("g", "kg"), Some(0.001));
+        assert_eq!(conversion_factor("KM", "m"), Some(1000.0));
+        assert_eq!(conversion_factor("kg", "km"), None);
+        assert_eq!(conversion_factor("BRL", "USD"), None);
+        assert!(compatible_units("BRL").is_empty());
+        assert_eq!(compatible_units("%").len(), 3);
+    }
+
+    #[test]
+    fn columns_converted_to_display_units() -> PolarsViewResult<()> {
+        let df = df!(
+            "weight" => &[1500, 250],
+            "price" => &[10.0, 20.0]
+        )?;
+        let units = HashMap::from([
+            (
+                "weight".to_string(),
+                ColumnUnit {
+                    unit: "g".to_string(),
+                    display_unit: Some("kg".to_string()),
+                },
+            ),
+            (
+                "price".to_string(),
+                ColumnUnit {
+                    unit: "BRL".to_string(),
+                    display_unit: None,
+                },
+            ),
+        ]);
+
+        let result = convert_units(&df, &units)?;
+        dbg!(&result);
+
+        let weights: Vec<Option<f64>> = result.column("weight")?.f64()?.into_iter().collect();
+        assert_eq!(weights, [Some(1.5), Some(0.25)]);
+        assert_eq!(result.column("price")?, df.column("price")?);
+        Ok(())
+    }
+}
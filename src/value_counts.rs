@@ -0,0 +1,373 @@
+//! "Group by this column" window (header context menu): the value counts of a column,
+//! optionally with aggregations of other columns per value (sum, mean, ...), shown as a
+//! secondary table. Computed with a lazy group-by off the UI thread
+//! (see `execute_polars_blocking`).
+
+use crate::{PolarsViewResult, execute_polars_blocking};
+
+use egui::{ComboBox, Context, RichText, Ui, Window};
+use egui_extras::{Column as TableColumn, TableBuilder};
+use polars::prelude::*;
+use std::sync::Arc;
+use tokio::{
+    runtime::Handle,
+    sync::oneshot::{self, error::TryRecvError},
+};
+
+/// Name of the row count column of the value counts.
+pub const VALUE_COUNT_COLUMN: &str = "count";
+
+/// Aggregation of another column per value of the grouped column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupAggregation {
+    #[default]
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Number of distinct values.
+    Distinct,
+}
+
+impl GroupAggregation {
+    pub const ALL: [GroupAggregation; 5] = [
+        GroupAggregation::Sum,
+        GroupAggregation::Mean,
+        GroupAggregation::Min,
+        GroupAggregation::Max,
+        GroupAggregation::Distinct,
+    ];
+
+    /// Name of the aggregation, also the prefix of its result column (e.g., `sum_price`).
+    pub fn label(&self) -> &'static str {
+        match self {
+            GroupAggregation::Sum => "sum",
+            GroupAggregation::Mean => "mean",
+            GroupAggregation::Min => "min",
+            GroupAggregation::Max => "max",
+            GroupAggregation::Distinct => "distinct",
+        }
+    }
+
+    /// Whether the aggregation applies to a column of type `dtype`.
+    pub fn applies_to(&self, dtype: &DataType) -> bool {
+        let is_numeric = dtype.is_primitive_numeric() || dtype.is_decimal();
+        match self {
+            GroupAggregation::Sum | GroupAggregation::Mean => is_numeric,
+            GroupAggregation::Min | GroupAggregation::Max => {
+                is_numeric || dtype.is_temporal() || dtype.is_string()
+            }
+            GroupAggregation::Distinct => !dtype.is_nested(),
+        }
+    }
+
+    /// The aggregation of the column `name`, named `<label>_<name>`.
+    fn expr(&self, name: &str) -> Expr {
+        let values = col(name);
+        let expr = match self {
+            GroupAggregation::Sum => values.sum(),
+            GroupAggregation::Mean => values.mean(),
+            GroupAggregation::Min => values.min(),
+            GroupAggregation::Max => values.max(),
+            GroupAggregation::Distinct => values.n_unique().cast(DataType::UInt64),
+        };
+        expr.alias(format!("{}_{name}", self.label()))
+    }
+}
+
+/// Groups `df` by the column `name`: one row per value (nulls included) with its row
+/// count (`VALUE_COUNT_COLUMN`, or `row_count` if the column itself is named so) and
+/// the `aggregations`, most frequent values first.
+pub fn group_by_column(
+    df: &DataFrame,
+    name: &str,
+    aggregations: &[(String, GroupAggregation)],
+) -> PolarsResult<DataFrame> {
+    let count = match name {
+        VALUE_COUNT_COLUMN => "row_count",
+        _ => VALUE_COUNT_COLUMN,
+    };
+    let mut exprs = vec![len().cast(DataType::UInt64).alias(count)];
+    exprs.extend(
+        aggregations
+            .iter()
+            .map(|(column, aggregation)| aggregation.expr(column)),
+    );
+
+    df.clone()
+        .lazy()
+        .group_by([col(name)])
+        .agg(exprs)
+        .sort_by_exprs(
+            [col(count), col(name)],
+            SortMultipleOptions::default()
+                .with_order_descending_multi([true, false])
+                .with_nulls_last(true),
+        )
+        .collect()
+}
+
+/// Parameters of a computation (a change triggers a new one).
+#[derive(Debug, Clone, PartialEq)]
+struct GroupByRequest {
+    column: String,
+    aggregations: Vec<(String, GroupAggregation)>,
+    /// Address of the `DataFrame`.
+    df: usize,
+}
+
+/// State of the "Group by" window: the grouped column, the aggregations and the
+/// result of the last (background) computation.
+#[derive(Debug, Default)]
+pub struct ValueCountsView {
+    /// Whether the window is shown.
+    pub open: bool,
+    /// The grouped column.
+    pub column: String,
+    /// Aggregations of other columns (column, aggregation), in result order.
+    pub aggregations: Vec<(String, GroupAggregation)>,
+    /// Aggregation being added (column, aggregation).
+    new_aggregation: (String, GroupAggregation),
+    result: Option<DataFrame>,
+    error: Option<String>,
+    requested: Option<GroupByRequest>,
+    pending: Option<oneshot::Receiver<PolarsViewResult<DataFrame>>>,
+}
+
+impl ValueCountsView {
+    /// Opens the window on the column `column` (header context menu).
+    pub fn open_for(&mut self, column: &str) {
+        if self.column != column {
+            self.aggregations.clear();
+        }
+        self.open = true;
+        self.column = column.to_string();
+    }
+
+    /// Whether a computation is still running (receives its result if it arrived).
+    ///
+    /// Used for the busy indicator of the tabs, including inactive ones.
+    pub fn is_pending(&mut self) -> bool {
+        self.poll();
+        self.pending.is_some()
+    }
+
+    /// Receives the result of a pending computation, if available.
+    fn poll(&mut self) {
+        if let Some(rx) = &mut self.pending {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.pending = None;
+                    match result {
+                        Ok(df) => self.result = Some(df),
+                        Err(error) => self.error = Some(error.to_string()),
+                    }
+                }
+                Err(TryRecvError::Empty) => {}
+                Err(TryRecvError::Closed) => {
+                    self.pending = None;
+                    self.error = Some("group-by computation ended unexpectedly".to_string());
+                }
+            }
+        }
+    }
+
+    /// Starts `group_by_column` on `runtime` (via `execute_polars_blocking`).
+    /// A computation still pending is abandoned (its result is dropped).
+    fn spawn_compute(&mut self, df: Arc<DataFrame>, runtime: &Handle, ctx: &Context) {
+        let (tx, rx) = oneshot::channel();
+        let (column, aggregations) = (self.column.clone(), self.aggregations.clone());
+        let ctx = ctx.clone();
+
+        runtime.spawn(async move {
+            let result =
+                execute_polars_blocking(move || group_by_column(&df, &column, &aggregations)).await;
+            if tx.send(result).is_err() {
+                tracing::debug!("Group-by receiver dropped.");
+            }
+            ctx.request_repaint();
+        });
+
+        self.result = None;
+        self.error = None;
+        self.pending = Some(rx);
+    }
+
+    /// Shows the window (if open) for the displayed `df`.
+    ///
+    /// The group-by is recomputed on `runtime` when the column, the aggregations or `df` change.
+    pub fn show(&mut self, ctx: &Context, df: &Arc<DataFrame>, runtime: &Handle) {
+        self.poll();
+
+        let schema = df.schema();
+        if !self.open || !schema.contains(&self.column) {
+            return;
+        }
+
+        let request = GroupByRequest {
+            column: self.column.clone(),
+            aggregations: self.aggregations.clone(),
+            df: Arc::as_ptr(df) as usize,
+        };
+        if self.requested.as_ref() != Some(&request) {
+            self.spawn_compute(df.clone(), runtime, ctx);
+            self.requested = Some(request);
+        }
+
+        let mut open = self.open;
+        Window::new(format!("Group by {}", self.column))
+            .id(egui::Id::new("value_counts_window"))
+            .open(&mut open)
+            .default_width(420.0)
+            .default_height(400.0)
+            .resizable(true)
+            .show(ctx, |ui| {
+                self.render_aggregations(ui, schema);
+                ui.separator();
+
+                if self.pending.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Grouping...");
+                    });
+                } else if let Some(error) = &self.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                } else if let Some(result) = &self.result {
+                    ui.label(format!("{} distinct values", result.height()));
+                    render_result(ui, result);
+                }
+            });
+        self.open = open;
+    }
+
+    /// Renders the list of aggregations (with remove buttons) and the "Add" controls.
+    fn render_aggregations(&mut self, ui: &mut Ui, schema: &Schema) {
+        let mut remove = None;
+        for (index, (column, aggregation)) in self.aggregations.iter().enumerate() {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}({column})", aggregation.label()));
+                if ui.small_button("✖").on_hover_text("Remove").clicked() {
+                    remove = Some(index);
+                }
+            });
+        }
+        if let Some(index) = remove {
+            self.aggregations.remove(index);
+        }
+
+        let columns: Vec<&PlSmallStr> = schema
+            .iter()
+            .filter(|(name, dtype)| name.as_str() != self.column && !dtype.is_nested())
+            .map(|(name, _dtype)| name)
+            .collect();
+        let Some(first) = columns.first() else {
+            return;
+        };
+        if !schema.contains(&self.new_aggregation.0) || self.new_aggregation.0 == self.column {
+            self.new_aggregation.0 = first.to_string();
+        }
+
+        ui.horizontal(|ui| {
+            let (column, aggregation) = &mut self.new_aggregation;
+            ComboBox::from_id_salt("value_counts_aggregation")
+                .selected_text(aggregation.label())
+                .width(80.0)
+                .show_ui(ui, |ui| {
+                    for value in GroupAggregation::ALL {
+                        ui.selectable_value(aggregation, value, value.label());
+                    }
+                });
+            ComboBox::from_id_salt("value_counts_column")
+                .selected_text(column.as_str())
+                .show_ui(ui, |ui| {
+                    for name in &columns {
+                        ui.selectable_value(column, name.to_string(), name.as_str());
+                    }
+                });
+
+            let applies = schema
+                .get(column.as_str())
+                .is_some_and(|dtype| aggregation.applies_to(dtype));
+            let entry = (column.clone(), *aggregation);
+            if ui
+                .add_enabled(
+                    applies && !self.aggregations.contains(&entry),
+                    egui::Button::new("Add"),
+                )
+                .on_disabled_hover_text("Not applicable to this column type, or already added.")
+                .clicked()
+            {
+                self.aggregations.push(entry);
+            }
+        });
+    }
+}
+
+/// Shows the group-by result as a striped table (virtual scrolling).
+fn render_result(ui: &mut Ui, df: &DataFrame) {
+    let text_height = egui::TextStyle::Body.resolve(ui.style()).size;
+
+    TableBuilder::new(ui)
+        .striped(true)
+        .resizable(true)
+        .columns(TableColumn::auto().at_least(60.0).clip(true), df.width())
+        .header(text_height + 6.0, |mut header| {
+            for name in df.get_column_names() {
+                header.col(|ui| {
+                    ui.label(RichText::new(name.as_str()).strong());
+                });
+            }
+        })
+        .body(|body| {
+            body.rows(text_height + 4.0, df.height(), |mut row| {
+                let index = row.index();
+                for column in df.columns() {
+                    let text = match column.get(index) {
+                        Ok(AnyValue::Null) | Err(_) => "null".to_string(),
+                        Ok(value) => value
+                            .get_str()
+                            .map(str::to_string)
+                            .unwrap_or_else(|| value.to_string()),
+                    };
+                    row.col(|ui| {
+                        ui.label(text);
+                    });
+                }
+            });
+        });
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_value_counts`
+#[cfg(test)]
+mod tests_value_counts {
+    use super::*;
+
+    #[test]
+    fn counts_and_aggregations_by_value() -> PolarsResult<()> {
+        let df = df!(
+            "city" => &[Some("b"), Some("a"), Some("b"), None, Some("b")],
+            "price" => &[1.0, 2.0, 3.0, 4.0, 5.0]
+        )?;
+
+        let result = group_by_column(&df, "city", &[("price".to_string(), GroupAggregation::Sum)])?;
+        dbg!(&result);
+
+        let cities: Vec<Option<&str>> = result.column("city")?.str()?.into_iter().collect();
+        assert_eq!(cities, [Some("b"), Some("a"), None]);
+
+        let counts: Vec<Option<u64>> = result
+            .column(VALUE_COUNT_COLUMN)?
+            .u64()?
+            .into_iter()
+            .collect();
+        assert_eq!(counts, [Some(3), Some(1), Some(1)]);
+
+        assert_eq!(result.column("sum_price")?.f64()?.get(0), Some(9.0));
+        Ok(())
+    }
+}
@@ -0,0 +1,434 @@
+use crate::{
+    ColumnFilters, ColumnType, ColumnUnit, DataContainer, DataFilter, DataFormat, ListDisplay,
+    NamedQuery, PolarsViewResult, SortBy, SortExpression, TextEncoding, VirtualColumn,
+};
+
+use egui::Align;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::Path,
+};
+
+/// Current version of the `ViewState` JSON layout.
+pub const VIEW_STATE_VERSION: u32 = 1;
+
+/// A shareable "view" over a dataset: load/query settings, column filters, sorting and
+/// display format.
+///
+/// Exported/imported as JSON from the "File" menu ("Export View..." / "Import View...")
+/// or applied at startup with `--view <FILE>`, so colleagues can open the same dataset
+/// the same way. The data path itself is not part of the view.
+///
+/// Missing fields take their default values (`#[serde(default)]`), so view files stay
+/// readable when new fields are added.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewState {
+    /// Layout version (see `VIEW_STATE_VERSION`).
+    pub version: u32,
+
+    // --- Load / Query (see `DataFilter`) ---
+    pub table_name: String,
+    pub csv_delimiter: String,
+    pub csv_quote_char: String,
+    pub csv_escape_char: String,
+    pub csv_comment_prefix: String,
+    pub csv_skip_rows: usize,
+    pub csv_encoding: TextEncoding,
+    pub infer_schema_rows: usize,
+    pub null_values: String,
+    pub force_string_patterns: Option<String>,
+    pub exclude_null_cols: bool,
+    pub lazy_scan: bool,
+    /// Preview mode: rows read from the file (`None`: all).
+    pub head_rows: Option<usize>,
+    pub add_row_index: bool,
+    pub index_column_name: String,
+    pub index_column_offset: u32,
+    pub normalize: bool,
+    pub normalize_regex: String,
+    pub drop: bool,
+    pub drop_regex: String,
+    pub parse_dates: bool,
+    pub date_regex: String,
+    pub date_format: String,
+    pub date_time_zone: String,
+    /// Data type overrides, keyed by column name (see `column_types.rs`).
+    pub column_types: BTreeMap<String, ColumnType>,
+    /// New column names, keyed by column name as read (see `column_renames.rs`).
+    pub column_renames: BTreeMap<String, String>,
+    pub flatten_structs: bool,
+    pub categorical: bool,
+    pub categorical_ratio: f64,
+    pub query: String,
+    /// Named queries run before `query` (see `NamedQuery`).
+    pub query_steps: Vec<NamedQuery>,
+
+    // --- Filters ---
+    /// Text of the filter row inputs, keyed by column name (see `column_filter.rs`).
+    pub column_filters: ColumnFilters,
+
+    // --- Sorting ---
+    /// Header sort criteria, in precedence order.
+    pub sort: Vec<SortBy>,
+    /// Sort by SQL expression, applied before `sort`.
+    pub sort_expression: Option<SortExpression>,
+
+    // --- Display (see `DataFormat`) ---
+    /// Alignment per data type, keyed by the data type name (e.g., `f64`, `str`).
+    pub alignments: BTreeMap<String, Align>,
+    pub auto_col_width: bool,
+    pub decimal: usize,
+    /// Infers the decimal places of each Float column from the data.
+    pub auto_decimal: bool,
+    /// Decimal places of `Decimal` columns (`None`: each column's own scale).
+    pub decimal_scale: Option<usize>,
+    pub header_padding: f32,
+    pub use_enhanced_header: bool,
+    /// Alignment of single columns, keyed by column name (header context menu).
+    pub column_alignments: BTreeMap<String, Align>,
+    /// Locked column widths, keyed by column name (header context menu).
+    pub locked_widths: BTreeMap<String, f32>,
+    /// Display mode of List/Array columns, keyed by column name.
+    pub list_displays: BTreeMap<String, ListDisplay>,
+    /// Cell renderer id of each column (see `CELL_RENDERERS`).
+    pub cell_renderers: BTreeMap<String, String>,
+    /// Unit of each column and the unit its values are shown in.
+    pub column_units: BTreeMap<String, ColumnUnit>,
+    /// Computed columns added to the displayed rows.
+    pub virtual_columns: Vec<VirtualColumn>,
+    /// Names of the columns hidden from the table.
+    pub hidden_columns: BTreeSet<String>,
+    /// Names of the columns frozen on the left of the table.
+    pub frozen_columns: Vec<String>,
+    /// Display order of the columns (empty: the data order).
+    pub column_order: Vec<String>,
+    /// Shows the row number gutter on the left of the table.
+    pub show_row_numbers: bool,
+    /// Expands the hovered (or selected) row to its wrapped content.
+    pub expand_hovered_row: bool,
+    /// Shows the per-column filter row under the header.
+    pub show_filter_row: bool,
+    pub show_null_badges: bool,
+    /// Paged mode and rows per page.
+    pub paged: bool,
+    pub rows_per_page: usize,
+}
+
+impl Default for ViewState {
+    fn default() -> Self {
+        ViewState::capture(
+            &DataFilter::default(),
+            &DataFormat::default(),
+            &ColumnFilters::new(),
+            &[],
+            None,
+        )
+    }
+}
+
+impl ViewState {
+    /// Captures a view from its parts.
+    pub fn capture(
+        filter: &DataFilter,
+        format: &DataFormat,
+        column_filters: &ColumnFilters,
+        sort: &[SortBy],
+        sort_expression: Option<&SortExpression>,
+    ) -> Self {
+        ViewState {
+            version: VIEW_STATE_VERSION,
+
+            table_name: filter.table_name.clone(),
+            csv_delimiter: filter.csv_delimiter.clone(),
+            csv_quote_char: filter.csv_quote_char.clone(),
+            csv_escape_char: filter.csv_escape_char.clone(),
+            csv_comment_prefix: filter.csv_comment_prefix.clone(),
+            csv_skip_rows: filter.csv_skip_rows,
+            csv_encoding: filter.csv_encoding,
+            infer_schema_rows: filter.infer_schema_rows,
+            null_values: filter.null_values.clone(),
+            force_string_patterns: filter.force_string_patterns.clone(),
+            exclude_null_cols: filter.exclude_null_cols,
+            lazy_scan: filter.lazy_scan,
+            head_rows: filter.head_rows,
+            add_row_index: filter.add_row_index,
+            index_column_name: filter.index_column_name.clone(),
+            index_column_offset: filter.index_column_offset,
+            normalize: filter.normalize,
+            normalize_regex: filter.normalize_regex.clone(),
+            drop: filter.drop,
+            drop_regex: filter.drop_regex.clone(),
+            parse_dates: filter.parse_dates,
+            date_regex: filter.date_regex.clone(),
+            date_format: filter.date_format.clone(),
+            date_time_zone: filter.date_time_zone.clone(),
+            column_types: filter.column_types.clone(),
+            column_renames: filter.column_renames.clone(),
+            flatten_structs: filter.flatten_structs,
+            categorical: filter.categorical,
+            categorical_ratio: filter.categorical_ratio,
+            query: filter.query.clone(),
+            query_steps: filter.query_steps.clone(),
+
+            column_filters: column_filters.clone(),
+
+            sort: sort.to_vec(),
+            sort_expression: sort_expression.cloned(),
+
+            alignments: format
+                .alignments
+                .iter()
+                .map(|(dtype, align)| (dtype.to_string(), *align))
+                .collect(),
+            auto_col_width: format.auto_col_width,
+            decimal: format.decimal,
+            auto_decimal: format.auto_decimal,
+            decimal_scale: format.decimal_scale,
+            header_padding: format.header_padding,
+            use_enhanced_header: format.use_enhanced_header,
+            column_alignments: format
+                .column_alignments
+                .iter()
+                .map(|(name, align)| (name.clone(), *align))
+                .collect(),
+            locked_widths: format
+                .locked_widths
+                .iter()
+                .map(|(name, width)| (name.clone(), *width))
+                .collect(),
+            list_displays: format
+                .list_displays
+                .iter()
+                .map(|(name, display)| (name.clone(), *display))
+                .collect(),
+            cell_renderers: format
+                .cell_renderers
+                .iter()
+                .map(|(name, id)| (name.clone(), id.clone()))
+                .collect(),
+            column_units: format
+                .column_units
+                .iter()
+                .map(|(name, unit)| (name.clone(), unit.clone()))
+                .collect(),
+            virtual_columns: format.virtual_columns.clone(),
+            hidden_columns: format.hidden_columns.iter().cloned().collect(),
+            frozen_columns: format.frozen_columns.clone(),
+            column_order: format.column_order.clone(),
+            show_row_numbers: format.show_row_numbers,
+            expand_hovered_row: format.expand_hovered_row,
+            show_filter_row: format.show_filter_row,
+            show_null_badges: format.show_null_badges,
+            paged: format.paged,
+            rows_per_page: format.rows_per_page,
+        }
+    }
+
+    /// Captures the view currently displayed by `container`.
+    pub fn from_container(container: &DataContainer) -> Self {
+        ViewState::capture(
+            &container.filter,
+            &container.format,
+            &container.column_filters,
+            &container.sort,
+            container.sort_expression.as_ref(),
+        )
+    }
+
+    /// Copies the load/query settings into `filter`, flagging the SQL query to be
+    /// (re-)applied and the file to be re-read if read options changed.
+    pub fn apply_to_filter(&self, filter: &mut DataFilter) {
+        let read_options_changed = filter.csv_delimiter != self.csv_delimiter
+            || filter.csv_quote_char != self.csv_quote_char
+            || filter.csv_escape_char != self.csv_escape_char
+            || filter.csv_comment_prefix != self.csv_comment_prefix
+            || filter.csv_skip_rows != self.csv_skip_rows
+            || filter.csv_encoding != self.csv_encoding
+            || filter.infer_schema_rows != self.infer_schema_rows
+            || filter.null_values != self.null_values
+            || filter.force_string_patterns != self.force_string_patterns
+            || filter.lazy_scan != self.lazy_scan
+            || filter.head_rows != self.head_rows
+            || filter.column_types != self.column_types;
+
+        filter.table_name = self.table_name.clone();
+        filter.csv_delimiter = self.csv_delimiter.clone();
+        filter.csv_quote_char = self.csv_quote_char.clone();
+        filter.csv_escape_char = self.csv_escape_char.clone();
+        filter.csv_comment_prefix = self.csv_comment_prefix.clone();
+        filter.csv_skip_rows = self.csv_skip_rows;
+        filter.csv_encoding = self.csv_encoding;
+        filter.infer_schema_rows = self.infer_schema_rows;
+        filter.null_values = self.null_values.clone();
+        filter.force_string_patterns = self.force_string_patterns.clone();
+        filter.exclude_null_cols = self.exclude_null_cols;
+        filter.lazy_scan = self.lazy_scan;
+        filter.head_rows = self.head_rows;
+        filter.add_row_index = self.add_row_index;
+        filter.index_column_name = self.index_column_name.clone();
+        filter.index_column_offset = self.index_column_offset;
+        filter.normalize = self.normalize;
+        filter.normalize_regex = self.normalize_regex.clone();
+        filter.drop = self.drop;
+        filter.drop_regex = self.drop_regex.clone();
+        filter.parse_dates = self.parse_dates;
+        filter.date_regex = self.date_regex.clone();
+        filter.date_format = self.date_format.clone();
+        filter.date_time_zone = self.date_time_zone.clone();
+        filter.column_types = self.column_types.clone();
+        filter.column_renames = self.column_renames.clone();
+        filter.flatten_structs = self.flatten_structs;
+        filter.categorical = self.categorical;
+        filter.categorical_ratio = self.categorical_ratio;
+        filter.query = self.query.clone();
+        filter.query_steps = self.query_steps.clone();
+
+        filter.apply_sql = true;
+        filter.read_data_from_file |= read_options_changed;
+    }
+
+    /// Builds the `DataFormat` of this view.
+    /// Alignments of unknown data type names are ignored.
+    pub fn to_format(&self) -> DataFormat {
+        let mut format = DataFormat {
+            auto_col_width: self.auto_col_width,
+            decimal: self.decimal,
+            auto_decimal: self.auto_decimal,
+            decimal_scale: self.decimal_scale,
+            header_padding: self.header_padding,
+            use_enhanced_header: self.use_enhanced_header,
+            column_alignments: self
+                .column_alignments
+                .iter()
+                .map(|(name, align)| (name.clone(), *align))
+                .collect(),
+            locked_widths: self
+                .locked_widths
+                .iter()
+                .map(|(name, width)| (name.clone(), *width))
+                .collect(),
+            list_displays: self
+                .list_displays
+                .iter()
+                .map(|(name, display)| (name.clone(), *display))
+                .collect(),
+            cell_renderers: self
+                .cell_renderers
+                .iter()
+                .map(|(name, id)| (name.clone(), id.clone()))
+                .collect(),
+            column_units: self
+                .column_units
+                .iter()
+                .map(|(name, unit)| (name.clone(), unit.clone()))
+                .collect(),
+            virtual_columns: self.virtual_columns.clone(),
+            hidden_columns: self.hidden_columns.iter().cloned().collect(),
+            frozen_columns: self.frozen_columns.clone(),
+            column_order: self.column_order.clone(),
+            show_row_numbers: self.show_row_numbers,
+            expand_hovered_row: self.expand_hovered_row,
+            show_filter_row: self.show_filter_row,
+            show_null_badges: self.show_null_badges,
+            paged: self.paged,
+            rows_per_page: self.rows_per_page,
+            ..Default::default()
+        };
+
+        for (dtype, align) in format.alignments.iter_mut() {
+            if let Some(saved) = self.alignments.get(&dtype.to_string()) {
+                *align = *saved;
+            }
+        }
+
+        format
+    }
+
+    /// Serializes the view as pretty-printed JSON.
+    pub fn to_json(&self) -> PolarsViewResult<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Deserializes a view from JSON.
+    pub fn from_json(json: &str) -> PolarsViewResult<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Writes the view as JSON to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> PolarsViewResult<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Reads a view from the JSON file at `path`.
+    pub fn load(path: impl AsRef<Path>) -> PolarsViewResult<Self> {
+        ViewState::from_json(&std::fs::read_to_string(path)?)
+    }
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_view_state`
+#[cfg(test)]
+mod tests_view_state {
+    use super::*;
+    use polars::prelude::DataType;
+
+    #[test]
+    fn test_json_round_trip() -> PolarsViewResult<()> {
+        let filter = DataFilter {
+            query: "SELECT * FROM AllData WHERE a > 1".to_string(),
+            drop: true,
+            ..Default::default()
+        };
+        let mut format = DataFormat {
+            decimal: 4,
+            ..Default::default()
+        };
+        format.alignments.insert(DataType::Float64, Align::LEFT);
+        format
+            .column_alignments
+            .insert("a".to_string(), Align::Center);
+        format.locked_widths.insert("a".to_string(), 80.0);
+        let sort = vec![SortBy {
+            column_name: "a".to_string(),
+            ascending: false,
+            nulls_last: true,
+        }];
+
+        let column_filters = ColumnFilters::from([("a".to_string(), ">1".to_string())]);
+
+        let view = ViewState::capture(&filter, &format, &column_filters, &sort, None);
+        let json = view.to_json()?;
+        println!("{json}");
+
+        let restored = ViewState::from_json(&json)?;
+        assert_eq!(restored, view);
+        assert_eq!(restored.to_format(), format);
+        assert_eq!(restored.column_filters, column_filters);
+
+        let mut new_filter = DataFilter::default();
+        restored.apply_to_filter(&mut new_filter);
+        assert_eq!(new_filter.query, filter.query);
+        assert!(new_filter.drop);
+        assert!(new_filter.apply_sql);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() -> PolarsViewResult<()> {
+        let view = ViewState::from_json(r#"{ "decimal": 3 }"#)?;
+
+        assert_eq!(view.decimal, 3);
+        assert_eq!(view.table_name, "AllData");
+        assert!(view.sort.is_empty());
+        assert!(view.column_filters.is_empty());
+        Ok(())
+    }
+}
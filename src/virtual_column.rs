@@ -0,0 +1,142 @@
+//! Virtual columns: named Polars SQL expressions (e.g., `price * quantity`) computed
+//! for the displayed rows only. They are added to `DataContainer::df` (so they can be
+//! sorted, filtered and exported with the view) but never to `df_unfiltered` or
+//! `df_original`: the SQL query and the other load options see only the data columns.
+//!
+//! Defined in the "Columns" side panel section (see `DataFormat::virtual_columns`).
+
+use crate::{PolarsViewError, PolarsViewResult};
+
+use polars::{prelude::*, sql::sql_expr};
+use serde::{Deserialize, Serialize};
+
+/// Number of rows on which a new virtual column is evaluated before it is added.
+const VALIDATION_ROWS: usize = 10;
+
+/// A named expression evaluated for the displayed rows (see the module documentation).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VirtualColumn {
+    /// Name of the computed column.
+    pub name: String,
+    /// Polars SQL expression over the data columns (and the previous virtual columns).
+    pub expression: String,
+}
+
+impl VirtualColumn {
+    /// Checks that the column can be added to `df` after the `existing` virtual columns:
+    /// its name is new and its expression evaluates on the first rows of `df`.
+    ///
+    /// Returns the data type of the computed column.
+    pub fn validate(
+        &self,
+        df: &DataFrame,
+        existing: &[VirtualColumn],
+    ) -> PolarsViewResult<DataType> {
+        let name = self.name.trim();
+        if name.is_empty() || self.expression.trim().is_empty() {
+            return Err(PolarsViewError::Other(
+                "A virtual column needs a name and an expression.".to_string(),
+            ));
+        }
+        if df.schema().contains(name) || existing.iter().any(|column| column.name == name) {
+            return Err(PolarsViewError::Other(format!(
+                "A column named '{name}' already exists."
+            )));
+        }
+
+        let mut columns = existing.to_vec();
+        columns.push(self.clone());
+        let sample = add_virtual_columns(&df.head(Some(VALIDATION_ROWS)), &columns)?;
+        Ok(sample.column(name)?.dtype().clone())
+    }
+}
+
+/// Appends the `columns` to `df`, in order (so an expression can use the previous ones).
+///
+/// ### Errors
+/// * `PolarsViewError::Other`: If a name is already a column of `df`.
+/// * `PolarsViewError::Polars`: If an expression cannot be parsed or evaluated.
+pub fn add_virtual_columns(
+    df: &DataFrame,
+    columns: &[VirtualColumn],
+) -> PolarsViewResult<DataFrame> {
+    if columns.is_empty() {
+        return Ok(df.clone());
+    }
+
+    let mut lazy = df.clone().lazy();
+    for column in columns {
+        let name = column.name.trim();
+        if df.schema().contains(name) {
+            return Err(PolarsViewError::Other(format!(
+                "Virtual column '{name}' conflicts with a data column."
+            )));
+        }
+        lazy = lazy.with_column(sql_expr(column.expression.trim())?.alias(name));
+    }
+
+    Ok(lazy.collect()?)
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_virtual_column`
+#[cfg(test)]
+mod tests_virtual_column {
+    use super::*;
+
+    fn virtual_column(name: &str, expression: &str) -> VirtualColumn {
+        VirtualColumn {
+            name: name.to_string(),
+            expression: expression.to_string(),
+        }
+    }
+
+    #[test]
+    fn computed_columns_are_appended() -> PolarsViewResult<()> {
+        let df = df!(
+            "price" => &[1.5, 2.0],
+            "quantity" => &[2, 3]
+        )?;
+        let columns = [
+            virtual_column("total", "price * quantity"),
+            virtual_column("double", "total * 2"),
+        ];
+
+        let result = add_virtual_columns(&df, &columns)?;
+        dbg!(&result);
+
+        assert_eq!(result.width(), 4);
+        let totals: Vec<Option<f64>> = result.column("double")?.f64()?.into_iter().collect();
+        assert_eq!(totals, [Some(6.0), Some(12.0)]);
+        // The source data is unchanged.
+        assert_eq!(df.width(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_columns_are_rejected() -> PolarsViewResult<()> {
+        let df = df!("price" => &[1.5, 2.0])?;
+
+        assert!(
+            virtual_column("price", "price * 2")
+                .validate(&df, &[])
+                .is_err()
+        );
+        assert!(
+            virtual_column("x", "missing + 1")
+                .validate(&df, &[])
+                .is_err()
+        );
+        assert!(virtual_column("", "price").validate(&df, &[]).is_err());
+        assert_eq!(
+            virtual_column("half", "price / 2").validate(&df, &[])?,
+            DataType::Float64
+        );
+        Ok(())
+    }
+}
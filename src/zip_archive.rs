@@ -0,0 +1,119 @@
+//! Zip archives: the supported files of a `.zip` (CSV, JSON, NDJSON, Parquet, Excel,
+//! SQLite, compressed or not) are listed in the Query panel ("Zip Entry") and the selected
+//! one is read like the file itself, without unpacking the archive first.
+//!
+//! The entry is extracted to the private temporary folder (see `extract_zip_entry`), since
+//! the Polars, Excel and SQLite readers need a seekable file.
+
+use crate::{FileExtension, PolarsViewError, PolarsViewResult, derived_entry_file};
+
+use std::{
+    fs::File,
+    io::{self, BufReader},
+    path::{Path, PathBuf},
+};
+use zip::ZipArchive;
+
+/// Returns `true` if the archive entry `name` can be read (not a folder, not a nested zip).
+fn is_supported_entry(name: &str) -> bool {
+    !name.ends_with('/')
+        && !matches!(
+            FileExtension::from_path(Path::new(name)),
+            FileExtension::Zip | FileExtension::Unknown(_) | FileExtension::Missing
+        )
+}
+
+/// Names of the readable entries of the zip archive at `path`, in archive order
+/// (macOS `__MACOSX/` metadata excluded).
+pub fn list_zip_entries(path: &Path) -> PolarsViewResult<Vec<String>> {
+    let archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+    Ok(archive
+        .file_names()
+        .filter(|name| is_supported_entry(name) && !name.starts_with("__MACOSX/"))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Extracts the entry `name` of the zip archive at `path` to the private temporary folder
+/// and returns the extracted file, reused while the archive is unchanged (see
+/// `derived_entry_file`: the file is keyed by the archive path, its size and modification
+/// time, and the entry name). The file keeps the entry's file name, so its extension
+/// selects the reader.
+pub fn extract_zip_entry(path: &Path, name: &str) -> PolarsViewResult<PathBuf> {
+    if Path::new(name).file_name().is_none() {
+        return Err(PolarsViewError::FileType(format!(
+            "Invalid zip entry: `{name}`"
+        )));
+    }
+
+    derived_entry_file("zip", path, name, |writer| {
+        let mut archive = ZipArchive::new(BufReader::new(File::open(path)?))?;
+        let mut entry = archive.by_name(name)?;
+        io::copy(&mut entry, writer)?;
+
+        tracing::info!("Zip entry `{name}` of '{}' extracted", path.display());
+        Ok(())
+    })
+}
+
+//----------------------------------------------------------------------------//
+//                                   Tests                                    //
+//----------------------------------------------------------------------------//
+
+/// Run tests with:
+/// `cargo test -- --show-output tests_zip_archive`
+#[cfg(test)]
+mod tests_zip_archive {
+    use super::*;
+    use std::io::Write;
+    use zip::{ZipWriter, write::SimpleFileOptions};
+
+    #[test]
+    fn zip_entries_are_listed_and_extracted() -> PolarsViewResult<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("exports.zip");
+
+        let mut zip = ZipWriter::new(File::create(&path)?);
+        let options = SimpleFileOptions::default();
+        zip.add_directory("2024/", options)?;
+        zip.start_file("2024/sales.csv", options)?;
+        zip.write_all(b"id;total\n1;10\n")?;
+        zip.start_file("notes.txt", options)?;
+        zip.write_all(b"not a table")?;
+        zip.start_file("__MACOSX/2024/._sales.csv", options)?;
+        zip.write_all(b"metadata")?;
+        zip.start_file("events.ndjson", options)?;
+        zip.write_all(b"{\"id\": 1}\n")?;
+        zip.finish()?;
+
+        let entries = list_zip_entries(&path)?;
+        assert_eq!(entries, ["2024/sales.csv", "events.ndjson"]);
+
+        let extracted = extract_zip_entry(&path, "2024/sales.csv")?;
+        assert!(extracted.to_string_lossy().ends_with("sales.csv"));
+        assert_eq!(std::fs::read_to_string(&extracted)?, "id;total\n1;10\n");
+
+        assert!(extract_zip_entry(&path, "missing.csv").is_err());
+
+        // An archive with the same name in another folder: its own copy of the entry.
+        let other_dir = dir.path().join("other");
+        std::fs::create_dir_all(&other_dir)?;
+        let other = other_dir.join("exports.zip");
+        let mut zip = ZipWriter::new(File::create(&other)?);
+        zip.start_file("2024/sales.csv", options)?;
+        zip.write_all(b"id;total\n2;20\n")?;
+        zip.finish()?;
+
+        let other_extracted = extract_zip_entry(&other, "2024/sales.csv")?;
+        assert_ne!(other_extracted, extracted);
+        assert_eq!(
+            std::fs::read_to_string(&other_extracted)?,
+            "id;total\n2;20\n"
+        );
+
+        for file in [extracted, other_extracted] {
+            std::fs::remove_file(file)?;
+        }
+        Ok(())
+    }
+}